@@ -0,0 +1,94 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use zscore_lib::backtest::models::Backtest;
+use zscore_lib::prelude::default_backtest_criteria;
+use zscore_lib::stats::metrics::{rolling_cointegration, rolling_zscore};
+
+/*
+  Backtest Benchmarks
+  Hot paths of the backtest engine at representative series lengths (see the perf budget in the README)
+  Run with: cargo bench --bench backtest
+*/
+
+/// Series lengths benchmarked (roughly 3 weeks, 3 months and 1 year of hourly bars)
+const SIZES: [usize; 3] = [500, 2_000, 8_760];
+
+/// Synthetic Pair
+/// Deterministic cointegrated pair with a mean reverting spread
+fn synthetic_pair(len: usize) -> (Vec<f64>, Vec<f64>) {
+  let series_1: Vec<f64> = (0..len).map(|i| 100.0 + (i as f64 / 10.0).sin() * 5.0 + i as f64 * 0.01).collect();
+  let series_0: Vec<f64> = series_1.iter().enumerate().map(|(i, p)| p * 2.0 + (i as f64 / 3.0).cos() * 2.0).collect();
+  (series_0, series_1)
+}
+
+/// Synthetic Zscore
+/// Oscillating indicator crossing the default entry and exit thresholds
+fn synthetic_zscore(len: usize) -> Vec<f64> {
+  (0..len).map(|i| (i as f64 / 7.0).sin() * 2.5).collect()
+}
+
+fn bench_create_signals(c: &mut Criterion) {
+  let mut group = c.benchmark_group("create_signals");
+  for size in SIZES {
+    let (series_0, series_1) = synthetic_pair(size);
+    let backtest: Backtest = Backtest::new(&series_0, &series_1, default_backtest_criteria(synthetic_zscore(size))).unwrap();
+    group.bench_with_input(BenchmarkId::from_parameter(size), &backtest, |b, bt| {
+      b.iter(|| black_box(bt.create_signals().unwrap()))
+    });
+  }
+  group.finish();
+}
+
+fn bench_signal_paths(c: &mut Criterion) {
+  let mut group = c.benchmark_group("signal_paths");
+  for size in SIZES {
+    let (series_0, series_1) = synthetic_pair(size);
+    let backtest: Backtest = Backtest::new(&series_0, &series_1, default_backtest_criteria(synthetic_zscore(size))).unwrap();
+    group.bench_with_input(BenchmarkId::new("loop", size), &backtest, |b, bt| {
+      b.iter(|| black_box(bt.create_signals_loop().unwrap()))
+    });
+    group.bench_with_input(BenchmarkId::new("vectorized", size), &backtest, |b, bt| {
+      b.iter(|| black_box(bt.create_signals_vectorized().unwrap()))
+    });
+  }
+  group.finish();
+}
+
+fn bench_strategy_returns(c: &mut Criterion) {
+  let mut group = c.benchmark_group("strategy_returns");
+  for size in SIZES {
+    let (series_0, series_1) = synthetic_pair(size);
+    let backtest: Backtest = Backtest::new(&series_0, &series_1, default_backtest_criteria(synthetic_zscore(size))).unwrap();
+    let (signals, trading_costs, _, _) = backtest.create_signals().unwrap();
+    group.bench_with_input(BenchmarkId::from_parameter(size), &backtest, |b, bt| {
+      b.iter(|| black_box(bt.strategy_returns(signals.clone(), trading_costs.clone())))
+    });
+  }
+  group.finish();
+}
+
+fn bench_rolling_zscore(c: &mut Criterion) {
+  let mut group = c.benchmark_group("rolling_zscore");
+  for size in SIZES {
+    let (series_0, _) = synthetic_pair(size);
+    group.bench_with_input(BenchmarkId::from_parameter(size), &series_0, |b, series| {
+      b.iter(|| black_box(rolling_zscore(series, 35).unwrap()))
+    });
+  }
+  group.finish();
+}
+
+fn bench_rolling_cointegration(c: &mut Criterion) {
+  let mut group = c.benchmark_group("rolling_cointegration");
+  group.sample_size(10);
+  for size in SIZES {
+    let (series_0, series_1) = synthetic_pair(size);
+    group.bench_with_input(BenchmarkId::from_parameter(size), &(series_0, series_1), |b, (s0, s1)| {
+      b.iter(|| black_box(rolling_cointegration(s0, s1, 90).unwrap()))
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench_create_signals, bench_signal_paths, bench_strategy_returns, bench_rolling_zscore, bench_rolling_cointegration);
+criterion_main!(benches);
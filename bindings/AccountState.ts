@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AccountBalance } from "./AccountBalance";
+import type { AccountPosition } from "./AccountPosition";
+
+export interface AccountState { positions: Array<AccountPosition>, balances: Array<AccountBalance>, }
\ No newline at end of file
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AlertKind } from "./AlertKind";
+import type { SignalThresholds } from "./SignalThresholds";
+
+export interface Alert { kind: AlertKind, asset_0: string, asset_1: string, zscore: number, thresholds: SignalThresholds, link: string | null, time: bigint, }
\ No newline at end of file
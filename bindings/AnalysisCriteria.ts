@@ -1,6 +1,8 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AssetType } from "./AssetType";
 import type { BacktestCriteria } from "./BacktestCriteria";
 import type { DataCriteria } from "./DataCriteria";
+import type { MissingDataPolicy } from "./MissingDataPolicy";
 import type { StatsCriteria } from "./StatsCriteria";
 
-export interface AnalysisCriteria { data_criteria: DataCriteria, stats_criteria: StatsCriteria | null, backtest_criteria: BacktestCriteria | null, }
\ No newline at end of file
+export interface AnalysisCriteria { data_criteria: DataCriteria, stats_criteria: StatsCriteria | null, backtest_criteria: BacktestCriteria | null, missing_data_policy: MissingDataPolicy, asset_type: AssetType | null, }
\ No newline at end of file
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ArbStats } from "./ArbStats";
+import type { BacktestMetrics } from "./BacktestMetrics";
+import type { PairPrices } from "./PairPrices";
+
+export interface ArbAnalysis { prices: PairPrices, arb_stats: ArbStats, bt_metrics: BacktestMetrics, }
\ No newline at end of file
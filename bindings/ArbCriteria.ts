@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BacktestCriteria } from "./BacktestCriteria";
+import type { Exchange } from "./Exchange";
+import type { IntervalPeriod } from "./IntervalPeriod";
+import type { MissingDataPolicy } from "./MissingDataPolicy";
+
+export interface ArbCriteria { symbol: string, exchange_0: Exchange, exchange_1: Exchange, interval_period: IntervalPeriod, zscore_window: number, backtest_criteria: BacktestCriteria | null, missing_data_policy: MissingDataPolicy, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface ArbStats { premium_bps: Array<number>, zscore: Array<number>, mean_premium_bps: number, std_premium_bps: number, latest_premium_bps: number, max_abs_premium_bps: number, }
\ No newline at end of file
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AssetType } from "./AssetType";
+import type { MarketCalendar } from "./MarketCalendar";
+
+export interface AssetPreset { asset_type: AssetType, calendar: MarketCalendar, trading_days: number, cost_per_leg: number, long_thresh: number, long_close_thresh: number, short_thresh: number, short_close_thresh: number, }
\ No newline at end of file
@@ -1,6 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EntryCondition } from "./EntryCondition";
+import type { Exchange } from "./Exchange";
+import type { FeeSide } from "./FeeSide";
 import type { LongSeries } from "./LongSeries";
 import type { Relation } from "./Relation";
+import type { SignalTiming } from "./SignalTiming";
 import type { TriggerIndicator } from "./TriggerIndicator";
 
-export interface BacktestCriteria { indicator_values: Array<number>, trigger_indicator: TriggerIndicator, relation: Relation, cost_per_leg: number | null, rets_weighting_s0_perc: number, long_series: LongSeries, stop_loss: number, long_thresh: number, long_close_thresh: number, short_thresh: number, short_close_thresh: number, }
\ No newline at end of file
+export interface BacktestCriteria { indicator_values: Array<number>, trigger_indicator: TriggerIndicator, relation: Relation, cost_per_leg: number | null, rets_weighting_s0_perc: number, long_series: LongSeries, stop_loss: number, long_thresh: number, long_close_thresh: number, short_thresh: number, short_close_thresh: number, long_entry: EntryCondition | null, short_entry: EntryCondition | null, index_beta: number | null, exchange: Exchange | null, fee_side: FeeSide, warmup_bars: number | null, signal_timing: SignalTiming, }
\ No newline at end of file
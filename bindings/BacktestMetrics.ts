@@ -1,4 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BacktestOutcome } from "./BacktestOutcome";
+import type { LegPnl } from "./LegPnl";
+import type { TradeStats } from "./TradeStats";
 import type { WinRate } from "./WinRate";
 
-export interface BacktestMetrics { arr: number, drawdowns: Array<number>, equity_curve: Array<number>, max_drawdown: number, mean_return: number, sharpe_ratio: number, sortino_ratio: number, total_return: number, win_rate_stats: WinRate, }
\ No newline at end of file
+export interface BacktestMetrics { arr: number, drawdowns: Array<number>, equity_curve: Array<number>, max_drawdown: number, mean_return: number, mean_return_per_bar: number, mean_return_per_trade: number, sharpe_ratio: number, sortino_ratio: number, total_return: number, win_rate_stats: WinRate, outcome: BacktestOutcome, leg_pnl: LegPnl, trade_stats: TradeStats, evaluation_start: number, }
\ No newline at end of file
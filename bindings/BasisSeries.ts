@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BasisType } from "./BasisType";
+
+export interface BasisSeries { labels: Array<bigint>, spot: Array<number>, perp: Array<number>, basis: Array<number>, basis_type: BasisType, }
\ No newline at end of file
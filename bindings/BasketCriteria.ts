@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BasketWeighting } from "./BasketWeighting";
+
+export interface BasketCriteria { symbols: Array<string>, weighting: BasketWeighting, }
\ No newline at end of file
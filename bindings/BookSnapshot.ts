@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OrderBook } from "./OrderBook";
+
+export interface BookSnapshot { time_ms: bigint, book: OrderBook, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface CapacityConfig { max_impact_bps: number, max_volume_share: number, }
\ No newline at end of file
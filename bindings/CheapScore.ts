@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface CheapScore { asset_0: string, asset_1: string, corr: number, ssd: number, bars: number, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CointMetric } from "./CointMetric";
+
+export interface CointHeatmap { symbols: Array<string>, metric: CointMetric, values: Array<number>, }
\ No newline at end of file
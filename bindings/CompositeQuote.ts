@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Exchange } from "./Exchange";
+import type { VenueQuote } from "./VenueQuote";
+
+export interface CompositeQuote { median: number, mean: number, weighted: number | null, venues: Array<VenueQuote>, failed: Array<[Exchange, string]>, }
\ No newline at end of file
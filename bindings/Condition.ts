@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Comparison } from "./Comparison";
+
+export interface Condition { name: string, values: Array<number>, comparison: Comparison, threshold: number, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReportTrade } from "./ReportTrade";
+
+export interface DailyReport { day_start: bigint, signals: number, trades: Array<ReportTrade>, realized_pnl: number, wins: number, losses: number, degraded: Array<string>, open_pairs: Array<string>, }
\ No newline at end of file
@@ -2,4 +2,4 @@
 import type { Exchange } from "./Exchange";
 import type { IntervalPeriod } from "./IntervalPeriod";
 
-export interface DataCriteria { exchange: Exchange, asset_0: string, asset_1: string, interval_period: IntervalPeriod, }
\ No newline at end of file
+export interface DataCriteria { exchange: Exchange, asset_0: string, asset_1: string, interval_period: IntervalPeriod, fallback_exchanges: Array<Exchange>, }
\ No newline at end of file
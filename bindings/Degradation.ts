@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DataCriteria } from "./DataCriteria";
+
+export interface Degradation { data_criteria: DataCriteria, previous_score: number | null, score: number, min_score: number, }
\ No newline at end of file
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ExpectedPosition } from "./ExpectedPosition";
+import type { LongSeries } from "./LongSeries";
+import type { PaperFill } from "./PaperFill";
+
+export interface EnginePosition { pair_id: string, long_series: LongSeries, entry_label: bigint, legs: Array<ExpectedPosition>, entry_fills: Array<PaperFill>, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Condition } from "./Condition";
+
+export type EntryCondition = { "Condition": Condition } | { "All": Array<EntryCondition> } | { "Any": Array<EntryCondition> } | { "Not": EntryCondition };
\ No newline at end of file
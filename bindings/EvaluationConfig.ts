@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReturnBasis } from "./ReturnBasis";
+
+export interface EvaluationConfig { risk_free_rate_annual: number, use_risk_free: boolean, benchmark_log_returns: Array<number> | null, return_basis: ReturnBasis, periods_per_year: number, }
\ No newline at end of file
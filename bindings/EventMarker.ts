@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EventKind } from "./EventKind";
+
+export interface EventMarker { index: number, label: bigint, kind: EventKind, name: string, }
\ No newline at end of file
@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type Exchange = "Binance" | "BinanceUs" | "ByBit" | "Coinbase" | "Dydx" | "Twelve";
\ No newline at end of file
+export type Exchange = "Alpaca" | "AlphaVantage" | "Binance" | "BinanceCoinM" | "BinanceSpot" | "BinanceUs" | "Bitfinex" | "ByBit" | "ByBitInverse" | "Coinbase" | "Dydx" | "GateIo" | "Hyperliquid" | "Kraken" | "KuCoin" | "Okx" | "Tiingo" | "Twelve" | "Yahoo";
\ No newline at end of file
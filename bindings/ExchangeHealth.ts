@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Exchange } from "./Exchange";
+import type { HealthStatus } from "./HealthStatus";
+
+export interface ExchangeHealth { exchange: Exchange, status: HealthStatus, latency_ms: bigint | null, message: string | null, }
\ No newline at end of file
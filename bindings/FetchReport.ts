@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LabelGap } from "./LabelGap";
+
+export interface FetchReport { requested_bars: number, received_bars: number, gaps: Array<LabelGap>, failed_calls: number, }
\ No newline at end of file
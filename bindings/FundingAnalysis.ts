@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BacktestMetrics } from "./BacktestMetrics";
+import type { PairPrices } from "./PairPrices";
+
+export interface FundingAnalysis { prices: PairPrices, funding_rates: Array<number>, funding_zscore: Array<number>, funding_per_bar: Array<number>, bt_metrics: BacktestMetrics, }
\ No newline at end of file
@@ -1,3 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FetchReport } from "./FetchReport";
+import type { LabelConvention } from "./LabelConvention";
 
-export interface HistoricalPrices { prices: Array<number>, labels: Array<bigint>, }
\ No newline at end of file
+export interface HistoricalPrices { prices: Array<number>, labels: Array<bigint>, label_convention: LabelConvention, fetch_report: FetchReport | null, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface IndicatorFilter { max_realized_vol: number | null, max_atr_perc: number | null, max_abs_skew: number | null, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GapKind } from "./GapKind";
+
+export interface LabelGap { from_label: bigint, to_label: bigint, missing_bars: number, kind: GapKind, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type LatencyModel = { "Fixed": bigint } | { "Uniform": { min_ms: bigint, max_ms: bigint, } } | { "Normal": { mean_ms: number, std_ms: number, } };
\ No newline at end of file
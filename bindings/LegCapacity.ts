@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface LegCapacity { symbol: string, volume_24h: number, depth_notional: number, capacity: number, }
\ No newline at end of file
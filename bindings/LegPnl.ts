@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface LegPnl { leg_0_log_returns: Array<number>, leg_1_log_returns: Array<number>, leg_0_total: number, leg_1_total: number, costs_total: number, leg_0_share: number, }
\ No newline at end of file
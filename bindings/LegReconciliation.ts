@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReconcileStatus } from "./ReconcileStatus";
+
+export interface LegReconciliation { symbol: string, expected_qty: number, actual_qty: number, diff: number, status: ReconcileStatus, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface LiveSpreadTick { price_0: number, price_1: number, spread: number, zscore: number, }
\ No newline at end of file
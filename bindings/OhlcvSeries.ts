@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LabelConvention } from "./LabelConvention";
+
+export interface OhlcvSeries { labels: Array<bigint>, open: Array<number>, high: Array<number>, low: Array<number>, close: Array<number>, volume: Array<number>, label_convention: LabelConvention, }
\ No newline at end of file
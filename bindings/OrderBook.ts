@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BookLevel } from "./BookLevel";
+
+export interface OrderBook { bids: Array<BookLevel>, asks: Array<BookLevel>, }
\ No newline at end of file
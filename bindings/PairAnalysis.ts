@@ -1,6 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { BacktestMetrics } from "./BacktestMetrics";
+import type { EventMarker } from "./EventMarker";
 import type { PairPrices } from "./PairPrices";
 import type { Statistics } from "./Statistics";
 
-export interface PairAnalysis { prices: PairPrices, stats: Statistics, bt_metrics: BacktestMetrics, }
\ No newline at end of file
+export interface PairAnalysis { prices: PairPrices, stats: Statistics, bt_metrics: BacktestMetrics, event_markers: Array<EventMarker>, }
\ No newline at end of file
@@ -1,3 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Exchange } from "./Exchange";
+import type { FetchReport } from "./FetchReport";
+import type { LabelConvention } from "./LabelConvention";
+import type { SeriesEvent } from "./SeriesEvent";
 
-export interface PairPrices { series_0: Array<number>, series_1: Array<number>, labels: Array<bigint>, }
\ No newline at end of file
+export interface PairPrices { series_0: Array<number>, series_1: Array<number>, labels: Array<bigint>, label_convention: LabelConvention, fetch_report: FetchReport | null, events: Array<SeriesEvent>, source: Exchange | null, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubScores } from "./SubScores";
+
+export interface PairScore { score: number, hurst: number, sub_scores: SubScores, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface PaperFill { filled_qty: number, avg_price: number, resting_qty: number, queue_ahead: number, latency_ms: bigint, arrival_ms: bigint, slippage_bps: number, is_partial: boolean, }
\ No newline at end of file
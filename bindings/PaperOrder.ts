@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OrderSide } from "./OrderSide";
+import type { OrderType } from "./OrderType";
+
+export interface PaperOrder { symbol: string, side: OrderSide, order_type: OrderType, qty: number, }
\ No newline at end of file
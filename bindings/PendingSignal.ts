@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SignalAction } from "./SignalAction";
+
+export interface PendingSignal { pair_id: string, action: SignalAction, zscore: number, bar_label: bigint, }
\ No newline at end of file
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CheapScore } from "./CheapScore";
+import type { ScreenerRow } from "./ScreenerRow";
+
+export interface PlannedScreening { screen_id: string, rows: Array<ScreenerRow>, survivors: Array<CheapScore>, pairs_total: number, pairs_passed: number, }
\ No newline at end of file
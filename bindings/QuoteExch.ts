@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export interface QuoteExch { binance: number, binance_us: number, bybit: number, coinbase: number, dydx: number, twelve: number, }
\ No newline at end of file
+export interface QuoteExch { alpaca: number, alpha_vantage: number, binance: number, binance_coinm: number, binance_spot: number, binance_us: number, bitfinex: number, bybit: number, bybit_inverse: number, coinbase: number, dydx: number, gateio: number, hyperliquid: number, kraken: number, kucoin: number, okx: number, tiingo: number, twelve: number, yahoo: number, }
\ No newline at end of file
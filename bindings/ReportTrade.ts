@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SignalAction } from "./SignalAction";
+
+export interface ReportTrade { pair_id: string, action: SignalAction, pnl: number | null, time: bigint, }
\ No newline at end of file
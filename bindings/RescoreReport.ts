@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DataCriteria } from "./DataCriteria";
+import type { Degradation } from "./Degradation";
+
+export interface RescoreReport { degradations: Array<Degradation>, failed: Array<[DataCriteria, string]>, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface RiskLimits { max_daily_loss: number | null, max_open_pairs: number | null, max_notional: number | null, flatten_on_breach: boolean, }
\ No newline at end of file
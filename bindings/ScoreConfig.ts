@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScoreWeights } from "./ScoreWeights";
+
+export interface ScoreConfig { weights: ScoreWeights, half_life_min: number, half_life_max: number, hurst_max_lag: number, sharpe_cap: number, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface ScoreWeights { coint: number, half_life: number, hurst: number, corr_stability: number, sharpe: number, }
\ No newline at end of file
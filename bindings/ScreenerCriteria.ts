@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CapacityConfig } from "./CapacityConfig";
+import type { Exchange } from "./Exchange";
+import type { IntervalPeriod } from "./IntervalPeriod";
+import type { ScoreConfig } from "./ScoreConfig";
+import type { Sector } from "./Sector";
+import type { StatsCriteria } from "./StatsCriteria";
+
+export interface ScreenerCriteria { exchange: Exchange, interval_period: IntervalPeriod, symbols: Array<string>, stats_criteria: StatsCriteria, score_config: ScoreConfig, capacity: CapacityConfig | null, sectors: Array<Sector>, same_sector: boolean, }
\ No newline at end of file
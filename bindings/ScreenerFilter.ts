@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface ScreenerFilter { min_score: number | null, min_corr: number | null, max_coint_p_value: number | null, max_half_life: number | null, min_zscore_abs: number | null, min_capacity: number | null, symbol: string | null, }
\ No newline at end of file
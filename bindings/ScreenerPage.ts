@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScreenerRow } from "./ScreenerRow";
+
+export interface ScreenerPage { screen_id: string, rows: Array<ScreenerRow>, page: number, page_size: number, total_rows: number, total_pages: number, }
\ No newline at end of file
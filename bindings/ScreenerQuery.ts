@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScreenerFilter } from "./ScreenerFilter";
+import type { ScreenerSortKey } from "./ScreenerSortKey";
+
+export interface ScreenerQuery { page: number, page_size: number, sort_by: ScreenerSortKey, ascending: boolean, filter: ScreenerFilter, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface ScreenerRow { asset_0: string, asset_1: string, score: number, corr: number, coint_p_value: number, half_life: number, hedge_ratio: number, hurst: number, zscore_latest: number, sharpe_ratio: number, bars: number, capacity: number | null, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScreenerRow } from "./ScreenerRow";
+
+export interface ScreeningCheckpoint { job_id: string, screen_id: string, rows: Array<ScreenerRow>, skipped: Array<[string, string]>, updated_at: bigint, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CheapMetric } from "./CheapMetric";
+
+export interface ScreeningPlan { rank_by: CheapMetric, top_k: number, min_corr: number | null, max_ssd: number | null, prefilter_period: number | null, }
\ No newline at end of file
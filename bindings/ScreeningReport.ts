@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScreenerRow } from "./ScreenerRow";
+
+export interface ScreeningReport { job_id: string, screen_id: string, rows: Array<ScreenerRow>, skipped: Array<[string, string]>, pending: Array<[string, string]>, resumed_rows: number, is_complete: boolean, }
\ No newline at end of file
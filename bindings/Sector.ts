@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Sector = "Layer1" | "Layer2" | "DeFi" | "Meme" | "ExchangeToken" | "Oracle" | "Gaming" | "AI" | "Privacy" | "Payments" | "Storage" | "Stablecoin" | "Unknown";
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EventKind } from "./EventKind";
+
+export interface SeriesEvent { label: bigint, kind: EventKind, name: string, exclude_before_secs: bigint, exclude_after_secs: bigint, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LongSeries } from "./LongSeries";
+
+export type SignalAction = { "Open": LongSeries } | "Close";
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SignalThresholds } from "./SignalThresholds";
+
+export interface SignalSource { name: string, values: Array<number>, thresholds: SignalThresholds, }
\ No newline at end of file
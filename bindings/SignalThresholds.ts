@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface SignalThresholds { long: number, long_close: number, short: number, short_close: number, }
\ No newline at end of file
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PreprocessConfig } from "./PreprocessConfig";
 import type { SpreadType } from "./SpreadType";
 
-export interface StatsCriteria { spread_type: SpreadType, zscore_window: number, roll_window: number, }
\ No newline at end of file
+export interface StatsCriteria { spread_type: SpreadType, zscore_window: number, roll_window: number, preprocess: PreprocessConfig | null, }
\ No newline at end of file
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BacktestMetrics } from "./BacktestMetrics";
+import type { IntervalPeriod } from "./IntervalPeriod";
+import type { PairPrices } from "./PairPrices";
+import type { Statistics } from "./Statistics";
+
+export interface TimeframeAnalysis { interval_period: IntervalPeriod, prices: PairPrices, stats: Statistics, bt_metrics: BacktestMetrics, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface Trade { id: bigint, time_ms: bigint, price: number, qty: number, is_buyer_maker: boolean, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface TradeStats { trade_returns: Array<number>, max_consecutive_losses: number, max_consecutive_wins: number, loss_streak_counts: Array<number>, pnl_autocorrelation: number, }
\ No newline at end of file
@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type TriggerIndicator = "Zscore" | "Spread";
\ No newline at end of file
+export type TriggerIndicator = "Zscore" | "Spread" | "TimeframeGatedZscore" | { "Custom": string };
\ No newline at end of file
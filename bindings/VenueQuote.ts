@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Exchange } from "./Exchange";
+
+export interface VenueQuote { exchange: Exchange, symbol: string, price: number, deviation_bps: number, }
\ No newline at end of file
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScoreConfig } from "./ScoreConfig";
+import type { WatchlistEntry } from "./WatchlistEntry";
+
+export interface Watchlist { entries: Array<WatchlistEntry>, rescore_interval_secs: bigint, score_config: ScoreConfig, }
\ No newline at end of file
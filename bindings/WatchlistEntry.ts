@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DataCriteria } from "./DataCriteria";
+import type { PairPrices } from "./PairPrices";
+import type { PairScore } from "./PairScore";
+import type { StatsCriteria } from "./StatsCriteria";
+
+export interface WatchlistEntry { data_criteria: DataCriteria, stats_criteria: StatsCriteria, min_score: number, prices: PairPrices | null, last_score: PairScore | null, last_scored_at: bigint | null, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WebhookSide } from "./WebhookSide";
+
+export interface WebhookSignal { symbol: string, side: WebhookSide, qty: number, comment: string, }
\ No newline at end of file
@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export interface WinRate { win_rate: number, opened: number, closed: number, closed_profit: number, }
\ No newline at end of file
+export interface WinRate { win_rate: number, opened: number, closed: number, closed_profit: number, has_trades: boolean, }
\ No newline at end of file
@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use crate::pricing::models::Exchange;
+use crate::pricing::utils::{alpaca_auth_query, api_request};
+
+/*
+  Account Reconciliation
+  Fetches positions and balances held on an exchange account and compares them with the positions an engine
+  expects per leg, flagging missed fills, manual trades or partial executions before the next order is sent
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct AccountPosition {
+  pub symbol: String,
+  pub qty: f64, // signed, negative when short
+  pub entry_price: f64
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct AccountBalance {
+  pub asset: String,
+  pub free: f64,
+  pub locked: f64
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default, TS)]
+#[ts(export)]
+pub struct AccountState {
+  pub positions: Vec<AccountPosition>,
+  pub balances: Vec<AccountBalance>
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct ExpectedPosition {
+  pub symbol: String,
+  pub qty: f64 // signed, zero when the engine expects to be flat
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, TS)]
+#[ts(export)]
+pub enum ReconcileStatus {
+  Matched,
+  Diverged, // held on both sides with different quantities
+  Missing, // expected but not held on the exchange
+  Unexpected // held on the exchange but expected flat or unknown to the engine
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct LegReconciliation {
+  pub symbol: String,
+  pub expected_qty: f64,
+  pub actual_qty: f64,
+  pub diff: f64, // actual less expected
+  pub status: ReconcileStatus
+}
+
+impl AccountState {
+
+  /// Position Qty
+  /// Net signed quantity held in a symbol (0.0 when not held)
+  pub fn position_qty(&self, symbol: &str) -> f64 {
+    self.positions.iter().filter(|p| p.symbol == symbol).map(|p| p.qty).sum()
+  }
+
+  /// Balance
+  /// Balance held in an asset if any
+  pub fn balance(&self, asset: &str) -> Option<&AccountBalance> {
+    self.balances.iter().find(|b| b.asset == asset)
+  }
+}
+
+/// Json F64
+/// Reads a number given either as a json number or a numeric string
+fn json_f64(value: Option<&serde_json::Value>) -> Option<f64> {
+  let value: &serde_json::Value = value?;
+  value.as_f64().or_else(|| value.as_str()?.parse().ok())
+}
+
+/// Extract Positions Alpaca
+/// Takes Alpaca open positions (qty is already negative for shorts)
+fn extract_positions_alpaca(data_obj: &serde_json::Value) -> Vec<AccountPosition> {
+  data_obj.as_array().map(|items| items.iter().filter_map(|item| {
+    Some(AccountPosition {
+      symbol: item.get("symbol")?.as_str()?.to_string(),
+      qty: json_f64(item.get("qty"))?,
+      entry_price: json_f64(item.get("avg_entry_price")).unwrap_or(f64::NAN)
+    })
+  }).collect()).unwrap_or_default()
+}
+
+/// Extract Positions Binance
+/// Takes Binance futures position risk, skipping symbols without a position
+fn extract_positions_binance(data_obj: &serde_json::Value) -> Vec<AccountPosition> {
+  data_obj.as_array().map(|items| items.iter().filter_map(|item| {
+    let qty: f64 = json_f64(item.get("positionAmt"))?;
+    if qty == 0.0 { return None }
+    Some(AccountPosition {
+      symbol: item.get("symbol")?.as_str()?.to_string(),
+      qty,
+      entry_price: json_f64(item.get("entryPrice")).unwrap_or(f64::NAN)
+    })
+  }).collect()).unwrap_or_default()
+}
+
+/// Extract Balances Alpaca
+/// Takes an Alpaca account, cash being free and the initial margin held against open positions locked
+/// Equity less cash is position value (short proceeds included in cash), not cash that is locked
+fn extract_balances_alpaca(data_obj: &serde_json::Value) -> Vec<AccountBalance> {
+  let Some(cash) = json_f64(data_obj.get("cash")) else { return vec![] };
+  let currency: &str = data_obj.get("currency").and_then(|c| c.as_str()).unwrap_or("USD");
+  let initial_margin: f64 = json_f64(data_obj.get("initial_margin")).unwrap_or(0.0);
+  vec![AccountBalance { asset: currency.to_string(), free: cash, locked: initial_margin.max(0.0) }]
+}
+
+/// Extract Balances Binance
+/// Takes Binance futures balances, skipping empty assets
+fn extract_balances_binance(data_obj: &serde_json::Value) -> Vec<AccountBalance> {
+  data_obj.as_array().map(|items| items.iter().filter_map(|item| {
+    let total: f64 = json_f64(item.get("balance"))?;
+    let free: f64 = json_f64(item.get("availableBalance")).unwrap_or(total);
+    if total == 0.0 { return None }
+    Some(AccountBalance { asset: item.get("asset")?.as_str()?.to_string(), free, locked: (total - free).max(0.0) })
+  }).collect()).unwrap_or_default()
+}
+
+/// Parse Account State
+/// Structures positions and balances responses for the exchange
+/// Binance futures positionRisk and balance responses are accepted for callers who sign those requests themselves
+pub fn parse_account_state(exchange: &Exchange, positions_obj: &serde_json::Value, balances_obj: &serde_json::Value) -> Result<AccountState, SmartError> {
+  match exchange {
+    Exchange::Alpaca => Ok(AccountState {
+      positions: extract_positions_alpaca(positions_obj),
+      balances: extract_balances_alpaca(balances_obj)
+    }),
+    Exchange::Binance => Ok(AccountState {
+      positions: extract_positions_binance(positions_obj),
+      balances: extract_balances_binance(balances_obj)
+    }),
+    _ => Err(SmartError::RuntimeCheck(format!("Account state not supported for exchange: {}", exchange.as_string())))
+  }
+}
+
+/// Request Json
+async fn request_json(url: &str) -> Result<serde_json::Value, SmartError> {
+  let res_data: reqwest::Response = api_request(url).await?;
+
+  // Guard: Ensure status code
+  if res_data.status() != 200 {
+    let e: String = format!("Failed to extract data: {:?}", res_data.text().await);
+    return Err(SmartError::APIResponseStatus(e));
+  }
+  Ok(res_data.json().await?)
+}
+
+/// Request Alpaca Account State
+/// Fetches open positions and cash from the Alpaca trading api (api key given as "KEY_ID:SECRET_KEY")
+pub async fn request_alpaca_account_state(api_key: &str, is_paper: bool) -> Result<AccountState, SmartError> {
+  let base_url: &str = if is_paper { "https://paper-api.alpaca.markets" } else { "https://api.alpaca.markets" };
+  let auth: String = alpaca_auth_query(api_key);
+  let positions_url: String = format!("{}/v2/positions?{}", base_url, auth);
+  let account_url: String = format!("{}/v2/account?{}", base_url, auth);
+  let (positions_obj, account_obj) = futures::join!(request_json(&positions_url), request_json(&account_url));
+  parse_account_state(&Exchange::Alpaca, &positions_obj?, &account_obj?)
+}
+
+/// Reconcile Positions
+/// Compares expected quantities per leg with the account, differences within tolerance (absolute qty) count as matched
+/// Positions held on the exchange in symbols the engine does not track are reported as unexpected
+pub fn reconcile_positions(expected: &[ExpectedPosition], state: &AccountState, tolerance: f64) -> Vec<LegReconciliation> {
+  let tolerance: f64 = tolerance.abs();
+  let mut legs: Vec<LegReconciliation> = expected.iter().map(|leg| {
+    let actual_qty: f64 = state.position_qty(&leg.symbol);
+    let diff: f64 = actual_qty - leg.qty;
+    let status: ReconcileStatus = match (leg.qty.abs() > tolerance, actual_qty.abs() > tolerance) {
+      _ if diff.abs() <= tolerance => ReconcileStatus::Matched,
+      (true, false) => ReconcileStatus::Missing,
+      (false, true) => ReconcileStatus::Unexpected,
+      _ => ReconcileStatus::Diverged
+    };
+    LegReconciliation { symbol: leg.symbol.clone(), expected_qty: leg.qty, actual_qty, diff, status }
+  }).collect();
+
+  // Positions the engine has no record of
+  for position in state.positions.iter() {
+    let is_tracked: bool = legs.iter().any(|l| l.symbol == position.symbol);
+    if !is_tracked && position.qty.abs() > tolerance {
+      let actual_qty: f64 = state.position_qty(&position.symbol);
+      legs.push(LegReconciliation {
+        symbol: position.symbol.clone(),
+        expected_qty: 0.0,
+        actual_qty,
+        diff: actual_qty,
+        status: ReconcileStatus::Unexpected
+      });
+    }
+  }
+  legs
+}
+
+/// Divergent Legs
+/// Legs needing attention before trading continues
+pub fn divergent_legs(legs: &[LegReconciliation]) -> Vec<&LegReconciliation> {
+  legs.iter().filter(|l| l.status != ReconcileStatus::Matched).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_parses_account_state() {
+    let positions: serde_json::Value = serde_json::json!([
+      { "symbol": "SPY", "qty": "10", "side": "long", "avg_entry_price": "450.5" },
+      { "symbol": "QQQ", "qty": "-12", "side": "short", "avg_entry_price": "380.0" }
+    ]);
+    let account: serde_json::Value = serde_json::json!({ "currency": "USD", "cash": "5000.5", "equity": "9000", "initial_margin": "2250" });
+    let state: AccountState = parse_account_state(&Exchange::Alpaca, &positions, &account).unwrap();
+    assert_eq!(state.position_qty("QQQ"), -12.0);
+    assert_eq!(state.position_qty("IWM"), 0.0);
+    assert_eq!(state.balance("USD").map(|b| (b.free, b.locked)), Some((5000.5, 2250.0)));
+
+    let risk: serde_json::Value = serde_json::json!([
+      { "symbol": "ETHUSDT", "positionAmt": "-0.5", "entryPrice": "2000" },
+      { "symbol": "BTCUSDT", "positionAmt": "0.000", "entryPrice": "0" }
+    ]);
+    let balances: serde_json::Value = serde_json::json!([{ "asset": "USDT", "balance": "100", "availableBalance": "80" }]);
+    let state: AccountState = parse_account_state(&Exchange::Binance, &risk, &balances).unwrap();
+    assert_eq!(state.positions.len(), 1);
+    assert_eq!(state.balance("USDT").map(|b| b.locked), Some(20.0));
+    assert!(parse_account_state(&Exchange::Kraken, &risk, &balances).is_err());
+  }
+
+  #[tokio::test]
+  async fn it_reconciles_expected_positions() {
+    let position = |symbol: &str, qty: f64| AccountPosition { symbol: symbol.to_string(), qty, entry_price: 1.0 };
+    let expected = |symbol: &str, qty: f64| ExpectedPosition { symbol: symbol.to_string(), qty };
+    let state: AccountState = AccountState {
+      positions: vec![position("SPY", 10.0), position("QQQ", -7.0), position("IWM", 3.0), position("DIA", 1.0)],
+      balances: vec![]
+    };
+    let legs: Vec<LegReconciliation> = reconcile_positions(
+      &[expected("SPY", 10.0), expected("QQQ", -12.0), expected("TLT", 5.0), expected("IWM", 0.0)],
+      &state,
+      1e-9
+    );
+    let statuses: Vec<(&str, ReconcileStatus)> = legs.iter().map(|l| (l.symbol.as_str(), l.status)).collect();
+    assert_eq!(statuses, vec![
+      ("SPY", ReconcileStatus::Matched),
+      ("QQQ", ReconcileStatus::Diverged),
+      ("TLT", ReconcileStatus::Missing),
+      ("IWM", ReconcileStatus::Unexpected),
+      ("DIA", ReconcileStatus::Unexpected)
+    ]);
+    assert_eq!(legs[1].diff, 5.0);
+    assert_eq!(divergent_legs(&legs).len(), 4);
+
+    // Small residuals within tolerance match
+    let legs: Vec<LegReconciliation> = reconcile_positions(&[expected("SPY", 10.001)], &state, 0.01);
+    assert_eq!(legs[0].status, ReconcileStatus::Matched);
+  }
+}
@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use crate::backtest::models::SignalThresholds;
+use crate::pricing::utils::api_post;
+
+/*
+  Alerts
+  Signal alerts rendered from a message template and sent through ready made notifiers
+  Telegram messages go through the bot api (sendMessage) and Discord messages through a channel webhook
+*/
+
+/// Default message template (see render_alert for placeholders)
+pub const DEFAULT_ALERT_TEMPLATE: &str = "{kind} {pair}\nZscore: {zscore}\nEntry: {short} / {long}\nExit: {short_close} / {long_close}";
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, TS)]
+#[ts(export)]
+pub enum AlertKind {
+  LongEntry, // long asset 0, short asset 1
+  ShortEntry,
+  Exit,
+  Degradation // pair no longer meets its score
+}
+
+impl AlertKind {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::LongEntry => "Long entry",
+      Self::ShortEntry => "Short entry",
+      Self::Exit => "Exit",
+      Self::Degradation => "Degradation"
+    }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct Alert {
+  pub kind: AlertKind,
+  pub asset_0: String,
+  pub asset_1: String,
+  pub zscore: f64,
+  pub thresholds: SignalThresholds,
+  pub link: Option<String>, // e.g. a chart or dashboard url for the pair
+  pub time: i64 // unix seconds
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub enum Notifier {
+  Telegram { bot_token: String, chat_id: String },
+  Discord { webhook_url: String }
+}
+
+/// Render Alert
+/// Fills a template, placeholders are {kind} {pair} {asset_0} {asset_1} {zscore} {long} {long_close} {short} {short_close} {link} {time}
+pub fn render_alert(template: &str, alert: &Alert) -> String {
+  let fmt = |value: f64| format!("{:.2}", value);
+  template
+    .replace("{kind}", alert.kind.as_str())
+    .replace("{pair}", &format!("{}/{}", alert.asset_0, alert.asset_1))
+    .replace("{asset_0}", &alert.asset_0)
+    .replace("{asset_1}", &alert.asset_1)
+    .replace("{zscore}", &fmt(alert.zscore))
+    .replace("{long_close}", &fmt(alert.thresholds.long_close))
+    .replace("{short_close}", &fmt(alert.thresholds.short_close))
+    .replace("{long}", &fmt(alert.thresholds.long))
+    .replace("{short}", &fmt(alert.thresholds.short))
+    .replace("{link}", alert.link.as_deref().unwrap_or_default())
+    .replace("{time}", &alert.time.to_string())
+}
+
+impl Notifier {
+
+  /// Request
+  /// Url and JSON body for an alert, the link is sent as a button (Telegram) or embed url (Discord)
+  pub fn request(&self, alert: &Alert, template: &str) -> (String, serde_json::Value) {
+    let text: String = render_alert(template, alert);
+    match self {
+      Self::Telegram { bot_token, chat_id } => {
+        let mut body: serde_json::Value = serde_json::json!({ "chat_id": chat_id, "text": text });
+        if let Some(link) = &alert.link {
+          body["reply_markup"] = serde_json::json!({ "inline_keyboard": [[{ "text": "Open", "url": link }]] });
+        }
+        (format!("https://api.telegram.org/bot{}/sendMessage", bot_token), body)
+      },
+      Self::Discord { webhook_url } => {
+        let mut embed: serde_json::Value = serde_json::json!({
+          "title": format!("{} {}/{}", alert.kind.as_str(), alert.asset_0, alert.asset_1),
+          "fields": [
+            { "name": "Zscore", "value": format!("{:.2}", alert.zscore), "inline": true },
+            { "name": "Entry", "value": format!("{:.2} / {:.2}", alert.thresholds.short, alert.thresholds.long), "inline": true },
+            { "name": "Exit", "value": format!("{:.2} / {:.2}", alert.thresholds.short_close, alert.thresholds.long_close), "inline": true }
+          ]
+        });
+        if let Some(link) = &alert.link {
+          embed["url"] = serde_json::json!(link);
+        }
+        (webhook_url.clone(), serde_json::json!({ "content": text, "embeds": [embed] }))
+      }
+    }
+  }
+
+  /// Text Request
+  /// Url and JSON body for a plain message (e.g. a daily report)
+  pub fn text_request(&self, text: &str) -> (String, serde_json::Value) {
+    match self {
+      Self::Telegram { bot_token, chat_id } => {
+        (format!("https://api.telegram.org/bot{}/sendMessage", bot_token), serde_json::json!({ "chat_id": chat_id, "text": text }))
+      },
+      Self::Discord { webhook_url } => (webhook_url.clone(), serde_json::json!({ "content": text }))
+    }
+  }
+
+  /// Send
+  /// Posts an alert rendered from the template
+  pub async fn send(&self, alert: &Alert, template: &str) -> Result<(), SmartError> {
+    let (url, body) = self.request(alert, template);
+    post_notification(&url, &body).await
+  }
+
+  /// Send Text
+  /// Posts a plain message
+  pub async fn send_text(&self, text: &str) -> Result<(), SmartError> {
+    let (url, body) = self.text_request(text);
+    post_notification(&url, &body).await
+  }
+}
+
+/// Post Notification
+async fn post_notification(url: &str, body: &serde_json::Value) -> Result<(), SmartError> {
+  let res_data: reqwest::Response = api_post(url, &body.to_string()).await?;
+
+  // Guard: Ensure success (Discord webhooks answer 204 No Content)
+  if !res_data.status().is_success() {
+    let e: String = format!("Failed to send notification: {:?}", res_data.text().await);
+    return Err(SmartError::APIResponseStatus(e));
+  }
+  Ok(())
+}
+
+/// Send Alert
+/// Sends an alert through every notifier, returning the errors of any that failed
+pub async fn send_alert(notifiers: &[Notifier], alert: &Alert, template: &str) -> Vec<SmartError> {
+  let results: Vec<Result<(), SmartError>> = futures::future::join_all(notifiers.iter().map(|n| n.send(alert, template))).await;
+  results.into_iter().filter_map(|r| r.err()).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn alert(link: Option<&str>) -> Alert {
+    Alert {
+      kind: AlertKind::LongEntry,
+      asset_0: "BTCUSDT".to_string(),
+      asset_1: "ETHUSDT".to_string(),
+      zscore: -2.134,
+      thresholds: SignalThresholds { long: -2.0, long_close: 0.0, short: 2.0, short_close: 0.0 },
+      link: link.map(|l| l.to_string()),
+      time: 1_700_000_000
+    }
+  }
+
+  #[tokio::test]
+  async fn it_renders_alert_templates() {
+    let text: String = render_alert(DEFAULT_ALERT_TEMPLATE, &alert(None));
+    assert_eq!(text, "Long entry BTCUSDT/ETHUSDT\nZscore: -2.13\nEntry: 2.00 / -2.00\nExit: 0.00 / 0.00");
+    assert_eq!(render_alert("{asset_0} {zscore} {link}", &alert(Some("https://x.io/p"))), "BTCUSDT -2.13 https://x.io/p");
+  }
+
+  #[tokio::test]
+  async fn it_builds_notifier_requests() {
+    let telegram: Notifier = Notifier::Telegram { bot_token: "123:ABC".to_string(), chat_id: "-100".to_string() };
+    let (url, body) = telegram.request(&alert(Some("https://x.io/p")), "{pair}");
+    assert_eq!(url, "https://api.telegram.org/bot123:ABC/sendMessage");
+    assert_eq!((body["chat_id"].as_str(), body["text"].as_str()), (Some("-100"), Some("BTCUSDT/ETHUSDT")));
+    assert_eq!(body["reply_markup"]["inline_keyboard"][0][0]["url"], "https://x.io/p");
+
+    let discord: Notifier = Notifier::Discord { webhook_url: "https://discord.com/api/webhooks/1/t".to_string() };
+    let (url, body) = discord.request(&alert(None), DEFAULT_ALERT_TEMPLATE);
+    assert_eq!(url, "https://discord.com/api/webhooks/1/t");
+    assert_eq!(body["embeds"][0]["fields"][0]["value"], "-2.13");
+    assert!(body["embeds"][0].get("url").is_none());
+
+    let (_, body) = discord.text_request("Daily report");
+    assert_eq!(body, serde_json::json!({ "content": "Daily report" }));
+  }
+}
@@ -1,5 +1,6 @@
 use super::models::WinRate;
 use super::utils::{log_to_simple_returns, round_float};
+use crate::presets::DEFAULT_TRADING_DAYS;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
@@ -11,11 +12,155 @@ pub struct BacktestMetrics {
   pub drawdowns: Vec<f64>,
   pub equity_curve: Vec<f64>,
   pub max_drawdown: f64,
-  pub mean_return: f64,
+  pub mean_return: f64, // on the configured return basis
+  #[serde(default)]
+  pub mean_return_per_bar: f64,
+  #[serde(default)]
+  pub mean_return_per_trade: f64,
   pub sharpe_ratio: f64,
   pub sortino_ratio: f64,
   pub total_return: f64,
-  pub win_rate_stats: WinRate
+  pub win_rate_stats: WinRate,
+  #[serde(default)]
+  pub outcome: BacktestOutcome,
+  #[serde(default)]
+  pub leg_pnl: LegPnl,
+  #[serde(default)]
+  pub trade_stats: TradeStats,
+  #[serde(default)]
+  pub evaluation_start: usize // first bar evaluated, the curves start here (earlier bars are the indicator warm-up)
+}
+
+/// Trade Stats
+/// Clustering of closed trade outcomes (streaks drive drawdown expectations more than win rate alone)
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, TS)]
+#[ts(export)]
+pub struct TradeStats {
+  pub trade_returns: Vec<f64>, // net log return per closed trade
+  pub max_consecutive_losses: u32,
+  pub max_consecutive_wins: u32,
+  pub loss_streak_counts: Vec<u32>, // index n = number of losing streaks of length n + 1
+  pub pnl_autocorrelation: f64 // lag 1 autocorrelation of trade returns
+}
+
+impl TradeStats {
+  pub fn new(trade_returns: Vec<f64>) -> Self {
+    let mut max_consecutive_losses: u32 = 0;
+    let mut max_consecutive_wins: u32 = 0;
+    let mut loss_streak_counts: Vec<u32> = vec![];
+    let mut losses: u32 = 0;
+    let mut wins: u32 = 0;
+
+    // Trailing NaN flushes a final losing streak (it counts as neither win nor loss)
+    for r in trade_returns.iter().chain(std::iter::once(&f64::NAN)) {
+      if *r <= 0.0 { losses += 1; } else if losses > 0 {
+        if loss_streak_counts.len() < losses as usize { loss_streak_counts.resize(losses as usize, 0); }
+        loss_streak_counts[losses as usize - 1] += 1;
+        losses = 0;
+      }
+      if *r > 0.0 { wins += 1; } else { wins = 0; }
+      max_consecutive_losses = max_consecutive_losses.max(losses);
+      max_consecutive_wins = max_consecutive_wins.max(wins);
+    }
+
+    let pnl_autocorrelation: f64 = round_float(lag_one_autocorrelation(&trade_returns), 3);
+    let trade_returns: Vec<f64> = trade_returns.iter().map(|f| round_float(*f, 5)).collect();
+    Self { trade_returns, max_consecutive_losses, max_consecutive_wins, loss_streak_counts, pnl_autocorrelation }
+  }
+}
+
+/// Lag One Autocorrelation
+/// Zero when fewer than 3 values or no variance
+fn lag_one_autocorrelation(values: &[f64]) -> f64 {
+  if values.len() < 3 { return 0.0 }
+  let mean: f64 = values.iter().sum::<f64>() / values.len() as f64;
+  let variance: f64 = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>();
+  if variance == 0.0 { return 0.0 }
+  let covariance: f64 = values.windows(2).map(|w| (w[0] - mean) * (w[1] - mean)).sum::<f64>();
+  covariance / variance
+}
+
+/// Backtest Outcome
+/// Whether any trade was triggered (metrics are all zero rather than NaN when not)
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default, TS)]
+#[ts(export)]
+pub enum BacktestOutcome {
+  #[default]
+  Traded,
+  NoTradesTriggered
+}
+
+/// Finite Or Zero
+/// Prevents NaN / infinite metrics reaching JSON consumers
+fn finite_or_zero(value: f64) -> f64 {
+  if value.is_finite() { value } else { 0.0 }
+}
+
+/// Leg PnL
+/// Strategy log returns attributed to each leg (before trading costs)
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, TS)]
+#[ts(export)]
+pub struct LegPnl {
+  pub leg_0_log_returns: Vec<f64>,
+  pub leg_1_log_returns: Vec<f64>,
+  pub leg_0_total: f64,
+  pub leg_1_total: f64,
+  pub costs_total: f64,
+  pub leg_0_share: f64 // share of absolute leg pnl coming from leg 0 (0.5 when both legs contribute equally)
+}
+
+impl LegPnl {
+  pub fn new(leg_0_log_returns: Vec<f64>, leg_1_log_returns: Vec<f64>, costs_total: f64) -> Self {
+    let leg_0_total: f64 = leg_0_log_returns.iter().sum();
+    let leg_1_total: f64 = leg_1_log_returns.iter().sum();
+    let abs_total: f64 = leg_0_total.abs() + leg_1_total.abs();
+    let leg_0_share: f64 = if abs_total == 0.0 { 0.5 } else { leg_0_total.abs() / abs_total };
+    Self {
+      leg_0_log_returns: leg_0_log_returns.iter().map(|f| round_float(*f, 6)).collect(),
+      leg_1_log_returns: leg_1_log_returns.iter().map(|f| round_float(*f, 6)).collect(),
+      leg_0_total: round_float(leg_0_total, 4),
+      leg_1_total: round_float(leg_1_total, 4),
+      costs_total: round_float(costs_total, 4),
+      leg_0_share: round_float(leg_0_share, 3)
+    }
+  }
+}
+
+/// Return Basis
+/// LogPerBar: mean_return is the geometric mean per bar, exp(mean bar log return) - 1, including flat bars
+/// SimplePerTrade: mean_return is the arithmetic mean of closed trade simple returns
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default, TS)]
+#[ts(export)]
+pub enum ReturnBasis {
+  #[default]
+  LogPerBar,
+  SimplePerTrade
+}
+
+/// Evaluation Config
+/// Hurdle used for Sharpe and Sortino: an annual risk free rate (optionally disabled) or a benchmark log return series
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+#[serde(default)]
+pub struct EvaluationConfig {
+  pub risk_free_rate_annual: f64,
+  pub use_risk_free: bool,
+  pub benchmark_log_returns: Option<Vec<f64>>, // per bar, aligned with the backtest bars (takes precedence over the risk free rate)
+  #[serde(default)]
+  pub return_basis: ReturnBasis,
+  pub periods_per_year: f64 // bars per year used to annualize (see AssetPreset::periods_per_year)
+}
+
+impl Default for EvaluationConfig {
+  fn default() -> Self {
+    Self {
+      risk_free_rate_annual: 0.015,
+      use_risk_free: true,
+      benchmark_log_returns: None,
+      return_basis: ReturnBasis::LogPerBar,
+      periods_per_year: DEFAULT_TRADING_DAYS as f64
+    }
+  }
 }
 
 #[derive(Debug)]
@@ -23,6 +168,10 @@ pub struct Evaluation {
   pub log_returns: Vec<f64>,
   pub cum_norm_returns: Vec<f64>,
   pub win_rate_stats: WinRate,
+  pub leg_pnl: LegPnl,
+  pub trade_stats: TradeStats,
+  pub trade_log_returns: Vec<f64>,
+  pub eval_config: EvaluationConfig
 }
 
 impl Evaluation {
@@ -31,21 +180,47 @@ impl Evaluation {
       log_returns,
       cum_norm_returns,
       win_rate_stats,
+      leg_pnl: LegPnl::default(),
+      trade_stats: TradeStats::default(),
+      trade_log_returns: vec![],
+      eval_config: EvaluationConfig::default()
     }
   }
 
+  /// With Config
+  /// Sets the risk free rate / benchmark hurdle
+  pub fn with_config(mut self, eval_config: EvaluationConfig) -> Self {
+    self.eval_config = eval_config;
+    self
+  }
+
+  /// With Trade Returns
+  /// Attaches the net return of each closed trade for streak and clustering stats
+  pub fn with_trade_returns(mut self, trade_returns: Vec<f64>) -> Self {
+    self.trade_stats = TradeStats::new(trade_returns.clone());
+    self.trade_log_returns = trade_returns;
+    self
+  }
+
+  /// With Leg PnL
+  /// Attaches the per leg attribution to be reported in the metrics
+  pub fn with_leg_pnl(mut self, leg_pnl: LegPnl) -> Self {
+    self.leg_pnl = leg_pnl;
+    self
+  }
+
   // Annual Rate of Return
+  // Always compounds the per bar mean (independent of the return basis)
   fn annual_rate_of_return(&self) -> f64 {
-    let mean_return: f64 = self.mean_return();
-    let periods_per_year: f64 = 252.0; // for daily returns
-    (1.0 + mean_return).powf(periods_per_year) - 1.0
+    let mean_return: f64 = self.mean_return_per_bar();
+    (1.0 + mean_return).powf(self.eval_config.periods_per_year) - 1.0
   }
 
   /// Drawdowns
   fn drawdowns(&self) -> Vec<f64> {
     let norm_returns: Vec<f64> = self.cum_norm_returns.clone();
     let mut drawdowns: Vec<f64> = Vec::new();
-    let mut max_return_so_far: f64 = norm_returns[0];
+    let Some(mut max_return_so_far) = norm_returns.first().copied() else { return drawdowns };
     for r in norm_returns {
       if r > max_return_so_far {
         max_return_so_far = r;
@@ -57,79 +232,99 @@ impl Evaluation {
     drawdowns
   }
 
+  /// Mean Return Per Bar
+  /// Geometric mean simple return per bar: exp(mean of all bar log returns) - 1 (flat bars included)
+  fn mean_return_per_bar(&self) -> f64 {
+    if self.log_returns.is_empty() { return 0.0 }
+    let mean_log: f64 = self.log_returns.iter().sum::<f64>() / self.log_returns.len() as f64;
+    f64::exp(mean_log) - 1.0
+  }
+
+  /// Mean Return Per Trade
+  /// Arithmetic mean of each closed trade's simple return (exp(trade log return) - 1)
+  fn mean_return_per_trade(&self) -> f64 {
+    if self.trade_log_returns.is_empty() { return 0.0 }
+    let simple: Vec<f64> = log_to_simple_returns(&self.trade_log_returns);
+    simple.iter().sum::<f64>() / simple.len() as f64
+  }
+
   /// Mean Return
-  /// Takes in log returns and provides a linear mean return value
+  /// Mean return on the configured basis
   fn mean_return(&self) -> f64 {
-    let filtered_vec: Vec<&f64> = self.log_returns.iter().filter(|&&x| x != 0.0).collect();
-    let sum: f64 = filtered_vec.iter().fold(0.0, |a, b| a + **b);
-    let count: usize = filtered_vec.len();
-    
-    let log_ret = match count {
-      0 => 0.0,
-      _ => sum / (count as f64),
-    };
+    match self.eval_config.return_basis {
+      ReturnBasis::LogPerBar => self.mean_return_per_bar(),
+      ReturnBasis::SimplePerTrade => self.mean_return_per_trade()
+    }
+  }
 
-    f64::exp(log_ret) - 1.0
+  /// Hurdle Returns
+  /// Per bar return to beat: the benchmark return if supplied, else the per bar risk free rate (or zero if disabled)
+  fn hurdle_returns(&self) -> Vec<f64> {
+    let config: &EvaluationConfig = &self.eval_config;
+    match &config.benchmark_log_returns {
+      Some(benchmark) => (0..self.log_returns.len()).map(|i| benchmark.get(i).copied().unwrap_or(0.0)).collect(),
+      None if config.use_risk_free => {
+        let risk_free_rate_daily: f64 = (1.0 + config.risk_free_rate_annual).powf(1.0 / config.periods_per_year) - 1.0;
+        vec![risk_free_rate_daily; self.log_returns.len()]
+      },
+      None => vec![0.0; self.log_returns.len()]
+    }
   }
 
   /// Sharpe Ratio
-  fn sharpe_ratio(&self, risk_free_rate_annual: f64) -> f64 {
+  /// Annualized mean excess return over the hurdle divided by the volatility of returns
+  /// Against a benchmark series the volatility of excess returns is used (information ratio)
+  fn sharpe_ratio(&self) -> f64 {
     let n: f64 = self.log_returns.len() as f64;
     if n == 0.0 { return 0.0; }
 
-    let annual_trading_days = 252.0;
-
-    // Convert the annual risk-free rate to a daily rate
-    let risk_free_rate_daily = (1.0 + risk_free_rate_annual).powf(1.0 / annual_trading_days) - 1.0;
+    let annual_trading_days: f64 = self.eval_config.periods_per_year;
+    let hurdle: Vec<f64> = self.hurdle_returns();
+    let excess: Vec<f64> = self.log_returns.iter().zip(hurdle.iter()).map(|(r, h)| r - h).collect();
+    let adjusted_mean: f64 = excess.iter().sum::<f64>() / n;
 
-    let mean: f64 = self.log_returns.iter().sum::<f64>() / n;
-    // Adjust the mean by subtracting the daily risk-free rate
-    let adjusted_mean = mean - risk_free_rate_daily;
-
-    let variance: f64 = self.log_returns.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / n;
+    let dispersion: &Vec<f64> = if self.eval_config.benchmark_log_returns.is_some() { &excess } else { &self.log_returns };
+    let mean: f64 = dispersion.iter().sum::<f64>() / n;
+    let variance: f64 = dispersion.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / n;
     if variance == 0.0 { return 0.0; }
 
     // Calculate the annualized Sharpe ratio
     adjusted_mean * annual_trading_days.sqrt() / variance.sqrt()
   }
 
-  /// Sortino Ratio without risk-free rate
-  fn sortino_ratio(&self, risk_free_rate_annual: f64) -> f64 {
-  let n: f64 = self.log_returns.len() as f64;
-  if n == 0.0 { return 0.0; }
-
-  let annual_trading_days = 252.0;
-
-  // Convert the annual risk-free rate to a daily rate
-  let risk_free_rate_daily = (1.0 + risk_free_rate_annual).powf(1.0 / annual_trading_days) - 1.0;
-
-  let mean: f64 = self.log_returns.iter().sum::<f64>() / n;
-  // Adjust the mean by subtracting the daily risk-free rate
-  let adjusted_mean = mean - risk_free_rate_daily;
+  /// Sortino Ratio
+  /// Annualized mean excess return over the hurdle divided by the downside deviation below the hurdle
+  fn sortino_ratio(&self) -> f64 {
+    let n: f64 = self.log_returns.len() as f64;
+    if n == 0.0 { return 0.0; }
 
-  // Calculate the downside deviation
-  let downside_deviation: f64 = self.log_returns.iter()
-    .filter(|&&x| x < risk_free_rate_daily) // consider only returns less than the risk-free rate
-    .map(|&x| (x - risk_free_rate_daily).powi(2))
-    .sum::<f64>() / n;
+    let annual_trading_days: f64 = self.eval_config.periods_per_year;
+    let hurdle: Vec<f64> = self.hurdle_returns();
+    let excess: Vec<f64> = self.log_returns.iter().zip(hurdle.iter()).map(|(r, h)| r - h).collect();
+    let adjusted_mean: f64 = excess.iter().sum::<f64>() / n;
 
-  if downside_deviation == 0.0 { return 0.0; }
+    // Calculate the downside deviation
+    let downside_deviation: f64 = excess.iter()
+      .filter(|&&x| x < 0.0) // consider only returns less than the hurdle
+      .map(|&x| x.powi(2))
+      .sum::<f64>() / n;
 
-  // Calculate the annualized Sortino ratio
-  adjusted_mean * annual_trading_days.sqrt() / downside_deviation.sqrt()
-}
+    if downside_deviation == 0.0 { return 0.0; }
 
+    // Calculate the annualized Sortino ratio
+    adjusted_mean * annual_trading_days.sqrt() / downside_deviation.sqrt()
+  }
 
   /// Total Return
   fn total_return(&self) -> f64 {
-    self.cum_norm_returns[self.cum_norm_returns.len() - 1]
+    self.cum_norm_returns.last().copied().unwrap_or(0.0)
   }
 
   // Max Drawdown
   fn calculate_max_drawdown(&self) -> f64 {
     let mut max_drawdown = 0.0;
     let equity_curve: Vec<f64> = self.log_returns.iter().scan(1.0, |state, &log_return| { *state *= log_return.exp(); Some(*state) }).collect();
-    let mut peak = equity_curve[0];
+    let Some(mut peak) = equity_curve.first().copied() else { return max_drawdown };
 
     for &value in equity_curve.iter() {
       if value > peak {
@@ -148,17 +343,81 @@ impl Evaluation {
   /// Calculates metrics and returns net evaluation serialized
   pub fn run_evaluation_metrics(&self) -> BacktestMetrics {
 
-    let arr: f64 = round_float(self.annual_rate_of_return(), 2);
+    let arr: f64 = round_float(finite_or_zero(self.annual_rate_of_return()), 2);
     let drawdowns: Vec<f64> = self.drawdowns().iter().map(|f| round_float(*f, 3)).collect();
     let equity_curve: Vec<f64> = self.cum_norm_returns.iter().map(|f| round_float(*f, 4)).collect();
-    let max_drawdown: f64 = -round_float(self.calculate_max_drawdown(), 2);
-    let mean_return: f64 = round_float(self.mean_return(), 3);
-    let sharpe_ratio: f64 = round_float(self.sharpe_ratio(0.015), 2);
-    let sortino_ratio: f64 = round_float(self.sortino_ratio(0.015), 2);
-    let total_return: f64 = round_float(self.total_return(), 2);
+    let max_drawdown: f64 = -round_float(finite_or_zero(self.calculate_max_drawdown()), 2);
+    let mean_return: f64 = round_float(finite_or_zero(self.mean_return()), 5);
+    let mean_return_per_bar: f64 = round_float(finite_or_zero(self.mean_return_per_bar()), 5);
+    let mean_return_per_trade: f64 = round_float(finite_or_zero(self.mean_return_per_trade()), 5);
+    let sharpe_ratio: f64 = round_float(finite_or_zero(self.sharpe_ratio()), 2);
+    let sortino_ratio: f64 = round_float(finite_or_zero(self.sortino_ratio()), 2);
+    let total_return: f64 = round_float(finite_or_zero(self.total_return()), 2);
     let win_rate_stats: WinRate = self.win_rate_stats.to_owned();
 
-    BacktestMetrics { arr, drawdowns, equity_curve, max_drawdown, mean_return, 
-      sharpe_ratio, sortino_ratio, total_return, win_rate_stats }
+    let leg_pnl: LegPnl = self.leg_pnl.clone();
+    let trade_stats: TradeStats = self.trade_stats.clone();
+    let outcome: BacktestOutcome = if win_rate_stats.has_trades { BacktestOutcome::Traded } else { BacktestOutcome::NoTradesTriggered };
+
+    BacktestMetrics { arr, drawdowns, equity_curve, max_drawdown, mean_return, mean_return_per_bar, mean_return_per_trade,
+      sharpe_ratio, sortino_ratio, total_return, win_rate_stats, outcome, leg_pnl, trade_stats, evaluation_start: 0 }
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_calculates_trade_streaks() {
+    let stats: TradeStats = TradeStats::new(vec![0.01, -0.02, -0.01, 0.03, -0.01, 0.02, 0.01, -0.01, -0.02, -0.03]);
+    assert_eq!(stats.max_consecutive_losses, 3);
+    assert_eq!(stats.max_consecutive_wins, 2);
+    assert_eq!(stats.loss_streak_counts, vec![1, 1, 1]);
+    assert_eq!(TradeStats::new(vec![]).max_consecutive_losses, 0);
   }
-}
\ No newline at end of file
+
+  #[tokio::test]
+  async fn it_reports_mean_return_on_each_basis() {
+    let log_returns: Vec<f64> = vec![0.0, 0.1, 0.0, -0.05, 0.0];
+    let win_rate: WinRate = WinRate::new(2, 2, 1);
+    let evaluation: Evaluation = Evaluation::new(log_returns, vec![0.0; 5], win_rate).with_trade_returns(vec![0.1, -0.05]);
+
+    assert!((evaluation.mean_return_per_bar() - (0.01f64.exp() - 1.0)).abs() < 1e-12);
+    let per_trade: f64 = ((0.1f64.exp() - 1.0) + ((-0.05f64).exp() - 1.0)) / 2.0;
+    assert!((evaluation.mean_return_per_trade() - per_trade).abs() < 1e-12);
+
+    let evaluation: Evaluation = evaluation.with_config(EvaluationConfig { return_basis: ReturnBasis::SimplePerTrade, ..Default::default() });
+    assert_eq!(evaluation.mean_return(), evaluation.mean_return_per_trade());
+  }
+
+  #[tokio::test]
+  async fn it_reports_no_trades_without_nan() {
+    let evaluation: Evaluation = Evaluation::new(vec![0.0; 5], vec![0.0; 5], WinRate::new(0, 0, 0));
+    let metrics: BacktestMetrics = evaluation.run_evaluation_metrics();
+    assert_eq!(metrics.outcome, BacktestOutcome::NoTradesTriggered);
+    assert!(!metrics.win_rate_stats.has_trades);
+    assert_eq!(metrics.win_rate_stats.win_rate, 0.0);
+    assert!(serde_json::to_string(&metrics).unwrap().find("null").is_none());
+
+    // A trade opened but not yet closed counts as traded with a zero win rate
+    let metrics: BacktestMetrics = Evaluation::new(vec![0.0; 5], vec![0.0; 5], WinRate::new(1, 0, 0)).run_evaluation_metrics();
+    assert_eq!(metrics.outcome, BacktestOutcome::Traded);
+    assert!(metrics.win_rate_stats.has_trades);
+    assert_eq!(metrics.win_rate_stats.win_rate, 0.0);
+  }
+
+  #[tokio::test]
+  async fn it_applies_configured_hurdle() {
+    let log_returns: Vec<f64> = vec![0.01, -0.005, 0.002, 0.004, -0.001, 0.003];
+    let win_rate: WinRate = WinRate::new(0, 0, 0);
+    let evaluation = |config: EvaluationConfig| Evaluation::new(log_returns.clone(), vec![0.0; 6], win_rate.clone()).with_config(config);
+
+    let default_sharpe: f64 = evaluation(EvaluationConfig::default()).sharpe_ratio();
+    let no_rf_sharpe: f64 = evaluation(EvaluationConfig { use_risk_free: false, ..Default::default() }).sharpe_ratio();
+    let benchmark_sharpe: f64 = evaluation(EvaluationConfig { benchmark_log_returns: Some(log_returns.clone()), ..Default::default() }).sharpe_ratio();
+    assert!(no_rf_sharpe > default_sharpe);
+    assert_eq!(benchmark_sharpe, 0.0);
+  }
+}
@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::SmartError;
+use super::evaluation::BacktestMetrics;
+use super::models::{Backtest, BacktestCriteria};
+
+/*
+  Backtest Golden Fixtures
+  Canonical input series with the expected signals and metrics stored as golden json
+  Allows downstream users to verify their build produces identical numbers after upgrades
+*/
+
+const GOLDEN_FILES: [(&str, &str); 3] = [
+  ("zscore_static", include_str!("golden/zscore_static.json")),
+  ("zscore_costs_stop_loss", include_str!("golden/zscore_costs_stop_loss.json")),
+  ("spread_long_series_1", include_str!("golden/spread_long_series_1.json"))
+];
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct GoldenCase {
+  pub name: String,
+  pub series_0: Vec<f64>,
+  pub series_1: Vec<f64>,
+  pub criteria: BacktestCriteria,
+  pub expected_signals: Vec<i32>,
+  pub expected_metrics: BacktestMetrics
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct GoldenResult {
+  pub name: String,
+  pub signals_match: bool,
+  pub metrics_match: bool,
+  pub mismatched_fields: Vec<String>
+}
+
+impl GoldenResult {
+  pub fn passed(&self) -> bool {
+    self.signals_match && self.metrics_match
+  }
+}
+
+/// Golden Cases
+/// Canonical cases embedded in the library
+pub fn golden_cases() -> Result<Vec<GoldenCase>, SmartError> {
+  GOLDEN_FILES.iter()
+    .map(|(_, json)| Ok(serde_json::from_str::<GoldenCase>(json)?))
+    .collect()
+}
+
+/// Generate Golden Case
+/// Runs a backtest and records its output as a golden case (used to regenerate the golden files)
+pub fn generate_golden_case(name: &str, series_0: Vec<f64>, series_1: Vec<f64>, criteria: BacktestCriteria) -> Result<GoldenCase, SmartError> {
+  let backtest: Backtest = Backtest::new(&series_0, &series_1, criteria.clone())?;
+  let expected_signals: Vec<i32> = backtest.signals()?;
+  let expected_metrics: BacktestMetrics = backtest.run_backtest()?;
+  Ok(GoldenCase { name: name.to_string(), series_0, series_1, criteria, expected_signals, expected_metrics })
+}
+
+/// Run Golden Case
+/// Re-runs a case and compares signals and every metrics field exactly
+pub fn run_golden_case(case: &GoldenCase) -> Result<GoldenResult, SmartError> {
+  let actual: GoldenCase = generate_golden_case(&case.name, case.series_0.clone(), case.series_1.clone(), case.criteria.clone())?;
+
+  let expected_value: Value = serde_json::to_value(&case.expected_metrics)?;
+  let actual_value: Value = serde_json::to_value(&actual.expected_metrics)?;
+  let mismatched_fields: Vec<String> = match (expected_value.as_object(), actual_value.as_object()) {
+    (Some(expected), Some(actual)) => expected.iter()
+      .filter(|(key, value)| actual.get(*key) != Some(*value))
+      .map(|(key, _)| key.clone())
+      .collect(),
+    _ => vec!["metrics".to_string()]
+  };
+
+  Ok(GoldenResult {
+    name: case.name.clone(),
+    signals_match: actual.expected_signals == case.expected_signals,
+    metrics_match: mismatched_fields.is_empty(),
+    mismatched_fields
+  })
+}
+
+/// Verify Golden
+/// Runs every embedded golden case
+pub fn verify_golden() -> Result<Vec<GoldenResult>, SmartError> {
+  golden_cases()?.iter().map(run_golden_case).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::backtest::models::{LongSeries, Relation, SignalTiming, TriggerIndicator};
+  use crate::pricing::fees::FeeSide;
+  use crate::stats::models::{SpreadType, Statistics};
+
+  fn canonical_series() -> (Vec<f64>, Vec<f64>) {
+    let series_1: Vec<f64> = (0..250).map(|i| 100.0 + (i as f64 / 10.0).sin() * 5.0 + i as f64 * 0.1).collect();
+    let series_0: Vec<f64> = series_1.iter().enumerate().map(|(i, p)| p * 2.0 + (i as f64 / 3.0).cos() * 2.0).collect();
+    (series_0, series_1)
+  }
+
+  fn canonical_cases() -> Vec<GoldenCase> {
+    let (series_0, series_1) = canonical_series();
+    let stats: Statistics = Statistics::calculate_statistics(&series_0, &series_1, SpreadType::Static, 35, 90).unwrap();
+    let base: BacktestCriteria = BacktestCriteria {
+      indicator_values: stats.zscore.clone(),
+      trigger_indicator: TriggerIndicator::Zscore,
+      relation: Relation::Ignore,
+      cost_per_leg: None,
+      rets_weighting_s0_perc: 0.5,
+      long_series: LongSeries::Series0,
+      stop_loss: 0.0,
+      long_thresh: -1.5,
+      long_close_thresh: 0.0,
+      short_thresh: 1.5,
+      short_close_thresh: 0.0,
+      long_entry: None,
+      short_entry: None,
+      index_beta: None,
+      exchange: None,
+      fee_side: FeeSide::Taker,
+      warmup_bars: None,
+      signal_timing: SignalTiming::SameClose
+    };
+    let costs: BacktestCriteria = BacktestCriteria { cost_per_leg: Some(0.0005), stop_loss: -0.02, ..base.clone() };
+    let spread: BacktestCriteria = BacktestCriteria {
+      indicator_values: stats.spread.clone(),
+      trigger_indicator: TriggerIndicator::Spread,
+      long_series: LongSeries::Series1,
+      rets_weighting_s0_perc: 0.4,
+      long_thresh: -2.0,
+      long_close_thresh: 0.0,
+      short_thresh: 2.0,
+      short_close_thresh: 0.0,
+      ..base.clone()
+    };
+
+    vec![
+      generate_golden_case("zscore_static", series_0.clone(), series_1.clone(), base).unwrap(),
+      generate_golden_case("zscore_costs_stop_loss", series_0.clone(), series_1.clone(), costs).unwrap(),
+      generate_golden_case("spread_long_series_1", series_0, series_1, spread).unwrap()
+    ]
+  }
+
+  #[tokio::test]
+  #[ignore] // run with --ignored to regenerate the golden files after an intended change
+  async fn regenerate_golden_files() {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/backtest/golden");
+    for case in canonical_cases() {
+      let json: String = serde_json::to_string_pretty(&case).unwrap();
+      std::fs::write(dir.join(format!("{}.json", case.name)), json).unwrap();
+    }
+  }
+
+  #[tokio::test]
+  async fn it_matches_golden_files() {
+    for result in verify_golden().unwrap() {
+      assert!(result.passed(), "golden mismatch: {:?}", result);
+    }
+  }
+}
@@ -0,0 +1,166 @@
+use crate::SmartError;
+use crate::presets::DEFAULT_TRADING_DAYS;
+use crate::pricing::calendar::MarketCalendar;
+use crate::pricing::models::Exchange;
+use crate::pricing::translate::{format_symbol, preferred_quote, SymbolParts};
+use crate::stats::metrics::rolling_zscore;
+use crate::stats::models::Relationship;
+use crate::stats::statistics::calculate_relaitonship;
+use super::models::{BacktestCriteria, LongSeries, Relation, SignalSource, SignalThresholds};
+
+/*
+  Index Hedge
+  Trades series_0 against series_1 as a market index (BTC for crypto, SPY for US equities) hedged by its market beta
+  The signal is the zscore of the beta hedged log price residual, i.e. single name mean reversion rather than a classic pair
+*/
+
+/// Index held against US equities
+const EQUITY_INDEX: &str = "SPY";
+
+/// Base asset of the index held against crypto
+const CRYPTO_INDEX_BASE: &str = "BTC";
+
+/// Market Index Symbol
+/// Index ticker on the exchange for the asset class of a symbol (None for forex, which has no tradable index)
+pub fn market_index_symbol(exchange: &Exchange, symbol: &str) -> Option<String> {
+  match MarketCalendar::infer(exchange, symbol) {
+    MarketCalendar::UsEquity => Some(EQUITY_INDEX.to_string()),
+    MarketCalendar::Continuous => {
+      let parts: SymbolParts = SymbolParts { base: CRYPTO_INDEX_BASE.to_string(), quote: preferred_quote(exchange) };
+      Some(format_symbol(exchange, &parts))
+    },
+    MarketCalendar::Forex => None
+  }
+}
+
+/// Market Beta
+/// Beta of series_0 to the index in series_1 over the full sample (Relationship beta_y_to_x)
+pub fn market_beta(series_0: &[f64], series_1: &[f64]) -> Result<f64, SmartError> {
+  let relationship: Relationship = calculate_relaitonship(series_0, series_1, DEFAULT_TRADING_DAYS)?;
+
+  // Guard: Ensure beta usable (a flat index has no variance)
+  if !relationship.beta_y_to_x.is_finite() {
+    return Err(SmartError::RuntimeCheck("Market beta is undefined for a constant index series".to_string()));
+  }
+
+  Ok(relationship.beta_y_to_x)
+}
+
+/// Beta Residual
+/// Log price of series_0 less beta times the log price of the index
+pub fn beta_residual(series_0: &[f64], series_1: &[f64], beta: f64) -> Result<Vec<f64>, SmartError> {
+
+  // Guard: Ensure correct lengths
+  if series_0.len() != series_1.len() {
+    let e: String = format!("Series lengths do not match (series_0: {}, series_1: {})", series_0.len(), series_1.len());
+    return Err(SmartError::RuntimeCheck(e));
+  }
+
+  // Guard: Ensure prices positive
+  if let Some(idx) = series_0.iter().chain(series_1.iter()).position(|p| !p.is_finite() || *p <= 0.0) {
+    return Err(SmartError::RuntimeCheck(format!("Price at index {} must be positive to take logs", idx % series_0.len().max(1))));
+  }
+
+  Ok(series_0.iter().zip(series_1.iter()).map(|(p0, p1)| p0.ln() - beta * p1.ln()).collect())
+}
+
+/// Index Hedge Signal Source
+/// Rolling zscore of the beta residual as a signal source (a rich residual shorts series_0 against the index)
+pub fn index_hedge_signal_source(
+  series_0: &[f64],
+  series_1: &[f64],
+  beta: f64,
+  window: usize,
+  thresholds: SignalThresholds
+) -> Result<SignalSource, SmartError> {
+  let residual: Vec<f64> = beta_residual(series_0, series_1, beta)?;
+  let zscore: Vec<f64> = rolling_zscore(&residual, window)?;
+  Ok(SignalSource::new(&format!("index_residual_zscore_{}", window), zscore, thresholds))
+}
+
+/// Index Hedged Criteria
+/// Trades series_0 beta hedged against the index on the residual zscore, keeping costs, stop loss and thresholds from base
+/// The cointegration / correlation gate does not apply to a single name residual so is ignored
+pub fn index_hedged_criteria(
+  series_0: &[f64],
+  series_1: &[f64],
+  window: usize,
+  base: BacktestCriteria
+) -> Result<BacktestCriteria, SmartError> {
+  let beta: f64 = market_beta(series_0, series_1)?;
+  let thresholds: SignalThresholds = SignalThresholds {
+    long: base.long_thresh,
+    long_close: base.long_close_thresh,
+    short: base.short_thresh,
+    short_close: base.short_close_thresh
+  };
+  let source: SignalSource = index_hedge_signal_source(series_0, series_1, beta, window, thresholds)?;
+  Ok(BacktestCriteria {
+    relation: Relation::Ignore,
+    long_series: LongSeries::Series0,
+    index_beta: Some(beta),
+    ..source.into_criteria(base)
+  })
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::backtest::evaluation::BacktestMetrics;
+  use crate::backtest::models::Backtest;
+  use crate::backtest::utils::log_returns;
+  use crate::prelude::default_backtest_criteria;
+
+  fn index_and_stock(len: usize, beta: f64) -> (Vec<f64>, Vec<f64>) {
+    let index: Vec<f64> = (0..len).map(|i| 100.0 * (0.01 * (i as f64 * 0.7).sin() + 0.001 * i as f64).exp()).collect();
+    let stock: Vec<f64> = index.iter().enumerate()
+      .map(|(i, p)| 50.0 * p.powf(beta) / 100f64.powf(beta) * (0.02 * (i as f64 * 0.3).cos()).exp())
+      .collect();
+    (stock, index)
+  }
+
+  #[tokio::test]
+  async fn it_selects_market_index() {
+    assert_eq!(market_index_symbol(&Exchange::Yahoo, "AAPL"), Some("SPY".to_string()));
+    assert_eq!(market_index_symbol(&Exchange::Binance, "ETHUSDT"), Some("BTCUSDT".to_string()));
+    assert_eq!(market_index_symbol(&Exchange::Coinbase, "ETH-USD"), Some("BTC-USD".to_string()));
+    assert_eq!(market_index_symbol(&Exchange::Twelve, "EUR/USD"), None);
+  }
+
+  #[tokio::test]
+  async fn it_removes_index_beta_from_residual() {
+    let (stock, index) = index_and_stock(200, 1.5);
+    let beta: f64 = market_beta(&stock, &index).unwrap();
+    assert!(beta > 1.0 && beta < 2.0);
+
+    let residual: Vec<f64> = beta_residual(&stock, &index, 1.5).unwrap();
+    let expected: Vec<f64> = (0..200).map(|i| 50f64.ln() - 1.5 * 100f64.ln() + 0.02 * (i as f64 * 0.3).cos()).collect();
+    assert!(residual.iter().zip(expected.iter()).all(|(r, e)| (r - e).abs() < 1e-9));
+    assert!(beta_residual(&stock, &index[1..], 1.5).is_err());
+  }
+
+  #[tokio::test]
+  async fn it_backtests_index_hedged_series() {
+    let (stock, index) = index_and_stock(300, 1.5);
+    let base: BacktestCriteria = default_backtest_criteria(vec![0.0; 300]);
+    let criteria: BacktestCriteria = index_hedged_criteria(&stock, &index, 10, base).unwrap();
+    let beta: f64 = criteria.index_beta.unwrap();
+    assert_eq!(criteria.relation, Relation::Ignore);
+    assert_eq!(criteria.indicator_values.len(), 300);
+
+    // Index leg carries beta times the exposure of series_0
+    let backtest: Backtest = Backtest::new(&stock, &index, criteria).unwrap();
+    let signals: Vec<i32> = vec![1; 300];
+    let (_, _, leg_pnl) = backtest.strategy_returns(signals, vec![0.0; 300]);
+    let rets_0: Vec<f64> = log_returns(&stock, true);
+    let rets_1: Vec<f64> = log_returns(&index, true);
+    let expected_0: f64 = rets_0.iter().sum::<f64>() * 2.0 / (1.0 + beta);
+    let expected_1: f64 = -rets_1.iter().sum::<f64>() * 2.0 * beta / (1.0 + beta);
+    assert!((leg_pnl.leg_0_total - expected_0).abs() < 1e-4);
+    assert!((leg_pnl.leg_1_total - expected_1).abs() < 1e-4);
+
+    let metrics: BacktestMetrics = backtest.run_backtest().unwrap();
+    assert!(metrics.win_rate_stats.opened > 0);
+  }
+}
@@ -0,0 +1,57 @@
+use std::fmt;
+use std::sync::Arc;
+
+/*
+  Bar Hooks
+  Optional user logic called on every bar of signal generation
+  Can veto or force entries and exits for strategy rules the criteria struct cannot express
+*/
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarContext {
+  pub bar: usize,
+  pub series_0: f64,
+  pub series_1: f64,
+  pub indicator: f64,
+  pub position: i32, // 1 long, -1 short, 0 flat (before this bar's action)
+  pub tracked_profit: f64, // open trade return so far
+  pub is_long_trigger: bool,
+  pub is_short_trigger: bool,
+  pub is_close_trigger: bool
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HookAction {
+  Continue, // keep the criteria decision
+  Veto, // ignore any entry or exit triggered on this bar
+  ForceLong, // open long if flat
+  ForceShort, // open short if flat
+  ForceClose // close if open
+}
+
+pub trait BarHook: Send + Sync {
+  fn on_bar(&self, ctx: &BarContext) -> HookAction;
+}
+
+impl<F> BarHook for F where F: Fn(&BarContext) -> HookAction + Send + Sync {
+  fn on_bar(&self, ctx: &BarContext) -> HookAction {
+    self(ctx)
+  }
+}
+
+/// On Bar Hook
+/// Shareable hook attached to a backtest
+#[derive(Clone)]
+pub struct OnBarHook(pub Arc<dyn BarHook>);
+
+impl OnBarHook {
+  pub fn new(hook: impl BarHook + 'static) -> Self {
+    Self(Arc::new(hook))
+  }
+}
+
+impl fmt::Debug for OnBarHook {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("OnBarHook")
+  }
+}
@@ -1,3 +1,6 @@
 pub mod evaluation;
+pub mod fixtures;
+pub mod hedge;
+pub mod hooks;
 pub mod models;
 pub mod utils;
\ No newline at end of file
@@ -1,10 +1,14 @@
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
 use crate::SmartError;
+use crate::metrics::record_backtest;
+use crate::pricing::fees::{fee_schedule, FeeSide};
+use crate::pricing::models::Exchange;
 use crate::stats::metrics::{cointegration_test_eg, pearson_correlation_coefficient};
-use crate::stats::models::Coint;
-use super::evaluation::{Evaluation, BacktestMetrics};
+use super::evaluation::{Evaluation, EvaluationConfig, BacktestMetrics, LegPnl};
+use super::hooks::{BarContext, BarHook, HookAction, OnBarHook};
 use super::utils::log_returns;
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
@@ -18,7 +22,71 @@ pub enum LongSeries {
 #[ts(export)]
 pub enum TriggerIndicator {
   Zscore,
-  Spread
+  Spread,
+  TimeframeGatedZscore, // Entry zscore gated by the direction of a coarser timeframe zscore
+  Custom(String) // Named user supplied indicator (see SignalSource)
+}
+
+/// Signal Timing
+/// When a signal decided at a bar close is filled (pair prices carry closes only, so the next open is taken as
+/// the signal bar close, which is exact for markets trading around the clock)
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default, TS)]
+#[ts(export)]
+pub enum SignalTiming {
+  #[default]
+  SameClose, // filled at the close of the signal bar
+  NextOpen, // filled at the open of the next bar
+  NextClose // filled at the close of the next bar
+}
+
+impl SignalTiming {
+
+  /// Delays
+  /// Bars from the signal to the bar the fill (and its fee) lands in, and to the first bar the position is held
+  pub fn delays(&self) -> (usize, usize) {
+    match self {
+      Self::SameClose => (0, 1),
+      Self::NextOpen => (1, 1),
+      Self::NextClose => (1, 2)
+    }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct SignalThresholds {
+  pub long: f64,
+  pub long_close: f64,
+  pub short: f64,
+  pub short_close: f64
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct SignalSource {
+  pub name: String,
+  pub values: Vec<f64>,
+  pub thresholds: SignalThresholds
+}
+
+impl SignalSource {
+  pub fn new(name: &str, values: Vec<f64>, thresholds: SignalThresholds) -> Self {
+    Self { name: name.to_string(), values, thresholds }
+  }
+
+  /// Into Criteria
+  /// Applies the indicator values and thresholds on top of base criteria (costs, weighting, stop loss etc)
+  pub fn into_criteria(self, base: BacktestCriteria) -> BacktestCriteria {
+    BacktestCriteria {
+      indicator_values: self.values,
+      trigger_indicator: TriggerIndicator::Custom(self.name),
+      long_thresh: self.thresholds.long,
+      long_close_thresh: self.thresholds.long_close,
+      short_thresh: self.thresholds.short,
+      short_close_thresh: self.thresholds.short_close,
+      ..base
+    }
+  }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
@@ -29,6 +97,108 @@ pub enum Relation {
   Ignore
 }
 
+/// Bars before each bar tested by the cointegration and correlation gates
+pub const RELATION_WINDOW: usize = 90;
+
+/// Absolute correlation required by the correlation gate
+pub const RELATION_CORR_THRESH: f64 = 0.8;
+
+impl Relation {
+
+  /// Gate Series
+  /// Whether the relation holds at each bar over the window of bars before it (false until a full window exists)
+  /// Computed once per backtest (windows in parallel) rather than re-testing inside the signal loop
+  pub fn gate_series(&self, series_0: &[f64], series_1: &[f64]) -> Result<Vec<bool>, SmartError> {
+    let len: usize = series_0.len().min(series_1.len());
+    (0..len).into_par_iter()
+      .map(|i| {
+        if i < RELATION_WINDOW { return Ok(matches!(self, Relation::Ignore)) }
+        let series_0_i: Vec<f64> = series_0[i-RELATION_WINDOW..i].to_vec();
+        let series_1_i: Vec<f64> = series_1[i-RELATION_WINDOW..i].to_vec();
+        match self {
+          Relation::Coint => Ok(cointegration_test_eg(&series_0_i, &series_1_i)?.is_coint),
+          Relation::Corr => Ok(pearson_correlation_coefficient(&series_0_i, &series_1_i)?.abs() >= RELATION_CORR_THRESH),
+          Relation::Ignore => Ok(true)
+        }
+      })
+      .collect()
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub enum Comparison {
+  Above,
+  Below
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct Condition {
+  pub name: String,
+  pub values: Vec<f64>, // one value per bar
+  pub comparison: Comparison,
+  pub threshold: f64
+}
+
+impl Condition {
+  pub fn above(name: &str, values: Vec<f64>, threshold: f64) -> Self {
+    Self { name: name.to_string(), values, comparison: Comparison::Above, threshold }
+  }
+
+  pub fn below(name: &str, values: Vec<f64>, threshold: f64) -> Self {
+    Self { name: name.to_string(), values, comparison: Comparison::Below, threshold }
+  }
+}
+
+/// Entry Condition
+/// Boolean combination of per bar conditions (e.g. zscore below -2 AND corr above 0.7)
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub enum EntryCondition {
+  Condition(Condition),
+  All(Vec<EntryCondition>),
+  Any(Vec<EntryCondition>),
+  Not(Box<EntryCondition>)
+}
+
+impl EntryCondition {
+
+  /// Evaluate
+  /// Whether the condition holds at the given bar (NaN values never satisfy a comparison)
+  pub fn evaluate(&self, bar: usize) -> bool {
+    match self {
+      Self::Condition(c) => match (c.values.get(bar), &c.comparison) {
+        (Some(v), Comparison::Above) => *v > c.threshold,
+        (Some(v), Comparison::Below) => *v < c.threshold,
+        (None, _) => false
+      },
+      Self::All(conditions) => conditions.iter().all(|c| c.evaluate(bar)),
+      Self::Any(conditions) => conditions.iter().any(|c| c.evaluate(bar)),
+      Self::Not(condition) => !condition.evaluate(bar)
+    }
+  }
+
+  /// Validate
+  /// Every condition must supply one finite or NaN value per bar
+  pub fn validate(&self, series_len: usize) -> Result<(), SmartError> {
+    match self {
+      Self::Condition(c) => {
+        if c.values.len() != series_len {
+          let e: String = format!("Condition {} values length ({}) must equal series length ({})", c.name, c.values.len(), series_len);
+          return Err(SmartError::RuntimeCheck(e));
+        }
+        if let Some(idx) = c.values.iter().position(|v| v.is_infinite()) {
+          return Err(SmartError::RuntimeCheck(format!("Condition {} value at index {} is infinite", c.name, idx)));
+        }
+        Ok(())
+      },
+      Self::All(conditions) | Self::Any(conditions) => conditions.iter().try_for_each(|c| c.validate(series_len)),
+      Self::Not(condition) => condition.validate(series_len)
+    }
+  }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, TS)]
 #[ts(export)]
 pub struct BacktestCriteria {
@@ -42,24 +212,140 @@ pub struct BacktestCriteria {
   pub long_thresh: f64,
   pub long_close_thresh: f64,
   pub short_thresh: f64,
-  pub short_close_thresh: f64
+  pub short_close_thresh: f64,
+  #[serde(default)]
+  pub long_entry: Option<EntryCondition>, // replaces the long threshold and relation gate when set
+  #[serde(default)]
+  pub short_entry: Option<EntryCondition>, // replaces the short threshold and relation gate when set
+  #[serde(default)]
+  pub index_beta: Option<f64>, // hedges series_0 against series_1 as a market index by beta (replaces rets_weighting_s0_perc, see hedge)
+  #[serde(default)]
+  pub exchange: Option<Exchange>, // fee schedule used when cost_per_leg is None
+  #[serde(default)]
+  pub fee_side: FeeSide,
+  #[serde(default)]
+  pub warmup_bars: Option<usize>, // bars at the start without a valid indicator (None detects the zscore padding, see warmup_len)
+  #[serde(default)]
+  pub signal_timing: SignalTiming
+}
+
+impl BacktestCriteria {
+
+  /// Effective Cost Per Leg
+  /// Explicit cost if set, otherwise the exchange fee for the fee side (zero only when neither is known)
+  pub fn effective_cost_per_leg(&self) -> f64 {
+    self.cost_per_leg
+      .or_else(|| self.exchange.as_ref().and_then(fee_schedule).map(|fees| fees.cost_per_leg(self.fee_side)))
+      .unwrap_or(0.0)
+  }
+
+  /// Warmup Len
+  /// Bars at the start excluded from signals and metrics, the configured count or else the leading zscore padding
+  /// (zeros or non finite values, see rolling_zscore), capped so at least 2 bars are evaluated
+  pub fn warmup_len(&self) -> usize {
+    let len: usize = self.indicator_values.len();
+    let bars: usize = match (self.warmup_bars, &self.trigger_indicator) {
+      (Some(bars), _) => bars,
+      (None, TriggerIndicator::Zscore | TriggerIndicator::TimeframeGatedZscore) => {
+        let padding: usize = self.indicator_values.iter().take_while(|v| **v == 0.0 || !v.is_finite()).count();
+        if padding == len { 0 } else { padding } // an all zero series is not padding
+      },
+      (None, _) => 0
+    };
+    bars.min(len.saturating_sub(2))
+  }
+
+  /// Validate
+  /// Checks the criteria against the series length, returning a descriptive error for the first problem found
+  pub fn validate(&self, series_len: usize) -> Result<(), SmartError> {
+    let fail = |msg: String| Err(SmartError::RuntimeCheck(msg));
+
+    // Guard: Ensure correct lengths
+    if self.indicator_values.len() != series_len {
+      return fail(format!("Indicator values length ({}) must equal series length ({})", self.indicator_values.len(), series_len));
+    }
+    if series_len < 2 {
+      return fail(format!("At least 2 bars are required to backtest, got {}", series_len));
+    }
+
+    // Guard: Ensure correct thresholds
+    if self.long_thresh > self.short_thresh {
+      return fail(format!("long_thresh ({}) must be <= short_thresh ({})", self.long_thresh, self.short_thresh));
+    }
+    if self.long_close_thresh < self.long_thresh {
+      return fail(format!("long_close_thresh ({}) must be >= long_thresh ({})", self.long_close_thresh, self.long_thresh));
+    }
+    if self.short_close_thresh > self.short_thresh {
+      return fail(format!("short_close_thresh ({}) must be <= short_thresh ({})", self.short_close_thresh, self.short_thresh));
+    }
+
+    // Guard: Ensure weighting and costs in range
+    if !(0.0..=1.0).contains(&self.rets_weighting_s0_perc) {
+      return fail(format!("rets_weighting_s0_perc ({}) must be between 0 and 1", self.rets_weighting_s0_perc));
+    }
+    if let Some(beta) = self.index_beta {
+      if !beta.is_finite() {
+        return fail(format!("index_beta ({}) must be a finite number", beta));
+      }
+    }
+    if let Some(cost) = self.cost_per_leg {
+      if !cost.is_finite() || cost < 0.0 {
+        return fail(format!("cost_per_leg ({}) must be a non negative number", cost));
+      }
+    }
+
+    if let Some(bars) = self.warmup_bars {
+      if bars + 2 > series_len {
+        return fail(format!("warmup_bars ({}) must leave at least 2 bars to backtest (series length {})", bars, series_len));
+      }
+    }
+
+    // Guard: Ensure indicator values usable
+    if let Some(idx) = self.indicator_values.iter().position(|v| v.is_infinite()) {
+      return fail(format!("Indicator value at index {} is infinite", idx));
+    }
+
+    // Guard: Ensure entry conditions usable
+    for entry in [&self.long_entry, &self.short_entry].into_iter().flatten() {
+      entry.validate(series_len)?;
+    }
+
+    Ok(())
+  }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, TS)]
 #[ts(export)]
 pub struct WinRate {
-  pub win_rate: f64,
+  pub win_rate: f64, // zero when no trades closed
   pub opened: u32,
   pub closed: u32,
-  pub closed_profit: u32
+  pub closed_profit: u32,
+  #[serde(default)]
+  pub has_trades: bool // any trade opened (see BacktestOutcome)
+}
+
+impl WinRate {
+  pub fn new(opened: u32, closed: u32, closed_profit: u32) -> Self {
+    let win_rate: f64 = if closed == 0 { 0.0 } else { closed_profit as f64 / closed as f64 };
+    Self { win_rate, opened, closed, closed_profit, has_trades: opened > 0 }
+  }
 }
 
+/// Signals, trading costs, win rate and closed bar flags
+pub type SignalOutput = (Vec<i32>, Vec<f64>, WinRate, Vec<u64>);
+
 #[derive(Debug)]
 pub struct Backtest {
   pub series_0: Vec<f64>, 
   pub series_1: Vec<f64>, 
   pub series_0_mul: f64, // for determining long or short
-  pub bt_criteria: BacktestCriteria
+  pub bt_criteria: BacktestCriteria,
+  pub eval_config: EvaluationConfig,
+  pub excluded_bars: Vec<bool>, // no positions held on excluded bars (e.g. around listings or forks)
+  pub on_bar: Option<OnBarHook>,
+  pub funding: Vec<f64>, // funding settled per bar on series_0 (perp), positive paid by longs
+  pub relation_gate: Vec<bool> // precomputed relation gate per bar (empty computes it from the criteria relation)
 }
 
 impl Backtest {
@@ -67,56 +353,152 @@ impl Backtest {
     series_0: &Vec<f64>, 
     series_1: &Vec<f64>, 
     bt_criteria: BacktestCriteria
-  ) -> Self {
+  ) -> Result<Self, SmartError> {
 
     // Guard: Ensure correct lengths
-    assert_eq!(series_0.len(), series_1.len());
-    assert_eq!(series_0.len(), bt_criteria.indicator_values.len());
+    if series_0.len() != series_1.len() {
+      let e: String = format!("Series lengths do not match (series_0: {}, series_1: {})", series_0.len(), series_1.len());
+      return Err(SmartError::RuntimeCheck(e));
+    }
 
-    // Guard: Ensure correct thresholds
-    assert!(bt_criteria.long_thresh <= bt_criteria.short_thresh);
-    assert!(bt_criteria.long_close_thresh >= bt_criteria.long_thresh);
-    assert!(bt_criteria.short_close_thresh <= bt_criteria.short_thresh);
+    // Guard: Ensure valid criteria
+    bt_criteria.validate(series_0.len())?;
 
     // Series 0 multiplication factor
     let series_0_mul: f64 = if bt_criteria.long_series == LongSeries::Series0 { 1.0 } else { -1.0 };
 
-    Self {
+    Ok(Self {
       series_0: series_0.clone(),
       series_1: series_1.clone(),
       series_0_mul,
-      bt_criteria
+      bt_criteria,
+      eval_config: EvaluationConfig::default(),
+      excluded_bars: vec![],
+      on_bar: None,
+      funding: vec![],
+      relation_gate: vec![]
+    })
+  }
+
+  /// With Evaluation Config
+  /// Sets the risk free rate / benchmark hurdle used for Sharpe and Sortino
+  pub fn with_evaluation_config(mut self, eval_config: EvaluationConfig) -> Self {
+    self.eval_config = eval_config;
+    self
+  }
+
+  /// With On Bar Hook
+  /// Calls the hook on every bar (after the criteria triggers are evaluated) to veto or force entries and exits
+  pub fn with_on_bar(mut self, hook: impl BarHook + 'static) -> Self {
+    self.on_bar = Some(OnBarHook::new(hook));
+    self
+  }
+
+  /// With Excluded Bars
+  /// Bars on which no position may be opened and any open position is closed (see PairPrices::excluded_bars)
+  pub fn with_excluded_bars(mut self, excluded_bars: Vec<bool>) -> Result<Self, SmartError> {
+
+    // Guard: Ensure correct length
+    if !excluded_bars.is_empty() && excluded_bars.len() != self.series_0.len() {
+      let e: String = format!("Excluded bars length ({}) must equal series length ({})", excluded_bars.len(), self.series_0.len());
+      return Err(SmartError::RuntimeCheck(e));
+    }
+
+    self.excluded_bars = excluded_bars;
+    Ok(self)
+  }
+
+  /// With Funding
+  /// Funding settled per bar on the series_0 perpetual (see FundingRates::payments_per_bar)
+  /// Held positions pay or receive funding in proportion to their series_0 weighting
+  pub fn with_funding(mut self, funding: Vec<f64>) -> Result<Self, SmartError> {
+
+    // Guard: Ensure correct length
+    if !funding.is_empty() && funding.len() != self.series_0.len() {
+      let e: String = format!("Funding length ({}) must equal series length ({})", funding.len(), self.series_0.len());
+      return Err(SmartError::RuntimeCheck(e));
+    }
+
+    self.funding = funding;
+    Ok(self)
+  }
+
+  /// With Relation Gate
+  /// Supplies the relation gate per bar (see Relation::gate_series) so repeated backtests of a pair
+  /// with different thresholds share one cointegration / correlation pass
+  pub fn with_relation_gate(mut self, relation_gate: Vec<bool>) -> Result<Self, SmartError> {
+
+    // Guard: Ensure correct length
+    if !relation_gate.is_empty() && relation_gate.len() != self.series_0.len() {
+      let e: String = format!("Relation gate length ({}) must equal series length ({})", relation_gate.len(), self.series_0.len());
+      return Err(SmartError::RuntimeCheck(e));
+    }
+
+    self.relation_gate = relation_gate;
+    Ok(self)
+  }
+
+  /// Is Vectorizable
+  /// Whether signals depend on no path state beyond the open position (no stop loss or on bar hook)
+  pub fn is_vectorizable(&self) -> bool {
+    self.on_bar.is_none() && self.bt_criteria.stop_loss == 0.0
+  }
+
+  /// Evaluation Range
+  /// Bars signals may open on and metrics are calculated over (everything after the warm-up)
+  pub fn evaluation_range(&self) -> std::ops::Range<usize> {
+    self.bt_criteria.warmup_len()..self.series_0.len()
+  }
+
+  /// Warmup Bars
+  /// Marks the warm-up bars, on which no position is opened
+  pub fn warmup_bars(&self) -> Vec<bool> {
+    let warmup: usize = self.bt_criteria.warmup_len();
+    (0..self.series_0.len()).map(|i| i < warmup).collect()
+  }
+
+  /// Relation Gate
+  /// Per bar relation check (supplied or computed once), empty when relation is ignored
+  fn relation_gate(&self) -> Result<Vec<bool>, SmartError> {
+    match (&self.bt_criteria.relation, self.relation_gate.is_empty()) {
+      (Relation::Ignore, _) => Ok(vec![]),
+      (relation, true) => relation.gate_series(&self.series_0, &self.series_1),
+      (_, false) => Ok(self.relation_gate.clone())
     }
   }
 
   /// Create Signals
   /// Generates Signals and Relevant Baktest Information
-  fn create_signals(&self) -> Result<(Vec<i32>, Vec<f64>, WinRate, Vec<u64>), SmartError> {
+  /// Uses the vectorized path where criteria allow, falling back to the bar by bar loop otherwise
+  pub fn create_signals(&self) -> Result<SignalOutput, SmartError> {
+    if self.is_vectorizable() { self.create_signals_vectorized() } else { self.create_signals_loop() }
+  }
+
+  /// Create Signals Loop
+  /// Bar by bar signal generation supporting stop losses and on bar hooks
+  pub fn create_signals_loop(&self) -> Result<SignalOutput, SmartError> {
 
     // Initialize
     let mut is_open: bool = false;
     let mut last: i32 = 0;
-    let mut signals: Vec<i32> = vec![0];
-    let mut trading_open_costs: Vec<f64> = vec![0.0];
-    let mut trading_close_costs: Vec<f64> = vec![0.0];
+    let mut positions: Vec<i32> = vec![0];
+    let mut opens: Vec<usize> = vec![];
+    let mut closes: Vec<usize> = vec![];
 
     let mut tracked_profit: f64 = 0.0;
-    let mut opened: u32 = 0;
-    let mut closed: u32 = 0;
     let mut closed_profit: u32 = 0;
 
-    let mut closed_ones: Vec<u64> = vec![0];
+    // Relation gate per bar (supplied or computed once)
+    let relation_gate: Vec<bool> = self.relation_gate()?;
 
-    let rolling_window: usize = 90; // used for cointegration check
-    let corr_thresh: f64 = 0.8; // used for correlation check
-
-    let cost_per_leg: f64 = match self.bt_criteria.cost_per_leg { Some(c) => c, None => 0.0 };
+    let cost_per_leg: f64 = self.bt_criteria.effective_cost_per_leg();
+    let warmup: usize = self.bt_criteria.warmup_len();
 
     for i in 1..self.bt_criteria.indicator_values.len() {
-      closed_ones.push(0);
 
       // Extract Indicator Value
       let ind_val: f64 = self.bt_criteria.indicator_values[i];
+      let is_excluded: bool = self.excluded_bars.get(i).copied().unwrap_or(false);
 
       // Handle Returns Calc (helps check if profit for win rate) - important THIS IS LAGGED (whereas signal lags later on in the function)
       let mut ser_0_ret = 0.0;
@@ -129,36 +511,21 @@ impl Backtest {
       // Confirm Long and Short Open Triggers
       let mut is_long_trigger: bool = false;
       let mut is_short_trigger: bool = false;
-      if !is_open {
-
-        let is_relation = match &self.bt_criteria.relation {
-          Relation::Coint => {
-            if i >= rolling_window {
-              let series_0_i: &Vec<f64> = &self.series_0[i-rolling_window..i].to_vec();
-              let series_1_i: &Vec<f64> = &self.series_1[i-rolling_window..i].to_vec();
-              let coint: Coint = cointegration_test_eg(series_0_i, series_1_i)?;
-              coint.is_coint
-            } else {
-              false
-            }
-          },
-          Relation::Corr => {
-            if i >= rolling_window {
-              let series_0_i: &Vec<f64> = &self.series_0[i-rolling_window..i].to_vec();
-              let series_1_i: &Vec<f64> = &self.series_1[i-rolling_window..i].to_vec();
-              let corr: f64 = pearson_correlation_coefficient(series_0_i, series_1_i)?;
-              corr.abs() >= corr_thresh
-            } else {
-              false
-            }
-          },
-          Relation::Ignore => true
+      if !is_open && !is_excluded {
+
+        let is_relation: bool = match &self.bt_criteria.relation {
+          Relation::Ignore => true,
+          _ => relation_gate.get(i).copied().unwrap_or(false)
         };
 
         if is_relation {
           if ind_val <= self.bt_criteria.long_thresh { is_long_trigger = true; }
           if ind_val >= self.bt_criteria.short_thresh { is_short_trigger = true; }
         }
+
+        // Composite entry conditions replace the threshold and relation gate
+        if let Some(entry) = &self.bt_criteria.long_entry { is_long_trigger = entry.evaluate(i); }
+        if let Some(entry) = &self.bt_criteria.short_entry { is_short_trigger = entry.evaluate(i); }
       }
       
       // Confirm Long and Short Close Triggers
@@ -176,18 +543,56 @@ impl Backtest {
             is_short_close_trigger = true;
           }
         }
+
+        // Handle excluded bars
+        if is_excluded {
+          is_long_close_trigger = true;
+          is_short_close_trigger = true;
+        }
+      }
+
+      // Apply user hook
+      if let Some(hook) = &self.on_bar {
+        let ctx: BarContext = BarContext {
+          bar: i,
+          series_0: self.series_0[i],
+          series_1: self.series_1[i],
+          indicator: ind_val,
+          position: if is_open { last } else { 0 },
+          tracked_profit,
+          is_long_trigger,
+          is_short_trigger,
+          is_close_trigger: is_long_close_trigger || is_short_close_trigger
+        };
+        match hook.0.on_bar(&ctx) {
+          HookAction::Continue => {},
+          HookAction::Veto => {
+            is_long_trigger = false;
+            is_short_trigger = false;
+            is_long_close_trigger = false;
+            is_short_close_trigger = false;
+          },
+          HookAction::ForceLong if !is_open => { is_long_trigger = true; is_short_trigger = false; },
+          HookAction::ForceShort if !is_open => { is_long_trigger = false; is_short_trigger = true; },
+          HookAction::ForceClose if is_open => { is_long_close_trigger = true; is_short_close_trigger = true; },
+          _ => {}
+        }
+      }
+
+      // Nothing opens during the warm-up (the indicator is padding)
+      if i < warmup {
+        is_long_trigger = false;
+        is_short_trigger = false;
       }
 
       // Open Long
       if is_long_trigger {
         is_open = true;
         last = 1;
-        signals.push(1);
-        trading_open_costs.push(cost_per_leg * 2.0);
-        trading_close_costs.push(0.0);
+        positions.push(1);
+        opens.push(i);
 
         tracked_profit = -cost_per_leg * 2.0;
-        opened += 1;
         continue;
       }
 
@@ -195,12 +600,10 @@ impl Backtest {
       if is_short_trigger {
         is_open = true;
         last = -1;
-        signals.push(-1);
-        trading_open_costs.push(cost_per_leg * 2.0);
-        trading_close_costs.push(0.0);
+        positions.push(-1);
+        opens.push(i);
 
         tracked_profit = -cost_per_leg * 2.0;
-        opened += 1;
         continue;
       }
 
@@ -209,16 +612,12 @@ impl Backtest {
         is_open = false;
         
         last = 0;
-        signals.push(0);
-        trading_close_costs.push(cost_per_leg * 2.0);
-        trading_open_costs.push(0.0);
+        positions.push(0);
+        closes.push(i);
         
         // tracked_profit += -cost_per_leg * 2.0;
         if tracked_profit > 0.0 { closed_profit += 1; } 
         tracked_profit = 0.0;
-        closed += 1;
-
-        closed_ones[i] = 1;
         continue;
       }
 
@@ -229,44 +628,130 @@ impl Backtest {
         tracked_profit = 0.0;
       }
 
-      // Update Signals and Costs
-      signals.push(last);
-      trading_open_costs.push(0.0);
-      trading_close_costs.push(0.0);
+      // Update Positions
+      positions.push(last);
+    }
+
+    // Apply execution timing to signals and costs
+    Ok(self.time_signals(&positions, &opens, &closes, closed_profit))
+  }
+
+  /// Create Signals Vectorized
+  /// Builds entry and exit masks over the whole indicator array, then resolves positions in a single scan
+  /// Only valid for criteria without path dependent state beyond the position (see is_vectorizable)
+  pub fn create_signals_vectorized(&self) -> Result<SignalOutput, SmartError> {
+
+    // Guard: Ensure criteria carry no path dependent state
+    if !self.is_vectorizable() {
+      return Err(SmartError::RuntimeCheck("Vectorized signals do not support stop losses or on bar hooks".to_string()));
     }
 
-    // Shift signals by 1 to avoid lookahead bias
-    if let Some(_) = signals.pop() { signals.insert(0, 0); }
-    if let Some(_) = trading_open_costs.pop() { trading_open_costs.insert(0, 0.0); }
+    let len: usize = self.bt_criteria.indicator_values.len().max(1);
+    let relation_gate: Vec<bool> = self.relation_gate()?;
+    let cost_per_leg: f64 = self.bt_criteria.effective_cost_per_leg();
+    let warmup: usize = self.bt_criteria.warmup_len();
+    let c: &BacktestCriteria = &self.bt_criteria;
 
-    // Combine trading costs for open and close fees
-    let trading_costs: Vec<f64> = trading_open_costs.iter().zip(trading_close_costs.iter())
-        .map(|(&x, &y)| x + y)
-        .collect();
+    // Entry and exit masks packed per bar (composite entry conditions replace the threshold and relation gate)
+    let masks: Vec<u8> = (0..len).map(|i| {
+      let ind_val: f64 = c.indicator_values.get(i).copied().unwrap_or(f64::NAN);
+      let is_excluded: bool = self.excluded_bars.get(i).copied().unwrap_or(false);
+      let is_relation: bool = matches!(c.relation, Relation::Ignore) || relation_gate.get(i).copied().unwrap_or(false);
+      let is_warmup: bool = i < warmup;
+      let long_open: bool = !is_excluded && !is_warmup && match &c.long_entry {
+        Some(entry) => entry.evaluate(i),
+        None => is_relation && ind_val <= c.long_thresh
+      };
+      let short_open: bool = !is_excluded && !is_warmup && match &c.short_entry {
+        Some(entry) => entry.evaluate(i),
+        None => is_relation && ind_val >= c.short_thresh
+      };
+      let long_close: bool = is_excluded || ind_val >= c.long_close_thresh;
+      let short_close: bool = is_excluded || ind_val <= c.short_close_thresh;
+      long_open as u8 | (short_open as u8) << 1 | (long_close as u8) << 2 | (short_close as u8) << 3
+    }).collect();
 
-    // Structure Win Rate Metrics
-    let mut win_rate: f64 = 0.0;
-    if closed != 0 { win_rate = closed_profit as f64 / closed as f64; }
-    let win_rate_metrics: WinRate = WinRate { win_rate, opened, closed, closed_profit };
+    // Resolve positions (long takes precedence when both entries trigger)
+    let positions: Vec<i32> = masks.iter().enumerate().scan(0, |position, (i, mask)| {
+      if i > 0 {
+        *position = match *position {
+          0 if mask & 1 != 0 => 1,
+          0 if mask & 2 != 0 => -1,
+          1 if mask & 4 != 0 => 0,
+          -1 if mask & 8 != 0 => 0,
+          p => p
+        };
+      }
+      Some(*position)
+    }).collect();
+
+    // Opens and closes are position changes
+    let opens: Vec<usize> = (1..len).filter(|&i| positions[i - 1] == 0 && positions[i] != 0).collect();
+    let closes: Vec<usize> = (1..len).filter(|&i| positions[i - 1] != 0 && positions[i] == 0).collect();
+
+    // Profit tracked over the bars held between each open and close (lagged returns as per the loop)
+    let bar_return = |i: usize| -> f64 {
+      (self.series_0[i + 1] / self.series_0[i]) * self.series_0_mul + (self.series_1[i + 1] / self.series_1[i]) * -self.series_0_mul
+    };
+    let closed_profit: usize = opens.iter().zip(closes.iter())
+      .filter(|(&open, &close)| (open + 1..close).fold(-cost_per_leg * 2.0, |acc, i| acc + bar_return(i)) > 0.0)
+      .count();
 
-    Ok((signals, trading_costs, win_rate_metrics, closed_ones))
+    // Apply execution timing to signals and costs
+    Ok(self.time_signals(&positions, &opens, &closes, closed_profit as u32))
+  }
+
+  /// Time Signals
+  /// Applies the signal timing to positions decided at each bar close and the bars entries and exits were decided on
+  /// Fees are booked on the bar the fill lands in and the position is held from the bar after the fill, so signals,
+  /// open costs, close costs and closed trade flags all move together (fills beyond the last bar never happen)
+  fn time_signals(&self, positions: &[i32], opens: &[usize], closes: &[usize], closed_profit: u32) -> SignalOutput {
+    let len: usize = positions.len();
+    let (fill_delay, hold_lag) = self.bt_criteria.signal_timing.delays();
+    let cost: f64 = self.bt_criteria.effective_cost_per_leg() * 2.0;
+
+    let signals: Vec<i32> = (0..len).map(|k| if k >= hold_lag { positions[k - hold_lag] } else { 0 }).collect();
+    let fills = |bars: &[usize]| -> Vec<usize> { bars.iter().map(|i| i + fill_delay).filter(|f| *f < len).collect() };
+    let (open_fills, close_fills) = (fills(opens), fills(closes));
+
+    let mut trading_costs: Vec<f64> = vec![0.0; len];
+    let mut closed_ones: Vec<u64> = vec![0; len];
+    open_fills.iter().for_each(|&f| trading_costs[f] += cost);
+    close_fills.iter().for_each(|&f| { trading_costs[f] += cost; closed_ones[f] = 1; });
+
+    let closed: u32 = close_fills.len() as u32;
+    let win_rate_metrics: WinRate = WinRate::new(open_fills.len() as u32, closed, closed_profit.min(closed));
+    (signals, trading_costs, win_rate_metrics, closed_ones)
+  }
+
+  /// Signals
+  /// Position per bar (1 long, -1 short, 0 flat) after the one bar lag applied to avoid lookahead
+  pub fn signals(&self) -> Result<Vec<i32>, SmartError> {
+    Ok(self.create_signals()?.0)
   }
 
   /// Strategy Returns
   /// Calculates Returns based on Signals and Trading Costs
-  fn strategy_returns(&self, signals: Vec<i32>, trading_costs: Vec<f64>) -> (Vec<f64>, Vec<f64>) {
+  pub fn strategy_returns(&self, signals: Vec<i32>, trading_costs: Vec<f64>) -> (Vec<f64>, Vec<f64>, LegPnl) {
 
     // Calculate weighting ratio
-    let s0_weighting_rate: f64 = 2.0 * self.bt_criteria.rets_weighting_s0_perc;
-    let s1_weighting_rate: f64 = 2.0 - s0_weighting_rate;
+    // Index hedges hold beta units of series_1 per unit of series_0, scaled to the same gross exposure as a pair
+    let (s0_weighting_rate, s1_weighting_rate) = match self.bt_criteria.index_beta {
+      Some(beta) => (2.0 / (1.0 + beta.abs()), 2.0 * beta / (1.0 + beta.abs())),
+      None => (2.0 * self.bt_criteria.rets_weighting_s0_perc, 2.0 - 2.0 * self.bt_criteria.rets_weighting_s0_perc)
+    };
 
     // Calculate log returns
     let log_rets_0: Vec<f64> = log_returns(&self.series_0, true);
     let log_rets_1: Vec<f64> = log_returns(&self.series_1, true);
     
-    // Calculate strategy log returns - series 0
-    let series_0_r: Vec<f64> = log_rets_0.iter().zip(signals.iter())
-    .map(|(&x, &y)| x * y as f64 * self.series_0_mul * s0_weighting_rate)
+    // Calculate strategy log returns - series 0 (including any funding carry)
+    let series_0_r: Vec<f64> = log_rets_0.iter().zip(signals.iter()).enumerate()
+    .map(|(i, (&x, &y))| {
+      let exposure: f64 = y as f64 * self.series_0_mul * s0_weighting_rate;
+      let funding: f64 = self.funding.get(i).copied().unwrap_or(0.0);
+      (x - funding) * exposure
+    })
     .collect();
   
     // Calculate strategy log returns - series 1
@@ -283,13 +768,18 @@ impl Backtest {
 
     // Adjust net returns for stop loss
     // Net returns also adjusted for stop loss later on
+    let mut leg_0_r: Vec<f64> = series_0_r;
+    let mut leg_1_r: Vec<f64> = series_1_r;
     if self.bt_criteria.stop_loss != 0.0 {
       for i in 0..net_lrets.len() {
         if (net_lrets[i].exp() + 1.0) < self.bt_criteria.stop_loss {
           net_lrets[i] = 0.0;
+          leg_0_r[i] = 0.0;
+          leg_1_r[i] = 0.0;
         }
       }
     }
+    let leg_pnl: LegPnl = LegPnl::new(leg_0_r, leg_1_r, trading_costs.iter().sum());
 
     // Calculate strategy cumulative log returns - net
     let net_cum_rets: Vec<f64> = net_lrets.iter()
@@ -301,33 +791,345 @@ impl Backtest {
       .collect();
 
     // Return output
-    (net_lrets, net_cum_rets)
+    (net_lrets, net_cum_rets, leg_pnl)
   }
 
   /// Run Backtest
   /// Entrypoint for running backtest
   pub fn run_backtest(&self) -> Result<BacktestMetrics, SmartError> {
     let (signals, trading_costs, initial_win_rate, closed_ones) = self.create_signals()?;
-    let (net_lrets, net_cum_rets) = self.strategy_returns(signals, trading_costs);
+    let (net_lrets, net_cum_rets, mut leg_pnl) = self.strategy_returns(signals, trading_costs);
+
+    // Drop the warm-up bars (always flat) so they do not dilute per bar metrics
+    let start: usize = self.evaluation_range().start;
+    let net_lrets: Vec<f64> = net_lrets[start..].to_vec();
+    let net_cum_rets: Vec<f64> = net_cum_rets[start..].to_vec();
+    let closed_ones: Vec<u64> = closed_ones[start..].to_vec();
+    leg_pnl.leg_0_log_returns.drain(..start);
+    leg_pnl.leg_1_log_returns.drain(..start);
+    let mut eval_config: EvaluationConfig = self.eval_config.clone();
+    if let Some(benchmark) = eval_config.benchmark_log_returns.as_mut() {
+      benchmark.drain(..start.min(benchmark.len())); // keep the hurdle aligned with the evaluated bars
+    }
 
     // Force sense check for number of winning trades based on equity curve
     let mut updated_closed_profit = 0;
     let mut trade_ret_cum = 0.0;
+    let mut trade_returns: Vec<f64> = vec![];
     for (i, c) in closed_ones.iter().enumerate() {
       trade_ret_cum += net_lrets[i];
       if *c == 1 {
         if trade_ret_cum > 0.0 { updated_closed_profit += 1; }
+        trade_returns.push(trade_ret_cum);
         trade_ret_cum = 0.0;
       }
     }
 
-    let mut win_rate: f64 = 0.0;
-    if initial_win_rate.closed != 0 { win_rate = updated_closed_profit as f64 / initial_win_rate.closed as f64; }
-    let win_rate_stats: WinRate = WinRate { win_rate, opened: initial_win_rate.opened, closed: initial_win_rate.closed, closed_profit: updated_closed_profit };
+    let win_rate_stats: WinRate = WinRate::new(initial_win_rate.opened, initial_win_rate.closed, updated_closed_profit);
 
     // Run evaluation
-    let evaluation: Evaluation = Evaluation::new(net_lrets, net_cum_rets, win_rate_stats);
-    let eval_metrics: BacktestMetrics = evaluation.run_evaluation_metrics();
+    let evaluation: Evaluation = Evaluation::new(net_lrets, net_cum_rets, win_rate_stats).with_leg_pnl(leg_pnl)
+      .with_trade_returns(trade_returns)
+      .with_config(eval_config);
+    let mut eval_metrics: BacktestMetrics = evaluation.run_evaluation_metrics();
+    eval_metrics.evaluation_start = start;
+    record_backtest();
     Ok(eval_metrics)
   }
 }
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn criteria(len: usize) -> BacktestCriteria {
+    BacktestCriteria {
+      indicator_values: vec![0.0; len],
+      trigger_indicator: TriggerIndicator::Zscore,
+      relation: Relation::Ignore,
+      cost_per_leg: None,
+      rets_weighting_s0_perc: 0.5,
+      long_series: LongSeries::Series0,
+      stop_loss: 0.0,
+      long_thresh: -1.5,
+      long_close_thresh: 0.0,
+      short_thresh: 1.5,
+      short_close_thresh: 0.0,
+      long_entry: None,
+      short_entry: None,
+      index_beta: None,
+      exchange: None,
+      fee_side: FeeSide::Taker,
+      warmup_bars: None,
+      signal_timing: SignalTiming::SameClose
+    }
+  }
+
+  #[tokio::test]
+  async fn it_returns_descriptive_criteria_errors() {
+    let series: Vec<f64> = vec![1.0; 10];
+    assert!(Backtest::new(&series, &series, criteria(10)).is_ok());
+
+    let err: String = Backtest::new(&series, &series, criteria(9)).unwrap_err().to_string();
+    assert!(err.contains("Indicator values length (9) must equal series length (10)"));
+
+    let bad_weight: BacktestCriteria = BacktestCriteria { rets_weighting_s0_perc: 1.5, ..criteria(10) };
+    assert!(bad_weight.validate(10).unwrap_err().to_string().contains("rets_weighting_s0_perc"));
+
+    let bad_thresh: BacktestCriteria = BacktestCriteria { long_close_thresh: -2.0, ..criteria(10) };
+    assert!(bad_thresh.validate(10).unwrap_err().to_string().contains("long_close_thresh"));
+  }
+
+  #[tokio::test]
+  async fn it_falls_back_to_exchange_fees() {
+    assert_eq!(criteria(10).effective_cost_per_leg(), 0.0);
+
+    let binance: BacktestCriteria = BacktestCriteria { exchange: Some(Exchange::Binance), ..criteria(10) };
+    assert_eq!(binance.effective_cost_per_leg(), 0.0005);
+    let maker: BacktestCriteria = BacktestCriteria { fee_side: FeeSide::Maker, ..binance.clone() };
+    assert_eq!(maker.effective_cost_per_leg(), 0.0002);
+    let explicit: BacktestCriteria = BacktestCriteria { cost_per_leg: Some(0.001), ..binance };
+    assert_eq!(explicit.effective_cost_per_leg(), 0.001);
+
+    // Fees lower the returns of the same signals
+    let series_0: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 / 3.0).sin() * 3.0).collect();
+    let series_1: Vec<f64> = vec![50.0; 60];
+    let zscore: Vec<f64> = series_0.iter().map(|p| (p - 100.0) / 1.5).collect();
+    let free: BacktestCriteria = BacktestCriteria { indicator_values: zscore.clone(), ..criteria(60) };
+    let charged: BacktestCriteria = BacktestCriteria { exchange: Some(Exchange::Coinbase), ..free.clone() };
+    let free_metrics: BacktestMetrics = Backtest::new(&series_0, &series_1, free).unwrap().run_backtest().unwrap();
+    let charged_metrics: BacktestMetrics = Backtest::new(&series_0, &series_1, charged).unwrap().run_backtest().unwrap();
+    assert!(charged_metrics.win_rate_stats.opened > 0);
+    assert!(charged_metrics.total_return < free_metrics.total_return);
+  }
+
+  #[tokio::test]
+  async fn it_enters_on_composite_conditions() {
+    let series_0: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 / 2.0).sin() * 2.0).collect();
+    let series_1: Vec<f64> = vec![50.0; 40];
+    let zscore: Vec<f64> = series_0.iter().map(|p| p - 100.0).collect();
+    let corr_low: Vec<f64> = vec![0.5; 40];
+    let corr_high: Vec<f64> = vec![0.9; 40];
+
+    let entry = |corr: Vec<f64>| EntryCondition::All(vec![
+      EntryCondition::Condition(Condition::below("zscore", zscore.clone(), -1.5)),
+      EntryCondition::Condition(Condition::above("corr", corr, 0.7))
+    ]);
+    let with_entry = |corr: Vec<f64>| BacktestCriteria {
+      indicator_values: zscore.clone(),
+      long_entry: Some(entry(corr)),
+      short_entry: Some(EntryCondition::Not(Box::new(EntryCondition::Any(vec![])))),
+      ..criteria(40)
+    };
+
+    // Short entry (NOT any of nothing) always holds, but long takes precedence when both trigger
+    let blocked: Vec<i32> = Backtest::new(&series_0, &series_1, with_entry(corr_low)).unwrap().signals().unwrap();
+    assert!(!blocked.contains(&1));
+    let allowed: Vec<i32> = Backtest::new(&series_0, &series_1, with_entry(corr_high)).unwrap().signals().unwrap();
+    assert!(allowed.contains(&1));
+
+    let bad: BacktestCriteria = BacktestCriteria { long_entry: Some(entry(vec![0.9; 39])), ..criteria(40) };
+    assert!(bad.validate(40).unwrap_err().to_string().contains("Condition corr values length (39)"));
+  }
+
+  #[tokio::test]
+  async fn it_applies_on_bar_hook() {
+    let series_0: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 / 2.0).sin() * 2.0).collect();
+    let series_1: Vec<f64> = vec![50.0; 40];
+    let zscore: Vec<f64> = series_0.iter().map(|p| p - 100.0).collect();
+    let bt_criteria: BacktestCriteria = BacktestCriteria { indicator_values: zscore, ..criteria(40) };
+
+    let base: Vec<i32> = Backtest::new(&series_0, &series_1, bt_criteria.clone()).unwrap().signals().unwrap();
+    assert!(base.contains(&1) && base.contains(&-1));
+
+    // Veto shorts only
+    let no_shorts: Vec<i32> = Backtest::new(&series_0, &series_1, bt_criteria.clone()).unwrap()
+      .with_on_bar(|ctx: &BarContext| if ctx.is_short_trigger { HookAction::Veto } else { HookAction::Continue })
+      .signals().unwrap();
+    assert!(no_shorts.contains(&1) && !no_shorts.contains(&-1));
+
+    // Force a long on bar 1 closed on bar 3
+    let neutral: BacktestCriteria = BacktestCriteria { indicator_values: vec![-1.0; 40], ..criteria(40) };
+    let forced: Vec<i32> = Backtest::new(&series_0, &series_1, neutral).unwrap()
+      .with_on_bar(|ctx: &BarContext| match ctx.bar { 1 => HookAction::ForceLong, 3 => HookAction::ForceClose, _ => HookAction::Continue })
+      .signals().unwrap();
+    assert_eq!(&forced[..5], &[0, 0, 1, 1, 0]);
+  }
+
+  #[tokio::test]
+  async fn it_matches_loop_signals_when_vectorized() {
+    let series_0: Vec<f64> = (0..300).map(|i| 100.0 + (i as f64 / 3.0).sin() * 2.0 + (i as f64 / 17.0).cos()).collect();
+    let series_1: Vec<f64> = (0..300).map(|i| 50.0 + (i as f64 / 3.0).sin() + (i as f64 / 11.0).sin() * 0.3).collect();
+    let zscore: Vec<f64> = (0..300).map(|i| (i as f64 / 4.0).sin() * 2.0 + (i as f64 / 9.0).cos()).collect();
+    let excluded: Vec<bool> = (0..300).map(|i| (120..140).contains(&i)).collect();
+
+    let cases: Vec<BacktestCriteria> = vec![
+      BacktestCriteria { indicator_values: zscore.clone(), ..criteria(300) },
+      BacktestCriteria { indicator_values: zscore.clone(), cost_per_leg: Some(0.001), long_series: LongSeries::Series1, ..criteria(300) },
+      BacktestCriteria { indicator_values: zscore.clone(), relation: Relation::Corr, ..criteria(300) },
+      BacktestCriteria {
+        indicator_values: zscore.clone(),
+        long_entry: Some(EntryCondition::Condition(Condition::below("zscore", zscore.clone(), -1.0))),
+        ..criteria(300)
+      }
+    ];
+
+    for bt_criteria in cases {
+      let backtest: Backtest = Backtest::new(&series_0, &series_1, bt_criteria).unwrap().with_excluded_bars(excluded.clone()).unwrap();
+      assert!(backtest.is_vectorizable());
+
+      let (loop_signals, loop_costs, loop_win, loop_closed) = backtest.create_signals_loop().unwrap();
+      let (vec_signals, vec_costs, vec_win, vec_closed) = backtest.create_signals_vectorized().unwrap();
+      assert!(loop_win.closed > 1);
+      assert_eq!(vec_signals, loop_signals);
+      assert_eq!(vec_costs, loop_costs);
+      assert_eq!(vec_closed, loop_closed);
+      assert_eq!((vec_win.opened, vec_win.closed, vec_win.closed_profit), (loop_win.opened, loop_win.closed, loop_win.closed_profit));
+    }
+
+    // Stop losses are path dependent so fall back to the loop
+    let stopped: Backtest = Backtest::new(&series_0, &series_1, BacktestCriteria { indicator_values: zscore.clone(), stop_loss: -0.01, ..criteria(300) }).unwrap();
+    assert!(!stopped.is_vectorizable());
+    assert!(stopped.create_signals_vectorized().is_err());
+    assert_eq!(stopped.signals().unwrap(), stopped.create_signals_loop().unwrap().0);
+  }
+
+  #[tokio::test]
+  async fn it_matches_per_bar_relation_checks() {
+    let mut seed: u64 = 7;
+    let mut noise = || { seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407); (seed >> 33) as f64 / (1u64 << 31) as f64 - 0.5 };
+    let mut walk: f64 = 100.0;
+    let series_1: Vec<f64> = (0..300).map(|_| { walk += noise(); walk }).collect();
+    let series_0: Vec<f64> = series_1.iter().enumerate()
+      .map(|(i, p)| if i < 150 { p * 2.0 + noise() * 0.5 } else { p * 2.0 + i as f64 * 0.3 + noise() * 4.0 })
+      .collect();
+    let zscore: Vec<f64> = (0..300).map(|i| (i as f64 / 5.0).sin() * 2.0).collect();
+
+    for relation in [Relation::Coint, Relation::Corr] {
+
+      // Previous behaviour re-tested the window before every bar
+      let expected: Vec<bool> = (0..300).map(|i| {
+        if i < RELATION_WINDOW { return false }
+        let s0: Vec<f64> = series_0[i-RELATION_WINDOW..i].to_vec();
+        let s1: Vec<f64> = series_1[i-RELATION_WINDOW..i].to_vec();
+        match relation {
+          Relation::Coint => cointegration_test_eg(&s0, &s1).unwrap().is_coint,
+          _ => pearson_correlation_coefficient(&s0, &s1).unwrap().abs() >= RELATION_CORR_THRESH
+        }
+      }).collect();
+      let gate: Vec<bool> = relation.gate_series(&series_0, &series_1).unwrap();
+      assert_eq!(gate, expected);
+
+      // Computed and supplied gates give identical signals
+      let bt_criteria: BacktestCriteria = BacktestCriteria { indicator_values: zscore.clone(), relation: relation.clone(), ..criteria(300) };
+      let computed: Vec<i32> = Backtest::new(&series_0, &series_1, bt_criteria.clone()).unwrap().signals().unwrap();
+      let supplied: Vec<i32> = Backtest::new(&series_0, &series_1, bt_criteria).unwrap()
+        .with_relation_gate(gate).unwrap()
+        .signals().unwrap();
+      assert_eq!(computed, supplied);
+    }
+
+    let short_gate: SmartError = Backtest::new(&series_0, &series_1, criteria(300)).unwrap().with_relation_gate(vec![true; 10]).unwrap_err();
+    assert!(short_gate.to_string().contains("Relation gate length (10)"));
+  }
+
+  #[tokio::test]
+  async fn it_backtests_custom_signal_source() {
+    let series_0: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 / 2.0).sin() * 2.0).collect();
+    let series_1: Vec<f64> = vec![50.0; 40];
+    let values: Vec<f64> = series_0.iter().map(|p| (p - 100.0) * 25.0 + 50.0).collect();
+    let thresholds: SignalThresholds = SignalThresholds { long: 30.0, long_close: 50.0, short: 70.0, short_close: 50.0 };
+    let source: SignalSource = SignalSource::new("oscillator", values, thresholds);
+
+    let criteria: BacktestCriteria = source.into_criteria(criteria(40));
+    assert_eq!(criteria.trigger_indicator, TriggerIndicator::Custom("oscillator".to_string()));
+    assert_eq!(criteria.short_thresh, 70.0);
+    let bt_metrics: BacktestMetrics = Backtest::new(&series_0, &series_1, criteria).unwrap().run_backtest().unwrap();
+    assert!(bt_metrics.win_rate_stats.opened > 0);
+  }
+
+  #[tokio::test]
+  async fn it_excludes_warmup_bars_from_signals_and_metrics() {
+    let series_0: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 / 3.0).sin() * 2.0).collect();
+    let series_1: Vec<f64> = (0..60).map(|i| 50.0 + (i as f64 / 5.0).cos()).collect();
+
+    // Zscore padded with 0.0 over a 10 bar window, which sits inside the long entry band
+    let zscore: Vec<f64> = (0..60).map(|i| if i < 10 { 0.0 } else { (i as f64 / 4.0).sin() * 2.0 }).collect();
+    let bt_criteria: BacktestCriteria = BacktestCriteria {
+      indicator_values: zscore.clone(), long_thresh: 0.5, long_close_thresh: 1.0, short_thresh: 1.5, short_close_thresh: 1.0, ..criteria(60)
+    };
+    assert_eq!(bt_criteria.warmup_len(), 10);
+    assert_eq!(BacktestCriteria { warmup_bars: Some(0), ..bt_criteria.clone() }.warmup_len(), 0);
+    assert_eq!(BacktestCriteria { trigger_indicator: TriggerIndicator::Spread, ..bt_criteria.clone() }.warmup_len(), 0);
+    assert_eq!(criteria(60).warmup_len(), 0);
+
+    let backtest: Backtest = Backtest::new(&series_0, &series_1, bt_criteria.clone()).unwrap();
+    assert_eq!(backtest.evaluation_range(), 10..60);
+    assert_eq!(backtest.warmup_bars().iter().filter(|w| **w).count(), 10);
+    let (loop_signals, ..) = backtest.create_signals_loop().unwrap();
+    let (vec_signals, ..) = backtest.create_signals_vectorized().unwrap();
+    assert_eq!(loop_signals, vec_signals);
+    assert!(loop_signals[..=10].iter().all(|s| *s == 0));
+
+    // Without the warm-up the padding opens a spurious long on the first bar
+    let padded: Backtest = Backtest::new(&series_0, &series_1, BacktestCriteria { warmup_bars: Some(0), ..bt_criteria.clone() }).unwrap();
+    assert_eq!(padded.signals().unwrap()[2], 1);
+
+    let bt_metrics: BacktestMetrics = backtest.run_backtest().unwrap();
+    assert_eq!(bt_metrics.evaluation_start, 10);
+    assert_eq!((bt_metrics.equity_curve.len(), bt_metrics.leg_pnl.leg_0_log_returns.len()), (50, 50));
+    assert!(serde_json::to_string(&bt_metrics).unwrap().find("null").is_none());
+
+    // The benchmark covers every bar, so its warm-up part must be dropped with the returns
+    let benchmark: Vec<f64> = (0..60).map(|i| if i < 10 { 0.05 } else { 0.0 }).collect();
+    let no_hurdle: BacktestMetrics = Backtest::new(&series_0, &series_1, bt_criteria.clone()).unwrap()
+      .with_evaluation_config(EvaluationConfig { use_risk_free: false, ..Default::default() }).run_backtest().unwrap();
+    let benchmarked: BacktestMetrics = Backtest::new(&series_0, &series_1, bt_criteria.clone()).unwrap()
+      .with_evaluation_config(EvaluationConfig { benchmark_log_returns: Some(benchmark), ..Default::default() }).run_backtest().unwrap();
+    assert_ne!(no_hurdle.sharpe_ratio, 0.0);
+    assert_eq!((benchmarked.sharpe_ratio, benchmarked.sortino_ratio), (no_hurdle.sharpe_ratio, no_hurdle.sortino_ratio));
+
+    let err: String = Backtest::new(&series_0, &series_1, BacktestCriteria { warmup_bars: Some(59), ..bt_criteria }).unwrap_err().to_string();
+    assert!(err.contains("warmup_bars (59) must leave at least 2 bars"));
+  }
+
+  #[tokio::test]
+  async fn it_times_signals_and_costs_consistently() {
+
+    // Long decided at the close of bar 1 and closed at the close of bar 3, series_1 flat so only leg 0 moves
+    let series_0: Vec<f64> = vec![100.0, 100.0, 110.0, 121.0, 121.0, 121.0];
+    let series_1: Vec<f64> = vec![50.0; 6];
+    let base: BacktestCriteria = BacktestCriteria {
+      indicator_values: vec![0.0, -2.0, -1.0, 0.5, 0.2, 0.0], cost_per_leg: Some(0.01), warmup_bars: Some(0), ..criteria(6)
+    };
+    let r: f64 = 1.1f64.ln();
+
+    // Hand computed: held bars (returns from the previous close), fees on the fill bar, closed trade flag on the exit fill
+    let cases: [(SignalTiming, [i32; 6], [f64; 6], [u64; 6], [f64; 6]); 3] = [
+      (SignalTiming::SameClose, [0, 0, 1, 1, 0, 0], [0.0, 0.02, 0.0, 0.02, 0.0, 0.0], [0, 0, 0, 1, 0, 0], [0.0, -0.02, r, r - 0.02, 0.0, 0.0]),
+      (SignalTiming::NextOpen, [0, 0, 1, 1, 0, 0], [0.0, 0.0, 0.02, 0.0, 0.02, 0.0], [0, 0, 0, 0, 1, 0], [0.0, 0.0, r - 0.02, r, -0.02, 0.0]),
+      (SignalTiming::NextClose, [0, 0, 0, 1, 1, 0], [0.0, 0.0, 0.02, 0.0, 0.02, 0.0], [0, 0, 0, 0, 1, 0], [0.0, 0.0, -0.02, r, -0.02, 0.0])
+    ];
+
+    for (timing, signals, costs, closed, net) in cases {
+      let backtest: Backtest = Backtest::new(&series_0, &series_1, BacktestCriteria { signal_timing: timing, ..base.clone() }).unwrap();
+      let (loop_signals, loop_costs, loop_win, loop_closed) = backtest.create_signals_loop().unwrap();
+      let (vec_signals, vec_costs, _, vec_closed) = backtest.create_signals_vectorized().unwrap();
+      assert_eq!((&loop_signals, &loop_costs, &loop_closed), (&vec_signals, &vec_costs, &vec_closed));
+      assert_eq!(loop_signals, signals.to_vec(), "{:?}", timing);
+      assert!(loop_costs.iter().zip(costs.iter()).all(|(a, b)| (a - b).abs() < 1e-12), "{:?}", timing);
+      assert_eq!(loop_closed, closed.to_vec(), "{:?}", timing);
+      assert_eq!((loop_win.opened, loop_win.closed), (1, 1));
+
+      let (net_lrets, ..) = backtest.strategy_returns(loop_signals, loop_costs);
+      assert!(net_lrets.iter().zip(net.iter()).all(|(a, b)| (a - b).abs() < 1e-12), "{:?} {:?}", timing, net_lrets);
+    }
+
+    // A signal on the last bar is never filled when the fill falls after the data
+    let late: BacktestCriteria = BacktestCriteria { indicator_values: vec![0.0, 0.1, 0.1, 0.1, 0.1, -2.0], signal_timing: SignalTiming::NextClose, ..base };
+    let (signals, costs, win, _) = Backtest::new(&series_0, &series_1, late).unwrap().create_signals().unwrap();
+    assert!(signals.iter().all(|s| *s == 0) && costs.iter().all(|c| *c == 0.0));
+    assert_eq!(win.opened, 0);
+  }
+}
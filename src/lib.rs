@@ -1,20 +1,47 @@
+pub mod account;
+pub mod alerts;
 pub mod backtest;
+pub mod metrics;
+pub mod paper;
+pub mod pair;
 pub mod prelude;
+pub mod presets;
+pub mod report;
 pub mod pricing;
+pub mod risk;
+pub mod screener;
+pub mod snapshot;
+pub mod state;
 pub mod stats;
+pub mod watchlist;
+pub mod webhook;
 
 #[derive(thiserror::Error, Debug)]
 pub enum SmartError {
   #[error("Failed to retrieve data")]
   APIResponseStatus(String),
-  #[error("Runtime error check failed")]
+  #[error("Runtime error check failed: {0}")]
   RuntimeCheck(String),
+  #[error("Circuit breaker open for exchange: {0}")]
+  CircuitOpen(String),
+  #[error("Unknown symbol: {0}")]
+  UnknownSymbol(String),
+  #[error("Symbol delisted or not trading: {0}")]
+  Delisted(String),
+  #[error("Response schema mismatch: {0}")]
+  SchemaMismatch(String),
   #[error(transparent)]
   Io(#[from] std::io::Error),
   #[error(transparent)]
   ParseFloat(#[from] std::num::ParseFloatError),
   #[error(transparent)]
-  Reqwest(#[from] reqwest::Error),
+  Reqwest(reqwest::Error),
   #[error(transparent)]
   SerdeJson(#[from] serde_json::Error)
 }
+
+impl From<reqwest::Error> for SmartError {
+  fn from(e: reqwest::Error) -> Self {
+    SmartError::Reqwest(pricing::utils::redact_reqwest_error(e))
+  }
+}
@@ -0,0 +1,149 @@
+use crate::pricing::models::Exchange;
+
+/*
+  Metrics
+  Prometheus counters and histograms for server / monitor deployments (metrics feature only)
+  Requests and errors per exchange, fetch latency, backtests run and signal events, so operations can alert on data source degradation
+  Without the feature (and on wasm) every recorder is a no-op
+*/
+
+#[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+use prometheus::{HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts, Registry};
+
+/// Exchange Label
+/// Metric label for the exchange serving a url
+pub fn exchange_label(url: &str) -> String {
+  Exchange::from_url(url).map(|e| e.as_string()).unwrap_or_else(|| "other".to_string())
+}
+
+#[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+struct Metrics {
+  registry: Registry,
+  requests: IntCounterVec,
+  request_errors: IntCounterVec,
+  request_latency: HistogramVec,
+  fetch_latency: HistogramVec,
+  backtests: IntCounter,
+  signal_events: IntCounterVec
+}
+
+#[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+static METRICS: std::sync::OnceLock<Metrics> = std::sync::OnceLock::new();
+
+#[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+fn metrics() -> &'static Metrics {
+  METRICS.get_or_init(|| {
+    let latency_buckets: Vec<f64> = vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+    let requests = IntCounterVec::new(Opts::new("zscore_requests_total", "HTTP requests sent per exchange"), &["exchange"])
+      .expect("valid metric");
+    let request_errors = IntCounterVec::new(Opts::new("zscore_request_errors_total", "Failed HTTP requests per exchange"), &["exchange"])
+      .expect("valid metric");
+    let request_latency = HistogramVec::new(
+      HistogramOpts::new("zscore_request_duration_seconds", "Time to response headers per exchange").buckets(latency_buckets.clone()),
+      &["exchange"]
+    ).expect("valid metric");
+    let fetch_latency = HistogramVec::new(
+      HistogramOpts::new("zscore_fetch_duration_seconds", "Time to fetch all candle pages for a symbol per exchange").buckets(latency_buckets),
+      &["exchange"]
+    ).expect("valid metric");
+    let backtests = IntCounter::new("zscore_backtests_total", "Backtests run").expect("valid metric");
+    let signal_events = IntCounterVec::new(Opts::new("zscore_signal_events_total", "Signal events raised by kind"), &["kind"])
+      .expect("valid metric");
+
+    let registry: Registry = Registry::new();
+    let _ = registry.register(Box::new(requests.clone()));
+    let _ = registry.register(Box::new(request_errors.clone()));
+    let _ = registry.register(Box::new(request_latency.clone()));
+    let _ = registry.register(Box::new(fetch_latency.clone()));
+    let _ = registry.register(Box::new(backtests.clone()));
+    let _ = registry.register(Box::new(signal_events.clone()));
+    Metrics { registry, requests, request_errors, request_latency, fetch_latency, backtests, signal_events }
+  })
+}
+
+/// Record Request
+/// Counts a request to the exchange serving the url and its latency in milliseconds
+#[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+pub fn record_request(url: &str, latency_ms: i64, is_ok: bool) {
+  let label: String = exchange_label(url);
+  let m: &Metrics = metrics();
+  m.requests.with_label_values(&[&label]).inc();
+  if !is_ok { m.request_errors.with_label_values(&[&label]).inc(); }
+  m.request_latency.with_label_values(&[&label]).observe(latency_ms.max(0) as f64 / 1000.0);
+}
+
+#[cfg(not(all(feature = "metrics", not(target_arch = "wasm32"))))]
+pub fn record_request(_url: &str, _latency_ms: i64, _is_ok: bool) {}
+
+/// Record Fetch
+/// Observes the time taken to fetch every candle page for a symbol
+#[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+pub fn record_fetch(exchange: &Exchange, latency_ms: i64) {
+  metrics().fetch_latency.with_label_values(&[&exchange.as_string()]).observe(latency_ms.max(0) as f64 / 1000.0);
+}
+
+#[cfg(not(all(feature = "metrics", not(target_arch = "wasm32"))))]
+pub fn record_fetch(_exchange: &Exchange, _latency_ms: i64) {}
+
+/// Record Backtest
+/// Counts a completed backtest run
+#[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+pub fn record_backtest() {
+  metrics().backtests.inc();
+}
+
+#[cfg(not(all(feature = "metrics", not(target_arch = "wasm32"))))]
+pub fn record_backtest() {}
+
+/// Record Signal Event
+/// Counts a signal event of the given kind (for example "degradation")
+#[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+pub fn record_signal_event(kind: &str) {
+  metrics().signal_events.with_label_values(&[kind]).inc();
+}
+
+#[cfg(not(all(feature = "metrics", not(target_arch = "wasm32"))))]
+pub fn record_signal_event(_kind: &str) {}
+
+/// Gather Metrics
+/// Renders every metric in the Prometheus text exposition format for a /metrics endpoint
+#[cfg(all(feature = "metrics", not(target_arch = "wasm32")))]
+pub fn gather_metrics() -> Result<String, crate::SmartError> {
+  use prometheus::Encoder;
+  let mut buffer: Vec<u8> = vec![];
+  prometheus::TextEncoder::new()
+    .encode(&metrics().registry.gather(), &mut buffer)
+    .map_err(|e| crate::SmartError::RuntimeCheck(format!("Failed to encode metrics: {}", e)))?;
+  String::from_utf8(buffer).map_err(|e| crate::SmartError::RuntimeCheck(e.to_string()))
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_labels_requests_by_exchange() {
+    assert_eq!(exchange_label("https://api.kucoin.com/api/v1/status"), Exchange::KuCoin.as_string());
+    assert_eq!(exchange_label("https://api.binance.com/api/v3/klines?symbol=BTCUSDT"), Exchange::BinanceSpot.as_string());
+    assert_eq!(exchange_label("https://example.com/x"), "other");
+  }
+
+  #[cfg(feature = "metrics")]
+  #[tokio::test]
+  async fn it_gathers_recorded_metrics() {
+    record_request("https://api.kucoin.com/api/v1/status", 120, true);
+    record_request("https://api.kucoin.com/api/v1/status", 80, false);
+    record_fetch(&Exchange::KuCoin, 1500);
+    record_backtest();
+    record_signal_event("degradation");
+
+    let text: String = gather_metrics().unwrap();
+    let label: String = Exchange::KuCoin.as_string();
+    assert!(text.contains(&format!("zscore_request_errors_total{{exchange=\"{}\"}} 1", label)));
+    assert!(text.contains("zscore_request_duration_seconds_bucket"));
+    assert!(text.contains("zscore_fetch_duration_seconds_count"));
+    assert!(text.contains("zscore_backtests_total"));
+    assert!(text.contains("zscore_signal_events_total{kind=\"degradation\"}"));
+  }
+}
@@ -0,0 +1,417 @@
+use crate::SmartError;
+use crate::backtest::evaluation::BacktestMetrics;
+use crate::backtest::models::{Backtest, BacktestCriteria, TriggerIndicator};
+use crate::prelude::{default_backtest_criteria_for, PairAnalysis};
+use crate::pricing::entry::get_prices_pair;
+use crate::pricing::models::{DataCriteria, EventMarker, Exchange, IntervalPeriod, LabelConvention, PairPrices};
+use crate::stats::models::{SpreadType, Statistics};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/*
+  Pair
+  High level fluent pipeline for fetching, analysing and backtesting a pair
+  Intermediate results are cached so each step is only computed once
+*/
+
+#[derive(Debug, Clone)]
+pub struct Pair {
+  pub data_criteria: DataCriteria,
+  twelve_api_key: Option<String>,
+  roll_window: usize,
+  prices: Option<PairPrices>,
+  stats: Option<(SpreadType, usize, Statistics)>,
+  bt_metrics: Option<BacktestMetrics>
+}
+
+impl Pair {
+  pub fn new(exchange: Exchange, asset_0: &str, asset_1: &str) -> Self {
+    let interval_period: IntervalPeriod = exchange.default_interval_period();
+    let data_criteria: DataCriteria = DataCriteria {
+      exchange,
+      asset_0: asset_0.to_string(),
+      asset_1: asset_1.to_string(),
+      interval_period,
+      fallback_exchanges: vec![]
+    };
+    Self { data_criteria, twelve_api_key: None, roll_window: 90, prices: None, stats: None, bt_metrics: None }
+  }
+
+  /// From Prices
+  /// Starts a pipeline from already available prices (no fetch required)
+  pub fn from_prices(data_criteria: DataCriteria, prices: PairPrices) -> Self {
+    Self { data_criteria, twelve_api_key: None, roll_window: 90, prices: Some(prices), stats: None, bt_metrics: None }
+  }
+
+  /// Interval
+  /// Sets the interval period (clears any cached results)
+  pub fn interval(mut self, interval_period: IntervalPeriod) -> Self {
+    self.data_criteria.interval_period = interval_period;
+    self.prices = None;
+    self.stats = None;
+    self.bt_metrics = None;
+    self
+  }
+
+  /// Twelve Api Key
+  /// Required only when the exchange is Twelve
+  pub fn twelve_api_key(mut self, api_key: &str) -> Self {
+    self.twelve_api_key = Some(api_key.to_string());
+    self
+  }
+
+  /// Roll Window
+  /// Sets the rolling cointegration and correlation window (clears cached stats)
+  pub fn roll_window(mut self, roll_window: usize) -> Self {
+    self.roll_window = roll_window;
+    self.stats = None;
+    self.bt_metrics = None;
+    self
+  }
+
+  /// Fetch
+  /// Retrieves prices for the pair (skipped if already cached)
+  pub async fn fetch(mut self) -> Result<Self, SmartError> {
+    if self.prices.is_none() {
+      let prices: PairPrices = get_prices_pair(self.data_criteria.clone(), self.twelve_api_key.as_deref()).await?;
+      self.prices = Some(prices);
+    }
+    Ok(self)
+  }
+
+  /// Stats
+  /// Calculates statistics for the fetched prices (skipped if cached with the same settings)
+  pub fn stats(mut self, spread_type: SpreadType, zscore_window: usize) -> Result<Self, SmartError> {
+    if let Some((cached_type, cached_window, _)) = &self.stats {
+      if *cached_type == spread_type && *cached_window == zscore_window { return Ok(self) }
+    }
+
+    let prices: &PairPrices = self.get_prices()?;
+    let stats: Statistics = Statistics::calculate_statistics(
+      &prices.series_0,
+      &prices.series_1,
+      spread_type.clone(),
+      zscore_window,
+      self.roll_window
+    )?;
+
+    self.stats = Some((spread_type, zscore_window, stats));
+    self.bt_metrics = None;
+    Ok(self)
+  }
+
+  /// Backtest
+  /// Runs a backtest on the fetched prices
+  /// If no indicator values are supplied, the cached zscore or spread is used given the trigger indicator
+  pub fn backtest(mut self, mut criteria: BacktestCriteria) -> Result<Self, SmartError> {
+    if criteria.indicator_values.is_empty() {
+      let stats: &Statistics = self.get_stats()?;
+      criteria.indicator_values = match criteria.trigger_indicator {
+        TriggerIndicator::Zscore => stats.zscore.clone(),
+        TriggerIndicator::Spread => stats.spread.clone(),
+        TriggerIndicator::TimeframeGatedZscore => {
+          return Err(SmartError::RuntimeCheck("Gated zscore values must be supplied, see timeframe_gated_zscore".to_string()))
+        },
+        TriggerIndicator::Custom(name) => {
+          return Err(SmartError::RuntimeCheck(format!("Values for custom indicator {} must be supplied, see SignalSource", name)))
+        }
+      };
+    }
+
+    let prices: &PairPrices = self.get_prices()?;
+    prices.ensure_complete()?;
+    let backtest: Backtest = Backtest::new(&prices.series_0, &prices.series_1, criteria)?
+      .with_excluded_bars(prices.excluded_bars())?;
+    self.bt_metrics = Some(backtest.run_backtest()?);
+    Ok(self)
+  }
+
+  /// Getters
+  pub fn get_prices(&self) -> Result<&PairPrices, SmartError> {
+    self.prices.as_ref().ok_or(SmartError::RuntimeCheck("Prices not fetched, call fetch first".to_string()))
+  }
+
+  pub fn get_stats(&self) -> Result<&Statistics, SmartError> {
+    self.stats.as_ref().map(|s| &s.2).ok_or(SmartError::RuntimeCheck("Stats not calculated, call stats first".to_string()))
+  }
+
+  pub fn get_bt_metrics(&self) -> Result<&BacktestMetrics, SmartError> {
+    self.bt_metrics.as_ref().ok_or(SmartError::RuntimeCheck("Backtest not run, call backtest first".to_string()))
+  }
+
+  /// Into Analysis
+  /// Consolidates cached results into a PairAnalysis
+  pub fn into_analysis(self) -> Result<PairAnalysis, SmartError> {
+    let bt_metrics: BacktestMetrics = self.get_bt_metrics()?.clone();
+    let stats: Statistics = self.get_stats()?.clone();
+    let prices: PairPrices = self.get_prices()?.clone();
+    let event_markers: Vec<EventMarker> = prices.event_markers();
+    Ok(PairAnalysis { prices, stats, bt_metrics, event_markers })
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct TimeframeAnalysis {
+  pub interval_period: IntervalPeriod,
+  pub prices: PairPrices,
+  pub stats: Statistics,
+  pub bt_metrics: BacktestMetrics
+}
+
+/// Multi Timeframe Analysis
+/// Fetches the finest interval once, resamples it to each requested interval and analyses every timeframe
+/// Cached prices are reused when their interval divides every requested interval and they cover the longest lookback
+/// Otherwise the finest interval is fetched, and a timeframe still short of its period is an error
+/// Uses the pair's cached stats settings (Dynamic, 35 otherwise) and the default backtest criteria
+pub async fn multi_timeframe_analysis(pair: &Pair, intervals: &[IntervalPeriod]) -> Result<Vec<TimeframeAnalysis>, SmartError> {
+
+  // Guard: Ensure intervals supplied
+  let Some(finest) = intervals.iter().min_by_key(|i| i.interval_seconds()) else {
+    return Err(SmartError::RuntimeCheck("No intervals supplied".to_string()))
+  };
+
+  // Guard: Ensure every interval is a multiple of the finest
+  let finest_secs: u64 = finest.interval_seconds();
+  if intervals.iter().any(|i| i.interval_seconds() % finest_secs != 0) {
+    return Err(SmartError::RuntimeCheck("Intervals must be multiples of the finest interval".to_string()));
+  }
+
+  // Reuse cached prices if their interval divides every requested interval and enough bars are held
+  let cached_secs: u64 = pair.data_criteria.interval_period.interval_seconds().max(1);
+  let cached_bars_needed: u64 = intervals.iter()
+    .map(|i| i.period() as u64 * i.interval_seconds() / cached_secs)
+    .max()
+    .unwrap_or(0);
+  let reuse_cached: bool = pair.prices.as_ref().is_some_and(|p| p.labels.len() as u64 >= cached_bars_needed)
+    && intervals.iter().all(|i| i.interval_seconds() % cached_secs == 0);
+
+  let fine_pair: Pair = if reuse_cached {
+    pair.clone()
+  } else {
+
+    // Enough fine bars to cover the longest lookback
+    let bars: u64 = intervals.iter()
+      .map(|i| i.period() as u64 * i.interval_seconds() / finest_secs)
+      .max()
+      .unwrap_or(0);
+    let fine_interval: IntervalPeriod = finest.with_period(bars as u32);
+    pair.clone().interval(fine_interval).fetch().await?
+  };
+
+  let fine_prices: &PairPrices = fine_pair.get_prices()?;
+  fine_prices.ensure_complete()?;
+
+  let (spread_type, zscore_window) = match &pair.stats {
+    Some((spread_type, zscore_window, _)) => (spread_type.clone(), *zscore_window),
+    None => (SpreadType::Dynamic, 35)
+  };
+
+  let mut analyses: Vec<TimeframeAnalysis> = vec![];
+  for interval_period in intervals.iter() {
+    let prices: PairPrices = fine_prices.resample(interval_period);
+
+    // Guard: Ensure the timeframe received its full period
+    if prices.labels.len() < interval_period.period() as usize {
+      let e: String = format!("Insufficient bars for {:?}: {} of {} received", interval_period, prices.labels.len(), interval_period.period());
+      return Err(SmartError::RuntimeCheck(e));
+    }
+
+    let stats: Statistics = Statistics::calculate_statistics(
+      &prices.series_0,
+      &prices.series_1,
+      spread_type.clone(),
+      zscore_window,
+      pair.roll_window
+    )?;
+    let criteria: BacktestCriteria = default_backtest_criteria_for(&pair.data_criteria, &prices, stats.zscore.clone());
+    let backtest: Backtest = Backtest::new(&prices.series_0, &prices.series_1, criteria)?
+      .with_excluded_bars(prices.excluded_bars())?;
+    let bt_metrics: BacktestMetrics = backtest.run_backtest()?;
+    analyses.push(TimeframeAnalysis { interval_period: interval_period.clone(), prices, stats, bt_metrics });
+  }
+
+  Ok(analyses)
+}
+
+/// Bar Close Times
+/// Time at which each bar of an analysis is complete
+fn bar_close_times(analysis: &TimeframeAnalysis) -> Vec<u64> {
+  let step: u64 = analysis.interval_period.interval_seconds();
+  match analysis.prices.label_convention {
+    LabelConvention::OpenTime => analysis.prices.labels.iter().map(|l| l + step).collect(),
+    LabelConvention::CloseTime => analysis.prices.labels.clone()
+  }
+}
+
+/// Timeframe Gated Zscore
+/// Combines an entry timeframe zscore with the direction of a coarser timeframe zscore
+/// Each entry bar uses the latest coarse bar already closed (no lookahead)
+/// Where the directions disagree (or no coarse bar has closed yet) the value is zero, blocking new entries
+/// Result aligns with the entry timeframe and can be used as indicator values with TriggerIndicator::TimeframeGatedZscore
+pub fn timeframe_gated_zscore(entry: &TimeframeAnalysis, gate: &TimeframeAnalysis) -> Result<Vec<f64>, SmartError> {
+
+  // Guard: Ensure gate is the coarser timeframe
+  if gate.interval_period.interval_seconds() < entry.interval_period.interval_seconds() {
+    return Err(SmartError::RuntimeCheck("Gate timeframe must be coarser than the entry timeframe".to_string()));
+  }
+
+  let entry_closes: Vec<u64> = bar_close_times(entry);
+  let gate_closes: Vec<u64> = bar_close_times(gate);
+
+  let mut closed_gates: usize = 0;
+  let mut gated: Vec<f64> = Vec::with_capacity(entry.stats.zscore.len());
+  for (close, z) in entry_closes.iter().zip(entry.stats.zscore.iter()) {
+    while closed_gates < gate_closes.len() && gate_closes[closed_gates] <= *close {
+      closed_gates += 1;
+    }
+    let gate_z: f64 = if closed_gates == 0 { 0.0 } else { gate.stats.zscore[closed_gates - 1] };
+    let agrees: bool = gate_z * z > 0.0;
+    gated.push(if agrees { *z } else { 0.0 });
+  }
+
+  Ok(gated)
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::backtest::models::{LongSeries, Relation, SignalTiming};
+  use crate::pricing::fees::FeeSide;
+  use crate::pricing::models::{EventKind, SeriesEvent};
+  use crate::prelude::default_backtest_criteria;
+
+  fn synthetic_prices() -> PairPrices {
+    let series_1: Vec<f64> = (0..300).map(|i| 100.0 + (i as f64 / 10.0).sin() * 5.0 + i as f64 * 0.1).collect();
+    let series_0: Vec<f64> = series_1.iter().enumerate().map(|(i, p)| p * 2.0 + (i as f64 / 3.0).cos() * 2.0).collect();
+    let labels: Vec<u64> = (0..300).map(|i| i * 3600).collect();
+    PairPrices { series_0, series_1, labels, label_convention: Default::default(), fetch_report: None, events: vec![], source: None }
+  }
+
+  #[tokio::test]
+  async fn it_runs_fluent_pipeline_from_prices() {
+    let data_criteria: DataCriteria = Pair::new(Exchange::Binance, "BTCUSDT", "ETHUSDT").data_criteria;
+    let criteria: BacktestCriteria = BacktestCriteria {
+      indicator_values: vec![],
+      trigger_indicator: TriggerIndicator::Zscore,
+      relation: Relation::Ignore,
+      cost_per_leg: Some(0.0005),
+      rets_weighting_s0_perc: 0.5,
+      long_series: LongSeries::Series0,
+      stop_loss: 0.0,
+      long_thresh: -1.5,
+      long_close_thresh: 0.0,
+      short_thresh: 1.5,
+      short_close_thresh: 0.0,
+      long_entry: None,
+      short_entry: None,
+      index_beta: None,
+      exchange: None,
+      fee_side: FeeSide::Taker,
+      warmup_bars: None,
+      signal_timing: SignalTiming::SameClose
+    };
+
+    let pair: Pair = Pair::from_prices(data_criteria, synthetic_prices())
+      .fetch().await.unwrap()
+      .stats(SpreadType::Dynamic, 35).unwrap()
+      .backtest(criteria).unwrap();
+
+    assert_eq!(pair.get_stats().unwrap().zscore.len(), 300);
+
+    // Leg attribution reconciles with the net equity curve
+    let bt_metrics: &BacktestMetrics = pair.get_bt_metrics().unwrap();
+    let leg_pnl = &bt_metrics.leg_pnl;
+    let net_log_return: f64 = (1.0 + bt_metrics.equity_curve.last().unwrap()).ln();
+    assert_eq!(bt_metrics.evaluation_start, 35); // zscore window warm-up
+    assert_eq!(leg_pnl.leg_0_log_returns.len(), 300 - 35);
+    assert!((leg_pnl.leg_0_total + leg_pnl.leg_1_total - leg_pnl.costs_total - net_log_return).abs() < 1e-3);
+    assert!(pair.into_analysis().is_ok());
+  }
+
+  #[tokio::test]
+  async fn it_resamples_pair_prices() {
+    let prices: PairPrices = synthetic_prices().resample(&IntervalPeriod::Hour(4, 10));
+    assert_eq!(prices.labels.len(), 10);
+    assert_eq!(prices.labels[1] - prices.labels[0], 4 * 3600);
+    assert_eq!(prices.series_0.last(), synthetic_prices().series_0.last());
+  }
+
+  #[tokio::test]
+  async fn it_analyses_multiple_timeframes_from_cached_prices() {
+    let data_criteria: DataCriteria = Pair::new(Exchange::Binance, "BTCUSDT", "ETHUSDT").data_criteria;
+    let pair: Pair = Pair::from_prices(data_criteria, synthetic_prices());
+    let intervals: Vec<IntervalPeriod> = vec![IntervalPeriod::Hour(1, 300), IntervalPeriod::Hour(2, 150)];
+    let analyses: Vec<TimeframeAnalysis> = multi_timeframe_analysis(&pair, &intervals).await.unwrap();
+    assert_eq!(analyses.len(), 2);
+    assert_eq!(analyses[0].stats.zscore.len(), 300);
+    assert_eq!(analyses[1].stats.zscore.len(), 150);
+  }
+
+  #[tokio::test]
+  async fn tests_multi_timeframe_fetches_beyond_short_cache() {
+    let data_criteria: DataCriteria = Pair::new(Exchange::Binance, "BTCUSDT", "ETHUSDT").data_criteria;
+    let pair: Pair = Pair::from_prices(data_criteria, synthetic_prices());
+
+    // 100 four hour bars need 400 hourly bars, more than the 300 cached
+    let intervals: Vec<IntervalPeriod> = vec![IntervalPeriod::Hour(1, 300), IntervalPeriod::Hour(4, 100)];
+    let analyses: Vec<TimeframeAnalysis> = multi_timeframe_analysis(&pair, &intervals).await.unwrap();
+    assert_eq!(analyses[0].stats.zscore.len(), 300);
+    assert_eq!(analyses[1].stats.zscore.len(), 100);
+  }
+
+  #[tokio::test]
+  async fn it_gates_entry_zscore_by_coarse_direction() {
+    let data_criteria: DataCriteria = Pair::new(Exchange::Binance, "BTCUSDT", "ETHUSDT").data_criteria;
+    let pair: Pair = Pair::from_prices(data_criteria, synthetic_prices()).roll_window(30);
+    let intervals: Vec<IntervalPeriod> = vec![IntervalPeriod::Hour(1, 300), IntervalPeriod::Hour(4, 75)];
+    let analyses: Vec<TimeframeAnalysis> = multi_timeframe_analysis(&pair, &intervals).await.unwrap();
+    let gated: Vec<f64> = timeframe_gated_zscore(&analyses[0], &analyses[1]).unwrap();
+    assert_eq!(gated.len(), 300);
+
+    // First entry bars close before any coarse bar
+    assert_eq!(gated[0], 0.0);
+    for (g, z) in gated.iter().zip(analyses[0].stats.zscore.iter()) {
+      assert!(*g == 0.0 || g == z);
+    }
+    assert!(timeframe_gated_zscore(&analyses[1], &analyses[0]).is_err());
+  }
+
+  #[tokio::test]
+  async fn it_excludes_event_windows_and_passes_markers() {
+    let event: SeriesEvent = SeriesEvent {
+      label: 150 * 3600 + 60,
+      kind: EventKind::Fork,
+      name: "Hard fork".to_string(),
+      exclude_before_secs: 10 * 3600,
+      exclude_after_secs: 20 * 3600
+    };
+    let prices: PairPrices = synthetic_prices().with_events(vec![event]);
+    let excluded: Vec<bool> = prices.excluded_bars();
+    assert_eq!(excluded.iter().filter(|e| **e).count(), 30);
+    assert!(excluded[141] && excluded[170] && !excluded[140] && !excluded[171]);
+
+    let data_criteria: DataCriteria = Pair::new(Exchange::Binance, "BTCUSDT", "ETHUSDT").data_criteria;
+    let analysis: PairAnalysis = Pair::from_prices(data_criteria, prices)
+      .stats(SpreadType::Static, 35).unwrap()
+      .backtest(default_backtest_criteria(vec![])).unwrap()
+      .into_analysis().unwrap();
+    assert_eq!(analysis.event_markers.len(), 1);
+    assert_eq!(analysis.event_markers[0].index, 151);
+
+    // No position held from the bar after the window opens until the window closes (signals lag one bar)
+    let backtest: Backtest = Backtest::new(&analysis.prices.series_0, &analysis.prices.series_1, default_backtest_criteria(analysis.stats.zscore.clone()))
+      .unwrap()
+      .with_excluded_bars(excluded)
+      .unwrap();
+    let signals: Vec<i32> = backtest.signals().unwrap();
+    assert!(signals[142..=171].iter().all(|s| *s == 0));
+  }
+
+  #[tokio::test]
+  async fn it_requires_prices_before_stats() {
+    let res = Pair::new(Exchange::Binance, "BTCUSDT", "ETHUSDT").stats(SpreadType::Static, 35);
+    assert!(res.is_err());
+  }
+}
@@ -0,0 +1,328 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use crate::pricing::capacity::{BookLevel, OrderBook};
+use crate::risk::{RiskAction, RiskGuard, RiskLimits};
+
+/*
+  Paper Execution
+  Fill model for paper trading against order book snapshots, so paper results carry realistic slippage
+  Orders reach the book after a sampled latency, market orders cross the spread level by level and limit orders
+  rest behind the quantity already queued at their price until enough volume trades through
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub enum LatencyModel {
+  Fixed(u64), // ms
+  Uniform { min_ms: u64, max_ms: u64 },
+  Normal { mean_ms: f64, std_ms: f64 } // truncated at zero
+}
+
+impl Default for LatencyModel {
+  fn default() -> Self {
+    Self::Fixed(0)
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, TS)]
+#[ts(export)]
+pub enum OrderSide {
+  Buy,
+  Sell
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, TS)]
+#[ts(export)]
+pub enum OrderType {
+  Market,
+  Limit(f64) // limit price
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct PaperOrder {
+  pub symbol: String,
+  pub side: OrderSide,
+  pub order_type: OrderType,
+  pub qty: f64
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct BookSnapshot {
+  pub time_ms: u64,
+  pub book: OrderBook
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct PaperFill {
+  pub filled_qty: f64,
+  pub avg_price: f64, // NaN when nothing filled
+  pub resting_qty: f64, // limit quantity left on the book
+  pub queue_ahead: f64, // quantity ahead of the resting order at its price
+  pub latency_ms: u64,
+  pub arrival_ms: u64, // submit time plus latency (the snapshot used is the latest at or before this)
+  pub slippage_bps: f64, // average fill against the mid at submit, positive is worse
+  pub is_partial: bool
+}
+
+/// Paper Executor
+/// Samples latency (seeded, so runs repeat) and fills orders against book snapshots
+#[derive(Debug, Clone)]
+pub struct PaperExecutor {
+  pub latency: LatencyModel,
+  pub risk: Option<RiskGuard>, // enforced by execute_entry and execute_exit
+  rng_state: u64
+}
+
+impl PaperExecutor {
+  pub fn new(latency: LatencyModel, seed: u64) -> Self {
+    Self { latency, risk: None, rng_state: seed }
+  }
+
+  /// With Risk Limits
+  /// Enforces account wide risk limits on pair entries
+  pub fn with_risk_limits(mut self, limits: RiskLimits) -> Self {
+    self.risk = Some(RiskGuard::new(limits));
+    self
+  }
+
+  /// Next Uniform
+  /// Uniform draw in [0, 1) from a linear congruential generator
+  fn next_uniform(&mut self) -> f64 {
+    self.rng_state = self.rng_state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    (self.rng_state >> 11) as f64 / (1u64 << 53) as f64
+  }
+
+  /// Sample Latency
+  /// Milliseconds until an order reaches the exchange
+  pub fn sample_latency(&mut self) -> u64 {
+    match self.latency.clone() {
+      LatencyModel::Fixed(ms) => ms,
+      LatencyModel::Uniform { min_ms, max_ms } => {
+        let (lo, hi) = (min_ms.min(max_ms), min_ms.max(max_ms));
+        lo + (self.next_uniform() * (hi - lo + 1) as f64) as u64
+      },
+      LatencyModel::Normal { mean_ms, std_ms } => {
+
+        // Box-Muller transform
+        let (u1, u2) = (self.next_uniform().max(f64::MIN_POSITIVE), self.next_uniform());
+        let z: f64 = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+        (mean_ms + std_ms * z).max(0.0).round() as u64
+      }
+    }
+  }
+
+  /// Execute
+  /// Fills an order against the latest snapshot at or before its arrival (submit time plus sampled latency)
+  pub fn execute(&mut self, order: &PaperOrder, submit_ms: u64, snapshots: &[BookSnapshot]) -> Result<PaperFill, SmartError> {
+
+    // Guard: Ensure a usable order
+    if !order.qty.is_finite() || order.qty <= 0.0 {
+      return Err(SmartError::RuntimeCheck(format!("Order quantity ({}) must be positive", order.qty)));
+    }
+
+    let snapshot_at = |time_ms: u64| snapshots.iter().rev().find(|s| s.time_ms <= time_ms);
+    let Some(at_submit) = snapshot_at(submit_ms) else {
+      return Err(SmartError::RuntimeCheck(format!("No order book snapshot at or before {}", submit_ms)))
+    };
+    let Some(mid) = at_submit.book.mid() else {
+      return Err(SmartError::RuntimeCheck("Order book snapshot has no bid or ask".to_string()))
+    };
+
+    let latency_ms: u64 = self.sample_latency();
+    let arrival_ms: u64 = submit_ms + latency_ms;
+    let book: &OrderBook = &snapshot_at(arrival_ms).unwrap_or(at_submit).book;
+    Ok(fill_against_book(order, book, mid, latency_ms, arrival_ms))
+  }
+
+  /// Execute Entry
+  /// Fills every leg of a pair entry, rejected before any leg is sent if the risk guard does not allow it
+  /// The guard is checked against the notional at submit mids and records the filled notional
+  pub fn execute_entry(&mut self, pair_id: &str, orders: &[PaperOrder], submit_ms: u64, snapshots: &[BookSnapshot]) -> Result<Vec<PaperFill>, SmartError> {
+    if let Some(guard) = &self.risk {
+      let mid: f64 = snapshots.iter().rev().find(|s| s.time_ms <= submit_ms).and_then(|s| s.book.mid()).unwrap_or(f64::NAN);
+      let notional: f64 = orders.iter().map(|o| o.qty * mid).sum();
+      guard.check_entry(pair_id, if notional.is_finite() { notional } else { 0.0 })?;
+    }
+
+    let fills: Vec<PaperFill> = orders.iter()
+      .map(|order| self.execute(order, submit_ms, snapshots))
+      .collect::<Result<Vec<PaperFill>, SmartError>>()?;
+    if let Some(guard) = self.risk.as_mut() {
+      let filled_notional: f64 = fills.iter().filter(|f| f.filled_qty > 0.0).map(|f| f.filled_qty * f.avg_price).sum();
+      guard.open_pair(pair_id, filled_notional)?;
+    }
+    Ok(fills)
+  }
+
+  /// Execute Exit
+  /// Fills the closing legs of a pair (never blocked by the guard), books pnl against the entry fills leg by leg
+  /// and returns the guard's action, which may ask for the remaining pairs to be flattened
+  pub fn execute_exit(
+    &mut self,
+    pair_id: &str,
+    orders: &[PaperOrder],
+    entry_fills: &[PaperFill],
+    submit_ms: u64,
+    snapshots: &[BookSnapshot]
+  ) -> Result<(Vec<PaperFill>, RiskAction), SmartError> {
+    let fills: Vec<PaperFill> = orders.iter()
+      .map(|order| self.execute(order, submit_ms, snapshots))
+      .collect::<Result<Vec<PaperFill>, SmartError>>()?;
+
+    // Selling closes a long entry, buying closes a short one
+    let realized_pnl: f64 = orders.iter().zip(fills.iter()).zip(entry_fills.iter())
+      .filter(|((_, exit), entry)| exit.filled_qty > 0.0 && entry.filled_qty > 0.0)
+      .map(|((order, exit), entry)| {
+        let direction: f64 = if order.side == OrderSide::Sell { 1.0 } else { -1.0 };
+        direction * (exit.avg_price - entry.avg_price) * exit.filled_qty.min(entry.filled_qty)
+      })
+      .sum();
+    let action: RiskAction = match self.risk.as_mut() {
+      Some(guard) => guard.close_pair(pair_id, realized_pnl, submit_ms),
+      None => RiskAction::Continue
+    };
+    Ok((fills, action))
+  }
+}
+
+/// Fill Against Book
+/// Crosses the opposite side up to the limit (market orders take any price), any limit remainder rests in the queue
+pub fn fill_against_book(order: &PaperOrder, book: &OrderBook, mid_at_submit: f64, latency_ms: u64, arrival_ms: u64) -> PaperFill {
+  let (opposite, same): (&[BookLevel], &[BookLevel]) = match order.side {
+    OrderSide::Buy => (&book.asks, &book.bids),
+    OrderSide::Sell => (&book.bids, &book.asks)
+  };
+  let is_within = |price: f64| match (order.order_type, order.side) {
+    (OrderType::Market, _) => true,
+    (OrderType::Limit(limit), OrderSide::Buy) => price <= limit,
+    (OrderType::Limit(limit), OrderSide::Sell) => price >= limit
+  };
+
+  // Cross the spread level by level
+  let mut remaining: f64 = order.qty;
+  let mut notional: f64 = 0.0;
+  for level in opposite.iter().take_while(|l| is_within(l.price)) {
+    if remaining <= 0.0 { break }
+    let take: f64 = remaining.min(level.qty);
+    notional += take * level.price;
+    remaining -= take;
+  }
+  let filled_qty: f64 = order.qty - remaining;
+  let avg_price: f64 = if filled_qty > 0.0 { notional / filled_qty } else { f64::NAN };
+
+  // Limit remainder joins the back of the queue at its price
+  let (resting_qty, queue_ahead) = match order.order_type {
+    OrderType::Limit(limit) if remaining > 0.0 => {
+      let ahead: f64 = same.iter().filter(|l| (l.price - limit).abs() <= f64::EPSILON * limit.abs().max(1.0)).map(|l| l.qty).sum();
+      (remaining, ahead)
+    },
+    _ => (0.0, 0.0)
+  };
+
+  let direction: f64 = if order.side == OrderSide::Buy { 1.0 } else { -1.0 };
+  let slippage_bps: f64 = if filled_qty > 0.0 { direction * (avg_price - mid_at_submit) / mid_at_submit * 10_000.0 } else { 0.0 };
+  PaperFill {
+    filled_qty,
+    avg_price,
+    resting_qty,
+    queue_ahead,
+    latency_ms,
+    arrival_ms,
+    slippage_bps,
+    is_partial: filled_qty < order.qty
+  }
+}
+
+/// Fill Resting
+/// Quantity of a resting limit order filled once traded_qty has traded at or through its price
+/// The queue ahead fills first, so the order fills only from what trades beyond it
+pub fn fill_resting(resting_qty: f64, queue_ahead: f64, traded_qty: f64) -> f64 {
+  (traded_qty - queue_ahead).max(0.0).min(resting_qty.max(0.0))
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn book() -> OrderBook {
+    OrderBook {
+      bids: vec![BookLevel { price: 99.9, qty: 2.0 }, BookLevel { price: 99.8, qty: 5.0 }],
+      asks: vec![BookLevel { price: 100.1, qty: 1.0 }, BookLevel { price: 100.2, qty: 3.0 }]
+    }
+  }
+
+  fn order(side: OrderSide, order_type: OrderType, qty: f64) -> PaperOrder {
+    PaperOrder { symbol: "ETHUSDT".to_string(), side, order_type, qty }
+  }
+
+  #[tokio::test]
+  async fn it_crosses_the_spread_for_market_orders() {
+    let mut executor: PaperExecutor = PaperExecutor::new(LatencyModel::Fixed(50), 1);
+    let snapshots: Vec<BookSnapshot> = vec![BookSnapshot { time_ms: 1_000, book: book() }];
+
+    let fill: PaperFill = executor.execute(&order(OrderSide::Buy, OrderType::Market, 2.0), 1_000, &snapshots).unwrap();
+    assert!((fill.avg_price - 100.15).abs() < 1e-9);
+    assert!((fill.slippage_bps - 15.0).abs() < 1e-6);
+    assert_eq!((fill.latency_ms, fill.arrival_ms, fill.is_partial), (50, 1_050, false));
+
+    // Book depth exhausted
+    let large: PaperFill = executor.execute(&order(OrderSide::Sell, OrderType::Market, 10.0), 1_000, &snapshots).unwrap();
+    assert_eq!((large.filled_qty, large.is_partial), (7.0, true));
+    assert!(large.slippage_bps > 0.0);
+    assert!(executor.execute(&order(OrderSide::Buy, OrderType::Market, 1.0), 999, &snapshots).is_err());
+  }
+
+  #[tokio::test]
+  async fn it_queues_limit_orders_and_applies_latency() {
+    let fill: PaperFill = fill_against_book(&order(OrderSide::Buy, OrderType::Limit(100.1), 3.0), &book(), 100.0, 0, 0);
+    assert_eq!((fill.filled_qty, fill.resting_qty, fill.queue_ahead), (1.0, 2.0, 0.0));
+
+    let passive: PaperFill = fill_against_book(&order(OrderSide::Buy, OrderType::Limit(99.9), 1.0), &book(), 100.0, 0, 0);
+    assert_eq!((passive.filled_qty, passive.resting_qty, passive.queue_ahead), (0.0, 1.0, 2.0));
+    assert!(passive.avg_price.is_nan());
+    assert_eq!(fill_resting(passive.resting_qty, passive.queue_ahead, 1.5), 0.0);
+    assert_eq!(fill_resting(passive.resting_qty, passive.queue_ahead, 2.5), 0.5);
+    assert_eq!(fill_resting(passive.resting_qty, passive.queue_ahead, 9.0), 1.0);
+
+    // The book moved away before the order arrived
+    let moved: OrderBook = OrderBook {
+      bids: vec![BookLevel { price: 100.4, qty: 1.0 }],
+      asks: vec![BookLevel { price: 100.5, qty: 5.0 }]
+    };
+    let snapshots: Vec<BookSnapshot> = vec![BookSnapshot { time_ms: 0, book: book() }, BookSnapshot { time_ms: 100, book: moved }];
+    let mut executor: PaperExecutor = PaperExecutor::new(LatencyModel::Uniform { min_ms: 150, max_ms: 300 }, 7);
+    let late: PaperFill = executor.execute(&order(OrderSide::Buy, OrderType::Market, 1.0), 0, &snapshots).unwrap();
+    assert!((150..=300).contains(&late.latency_ms));
+    assert_eq!(late.avg_price, 100.5);
+
+    // Seeded latency repeats
+    let mut a: PaperExecutor = PaperExecutor::new(LatencyModel::Normal { mean_ms: 80.0, std_ms: 20.0 }, 42);
+    let mut b: PaperExecutor = PaperExecutor::new(LatencyModel::Normal { mean_ms: 80.0, std_ms: 20.0 }, 42);
+    let samples: Vec<u64> = (0..5).map(|_| a.sample_latency()).collect();
+    assert_eq!(samples, (0..5).map(|_| b.sample_latency()).collect::<Vec<u64>>());
+  }
+
+  #[tokio::test]
+  async fn it_enforces_risk_limits_on_pair_entries() {
+    let limits: RiskLimits = RiskLimits { max_open_pairs: Some(1), max_daily_loss: Some(0.1), ..Default::default() };
+    let mut executor: PaperExecutor = PaperExecutor::new(LatencyModel::Fixed(0), 1).with_risk_limits(limits);
+    let snapshots: Vec<BookSnapshot> = vec![BookSnapshot { time_ms: 0, book: book() }];
+    let entry: Vec<PaperOrder> = vec![order(OrderSide::Buy, OrderType::Market, 1.0)];
+
+    let entry_fills: Vec<PaperFill> = executor.execute_entry("ETH/BTC", &entry, 0, &snapshots).unwrap();
+    assert!(executor.execute_entry("SOL/AVAX", &entry, 0, &snapshots).is_err());
+
+    // Bought at 100.1 and sold at 99.9, the loss breaches the daily limit
+    let exit: Vec<PaperOrder> = vec![order(OrderSide::Sell, OrderType::Market, 1.0)];
+    let (_, action) = executor.execute_exit("ETH/BTC", &exit, &entry_fills, 10, &snapshots).unwrap();
+    assert_eq!(action, RiskAction::HaltEntries);
+    assert!((executor.risk.as_ref().unwrap().daily_pnl() + 0.2).abs() < 1e-9);
+    assert!(executor.execute_entry("SOL/AVAX", &entry, 20, &snapshots).is_err());
+  }
+}
@@ -6,25 +6,40 @@ use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
 use crate::SmartError;
-use super::backtest::evaluation::BacktestMetrics;
-use super::backtest::models::{Backtest, BacktestCriteria, LongSeries, TriggerIndicator, Relation};
-use super::pricing::models::{AssetType, DataCriteria, Exchange, PairPrices, QuotePrice};
+use super::backtest::evaluation::{BacktestMetrics, EvaluationConfig};
+use super::backtest::models::{Backtest, BacktestCriteria, LongSeries, TriggerIndicator, Relation, SignalTiming};
+use super::pricing::models::{AssetType, DataCriteria, DataTolerance, EventMarker, Exchange, IntervalPeriod, MissingDataPolicy, PairPrices, QuotePrice};
 use super::pricing::symbols::request_symbols;
-use super::pricing::entry::fetch_prices;
+use super::pricing::health::{exchange_health, ExchangeHealth};
+use super::pricing::entry::{fetch_prices, get_prices_cross_venue, get_prices_pair_with_options};
 use super::pricing::quotes::request_quote;
 use super::pricing::quotemulti::request_multi_quote;
 use super::stats::models::{SpreadType, Statistics, Coint};
+use super::stats::preprocess::PreprocessConfig;
+use super::stats::arbitrage::{arb_backtest_criteria, ArbStats};
+use super::stats::indicators::funding_signal_source;
+use super::pricing::basis::{fetch_basis, BasisType};
+use super::pricing::funding::{request_funding_rates, FundingRates};
+use super::pricing::fees::FeeSide;
+use super::pricing::forex::forex_cost_per_leg;
+use super::presets::{AssetPreset, DEFAULT_TRADING_DAYS};
+use super::screener::{query_screener, screen_pairs, ScreenerCriteria, ScreenerPage, ScreenerQuery};
 use super::stats::metrics::{
   spread_dynamic_kalman, spread_static_std, rolling_zscore, 
   cointegration_test_eg, pearson_correlation_coefficient, half_life_mean_reversion
 };
 
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
+
 #[derive(Debug, Deserialize, Serialize, Clone, TS)]
 #[ts(export)]
 pub struct StatsCriteria {
   pub spread_type: SpreadType,
   pub zscore_window: usize,
-  pub roll_window: usize
+  pub roll_window: usize,
+  #[serde(default)]
+  pub preprocess: Option<PreprocessConfig>
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, TS)]
@@ -32,7 +47,11 @@ pub struct StatsCriteria {
 pub struct AnalysisCriteria {
   pub data_criteria: DataCriteria,
   pub stats_criteria: Option<StatsCriteria>,
-  pub backtest_criteria: Option<BacktestCriteria>
+  pub backtest_criteria: Option<BacktestCriteria>,
+  #[serde(default)]
+  pub missing_data_policy: MissingDataPolicy,
+  #[serde(default)]
+  pub asset_type: Option<AssetType> // selects the asset preset (inferred from the exchange and asset_0 if none)
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, TS)]
@@ -40,6 +59,39 @@ pub struct AnalysisCriteria {
 pub struct PairAnalysis {
   pub prices: PairPrices,
   pub stats: Statistics,
+  pub bt_metrics: BacktestMetrics,
+  #[serde(default)]
+  pub event_markers: Vec<EventMarker>
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct ArbCriteria {
+  pub symbol: String,
+  pub exchange_0: Exchange,
+  pub exchange_1: Exchange,
+  pub interval_period: IntervalPeriod,
+  pub zscore_window: usize,
+  pub backtest_criteria: Option<BacktestCriteria>,
+  #[serde(default)]
+  pub missing_data_policy: MissingDataPolicy
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct ArbAnalysis {
+  pub prices: PairPrices,
+  pub arb_stats: ArbStats,
+  pub bt_metrics: BacktestMetrics
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct FundingAnalysis {
+  pub prices: PairPrices, // series_0 perp, series_1 spot
+  pub funding_rates: Vec<f64>, // latest rate known at each bar
+  pub funding_zscore: Vec<f64>,
+  pub funding_per_bar: Vec<f64>,
   pub bt_metrics: BacktestMetrics
 }
 
@@ -55,6 +107,49 @@ pub async fn multi_symbol_quote(exchange: &Exchange, symbols: Vec<&str>, twelve_
   request_multi_quote(exchange, symbols, twelve_api_key).await
 }
 
+/// Default Backtest Criteria
+/// Zscore mean reversion criteria used when none are supplied
+pub fn default_backtest_criteria(indicator_values: Vec<f64>) -> BacktestCriteria {
+  BacktestCriteria {
+    indicator_values,
+    trigger_indicator: TriggerIndicator::Zscore,
+    relation: Relation::Ignore,
+    cost_per_leg: Some(0.0005),
+    rets_weighting_s0_perc: 0.5,
+    long_series: LongSeries::Series0,
+    stop_loss: 0.0,
+    long_thresh: -1.5,
+    long_close_thresh: 0.0,
+    short_thresh: 1.5,
+    short_close_thresh: 0.0,
+    long_entry: None,
+    short_entry: None,
+    index_beta: None,
+    exchange: None,
+    fee_side: FeeSide::Taker,
+    warmup_bars: None,
+    signal_timing: SignalTiming::SameClose
+  }
+}
+
+/// Default Backtest Criteria For
+/// Default criteria from the pair's asset preset (Twelve forex pairs use session spread costs)
+pub fn default_backtest_criteria_for(data_criteria: &DataCriteria, prices: &PairPrices, indicator_values: Vec<f64>) -> BacktestCriteria {
+  let preset: AssetPreset = AssetPreset::infer(&data_criteria.exchange, &data_criteria.asset_0);
+  preset_backtest_criteria(&preset, data_criteria, prices, indicator_values)
+}
+
+/// Preset Backtest Criteria
+/// Preset thresholds and costs, with forex session spread costs where available
+fn preset_backtest_criteria(preset: &AssetPreset, data_criteria: &DataCriteria, prices: &PairPrices, indicator_values: Vec<f64>) -> BacktestCriteria {
+  let exchange: &Exchange = prices.source.as_ref().unwrap_or(&data_criteria.exchange);
+  let criteria: BacktestCriteria = BacktestCriteria { exchange: Some(exchange.clone()), ..preset.backtest_criteria(indicator_values) };
+  match forex_cost_per_leg(exchange, &data_criteria.asset_0, &data_criteria.asset_1, prices) {
+    Some(cost) => BacktestCriteria { cost_per_leg: Some(cost), ..criteria },
+    None => criteria
+  }
+}
+
 /// Full Analysis From Pair Prices
 /// Retrieves Stats, Eval Metrics and ML Metrics given the pair prices
 pub async fn full_analysis_from_pair_prices(
@@ -62,46 +157,64 @@ pub async fn full_analysis_from_pair_prices(
   stats_criteria_opt: Option<StatsCriteria>,
   backtest_criteria_opt: Option<BacktestCriteria>
 ) -> Result<PairAnalysis, SmartError> {
+  analysis_from_pair_prices(prices, stats_criteria_opt, backtest_criteria_opt, None)
+}
+
+/// Analysis From Pair Prices
+/// Full analysis annualized and (if no criteria supplied) costed by the pair's asset preset
+fn analysis_from_pair_prices(
+  prices: PairPrices,
+  stats_criteria_opt: Option<StatsCriteria>,
+  backtest_criteria_opt: Option<BacktestCriteria>,
+  preset_opt: Option<(&AssetPreset, &DataCriteria)>
+) -> Result<PairAnalysis, SmartError> {
+
+  // Guard: Partial data is not accepted for backtesting
+  prices.ensure_complete()?;
+
+  let (calc_type, z_score_w, roll_w, preprocess) = match stats_criteria_opt {
+    Some(st) => (st.spread_type, st.zscore_window, st.roll_window, st.preprocess),
+    None => (SpreadType::Dynamic, 35, 90, None)
+  };
+
+  // Preprocess outliers (original prices are preserved in the analysis output)
+  let cleaned: PairPrices = match &preprocess {
+    Some(config) => prices.preprocessed(config)?,
+    None => prices.clone()
+  };
 
-  let (calc_type, z_score_w, roll_w) = match stats_criteria_opt {
-    Some(st) => (st.spread_type, st.zscore_window, st.roll_window),
-    None => (SpreadType::Dynamic, 35, 90)
+  let periods_per_year: f64 = match preset_opt {
+    Some((preset, data_criteria)) => preset.periods_per_year(&data_criteria.interval_period),
+    None => DEFAULT_TRADING_DAYS as f64
   };
 
-  let stats: Statistics = Statistics::calculate_statistics(
-    &prices.series_0, 
-    &prices.series_1, 
+  let stats: Statistics = Statistics::calculate_statistics_annualized(
+    &cleaned.series_0, 
+    &cleaned.series_1, 
     calc_type, 
     z_score_w,
-    roll_w
+    roll_w,
+    periods_per_year as usize
   )?;
 
-  let backtest_criteria: BacktestCriteria = match backtest_criteria_opt {
-    Some(bt) => bt,
-    None => BacktestCriteria {
-      indicator_values: stats.zscore.clone(),
-      trigger_indicator: TriggerIndicator::Zscore,
-      relation: Relation::Ignore,
-      cost_per_leg: Some(0.0005),
-      rets_weighting_s0_perc: 0.5,
-      long_series: LongSeries::Series0,
-      stop_loss: 0.0,
-      long_thresh: -1.5,
-      long_close_thresh: 0.0,
-      short_thresh: 1.5,
-      short_close_thresh: 0.0
-    },
+  let backtest_criteria: BacktestCriteria = match (backtest_criteria_opt, preset_opt) {
+    (Some(bt), _) => bt,
+    (None, Some((preset, data_criteria))) => preset_backtest_criteria(preset, data_criteria, &prices, stats.zscore.clone()),
+    (None, None) => default_backtest_criteria(stats.zscore.clone())
   };
 
+  let eval_config: EvaluationConfig = EvaluationConfig { periods_per_year, ..Default::default() };
   let backtest: Backtest = Backtest::new(
-    &prices.series_0,
-    &prices.series_1,
+    &cleaned.series_0,
+    &cleaned.series_1,
     backtest_criteria
-  );
+  )?.with_excluded_bars(prices.excluded_bars())?
+    .with_evaluation_config(eval_config);
 
   let bt_metrics: BacktestMetrics = backtest.run_backtest()?;
+  let event_markers: Vec<EventMarker> = prices.event_markers();
 
-  Ok(PairAnalysis { prices, stats, bt_metrics })
+  Ok(PairAnalysis { prices, stats, bt_metrics, event_markers })
 }
 
 /// Pair Prices
@@ -119,20 +232,129 @@ pub async fn pair_prices(data_criteria: DataCriteria, twelve_api_key: Option<&st
 /// Full Pair Analysis
 /// Retrieves Prices, Stats, Eval Metrics and ML Metrics
 pub async fn full_pair_analysis(analysis_criteria: AnalysisCriteria, twelve_api_key: Option<&str>) -> Result<PairAnalysis, SmartError> {
-  let prices: PairPrices = pair_prices(analysis_criteria.data_criteria, twelve_api_key).await?;
-  let analysis: PairAnalysis = full_analysis_from_pair_prices(
+  let data_criteria: DataCriteria = analysis_criteria.data_criteria.clone();
+  let preset: AssetPreset = match &analysis_criteria.asset_type {
+    Some(asset_type) => AssetPreset::for_asset_type(asset_type),
+    None => AssetPreset::infer(&data_criteria.exchange, &data_criteria.asset_0)
+  };
+  let prices: PairPrices = get_prices_pair_with_options(
+    analysis_criteria.data_criteria,
+    twelve_api_key,
+    DataTolerance::Strict,
+    analysis_criteria.missing_data_policy
+  ).await?;
+  let analysis: PairAnalysis = analysis_from_pair_prices(
     prices, 
     analysis_criteria.stats_criteria, 
-    analysis_criteria.backtest_criteria
-  ).await?;
+    analysis_criteria.backtest_criteria,
+    Some((&preset, &data_criteria))
+  )?;
   Ok(analysis)
 }
 
+/// Cross Venue Analysis From Pair Prices
+/// Premium stats and arbitrage backtest for the same asset on two venues (series_0 venue 0, series_1 venue 1)
+pub fn cross_venue_analysis_from_pair_prices(
+  prices: PairPrices,
+  zscore_window: usize,
+  backtest_criteria_opt: Option<BacktestCriteria>
+) -> Result<ArbAnalysis, SmartError> {
+
+  // Guard: Partial data is not accepted for backtesting
+  prices.ensure_complete()?;
+
+  let arb_stats: ArbStats = ArbStats::calculate(&prices.series_0, &prices.series_1, zscore_window)?;
+  let backtest_criteria: BacktestCriteria = match backtest_criteria_opt {
+    Some(bt) => bt,
+    None => arb_backtest_criteria(arb_stats.zscore.clone())
+  };
+
+  let backtest: Backtest = Backtest::new(&prices.series_0, &prices.series_1, backtest_criteria)?
+    .with_excluded_bars(prices.excluded_bars())?;
+  let bt_metrics: BacktestMetrics = backtest.run_backtest()?;
+
+  Ok(ArbAnalysis { prices, arb_stats, bt_metrics })
+}
+
+/// Cross Venue Analysis
+/// Retrieves the asset from both venues and runs the arbitrage analysis
+pub async fn cross_venue_analysis(arb_criteria: ArbCriteria, twelve_api_key: Option<&str>) -> Result<ArbAnalysis, SmartError> {
+  let prices: PairPrices = get_prices_cross_venue(
+    &arb_criteria.symbol,
+    &arb_criteria.exchange_0,
+    &arb_criteria.exchange_1,
+    &arb_criteria.interval_period,
+    twelve_api_key,
+    arb_criteria.missing_data_policy
+  ).await?;
+  cross_venue_analysis_from_pair_prices(prices, arb_criteria.zscore_window, arb_criteria.backtest_criteria)
+}
+
+/// Funding Harvest From Pair Prices
+/// Trades the funding rate zscore with series_0 as the perp and series_1 as the hedge, accruing funding on held positions
+/// Base criteria supply costs, weighting and stop loss (defaults used if none)
+pub fn funding_harvest_from_pair_prices(
+  prices: PairPrices,
+  funding: &FundingRates,
+  interval_period: &IntervalPeriod,
+  zscore_window: usize,
+  entry_zscore: f64,
+  base_criteria_opt: Option<BacktestCriteria>
+) -> Result<FundingAnalysis, SmartError> {
+
+  // Guard: Partial data is not accepted for backtesting
+  prices.ensure_complete()?;
+
+  let funding_rates: Vec<f64> = funding.rates_at_labels(&prices.labels, prices.label_convention, interval_period);
+  let funding_per_bar: Vec<f64> = funding.payments_per_bar(&prices.labels, prices.label_convention, interval_period);
+  let source = funding_signal_source(&funding_rates, zscore_window, entry_zscore)?;
+  let funding_zscore: Vec<f64> = source.values.clone();
+
+  let base: BacktestCriteria = base_criteria_opt.unwrap_or_else(|| default_backtest_criteria(vec![]));
+  let backtest: Backtest = Backtest::new(&prices.series_0, &prices.series_1, source.into_criteria(base))?
+    .with_excluded_bars(prices.excluded_bars())?
+    .with_funding(funding_per_bar.clone())?;
+  let bt_metrics: BacktestMetrics = backtest.run_backtest()?;
+
+  Ok(FundingAnalysis { prices, funding_rates, funding_zscore, funding_per_bar, bt_metrics })
+}
+
+/// Funding Harvest Analysis
+/// Retrieves perp and spot candles plus funding history for the symbol and runs the funding harvest backtest
+pub async fn funding_harvest_analysis(
+  exchange: &Exchange,
+  symbol: &str,
+  interval_period: &IntervalPeriod,
+  zscore_window: usize,
+  entry_zscore: f64,
+  base_criteria_opt: Option<BacktestCriteria>
+) -> Result<FundingAnalysis, SmartError> {
+  let (basis, funding) = futures::join!(
+    fetch_basis(exchange, symbol, interval_period, BasisType::Percent),
+    request_funding_rates(exchange, symbol)
+  );
+  let prices: PairPrices = basis?.to_pair_prices();
+  funding_harvest_from_pair_prices(prices, &funding?, interval_period, zscore_window, entry_zscore, base_criteria_opt)
+}
+
 /*
   WASM
   Web Assembly Calls
 */
 
+/// WASM Entry - Init Thread Pool
+/// Starts the web worker pool used by rolling cointegration and batch screening (wasm-threads feature only)
+/// Requires cross-origin isolation; from JS: await initThreadPool(navigator.hardwareConcurrency)
+#[cfg(all(feature = "wasm-threads", target_arch = "wasm32"))]
+pub use wasm_bindgen_rayon::init_thread_pool;
+
+/// WASM Entry - Stats Threads
+/// Threads available to parallel statistics (1 when the pool is not started and work runs on the calling thread)
+#[wasm_bindgen]
+pub fn wasm_stats_threads() -> usize {
+  rayon::current_num_threads()
+}
+
 /// WASM Entry - Exchange Tickers
 /// Provides 
 #[wasm_bindgen]
@@ -196,6 +418,15 @@ pub async fn wasm_exchange_quotes() -> Result<String, String> {
   Ok(serde_json::to_string(&results).unwrap_or_else(|e| e.to_string()))
 }
 
+/// WASM Entry - Exchange Health
+/// Probes an exchange for latency and operational status
+#[wasm_bindgen]
+pub async fn wasm_exchange_health(exchange: String) -> Result<String, String> {
+  let exchange: Exchange = Exchange::create_from_string(exchange.as_str());
+  let health: ExchangeHealth = exchange_health(&exchange).await;
+  serde_json::to_string(&health).map_err(|e| e.to_string())
+}
+
 /// WASM Entry - Pair Prices
 /// Retrieves Prices for given pair
 #[wasm_bindgen]
@@ -251,8 +482,7 @@ pub async fn wasm_quick_stats(json_input: String, zscore_window_str: String) ->
   let corr: f64 = pearson_correlation_coefficient(&pair_prices.series_0, &pair_prices.series_1).map_err(|e| e.to_string())?;
   
   // Relationship
-  let trading_days: usize = 252;
-  let relationship: Relationship = calculate_relaitonship(&pair_prices.series_0, &pair_prices.series_1, trading_days).map_err(|e| e.to_string())?;
+  let relationship: Relationship = calculate_relaitonship(&pair_prices.series_0, &pair_prices.series_1, DEFAULT_TRADING_DAYS).map_err(|e| e.to_string())?;
 
   let stats_static: QuickStats = QuickStats { 
     spread: spread_static,
@@ -283,6 +513,9 @@ pub async fn wasm_quick_backtest(pair_prices_json: String, bt_criteria_json: Str
   // Deserialize - Pair Prices
   let pair_prices: PairPrices = serde_json::from_str::<PairPrices>(&pair_prices_json).map_err(|e| e.to_string())?;
 
+  // Guard: Partial data is not accepted for backtesting
+  pair_prices.ensure_complete().map_err(|e| e.to_string())?;
+
   // Deserialize - Backtest Criteria
   let bt_criteria: BacktestCriteria = serde_json::from_str::<BacktestCriteria>(&bt_criteria_json).map_err(|e| e.to_string())?;
 
@@ -291,7 +524,7 @@ pub async fn wasm_quick_backtest(pair_prices_json: String, bt_criteria_json: Str
     &pair_prices.series_0,
     &pair_prices.series_1,
     bt_criteria
-  );
+  ).and_then(|bt| bt.with_excluded_bars(pair_prices.excluded_bars())).map_err(|e| e.to_string())?;
 
   // Perform Backtest
   let bt_metrics: BacktestMetrics = backtest.run_backtest().map_err(|e| e.to_string())?;
@@ -327,11 +560,67 @@ pub async fn wasm_full_pair_analysis_crypto(json_input: String) -> Result<String
 }
 
 
+/// WASM Entry - Screen Pairs
+/// Screens every pair of the symbols and returns the first page (rows are kept for wasm_screener_page)
+/// Only for use on exchanges as no api key should be sent via wasm
+#[wasm_bindgen]
+pub async fn wasm_screen_pairs(criteria_json: String, query_json: String) -> Result<String, String> {
+  let criteria: ScreenerCriteria = serde_json::from_str::<ScreenerCriteria>(&criteria_json).map_err(|e| e.to_string())?;
+  let query: ScreenerQuery = serde_json::from_str::<ScreenerQuery>(&query_json).map_err(|e| e.to_string())?;
+  screen_pairs(&criteria, None).await.map_err(|e| e.to_string())?;
+  let page: ScreenerPage = query_screener(&criteria.screen_id(), &query).map_err(|e| e.to_string())?;
+  serde_json::to_string::<ScreenerPage>(&page).map_err(|e| e.to_string())
+}
+
+/// WASM Entry - Screener Page
+/// Pages, sorts and filters the rows of an earlier screen without re-running it
+#[wasm_bindgen]
+pub fn wasm_screener_page(screen_id: String, query_json: String) -> Result<String, String> {
+  let query: ScreenerQuery = serde_json::from_str::<ScreenerQuery>(&query_json).map_err(|e| e.to_string())?;
+  let page: ScreenerPage = query_screener(&screen_id, &query).map_err(|e| e.to_string())?;
+  serde_json::to_string::<ScreenerPage>(&page).map_err(|e| e.to_string())
+}
+
+
 #[cfg(test)]
 mod tests {
   use super::*;
   use crate::pricing::models::{DataCriteria, Exchange, IntervalPeriod};
 
+  #[tokio::test]
+  async fn it_harvests_funding_from_prices() {
+    let n: u64 = 120;
+    let series_0: Vec<f64> = (0..n).map(|i| 100.0 + (i as f64 * 0.2).sin()).collect();
+    let series_1: Vec<f64> = series_0.clone();
+    let labels: Vec<u64> = (0..n).map(|i| i * 3600).collect();
+    let prices: PairPrices = PairPrices { series_0, series_1, labels, label_convention: Default::default(), fetch_report: None, events: vec![], source: None };
+
+    // Funding every 8 hours, spiking from hour 64 onwards
+    let events: Vec<(u64, f64)> = (1..n / 8).map(|k| (k * 8 * 3600, if k >= 8 { 0.001 } else { 0.0001 * (k % 2) as f64 })).collect();
+    let funding: FundingRates = FundingRates::from_events(events);
+    let interval: IntervalPeriod = IntervalPeriod::Hour(1, n as u32);
+    let base: BacktestCriteria = BacktestCriteria { cost_per_leg: Some(0.0), ..default_backtest_criteria(vec![]) };
+
+    let analysis: FundingAnalysis = funding_harvest_from_pair_prices(prices, &funding, &interval, 24, 1.5, Some(base)).unwrap();
+    assert_eq!(analysis.funding_per_bar.len(), n as usize);
+    assert!(analysis.funding_zscore.iter().any(|z| *z >= 1.5));
+
+    // Identical legs so the only return is the funding received while short the perp
+    assert!(*analysis.bt_metrics.equity_curve.last().unwrap() > 0.0);
+  }
+
+  #[tokio::test]
+  async fn it_performs_cross_venue_analysis_from_prices() {
+    let series_1: Vec<f64> = (0..200).map(|i| 100.0 + (i as f64 * 0.3).sin()).collect();
+    let series_0: Vec<f64> = series_1.iter().enumerate().map(|(i, p)| p * (1.0 + 0.001 * (i as f64 * 0.7).sin())).collect();
+    let labels: Vec<u64> = (0..200).map(|i| i * 3600).collect();
+    let prices: PairPrices = PairPrices { series_0, series_1, labels, label_convention: Default::default(), fetch_report: None, events: vec![], source: None };
+
+    let analysis: ArbAnalysis = cross_venue_analysis_from_pair_prices(prices, 20, None).unwrap();
+    assert_eq!(analysis.arb_stats.premium_bps.len(), 200);
+    assert!(analysis.arb_stats.max_abs_premium_bps <= 10.0 + 1e-9);
+  }
+
   #[tokio::test]
   async fn it_performs_full_pair_analysis() {
 
@@ -344,13 +633,16 @@ mod tests {
       exchange, 
       asset_0, 
       asset_1, 
-      interval_period
+      interval_period,
+      fallback_exchanges: vec![]
     };
 
     let analysis_criteria: AnalysisCriteria = AnalysisCriteria {
       data_criteria,
       stats_criteria: None,
-      backtest_criteria: None
+      backtest_criteria: None,
+      missing_data_policy: MissingDataPolicy::DropBar,
+      asset_type: None
     };
 
     let json_input: String = serde_json::to_string::<AnalysisCriteria>(&analysis_criteria).unwrap();
@@ -394,7 +686,8 @@ mod tests {
       exchange, 
       asset_0, 
       asset_1, 
-      interval_period
+      interval_period,
+      fallback_exchanges: vec![]
     };
 
     let prices: PairPrices = pair_prices(data_criteria, None).await.unwrap();
@@ -412,7 +705,14 @@ mod tests {
       long_thresh: -1.5,
       long_close_thresh: 0.0,
       short_thresh: 1.5,
-      short_close_thresh: 0.0
+      short_close_thresh: 0.0,
+      long_entry: None,
+      short_entry: None,
+      index_beta: None,
+      exchange: None,
+      fee_side: FeeSide::Taker,
+      warmup_bars: None,
+      signal_timing: SignalTiming::SameClose
     };
 
     let pair_prices_json = serde_json::to_string(&prices).unwrap();
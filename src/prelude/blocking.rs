@@ -0,0 +1,68 @@
+use std::future::Future;
+
+use crate::SmartError;
+use crate::backtest::models::BacktestCriteria;
+use crate::pricing::models::{DataCriteria, Exchange, PairPrices, QuotePrice};
+use super::{AnalysisCriteria, PairAnalysis, StatsCriteria};
+
+/*
+  Blocking API
+  Wraps the async prelude with a built-in runtime (feature "blocking")
+  Allows simple scripts and examples to run without setting up tokio
+*/
+
+/// Block On
+/// Runs a future to completion on a single threaded runtime
+fn block_on<F: Future>(future: F) -> Result<F::Output, SmartError> {
+  let runtime: tokio::runtime::Runtime = tokio::runtime::Builder::new_current_thread()
+    .enable_all()
+    .build()?;
+  Ok(runtime.block_on(future))
+}
+
+/// Single Quote
+/// Retrieves a single quote from an exchange provider
+pub fn single_quote(exchange: &Exchange, symbol: &str, twelve_api_key: Option<&str>) -> Result<f64, SmartError> {
+  block_on(super::single_quote(exchange, symbol, twelve_api_key))?
+}
+
+/// Multi Symbol Quote
+/// Retrieves quotes for multiple symbols from an exchange provider
+pub fn multi_symbol_quote(exchange: &Exchange, symbols: Vec<&str>, twelve_api_key: Option<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  block_on(super::multi_symbol_quote(exchange, symbols, twelve_api_key))?
+}
+
+/// Pair Prices
+/// Retrieves Prices
+pub fn pair_prices(data_criteria: DataCriteria, twelve_api_key: Option<&str>) -> Result<PairPrices, SmartError> {
+  block_on(super::pair_prices(data_criteria, twelve_api_key))?
+}
+
+/// Full Analysis From Pair Prices
+/// Retrieves Stats, Eval Metrics and ML Metrics given the pair prices (no network access)
+///
+/// ```
+/// use zscore_lib::prelude::blocking;
+/// use zscore_lib::pricing::models::PairPrices;
+///
+/// let series_1: Vec<f64> = (0..300).map(|i| 100.0 + (i as f64 / 10.0).sin() * 5.0 + i as f64 * 0.1).collect();
+/// let series_0: Vec<f64> = series_1.iter().enumerate().map(|(i, p)| p * 2.0 + (i as f64 / 3.0).cos() * 2.0).collect();
+/// let labels: Vec<u64> = (0..300).map(|i| i * 3600).collect();
+/// let prices = PairPrices { series_0, series_1, labels, label_convention: Default::default(), fetch_report: None, events: vec![], source: None };
+///
+/// let analysis = blocking::full_analysis_from_pair_prices(prices, None, None).unwrap();
+/// assert_eq!(analysis.stats.zscore.len(), 300);
+/// ```
+pub fn full_analysis_from_pair_prices(
+  prices: PairPrices,
+  stats_criteria_opt: Option<StatsCriteria>,
+  backtest_criteria_opt: Option<BacktestCriteria>
+) -> Result<PairAnalysis, SmartError> {
+  block_on(super::full_analysis_from_pair_prices(prices, stats_criteria_opt, backtest_criteria_opt))?
+}
+
+/// Full Pair Analysis
+/// Retrieves Prices, Stats, Eval Metrics and ML Metrics
+pub fn full_pair_analysis(analysis_criteria: AnalysisCriteria, twelve_api_key: Option<&str>) -> Result<PairAnalysis, SmartError> {
+  block_on(super::full_pair_analysis(analysis_criteria, twelve_api_key))?
+}
@@ -0,0 +1,144 @@
+use crate::backtest::evaluation::EvaluationConfig;
+use crate::backtest::models::BacktestCriteria;
+use crate::prelude::default_backtest_criteria;
+use crate::pricing::calendar::MarketCalendar;
+use crate::pricing::models::{AssetType, Exchange, IntervalPeriod};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/*
+  Asset Presets
+  Trading days, costs and thresholds suited to each asset class
+  Annualization follows the bars per year the asset actually trades (24/7 crypto, 24/5 forex, one session for equities)
+*/
+
+/// Trading days assumed where no asset class is known
+pub const DEFAULT_TRADING_DAYS: usize = 252;
+
+/// NYSE regular session length in seconds
+const EQUITY_SESSION_SECS: u64 = 23400;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct AssetPreset {
+  pub asset_type: AssetType,
+  pub calendar: MarketCalendar,
+  pub trading_days: usize,
+  pub cost_per_leg: f64,
+  pub long_thresh: f64,
+  pub long_close_thresh: f64,
+  pub short_thresh: f64,
+  pub short_close_thresh: f64
+}
+
+impl AssetPreset {
+
+  /// For Asset Type
+  /// Preset for an asset class
+  pub fn for_asset_type(asset_type: &AssetType) -> Self {
+    let calendar: MarketCalendar = MarketCalendar::from_asset_type(asset_type);
+    let (trading_days, cost_per_leg, thresh) = match asset_type {
+      AssetType::Crypto => (365, 0.0005, 1.5),
+      AssetType::Forex => (260, 0.00005, 2.0),
+      AssetType::Etf => (DEFAULT_TRADING_DAYS, 0.0001, 2.0),
+      AssetType::Indices => (DEFAULT_TRADING_DAYS, 0.0002, 2.0),
+      AssetType::Stock => (DEFAULT_TRADING_DAYS, 0.0003, 2.0)
+    };
+    Self {
+      asset_type: asset_type.clone(),
+      calendar,
+      trading_days,
+      cost_per_leg,
+      long_thresh: -thresh,
+      long_close_thresh: 0.0,
+      short_thresh: thresh,
+      short_close_thresh: 0.0
+    }
+  }
+
+  /// Infer
+  /// Preset implied by an exchange and symbol (stocks assumed where the calendar is US equity)
+  pub fn infer(exchange: &Exchange, symbol: &str) -> Self {
+    let asset_type: AssetType = match MarketCalendar::infer(exchange, symbol) {
+      MarketCalendar::Continuous => AssetType::Crypto,
+      MarketCalendar::Forex => AssetType::Forex,
+      MarketCalendar::UsEquity => AssetType::Stock
+    };
+    Self::for_asset_type(&asset_type)
+  }
+
+  /// Periods Per Year
+  /// Bars per year at the given interval, counting only the hours the asset trades
+  pub fn periods_per_year(&self, interval_period: &IntervalPeriod) -> f64 {
+    let step: u64 = interval_period.interval_seconds().max(1);
+    let session_secs: u64 = match self.calendar {
+      MarketCalendar::Continuous | MarketCalendar::Forex => 86400,
+      MarketCalendar::UsEquity => EQUITY_SESSION_SECS
+    };
+    let bars_per_day: f64 = (session_secs as f64 / step as f64).ceil().max(1.0);
+    let days_per_bar: f64 = (step as f64 / 86400.0).max(1.0);
+    self.trading_days as f64 * bars_per_day / days_per_bar
+  }
+
+  /// Backtest Criteria
+  /// Default zscore criteria with the preset thresholds and costs
+  pub fn backtest_criteria(&self, indicator_values: Vec<f64>) -> BacktestCriteria {
+    BacktestCriteria {
+      cost_per_leg: Some(self.cost_per_leg),
+      long_thresh: self.long_thresh,
+      long_close_thresh: self.long_close_thresh,
+      short_thresh: self.short_thresh,
+      short_close_thresh: self.short_close_thresh,
+      ..default_backtest_criteria(indicator_values)
+    }
+  }
+
+  /// Evaluation Config
+  /// Default evaluation annualized for the preset at the given interval
+  pub fn evaluation_config(&self, interval_period: &IntervalPeriod) -> EvaluationConfig {
+    EvaluationConfig { periods_per_year: self.periods_per_year(interval_period), ..Default::default() }
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_selects_presets_by_asset_type() {
+    let crypto: AssetPreset = AssetPreset::infer(&Exchange::Binance, "BTCUSDT");
+    assert_eq!(crypto.asset_type, AssetType::Crypto);
+    assert_eq!(crypto.trading_days, 365);
+
+    let stock: AssetPreset = AssetPreset::infer(&Exchange::Twelve, "AAPL");
+    assert_eq!(stock.calendar, MarketCalendar::UsEquity);
+    assert_eq!(stock.trading_days, DEFAULT_TRADING_DAYS);
+
+    let etf: BacktestCriteria = AssetPreset::for_asset_type(&AssetType::Etf).backtest_criteria(vec![]);
+    assert_eq!(etf.cost_per_leg, Some(0.0001));
+    assert_eq!(etf.short_thresh, 2.0);
+  }
+
+  #[tokio::test]
+  async fn it_matches_default_criteria_for_crypto() {
+    let preset: BacktestCriteria = AssetPreset::for_asset_type(&AssetType::Crypto).backtest_criteria(vec![]);
+    let default: BacktestCriteria = default_backtest_criteria(vec![]);
+    assert_eq!(preset.cost_per_leg, default.cost_per_leg);
+    assert_eq!(preset.long_thresh, default.long_thresh);
+    assert_eq!(preset.short_thresh, default.short_thresh);
+  }
+
+  #[tokio::test]
+  async fn it_annualizes_by_trading_hours() {
+    let crypto: AssetPreset = AssetPreset::for_asset_type(&AssetType::Crypto);
+    let stock: AssetPreset = AssetPreset::for_asset_type(&AssetType::Stock);
+    let forex: AssetPreset = AssetPreset::for_asset_type(&AssetType::Forex);
+
+    assert_eq!(crypto.periods_per_year(&IntervalPeriod::Hour(1, 100)), 365.0 * 24.0);
+    assert_eq!(stock.periods_per_year(&IntervalPeriod::Hour(1, 100)), 252.0 * 7.0);
+    assert_eq!(stock.periods_per_year(&IntervalPeriod::Day(1, 100)), 252.0);
+    assert_eq!(forex.periods_per_year(&IntervalPeriod::Day(1, 100)), 260.0);
+    assert_eq!(stock.evaluation_config(&IntervalPeriod::Day(1, 100)).periods_per_year, 252.0);
+  }
+}
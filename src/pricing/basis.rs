@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use super::candles::CandleBuilder;
+use super::models::{Exchange, HistoricalPrices, IntervalPeriod, PairPrices};
+use super::files::align_historical_prices;
+
+/*
+  Basis
+  Perpetual minus spot for the same underlying, aligned on candle labels
+  Basis mean reversion can then be analysed with the same engine as any other pair
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, TS)]
+#[ts(export)]
+pub enum BasisType {
+  Absolute, // perp - spot
+  Percent // (perp - spot) / spot * 100
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct BasisSeries {
+  pub labels: Vec<u64>,
+  pub spot: Vec<f64>,
+  pub perp: Vec<f64>,
+  pub basis: Vec<f64>,
+  pub basis_type: BasisType
+}
+
+impl BasisSeries {
+
+  /// To Pair Prices
+  /// Perp as series 0 and spot as series 1 for use with the stats and backtest engine
+  pub fn to_pair_prices(&self) -> PairPrices {
+    PairPrices {
+      series_0: self.perp.clone(),
+      series_1: self.spot.clone(),
+      labels: self.labels.clone(),
+      label_convention: Default::default(),
+      fetch_report: None,
+      events: vec![],
+      source: None
+    }
+  }
+}
+
+/// Spot Query Url
+/// Spot kline endpoint for exchanges whose candle builder defaults to perpetuals
+pub fn spot_query_url(exchange: &Exchange) -> Option<String> {
+  match exchange {
+    Exchange::Binance => Some("https://api.binance.com/api/v3/klines?symbol={symbol}&interval={interval}&startTime={fromTime}&endTime={toTime}&limit={limit}".to_string()),
+    Exchange::ByBit => Some("https://api.bybit.com/v5/market/kline?category=spot&symbol={symbol}&interval={interval}&start={fromTime}&end={toTime}&limit={limit}".to_string()),
+    _ => None
+  }
+}
+
+/// Build Basis
+/// Aligns spot and perp prices on matching labels and calculates the basis
+pub fn build_basis(spot: HistoricalPrices, perp: HistoricalPrices, basis_type: BasisType) -> Result<BasisSeries, SmartError> {
+  let aligned: PairPrices = align_historical_prices(spot, perp)?;
+  let (spot, perp, labels) = (aligned.series_0, aligned.series_1, aligned.labels);
+
+  let basis: Vec<f64> = spot.iter().zip(perp.iter())
+    .map(|(s, p)| match basis_type {
+      BasisType::Absolute => p - s,
+      BasisType::Percent => (p - s) / s * 100.0
+    })
+    .collect();
+
+  Ok(BasisSeries { labels, spot, perp, basis, basis_type })
+}
+
+/// Fetch Basis
+/// Fetches spot and perpetual candles for the same symbol and returns the aligned basis
+pub async fn fetch_basis(
+  exchange: &Exchange,
+  symbol: &str,
+  interval_period: &IntervalPeriod,
+  basis_type: BasisType
+) -> Result<BasisSeries, SmartError> {
+  let Some(spot_url) = spot_query_url(exchange) else {
+    return Err(SmartError::RuntimeCheck(format!("Basis not supported for exchange: {}", exchange.as_string())))
+  };
+
+  let perp_builder: CandleBuilder = CandleBuilder::new(symbol.to_string(), interval_period.clone(), exchange.clone(), None);
+  let mut spot_builder: CandleBuilder = CandleBuilder::new(symbol.to_string(), interval_period.clone(), exchange.clone(), None);
+  spot_builder.query_url = spot_url;
+
+  let (spot, perp) = futures::future::join(spot_builder.fetch_prices_candles(), perp_builder.fetch_prices_candles()).await;
+  build_basis(spot?, perp?, basis_type)
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::pricing::models::LabelConvention;
+
+  #[tokio::test]
+  async fn it_builds_aligned_basis() {
+    let spot: HistoricalPrices = HistoricalPrices { prices: vec![100.0, 101.0, 102.0], labels: vec![1, 2, 3], label_convention: LabelConvention::OpenTime, fetch_report: None };
+    let perp: HistoricalPrices = HistoricalPrices { prices: vec![101.0, 103.0, 104.0], labels: vec![2, 3, 4], label_convention: LabelConvention::OpenTime, fetch_report: None };
+
+    let basis: BasisSeries = build_basis(spot.clone(), perp.clone(), BasisType::Absolute).unwrap();
+    assert_eq!(basis.labels, vec![2, 3]);
+    assert_eq!(basis.basis, vec![0.0, 1.0]);
+
+    let basis: BasisSeries = build_basis(spot, perp, BasisType::Percent).unwrap();
+    assert!((basis.basis[1] - 100.0 / 102.0).abs() < 1e-12);
+    assert_eq!(basis.to_pair_prices().series_0, vec![101.0, 103.0]);
+  }
+}
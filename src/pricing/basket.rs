@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use ts_rs::TS;
+
+use crate::SmartError;
+use super::controller::PriceController;
+use super::utils::join_pair_series;
+use super::models::{DataTolerance, Exchange, FetchReport, HistoricalPrices, IntervalPeriod, LabelConvention, MissingDataPolicy, PairPrices};
+
+/*
+  Synthetic Index
+  Builds series_1 as a basket of N symbols so one asset can be traded against its sector
+  Each leg is rebased to 1.0 at the first common label then weighted (buy and hold from the base), the index starts at 100
+*/
+
+/// Index level at the first common label
+const INDEX_BASE: f64 = 100.0;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub enum BasketWeighting {
+  Equal,
+  CapWeighted(Vec<f64>) // market cap per symbol, in the same order as the basket symbols
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct BasketCriteria {
+  pub symbols: Vec<String>,
+  pub weighting: BasketWeighting
+}
+
+impl BasketCriteria {
+
+  /// Weights
+  /// Normalized weight per symbol (summing to 1.0)
+  pub fn weights(&self) -> Result<Vec<f64>, SmartError> {
+
+    // Guard: Ensure enough symbols for a basket
+    if self.symbols.len() < 2 {
+      return Err(SmartError::RuntimeCheck("Synthetic index requires at least 2 symbols".to_string()));
+    }
+
+    match &self.weighting {
+      BasketWeighting::Equal => Ok(vec![1.0 / self.symbols.len() as f64; self.symbols.len()]),
+      BasketWeighting::CapWeighted(caps) => {
+
+        // Guard: Ensure a positive cap for every symbol
+        if caps.len() != self.symbols.len() {
+          let e: String = format!("Market caps length ({}) does not match symbols length ({})", caps.len(), self.symbols.len());
+          return Err(SmartError::RuntimeCheck(e));
+        }
+        if let Some(idx) = caps.iter().position(|c| !c.is_finite() || *c <= 0.0) {
+          return Err(SmartError::RuntimeCheck(format!("Market cap for {} must be positive", self.symbols[idx])));
+        }
+
+        let total: f64 = caps.iter().sum();
+        Ok(caps.iter().map(|c| c / total).collect())
+      }
+    }
+  }
+}
+
+/// Build Synthetic Index
+/// Weighted sum of rebased legs over the labels every leg shares
+pub fn build_synthetic_index(legs: Vec<HistoricalPrices>, weights: &[f64]) -> Result<HistoricalPrices, SmartError> {
+
+  // Guard: Ensure a weight per leg
+  if legs.len() != weights.len() || legs.is_empty() {
+    return Err(SmartError::RuntimeCheck(format!("Expected {} legs for the synthetic index, received {}", weights.len(), legs.len())));
+  }
+
+  // Guard: Ensure label conventions are the same
+  let label_convention: LabelConvention = legs[0].label_convention;
+  if legs.iter().any(|leg| leg.label_convention != label_convention) {
+    return Err(SmartError::RuntimeCheck("Failed to build synthetic index (label conventions differ)".to_string()));
+  }
+
+  // Labels shared by every leg
+  let mut common: HashSet<u64> = legs[0].labels.iter().copied().collect();
+  for leg in legs.iter().skip(1) {
+    let leg_labels: HashSet<u64> = leg.labels.iter().copied().collect();
+    common.retain(|label| leg_labels.contains(label));
+  }
+  let mut labels: Vec<u64> = common.into_iter().collect();
+  labels.sort_unstable();
+
+  // Guard: Ensure overlapping history
+  if labels.is_empty() {
+    return Err(SmartError::RuntimeCheck("Failed to build synthetic index (no common labels)".to_string()));
+  }
+
+  // Weighted rebased prices
+  let mut index: Vec<f64> = vec![0.0; labels.len()];
+  for (leg, weight) in legs.iter().zip(weights.iter()) {
+    let prices: Vec<f64> = labels.iter()
+      .filter_map(|label| leg.labels.binary_search(label).ok().map(|i| leg.prices[i]))
+      .collect();
+
+    // Guard: Ensure a usable base price
+    let base: f64 = prices[0];
+    if !base.is_finite() || base <= 0.0 {
+      return Err(SmartError::RuntimeCheck("Synthetic index leg has no positive base price".to_string()));
+    }
+
+    for (level, price) in index.iter_mut().zip(prices.iter()) {
+      *level += weight * price / base * INDEX_BASE;
+    }
+  }
+
+  let fetch_report: Option<FetchReport> = legs.iter()
+    .filter_map(|leg| leg.fetch_report.clone())
+    .reduce(|a, b| a.combine(&b));
+  Ok(HistoricalPrices { prices: index, labels, label_convention, fetch_report })
+}
+
+/// Fetch Basket Legs
+/// Fetches every basket symbol concurrently (identical in flight requests are shared)
+pub async fn fetch_basket_legs(
+  exchange: &Exchange,
+  interval_period: &IntervalPeriod,
+  symbols: &[String],
+  twelve_api_key: Option<&str>,
+  tolerance: DataTolerance
+) -> Result<Vec<HistoricalPrices>, SmartError> {
+  let controllers: Vec<PriceController> = symbols.iter()
+    .map(|symbol| PriceController::new(symbol.clone(), interval_period.clone(), exchange.clone(), twelve_api_key).with_tolerance(tolerance))
+    .collect();
+  let results: Vec<Result<HistoricalPrices, SmartError>> = futures::future::join_all(controllers.iter().map(|c| c.get_latest_prices())).await;
+  results.into_iter().zip(symbols.iter())
+    .map(|(res, symbol)| res.map_err(|e| SmartError::RuntimeCheck(format!("Failed to fetch basket symbol {}: {}", symbol, e))))
+    .collect()
+}
+
+/// Fetch Synthetic Index
+/// Fetches the basket and builds its synthetic index
+pub async fn fetch_synthetic_index(
+  exchange: &Exchange,
+  interval_period: &IntervalPeriod,
+  basket: &BasketCriteria,
+  twelve_api_key: Option<&str>,
+  tolerance: DataTolerance
+) -> Result<HistoricalPrices, SmartError> {
+  let weights: Vec<f64> = basket.weights()?;
+  let legs: Vec<HistoricalPrices> = fetch_basket_legs(exchange, interval_period, &basket.symbols, twelve_api_key, tolerance).await?;
+  build_synthetic_index(legs, &weights)
+}
+
+/// Get Prices Against Index
+/// Series 0 is the asset and series 1 the synthetic index of the basket (the basket would usually exclude the asset)
+pub async fn get_prices_against_index(
+  exchange: &Exchange,
+  interval_period: &IntervalPeriod,
+  asset_0: &str,
+  basket: &BasketCriteria,
+  twelve_api_key: Option<&str>,
+  missing_data_policy: MissingDataPolicy
+) -> Result<PairPrices, SmartError> {
+  let controller_0: PriceController = PriceController::new(asset_0.to_string(), interval_period.clone(), exchange.clone(), twelve_api_key);
+  let (asset, index) = futures::join!(
+    controller_0.get_latest_prices(),
+    fetch_synthetic_index(exchange, interval_period, basket, twelve_api_key, DataTolerance::Strict)
+  );
+  let (asset, index) = (asset?, index?);
+
+  let label_convention: LabelConvention = asset.label_convention;
+  let fetch_report: Option<FetchReport> = match (&asset.fetch_report, &index.fetch_report) {
+    (Some(r0), Some(r1)) => Some(r0.combine(r1)),
+    _ => None
+  };
+  let (series_0, series_1, labels) = join_pair_series(asset, index, missing_data_policy)?;
+  Ok(PairPrices { series_0, series_1, labels, label_convention, fetch_report, events: vec![], source: Some(exchange.clone()) })
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn leg(labels: Vec<u64>, prices: Vec<f64>) -> HistoricalPrices {
+    HistoricalPrices { prices, labels, label_convention: LabelConvention::OpenTime, fetch_report: None }
+  }
+
+  #[tokio::test]
+  async fn it_builds_equal_weighted_index() {
+    let basket: BasketCriteria = BasketCriteria { symbols: vec!["ETHUSDT".to_string(), "SOLUSDT".to_string()], weighting: BasketWeighting::Equal };
+    let weights: Vec<f64> = basket.weights().unwrap();
+    let legs: Vec<HistoricalPrices> = vec![
+      leg(vec![1, 2, 3, 4], vec![10.0, 11.0, 12.0, 13.0]),
+      leg(vec![2, 3, 4], vec![200.0, 180.0, 220.0])
+    ];
+
+    // Only labels held by every leg, each leg rebased at label 2
+    let index: HistoricalPrices = build_synthetic_index(legs, &weights).unwrap();
+    assert_eq!(index.labels, vec![2, 3, 4]);
+    assert_eq!(index.prices[0], INDEX_BASE);
+    assert!((index.prices[1] - (0.5 * 12.0 / 11.0 + 0.5 * 0.9) * 100.0).abs() < 1e-9);
+    assert!((index.prices[2] - (0.5 * 13.0 / 11.0 + 0.5 * 1.1) * 100.0).abs() < 1e-9);
+  }
+
+  #[tokio::test]
+  async fn it_builds_cap_weighted_index() {
+    let basket: BasketCriteria = BasketCriteria {
+      symbols: vec!["AAPL".to_string(), "MSFT".to_string()],
+      weighting: BasketWeighting::CapWeighted(vec![300.0, 100.0])
+    };
+    assert_eq!(basket.weights().unwrap(), vec![0.75, 0.25]);
+
+    let legs: Vec<HistoricalPrices> = vec![leg(vec![1, 2], vec![100.0, 110.0]), leg(vec![1, 2], vec![50.0, 40.0])];
+    let index: HistoricalPrices = build_synthetic_index(legs, &basket.weights().unwrap()).unwrap();
+    assert!((index.prices[1] - (0.75 * 1.1 + 0.25 * 0.8) * 100.0).abs() < 1e-9);
+
+    // Invalid baskets are rejected
+    let short: BasketCriteria = BasketCriteria { symbols: vec!["AAPL".to_string()], weighting: BasketWeighting::Equal };
+    assert!(short.weights().is_err());
+    let mismatched: BasketCriteria = BasketCriteria { weighting: BasketWeighting::CapWeighted(vec![1.0]), ..basket.clone() };
+    assert!(mismatched.weights().is_err());
+    let no_overlap: Vec<HistoricalPrices> = vec![leg(vec![1], vec![1.0]), leg(vec![2], vec![1.0])];
+    assert!(build_synthetic_index(no_overlap, &[0.5, 0.5]).is_err());
+  }
+
+  #[tokio::test]
+  async fn tests_get_prices_against_index() {
+    let basket: BasketCriteria = BasketCriteria {
+      symbols: vec!["SOLUSDT".to_string(), "AVAXUSDT".to_string(), "NEARUSDT".to_string()],
+      weighting: BasketWeighting::Equal
+    };
+    let prices: PairPrices = get_prices_against_index(
+      &Exchange::Binance, &IntervalPeriod::Hour(1, 200), "ETHUSDT", &basket, None, MissingDataPolicy::DropBar
+    ).await.unwrap();
+    assert_eq!(prices.series_0.len(), prices.series_1.len());
+    assert!(!prices.labels.is_empty());
+  }
+}
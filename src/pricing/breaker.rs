@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::SmartError;
+use super::clock::now_ms;
+use super::models::Exchange;
+
+/*
+  Circuit Breaker
+  Trips after consecutive failures from a single exchange and fails fast for a cooldown period
+  Prevents one exchange outage from stalling a whole screening batch on timeouts
+  Once the cooldown passes a single trial request is admitted, the rest keep failing fast until the trial succeeds or fails
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub enum BreakerState {
+  Closed, // Requests flow normally
+  Open, // Requests fail fast until cooldown passes
+  HalfOpen // Cooldown passed, a single trial request is admitted
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+pub struct BreakerConfig {
+  pub failure_threshold: u32,
+  pub cooldown_ms: i64
+}
+
+impl Default for BreakerConfig {
+  fn default() -> Self {
+    Self { failure_threshold: 5, cooldown_ms: 60_000 }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct BreakerStatus {
+  pub consecutive_failures: u32,
+  pub opened_at_ms: Option<i64>,
+  #[serde(default)]
+  pub trial_started_ms: Option<i64> // trial request in flight while half open
+}
+
+impl BreakerStatus {
+
+  /// State
+  /// Derives the breaker state given config and current time
+  pub fn state(&self, config: &BreakerConfig, now_ms: i64) -> BreakerState {
+    match self.opened_at_ms {
+      None => BreakerState::Closed,
+      Some(opened) if now_ms - opened >= config.cooldown_ms => BreakerState::HalfOpen,
+      Some(_) => BreakerState::Open
+    }
+  }
+
+  /// Admit
+  /// Whether a request may be sent, starting the trial when half open
+  /// A trial that never reports (e.g. a dropped request) is replaced once a further cooldown has passed
+  pub fn admit(&mut self, config: &BreakerConfig, now_ms: i64) -> bool {
+    match self.state(config, now_ms) {
+      BreakerState::Closed => true,
+      BreakerState::Open => false,
+      BreakerState::HalfOpen => match self.trial_started_ms {
+        Some(started) if now_ms - started < config.cooldown_ms => false,
+        _ => {
+          self.trial_started_ms = Some(now_ms);
+          true
+        }
+      }
+    }
+  }
+
+  /// Record Success
+  pub fn record_success(&mut self) {
+    self.consecutive_failures = 0;
+    self.opened_at_ms = None;
+    self.trial_started_ms = None;
+  }
+
+  /// Record Failure
+  /// Opens (or re-opens after a failed trial) once the threshold is reached
+  pub fn record_failure(&mut self, config: &BreakerConfig, now_ms: i64) {
+    self.consecutive_failures += 1;
+    self.trial_started_ms = None;
+    if self.consecutive_failures >= config.failure_threshold {
+      self.opened_at_ms = Some(now_ms);
+    }
+  }
+}
+
+static BREAKER_CONFIG: RwLock<Option<BreakerConfig>> = RwLock::new(None);
+static BREAKERS: RwLock<Option<HashMap<Exchange, BreakerStatus>>> = RwLock::new(None);
+
+/// Set Breaker Config
+/// Overrides the default failure threshold and cooldown
+pub fn set_breaker_config(config: BreakerConfig) {
+  if let Ok(mut guard) = BREAKER_CONFIG.write() {
+    *guard = Some(config);
+  }
+}
+
+/// Get Breaker Config
+pub fn get_breaker_config() -> BreakerConfig {
+  BREAKER_CONFIG.read().ok().and_then(|guard| *guard).unwrap_or_default()
+}
+
+/// Breaker State
+/// Reports the current breaker state for an exchange
+pub fn breaker_state(exchange: &Exchange) -> BreakerState {
+  let now_ms: i64 = now_ms();
+  BREAKERS.read().ok()
+    .and_then(|guard| guard.as_ref().and_then(|hm| hm.get(exchange).cloned()))
+    .map(|status| status.state(&get_breaker_config(), now_ms))
+    .unwrap_or(BreakerState::Closed)
+}
+
+/// Reset Breaker
+/// Manually closes the breaker for an exchange
+pub fn reset_breaker(exchange: &Exchange) {
+  if let Ok(mut guard) = BREAKERS.write() {
+    if let Some(hm) = guard.as_mut() { hm.remove(exchange); }
+  }
+}
+
+/// Check Breaker
+/// Fails fast if the breaker for the url's exchange is open, or half open with its trial request in flight
+pub fn check_breaker(url: &str) -> Result<(), SmartError> {
+  let Some(exchange) = Exchange::from_url(url) else { return Ok(()) };
+  let config: BreakerConfig = get_breaker_config();
+  let now_ms: i64 = now_ms();
+  let admitted: bool = match BREAKERS.write() {
+    Ok(mut guard) => guard.as_mut()
+      .and_then(|hm| hm.get_mut(&exchange))
+      .map(|status| status.admit(&config, now_ms))
+      .unwrap_or(true),
+    Err(_) => true
+  };
+  if !admitted {
+    return Err(SmartError::CircuitOpen(exchange.as_string()));
+  }
+  Ok(())
+}
+
+/// Record Outcome
+/// Updates the breaker for the url's exchange after a request
+pub fn record_outcome(url: &str, is_success: bool) {
+  let Some(exchange) = Exchange::from_url(url) else { return };
+  let config: BreakerConfig = get_breaker_config();
+  let now_ms: i64 = now_ms();
+  if let Ok(mut guard) = BREAKERS.write() {
+    let status: &mut BreakerStatus = guard.get_or_insert_with(HashMap::new).entry(exchange).or_default();
+    if is_success { status.record_success() } else { status.record_failure(&config, now_ms) }
+  }
+}
+
+/// Is Breaker Failure
+/// Only server side failures and throttling count towards tripping the breaker
+pub fn is_breaker_failure(status: reqwest::StatusCode) -> bool {
+  status.is_server_error() || status.as_u16() == 429 || status.as_u16() == 418
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_trips_and_recovers_after_cooldown() {
+    let config: BreakerConfig = BreakerConfig { failure_threshold: 3, cooldown_ms: 1000 };
+    let mut status: BreakerStatus = BreakerStatus::default();
+    status.record_failure(&config, 0);
+    status.record_failure(&config, 10);
+    assert_eq!(status.state(&config, 20), BreakerState::Closed);
+    status.record_failure(&config, 20);
+    assert_eq!(status.state(&config, 500), BreakerState::Open);
+    assert_eq!(status.state(&config, 1020), BreakerState::HalfOpen);
+
+    // A single trial is admitted while half open
+    assert!(status.admit(&config, 1020));
+    assert!(!status.admit(&config, 1030));
+    assert!(!status.admit(&config, 1040));
+
+    // Failed trial re-opens, success closes
+    status.record_failure(&config, 1100);
+    assert_eq!(status.state(&config, 1200), BreakerState::Open);
+    assert!(!status.admit(&config, 1200));
+    assert!(status.admit(&config, 2100));
+    status.record_success();
+    assert_eq!(status.state(&config, 1200), BreakerState::Closed);
+    assert!(status.admit(&config, 1200) && status.admit(&config, 1210));
+  }
+
+  #[tokio::test]
+  async fn it_replaces_a_trial_that_never_reports() {
+    let config: BreakerConfig = BreakerConfig { failure_threshold: 1, cooldown_ms: 1000 };
+    let mut status: BreakerStatus = BreakerStatus::default();
+    status.record_failure(&config, 0);
+    assert!(status.admit(&config, 1000));
+    assert!(!status.admit(&config, 1999));
+    assert!(status.admit(&config, 2000));
+  }
+
+  #[tokio::test]
+  async fn it_fails_fast_when_open() {
+    let url: &str = "https://indexer.dydx.trade/v4/perpetualMarkets";
+    for _ in 0..get_breaker_config().failure_threshold { record_outcome(url, false); }
+    assert!(matches!(check_breaker(url), Err(SmartError::CircuitOpen(_))));
+    reset_breaker(&Exchange::Dydx);
+    assert!(check_breaker(url).is_ok());
+  }
+}
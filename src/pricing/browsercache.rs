@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+
+use super::models::{Exchange, IntervalPeriod};
+
+/*
+  Browser Candle Cache
+  Caches fetched candles in localStorage per exchange / symbol / interval / UTC day (wasm feature only)
+  Re-running an analysis in the web app then skips every page already held instead of refetching
+*/
+
+const DAY_SECS: i64 = 86_400;
+const KEY_PREFIX: &str = "zscore_lib:candles";
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct CachedDay {
+  pub labels: Vec<u64>,
+  pub prices: Vec<f64>
+}
+
+/// Day Key
+/// Storage key for one UTC day of candles
+pub fn day_key(exchange: &Exchange, symbol: &str, interval: &IntervalPeriod, day: i64) -> String {
+  format!("{}:{:?}:{}:{}:{}", KEY_PREFIX, exchange, symbol, interval.interval_seconds(), day)
+}
+
+/// Complete Days
+/// Splits candles into the UTC days lying entirely within the fetched from / to range
+/// Partially covered days (such as today) are never returned so the cache only holds complete days
+pub fn complete_days(labels: &[u64], prices: &[f64], from_time: i64, to_time: i64) -> Vec<(i64, CachedDay)> {
+  let first_day: i64 = (from_time + DAY_SECS - 1).div_euclid(DAY_SECS);
+  let last_day: i64 = to_time.div_euclid(DAY_SECS) - 1;
+  (first_day..=last_day)
+    .map(|day| {
+      let (start, end) = ((day * DAY_SECS) as u64, ((day + 1) * DAY_SECS) as u64);
+      let from: usize = labels.partition_point(|l| *l < start);
+      let to: usize = labels.partition_point(|l| *l < end);
+      (day, CachedDay { labels: labels[from..to].to_vec(), prices: prices[from..to].to_vec() })
+    })
+    .collect()
+}
+
+/// Days For Range
+/// UTC days touched by a from / to call range
+pub fn days_for_range(from_time: i64, to_time: i64) -> std::ops::RangeInclusive<i64> {
+  from_time.div_euclid(DAY_SECS)..=to_time.div_euclid(DAY_SECS)
+}
+
+/// Collect Range
+/// Joins cached days and keeps the candles within the call range (None if any day is missing)
+pub fn collect_range(days: Vec<Option<CachedDay>>, from_time: i64, to_time: i64) -> Option<(Vec<u64>, Vec<f64>)> {
+  let mut labels: Vec<u64> = vec![];
+  let mut prices: Vec<f64> = vec![];
+  for day in days {
+    let day: CachedDay = day?;
+    for (label, price) in day.labels.into_iter().zip(day.prices) {
+      if label as i64 >= from_time && label as i64 <= to_time {
+        labels.push(label);
+        prices.push(price);
+      }
+    }
+  }
+  Some((labels, prices))
+}
+
+/// Read Cached Range
+/// Candles for a call range if every day it touches is cached
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub fn read_cached_range(exchange: &Exchange, symbol: &str, interval: &IntervalPeriod, from_time: i64, to_time: i64) -> Option<(Vec<u64>, Vec<f64>)> {
+  let storage: web_sys::Storage = web_sys::window()?.local_storage().ok()??;
+  let days: Vec<Option<CachedDay>> = days_for_range(from_time, to_time)
+    .map(|day| {
+      let json: String = storage.get_item(&day_key(exchange, symbol, interval, day)).ok()??;
+      serde_json::from_str::<CachedDay>(&json).ok()
+    })
+    .collect();
+  collect_range(days, from_time, to_time)
+}
+
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+pub fn read_cached_range(_exchange: &Exchange, _symbol: &str, _interval: &IntervalPeriod, _from_time: i64, _to_time: i64) -> Option<(Vec<u64>, Vec<f64>)> {
+  None
+}
+
+/// Write Cached Days
+/// Stores every complete day of a fetch (storage quota errors are ignored as the cache is best effort)
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub fn write_cached_days(exchange: &Exchange, symbol: &str, interval: &IntervalPeriod, from_time: i64, to_time: i64, labels: &[u64], prices: &[f64]) {
+  let Some(storage) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) else { return };
+  for (day, cached) in complete_days(labels, prices, from_time, to_time) {
+    if let Ok(json) = serde_json::to_string(&cached) {
+      let _ = storage.set_item(&day_key(exchange, symbol, interval, day), &json);
+    }
+  }
+}
+
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+pub fn write_cached_days(_exchange: &Exchange, _symbol: &str, _interval: &IntervalPeriod, _from_time: i64, _to_time: i64, _labels: &[u64], _prices: &[f64]) {}
+
+/// Clear Candle Cache
+/// Removes every cached candle day from localStorage
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub fn clear_candle_cache() {
+  let Some(storage) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) else { return };
+  let len: u32 = storage.length().unwrap_or(0);
+  let keys: Vec<String> = (0..len).filter_map(|i| storage.key(i).ok().flatten()).filter(|k| k.starts_with(KEY_PREFIX)).collect();
+  for key in keys {
+    let _ = storage.remove_item(&key);
+  }
+}
+
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+pub fn clear_candle_cache() {}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_splits_complete_days_and_collects_ranges() {
+    let labels: Vec<u64> = (0..72).map(|h| 43_200 + h * 3600).collect(); // noon day 0 to noon day 3
+    let prices: Vec<f64> = (0..72).map(|h| h as f64).collect();
+    let (from_time, to_time) = (43_200, 43_200 + 72 * 3600);
+
+    // Partial first and last days are not cached
+    let days: Vec<(i64, CachedDay)> = complete_days(&labels, &prices, from_time, to_time);
+    assert_eq!(days.iter().map(|d| d.0).collect::<Vec<i64>>(), vec![1, 2]);
+    assert_eq!(days[0].1.labels.len(), 24);
+    assert_eq!(days[0].1.labels[0], 86_400);
+
+    // A call range within cached days is served, one touching an uncached day is not
+    let cached = |day: i64| days.iter().find(|d| d.0 == day).map(|d| d.1.clone());
+    let (call_from, call_to) = (90_000, 180_000);
+    let range: Vec<Option<CachedDay>> = days_for_range(call_from, call_to).map(cached).collect();
+    let (hit_labels, _) = collect_range(range, call_from, call_to).unwrap();
+    assert!(hit_labels.iter().all(|l| *l >= 90_000 && *l <= 180_000));
+    assert_eq!(hit_labels.len(), 26);
+
+    let miss: Vec<Option<CachedDay>> = days_for_range(50_000, call_to).map(cached).collect();
+    assert!(collect_range(miss, 50_000, call_to).is_none());
+
+    let key: String = day_key(&Exchange::Binance, "BTCUSDT", &IntervalPeriod::Hour(1, 100), 19_000);
+    assert_eq!(key, "zscore_lib:candles:Binance:BTCUSDT:3600:19000");
+  }
+}
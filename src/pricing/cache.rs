@@ -0,0 +1,272 @@
+use serde::{Deserialize, Serialize};
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+use std::path::{Path, PathBuf};
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+use std::sync::RwLock;
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+use crate::SmartError;
+use super::models::{Exchange, IntervalPeriod};
+
+/*
+  Candle Cache
+  Persistent cache of fetched closes keyed by exchange / symbol / interval with the from / to ranges already covered
+  Repeated analyses over the same window are served from disk instead of re-hitting exchange apis (browser builds use
+  localStorage through the browser cache instead). Only closed bars are stored and the cache is off until a directory is set
+  The disk cache needs the candle-cache feature. It is a JSON file per series rather than SQLite as the crate takes no native
+  database dependency. Disk I/O runs on the blocking pool, writers merge under an OS lock on the series and replace the file
+  atomically, so concurrent writers (in any process) never drop each other's ranges and a crashed writer never leaves a lock held
+*/
+
+/// Environment variable used to enable the candle cache without code changes
+pub const CANDLE_CACHE_DIR_ENV: &str = "ZSCORE_CANDLE_CACHE_DIR";
+
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+static CANDLE_CACHE_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+static TMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct CachedSeries {
+  pub labels: Vec<u64>, // native exchange labels, sorted
+  pub prices: Vec<f64>,
+  pub covered: Vec<(i64, i64)> // from / to ranges fully fetched, sorted and disjoint
+}
+
+impl CachedSeries {
+
+  /// Range
+  /// Candles within a from / to range if the range is fully covered
+  pub fn range(&self, from_time: i64, to_time: i64) -> Option<(Vec<u64>, Vec<f64>)> {
+    if !self.covered.iter().any(|(from, to)| *from <= from_time && *to >= to_time) { return None }
+    let from: usize = self.labels.partition_point(|l| (*l as i64) < from_time);
+    let to: usize = self.labels.partition_point(|l| (*l as i64) <= to_time);
+    Some((self.labels[from..to].to_vec(), self.prices[from..to].to_vec()))
+  }
+
+  /// Merge
+  /// Adds the candles of a fetched range, newer prices replacing cached ones at the same label
+  pub fn merge(&mut self, from_time: i64, to_time: i64, labels: &[u64], prices: &[f64]) {
+    let mut rows: Vec<(u64, f64)> = labels.iter().copied().zip(prices.iter().copied())
+      .filter(|(l, _)| (*l as i64) >= from_time && (*l as i64) <= to_time)
+      .chain(self.labels.iter().copied().zip(self.prices.iter().copied()))
+      .collect();
+    rows.sort_by_key(|r| r.0);
+    rows.dedup_by_key(|r| r.0);
+    (self.labels, self.prices) = rows.into_iter().unzip();
+
+    // Join overlapping or touching ranges
+    let mut covered: Vec<(i64, i64)> = self.covered.clone();
+    covered.push((from_time, to_time));
+    covered.sort();
+    let mut joined: Vec<(i64, i64)> = vec![];
+    for (from, to) in covered {
+      match joined.last_mut() {
+        Some(last) if from <= last.1 + 1 => last.1 = last.1.max(to),
+        _ => joined.push((from, to))
+      }
+    }
+    self.covered = joined;
+  }
+}
+
+/// Closed Until
+/// Latest time up to which bars have closed, so the live bar is never cached
+pub fn closed_until(now_secs: i64, interval: &IntervalPeriod) -> i64 {
+  now_secs - interval.interval_seconds() as i64
+}
+
+/// Set Candle Cache Dir
+/// Enables (or disables with None) the persistent candle cache
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+pub fn set_candle_cache_dir(dir: Option<PathBuf>) {
+  if let Ok(mut guard) = CANDLE_CACHE_DIR.write() {
+    *guard = dir;
+  }
+}
+
+/// Get Candle Cache Dir
+/// Returns the programmatic directory, falling back to the environment variable
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+pub fn get_candle_cache_dir() -> Option<PathBuf> {
+  if let Some(dir) = CANDLE_CACHE_DIR.read().ok().and_then(|guard| guard.clone()) {
+    return Some(dir);
+  }
+  std::env::var(CANDLE_CACHE_DIR_ENV).ok()
+    .filter(|dir| !dir.is_empty())
+    .map(PathBuf::from)
+}
+
+/// Cache Path
+/// File holding one exchange / symbol / interval
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+pub fn cache_path(dir: &Path, exchange: &Exchange, symbol: &str, interval: &IntervalPeriod) -> PathBuf {
+  let symbol: String = symbol.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' }).collect();
+  dir.join(format!("{:?}_{}_{}.json", exchange, symbol, interval.interval_seconds()))
+}
+
+/// Load Cached Series
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+fn load_cached_series(path: &Path) -> Result<Option<CachedSeries>, SmartError> {
+  if !path.exists() { return Ok(None) }
+  let text: String = std::fs::read_to_string(path)?;
+  Ok(Some(serde_json::from_str::<CachedSeries>(&text)?))
+}
+
+/// Save Cached Series
+/// Writes through a temporary file unique to the writer then renames it into place, so readers never see a partial file
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+fn save_cached_series(path: &Path, series: &CachedSeries) -> Result<(), SmartError> {
+  if let Some(dir) = path.parent() { std::fs::create_dir_all(dir)?; }
+  let tmp_id: u64 = TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+  let tmp_path: PathBuf = path.with_extension(format!("json.{}.{}.tmp", std::process::id(), tmp_id));
+  if let Err(e) = std::fs::write(&tmp_path, serde_json::to_string(series)?).and_then(|_| std::fs::rename(&tmp_path, path)) {
+    let _ = std::fs::remove_file(&tmp_path);
+    return Err(e.into());
+  }
+  Ok(())
+}
+
+/// Lock Series
+/// Holds an exclusive OS lock on the lock file of a series until the returned file is dropped (blocks while another writer holds it)
+/// The lock is released by the OS if the holder exits, so no stale lock can be left behind
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+fn lock_series(path: &Path) -> Result<std::fs::File, SmartError> {
+  if let Some(dir) = path.parent() { std::fs::create_dir_all(dir)?; }
+  let lock_file: std::fs::File = std::fs::OpenOptions::new().create(true).truncate(false).write(true).open(path.with_extension("json.lock"))?;
+  lock_file.lock()?;
+  Ok(lock_file)
+}
+
+/// Merge Cached Range
+/// Merges a fetched range into the series file under the series lock (blocking)
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+fn merge_cached_range(path: &Path, from_time: i64, to_time: i64, labels: &[u64], prices: &[f64]) -> Result<(), SmartError> {
+  let _lock: std::fs::File = lock_series(path)?;
+  let mut series: CachedSeries = load_cached_series(path)?.unwrap_or_default();
+  series.merge(from_time, to_time, labels, prices);
+  save_cached_series(path, &series)
+}
+
+/// Read Cached Range
+/// Candles for a call range if the cache covers it (read on the blocking pool, browser builds read localStorage)
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+pub async fn read_cached_range(exchange: &Exchange, symbol: &str, interval: &IntervalPeriod, from_time: i64, to_time: i64) -> Option<(Vec<u64>, Vec<f64>)> {
+  let path: PathBuf = cache_path(&get_candle_cache_dir()?, exchange, symbol, interval);
+  let series: CachedSeries = async_std::task::spawn_blocking(move || load_cached_series(&path)).await.ok()??;
+  series.range(from_time, to_time)
+}
+
+#[cfg(all(not(feature = "candle-cache"), not(target_arch = "wasm32")))]
+pub async fn read_cached_range(_exchange: &Exchange, _symbol: &str, _interval: &IntervalPeriod, _from_time: i64, _to_time: i64) -> Option<(Vec<u64>, Vec<f64>)> {
+  None
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn read_cached_range(exchange: &Exchange, symbol: &str, interval: &IntervalPeriod, from_time: i64, to_time: i64) -> Option<(Vec<u64>, Vec<f64>)> {
+  super::browsercache::read_cached_range(exchange, symbol, interval, from_time, to_time)
+}
+
+/// Write Cached Range
+/// Stores the closed bars of a fetched range on the blocking pool (write errors are ignored as the cache is best effort)
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+pub async fn write_cached_range(exchange: &Exchange, symbol: &str, interval: &IntervalPeriod, from_time: i64, to_time: i64, labels: &[u64], prices: &[f64]) {
+  let Some(dir) = get_candle_cache_dir() else { return };
+  let to_time: i64 = to_time.min(closed_until(super::clock::now_ms() / 1000, interval));
+
+  // Guard: Ensure closed bars were fetched
+  if to_time < from_time { return }
+
+  let path: PathBuf = cache_path(&dir, exchange, symbol, interval);
+  let (labels, prices): (Vec<u64>, Vec<f64>) = (labels.to_vec(), prices.to_vec());
+  let _ = async_std::task::spawn_blocking(move || merge_cached_range(&path, from_time, to_time, &labels, &prices)).await;
+}
+
+#[cfg(all(not(feature = "candle-cache"), not(target_arch = "wasm32")))]
+pub async fn write_cached_range(_exchange: &Exchange, _symbol: &str, _interval: &IntervalPeriod, _from_time: i64, _to_time: i64, _labels: &[u64], _prices: &[f64]) {}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn write_cached_range(exchange: &Exchange, symbol: &str, interval: &IntervalPeriod, from_time: i64, to_time: i64, labels: &[u64], prices: &[f64]) {
+  super::browsercache::write_cached_days(exchange, symbol, interval, from_time, to_time, labels, prices)
+}
+
+/// Clear Candle Cache Files
+/// Removes every cached series from the cache directory
+#[cfg(all(feature = "candle-cache", not(target_arch = "wasm32")))]
+pub fn clear_candle_cache_files() -> Result<(), SmartError> {
+  let Some(dir) = get_candle_cache_dir() else { return Ok(()) };
+  if !dir.exists() { return Ok(()) }
+  for entry in std::fs::read_dir(dir)? {
+    let path: PathBuf = entry?.path();
+    if path.extension().is_some_and(|e| e == "json") {
+      std::fs::remove_file(path)?;
+    }
+  }
+  Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_merges_ranges_and_serves_covered_calls() {
+    let mut series: CachedSeries = CachedSeries::default();
+    series.merge(0, 7_200, &[0, 3_600, 7_200, 10_800], &[1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(series.labels, vec![0, 3_600, 7_200]);
+    assert!(series.range(3_600, 10_800).is_none());
+
+    // Touching ranges join, so a call spanning both is served
+    series.merge(7_201, 14_400, &[10_800, 14_400], &[4.0, 5.0]);
+    assert_eq!(series.covered, vec![(0, 14_400)]);
+    assert_eq!(series.range(3_600, 10_800), Some((vec![3_600, 7_200, 10_800], vec![2.0, 3.0, 4.0])));
+
+    // Gaps stay uncovered
+    series.merge(36_000, 39_600, &[36_000], &[9.0]);
+    assert_eq!(series.covered.len(), 2);
+    assert!(series.range(10_800, 36_000).is_none());
+  }
+
+  #[cfg(feature = "candle-cache")]
+  #[tokio::test]
+  async fn it_persists_closed_bars_to_disk() {
+    let dir: PathBuf = std::env::temp_dir().join(format!("zscore_candle_cache_{}", std::process::id()));
+    let interval: IntervalPeriod = IntervalPeriod::Hour(1, 10);
+    let path: PathBuf = cache_path(&dir, &Exchange::Coinbase, "BTC-USD", &interval);
+    assert!(path.ends_with("Coinbase_BTC-USD_3600.json"));
+
+    // Written directly as the cache directory is a process wide setting
+    let mut series: CachedSeries = CachedSeries::default();
+    series.merge(0, 7_200, &[0, 3_600, 7_200], &[1.0, 2.0, 3.0]);
+    save_cached_series(&path, &series).unwrap();
+    assert_eq!(load_cached_series(&path).unwrap(), Some(series));
+    assert_eq!(closed_until(10_000, &interval), 6_400);
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[cfg(feature = "candle-cache")]
+  #[tokio::test]
+  async fn it_serializes_concurrent_cache_writers() {
+    let dir: PathBuf = std::env::temp_dir().join(format!("zscore_candle_cache_lock_{}", std::process::id()));
+    let path: PathBuf = cache_path(&dir, &Exchange::Coinbase, "ETH-USD", &IntervalPeriod::Hour(1, 10));
+
+    // Writers merging disjoint ranges at the same time keep every range
+    let handles: Vec<std::thread::JoinHandle<()>> = (0..8).map(|i: i64| {
+      let path: PathBuf = path.clone();
+      std::thread::spawn(move || merge_cached_range(&path, i * 7_200, i * 7_200 + 3_600, &[(i * 7_200) as u64], &[i as f64]).unwrap())
+    }).collect();
+    handles.into_iter().for_each(|h| h.join().unwrap());
+
+    let series: CachedSeries = load_cached_series(&path).unwrap().unwrap();
+    assert_eq!((series.labels.len(), series.covered.len()), (8, 8));
+
+    // Only the series and its lock file remain (no temporary files), and the lock is free again
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+    assert!(lock_series(&path).unwrap().try_lock().is_ok());
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+}
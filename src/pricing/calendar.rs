@@ -0,0 +1,281 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Timelike, Weekday};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::models::{AssetType, Exchange, GapKind, IntervalPeriod, LabelGap, find_label_gaps};
+use super::times::{convert_timestamp_to_us_eastern, subtract_time};
+use super::translate::is_tiingo_crypto;
+
+/*
+  Trading Calendars
+  Forex closes at weekends and US equities trade a single session on NYSE business days
+  Call planning and gap classification follow the calendar so closures are not mistaken for missing data
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Default, TS)]
+#[ts(export)]
+pub enum MarketCalendar {
+  #[default]
+  Continuous, // crypto (24/7)
+  Forex, // closed from Friday 17:00 to Sunday 17:00 New York time
+  UsEquity // NYSE session 09:30 to 16:00 New York time, excluding holidays
+}
+
+/// Currencies treated as fiat when classifying slash separated symbols (EUR/USD is forex, BTC/USD is crypto)
+const FIAT_CURRENCIES: [&str; 30] = [
+  "USD", "EUR", "GBP", "JPY", "CHF", "AUD", "NZD", "CAD", "SEK", "NOK", "DKK", "HKD", "SGD", "CNH", "CNY",
+  "MXN", "ZAR", "TRY", "PLN", "HUF", "CZK", "ILS", "INR", "KRW", "BRL", "THB", "TWD", "IDR", "RUB", "SAR"
+];
+
+/// Seconds from Monday 00:00 (New York time) at which the forex weekend closure starts and ends
+const FOREX_CLOSE_SECS: i64 = 4 * 86400 + 17 * 3600;
+const FOREX_OPEN_SECS: i64 = 6 * 86400 + 17 * 3600;
+
+/// NYSE regular session in seconds from midnight (New York time)
+const EQUITY_OPEN_SECS: i64 = 9 * 3600 + 30 * 60;
+const EQUITY_CLOSE_SECS: i64 = 16 * 3600;
+
+impl MarketCalendar {
+
+  /// From Asset Type
+  /// Calendar an asset class trades on
+  pub fn from_asset_type(asset_type: &AssetType) -> Self {
+    match asset_type {
+      AssetType::Crypto => Self::Continuous,
+      AssetType::Forex => Self::Forex,
+      AssetType::Etf | AssetType::Indices | AssetType::Stock => Self::UsEquity
+    }
+  }
+
+  /// Infer
+  /// Calendar implied by an exchange and symbol
+  /// Crypto venues trade continuously, Twelve and AlphaVantage symbols are classified by form (EUR/USD, BTC/USD or AAPL)
+  /// Alpaca lists US stocks and crypto only, Yahoo marks forex with =X and crypto with a dash and fiat quote (BTC-USD)
+  /// Tiingo lists US stocks and concatenated crypto tickers (btcusd)
+  pub fn infer(exchange: &Exchange, symbol: &str) -> Self {
+    match exchange {
+      Exchange::Alpaca if symbol.contains('/') => Self::Continuous,
+      Exchange::Alpaca => Self::UsEquity,
+      Exchange::AlphaVantage | Exchange::Twelve => match symbol.to_uppercase().split_once('/') {
+        Some((base, quote)) if FIAT_CURRENCIES.contains(&base) && FIAT_CURRENCIES.contains(&quote) => Self::Forex,
+        Some(_) => Self::Continuous,
+        None => Self::UsEquity
+      },
+      Exchange::Tiingo if is_tiingo_crypto(symbol) => Self::Continuous,
+      Exchange::Tiingo => Self::UsEquity,
+      Exchange::Yahoo if symbol.to_uppercase().ends_with("=X") => Self::Forex,
+      Exchange::Yahoo => match symbol.to_uppercase().split_once('-') {
+        Some((_, quote)) if FIAT_CURRENCIES.contains(&quote) => Self::Continuous,
+        _ => Self::UsEquity // stocks (including BRK-B), ETFs and indices
+      },
+      _ => Self::Continuous
+    }
+  }
+
+  /// Is Trading Day
+  /// True if the market opens at all on the given date
+  pub fn is_trading_day(&self, date: NaiveDate) -> bool {
+    match self {
+      Self::Continuous => true,
+      Self::Forex => !matches!(date.weekday(), Weekday::Sat | Weekday::Sun),
+      Self::UsEquity => !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !is_nyse_holiday(date)
+    }
+  }
+
+  /// Is Open
+  /// True if any part of the bar opening at the given time falls within trading hours
+  /// Daily bars are judged by their date alone
+  pub fn is_open(&self, open_time: i64, interval: &IntervalPeriod) -> bool {
+    let step: i64 = interval.interval_seconds() as i64;
+    if *self == Self::Continuous { return true }
+
+    // Daily bars are labelled by date
+    if step >= 86400 {
+      let date: NaiveDate = DateTime::from_timestamp(open_time, 0)
+        .map(|dt| dt.date_naive())
+        .unwrap_or_default();
+      return self.is_trading_day(date);
+    }
+
+    let local: NaiveDateTime = convert_timestamp_to_us_eastern(open_time);
+    let secs_of_day: i64 = local.num_seconds_from_midnight() as i64;
+    match self {
+      Self::Continuous => true,
+      Self::Forex => {
+        let week_secs: i64 = local.weekday().num_days_from_monday() as i64 * 86400 + secs_of_day;
+        !(week_secs >= FOREX_CLOSE_SECS && week_secs + step <= FOREX_OPEN_SECS)
+      },
+      Self::UsEquity => {
+        // Bars may start the evening before a session (e.g. 12 hour bars)
+        [0_i64, 1].iter().any(|days| {
+          let date: NaiveDate = local.date() + Duration::days(*days);
+          let bar_start: i64 = secs_of_day - days * 86400;
+          self.is_trading_day(date) && bar_start < EQUITY_CLOSE_SECS && bar_start + step > EQUITY_OPEN_SECS
+        })
+      }
+    }
+  }
+
+  /// Subtract Open Bars
+  /// Start time such that the given number of open bars lie between it and the end time
+  /// Continuous calendars match subtract_time
+  pub fn subtract_open_bars(&self, end_time: i64, interval: &IntervalPeriod, bars: i64) -> i64 {
+    if *self == Self::Continuous { return subtract_time(end_time, interval, &bars) }
+    let step: i64 = interval.interval_seconds() as i64;
+    let mut start_time: i64 = subtract_time(end_time, interval, &0);
+    let mut counted: i64 = 0;
+    while counted < bars && step > 0 {
+      start_time -= step;
+      if self.is_open(start_time, interval) { counted += 1 }
+    }
+    start_time
+  }
+
+  /// Open Bars Between
+  /// Number of open bars strictly between two labels
+  pub fn open_bars_between(&self, from_label: u64, to_label: u64, interval: &IntervalPeriod) -> u32 {
+    let step: u64 = interval.interval_seconds();
+    if step == 0 || to_label <= from_label { return 0 }
+    (1..(to_label - from_label) / step)
+      .filter(|n| self.is_open((from_label + n * step) as i64, interval))
+      .count() as u32
+  }
+
+  /// Classify Label Gaps
+  /// Identifies jumps in labels and separates expected closures from missing data
+  /// A gap's missing bars only count bars the market was open for
+  pub fn classify_label_gaps(&self, labels: &[u64], interval: &IntervalPeriod) -> Vec<LabelGap> {
+    if *self == Self::Continuous { return find_label_gaps(labels, interval) }
+    find_label_gaps(labels, interval).into_iter()
+      .map(|gap| {
+        let missing_bars: u32 = self.open_bars_between(gap.from_label, gap.to_label, interval);
+        let kind: GapKind = if missing_bars == 0 { GapKind::Closure } else { GapKind::Missing };
+        LabelGap { missing_bars, kind, ..gap }
+      })
+      .collect()
+  }
+}
+
+/// Observed
+/// Holidays falling on a Saturday are observed the Friday before and on a Sunday the Monday after
+fn observed(date: NaiveDate) -> NaiveDate {
+  match date.weekday() {
+    Weekday::Sat => date - Duration::days(1),
+    Weekday::Sun => date + Duration::days(1),
+    _ => date
+  }
+}
+
+/// Easter Sunday
+/// Anonymous Gregorian algorithm
+fn easter_sunday(year: i32) -> NaiveDate {
+  let a: i32 = year % 19;
+  let (b, c): (i32, i32) = (year / 100, year % 100);
+  let g: i32 = (b - (b + 8) / 25 + 1) / 3;
+  let h: i32 = (19 * a + b - b / 4 - g + 15) % 30;
+  let l: i32 = (32 + 2 * (b % 4) + 2 * (c / 4) - h - c % 4) % 7;
+  let f: i32 = h + l - 7 * ((a + 11 * h + 22 * l) / 451) + 114;
+  NaiveDate::from_ymd_opt(year, (f / 31) as u32, (f % 31 + 1) as u32).expect("Failed to build easter date")
+}
+
+/// Is NYSE Holiday
+/// Full day market closures (early closes are treated as full sessions)
+fn is_nyse_holiday(date: NaiveDate) -> bool {
+  let year: i32 = date.year();
+  let ymd = |month: u32, day: u32| NaiveDate::from_ymd_opt(year, month, day).expect("Failed to build holiday date");
+  let nth = |month: u32, weekday: Weekday, n: u8| NaiveDate::from_weekday_of_month_opt(year, month, weekday, n).expect("Failed to build holiday date");
+  let last_monday_may: NaiveDate = (0..7).map(|d| ymd(5, 31) - Duration::days(d)).find(|d| d.weekday() == Weekday::Mon).unwrap_or(ymd(5, 31));
+
+  // New Year's Day on a Saturday is not observed on the prior Friday
+  let new_year: NaiveDate = ymd(1, 1);
+  let mut holidays: Vec<NaiveDate> = vec![
+    nth(1, Weekday::Mon, 3), // Martin Luther King Jr. Day
+    nth(2, Weekday::Mon, 3), // Presidents' Day
+    easter_sunday(year) - Duration::days(2), // Good Friday
+    last_monday_may, // Memorial Day
+    observed(ymd(7, 4)),
+    nth(9, Weekday::Mon, 1), // Labor Day
+    nth(11, Weekday::Thu, 4), // Thanksgiving
+    observed(ymd(12, 25))
+  ];
+  if new_year.weekday() != Weekday::Sat { holidays.push(observed(new_year)) }
+  if year >= 2022 { holidays.push(observed(ymd(6, 19))) } // Juneteenth
+  holidays.contains(&date)
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_infers_calendars() {
+    assert_eq!(MarketCalendar::infer(&Exchange::Twelve, "EUR/USD"), MarketCalendar::Forex);
+    assert_eq!(MarketCalendar::infer(&Exchange::Twelve, "BTC/USD"), MarketCalendar::Continuous);
+    assert_eq!(MarketCalendar::infer(&Exchange::AlphaVantage, "AAPL"), MarketCalendar::UsEquity);
+    assert_eq!(MarketCalendar::infer(&Exchange::Binance, "BTCUSDT"), MarketCalendar::Continuous);
+    assert_eq!(MarketCalendar::infer(&Exchange::Yahoo, "EURUSD=X"), MarketCalendar::Forex);
+    assert_eq!(MarketCalendar::infer(&Exchange::Yahoo, "BTC-USD"), MarketCalendar::Continuous);
+    assert_eq!(MarketCalendar::infer(&Exchange::Yahoo, "BRK-B"), MarketCalendar::UsEquity);
+    assert_eq!(MarketCalendar::infer(&Exchange::Tiingo, "btcusd"), MarketCalendar::Continuous);
+    assert_eq!(MarketCalendar::infer(&Exchange::Tiingo, "AAPL"), MarketCalendar::UsEquity);
+    assert_eq!(MarketCalendar::from_asset_type(&AssetType::Etf), MarketCalendar::UsEquity);
+  }
+
+  #[tokio::test]
+  async fn it_knows_nyse_holidays() {
+    let date = |y: i32, m: u32, d: u32| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    assert!(is_nyse_holiday(date(2024, 3, 29))); // Good Friday
+    assert!(is_nyse_holiday(date(2023, 1, 2))); // New Year observed
+    assert!(is_nyse_holiday(date(2021, 12, 24))); // Christmas observed
+    assert!(!is_nyse_holiday(date(2021, 12, 31))); // Saturday New Year not observed
+    assert!(is_nyse_holiday(date(2024, 5, 27)) && is_nyse_holiday(date(2023, 11, 23)));
+    assert!(!MarketCalendar::UsEquity.is_trading_day(date(2024, 7, 4)));
+    assert!(MarketCalendar::UsEquity.is_trading_day(date(2024, 7, 5)));
+  }
+
+  #[tokio::test]
+  async fn it_checks_session_hours() {
+    let hourly: IntervalPeriod = IntervalPeriod::Hour(1, 10);
+    let equity: MarketCalendar = MarketCalendar::UsEquity;
+    assert!(equity.is_open(1688391000, &hourly)); // Mon 3 Jul 2023 09:30 New York
+    assert!(equity.is_open(1688389200, &hourly)); // 09:00 bar overlaps the open
+    assert!(!equity.is_open(1688385600, &hourly)); // 08:00
+    assert!(!equity.is_open(1688482800, &hourly)); // Tue 4 Jul holiday 11:00
+
+    let forex: MarketCalendar = MarketCalendar::Forex;
+    assert!(forex.is_open(1688151600, &hourly)); // Fri 30 Jun 2023 15:00 New York
+    assert!(!forex.is_open(1688169600, &hourly)); // Fri 20:00
+    assert!(!forex.is_open(1688328000, &hourly)); // Sun 16:00
+    assert!(forex.is_open(1688331600, &hourly)); // Sun 17:00 reopen
+  }
+
+  #[tokio::test]
+  async fn it_classifies_weekend_gaps() {
+    let hourly: IntervalPeriod = IntervalPeriod::Hour(1, 10);
+    let forex: MarketCalendar = MarketCalendar::Forex;
+
+    // Friday 16:00 bar to Sunday 17:00 bar is an expected closure
+    let labels: Vec<u64> = vec![1688151600, 1688155200, 1688331600, 1688335200];
+    let gaps: Vec<LabelGap> = forex.classify_label_gaps(&labels, &hourly);
+    assert_eq!(gaps.len(), 1);
+    assert_eq!((gaps[0].kind, gaps[0].missing_bars), (GapKind::Closure, 0));
+
+    // A gap extending into Sunday trading hours is partly missing data
+    let labels: Vec<u64> = vec![1688151600, 1688155200, 1688338800];
+    let gaps: Vec<LabelGap> = forex.classify_label_gaps(&labels, &hourly);
+    assert_eq!((gaps[0].kind, gaps[0].missing_bars), (GapKind::Missing, 2));
+
+    // Continuous calendars count every skipped bar
+    assert_eq!(MarketCalendar::Continuous.classify_label_gaps(&labels, &hourly)[0].kind, GapKind::Missing);
+  }
+
+  #[tokio::test]
+  async fn it_plans_calls_over_closures() {
+    let daily: IntervalPeriod = IntervalPeriod::Day(1, 10);
+    let monday: i64 = 1688342400; // Mon 3 Jul 2023 00:00 UTC
+    let start: i64 = MarketCalendar::Forex.subtract_open_bars(monday, &daily, 5);
+    assert_eq!(start, monday - 7 * 86400); // five weekdays back spans the weekend
+    assert_eq!(MarketCalendar::Continuous.subtract_open_bars(monday, &daily, 5), subtract_time(monday, &daily, &5));
+  }
+}
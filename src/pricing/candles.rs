@@ -1,16 +1,83 @@
+use futures::stream::{self, Stream};
 
 use crate::SmartError;
-use super::utils::{api_request, sleep};
-use super::times::{get_world_time_utc, subtract_time, convert_timestamp_to_iso, convert_iso_to_timestamp};
-use super::models::{Exchange, DydxCandle, IntervalPeriod, HistoricalPrices, CallItem};
+use crate::metrics::record_fetch;
+use super::schemas::{decode, AlpacaBars, AlphaVantageSeries, ByBitResponse, CandleRow, CoinbaseRow, GateCandle, HyperliquidCandle, KrakenResponse, OpenCloseRow, KuCoinResponse, OkxResponse, TiingoBar, TiingoCryptoPrices, TwelveSeries, TwelveValue, YahooChart, YahooResult};
+use super::jsonstream::stream_json_array;
+use super::utils::{alpaca_auth_query, api_post_with_timeout, api_request_with_timeout, sleep, TimeoutConfig};
+use super::ratelimit::tracks_request_weight;
+use super::quotes::yahoo_symbol;
+use super::cache::{read_cached_range, write_cached_range};
+use super::calendar::MarketCalendar;
+use super::clock::get_clock;
+use super::coalesce::coalesce;
+use super::prefetch::prefetched;
+use super::times::{get_world_time_utc, subtract_time, convert_timestamp_to_date, convert_timestamp_to_iso, convert_iso_to_timestamp, convert_us_eastern_to_timestamp};
+use super::translate::is_tiingo_crypto;
+use super::models::{
+  Exchange, DydxCandle, IntervalPeriod, HistoricalPrices, CallItem, LabelConvention,
+  DataTolerance, FetchReport, OhlcvSeries
+};
+
+/// Hyperliquid candle snapshot request body (the info API is POST only, times in milliseconds)
+const HYPERLIQUID_CANDLE_BODY: &str = r#"{"type":"candleSnapshot","req":{"coin":"{symbol}","interval":"{interval}","startTime":{fromTime},"endTime":{toTime}}}"#;
+
+/// Kraken OHLC serves only the latest 720 bars of an interval, however far back the since parameter reaches
+const KRAKEN_MAX_BARS: u32 = 720;
+
+/// Alpha Vantage Series Url
+/// Forex symbols (EUR/USD) and stocks (AAPL) use different series functions, as do daily and intraday bars
+fn alpha_vantage_series_url(symbol: &str, interval: &IntervalPeriod, api_key: &str) -> String {
+  let is_daily: bool = matches!(interval, IntervalPeriod::Day(_, _));
+  let query: String = match (symbol.split_once('/'), is_daily) {
+    (Some((from, to)), true) => format!("function=FX_DAILY&from_symbol={}&to_symbol={}", from, to),
+    (Some((from, to)), false) => format!("function=FX_INTRADAY&from_symbol={}&to_symbol={}&interval={{interval}}", from, to),
+    (None, true) => "function=TIME_SERIES_DAILY&symbol={symbol}".to_string(),
+    (None, false) => "function=TIME_SERIES_INTRADAY&symbol={symbol}&interval={interval}".to_string()
+  };
+  format!("https://www.alphavantage.co/query?{}&outputsize=full&apikey={}", query, api_key)
+}
+
+/// Alpaca Bars Url
+/// Crypto symbols (BTC/USD) use the crypto bars endpoint and stocks (AAPL) the IEX feed, credentials are sent as headers
+fn alpaca_bars_url(symbol: &str, api_key: &str) -> String {
+  let base_url: &str = match symbol.contains('/') {
+    true => "https://data.alpaca.markets/v1beta3/crypto/us/bars?symbols={symbol}&timeframe={interval}&start={fromTime}&end={toTime}&limit={limit}",
+    false => "https://data.alpaca.markets/v2/stocks/{symbol}/bars?timeframe={interval}&start={fromTime}&end={toTime}&limit={limit}&adjustment=raw&feed=iex"
+  };
+  format!("{}&{}", base_url, alpaca_auth_query(api_key))
+}
+
+/// Tiingo Prices Url
+/// Crypto tickers (btcusd) use the crypto endpoint, stocks the IEX feed for intraday bars and end of day prices for daily bars
+fn tiingo_prices_url(symbol: &str, interval: &IntervalPeriod, api_key: &str) -> String {
+  let base_url: &str = match (is_tiingo_crypto(symbol), interval) {
+    (true, _) => "https://api.tiingo.com/tiingo/crypto/prices?tickers={symbol}&startDate={fromTime}&endDate={toTime}&resampleFreq={interval}",
+    (false, IntervalPeriod::Day(_, _)) => "https://api.tiingo.com/tiingo/daily/{symbol}/prices?startDate={fromTime}&endDate={toTime}&resampleFreq={interval}",
+    (false, _) => "https://api.tiingo.com/iex/{symbol}/prices?startDate={fromTime}&endDate={toTime}&resampleFreq={interval}&columns=open,high,low,close,volume"
+  };
+  format!("{}&token={}", base_url, api_key)
+}
 
 /// API DOCUMENTATION:
+/// Alpaca: https://docs.alpaca.markets/reference/stockbars
+/// AlphaVantage: https://www.alphavantage.co/documentation/
 /// Binance: https://binance-docs.github.io/apidocs/futures/en/#change-log
+/// BinanceCoinM: https://binance-docs.github.io/apidocs/delivery/en/#kline-candlestick-data
+/// BinanceSpot: https://binance-docs.github.io/apidocs/spot/en/#kline-candlestick-data
 /// BinanceUs: https://docs.binance.us/#get-order-book-depth
+/// Bitfinex: https://docs.bitfinex.com/reference/rest-public-candles
 /// ByBit: https://bybit-exchange.github.io/docs/api-explorer/v5/market/kline
 /// Coinbase: https://docs.cloud.coinbase.com/exchange/reference/
-/// Dydx: https://dydxprotocol.github.io/v3-teacher/#public-http-api
+/// Dydx: https://docs.dydx.exchange/api_integration-indexer/indexer_api
+/// GateIo: https://www.gate.io/docs/developers/apiv4/#get-futures-candlesticks
+/// Hyperliquid: https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/info-endpoint#candle-snapshot
+/// Kraken: https://docs.kraken.com/rest/#tag/Market-Data/operation/getOHLCData
+/// KuCoin: https://www.kucoin.com/docs/rest/spot-trading/market-data/get-klines
+/// Okx: https://www.okx.com/docs-v5/en/#public-data-rest-api-get-candlesticks-history
+/// Tiingo: https://www.tiingo.com/documentation/iex
 /// Twelve: https://twelvedata.com/docs
+/// Yahoo: https://query1.finance.yahoo.com/v8/finance/chart (unofficial, no published documentation)
 
 /*
   Price Builder Models
@@ -23,7 +90,11 @@ pub struct CandleBuilder {
   pub interval: IntervalPeriod,
   pub exchange: Exchange,
   pub max_limit: i64,
-  pub query_url: String
+  pub query_url: String,
+  pub label_convention: LabelConvention,
+  pub tolerance: DataTolerance,
+  pub calendar: MarketCalendar, // trading hours used for call planning and gap classification
+  pub timeout: Option<TimeoutConfig> // overrides the configured request timeouts
 }
 
 impl CandleBuilder {
@@ -34,13 +105,41 @@ impl CandleBuilder {
     twelve_api_key: Option<&str>
   ) -> Self {
     let max_limit: i64 = Self::get_max_limit(&exchange);
+    let calendar: MarketCalendar = MarketCalendar::infer(&exchange, &symbol);
 
     let query_url: String = match exchange {
+      Exchange::Alpaca => {
+        match twelve_api_key {
+          Some(api_key) => alpaca_bars_url(&symbol, api_key), // Limit 10000
+          None => panic!("Must provide an API key for Alpaca provider")
+        }
+      },
+      Exchange::AlphaVantage => {
+        match twelve_api_key {
+          Some(api_key) => alpha_vantage_series_url(&symbol, &interval, api_key), // Full history in a single call
+          None => panic!("Must provide an API key for AlphaVantage provider")
+        }
+      },
       Exchange::Binance => "https://fapi.binance.com/fapi/v1/klines?symbol={symbol}&interval={interval}&startTime={fromTime}&endTime={toTime}&limit={limit}".to_string(), // Limit 1000
+      Exchange::BinanceCoinM => "https://dapi.binance.com/dapi/v1/klines?symbol={symbol}&interval={interval}&startTime={fromTime}&endTime={toTime}&limit={limit}".to_string(), // Limit 1500
+      Exchange::BinanceSpot => "https://api.binance.com/api/v3/klines?symbol={symbol}&interval={interval}&startTime={fromTime}&endTime={toTime}&limit={limit}".to_string(), // Limit 1000
       Exchange::BinanceUs => "https://api.binance.us/api/v3/klines?symbol={symbol}&interval={interval}&startTime={fromTime}&endTime={toTime}&limit={limit}".to_string(), // Limit 1000
+      Exchange::Bitfinex => "https://api-pub.bitfinex.com/v2/candles/trade:{interval}:{symbol}/hist?start={fromTime}&end={toTime}&limit={limit}&sort=1".to_string(), // Limit 10000
       Exchange::ByBit => "https://api.bybit.com/v5/market/kline?category=linear&symbol={symbol}&interval={interval}&start={fromTime}&end={toTime}&limit={limit}".to_string(), // Limit 200
+      Exchange::ByBitInverse => "https://api.bybit.com/v5/market/kline?category=inverse&symbol={symbol}&interval={interval}&start={fromTime}&end={toTime}&limit={limit}".to_string(), // Limit 200
       Exchange::Coinbase => "https://api.exchange.coinbase.com/products/{symbol}/candles?granularity={interval}&start={fromTime}&end={toTime}".to_string(), // Limit 300
-      Exchange::Dydx => "https://api.dydx.exchange/v3/candles/{symbol}?resolution={interval}&fromISO={fromTime}&toISO={toTime}&limit={limit}".to_string(), // Limit 100
+      Exchange::Dydx => "https://indexer.dydx.trade/v4/candles/perpetualMarkets/{symbol}?resolution={interval}&fromISO={fromTime}&toISO={toTime}&limit={limit}".to_string(), // Limit 1000
+      Exchange::GateIo => "https://api.gateio.ws/api/v4/futures/usdt/candlestick?contract={symbol}&interval={interval}&from={fromTime}&to={toTime}".to_string(), // Limit 2000
+      Exchange::Hyperliquid => "https://api.hyperliquid.xyz/info".to_string(), // Limit 5000 (request in the POST body)
+      Exchange::Kraken => "https://api.kraken.com/0/public/OHLC?pair={symbol}&interval={interval}&since={fromTime}".to_string(), // Latest 720 only
+      Exchange::KuCoin => "https://api.kucoin.com/api/v1/market/candles?type={interval}&symbol={symbol}&startAt={fromTime}&endAt={toTime}".to_string(), // Limit 1500
+      Exchange::Okx => "https://www.okx.com/api/v5/market/history-candles?instId={symbol}&bar={interval}&before={fromTime}&after={toTime}&limit={limit}".to_string(), // Limit 100
+      Exchange::Tiingo => {
+        match twelve_api_key {
+          Some(api_key) => tiingo_prices_url(&symbol, &interval, api_key), // Limit 10000 (IEX intraday)
+          None => panic!("Must provide an API key for Tiingo provider")
+        }
+      },
       Exchange::Twelve => {
         match twelve_api_key {
           Some(api_key) => {
@@ -49,6 +148,10 @@ impl CandleBuilder {
           },
           None => panic!("Must provide an API key for Twelve provider")
         }
+      },
+      Exchange::Yahoo => {
+        let chart_symbol: String = yahoo_symbol(&symbol); // index symbols (^GSPC) are percent encoded
+        format!("https://query1.finance.yahoo.com/v8/finance/chart/{}?interval={{interval}}&period1={{fromTime}}&period2={{toTime}}&events=history", chart_symbol) // No limit (range based)
       }
     };
 
@@ -57,10 +160,42 @@ impl CandleBuilder {
       interval,
       exchange,
       max_limit,
-      query_url
+      query_url,
+      label_convention: LabelConvention::OpenTime,
+      tolerance: DataTolerance::Strict,
+      calendar,
+      timeout: None
     }
   }
 
+  /// With Tolerance
+  /// Sets whether failed pages abort the fetch or are reported as partial data
+  pub fn with_tolerance(mut self, tolerance: DataTolerance) -> Self {
+    self.tolerance = tolerance;
+    self
+  }
+
+  /// With Label Convention
+  /// Sets the label convention returned prices are normalized to (defaults to open time)
+  pub fn with_label_convention(mut self, label_convention: LabelConvention) -> Self {
+    self.label_convention = label_convention;
+    self
+  }
+
+  /// With Calendar
+  /// Overrides the trading calendar inferred from the exchange and symbol (e.g. from a known asset type)
+  pub fn with_calendar(mut self, calendar: MarketCalendar) -> Self {
+    self.calendar = calendar;
+    self
+  }
+
+  /// With Timeout
+  /// Overrides request timeouts for this fetch (e.g. large Twelve pages on slow links)
+  pub fn with_timeout(mut self, timeout: TimeoutConfig) -> Self {
+    self.timeout = Some(timeout);
+    self
+  }
+
   /// Get Max Limit
   /// Identifies max rows to be returned given exchange
   pub fn get_max_limit(exchange: &Exchange) -> i64 {
@@ -70,11 +205,22 @@ impl CandleBuilder {
     let buffer: i64 = 5;
 
     match exchange {
-      Exchange::Binance | Exchange::BinanceUs => 1000 - buffer,
-      Exchange::ByBit => 200 - buffer,
+      Exchange::Alpaca => 10000 - buffer,
+      Exchange::AlphaVantage => 100000 - buffer, // full output is returned in one call
+      Exchange::Binance | Exchange::BinanceSpot | Exchange::BinanceUs => 1000 - buffer,
+      Exchange::BinanceCoinM => 1500 - buffer,
+      Exchange::Bitfinex => 10000 - buffer,
+      Exchange::ByBit | Exchange::ByBitInverse => 200 - buffer,
       Exchange::Coinbase => 300 - buffer,
-      Exchange::Dydx => 100 - buffer,
-      Exchange::Twelve => 5000 - buffer
+      Exchange::Dydx => 1000 - buffer,
+      Exchange::GateIo => 2000 - buffer,
+      Exchange::Hyperliquid => 5000 - buffer,
+      Exchange::Kraken => KRAKEN_MAX_BARS as i64 - buffer,
+      Exchange::KuCoin => 1500 - buffer,
+      Exchange::Okx => 100 - buffer,
+      Exchange::Tiingo => 10000 - buffer,
+      Exchange::Twelve => 5000 - buffer,
+      Exchange::Yahoo => 20000 - buffer // range based, no row limit
     }
   }
 
@@ -92,30 +238,53 @@ impl CandleBuilder {
   /// Structure Interval
   /// Converts Interval details into exchange readable str
   fn structure_interval<'a>(&self) -> Result<&'a str, SmartError> {
-    use Exchange::{Binance, BinanceUs, ByBit, Coinbase, Dydx, Twelve};
+    use Exchange::{Alpaca, AlphaVantage, Binance, BinanceCoinM, BinanceSpot, BinanceUs, Bitfinex, ByBit, ByBitInverse, Coinbase, Dydx, GateIo, Hyperliquid, Kraken, KuCoin, Okx, Tiingo, Twelve, Yahoo};
     use IntervalPeriod::{Min, Hour, Day};
 
     let interval: &str = match (&self.exchange, &self.interval) {
-      (Binance | BinanceUs, Min(int, _)) if *int == 5 => "5m",
-      (Binance | BinanceUs, Min(int, _)) if *int == 15 => "15m",
-      (Binance | BinanceUs, Min(int, _)) if *int == 30 => "30m",
-      (Binance | BinanceUs, Hour(int, _)) if *int == 1 => "1h",
-      (Binance | BinanceUs, Hour(int, _)) if *int == 2 => "2h",
-      (Binance | BinanceUs, Hour(int, _)) if *int == 4 => "4h",
-      (Binance | BinanceUs, Hour(int, _)) if *int == 6 => "6h",
-      (Binance | BinanceUs, Hour(int, _)) if *int == 8 => "8h",
-      (Binance | BinanceUs, Hour(int, _)) if *int == 12 => "12h",
-      (Binance | BinanceUs, Day(int, _)) if *int == 1 => "1d",
-
-      (ByBit, Min(int, _)) if *int == 5 => "5",
-      (ByBit, Min(int, _)) if *int == 15 => "15",
-      (ByBit, Min(int, _)) if *int == 30 => "30",
-      (ByBit, Hour(int, _)) if *int == 1 => "60",
-      (ByBit, Hour(int, _)) if *int == 2 => "120",
-      (ByBit, Hour(int, _)) if *int == 4 => "240",
-      (ByBit, Hour(int, _)) if *int == 6 => "360",
-      (ByBit, Hour(int, _)) if *int == 12 => "720",
-      (ByBit, Day(int, _)) if *int == 1 => "D",
+      (Alpaca, Min(int, _)) if *int == 5 => "5Min",
+      (Alpaca, Min(int, _)) if *int == 15 => "15Min",
+      (Alpaca, Min(int, _)) if *int == 30 => "30Min",
+      (Alpaca, Hour(int, _)) if *int == 1 => "1Hour",
+      (Alpaca, Hour(int, _)) if *int == 2 => "2Hour",
+      (Alpaca, Hour(int, _)) if *int == 4 => "4Hour",
+      (Alpaca, Hour(int, _)) if *int == 12 => "12Hour",
+      (Alpaca, Day(int, _)) if *int == 1 => "1Day",
+
+      (AlphaVantage, Min(int, _)) if *int == 5 => "5min",
+      (AlphaVantage, Min(int, _)) if *int == 15 => "15min",
+      (AlphaVantage, Min(int, _)) if *int == 30 => "30min",
+      (AlphaVantage, Hour(int, _)) if *int == 1 => "60min",
+      (AlphaVantage, Day(int, _)) if *int == 1 => "daily",
+
+      (Binance | BinanceCoinM | BinanceSpot | BinanceUs, Min(int, _)) if *int == 5 => "5m",
+      (Binance | BinanceCoinM | BinanceSpot | BinanceUs, Min(int, _)) if *int == 15 => "15m",
+      (Binance | BinanceCoinM | BinanceSpot | BinanceUs, Min(int, _)) if *int == 30 => "30m",
+      (Binance | BinanceCoinM | BinanceSpot | BinanceUs, Hour(int, _)) if *int == 1 => "1h",
+      (Binance | BinanceCoinM | BinanceSpot | BinanceUs, Hour(int, _)) if *int == 2 => "2h",
+      (Binance | BinanceCoinM | BinanceSpot | BinanceUs, Hour(int, _)) if *int == 4 => "4h",
+      (Binance | BinanceCoinM | BinanceSpot | BinanceUs, Hour(int, _)) if *int == 6 => "6h",
+      (Binance | BinanceCoinM | BinanceSpot | BinanceUs, Hour(int, _)) if *int == 8 => "8h",
+      (Binance | BinanceCoinM | BinanceSpot | BinanceUs, Hour(int, _)) if *int == 12 => "12h",
+      (Binance | BinanceCoinM | BinanceSpot | BinanceUs, Day(int, _)) if *int == 1 => "1d",
+
+      (Bitfinex, Min(int, _)) if *int == 5 => "5m",
+      (Bitfinex, Min(int, _)) if *int == 15 => "15m",
+      (Bitfinex, Min(int, _)) if *int == 30 => "30m",
+      (Bitfinex, Hour(int, _)) if *int == 1 => "1h",
+      (Bitfinex, Hour(int, _)) if *int == 6 => "6h",
+      (Bitfinex, Hour(int, _)) if *int == 12 => "12h",
+      (Bitfinex, Day(int, _)) if *int == 1 => "1D",
+
+      (ByBit | ByBitInverse, Min(int, _)) if *int == 5 => "5",
+      (ByBit | ByBitInverse, Min(int, _)) if *int == 15 => "15",
+      (ByBit | ByBitInverse, Min(int, _)) if *int == 30 => "30",
+      (ByBit | ByBitInverse, Hour(int, _)) if *int == 1 => "60",
+      (ByBit | ByBitInverse, Hour(int, _)) if *int == 2 => "120",
+      (ByBit | ByBitInverse, Hour(int, _)) if *int == 4 => "240",
+      (ByBit | ByBitInverse, Hour(int, _)) if *int == 6 => "360",
+      (ByBit | ByBitInverse, Hour(int, _)) if *int == 12 => "720",
+      (ByBit | ByBitInverse, Day(int, _)) if *int == 1 => "D",
 
       (Coinbase, Min(int, _)) if *int == 5 => "300",
       (Coinbase, Min(int, _)) if *int == 15 => "900",
@@ -130,6 +299,54 @@ impl CandleBuilder {
       (Dydx, Hour(int, _)) if *int == 4 => "4HOURS",
       (Dydx, Day(int, _)) if *int == 1 => "1DAY",
 
+      (GateIo, Min(int, _)) if *int == 5 => "5m",
+      (GateIo, Min(int, _)) if *int == 15 => "15m",
+      (GateIo, Min(int, _)) if *int == 30 => "30m",
+      (GateIo, Hour(int, _)) if *int == 1 => "1h",
+      (GateIo, Hour(int, _)) if *int == 4 => "4h",
+      (GateIo, Hour(int, _)) if *int == 8 => "8h",
+      (GateIo, Day(int, _)) if *int == 1 => "1d",
+
+      (Hyperliquid, Min(int, _)) if *int == 5 => "5m",
+      (Hyperliquid, Min(int, _)) if *int == 15 => "15m",
+      (Hyperliquid, Min(int, _)) if *int == 30 => "30m",
+      (Hyperliquid, Hour(int, _)) if *int == 1 => "1h",
+      (Hyperliquid, Hour(int, _)) if *int == 2 => "2h",
+      (Hyperliquid, Hour(int, _)) if *int == 4 => "4h",
+      (Hyperliquid, Hour(int, _)) if *int == 8 => "8h",
+      (Hyperliquid, Hour(int, _)) if *int == 12 => "12h",
+      (Hyperliquid, Day(int, _)) if *int == 1 => "1d",
+
+      (Kraken, Min(int, _)) if *int == 5 => "5",
+      (Kraken, Min(int, _)) if *int == 15 => "15",
+      (Kraken, Min(int, _)) if *int == 30 => "30",
+      (Kraken, Hour(int, _)) if *int == 1 => "60",
+      (Kraken, Hour(int, _)) if *int == 4 => "240",
+      (Kraken, Day(int, _)) if *int == 1 => "1440",
+
+      (KuCoin, Min(int, _)) if *int == 5 => "5min",
+      (KuCoin, Min(int, _)) if *int == 15 => "15min",
+      (KuCoin, Min(int, _)) if *int == 30 => "30min",
+      (KuCoin, Hour(int, _)) if *int == 1 => "1hour",
+      (KuCoin, Hour(int, _)) if *int == 4 => "4hour",
+      (KuCoin, Day(int, _)) if *int == 1 => "1day",
+
+      (Okx, Min(int, _)) if *int == 5 => "5m",
+      (Okx, Min(int, _)) if *int == 15 => "15m",
+      (Okx, Min(int, _)) if *int == 30 => "30m",
+      (Okx, Hour(int, _)) if *int == 1 => "1H",
+      (Okx, Hour(int, _)) if *int == 4 => "4H",
+      (Okx, Day(int, _)) if *int == 1 => "1Dutc", // 1D is aligned to Hong Kong time
+
+      (Tiingo, Min(int, _)) if *int == 5 => "5min",
+      (Tiingo, Min(int, _)) if *int == 15 => "15min",
+      (Tiingo, Min(int, _)) if *int == 30 => "30min",
+      (Tiingo, Hour(int, _)) if *int == 1 => "1hour",
+      (Tiingo, Hour(int, _)) if *int == 2 => "2hour",
+      (Tiingo, Hour(int, _)) if *int == 4 => "4hour",
+      (Tiingo, Day(int, _)) if *int == 1 && is_tiingo_crypto(&self.symbol) => "1day",
+      (Tiingo, Day(int, _)) if *int == 1 => "daily",
+
       (Twelve, Min(int, _)) if *int == 5 => "5min",
       (Twelve, Min(int, _)) if *int == 15 => "15min",
       (Twelve, Min(int, _)) if *int == 30 => "30min",
@@ -138,6 +355,9 @@ impl CandleBuilder {
       (Twelve, Hour(int, _)) if *int == 4 => "4h",
       (Twelve, Day(int, _)) if *int == 1 => "1day",
 
+      (Yahoo, Day(int, _)) if *int == 1 => "1d",
+      (Yahoo, Day(int, _)) if *int == 7 => "1wk",
+
       _ => return Err(SmartError::RuntimeCheck("Interval exchange match not found".to_string()))
     };
 
@@ -149,40 +369,32 @@ impl CandleBuilder {
   /// Second Item: The final amount of rows required on the last call
   fn calculate_call_count(&self) -> (usize, i64) {
 
-    let total_factor: f32 = match self.interval {
-      IntervalPeriod::Min(_int, minutes) => {
-        // minutes as f32 / int as f32
-        minutes as f32
-      },
-      IntervalPeriod::Hour(_int, hours) => {
-        // hours as f32 / int as f32
-        hours as f32
-      },
-      IntervalPeriod::Day(_int, days) => {
-        // days as f32 / int as f32
-        days as f32
-      }
+    let total_factor: i64 = match self.interval {
+      IntervalPeriod::Min(_int, minutes) => minutes as i64,
+      IntervalPeriod::Hour(_int, hours) => hours as i64,
+      IntervalPeriod::Day(_int, days) => days as i64
     };
 
     // Calculate call count required given calls needed and max limit
-    let call_ratio: f32 = total_factor / self.max_limit as f32;
-    let iterations: usize = call_ratio.floor() as usize;
-    let final_n: f32 = call_ratio.rem_euclid(1.0) * self.max_limit as f32;
-
-    // dbg!(&total_factor);
-    // dbg!(&call_ratio);
-    // dbg!(&self.max_limit);
-    // dbg!(&iterations);
-    // dbg!(&final_n);
+    // Integer division so the final call never loses a bar to float rounding
+    let iterations: usize = (total_factor / self.max_limit) as usize;
+    let final_n: i64 = total_factor % self.max_limit;
 
     // Return iterations and final n
-    (iterations, final_n as i64)
+    (iterations, final_n)
   }
 
   /// Set Calls Required as Vector
   /// Structures vector of times required
+  /// Each call spans max limit open bars so market closures do not shorten the history received
   pub async fn calls_required(&self) -> Result<Vec<CallItem>, SmartError> {
 
+    // Guard: Ensure Kraken can serve the requested history
+    if self.exchange == Exchange::Kraken && self.requested_bars() > KRAKEN_MAX_BARS {
+      let e: String = format!("Kraken serves only the latest {} bars, {} requested for {}", KRAKEN_MAX_BARS, self.requested_bars(), self.symbol);
+      return Err(SmartError::RuntimeCheck(e));
+    }
+
     // Initialize
     let mut call_items: Vec<CallItem> = vec![];
     let (iterations, final_n) = self.calculate_call_count();
@@ -193,7 +405,7 @@ impl CandleBuilder {
 
     // Structure times
     for _ in 0..iterations {
-      let start_time: i64 = subtract_time(end_time, &self.interval, &self.max_limit);
+      let start_time: i64 = self.calendar.subtract_open_bars(end_time, &self.interval, self.max_limit);
       let call_item: CallItem = CallItem {
         from_time: start_time,
         to_time: end_time,
@@ -207,7 +419,7 @@ impl CandleBuilder {
     
     // Add final number if less than max required
     if final_n > 0 {
-      let start_time: i64 = subtract_time(end_time, &self.interval, &final_n);
+      let start_time: i64 = self.calendar.subtract_open_bars(end_time, &self.interval, final_n);
       
       let call_item: CallItem = CallItem {
         from_time: start_time,
@@ -224,7 +436,7 @@ impl CandleBuilder {
   /// Format call times
   /// Format call times depending on exchange
   fn format_call_times(&self, timestamp: i64, is_offset: bool) -> String {
-    use Exchange::{Binance, BinanceUs, ByBit, Coinbase, Dydx, Twelve};
+    use Exchange::{Alpaca, AlphaVantage, Binance, BinanceCoinM, BinanceSpot, BinanceUs, Bitfinex, ByBit, ByBitInverse, Coinbase, Dydx, GateIo, Hyperliquid, Kraken, KuCoin, Okx, Tiingo, Twelve, Yahoo};
 
     // Offset to ensure adequate coverage of from and to times
     // Different exchanges provide different coverage depending on times
@@ -232,170 +444,293 @@ impl CandleBuilder {
     let offset: i64 = if is_offset { 10 } else { 0 };
 
     match self.exchange {
-      Binance | BinanceUs | ByBit => {
+      Alpaca => convert_timestamp_to_iso(timestamp - offset),
+      AlphaVantage => timestamp.to_string(),
+      Binance | BinanceCoinM | BinanceSpot | BinanceUs | ByBit | ByBitInverse => {
         let new_timestamp: i64 = timestamp * 1000;
         new_timestamp.to_string()
       },
+      Bitfinex => ((timestamp - offset) * 1000).to_string(),
       Coinbase => timestamp.to_string(),
       Dydx => convert_timestamp_to_iso(timestamp - offset),
-      Twelve => timestamp.to_string()
+      GateIo => (timestamp - offset).to_string(),
+      Hyperliquid => ((timestamp - offset) * 1000).to_string(),
+      Kraken => (timestamp - offset).to_string(),
+      KuCoin => (timestamp - offset).to_string(),
+      Okx => ((timestamp - offset) * 1000).to_string(), // before and after bounds are exclusive
+      Tiingo => convert_timestamp_to_date(timestamp - offset), // whole days, overlapping bars are removed as duplicates
+      Twelve => timestamp.to_string(),
+      Yahoo => timestamp.to_string()
     }
   }
 
   /// Remove duplicate candles
-  /// Removes any duplicate candles depending on exchange quirks
-  fn remove_duplicates(&self, labels: &mut Vec<u64>, prices: &mut Vec<f64>) {
-    let mut indices_to_remove: Vec<usize> = vec![];
-
-    // Start from the end
-    // Removing elements from the beginning would shift the remaining indices
-    let len = labels.len();
-    for i in (1..len).rev() {
-        if labels[i] == labels[i-1] {
-            indices_to_remove.push(i);
-        }
-    }
+  /// Removes any duplicate candles depending on exchange quirks (consecutive bars sharing a label)
+  fn remove_duplicates(&self, bars: &mut Vec<CandleRow>) {
+    bars.dedup_by_key(|bar| bar.time);
+  }
 
-    // Remove identified indices
-    for &index in indices_to_remove.iter() {
-        labels.remove(index);
-        prices.remove(index);
-    }
+  /// Deserialize Candles - Alpaca
+  /// Deserializes candles into bars - Alpaca
+  fn deserialize_candles_alpaca(&self, data_obj: serde_json::Value) -> Result<Vec<CandleRow>, SmartError>  {
+    let bars: AlpacaBars = decode(&self.exchange, data_obj)?;
+    Ok(bars.into_bars(&self.symbol).into_iter()
+      .map(|bar| CandleRow::from_fields(convert_iso_to_timestamp(bar.t, "%Y-%m-%dT%H:%M:%S%z"), bar.o, bar.h, bar.l, bar.c.0, bar.v))
+      .collect())
+  }
+
+  /// Deserialize Candles - AlphaVantage
+  /// Deserializes candles into bars - AlphaVantage (stock intraday bars are labelled in US/Eastern)
+  fn deserialize_candles_alpha_vantage(&self, data_obj: serde_json::Value) -> Result<Vec<CandleRow>, SmartError>  {
+    let series: AlphaVantageSeries = decode(&self.exchange, data_obj)?;
+    let is_eastern: bool = series.time_zone().map(|tz| tz == "US/Eastern").unwrap_or(false);
+    Ok(series.into_bars()?.into_iter()
+      .map(|(datetime, bar)| {
+        let label: u64 = match is_eastern && datetime.contains(' ') {
+          true => convert_us_eastern_to_timestamp(&datetime),
+          false => convert_iso_to_timestamp(datetime, "%Y-%m-%dT%H:%M:%S%z")
+        };
+        CandleRow::from_fields(label, bar.open, bar.high, bar.low, bar.close.0, bar.volume)
+      })
+      .collect())
   }
 
   /// Deserialize Candles - Binance
-  /// Deserializes candles into time labels and prices - Binance
-  async fn deserialize_candles_binance(&self, res_data: reqwest::Response) -> Result<(Vec<u64>, Vec<f64>), SmartError>  {
-    let candles_json: Vec<serde_json::Value> = res_data.json().await?;
-    let mut prices: Vec<f64> = vec![];
-    let mut labels: Vec<u64> = vec![];
-    for candle in candles_json.iter() {
-      let close: f64 = candle[4].as_str().and_then(|s| s.parse().ok()).unwrap_or(0.0);
-      let label: u64 = match candle[0].as_u64() {
-        Some(val) => val / 1000,
-        None => 0
-      };
-      prices.push(close);
-      labels.push(label);
-    }
-    Ok((labels, prices))
+  /// Deserializes candles into bars - Binance
+  fn deserialize_candles_binance(&self, rows: Vec<CandleRow>) -> Result<Vec<CandleRow>, SmartError>  {
+    Ok(rows.into_iter().map(|row| CandleRow { time: row.time / 1000, ..row }).collect())
+  }
+
+  /// Deserialize Candles - Bitfinex
+  /// Deserializes candles into bars - Bitfinex
+  fn deserialize_candles_bitfinex(&self, rows: Vec<OpenCloseRow>) -> Result<Vec<CandleRow>, SmartError>  {
+    Ok(rows.into_iter().map(|row| CandleRow { time: row.0.time / 1000, ..row.0 }).collect())
   }
 
   /// Deserialize Candles - ByBit
-  /// Deserializes candles into time labels and prices - ByBit
-  async fn deserialize_candles_bybit(&self, res_data: reqwest::Response) -> Result<(Vec<u64>, Vec<f64>), SmartError>  {
-    let candles_json: serde_json::Value = res_data.json().await?;
-    let mut prices: Vec<f64> = vec![];
-    let mut labels: Vec<u64> = vec![];
-    if let Some(candles_json) = candles_json.get("result").and_then(|res| res.get("list")).and_then(|list| list.as_array()) {
-      for candle in candles_json.iter() {
-        if let Some(candle_array) = candle.as_array() {
-          let close: f64 = candle_array.get(4).and_then(|s| s.as_str()).and_then(|s| s.parse().ok()).unwrap_or(0.0);
-          let label: u64 = candle_array.get(0).and_then(|s| s.as_str()).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0) / 1000;
-          
-          prices.push(close);
-          labels.push(label);
-        }
-      }
-    }
-    labels.reverse();
-    prices.reverse();
-    Ok((labels, prices))
+  /// Deserializes candles into bars - ByBit
+  fn deserialize_candles_bybit(&self, data_obj: serde_json::Value) -> Result<Vec<CandleRow>, SmartError>  {
+    let rows: Vec<CandleRow> = decode::<ByBitResponse>(&self.exchange, data_obj)?.into_list()?;
+    Ok(rows.into_iter().rev().map(|row| CandleRow { time: row.time / 1000, ..row }).collect())
   }
 
   /// Deserialize API Response - Coinbase
-  /// Deserializes candles into time labels and prices - Coinbase
-  async fn deserialize_candles_coinbase(&self, res_data: reqwest::Response) -> Result<(Vec<u64>, Vec<f64>), SmartError>  {
-    let candles_json: Vec<serde_json::Value> = res_data.json().await?;
-    let mut prices: Vec<f64> = vec![];
-    let mut labels: Vec<u64> = vec![];
-    for candle in candles_json.iter() {
-      let close: f64 = match candle[4].as_f64() {
-        Some(val) => val,
-        None => 0.0
-      };
-      let label: u64 = match candle[0].as_u64() {
-        Some(val) => val,
-        None => 0
-      };
-      prices.push(close);
-      labels.push(label);
-    }
-    labels.reverse();
-    prices.reverse();
-    Ok((labels, prices))
+  /// Deserializes candles into bars - Coinbase
+  fn deserialize_candles_coinbase(&self, data_obj: serde_json::Value) -> Result<Vec<CandleRow>, SmartError>  {
+    let rows: Vec<CoinbaseRow> = decode(&self.exchange, data_obj)?;
+    Ok(rows.into_iter().rev().map(|row| row.0).collect())
   }
 
   /// Deserialize API Response - Dydx
-  /// Deserializes candles into time labels and prices - Dydx
-  async fn deserialize_candles_dydx(&self, res_data: reqwest::Response) -> Result<(Vec<u64>, Vec<f64>), SmartError>  {
-    let candles_json: serde_json::Value = res_data.json().await?;
-    let candles: DydxCandle = serde_json::from_value(candles_json)?;
-
-    let mut prices: Vec<f64> = vec![];
-    let mut labels: Vec<u64> = vec![];
+  /// Deserializes candles into bars - Dydx
+  fn deserialize_candles_dydx(&self, data_obj: serde_json::Value) -> Result<Vec<CandleRow>, SmartError>  {
+    let candles: DydxCandle = decode(&self.exchange, data_obj)?;
 
+    let mut bars: Vec<CandleRow> = vec![];
     for candle in candles.candles {
-      let close: f64 = candle.close.parse()?;
       let label_str: String = candle.startedAt;
       let label: u64 = convert_iso_to_timestamp(label_str, "%Y-%m-%dT%H:%M:%S%.3f%z");
-      prices.push(close);
-      labels.push(label);
+      bars.push(CandleRow {
+        time: label,
+        open: candle.open.parse()?,
+        high: candle.high.parse()?,
+        low: candle.low.parse()?,
+        close: candle.close.parse()?,
+        volume: candle.baseTokenVolume.parse()?
+      });
     }
 
-    labels.reverse();
-    prices.reverse();
+    bars.reverse();
+    Ok(bars)
+  }
+
+  /// Deserialize API Response - GateIo
+  /// Deserializes candles into bars - GateIo
+  fn deserialize_candles_gateio(&self, data_obj: serde_json::Value) -> Result<Vec<CandleRow>, SmartError>  {
+    let candles: Vec<GateCandle> = decode(&self.exchange, data_obj)?;
+    Ok(candles.into_iter().map(|candle| CandleRow::from_fields(candle.t.0 as u64, candle.o, candle.h, candle.l, candle.c.0, candle.v)).collect())
+  }
+
+  /// Deserialize API Response - Hyperliquid
+  /// Deserializes candles into bars - Hyperliquid
+  fn deserialize_candles_hyperliquid(&self, data_obj: serde_json::Value) -> Result<Vec<CandleRow>, SmartError>  {
+    let candles: Vec<HyperliquidCandle> = decode(&self.exchange, data_obj)?;
+    Ok(candles.into_iter().map(|candle| CandleRow::from_fields(candle.t / 1000, candle.o, candle.h, candle.l, candle.c.0, candle.v)).collect())
+  }
 
-    Ok((labels, prices))
+  /// Deserialize API Response - Kraken
+  /// Deserializes candles into bars - Kraken
+  fn deserialize_candles_kraken(&self, data_obj: serde_json::Value) -> Result<Vec<CandleRow>, SmartError>  {
+    decode::<KrakenResponse>(&self.exchange, data_obj)?.into_candles()
+  }
+
+  /// Deserialize API Response - KuCoin
+  /// Deserializes candles into bars - KuCoin
+  fn deserialize_candles_kucoin(&self, data_obj: serde_json::Value) -> Result<Vec<CandleRow>, SmartError>  {
+    let rows: Vec<OpenCloseRow> = decode::<KuCoinResponse>(&self.exchange, data_obj)?.into_data()?;
+    Ok(rows.into_iter().rev().map(|row| row.0).collect())
+  }
+
+  /// Deserialize API Response - Okx
+  /// Deserializes candles into bars - Okx
+  fn deserialize_candles_okx(&self, data_obj: serde_json::Value) -> Result<Vec<CandleRow>, SmartError>  {
+    let rows: Vec<CandleRow> = decode::<OkxResponse>(&self.exchange, data_obj)?.into_list()?;
+    Ok(rows.into_iter().rev().map(|row| CandleRow { time: row.time / 1000, ..row }).collect())
+  }
+
+  /// Deserialize Candles - Tiingo
+  /// Deserializes candles into bars - Tiingo (crypto bars are wrapped per ticker)
+  fn deserialize_candles_tiingo(&self, data_obj: serde_json::Value) -> Result<Vec<CandleRow>, SmartError>  {
+    let bars: Vec<TiingoBar> = match is_tiingo_crypto(&self.symbol) {
+      true => decode::<Vec<TiingoCryptoPrices>>(&self.exchange, data_obj)?.into_iter()
+        .find(|prices| prices.ticker.eq_ignore_ascii_case(&self.symbol))
+        .map(|prices| prices.price_data)
+        .unwrap_or_default(),
+      false => decode(&self.exchange, data_obj)?
+    };
+    Ok(bars.into_iter()
+      .map(|bar| {
+        let label: u64 = convert_iso_to_timestamp(bar.date, "%Y-%m-%dT%H:%M:%S%.f%z");
+        CandleRow::from_fields(label, bar.open, bar.high, bar.low, bar.close.0, bar.volume)
+      })
+      .collect())
   }
 
   /// Deserialize API Response - Twelve
-  /// Deserializes candles into time labels and prices - Coinbase
-  async fn deserialize_candles_twelve(&self, res_data: reqwest::Response) -> Result<(Vec<u64>, Vec<f64>), SmartError>  {
-    let data: serde_json::Value = res_data.json().await?;
-    let mut prices: Vec<f64> = vec![];
-    let mut labels: Vec<u64> = vec![];
-    if let Some(values) = data.get("values") {
-      for value in values.as_array().unwrap() {
-        let close: f64 = match value["close"].as_str() {
-          Some(val) => val.parse().unwrap_or(0.0),
-          None => 0.0
-        };
-        let label_str: String = match value["datetime"].as_str() {
-          Some(val) => val.to_string(),
-          None => "".to_string()
-        };
+  /// Deserializes candles into bars - Twelve
+  fn deserialize_candles_twelve(&self, series: TwelveSeries) -> Result<Vec<CandleRow>, SmartError>  {
+    Ok(series.values.into_iter().rev()
+      .map(|value| {
+        let label: u64 = convert_iso_to_timestamp(value.datetime, "%Y-%m-%dT%H:%M:%S%z");
+        CandleRow::from_fields(label, value.open, value.high, value.low, value.close.0, value.volume)
+      })
+      .collect())
+  }
+
+  /// Deserialize Candles - Yahoo
+  /// Deserializes candles into bars - Yahoo
+  /// Bars are stamped at the session open so labels are floored to the exchange's local date
+  fn deserialize_candles_yahoo(&self, data_obj: serde_json::Value) -> Result<Vec<CandleRow>, SmartError>  {
+    let result: YahooResult = decode::<YahooChart>(&self.exchange, data_obj)?.into_result()?;
+    let gmtoffset: i64 = result.meta.gmtoffset;
+    Ok(result.into_bars().into_iter()
+      .map(|bar| {
+        let local_time: u64 = (bar.time as i64 + gmtoffset).max(0) as u64;
+        CandleRow { time: local_time - local_time % 86400, ..bar }
+      })
+      .collect())
+  }
 
-        let label: u64 = convert_iso_to_timestamp(label_str, "%Y-%m-%dT%H:%M:%S%z");
-        prices.push(close);
-        labels.push(label);
+  /// Deserialize API Response based on exchange
+  /// Deserializes the API response into bars (time in seconds)
+  /// Large pages (Binance, Bitfinex and Twelve) are decoded row by row as the body streams in
+  async fn deserialize_api_response_candles(&self, res_data: reqwest::Response) -> Result<Vec<CandleRow>, SmartError> {
+    match self.exchange {
+      Exchange::Binance | Exchange::BinanceCoinM | Exchange::BinanceSpot | Exchange::BinanceUs => {
+        let (rows, _) = stream_json_array::<CandleRow>(res_data, &self.exchange, &[]).await?;
+        self.deserialize_candles_binance(rows)
+      },
+      Exchange::Bitfinex => {
+        let (rows, _) = stream_json_array::<OpenCloseRow>(res_data, &self.exchange, &[]).await?;
+        self.deserialize_candles_bitfinex(rows)
+      },
+      Exchange::Twelve => {
+        let (values, envelope) = stream_json_array::<TwelveValue>(res_data, &self.exchange, &["values"]).await?;
+        decode::<TwelveSeries>(&self.exchange, envelope)?; // surfaces error payloads
+        self.deserialize_candles_twelve(TwelveSeries { values })
+      },
+      _ => {
+        let data_obj: serde_json::Value = res_data.json().await?;
+        self.deserialize_candles(data_obj)
       }
     }
-    labels.reverse();
-    prices.reverse();
-    Ok((labels, prices))
-}
+  }
 
-  /// Deserialize API Response based on exchange
-  /// Deserializes the API response into a price array
-  async fn deserialize_api_response_candles(&self, res_data: reqwest::Response) -> Result<(Vec<u64>, Vec<f64>), SmartError> {
-    let (labels, prices) = match self.exchange {
-      Exchange::Binance | Exchange::BinanceUs => self.deserialize_candles_binance(res_data).await?,
-      Exchange::ByBit => self.deserialize_candles_bybit(res_data).await?,
-      Exchange::Coinbase => self.deserialize_candles_coinbase(res_data).await?,
-      Exchange::Dydx => self.deserialize_candles_dydx(res_data).await?,
-      Exchange::Twelve => self.deserialize_candles_twelve(res_data).await?
+  /// Deserialize Candles
+  /// Routes a decoded response to the exchange's candle schema
+  fn deserialize_candles(&self, data_obj: serde_json::Value) -> Result<Vec<CandleRow>, SmartError> {
+    match self.exchange {
+      Exchange::Alpaca => self.deserialize_candles_alpaca(data_obj),
+      Exchange::AlphaVantage => self.deserialize_candles_alpha_vantage(data_obj),
+      Exchange::Binance | Exchange::BinanceCoinM | Exchange::BinanceSpot | Exchange::BinanceUs => self.deserialize_candles_binance(decode(&self.exchange, data_obj)?),
+      Exchange::Bitfinex => self.deserialize_candles_bitfinex(decode(&self.exchange, data_obj)?),
+      Exchange::ByBit | Exchange::ByBitInverse => self.deserialize_candles_bybit(data_obj),
+      Exchange::Coinbase => self.deserialize_candles_coinbase(data_obj),
+      Exchange::Dydx => self.deserialize_candles_dydx(data_obj),
+      Exchange::GateIo => self.deserialize_candles_gateio(data_obj),
+      Exchange::Hyperliquid => self.deserialize_candles_hyperliquid(data_obj),
+      Exchange::Kraken => self.deserialize_candles_kraken(data_obj),
+      Exchange::KuCoin => self.deserialize_candles_kucoin(data_obj),
+      Exchange::Okx => self.deserialize_candles_okx(data_obj),
+      Exchange::Tiingo => self.deserialize_candles_tiingo(data_obj),
+      Exchange::Twelve => self.deserialize_candles_twelve(decode(&self.exchange, data_obj)?),
+      Exchange::Yahoo => self.deserialize_candles_yahoo(data_obj)
+    }
+  }
+
+  /// Fetch Call
+  /// Makes a single paginated candle request and decodes the response
+  async fn fetch_call(&self, url: &str, body: Option<&str>) -> Result<Vec<CandleRow>, SmartError> {
+
+    // Make request
+    let res_data: reqwest::Response = match body {
+      Some(body) => api_post_with_timeout(url, body, self.timeout).await?,
+      None => api_request_with_timeout(url, self.timeout).await?
     };
 
-    Ok((labels, prices))
+    // Guard: Ensure status code
+    if res_data.status() != 200 {
+      let e: String = format!("Failed to extract data: {:?}", res_data.text().await);
+      return Err(SmartError::APIResponseStatus(e));
+    }
+
+    // Decode response
+    self.deserialize_api_response_candles(res_data).await
+  }
+
+  /// Requested Bars
+  /// Total number of bars the fetch is expected to return
+  fn requested_bars(&self) -> u32 {
+    match self.interval {
+      IntervalPeriod::Min(_, n) | IntervalPeriod::Hour(_, n) | IntervalPeriod::Day(_, n) => n
+    }
+  }
+
+  /// Cache Key
+  /// Identifies identical candle requests (used for coalescing and prefetching)
+  pub fn cache_key(&self) -> String {
+    format!("{:?}:{}:{:?}:{:?}:{:?}:{:?}", self.exchange, self.symbol, self.interval, self.label_convention, self.tolerance, self.calendar)
   }
 
   /// Fetch Prices - candles
   /// Retrieves prices required for candles
+  /// Served from the prefetch cache when fresh, and concurrent identical requests share a single fetch
   pub async fn fetch_prices_candles(&self) -> Result<HistoricalPrices, SmartError> {
+    let key: String = self.cache_key();
+    if let Some(prices) = prefetched(&key, get_world_time_utc()?, &self.interval) {
+      return Ok(prices);
+    }
+    coalesce(key, || self.fetch_prices_candles_uncoalesced()).await
+  }
 
-    // Get request_url
-    let mut request_url: String = self.get_request_url();
+  /// Page URL Template
+  /// Request url with symbol, interval and limit filled (from and to times left as placeholders)
+  fn page_url_template(&self) -> Result<String, SmartError> {
+    self.fill_template(&self.get_request_url())
+  }
+
+  /// Page Body Template
+  /// POST body with symbol, interval and limit filled for exchanges taking requests in the body (None for GET)
+  fn page_body_template(&self) -> Result<Option<String>, SmartError> {
+    match self.exchange {
+      Exchange::Hyperliquid => self.fill_template(HYPERLIQUID_CANDLE_BODY).map(Some),
+      _ => Ok(None)
+    }
+  }
+
+  /// Fill Template
+  /// Replaces the symbol, interval and limit placeholders
+  fn fill_template(&self, template: &str) -> Result<String, SmartError> {
 
     // Structure interval
     let interval_str: &str = self.structure_interval()?;
@@ -403,63 +738,191 @@ impl CandleBuilder {
     // Extract max limit
     let max_limit: String = Self::get_max_limit(&self.exchange).to_string();
     
-    // Replace url placeholders
-    request_url = request_url.replace("{symbol}", &self.symbol);
-    request_url = request_url.replace("{interval}", interval_str);
-    request_url = request_url.replace("{limit}", &max_limit);
-
-    // Get calls required
-    let calls_required: Vec<CallItem> = self.calls_required().await?;
+    // Replace placeholders
+    let mut filled: String = template.replace("{symbol}", &self.symbol);
+    filled = filled.replace("{interval}", interval_str);
+    filled = filled.replace("{limit}", &max_limit);
+    Ok(filled)
+  }
 
-    // Make API calls
-    let mut url: String;
-    let mut labels_full: Vec<u64> = vec![];
-    let mut prices_full: Vec<f64> = vec![];
-    let mut call_count:u8 = 0;
-    for call in calls_required {
+  /// Fetch Page
+  /// Waits as required by the rate limit then requests a single page of candles
+  async fn fetch_page(&self, request_url: &str, call: &CallItem, call_count: u8) -> Result<Vec<CandleRow>, SmartError> {
 
-      // Handle sleeping - protects API rate limit usage
-      call_count += 1;
+    // Handle sleeping - protects API rate limit usage
+    // Exchanges reporting request weight are paused by api_request instead
+    // Bitfinex allows 30 candle requests a minute so pages are evenly spaced
+    if self.exchange == Exchange::Bitfinex {
+      if call_count > 1 { sleep(2000).await }
+    } else if !tracks_request_weight(&self.exchange) {
       match call_count {
         1..=2 => sleep(50).await,
         3..=7 => sleep(500).await,
         8..=12 => sleep(1000).await,
-        13..=20 => sleep(2000).await,
-        _ => { break; }
+        _ => sleep(2000).await
       };
+    }
 
-      // Update from and to intervals
-      let from_time: String = self.format_call_times(call.from_time, true);
-      let to_time: String = self.format_call_times(call.to_time, false);
-      
-      // Update url
-      url = request_url.replace("{fromTime}", &from_time).to_string();
-      url = url.replace("{toTime}", &to_time).to_string();
+    // Update from and to intervals
+    let from_time: String = self.format_call_times(call.from_time, true);
+    let to_time: String = self.format_call_times(call.to_time, false);
+    
+    // Update url (and body for POST requests)
+    let url: String = request_url.replace("{fromTime}", &from_time).replace("{toTime}", &to_time);
+    let body: Option<String> = self.page_body_template()?
+      .map(|body| body.replace("{fromTime}", &from_time).replace("{toTime}", &to_time));
+    let mut bars: Vec<CandleRow> = self.fetch_call(&url, body.as_deref()).await?;
+
+    // Kraken has no end time parameter so returns everything since the start, clip to the page window
+    if self.exchange == Exchange::Kraken {
+      let to_time: u64 = call.to_time.max(0) as u64;
+      bars.retain(|bar| bar.time <= to_time);
+    }
 
-      // Make request
-      let res_data: reqwest::Response = api_request(&url).await?;
+    // AlphaVantage has no time parameters and returns the full history, and Tiingo bounds by whole days, clip to the page window
+    if matches!(self.exchange, Exchange::AlphaVantage | Exchange::Tiingo) {
+      let (from_time, to_time): (u64, u64) = (call.from_time.max(0) as u64, call.to_time.max(0) as u64);
+      bars.retain(|bar| bar.time >= from_time && bar.time <= to_time);
+    }
+    Ok(bars)
+  }
 
-      // Guard: Ensure status code
-      if res_data.status() != 200 {
-        let e: String = format!("Failed to extract data: {:?}", res_data.text().await);
-        return Err(SmartError::APIResponseStatus(e));
+  /// Fetch Prices - candles stream
+  /// Yields each page as it arrives with labels normalized (no fetch report, duplicates across pages not removed)
+  /// Allows progress reporting or incremental processing before the full pagination sequence completes
+  pub async fn fetch_prices_candles_stream(&self) -> Result<impl Stream<Item = Result<HistoricalPrices, SmartError>> + '_, SmartError> {
+    let request_url: String = self.page_url_template()?;
+    let calls_required: Vec<CallItem> = self.calls_required().await?;
+
+    Ok(stream::unfold((calls_required.into_iter(), 0_u8), move |(mut calls, mut call_count)| {
+      let request_url: String = request_url.clone();
+      async move {
+        let call: CallItem = calls.next()?;
+        let page: Result<(Vec<u64>, Vec<f64>), SmartError> = match read_cached_range(&self.exchange, &self.symbol, &self.interval, call.from_time, call.to_time).await {
+          Some(page) => Ok(page),
+          None => {
+            call_count += 1;
+            if call_count > 20 { return None }
+            self.fetch_page(&request_url, &call, call_count).await
+              .map(|bars| bars.into_iter().map(|bar| (bar.time, bar.close)).unzip())
+          }
+        };
+        let chunk: Result<HistoricalPrices, SmartError> = page.map(|(labels, prices)| {
+          let native: HistoricalPrices = HistoricalPrices { labels, prices, label_convention: self.exchange.label_convention(), fetch_report: None };
+          native.to_label_convention(self.label_convention, &self.interval)
+        });
+        Some((chunk, (calls, call_count)))
       }
+    }))
+  }
+
+  /// Fetch Prices - candles (uncoalesced)
+  /// Runs the paginated candle requests
+  async fn fetch_prices_candles_uncoalesced(&self) -> Result<HistoricalPrices, SmartError> {
+    let (bars, fetch_report) = self.fetch_bars(true).await?;
+    let (labels, prices): (Vec<u64>, Vec<f64>) = bars.into_iter().map(|bar| (bar.time, bar.close)).unzip();
+
+    // Normalize labels from the exchange's native convention
+    let prices = HistoricalPrices {
+      labels,
+      prices,
+      label_convention: self.exchange.label_convention(),
+      fetch_report: Some(fetch_report)
+    };
+    Ok(prices.to_label_convention(self.label_convention, &self.interval))
+  }
 
-      // Decode and append response
-      let (mut labels, mut prices) = self.deserialize_api_response_candles(res_data).await?;
-      labels_full.append(&mut labels);
-      prices_full.append(&mut prices);
+  /// Fetch Candles - OHLCV
+  /// Runs the paginated candle requests keeping open, high, low and volume alongside the close
+  /// Fields a provider does not report are filled from the close (volume zero), cached pages are bypassed as they hold closes only
+  pub async fn fetch_candles_ohlcv(&self) -> Result<OhlcvSeries, SmartError> {
+    let (bars, _) = self.fetch_bars(false).await?;
+
+    // Normalize labels from the exchange's native convention
+    let ohlcv: OhlcvSeries = OhlcvSeries {
+      labels: bars.iter().map(|bar| bar.time).collect(),
+      open: bars.iter().map(|bar| bar.open).collect(),
+      high: bars.iter().map(|bar| bar.high).collect(),
+      low: bars.iter().map(|bar| bar.low).collect(),
+      close: bars.iter().map(|bar| bar.close).collect(),
+      volume: bars.iter().map(|bar| bar.volume).collect(),
+      label_convention: self.exchange.label_convention()
+    };
+    Ok(ohlcv.to_label_convention(self.label_convention, &self.interval))
+  }
+
+  /// Fetch Bars
+  /// Runs the paginated candle requests returning deduplicated bars (native labels) with a completeness report
+  /// Cached pages hold closes only so are served (and written) only when use_cache is set
+  async fn fetch_bars(&self, use_cache: bool) -> Result<(Vec<CandleRow>, FetchReport), SmartError> {
+    let request_url: String = self.page_url_template()?;
+    let started_ms: i64 = get_clock().now_ms();
+
+    // Get calls required
+    let calls_required: Vec<CallItem> = self.calls_required().await?;
+
+    // Make API calls
+    let mut bars_full: Vec<CandleRow> = vec![];
+    let mut call_count:u8 = 0;
+    let mut failed_calls: u32 = 0;
+    let mut covered: Option<(i64, i64)> = None;
+    for call in calls_required {
+
+      // Serve pages already held in the candle cache (disk when a cache dir is set, localStorage on wasm)
+      if use_cache {
+        if let Some((labels, prices)) = read_cached_range(&self.exchange, &self.symbol, &self.interval, call.from_time, call.to_time).await {
+          bars_full.extend(labels.into_iter().zip(prices).map(|(label, price)| CandleRow::from_fields(label, None, None, None, price, None)));
+          covered = Some((covered.map(|c| c.0).unwrap_or(call.from_time), call.to_time));
+          continue;
+        }
+      }
+
+      // Limit pagination length
+      call_count += 1;
+      if call_count > 20 { break; }
+
+      // Make request and append response
+      // Failed pages are only tolerated when partial data is accepted
+      match self.fetch_page(&request_url, &call, call_count).await {
+        Ok(mut bars) => {
+          bars_full.append(&mut bars);
+          covered = Some((covered.map(|c| c.0).unwrap_or(call.from_time), call.to_time));
+        },
+        Err(e) => match self.tolerance {
+          DataTolerance::Strict => return Err(e),
+          DataTolerance::Partial(_) => failed_calls += 1
+        }
+      }
     };
     
     // Remove duplicates (if any)
-    self.remove_duplicates(&mut labels_full, &mut prices_full);
+    self.remove_duplicates(&mut bars_full);
+    let labels_full: Vec<u64> = bars_full.iter().map(|bar| bar.time).collect();
 
-    // Return labels and prices
-    let prices = HistoricalPrices {
-      labels: labels_full,
-      prices: prices_full
+    // Cache fetched closes for subsequent runs
+    if let (true, Some((from_time, to_time)), 0) = (use_cache, covered, failed_calls) {
+      let prices_full: Vec<f64> = bars_full.iter().map(|bar| bar.close).collect();
+      write_cached_range(&self.exchange, &self.symbol, &self.interval, from_time, to_time, &labels_full, &prices_full).await;
+    }
+
+    // Structure completeness report
+    let fetch_report: FetchReport = FetchReport {
+      requested_bars: self.requested_bars(),
+      received_bars: labels_full.len() as u32,
+      gaps: self.calendar.classify_label_gaps(&labels_full, &self.interval),
+      failed_calls
     };
-    Ok(prices)
+
+    // Guard: Ensure partial data meets minimum completeness
+    if let DataTolerance::Partial(min_completeness) = self.tolerance {
+      if labels_full.is_empty() || fetch_report.completeness() < min_completeness {
+        let e: String = format!("Insufficient data for {}: {:.1}% received", self.symbol, fetch_report.completeness() * 100.0);
+        return Err(SmartError::RuntimeCheck(e));
+      }
+    }
+
+    record_fetch(&self.exchange, get_clock().now_ms() - started_ms);
+    Ok((bars_full, fetch_report))
   }
 }
 
@@ -467,8 +930,14 @@ impl CandleBuilder {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::pricing::models::find_label_gaps;
   use std::collections::HashSet;
 
+  // Split bars into labels and closes
+  fn labels_prices(bars: Vec<CandleRow>) -> (Vec<u64>, Vec<f64>) {
+    bars.into_iter().map(|bar| (bar.time, bar.close)).unzip()
+  }
+
   // Test consistency of time intervals
   fn test_label_consistency(labels: &Vec<u64>) -> bool {
     let mut label_set: HashSet<u64> = HashSet::new();
@@ -503,10 +972,15 @@ mod tests {
 
   #[tokio::test]
   async fn tests_calculate_call_count() {
-    let price_builder: CandleBuilder = structure_candle_builder(Exchange::Dydx, "BTCUSDT", None);
-    let (iterations, final_n) = price_builder.calculate_call_count();
-    assert!(iterations > 0);
-    assert!(final_n > 0);
+
+    // Dydx v4 indexer returns up to 1000 candles per call (less the coverage buffer)
+    assert_eq!(CandleBuilder::get_max_limit(&Exchange::Dydx), 995);
+    let price_builder: CandleBuilder = CandleBuilder::new("BTC-USD".to_string(), IntervalPeriod::Hour(1, 2_500), Exchange::Dydx, None);
+    assert_eq!(price_builder.calculate_call_count(), (2, 510));
+
+    // A request within the limit needs only the final call
+    let price_builder: CandleBuilder = structure_candle_builder(Exchange::Dydx, "BTC-USD", None);
+    assert_eq!(price_builder.calculate_call_count(), (0, 200));
   }
 
   #[tokio::test]
@@ -516,6 +990,258 @@ mod tests {
     assert!(final_n > 0);
   }
 
+  #[tokio::test]
+  async fn tests_label_convention_shift() {
+    let interval: IntervalPeriod = IntervalPeriod::Hour(4, 10);
+    let prices: HistoricalPrices = HistoricalPrices { prices: vec![1.0, 2.0], labels: vec![0, 14400], label_convention: LabelConvention::OpenTime, fetch_report: None };
+    let closed: HistoricalPrices = prices.to_label_convention(LabelConvention::CloseTime, &interval);
+    assert_eq!(closed.labels, vec![14400, 28800]);
+    assert_eq!(closed.to_label_convention(LabelConvention::OpenTime, &interval).labels, prices.labels);
+  }
+
+  #[tokio::test]
+  async fn tests_fetch_report_gaps() {
+    let interval: IntervalPeriod = IntervalPeriod::Hour(1, 10);
+    let labels: Vec<u64> = vec![0, 3600, 14400, 18000];
+    let gaps = find_label_gaps(&labels, &interval);
+    assert_eq!(gaps.len(), 1);
+    assert_eq!(gaps[0].missing_bars, 2);
+
+    let report: FetchReport = FetchReport { requested_bars: 10, received_bars: 4, gaps, failed_calls: 1 };
+    assert_eq!(report.completeness(), 0.4);
+    assert_eq!(report.missing_gaps().len(), 1);
+    assert!(!report.is_complete());
+  }
+
+  #[tokio::test]
+  async fn it_plans_calls_around_market_closures() {
+    let forex: CandleBuilder = structure_candle_builder(Exchange::Twelve, "EUR/USD", Some(""));
+    assert_eq!(forex.calendar, MarketCalendar::Forex);
+
+    // 200 open hourly bars always span at least one 48 hour weekend closure
+    let calls: Vec<CallItem> = forex.calls_required().await.unwrap();
+    assert_eq!(calls.len(), 1);
+    assert!(calls[0].to_time - calls[0].from_time >= 248 * 3600);
+
+    let crypto: CandleBuilder = structure_candle_builder(Exchange::Twelve, "BTC/USD", Some(""));
+    let calls: Vec<CallItem> = crypto.calls_required().await.unwrap();
+    assert_eq!(calls[0].to_time - calls[0].from_time, 200 * 3600);
+    assert_eq!(crypto.with_calendar(MarketCalendar::UsEquity).calendar, MarketCalendar::UsEquity);
+  }
+
+  #[tokio::test]
+  async fn it_rejects_kraken_history_beyond_latest_bars() {
+    let kraken: CandleBuilder = CandleBuilder::new("XBTUSD".to_string(), IntervalPeriod::Hour(1, 1000), Exchange::Kraken, None);
+    assert!(matches!(kraken.calls_required().await, Err(SmartError::RuntimeCheck(_))));
+    assert!(matches!(kraken.fetch_prices_candles().await, Err(SmartError::RuntimeCheck(_))));
+  }
+
+  #[tokio::test]
+  async fn tests_deserialize_typed_candles() {
+    let bybit: CandleBuilder = structure_candle_builder(Exchange::ByBit, "BTCUSDT", None);
+    let data_obj: serde_json::Value = serde_json::json!({ "retCode": 0, "retMsg": "OK", "result": { "list": [
+      ["1700003600000", "2", "3", "1", "2.5", "10", "20"],
+      ["1700000000000", "1", "2", "0.5", "1.5", "10", "20"]
+    ]}});
+    let (labels, prices) = labels_prices(bybit.deserialize_candles(data_obj).unwrap());
+    assert_eq!(labels, vec![1_700_000_000, 1_700_003_600]);
+    assert_eq!(prices, vec![1.5, 2.5]);
+
+    // A malformed close is an error rather than a zero price
+    let binance: CandleBuilder = structure_candle_builder(Exchange::Binance, "BTCUSDT", None);
+    let bad_close: serde_json::Value = serde_json::json!([[1700000000000u64, "1", "2", "0.5", null, "10"]]);
+    assert!(matches!(binance.deserialize_candles(bad_close), Err(SmartError::SchemaMismatch(_))));
+  }
+
+  #[tokio::test]
+  async fn it_deserializes_alpaca_candles() {
+    let stock: CandleBuilder = structure_candle_builder(Exchange::Alpaca, "AAPL", Some("id:secret"));
+    assert_eq!(stock.calendar, MarketCalendar::UsEquity);
+    assert!(stock.get_request_url().contains("/v2/stocks/{symbol}/bars?timeframe={interval}"));
+    assert!(stock.get_request_url().ends_with("&APCA-API-KEY-ID=id&APCA-API-SECRET-KEY=secret"));
+    let data_obj: serde_json::Value = serde_json::json!({ "bars": [
+      { "t": "2023-07-03T13:00:00Z", "o": 193.8, "h": 194.4, "l": 193.6, "c": 194.1, "v": 1200, "n": 20, "vw": 194.0 },
+      { "t": "2023-07-03T14:00:00Z", "o": 194.1, "h": 194.5, "l": 193.9, "c": 194.3, "v": 900, "n": 15, "vw": 194.2 }
+    ], "symbol": "AAPL", "next_page_token": null });
+    let (labels, prices) = labels_prices(stock.deserialize_candles(data_obj).unwrap());
+    assert_eq!(labels, vec![1688389200, 1688392800]);
+    assert_eq!(prices, vec![194.1, 194.3]);
+
+    let crypto: CandleBuilder = structure_candle_builder(Exchange::Alpaca, "BTC/USD", Some("id:secret"));
+    assert_eq!(crypto.calendar, MarketCalendar::Continuous);
+    let data_obj: serde_json::Value = serde_json::json!({ "bars": { "BTC/USD": [
+      { "t": "2023-07-03T13:00:00Z", "o": 30600.0, "h": 30700.0, "l": 30550.0, "c": 30650.5, "v": 1.2, "n": 30, "vw": 30640.0 }
+    ]}, "next_page_token": null });
+    assert_eq!(labels_prices(crypto.deserialize_candles(data_obj).unwrap()).1, vec![30650.5]);
+    assert!(labels_prices(crypto.deserialize_candles(serde_json::json!({ "bars": null })).unwrap()).0.is_empty());
+  }
+
+  #[tokio::test]
+  async fn it_deserializes_alpha_vantage_candles() {
+    let stock: CandleBuilder = structure_candle_builder(Exchange::AlphaVantage, "IBM", Some("demo"));
+    assert!(stock.get_request_url().contains("function=TIME_SERIES_INTRADAY&symbol={symbol}&interval={interval}"));
+    let data_obj: serde_json::Value = serde_json::json!({
+      "Meta Data": { "2. Symbol": "IBM", "6. Time Zone": "US/Eastern" },
+      "Time Series (60min)": {
+        "2023-07-03 10:00:00": { "1. open": "134.1", "4. close": "134.2" },
+        "2023-07-03 09:00:00": { "1. open": "134.0", "4. close": "134.1" }
+      }
+    });
+    let (labels, prices) = labels_prices(stock.deserialize_candles(data_obj).unwrap());
+    assert_eq!(labels, vec![1688389200, 1688392800]); // 13:00 and 14:00 UTC during DST
+    assert_eq!(prices, vec![134.1, 134.2]);
+
+    let forex: CandleBuilder = structure_candle_builder_day(Exchange::AlphaVantage, "EUR/USD", Some("demo"));
+    assert!(forex.get_request_url().contains("function=FX_DAILY&from_symbol=EUR&to_symbol=USD"));
+    let data_obj: serde_json::Value = serde_json::json!({
+      "Meta Data": { "2. From Symbol": "EUR", "6. Time Zone": "UTC" },
+      "Time Series FX (Daily)": { "2023-07-03": { "1. open": "1.09", "4. close": "1.091" } }
+    });
+    let (labels, _) = labels_prices(forex.deserialize_candles(data_obj).unwrap());
+    assert_eq!(labels, vec![1688342400]);
+  }
+
+  #[tokio::test]
+  async fn it_deserializes_yahoo_candles() {
+    let index: CandleBuilder = structure_candle_builder_day(Exchange::Yahoo, "^GSPC", None);
+    assert!(index.get_request_url().contains("/chart/%5EGSPC?interval={interval}&period1={fromTime}&period2={toTime}"));
+    assert_eq!(index.structure_interval().unwrap(), "1d");
+
+    let data_obj: serde_json::Value = serde_json::json!({ "chart": { "result": [{
+      "meta": { "symbol": "^GSPC", "gmtoffset": -14400 },
+      "timestamp": [1688391000, 1688563800, 1688650200],
+      "indicators": { "quote": [{ "close": [4455.59, null, 4411.59] }] }
+    }], "error": null } });
+    let (labels, prices) = labels_prices(index.deserialize_candles(data_obj).unwrap());
+    assert_eq!(labels, vec![1688342400, 1688601600]); // 2023-07-03 and 2023-07-06 (null close skipped)
+    assert_eq!(prices, vec![4455.59, 4411.59]);
+
+    let weekly: CandleBuilder = CandleBuilder::new("SPY".to_string(), IntervalPeriod::Day(7, 100), Exchange::Yahoo, None);
+    assert_eq!(weekly.structure_interval().unwrap(), "1wk");
+  }
+
+  #[tokio::test]
+  async fn it_deserializes_tiingo_candles() {
+    let stock: CandleBuilder = structure_candle_builder(Exchange::Tiingo, "AAPL", Some("abc"));
+    assert_eq!(stock.calendar, MarketCalendar::UsEquity);
+    assert!(stock.get_request_url().starts_with("https://api.tiingo.com/iex/{symbol}/prices?startDate={fromTime}"));
+    assert!(stock.get_request_url().ends_with("&token=abc"));
+    assert_eq!(stock.structure_interval().unwrap(), "1hour");
+    assert_eq!(stock.format_call_times(1688391000, false), "2023-07-03");
+    let data_obj: serde_json::Value = serde_json::json!([
+      { "date": "2023-07-03T13:00:00.000Z", "open": 193.8, "high": 194.4, "low": 193.6, "close": 194.1, "volume": 1200 },
+      { "date": "2023-07-03T14:00:00.000Z", "open": 194.1, "high": 194.5, "low": 193.9, "close": 194.3, "volume": 900 }
+    ]);
+    let (labels, prices) = labels_prices(stock.deserialize_candles(data_obj).unwrap());
+    assert_eq!(labels, vec![1688389200, 1688392800]);
+    assert_eq!(prices, vec![194.1, 194.3]);
+
+    let daily: CandleBuilder = structure_candle_builder_day(Exchange::Tiingo, "SPY", Some("abc"));
+    assert!(daily.get_request_url().contains("/tiingo/daily/{symbol}/prices?"));
+    assert_eq!(daily.structure_interval().unwrap(), "daily");
+
+    let crypto: CandleBuilder = structure_candle_builder_day(Exchange::Tiingo, "btcusd", Some("abc"));
+    assert_eq!(crypto.calendar, MarketCalendar::Continuous);
+    assert_eq!(crypto.structure_interval().unwrap(), "1day");
+    let data_obj: serde_json::Value = serde_json::json!([{ "ticker": "btcusd", "baseCurrency": "btc", "quoteCurrency": "usd", "priceData": [
+      { "date": "2023-07-03T00:00:00+00:00", "open": 30600.0, "high": 31300.0, "low": 30550.0, "close": 31150.5, "volume": 1.2 }
+    ]}]);
+    assert_eq!(labels_prices(crypto.deserialize_candles(data_obj).unwrap()), (vec![1688342400], vec![31150.5]));
+
+    let not_found: serde_json::Value = serde_json::json!({ "detail": "Error: Ticker 'XXXX' not found" });
+    assert!(matches!(stock.deserialize_candles(not_found), Err(SmartError::APIResponseStatus(_))));
+  }
+
+  #[tokio::test]
+  async fn tests_fetch_prices_gateio() {
+    let price_builder: CandleBuilder = structure_candle_builder(Exchange::GateIo, "BTC_USDT", None);
+    let hist_prices: HistoricalPrices = price_builder.fetch_prices_candles().await.unwrap();
+    assert!(!hist_prices.labels.is_empty() && !hist_prices.prices.is_empty());
+    let consistency: bool = test_label_consistency(&hist_prices.labels);
+    assert!(consistency);
+  }
+
+  #[tokio::test]
+  async fn tests_fetch_prices_hyperliquid() {
+    let price_builder: CandleBuilder = structure_candle_builder(Exchange::Hyperliquid, "BTC", None);
+    let hist_prices: HistoricalPrices = price_builder.fetch_prices_candles().await.unwrap();
+    assert!(!hist_prices.labels.is_empty() && !hist_prices.prices.is_empty());
+    let consistency: bool = test_label_consistency(&hist_prices.labels);
+    assert!(consistency);
+  }
+
+  #[tokio::test]
+  async fn tests_fetch_prices_kraken() {
+    let price_builder: CandleBuilder = structure_candle_builder(Exchange::Kraken, "XBTUSD", None);
+    let hist_prices: HistoricalPrices = price_builder.fetch_prices_candles().await.unwrap();
+    assert!(!hist_prices.labels.is_empty() && !hist_prices.prices.is_empty());
+    let consistency: bool = test_label_consistency(&hist_prices.labels);
+    assert!(consistency);
+  }
+
+  #[tokio::test]
+  async fn tests_fetch_prices_bitfinex() {
+    let price_builder: CandleBuilder = structure_candle_builder(Exchange::Bitfinex, "tBTCUSD", None);
+    let hist_prices: HistoricalPrices = price_builder.fetch_prices_candles().await.unwrap();
+    assert!(!hist_prices.labels.is_empty() && !hist_prices.prices.is_empty());
+    let consistency: bool = test_label_consistency(&hist_prices.labels);
+    assert!(consistency);
+  }
+
+  #[tokio::test]
+  async fn tests_fetch_prices_kucoin() {
+    let price_builder: CandleBuilder = structure_candle_builder(Exchange::KuCoin, "BTC-USDT", None);
+    let hist_prices: HistoricalPrices = price_builder.fetch_prices_candles().await.unwrap();
+    assert!(!hist_prices.labels.is_empty() && !hist_prices.prices.is_empty());
+    let consistency: bool = test_label_consistency(&hist_prices.labels);
+    assert!(consistency);
+  }
+
+  #[tokio::test]
+  async fn tests_fetch_prices_okx() {
+    let price_builder: CandleBuilder = structure_candle_builder(Exchange::Okx, "BTC-USDT-SWAP", None);
+    let hist_prices: HistoricalPrices = price_builder.fetch_prices_candles().await.unwrap();
+    assert!(!hist_prices.labels.is_empty() && !hist_prices.prices.is_empty());
+    let consistency: bool = test_label_consistency(&hist_prices.labels);
+    assert!(consistency);
+  }
+
+  #[tokio::test]
+  async fn it_deserializes_ohlcv_bars() {
+    let coinbase: CandleBuilder = structure_candle_builder(Exchange::Coinbase, "BTC-USD", None);
+    let data_obj: serde_json::Value = serde_json::json!([
+      [1700003600u64, 0.75, 2.5, 1.25, 2.0, 12.0],
+      [1700000000u64, 0.5, 2.0, 1.0, 1.25, 10.0]
+    ]);
+    let bars: Vec<CandleRow> = coinbase.deserialize_candles(data_obj).unwrap();
+    assert_eq!(bars[0], CandleRow { time: 1700000000, open: 1.0, high: 2.0, low: 0.5, close: 1.25, volume: 10.0 });
+    assert_eq!(bars[1].volume, 12.0);
+
+    // Forex carries no volume so it defaults to zero
+    let forex: CandleBuilder = structure_candle_builder(Exchange::Twelve, "EUR/USD", Some(""));
+    let data_obj: serde_json::Value = serde_json::json!({ "values": [
+      { "datetime": "2023-07-03 01:00:00", "open": "1.0905", "high": "1.0912", "low": "1.0901", "close": "1.0910" }
+    ], "status": "ok" });
+    let bars: Vec<CandleRow> = forex.deserialize_candles(data_obj).unwrap();
+    assert_eq!((bars[0].open, bars[0].high, bars[0].low, bars[0].volume), (1.0905, 1.0912, 1.0901, 0.0));
+
+    // Duplicate labels across pages are dropped
+    let mut paged: Vec<CandleRow> = vec![bars[0], bars[0]];
+    forex.remove_duplicates(&mut paged);
+    assert_eq!(paged.len(), 1);
+  }
+
+  #[tokio::test]
+  async fn tests_fetch_candles_ohlcv_binance() {
+    let price_builder: CandleBuilder = structure_candle_builder(Exchange::Binance, "BTCUSDT", None);
+    let ohlcv: OhlcvSeries = price_builder.fetch_candles_ohlcv().await.unwrap();
+    ohlcv.ensure_aligned().unwrap();
+    assert!(!ohlcv.labels.is_empty());
+    assert!(ohlcv.high.iter().zip(ohlcv.low.iter()).all(|(h, l)| h >= l));
+    assert!(ohlcv.volume.iter().any(|v| *v > 0.0));
+    assert!(test_label_consistency(&ohlcv.labels));
+  }
+
   #[tokio::test]
   async fn tests_calls_required() {
     let price_builder: CandleBuilder = structure_candle_builder_day(Exchange::Twelve, "AAPL", Some(""));
@@ -523,6 +1249,16 @@ mod tests {
     assert!(calls_required.len() > 0);
   }
 
+  #[tokio::test]
+  async fn tests_fetch_prices_stream_binance() {
+    use futures::StreamExt;
+    let price_builder: CandleBuilder = structure_candle_builder(Exchange::Binance, "BTCUSDT", None);
+    let pages: Vec<Result<HistoricalPrices, SmartError>> = price_builder.fetch_prices_candles_stream().await.unwrap().collect().await;
+    assert!(!pages.is_empty());
+    let bars: usize = pages.into_iter().map(|p| p.unwrap().labels.len()).sum();
+    assert!(bars >= 200);
+  }
+
   #[tokio::test]
   async fn tests_fetch_prices_binance() {
     let price_builder: CandleBuilder = structure_candle_builder(Exchange::Binance, "BTCUSDT", None);
@@ -532,6 +1268,15 @@ mod tests {
     assert!(consistency);
   }
 
+  #[tokio::test]
+  async fn tests_fetch_prices_binance_spot() {
+    let price_builder: CandleBuilder = structure_candle_builder(Exchange::BinanceSpot, "BTCUSDT", None);
+    let hist_prices: HistoricalPrices = price_builder.fetch_prices_candles().await.unwrap();
+    assert!(!hist_prices.labels.is_empty() && !hist_prices.prices.is_empty());
+    let consistency: bool = test_label_consistency(&hist_prices.labels);
+    assert!(consistency);
+  }
+
   #[tokio::test]
   async fn tests_fetch_prices_binance_us() {
     let price_builder: CandleBuilder = structure_candle_builder(Exchange::BinanceUs, "BTCUSDT", None);
@@ -541,6 +1286,24 @@ mod tests {
     assert!(consistency);
   }
 
+  #[tokio::test]
+  async fn tests_fetch_prices_binance_coinm() {
+    let price_builder: CandleBuilder = structure_candle_builder(Exchange::BinanceCoinM, "BTCUSD_PERP", None);
+    let hist_prices: HistoricalPrices = price_builder.fetch_prices_candles().await.unwrap();
+    assert!(!hist_prices.labels.is_empty() && !hist_prices.prices.is_empty());
+    let consistency: bool = test_label_consistency(&hist_prices.labels);
+    assert!(consistency);
+  }
+
+  #[tokio::test]
+  async fn tests_fetch_prices_bybit_inverse() {
+    let price_builder: CandleBuilder = structure_candle_builder(Exchange::ByBitInverse, "BTCUSD", None);
+    let hist_prices: HistoricalPrices = price_builder.fetch_prices_candles().await.unwrap();
+    assert!(!hist_prices.labels.is_empty() && !hist_prices.prices.is_empty());
+    let consistency: bool = test_label_consistency(&hist_prices.labels);
+    assert!(consistency);
+  }
+
   #[tokio::test]
   async fn tests_fetch_prices_bybit() {
     let price_builder: CandleBuilder = structure_candle_builder(Exchange::ByBit, "BTCUSDT", None);
@@ -583,4 +1346,59 @@ mod tests {
     let hist_prices: HistoricalPrices = price_builder.fetch_prices_candles().await.unwrap();
     assert!(hist_prices.labels.len() > 0 && hist_prices.prices.len() > 0);
   }
-}
\ No newline at end of file
+
+  #[tokio::test]
+  async fn tests_fetch_prices_yahoo() {
+    let price_builder: CandleBuilder = structure_candle_builder_day(Exchange::Yahoo, "SPY", None);
+    let hist_prices: HistoricalPrices = price_builder.fetch_prices_candles().await.unwrap();
+    assert!(hist_prices.labels.len() > 0 && hist_prices.prices.len() > 0);
+  }
+
+  #[tokio::test]
+  async fn tests_fetch_prices_alpha_vantage() {
+    use dotenv::dotenv;
+    use std::env;
+    dotenv().ok();
+
+    let api_key: String = match env::var("ALPHA_VANTAGE_API_KEY") {
+      Ok(val) => val,
+      Err(_e) => panic!("Failed to read ALPHA_VANTAGE_API_KEY"),
+    };
+
+    let price_builder: CandleBuilder = structure_candle_builder_day(Exchange::AlphaVantage, "EUR/USD", Some(&api_key));
+    let hist_prices: HistoricalPrices = price_builder.fetch_prices_candles().await.unwrap();
+    assert!(hist_prices.labels.len() > 0 && hist_prices.prices.len() > 0);
+  }
+
+  #[tokio::test]
+  async fn tests_fetch_prices_alpaca() {
+    use dotenv::dotenv;
+    use std::env;
+    dotenv().ok();
+
+    let api_key: String = match env::var("ALPACA_API_KEY") {
+      Ok(val) => val,
+      Err(_e) => panic!("Failed to read ALPACA_API_KEY"),
+    };
+
+    let price_builder: CandleBuilder = structure_candle_builder_day(Exchange::Alpaca, "SPY", Some(&api_key));
+    let hist_prices: HistoricalPrices = price_builder.fetch_prices_candles().await.unwrap();
+    assert!(hist_prices.labels.len() > 0 && hist_prices.prices.len() > 0);
+  }
+
+  #[tokio::test]
+  async fn tests_fetch_prices_tiingo() {
+    use dotenv::dotenv;
+    use std::env;
+    dotenv().ok();
+
+    let api_key: String = match env::var("TIINGO_API_KEY") {
+      Ok(val) => val,
+      Err(_e) => panic!("Failed to read TIINGO_API_KEY"),
+    };
+
+    let price_builder: CandleBuilder = structure_candle_builder(Exchange::Tiingo, "SPY", Some(&api_key));
+    let hist_prices: HistoricalPrices = price_builder.fetch_prices_candles().await.unwrap();
+    assert!(hist_prices.labels.len() > 0 && hist_prices.prices.len() > 0);
+  }
+}
@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use super::models::Exchange;
+use super::schemas::{decode, BinanceDepth, BinanceTicker24h, ByBitBook, ByBitResponse, ByBitTicker24h, Num};
+use super::utils::api_request;
+
+/*
+  Notional Capacity
+  Estimates the largest notional a leg can trade without moving price more than a set number of basis points
+  Order book depth within the impact band is capped by a share of 24h volume, so a thin book refreshed rarely is not overstated
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, TS)]
+#[ts(export)]
+pub struct CapacityConfig {
+  pub max_impact_bps: f64, // furthest a fill may be from mid
+  pub max_volume_share: f64 // largest share of 24h quote volume one order may take (0.01 = 1%)
+}
+
+impl Default for CapacityConfig {
+  fn default() -> Self {
+    Self { max_impact_bps: 10.0, max_volume_share: 0.01 }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, TS)]
+#[ts(export)]
+pub struct BookLevel {
+  pub price: f64,
+  pub qty: f64
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct OrderBook {
+  pub bids: Vec<BookLevel>, // best (highest) first
+  pub asks: Vec<BookLevel> // best (lowest) first
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct LegCapacity {
+  pub symbol: String,
+  pub volume_24h: f64, // quote notional
+  pub depth_notional: f64, // smaller of the bid and ask notional within the impact band
+  pub capacity: f64 // depth notional capped by the volume share
+}
+
+/// Levels
+/// Order book levels from [price, qty] pairs, dropping empty or invalid levels
+fn levels(raw: Vec<(Num, Num)>) -> Vec<BookLevel> {
+  raw.into_iter()
+    .map(|(price, qty)| BookLevel { price: price.0, qty: qty.0 })
+    .filter(|l| l.price.is_finite() && l.qty.is_finite() && l.price > 0.0 && l.qty > 0.0)
+    .collect()
+}
+
+impl OrderBook {
+
+  /// Mid
+  /// Midpoint of the best bid and ask
+  pub fn mid(&self) -> Option<f64> {
+    let (bid, ask) = (self.bids.first()?, self.asks.first()?);
+    Some((bid.price + ask.price) / 2.0)
+  }
+
+  /// Notional Within Bps
+  /// Smaller of the bid and ask notional resting within the band around mid (both sides are crossed over a trade's life)
+  pub fn notional_within_bps(&self, bps: f64) -> f64 {
+    let Some(mid) = self.mid() else { return 0.0 };
+    let band: f64 = mid * bps / 10_000.0;
+    let bid_notional: f64 = self.bids.iter()
+      .filter(|l| l.price >= mid - band)
+      .map(|l| l.price * l.qty)
+      .sum();
+    let ask_notional: f64 = self.asks.iter()
+      .filter(|l| l.price <= mid + band)
+      .map(|l| l.price * l.qty)
+      .sum();
+    bid_notional.min(ask_notional)
+  }
+}
+
+impl LegCapacity {
+
+  /// Estimate
+  /// Capacity of a leg from its order book and 24h quote volume
+  pub fn estimate(symbol: &str, book: &OrderBook, volume_24h: f64, config: &CapacityConfig) -> Self {
+    let depth_notional: f64 = book.notional_within_bps(config.max_impact_bps);
+    let volume_cap: f64 = if volume_24h.is_finite() { volume_24h.max(0.0) * config.max_volume_share } else { 0.0 };
+    Self { symbol: symbol.to_string(), volume_24h, depth_notional, capacity: depth_notional.min(volume_cap) }
+  }
+}
+
+/// Pair Capacity
+/// Notional per leg a pair can trade, limited by its thinner leg
+pub fn pair_capacity(leg_0: &LegCapacity, leg_1: &LegCapacity) -> f64 {
+  leg_0.capacity.min(leg_1.capacity)
+}
+
+/// Get Book Urls
+/// Order book and 24h ticker urls for exchanges with depth available
+fn get_book_urls(exchange: &Exchange, symbol: &str) -> Result<(String, String), SmartError> {
+  let (book_url, ticker_url) = match exchange {
+    Exchange::Binance => (
+      "https://fapi.binance.com/fapi/v1/depth?symbol={symbol}&limit=100",
+      "https://fapi.binance.com/fapi/v1/ticker/24hr?symbol={symbol}"
+    ),
+    Exchange::BinanceSpot => (
+      "https://api.binance.com/api/v3/depth?symbol={symbol}&limit=100",
+      "https://api.binance.com/api/v3/ticker/24hr?symbol={symbol}"
+    ),
+    Exchange::BinanceUs => (
+      "https://api.binance.us/api/v3/depth?symbol={symbol}&limit=100",
+      "https://api.binance.us/api/v3/ticker/24hr?symbol={symbol}"
+    ),
+    Exchange::ByBit => (
+      "https://api.bybit.com/v5/market/orderbook?category=linear&symbol={symbol}&limit=200",
+      "https://api.bybit.com/v5/market/tickers?category=linear&symbol={symbol}"
+    ),
+    _ => return Err(SmartError::RuntimeCheck(format!("Order book depth not available for {:?}", exchange)))
+  };
+  Ok((book_url.replace("{symbol}", symbol), ticker_url.replace("{symbol}", symbol)))
+}
+
+/// Parse Order Book
+pub fn parse_order_book(exchange: &Exchange, data_obj: serde_json::Value) -> Result<OrderBook, SmartError> {
+  match exchange {
+    Exchange::ByBit => {
+      let book: ByBitBook = decode::<ByBitResponse>(exchange, data_obj)?.into_result()?;
+      Ok(OrderBook { bids: levels(book.b), asks: levels(book.a) })
+    },
+    _ => {
+      let book: BinanceDepth = decode(exchange, data_obj)?;
+      Ok(OrderBook { bids: levels(book.bids), asks: levels(book.asks) })
+    }
+  }
+}
+
+/// Parse Volume 24h
+/// Quote notional traded over the last 24 hours
+pub fn parse_volume_24h(exchange: &Exchange, data_obj: serde_json::Value) -> Result<f64, SmartError> {
+  match exchange {
+    Exchange::ByBit => {
+      let tickers: Vec<ByBitTicker24h> = decode::<ByBitResponse>(exchange, data_obj)?.into_list()?;
+      tickers.first().map(|t| t.turnover24h.0)
+        .ok_or_else(|| SmartError::RuntimeCheck("ByBit ticker not found".to_string()))
+    },
+    _ => Ok(decode::<BinanceTicker24h>(exchange, data_obj)?.quote_volume.0)
+  }
+}
+
+/// Request Json
+async fn request_json(url: &str) -> Result<serde_json::Value, SmartError> {
+  let res_data: reqwest::Response = api_request(url).await?;
+
+  // Guard: Ensure status code
+  if res_data.status() != 200 {
+    let e: String = format!("Failed to extract data: {:?}", res_data.text().await);
+    return Err(SmartError::APIResponseStatus(e));
+  }
+  Ok(res_data.json().await?)
+}
+
+/// Request Leg Capacity
+/// Fetches the order book and 24h ticker of a symbol and estimates its capacity
+pub async fn request_leg_capacity(exchange: &Exchange, symbol: &str, config: &CapacityConfig) -> Result<LegCapacity, SmartError> {
+  let (book_url, ticker_url) = get_book_urls(exchange, symbol)?;
+  let (book_obj, ticker_obj) = futures::join!(request_json(&book_url), request_json(&ticker_url));
+  let book: OrderBook = parse_order_book(exchange, book_obj?)?;
+  let volume_24h: f64 = parse_volume_24h(exchange, ticker_obj?)?;
+  Ok(LegCapacity::estimate(symbol, &book, volume_24h, config))
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_estimates_leg_capacity() {
+    let data_obj: serde_json::Value = serde_json::json!({
+      "lastUpdateId": 1,
+      "bids": [["99.95", "10"], ["99.9", "20"], ["99.0", "1000"]],
+      "asks": [["100.05", "5"], ["100.1", "10"], ["101.0", "1000"]]
+    });
+    let book: OrderBook = parse_order_book(&Exchange::Binance, data_obj).unwrap();
+    assert_eq!(book.mid(), Some(100.0));
+
+    // 10 bps band is 99.9 to 100.1, the ask side is thinner
+    let depth: f64 = book.notional_within_bps(10.0);
+    assert!((depth - (100.05 * 5.0 + 100.1 * 10.0)).abs() < 1e-9);
+
+    // Capped by 1% of 24h volume when the book is deeper
+    let config: CapacityConfig = CapacityConfig::default();
+    let thin_volume: LegCapacity = LegCapacity::estimate("ETHUSDT", &book, 50_000.0, &config);
+    assert_eq!(thin_volume.capacity, 500.0);
+    let deep_volume: LegCapacity = LegCapacity::estimate("SOLUSDT", &book, 1e9, &config);
+    assert_eq!(deep_volume.capacity, depth);
+    assert_eq!(pair_capacity(&thin_volume, &deep_volume), 500.0);
+  }
+
+  #[tokio::test]
+  async fn it_parses_bybit_book_and_turnover() {
+    let book_obj: serde_json::Value = serde_json::json!({ "retCode": 0, "retMsg": "OK", "result": {
+      "s": "BTCUSDT", "b": [["50000", "1"]], "a": [["50010", "2"], ["50020", "0"]]
+    }});
+    let book: OrderBook = parse_order_book(&Exchange::ByBit, book_obj).unwrap();
+    assert_eq!((book.bids.len(), book.asks.len()), (1, 1));
+
+    let ticker_obj: serde_json::Value = serde_json::json!({ "retCode": 0, "retMsg": "OK", "result": {
+      "list": [{ "symbol": "BTCUSDT", "turnover24h": "123456.5" }]
+    }});
+    assert_eq!(parse_volume_24h(&Exchange::ByBit, ticker_obj).unwrap(), 123456.5);
+    assert!(request_leg_capacity(&Exchange::Coinbase, "BTC-USD", &CapacityConfig::default()).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn tests_request_leg_capacity_binance() {
+    let capacity: LegCapacity = request_leg_capacity(&Exchange::Binance, "ETHUSDT", &CapacityConfig::default()).await.unwrap();
+    assert!(capacity.capacity > 0.0);
+  }
+}
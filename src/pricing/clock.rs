@@ -0,0 +1,143 @@
+use chrono::Utc;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/*
+  Clock
+  Source of current time and sleeps for pricing code (rate limits, breakers, pacing between calls)
+  The system clock is used by default; a simulated clock lets tests and replays control time
+*/
+
+pub type SleepFuture<'a> = Pin<Box<dyn Future<Output = ()> + 'a>>;
+
+pub trait Clock: Send + Sync {
+
+  /// Now Ms
+  /// Current unix time in milliseconds
+  fn now_ms(&self) -> i64;
+
+  /// Sleep
+  /// Waits for the given milliseconds
+  fn sleep(&self, millis: u64) -> SleepFuture<'_>;
+}
+
+static CLOCK: RwLock<Option<Arc<dyn Clock>>> = RwLock::new(None);
+
+/// System Clock
+/// Wall clock time with a real async sleep (a timer on native targets, a browser timeout in wasm)
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+  fn now_ms(&self) -> i64 {
+    Utc::now().timestamp_millis()
+  }
+
+  #[cfg(not(target_arch = "wasm32"))]
+  fn sleep(&self, millis: u64) -> SleepFuture<'_> {
+    Box::pin(async_std::task::sleep(std::time::Duration::from_millis(millis)))
+  }
+
+  /// Yields to the browser event loop through a timeout (setTimeout) rather than blocking the main thread
+  #[cfg(target_arch = "wasm32")]
+  fn sleep(&self, millis: u64) -> SleepFuture<'_> {
+    Box::pin(gloo_timers::future::TimeoutFuture::new(millis.min(u32::MAX as u64) as u32))
+  }
+}
+
+/// Simulated Clock
+/// Time only moves when advanced or slept on, so sleeps return immediately
+#[derive(Debug, Default)]
+pub struct SimulatedClock {
+  now_ms: AtomicI64
+}
+
+impl SimulatedClock {
+  pub fn new(start_ms: i64) -> Self {
+    Self { now_ms: AtomicI64::new(start_ms) }
+  }
+
+  /// Advance
+  /// Moves simulated time forward
+  pub fn advance(&self, millis: u64) {
+    self.now_ms.fetch_add(millis as i64, Ordering::SeqCst);
+  }
+
+  /// Set
+  /// Jumps simulated time to a point (e.g. the start of a replayed run)
+  pub fn set(&self, now_ms: i64) {
+    self.now_ms.store(now_ms, Ordering::SeqCst);
+  }
+}
+
+impl Clock for SimulatedClock {
+  fn now_ms(&self) -> i64 {
+    self.now_ms.load(Ordering::SeqCst)
+  }
+
+  fn sleep(&self, millis: u64) -> SleepFuture<'_> {
+    self.advance(millis);
+    Box::pin(async {})
+  }
+}
+
+/// Set Clock
+/// Replaces the clock used by pricing code
+pub fn set_clock(clock: Arc<dyn Clock>) {
+  if let Ok(mut guard) = CLOCK.write() {
+    *guard = Some(clock);
+  }
+}
+
+/// Reset Clock
+/// Restores the system clock
+pub fn reset_clock() {
+  if let Ok(mut guard) = CLOCK.write() {
+    *guard = None;
+  }
+}
+
+/// Get Clock
+pub fn get_clock() -> Arc<dyn Clock> {
+  CLOCK.read().ok()
+    .and_then(|guard| guard.clone())
+    .unwrap_or_else(|| Arc::new(SystemClock))
+}
+
+/// Now Ms
+/// Current time in milliseconds from the configured clock
+pub fn now_ms() -> i64 {
+  get_clock().now_ms()
+}
+
+/// Now Secs
+/// Current time in seconds from the configured clock
+pub fn now_secs() -> i64 {
+  now_ms().div_euclid(1000)
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_simulates_time() {
+    let clock: SimulatedClock = SimulatedClock::new(1_700_000_000_000);
+    clock.sleep(2_500).await;
+    assert_eq!(clock.now_ms(), 1_700_000_002_500);
+
+    clock.advance(500);
+    assert_eq!(clock.now_ms(), 1_700_000_003_000);
+
+    clock.set(0);
+    assert_eq!(clock.now_ms(), 0);
+
+    let system: SystemClock = SystemClock;
+    let before: i64 = system.now_ms();
+    system.sleep(5).await;
+    assert!(system.now_ms() >= before + 5);
+  }
+}
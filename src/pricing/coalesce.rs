@@ -0,0 +1,111 @@
+use futures::channel::oneshot;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::RwLock;
+
+use crate::SmartError;
+use super::models::HistoricalPrices;
+
+/*
+  Request Coalescing
+  Concurrent fetches for the same symbol / interval share one in-flight pagination sequence
+  e.g. BTCUSDT appearing in 30 screened pairs is only fetched once
+*/
+
+type SharedResult = Result<HistoricalPrices, String>;
+type Waiters = HashMap<String, Vec<oneshot::Sender<SharedResult>>>;
+
+static IN_FLIGHT: RwLock<Option<Waiters>> = RwLock::new(None);
+
+/// In Flight Guard
+/// Releases the key if the leading request is dropped, so waiters fall back to fetching themselves
+struct InFlightGuard {
+  key: String
+}
+
+impl InFlightGuard {
+  fn take_waiters(&self) -> Vec<oneshot::Sender<SharedResult>> {
+    IN_FLIGHT.write().ok()
+      .and_then(|mut store| store.as_mut().and_then(|m| m.remove(&self.key)))
+      .unwrap_or_default()
+  }
+}
+
+impl Drop for InFlightGuard {
+  fn drop(&mut self) {
+    self.take_waiters();
+  }
+}
+
+/// Join In Flight
+/// Registers as a waiter if a request for the key is in flight, otherwise marks the key as in flight
+fn join_in_flight(key: &str) -> Option<oneshot::Receiver<SharedResult>> {
+  let Ok(mut store) = IN_FLIGHT.write() else { return None };
+  let map: &mut Waiters = store.get_or_insert_with(HashMap::new);
+  match map.get_mut(key) {
+    Some(waiters) => {
+      let (sender, receiver) = oneshot::channel();
+      waiters.push(sender);
+      Some(receiver)
+    },
+    None => {
+      map.insert(key.to_string(), vec![]);
+      None
+    }
+  }
+}
+
+/// Coalesce
+/// Runs the fetch unless an identical one is already in flight, in which case its result is shared
+/// Errors shared with waiters are reported as runtime errors carrying the original message
+pub async fn coalesce<F, Fut>(key: String, fetch: F) -> Result<HistoricalPrices, SmartError>
+where
+  F: FnOnce() -> Fut,
+  Fut: Future<Output = Result<HistoricalPrices, SmartError>>
+{
+  if let Some(receiver) = join_in_flight(&key) {
+    return match receiver.await {
+      Ok(shared) => shared.map_err(SmartError::RuntimeCheck),
+      Err(_) => fetch().await // leading request dropped
+    };
+  }
+
+  let guard: InFlightGuard = InFlightGuard { key };
+  let result: Result<HistoricalPrices, SmartError> = fetch().await;
+  for waiter in guard.take_waiters() {
+    let _ = waiter.send(result.as_ref().map(|p| p.clone()).map_err(|e| e.to_string()));
+  }
+  result
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  static FETCHES: AtomicUsize = AtomicUsize::new(0);
+
+  async fn counted_fetch() -> Result<HistoricalPrices, SmartError> {
+    FETCHES.fetch_add(1, Ordering::SeqCst);
+    tokio::task::yield_now().await;
+    Ok(HistoricalPrices { prices: vec![1.0], labels: vec![1], label_convention: Default::default(), fetch_report: None })
+  }
+
+  #[tokio::test]
+  async fn it_coalesces_concurrent_fetches() {
+    let key: String = "test:coalesce:BTCUSDT".to_string();
+    let (a, b, c) = futures::join!(
+      coalesce(key.clone(), counted_fetch),
+      coalesce(key.clone(), counted_fetch),
+      coalesce(key.clone(), counted_fetch)
+    );
+    assert_eq!(FETCHES.load(Ordering::SeqCst), 1);
+    assert_eq!(a.unwrap().prices, b.unwrap().prices);
+    assert!(c.is_ok());
+
+    // Key released once complete
+    coalesce(key, counted_fetch).await.unwrap();
+    assert_eq!(FETCHES.load(Ordering::SeqCst), 2);
+  }
+}
@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use ts_rs::TS;
+
+use crate::SmartError;
+use super::models::Exchange;
+use super::quotes::request_quote;
+use super::translate::translate_symbol;
+
+/*
+  Composite Quote
+  Consolidates quotes for the same asset across venues into a median and weighted price
+  Per venue deviations (bps) highlight venue specific dislocations
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct VenueQuote {
+  pub exchange: Exchange,
+  pub symbol: String,
+  pub price: f64,
+  pub deviation_bps: f64 // from the composite median
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct CompositeQuote {
+  pub median: f64,
+  pub mean: f64,
+  pub weighted: Option<f64>, // weighted by supplied venue volumes (VWAP style)
+  pub venues: Vec<VenueQuote>,
+  pub failed: Vec<(Exchange, String)>
+}
+
+impl CompositeQuote {
+
+  /// Dislocated
+  /// Venues deviating from the median by at least the threshold
+  pub fn dislocated(&self, threshold_bps: f64) -> Vec<&VenueQuote> {
+    self.venues.iter().filter(|v| v.deviation_bps.abs() >= threshold_bps).collect()
+  }
+}
+
+/// Median
+fn median(values: &[f64]) -> f64 {
+  let mut sorted: Vec<f64> = values.to_vec();
+  sorted.sort_by(|a, b| a.total_cmp(b));
+  let mid: usize = sorted.len() / 2;
+  if sorted.len().is_multiple_of(2) { (sorted[mid - 1] + sorted[mid]) / 2.0 } else { sorted[mid] }
+}
+
+/// Build Composite
+/// Consolidates venue prices (zero or negative prices are treated as failed quotes)
+/// Weighted price is only given when every venue has a positive weight
+pub fn build_composite(
+  quotes: Vec<(Exchange, String, f64)>,
+  weights: Option<&HashMap<Exchange, f64>>,
+  mut failed: Vec<(Exchange, String)>
+) -> Result<CompositeQuote, SmartError> {
+  let mut valid: Vec<(Exchange, String, f64)> = vec![];
+  for (exchange, symbol, price) in quotes {
+    if price > 0.0 { valid.push((exchange, symbol, price)) } else { failed.push((exchange, "No price returned".to_string())) }
+  }
+
+  // Guard: Ensure at least one venue quoted
+  if valid.is_empty() {
+    return Err(SmartError::RuntimeCheck("No venue returned a quote for composite".to_string()));
+  }
+
+  let prices: Vec<f64> = valid.iter().map(|q| q.2).collect();
+  let median: f64 = median(&prices);
+  let mean: f64 = prices.iter().sum::<f64>() / prices.len() as f64;
+
+  let weighted: Option<f64> = weights.and_then(|w| {
+    let venue_weights: Vec<f64> = valid.iter().map(|q| w.get(&q.0).copied().unwrap_or(0.0)).collect();
+    if venue_weights.iter().any(|x| *x <= 0.0) { return None }
+    let total: f64 = venue_weights.iter().sum();
+    Some(prices.iter().zip(venue_weights.iter()).map(|(p, x)| p * x).sum::<f64>() / total)
+  });
+
+  let venues: Vec<VenueQuote> = valid.into_iter()
+    .map(|(exchange, symbol, price)| VenueQuote { exchange, symbol, price, deviation_bps: (price / median - 1.0) * 10_000.0 })
+    .collect();
+
+  Ok(CompositeQuote { median, mean, weighted, venues, failed })
+}
+
+/// Request Composite Quote
+/// Quotes the asset on each exchange (symbol translated per venue) and consolidates the results
+/// Weights are optional venue volumes for the weighted price
+pub async fn request_composite_quote(
+  symbol: &str,
+  exchanges: &[Exchange],
+  weights: Option<&HashMap<Exchange, f64>>,
+  twelve_api_key: Option<&str>
+) -> Result<CompositeQuote, SmartError> {
+  let mut failed: Vec<(Exchange, String)> = vec![];
+  let mut venues: Vec<(Exchange, String)> = vec![];
+  for exchange in exchanges {
+    match translate_symbol(symbol, exchange) {
+      Ok(venue_symbol) => venues.push((exchange.clone(), venue_symbol)),
+      Err(e) => failed.push((exchange.clone(), e.to_string()))
+    }
+  }
+
+  let requests = venues.iter().map(|(exchange, venue_symbol)| request_quote(exchange, venue_symbol, twelve_api_key));
+  let results: Vec<Result<f64, SmartError>> = futures::future::join_all(requests).await;
+
+  let mut quotes: Vec<(Exchange, String, f64)> = vec![];
+  for ((exchange, venue_symbol), result) in venues.into_iter().zip(results) {
+    match result {
+      Ok(price) => quotes.push((exchange, venue_symbol, price)),
+      Err(e) => failed.push((exchange, e.to_string()))
+    }
+  }
+
+  build_composite(quotes, weights, failed)
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_builds_composite_with_deviations() {
+    let quotes: Vec<(Exchange, String, f64)> = vec![
+      (Exchange::Binance, "BTCUSDT".to_string(), 100.0),
+      (Exchange::ByBit, "BTCUSDT".to_string(), 101.0),
+      (Exchange::Coinbase, "BTC-USD".to_string(), 99.0),
+      (Exchange::Dydx, "BTC-USD".to_string(), 0.0)
+    ];
+    let weights: HashMap<Exchange, f64> = HashMap::from([
+      (Exchange::Binance, 2.0), (Exchange::ByBit, 1.0), (Exchange::Coinbase, 1.0)
+    ]);
+    let composite: CompositeQuote = build_composite(quotes, Some(&weights), vec![]).unwrap();
+
+    assert_eq!(composite.median, 100.0);
+    assert_eq!(composite.weighted, Some(100.0));
+    assert_eq!(composite.venues.len(), 3);
+    assert_eq!(composite.failed.len(), 1);
+    assert!((composite.venues[1].deviation_bps - 100.0).abs() < 1e-9);
+    assert_eq!(composite.dislocated(50.0).len(), 2);
+
+    assert!(build_composite(vec![], None, vec![]).is_err());
+  }
+
+  #[tokio::test]
+  async fn tests_request_composite_quote() {
+    let exchanges: [Exchange; 3] = [Exchange::Binance, Exchange::ByBit, Exchange::Coinbase];
+    let composite: CompositeQuote = request_composite_quote("BTCUSDT", &exchanges, None, None).await.unwrap();
+    assert!(composite.median > 0.0);
+  }
+}
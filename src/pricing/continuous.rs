@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use super::candles::CandleBuilder;
+use super::models::{Exchange, HistoricalPrices, IntervalPeriod};
+
+/*
+  Continuous Futures
+  Stitches dated futures contracts (e.g. Binance BTCUSDT_240628) into one roll adjusted series
+  Without adjustment the gap between contracts at each roll shows up as a jump in the spread
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, TS)]
+#[ts(export)]
+pub enum RollAdjustment {
+  BackAdjusted, // earlier prices shifted by the price gap at each roll (preserves price differences)
+  RatioAdjusted, // earlier prices scaled by the price ratio at each roll (preserves returns)
+  Unadjusted // contracts joined as is
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct RollPoint {
+  pub label: u64,
+  pub front_price: f64,
+  pub next_price: f64
+}
+
+/// Roll Point
+/// Latest label traded by both contracts, at least roll_before_secs before the front contract's last label
+fn find_roll_point(front: &HistoricalPrices, next: &HistoricalPrices, roll_before_secs: u64) -> Result<RollPoint, SmartError> {
+  let Some(front_last) = front.labels.last() else {
+    return Err(SmartError::RuntimeCheck("Front contract has no prices".to_string()))
+  };
+  let cutoff: u64 = front_last.saturating_sub(roll_before_secs);
+
+  front.labels.iter().zip(front.prices.iter()).rev()
+    .filter(|(label, _)| **label <= cutoff)
+    .find_map(|(label, front_price)| {
+      next.labels.binary_search(label).ok().map(|idx| RollPoint { label: *label, front_price: *front_price, next_price: next.prices[idx] })
+    })
+    .ok_or(SmartError::RuntimeCheck("Contracts do not overlap before the roll cutoff".to_string()))
+}
+
+/// Stitch Contracts
+/// Joins contracts ordered oldest to newest, switching to the next contract at each roll point
+/// Returns the continuous series and the roll points used
+pub fn stitch_contracts(
+  contracts: &[HistoricalPrices],
+  adjustment: RollAdjustment,
+  roll_before_secs: u64
+) -> Result<(HistoricalPrices, Vec<RollPoint>), SmartError> {
+
+  // Guard: Ensure contracts supplied
+  let Some(last) = contracts.last() else {
+    return Err(SmartError::RuntimeCheck("No contracts supplied".to_string()))
+  };
+
+  let rolls: Vec<RollPoint> = contracts.windows(2)
+    .map(|w| find_roll_point(&w[0], &w[1], roll_before_secs))
+    .collect::<Result<Vec<_>, SmartError>>()?;
+
+  // Guard: Ensure every contract gets at least part of the series
+  if rolls.windows(2).any(|r| r[1].label <= r[0].label) {
+    return Err(SmartError::RuntimeCheck("Contracts must be ordered oldest to newest with increasing roll dates".to_string()));
+  }
+
+  let mut labels: Vec<u64> = vec![];
+  let mut prices: Vec<f64> = vec![];
+  for (i, contract) in contracts.iter().enumerate() {
+    let from: u64 = if i == 0 { 0 } else { rolls[i - 1].label };
+    let to: u64 = rolls.get(i).map(|r| r.label).unwrap_or(u64::MAX);
+
+    // Adjustment accumulated over all later rolls
+    let later: &[RollPoint] = &rolls[i..];
+    let shift: f64 = later.iter().map(|r| r.next_price - r.front_price).sum();
+    let ratio: f64 = later.iter().map(|r| r.next_price / r.front_price).product();
+
+    for (label, price) in contract.labels.iter().zip(contract.prices.iter()) {
+      if *label < from || *label >= to { continue }
+      labels.push(*label);
+      prices.push(match adjustment {
+        RollAdjustment::BackAdjusted => price + shift,
+        RollAdjustment::RatioAdjusted => price * ratio,
+        RollAdjustment::Unadjusted => *price
+      });
+    }
+  }
+
+  let stitched: HistoricalPrices = HistoricalPrices {
+    prices,
+    labels,
+    label_convention: last.label_convention,
+    fetch_report: None
+  };
+  Ok((stitched, rolls))
+}
+
+/// Fetch Continuous
+/// Fetches each dated contract (oldest first) and stitches them into a continuous series
+pub async fn fetch_continuous(
+  exchange: &Exchange,
+  contract_symbols: &[&str],
+  interval_period: &IntervalPeriod,
+  adjustment: RollAdjustment,
+  roll_before_secs: u64
+) -> Result<HistoricalPrices, SmartError> {
+  let mut contracts: Vec<HistoricalPrices> = vec![];
+  for symbol in contract_symbols {
+    let builder: CandleBuilder = CandleBuilder::new(symbol.to_string(), interval_period.clone(), exchange.clone(), None);
+    contracts.push(builder.fetch_prices_candles().await?);
+  }
+  let (stitched, _) = stitch_contracts(&contracts, adjustment, roll_before_secs)?;
+  Ok(stitched)
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::pricing::models::LabelConvention;
+
+  fn contract(labels: Vec<u64>, prices: Vec<f64>) -> HistoricalPrices {
+    HistoricalPrices { prices, labels, label_convention: LabelConvention::OpenTime, fetch_report: None }
+  }
+
+  #[tokio::test]
+  async fn it_stitches_contracts_without_roll_jumps() {
+    let front: HistoricalPrices = contract(vec![1, 2, 3, 4], vec![100.0, 101.0, 102.0, 103.0]);
+    let next: HistoricalPrices = contract(vec![3, 4, 5, 6], vec![112.0, 113.0, 114.0, 115.0]);
+
+    let (back, rolls) = stitch_contracts(&[front.clone(), next.clone()], RollAdjustment::BackAdjusted, 0).unwrap();
+    assert_eq!(rolls[0].label, 4);
+    assert_eq!(back.labels, vec![1, 2, 3, 4, 5, 6]);
+    assert_eq!(back.prices, vec![110.0, 111.0, 112.0, 113.0, 114.0, 115.0]);
+
+    let (ratio, _) = stitch_contracts(&[front.clone(), next.clone()], RollAdjustment::RatioAdjusted, 1).unwrap();
+    assert_eq!(ratio.labels, vec![1, 2, 3, 4, 5, 6]);
+    assert!((ratio.prices[2] - 112.0).abs() < 1e-9);
+    assert!((ratio.prices[1] / ratio.prices[0] - 1.01).abs() < 1e-9);
+
+    let (raw, _) = stitch_contracts(&[front, next], RollAdjustment::Unadjusted, 0).unwrap();
+    assert_eq!(raw.prices, vec![100.0, 101.0, 102.0, 113.0, 114.0, 115.0]);
+  }
+
+  #[tokio::test]
+  async fn it_rejects_non_overlapping_contracts() {
+    let front: HistoricalPrices = contract(vec![1, 2], vec![100.0, 101.0]);
+    let next: HistoricalPrices = contract(vec![3, 4], vec![110.0, 111.0]);
+    assert!(stitch_contracts(&[front, next], RollAdjustment::BackAdjusted, 0).is_err());
+  }
+}
@@ -1,6 +1,6 @@
 use crate::SmartError;
 use super::candles::CandleBuilder;
-use super::models::{Exchange, IntervalPeriod, HistoricalPrices};
+use super::models::{Exchange, IntervalPeriod, HistoricalPrices, DataTolerance};
 
 #[derive(Debug)]
 pub struct PriceController {
@@ -14,6 +14,13 @@ impl PriceController {
     Self { candle_builder }
   }
 
+  /// With Tolerance
+  /// Sets partial data tolerance for the underlying candle fetch
+  pub fn with_tolerance(mut self, tolerance: DataTolerance) -> Self {
+    self.candle_builder = self.candle_builder.with_tolerance(tolerance);
+    self
+  }
+
   /// Get latest prices
   /// Retrieve latest close prices and labels including current price
   pub async fn get_latest_prices(&self) -> Result<HistoricalPrices, SmartError> {
@@ -2,14 +2,64 @@
 
 use crate::SmartError;
 use super::controller::PriceController;
-use super::utils::extract_match_series;
+use super::utils::join_pair_series;
 use super::quotes::request_quote;
-use super::models::{AssetType, Exchange, IntervalPeriod, DataCriteria, PairPrices};
+use super::symbols::validate_pair;
+use super::translate::translate_symbol;
+use super::stablecoin::{normalize_historical_prices, request_conversion_rates, StablecoinRate};
+use super::models::{AssetType, Exchange, IntervalPeriod, DataCriteria, DataTolerance, FetchReport, HistoricalPrices, LabelConvention, MissingDataPolicy, PairPrices};
 
 /// Get Prices for Pair
 /// Retrieves prices for items specified by user
 /// Executes request simultaneously via two threads
 pub async fn get_prices_pair(data_criteria: DataCriteria, twelve_api_key: Option<&str>) -> Result<PairPrices, SmartError> {
+  get_prices_pair_with_tolerance(data_criteria, twelve_api_key, DataTolerance::Strict).await
+}
+
+/// Get Prices for Pair With Tolerance
+/// As get_prices_pair but allows partial data (e.g. for screening) and reports completeness
+pub async fn get_prices_pair_with_tolerance(
+  data_criteria: DataCriteria, 
+  twelve_api_key: Option<&str>,
+  tolerance: DataTolerance
+) -> Result<PairPrices, SmartError> {
+  get_prices_pair_with_options(data_criteria, twelve_api_key, tolerance, MissingDataPolicy::default()).await
+}
+
+/// Get Prices for Pair With Options
+/// As get_prices_pair_with_tolerance with an explicit policy for candles missing from one leg
+/// If the primary exchange fails each fallback exchange is tried in turn, with the serving exchange recorded as the source
+pub async fn get_prices_pair_with_options(
+  data_criteria: DataCriteria, 
+  twelve_api_key: Option<&str>,
+  tolerance: DataTolerance,
+  missing_data_policy: MissingDataPolicy
+) -> Result<PairPrices, SmartError> {
+  let mut failures: Vec<String> = vec![];
+  for source in data_criteria.source_chain() {
+    match fetch_pair_from_source(&source, twelve_api_key, tolerance, missing_data_policy).await {
+      Ok(prices) => return Ok(PairPrices { source: Some(source.exchange), ..prices }),
+      Err(e) if data_criteria.fallback_exchanges.is_empty() => return Err(e),
+      Err(e) => failures.push(format!("{}: {}", source.exchange.as_string(), e))
+    }
+  }
+  Err(SmartError::RuntimeCheck(format!("All data sources failed ({})", failures.join("; "))))
+}
+
+/// Fetch Pair From Source
+/// Fetches and joins both legs from a single exchange
+async fn fetch_pair_from_source(
+  data_criteria: &DataCriteria, 
+  twelve_api_key: Option<&str>,
+  tolerance: DataTolerance,
+  missing_data_policy: MissingDataPolicy
+) -> Result<PairPrices, SmartError> {
+
+  // Guard: Ensure both symbols are listed and trading
+  // Failure to retrieve listings does not block the price request
+  if let Err(e) = validate_pair(&data_criteria.exchange, &data_criteria.asset_0, &data_criteria.asset_1).await {
+    if matches!(e, SmartError::UnknownSymbol(_) | SmartError::Delisted(_)) { return Err(e) }
+  }
 
   // Initialize price controller - asset_1
   let controller_1: PriceController = PriceController::new(
@@ -17,7 +67,7 @@ pub async fn get_prices_pair(data_criteria: DataCriteria, twelve_api_key: Option
     data_criteria.interval_period.clone(), 
     data_criteria.exchange.clone(),
     twelve_api_key
-  );
+  ).with_tolerance(tolerance);
 
   // Initialize price controller - asset_2
   let controller_2: PriceController = PriceController::new(
@@ -25,7 +75,7 @@ pub async fn get_prices_pair(data_criteria: DataCriteria, twelve_api_key: Option
     data_criteria.interval_period.clone(), 
     data_criteria.exchange.clone(),
     twelve_api_key
-  );
+  ).with_tolerance(tolerance);
 
   let asset_1_future = controller_1.get_latest_prices();
   let asset_2_future = controller_2.get_latest_prices();
@@ -44,20 +94,66 @@ pub async fn get_prices_pair(data_criteria: DataCriteria, twelve_api_key: Option
   //   .expect("Failed to join concurrent price processes");
 
   // Ensure time and length match
+  let label_convention: LabelConvention = match &asset_1_res {
+    Ok(asset_1) => asset_1.label_convention,
+    Err(_) => LabelConvention::OpenTime
+  };
+  let fetch_report: Option<FetchReport> = match (&asset_1_res, &asset_2_res) {
+    (Ok(asset_1), Ok(asset_2)) => match (&asset_1.fetch_report, &asset_2.fetch_report) {
+      (Some(r1), Some(r2)) => Some(r1.combine(r2)),
+      _ => None
+    },
+    _ => None
+  };
   let (series_0, series_1, labels) = match asset_1_res {
     Ok(asset_1) => match asset_2_res {
       Ok(asset_2) => {
-        match extract_match_series(asset_1, asset_2) {
-          Ok((series_1, series_2, labels)) => (series_1, series_2, labels),
-          Err(_) => return Err(SmartError::RuntimeCheck("Could not match series".to_string()))
-        }
+        join_pair_series(asset_1, asset_2, missing_data_policy)?
       },
       Err(e) => return Err(SmartError::RuntimeCheck(e.to_string()))
     },
     Err(e) => return Err(SmartError::RuntimeCheck(e.to_string()))
   };
 
-  Ok(PairPrices { series_0, series_1, labels })
+  Ok(PairPrices { series_0, series_1, labels, label_convention, fetch_report, events: vec![], source: None })
+}
+
+/// Get Prices Cross Venue
+/// Retrieves the same asset from two exchanges (symbol translated per venue) joined on timestamp
+/// A venue quoting a stablecoin with a USD rate market (USDT) is converted to USD when the other venue quotes USD
+/// Series 0 is venue 0 and series 1 is venue 1, for cross venue arbitrage analysis
+pub async fn get_prices_cross_venue(
+  symbol: &str,
+  exchange_0: &Exchange,
+  exchange_1: &Exchange,
+  interval_period: &IntervalPeriod,
+  twelve_api_key: Option<&str>,
+  missing_data_policy: MissingDataPolicy
+) -> Result<PairPrices, SmartError> {
+
+  // Guard: Ensure venues differ
+  if exchange_0 == exchange_1 {
+    return Err(SmartError::RuntimeCheck("Cross venue analysis requires two different exchanges".to_string()));
+  }
+
+  let (symbol_0, symbol_1) = (translate_symbol(symbol, exchange_0)?, translate_symbol(symbol, exchange_1)?);
+  let controller_0: PriceController = PriceController::new(symbol_0.clone(), interval_period.clone(), exchange_0.clone(), twelve_api_key);
+  let controller_1: PriceController = PriceController::new(symbol_1.clone(), interval_period.clone(), exchange_1.clone(), twelve_api_key);
+  let symbols: [&str; 2] = [&symbol_0, &symbol_1];
+  let (venue_0, venue_1, rates) = futures::join!(
+    controller_0.get_latest_prices(),
+    controller_1.get_latest_prices(),
+    request_conversion_rates(&symbols, interval_period)
+  );
+
+  // Convert a stablecoin quoted venue to USD when the other venue quotes USD
+  let rates: Vec<StablecoinRate> = rates?;
+  let venue_0: HistoricalPrices = normalize_historical_prices(venue_0?, &symbol_0, &rates)?;
+  let venue_1: HistoricalPrices = normalize_historical_prices(venue_1?, &symbol_1, &rates)?;
+
+  let label_convention: LabelConvention = venue_0.label_convention;
+  let (series_0, series_1, labels) = join_pair_series(venue_0, venue_1, missing_data_policy)?;
+  Ok(PairPrices { series_0, series_1, labels, label_convention, fetch_report: None, events: vec![], source: None })
 }
 
 /// Get Available Assets
@@ -83,7 +179,8 @@ pub async fn fetch_prices(
     interval_period: interval_period.clone(),
     asset_0: asset_0.to_string(),
     asset_1: asset_1.to_string(),
-    exchange: exchange.clone()
+    exchange: exchange.clone(),
+    fallback_exchanges: vec![]
   };
 
   let prices: PairPrices = get_prices_pair(data_criteria, twelve_api_key).await?;
@@ -101,6 +198,30 @@ pub async fn get_latest_quote(symbol: &str, exchange: &Exchange, twelve_api_key:
 #[cfg(test)]
 mod tests {
   use super::*;
+  use super::super::models::HistoricalPrices;
+
+  fn leg(labels: Vec<u64>, prices: Vec<f64>) -> HistoricalPrices {
+    HistoricalPrices { prices, labels, label_convention: LabelConvention::OpenTime, fetch_report: None }
+  }
+
+  #[tokio::test]
+  async fn it_joins_series_with_missing_data_policy() {
+    let asset_0: HistoricalPrices = leg(vec![1, 2, 3, 4, 5], vec![10.0, 11.0, 12.0, 13.0, 14.0]);
+    let asset_1: HistoricalPrices = leg(vec![2, 3, 5], vec![20.0, 21.0, 23.0]);
+
+    let (s0, s1, labels) = join_pair_series(asset_0.clone(), asset_1.clone(), MissingDataPolicy::DropBar).unwrap();
+    assert_eq!(labels, vec![2, 3, 5]);
+    assert_eq!(s0, vec![11.0, 12.0, 14.0]);
+    assert_eq!(s1, vec![20.0, 21.0, 23.0]);
+
+    let (s0, s1, labels) = join_pair_series(asset_0.clone(), asset_1.clone(), MissingDataPolicy::ForwardFill).unwrap();
+    assert_eq!(labels, vec![2, 3, 4, 5]);
+    assert_eq!(s0, vec![11.0, 12.0, 13.0, 14.0]);
+    assert_eq!(s1, vec![20.0, 21.0, 21.0, 23.0]);
+
+    let err: String = join_pair_series(asset_0, asset_1, MissingDataPolicy::Strict).unwrap_err().to_string();
+    assert!(err.contains("Missing candle at label 4"));
+  }
 
   #[tokio::test]
   async fn it_matches_period_request_twelve() {
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use ts_rs::TS;
+
+use super::models::Exchange;
+
+/*
+  Fee Schedule
+  Default maker and taker fees per exchange (base tier, as a fraction of notional) for backtests without an explicit cost
+  Defaults can be replaced per exchange (e.g. for a VIP tier or rebates) and are looked up overrides first
+*/
+
+static FEE_OVERRIDES: RwLock<Option<HashMap<Exchange, FeeSchedule>>> = RwLock::new(None);
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, TS)]
+#[ts(export)]
+pub struct FeeSchedule {
+  pub maker: f64,
+  pub taker: f64
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default, TS)]
+#[ts(export)]
+pub enum FeeSide {
+  Maker,
+  #[default]
+  Taker // market orders, the usual case for both legs of a pair entering together
+}
+
+impl FeeSchedule {
+
+  /// Cost Per Leg
+  /// Fee charged on one leg for the side traded
+  pub fn cost_per_leg(&self, side: FeeSide) -> f64 {
+    match side {
+      FeeSide::Maker => self.maker,
+      FeeSide::Taker => self.taker
+    }
+  }
+}
+
+/// Default Fee Schedule
+/// Built in base tier fees (Binance USDT-margined futures, ByBit linear perpetuals, Coinbase Advanced and dYdX v4)
+pub fn default_fee_schedule(exchange: &Exchange) -> Option<FeeSchedule> {
+  match exchange {
+    Exchange::Binance => Some(FeeSchedule { maker: 0.0002, taker: 0.0005 }),
+    Exchange::ByBit => Some(FeeSchedule { maker: 0.0002, taker: 0.00055 }),
+    Exchange::Coinbase => Some(FeeSchedule { maker: 0.004, taker: 0.006 }),
+    Exchange::Dydx => Some(FeeSchedule { maker: 0.0001, taker: 0.0005 }),
+    _ => None
+  }
+}
+
+/// Fee Schedule
+/// Override for the exchange if set, otherwise its default
+pub fn fee_schedule(exchange: &Exchange) -> Option<FeeSchedule> {
+  let overridden: Option<FeeSchedule> = FEE_OVERRIDES.read().ok()
+    .and_then(|store| store.as_ref().and_then(|s| s.get(exchange).copied()));
+  overridden.or_else(|| default_fee_schedule(exchange))
+}
+
+/// Set Fee Schedule
+/// Replaces the fees used for an exchange
+pub fn set_fee_schedule(exchange: &Exchange, schedule: FeeSchedule) {
+  if let Ok(mut store) = FEE_OVERRIDES.write() {
+    store.get_or_insert_with(HashMap::new).insert(exchange.clone(), schedule);
+  }
+}
+
+/// Clear Fee Schedules
+/// Drops every override, restoring the defaults
+pub fn clear_fee_schedules() {
+  if let Ok(mut store) = FEE_OVERRIDES.write() {
+    *store = None;
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_looks_up_default_and_overridden_fees() {
+    assert_eq!(fee_schedule(&Exchange::Binance).map(|f| f.cost_per_leg(FeeSide::Taker)), Some(0.0005));
+    assert_eq!(fee_schedule(&Exchange::Dydx).map(|f| f.cost_per_leg(FeeSide::Maker)), Some(0.0001));
+    assert!(fee_schedule(&Exchange::Twelve).is_none());
+
+    set_fee_schedule(&Exchange::Kraken, FeeSchedule { maker: 0.0016, taker: 0.0026 });
+    assert_eq!(fee_schedule(&Exchange::Kraken).map(|f| f.taker), Some(0.0026));
+    clear_fee_schedules();
+    assert!(fee_schedule(&Exchange::Kraken).is_none());
+  }
+}
@@ -0,0 +1,365 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::SmartError;
+use super::models::{HistoricalPrices, LabelConvention, PairPrices};
+
+/*
+  Local File Loading
+  Allows offline datasets (vendor exports, research dumps) to be analysed without network access
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub enum CsvColumn {
+  Index(usize), // zero based
+  Name(String), // header name, case insensitive
+  Names(Vec<String>) // first of these header names present, case insensitive
+}
+
+/// CSV Mapping
+/// Which columns hold the timestamp and close price for a vendor's csv layout
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CsvMapping {
+  pub timestamp: CsvColumn,
+  pub close: CsvColumn,
+  pub delimiter: char,
+  pub has_header: bool, // whether the first row holds column names (required by Name columns)
+  pub label_convention: LabelConvention // whether the vendor stamps bars by open or close time
+}
+
+impl Default for CsvMapping {
+  fn default() -> Self {
+    Self {
+      timestamp: CsvColumn::Index(0),
+      close: CsvColumn::Names(vec!["close".to_string(), "price".to_string()]),
+      delimiter: ',',
+      has_header: true,
+      label_convention: LabelConvention::OpenTime
+    }
+  }
+}
+
+impl CsvColumn {
+
+  /// Resolve
+  /// Column index within a row, names requiring a header row
+  fn resolve(&self, header: Option<&Vec<String>>) -> Result<usize, SmartError> {
+    match self {
+      Self::Index(idx) => Ok(*idx),
+      Self::Name(name) => header
+        .and_then(|h| h.iter().position(|c| c == &name.to_lowercase()))
+        .ok_or_else(|| SmartError::RuntimeCheck(format!("Column \"{}\" not found in csv header", name))),
+      Self::Names(names) => header
+        .and_then(|h| names.iter().find_map(|name| h.iter().position(|c| c == &name.to_lowercase())))
+        .ok_or_else(|| SmartError::RuntimeCheck(format!("None of the columns {:?} found in csv header", names)))
+    }
+  }
+}
+
+/// Parse Timestamp
+/// Accepts unix seconds, unix milliseconds or ISO / date strings and returns unix seconds
+fn parse_timestamp(value: &str) -> Result<u64, SmartError> {
+  let value: &str = value.trim().trim_matches('"');
+
+  if let Ok(num) = value.parse::<f64>() {
+    let ts: u64 = num as u64;
+    return Ok(if ts > 100_000_000_000 { ts / 1000 } else { ts });
+  }
+
+  if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+    return Ok(dt.timestamp() as u64);
+  }
+
+  for fmt in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S"] {
+    if let Ok(dt) = NaiveDateTime::parse_from_str(value, fmt) {
+      return Ok(dt.and_utc().timestamp() as u64);
+    }
+  }
+
+  if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+    if let Some(dt) = date.and_hms_opt(0, 0, 0) {
+      return Ok(dt.and_utc().timestamp() as u64);
+    }
+  }
+
+  Err(SmartError::RuntimeCheck(format!("Unable to parse timestamp: {}", value)))
+}
+
+/// Parse Price
+/// Parses a single csv cell into a price
+fn parse_price(value: &str) -> Result<f64, SmartError> {
+  Ok(value.trim().trim_matches('"').parse::<f64>()?)
+}
+
+/// Read CSV Rows
+/// Splits csv text into the header (lowercased, when the first row is one) and data rows
+fn read_csv_rows(text: &str, delimiter: char, has_header: bool) -> (Option<Vec<String>>, Vec<Vec<String>>) {
+  let mut lines = text.lines().map(|l| l.trim()).filter(|l| !l.is_empty());
+  let header: Option<Vec<String>> = if has_header {
+    lines.next().map(|first| first.split(delimiter).map(|c| c.trim().trim_matches('"').to_lowercase()).collect())
+  } else {
+    None
+  };
+  let rows: Vec<Vec<String>> = lines.map(|line| line.split(delimiter).map(|c| c.to_string()).collect()).collect();
+  (header, rows)
+}
+
+/// Sort and Dedupe
+/// Ensures labels are ascending and unique, keeping the last value seen for a label
+fn sort_and_dedupe(mut rows: Vec<(u64, Vec<f64>)>) -> Vec<(u64, Vec<f64>)> {
+  rows.sort_by_key(|r| r.0);
+  let mut deduped: Vec<(u64, Vec<f64>)> = Vec::with_capacity(rows.len());
+  for row in rows {
+    match deduped.last_mut() {
+      Some(last) if last.0 == row.0 => *last = row,
+      _ => deduped.push(row)
+    }
+  }
+  deduped
+}
+
+/// Historical Prices From CSV
+/// Reads a single asset csv file with a header row, a timestamp first column and a "close" or "price" column
+/// Shorthand for HistoricalPrices::from_csv with the default mapping
+pub fn historical_prices_from_csv(path: impl AsRef<Path>) -> Result<HistoricalPrices, SmartError> {
+  HistoricalPrices::from_csv(path, &CsvMapping::default())
+}
+
+impl HistoricalPrices {
+
+  /// From CSV
+  /// Reads a single asset csv file using a column mapping
+  pub fn from_csv(path: impl AsRef<Path>, mapping: &CsvMapping) -> Result<Self, SmartError> {
+    Self::from_csv_str(&std::fs::read_to_string(path)?, mapping)
+  }
+
+  /// From CSV Str
+  /// Parses csv text using a column mapping (rows with an empty or unparsable close are skipped)
+  pub fn from_csv_str(text: &str, mapping: &CsvMapping) -> Result<Self, SmartError> {
+    let (header, rows) = read_csv_rows(text, mapping.delimiter, mapping.has_header);
+    let timestamp_idx: usize = mapping.timestamp.resolve(header.as_ref())?;
+    let close_idx: usize = mapping.close.resolve(header.as_ref())?;
+
+    let mut parsed: Vec<(u64, Vec<f64>)> = vec![];
+    for row in rows.iter() {
+      let Some(label_str) = row.get(timestamp_idx) else {
+        return Err(SmartError::RuntimeCheck(format!("Missing timestamp column in row: {}", row.join(&mapping.delimiter.to_string()))))
+      };
+      if let Some(Ok(price)) = row.get(close_idx).map(|p| parse_price(p)) {
+        parsed.push((parse_timestamp(label_str)?, vec![price]));
+      }
+    }
+
+    let parsed: Vec<(u64, Vec<f64>)> = sort_and_dedupe(parsed);
+    if parsed.is_empty() {
+      return Err(SmartError::RuntimeCheck("No price rows found in csv".to_string()));
+    }
+    Ok(Self {
+      prices: parsed.iter().map(|r| r.1[0]).collect(),
+      labels: parsed.iter().map(|r| r.0).collect(),
+      label_convention: mapping.label_convention,
+      fetch_report: None
+    })
+  }
+}
+
+/// Align Historical Prices
+/// Keeps only the labels present in both series
+pub fn align_historical_prices(asset_0: HistoricalPrices, asset_1: HistoricalPrices) -> Result<PairPrices, SmartError> {
+
+  // Guard: Ensure labels mean the same bar time
+  if asset_0.label_convention != asset_1.label_convention {
+    return Err(SmartError::RuntimeCheck("Failed to align series (label conventions differ)".to_string()));
+  }
+
+  let lookup: HashMap<u64, f64> = asset_1.labels.iter().copied().zip(asset_1.prices.iter().copied()).collect();
+
+  let mut series_0: Vec<f64> = vec![];
+  let mut series_1: Vec<f64> = vec![];
+  let mut labels: Vec<u64> = vec![];
+  for (label, price) in asset_0.labels.iter().zip(asset_0.prices.iter()) {
+    if let Some(price_1) = lookup.get(label) {
+      series_0.push(*price);
+      series_1.push(*price_1);
+      labels.push(*label);
+    }
+  }
+
+  if labels.is_empty() {
+    return Err(SmartError::RuntimeCheck("No overlapping timestamps between series".to_string()));
+  }
+
+  Ok(PairPrices { series_0, series_1, labels, label_convention: asset_0.label_convention, fetch_report: None, events: vec![], source: None })
+}
+
+impl PairPrices {
+
+  /// From CSV Files
+  /// Loads one csv per asset with the default mapping (see historical_prices_from_csv) and aligns them on matching timestamps
+  pub fn from_csv_files(path_0: impl AsRef<Path>, path_1: impl AsRef<Path>) -> Result<Self, SmartError> {
+    Self::from_csv_pair(path_0, path_1, &CsvMapping::default())
+  }
+
+  /// From CSV Pair
+  /// Loads one csv per asset with the same column mapping and aligns them on matching timestamps
+  pub fn from_csv_pair(path_0: impl AsRef<Path>, path_1: impl AsRef<Path>, mapping: &CsvMapping) -> Result<Self, SmartError> {
+    let asset_0: HistoricalPrices = HistoricalPrices::from_csv(path_0, mapping)?;
+    let asset_1: HistoricalPrices = HistoricalPrices::from_csv(path_1, mapping)?;
+    align_historical_prices(asset_0, asset_1)
+  }
+
+  /// From CSV File
+  /// Loads a combined csv file with columns: timestamp, series_0, series_1
+  /// The first row is taken as a header only when its timestamp does not parse
+  /// Rows with a missing or unparsable price on either leg are skipped
+  pub fn from_csv_file(path: impl AsRef<Path>) -> Result<Self, SmartError> {
+    let text: String = std::fs::read_to_string(path)?;
+    let (_, rows) = read_csv_rows(&text, ',', false);
+
+    let mut parsed: Vec<(u64, Vec<f64>)> = vec![];
+    for (i, row) in rows.iter().enumerate() {
+      if row.len() < 3 {
+        return Err(SmartError::RuntimeCheck(format!("Expected 3 columns in row: {}", row.join(","))));
+      }
+      let label: u64 = match parse_timestamp(&row[0]) {
+        Ok(label) => label,
+        Err(_) if i == 0 => continue,
+        Err(e) => return Err(e)
+      };
+      if let (Ok(p0), Ok(p1)) = (parse_price(&row[1]), parse_price(&row[2])) {
+        parsed.push((label, vec![p0, p1]));
+      }
+    }
+
+    let parsed: Vec<(u64, Vec<f64>)> = sort_and_dedupe(parsed);
+    if parsed.is_empty() {
+      return Err(SmartError::RuntimeCheck("No price rows found in file".to_string()));
+    }
+
+    Ok(Self {
+      series_0: parsed.iter().map(|r| r.1[0]).collect(),
+      series_1: parsed.iter().map(|r| r.1[1]).collect(),
+      labels: parsed.iter().map(|r| r.0).collect(),
+      label_convention: LabelConvention::OpenTime,
+      fetch_report: None,
+      events: vec![],
+      source: None
+    })
+  }
+
+  /// From JSON File
+  /// Loads a serialized PairPrices object (as produced by wasm_pair_prices)
+  pub fn from_json_file(path: impl AsRef<Path>) -> Result<Self, SmartError> {
+    let text: String = std::fs::read_to_string(path)?;
+    let prices: Self = serde_json::from_str::<Self>(&text)?;
+
+    // Guard: Ensure lengths match
+    if prices.series_0.len() != prices.series_1.len() || prices.series_0.len() != prices.labels.len() {
+      return Err(SmartError::RuntimeCheck("PairPrices series and labels lengths do not match".to_string()));
+    }
+
+    Ok(prices)
+  }
+
+  /// From JSON Files
+  /// Loads one serialized HistoricalPrices object per asset and aligns them on matching timestamps
+  pub fn from_json_files(path_0: impl AsRef<Path>, path_1: impl AsRef<Path>) -> Result<Self, SmartError> {
+    let asset_0: HistoricalPrices = serde_json::from_str(&std::fs::read_to_string(path_0)?)?;
+    let asset_1: HistoricalPrices = serde_json::from_str(&std::fs::read_to_string(path_1)?)?;
+    align_historical_prices(asset_0, asset_1)
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::path::PathBuf;
+
+  fn write_temp(name: &str, contents: &str) -> PathBuf {
+    let path: PathBuf = std::env::temp_dir().join(format!("zscore_lib_files_{}", name));
+    std::fs::write(&path, contents).unwrap();
+    path
+  }
+
+  #[tokio::test]
+  async fn it_loads_and_aligns_csv_files() {
+    let path_0 = write_temp("a0.csv", "timestamp,open,close\n1700000000,1,10.0\n1700003600,1,11.0\n1700007200,1,12.0\n");
+    let path_1 = write_temp("a1.csv", "time,close\n1700003600000,20.0\n1700007200000,21.0\n1700010800000,22.0\n");
+    let prices: PairPrices = PairPrices::from_csv_files(&path_0, &path_1).unwrap();
+    assert_eq!(prices.labels, vec![1700003600, 1700007200]);
+    assert_eq!(prices.series_0, vec![11.0, 12.0]);
+    assert_eq!(prices.series_1, vec![20.0, 21.0]);
+  }
+
+  #[tokio::test]
+  async fn it_loads_combined_csv_file() {
+    let path = write_temp("combined.csv", "date,btc,eth\n2023-01-02,2,20\n2023-01-01,1,10\n2023-01-03,,30\n");
+    let prices: PairPrices = PairPrices::from_csv_file(&path).unwrap();
+    assert_eq!(prices.labels, vec![1672531200, 1672617600]);
+    assert_eq!(prices.series_0, vec![1.0, 2.0]);
+    assert_eq!(prices.series_1, vec![10.0, 20.0]);
+  }
+
+  #[tokio::test]
+  async fn it_loads_csv_with_column_mapping() {
+    let vendor_0 = write_temp("vendor0.csv", "Symbol;Date;Open;Adj Close\nSPY;2023-01-02;1;400.5\nSPY;2023-01-03;1;\nSPY;2023-01-04;1;402.0\n");
+    let vendor_1 = write_temp("vendor1.csv", "Symbol;Date;Open;Adj Close\nQQQ;2023-01-04;1;270.0\nQQQ;2023-01-02;1;265.5\n");
+    let mapping: CsvMapping = CsvMapping {
+      timestamp: CsvColumn::Name("Date".to_string()),
+      close: CsvColumn::Index(3),
+      delimiter: ';',
+      has_header: true,
+      label_convention: LabelConvention::CloseTime
+    };
+
+    let asset_0: HistoricalPrices = HistoricalPrices::from_csv(&vendor_0, &mapping).unwrap();
+    assert_eq!(asset_0.labels, vec![1672617600, 1672790400]);
+    assert_eq!(asset_0.label_convention, LabelConvention::CloseTime);
+
+    let prices: PairPrices = PairPrices::from_csv_pair(&vendor_0, &vendor_1, &mapping).unwrap();
+    assert_eq!(prices.series_0, vec![400.5, 402.0]);
+    assert_eq!(prices.series_1, vec![265.5, 270.0]);
+
+    let missing: CsvMapping = CsvMapping { close: CsvColumn::Name("last".to_string()), ..mapping };
+    assert!(HistoricalPrices::from_csv(&vendor_0, &missing).is_err());
+  }
+
+  #[tokio::test]
+  async fn it_loads_headerless_csv() {
+
+    // The second column is a symbol, so the first row must not be mistaken for a header
+    let text: &str = "2023-01-02,SPY,400.5\n2023-01-03,SPY,401.0\n2023-01-04,SPY,402.0\n";
+    let mapping: CsvMapping = CsvMapping { close: CsvColumn::Index(2), has_header: false, ..Default::default() };
+    let prices: HistoricalPrices = HistoricalPrices::from_csv_str(text, &mapping).unwrap();
+    assert_eq!(prices.labels, vec![1672617600, 1672704000, 1672790400]);
+    assert_eq!(prices.prices, vec![400.5, 401.0, 402.0]);
+
+    // Named columns need a header
+    assert!(HistoricalPrices::from_csv_str(text, &CsvMapping { has_header: false, ..Default::default() }).is_err());
+
+    let combined = write_temp("combined_headerless.csv", "2023-01-01,1,10\n2023-01-02,2,20\n");
+    assert_eq!(PairPrices::from_csv_file(&combined).unwrap().labels, vec![1672531200, 1672617600]);
+  }
+
+  #[tokio::test]
+  async fn it_loads_pair_prices_json_file() {
+    let path = write_temp("pair.json", r#"{"series_0":[1.0,2.0],"series_1":[3.0,4.0],"labels":[1,2]}"#);
+    let prices: PairPrices = PairPrices::from_json_file(&path).unwrap();
+    assert_eq!(prices.series_1, vec![3.0, 4.0]);
+  }
+
+  #[tokio::test]
+  async fn it_aligns_json_files_of_different_lengths() {
+    let path_0 = write_temp("h0.json", r#"{"prices":[1.0,2.0,3.0],"labels":[100,200,300]}"#);
+    let path_1 = write_temp("h1.json", r#"{"prices":[20.0,30.0,40.0,50.0],"labels":[200,300,400,500]}"#);
+    let prices: PairPrices = PairPrices::from_json_files(&path_0, &path_1).unwrap();
+    assert_eq!(prices.labels, vec![200, 300]);
+    assert_eq!((prices.series_0, prices.series_1), (vec![2.0, 3.0], vec![20.0, 30.0]));
+
+    let closed = write_temp("h2.json", r#"{"prices":[20.0],"labels":[200],"label_convention":"CloseTime"}"#);
+    assert!(PairPrices::from_json_files(&path_0, &closed).is_err());
+  }
+}
@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use crate::SmartError;
+use super::utils::{is_secret_param, redact_path};
+
+/*
+  HTTP Fixtures (VCR Mode)
+  Records api responses to disk and replays them on subsequent runs
+  Makes integration tests deterministic and allows analyses to be reproduced from archived snapshots
+*/
+
+/// Environment variable used to enable fixtures without code changes
+pub const FIXTURE_DIR_ENV: &str = "ZSCORE_FIXTURE_DIR";
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub enum FixtureMode {
+  Off, // Always hit the network
+  Record, // Always hit the network and overwrite fixtures
+  Replay, // Only read fixtures, error if missing
+  Auto // Replay if fixture exists, otherwise record
+}
+
+#[derive(Debug, Clone)]
+pub struct FixtureConfig {
+  pub dir: PathBuf,
+  pub mode: FixtureMode
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Fixture {
+  pub url: String,
+  pub status: u16,
+  pub headers: HashMap<String, String>,
+  pub body: String
+}
+
+static FIXTURE_CONFIG: RwLock<Option<FixtureConfig>> = RwLock::new(None);
+
+/// Set Fixture Config
+/// Enables (or disables with None) fixture recording / replaying for all api requests
+pub fn set_fixture_config(config: Option<FixtureConfig>) {
+  if let Ok(mut guard) = FIXTURE_CONFIG.write() {
+    *guard = config;
+  }
+}
+
+/// Get Fixture Config
+/// Returns the programmatic config, falling back to the environment variable (Auto mode)
+pub fn get_fixture_config() -> Option<FixtureConfig> {
+  if let Ok(guard) = FIXTURE_CONFIG.read() {
+    if let Some(config) = guard.as_ref() {
+      if config.mode == FixtureMode::Off { return None }
+      return Some(config.clone());
+    }
+  }
+
+  std::env::var(FIXTURE_DIR_ENV).ok()
+    .filter(|dir| !dir.is_empty())
+    .map(|dir| FixtureConfig { dir: PathBuf::from(dir), mode: FixtureMode::Auto })
+}
+
+/// Strip Secrets
+/// Removes api keys from a url so fixtures can be shared and keyed independently of credentials
+pub fn strip_secrets(url: &str) -> String {
+  let url: String = redact_path(url);
+  let Some((base, query)) = url.split_once('?') else { return url };
+  let params: Vec<&str> = query.split('&')
+    .filter(|p| !is_secret_param(p))
+    .collect();
+  if params.is_empty() { base.to_string() } else { format!("{}?{}", base, params.join("&")) }
+}
+
+/// URL Hash
+/// Stable FNV-1a hash of the url (secrets removed) used as the fixture file key
+pub fn url_hash(url: &str) -> String {
+  let mut hash: u64 = 0xcbf29ce484222325;
+  for byte in strip_secrets(url).as_bytes() {
+    hash ^= *byte as u64;
+    hash = hash.wrapping_mul(0x100000001b3);
+  }
+  format!("{:016x}", hash)
+}
+
+/// Fixture Path
+/// Location on disk for a given url
+pub fn fixture_path(config: &FixtureConfig, url: &str) -> PathBuf {
+  config.dir.join(format!("{}.json", url_hash(url)))
+}
+
+/// Load Fixture
+/// Reads a previously recorded fixture if one exists
+pub fn load_fixture(config: &FixtureConfig, url: &str) -> Result<Option<Fixture>, SmartError> {
+  let path: PathBuf = fixture_path(config, url);
+  if !path.exists() { return Ok(None) }
+  let text: String = std::fs::read_to_string(path)?;
+  Ok(Some(serde_json::from_str::<Fixture>(&text)?))
+}
+
+/// Save Fixture
+/// Writes a fixture to disk, creating the directory if required
+pub fn save_fixture(config: &FixtureConfig, url: &str, fixture: &Fixture) -> Result<(), SmartError> {
+  std::fs::create_dir_all(&config.dir)?;
+  let text: String = serde_json::to_string_pretty(fixture)?;
+  std::fs::write(fixture_path(config, url), text)?;
+  Ok(())
+}
+
+/// Fixture Into Response
+/// Rebuilds a reqwest response from a recorded fixture
+pub fn fixture_into_response(fixture: Fixture) -> Result<reqwest::Response, SmartError> {
+  let mut builder = http::Response::builder().status(fixture.status);
+  for (name, value) in fixture.headers.iter() {
+    builder = builder.header(name.as_str(), value.as_str());
+  }
+  let response: http::Response<String> = builder.body(fixture.body)
+    .map_err(|e| SmartError::RuntimeCheck(format!("Failed to rebuild fixture response: {}", e)))?;
+  Ok(reqwest::Response::from(response))
+}
+
+/// Record Response
+/// Consumes a live response, saves it as a fixture and returns an equivalent response
+pub async fn record_response(config: &FixtureConfig, url: &str, res: reqwest::Response) -> Result<reqwest::Response, SmartError> {
+  let status: u16 = res.status().as_u16();
+  let headers: HashMap<String, String> = res.headers().iter()
+    .filter_map(|(k, v)| v.to_str().ok().map(|v| (k.to_string(), v.to_string())))
+    .collect();
+  let body: String = res.text().await?;
+  let fixture: Fixture = Fixture { url: strip_secrets(url), status, headers, body };
+  save_fixture(config, url, &fixture)?;
+  fixture_into_response(fixture)
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_strips_secrets_before_hashing() {
+    let url_a: &str = "https://api.twelvedata.com/price?symbol=AAPL&apikey=abc";
+    let url_b: &str = "https://api.twelvedata.com/price?symbol=AAPL&apikey=xyz";
+    assert_eq!(strip_secrets(url_a), "https://api.twelvedata.com/price?symbol=AAPL");
+    assert_eq!(url_hash(url_a), url_hash(url_b));
+  }
+
+  #[tokio::test]
+  async fn it_round_trips_a_fixture() {
+    let config: FixtureConfig = FixtureConfig {
+      dir: std::env::temp_dir().join("zscore_lib_fixtures_test"),
+      mode: FixtureMode::Auto
+    };
+    let url: &str = "https://example.com/prices?symbol=BTCUSDT";
+    let mut headers: HashMap<String, String> = HashMap::new();
+    headers.insert("x-test".to_string(), "1".to_string());
+    let fixture: Fixture = Fixture { url: url.to_string(), status: 200, headers, body: r#"{"price":"1.5"}"#.to_string() };
+    save_fixture(&config, url, &fixture).unwrap();
+
+    let loaded: Fixture = load_fixture(&config, url).unwrap().unwrap();
+    let res: reqwest::Response = fixture_into_response(loaded).unwrap();
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers().get("x-test").unwrap(), "1");
+    assert_eq!(res.text().await.unwrap(), r#"{"price":"1.5"}"#);
+  }
+}
@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::calendar::MarketCalendar;
+use super::models::{Exchange, PairPrices};
+
+/*
+  Forex Sessions
+  Twelve serves no forex volume, so session activity stands in as a volume proxy
+  Typical spreads per session (in pips) are converted to return units for use as the default cost per leg
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, TS)]
+#[ts(export)]
+pub enum ForexSession {
+  Sydney, // 21:00 to 00:00 UTC
+  Tokyo, // 00:00 to 07:00 UTC
+  London, // 07:00 to 12:00 UTC
+  LondonNewYork, // 12:00 to 16:00 UTC (overlap)
+  NewYork // 16:00 to 21:00 UTC
+}
+
+/// Typical majors spread in pips during the London session (other pairs use DEFAULT_SPREAD_PIPS)
+const MAJOR_SPREAD_PIPS: [(&str, f64); 10] = [
+  ("EUR/USD", 0.6), ("USD/JPY", 0.7), ("GBP/USD", 0.9), ("USD/CHF", 1.0), ("AUD/USD", 0.8),
+  ("USD/CAD", 1.1), ("NZD/USD", 1.3), ("EUR/GBP", 1.0), ("EUR/JPY", 1.1), ("GBP/JPY", 1.8)
+];
+const DEFAULT_SPREAD_PIPS: f64 = 3.0;
+
+impl ForexSession {
+
+  /// From Timestamp
+  /// Session active at a unix timestamp (seconds)
+  pub fn from_timestamp(timestamp: u64) -> Self {
+    match (timestamp % 86400) / 3600 {
+      0..=6 => Self::Tokyo,
+      7..=11 => Self::London,
+      12..=15 => Self::LondonNewYork,
+      16..=20 => Self::NewYork,
+      _ => Self::Sydney
+    }
+  }
+
+  /// Volume Proxy
+  /// Relative activity of the session (London = 1.0)
+  pub fn volume_proxy(&self) -> f64 {
+    match self {
+      Self::Sydney => 0.25,
+      Self::Tokyo => 0.5,
+      Self::London => 1.0,
+      Self::LondonNewYork => 1.4,
+      Self::NewYork => 0.75
+    }
+  }
+
+  /// Spread Multiplier
+  /// Typical spread relative to the London session
+  pub fn spread_multiplier(&self) -> f64 {
+    match self {
+      Self::Sydney => 2.5,
+      Self::Tokyo => 1.5,
+      Self::London => 1.0,
+      Self::LondonNewYork => 0.9,
+      Self::NewYork => 1.2
+    }
+  }
+}
+
+/// Pip Size
+/// Price increment of one pip (JPY quoted pairs use 0.01)
+pub fn pip_size(symbol: &str) -> f64 {
+  if symbol.to_uppercase().ends_with("JPY") { 0.01 } else { 0.0001 }
+}
+
+/// Typical Spread Pips
+/// Spread in pips for a forex pair during the given session
+pub fn typical_spread_pips(symbol: &str, session: ForexSession) -> f64 {
+  let symbol: String = symbol.to_uppercase();
+  let base_pips: f64 = MAJOR_SPREAD_PIPS.iter()
+    .find(|(s, _)| *s == symbol)
+    .map(|(_, pips)| *pips)
+    .unwrap_or(DEFAULT_SPREAD_PIPS);
+  base_pips * session.spread_multiplier()
+}
+
+/// Spread Cost Per Leg
+/// Half the typical spread as a return, paid each time a leg is entered or exited
+pub fn spread_cost_per_leg(symbol: &str, price: f64, session: ForexSession) -> f64 {
+
+  // Guard: Ensure price usable
+  if !price.is_finite() || price <= 0.0 { return 0.0 }
+
+  typical_spread_pips(symbol, session) * pip_size(symbol) / price / 2.0
+}
+
+/// Session Volume Proxies
+/// Volume proxy for each label
+pub fn session_volume_proxies(labels: &[u64]) -> Vec<f64> {
+  labels.iter().map(|l| ForexSession::from_timestamp(*l).volume_proxy()).collect()
+}
+
+/// Session Spread Cost
+/// Average cost per leg over the bars of a series, each bar priced by its session
+pub fn session_spread_cost(symbol: &str, series: &[f64], labels: &[u64]) -> f64 {
+  let costs: Vec<f64> = series.iter().zip(labels.iter())
+    .map(|(price, label)| spread_cost_per_leg(symbol, *price, ForexSession::from_timestamp(*label)))
+    .filter(|cost| *cost > 0.0)
+    .collect();
+  if costs.is_empty() { return 0.0 }
+  costs.iter().sum::<f64>() / costs.len() as f64
+}
+
+/// Forex Cost Per Leg
+/// Default cost per leg for a Twelve forex pair (None for any other exchange or asset class)
+/// Both legs are averaged as the backtest applies a single cost to each
+pub fn forex_cost_per_leg(exchange: &Exchange, asset_0: &str, asset_1: &str, prices: &PairPrices) -> Option<f64> {
+  if *exchange != Exchange::Twelve { return None }
+  let is_forex = |symbol: &str| MarketCalendar::infer(exchange, symbol) == MarketCalendar::Forex;
+  if !is_forex(asset_0) || !is_forex(asset_1) { return None }
+
+  let cost_0: f64 = session_spread_cost(asset_0, &prices.series_0, &prices.labels);
+  let cost_1: f64 = session_spread_cost(asset_1, &prices.series_1, &prices.labels);
+  Some((cost_0 + cost_1) / 2.0)
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_classifies_forex_sessions() {
+    assert_eq!(ForexSession::from_timestamp(1688342400), ForexSession::Tokyo); // 2023-07-03 00:00 UTC
+    assert_eq!(ForexSession::from_timestamp(1688342400 + 8 * 3600), ForexSession::London);
+    assert_eq!(ForexSession::from_timestamp(1688342400 + 13 * 3600), ForexSession::LondonNewYork);
+    assert_eq!(ForexSession::from_timestamp(1688342400 + 18 * 3600), ForexSession::NewYork);
+    assert_eq!(ForexSession::from_timestamp(1688342400 + 22 * 3600), ForexSession::Sydney);
+    assert_eq!(session_volume_proxies(&[1688342400 + 13 * 3600]), vec![1.4]);
+  }
+
+  #[tokio::test]
+  async fn it_converts_spread_pips_to_returns() {
+    let eurusd: f64 = spread_cost_per_leg("EUR/USD", 1.1, ForexSession::London);
+    assert!((eurusd - 0.6 * 0.0001 / 1.1 / 2.0).abs() < 1e-12);
+    assert!(eurusd < 0.0005 / 10.0);
+
+    let usdjpy: f64 = spread_cost_per_leg("USD/JPY", 140.0, ForexSession::Sydney);
+    assert!((usdjpy - 0.7 * 2.5 * 0.01 / 140.0 / 2.0).abs() < 1e-12);
+    assert_eq!(spread_cost_per_leg("EUR/USD", 0.0, ForexSession::London), 0.0);
+  }
+
+  #[tokio::test]
+  async fn it_defaults_forex_cost_per_leg() {
+    let labels: Vec<u64> = (0..48).map(|i| 1688342400 + i * 3600).collect();
+    let prices: PairPrices = PairPrices {
+      series_0: vec![1.1; 48],
+      series_1: vec![1.27; 48],
+      labels,
+      label_convention: Default::default(),
+      fetch_report: None,
+      events: vec![],
+      source: None
+    };
+
+    let cost: f64 = forex_cost_per_leg(&Exchange::Twelve, "EUR/USD", "GBP/USD", &prices).unwrap();
+    assert!(cost > 0.0 && cost < 0.0001);
+    assert!(forex_cost_per_leg(&Exchange::Twelve, "BTC/USD", "ETH/USD", &prices).is_none());
+    assert!(forex_cost_per_leg(&Exchange::Binance, "EUR/USD", "GBP/USD", &prices).is_none());
+  }
+}
@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use super::models::{Exchange, IntervalPeriod, LabelConvention};
+use super::times::convert_iso_to_timestamp;
+use super::utils::api_request;
+
+/*
+  Funding Rates
+  Perpetual funding rate history, aligned to candle labels for use as an indicator and as backtest carry
+  Rates are per funding event (e.g. every 8 hours on Binance and ByBit, hourly on Dydx)
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default, TS)]
+#[ts(export)]
+pub struct FundingRates {
+  pub labels: Vec<u64>, // funding times (secs)
+  pub rates: Vec<f64>
+}
+
+impl FundingRates {
+
+  /// From Events
+  /// Structures (time, rate) events sorted by time with duplicates removed
+  pub fn from_events(mut events: Vec<(u64, f64)>) -> Self {
+    events.sort_by_key(|e| e.0);
+    events.dedup_by_key(|e| e.0);
+    let (labels, rates) = events.into_iter().unzip();
+    Self { labels, rates }
+  }
+
+  /// Bar Close Times
+  /// Time at which each candle's price is known given the label convention
+  fn bar_close_times(labels: &[u64], label_convention: LabelConvention, interval: &IntervalPeriod) -> Vec<u64> {
+    let offset: u64 = if label_convention == LabelConvention::OpenTime { interval.interval_seconds() } else { 0 };
+    labels.iter().map(|l| l + offset).collect()
+  }
+
+  /// Rates At Labels
+  /// Latest funding rate known at each bar close (0.0 before the first event), usable as an indicator without lookahead
+  pub fn rates_at_labels(&self, labels: &[u64], label_convention: LabelConvention, interval: &IntervalPeriod) -> Vec<f64> {
+    Self::bar_close_times(labels, label_convention, interval).iter()
+      .map(|close| {
+        let idx: usize = self.labels.partition_point(|t| t <= close);
+        if idx == 0 { 0.0 } else { self.rates[idx - 1] }
+      })
+      .collect()
+  }
+
+  /// Payments Per Bar
+  /// Sum of funding rates settled during each bar (between the previous and current bar close)
+  /// Positive values are paid by longs to shorts
+  pub fn payments_per_bar(&self, labels: &[u64], label_convention: LabelConvention, interval: &IntervalPeriod) -> Vec<f64> {
+    let closes: Vec<u64> = Self::bar_close_times(labels, label_convention, interval);
+    let mut payments: Vec<f64> = vec![0.0; closes.len()];
+    for i in 1..closes.len() {
+      let from: usize = self.labels.partition_point(|t| *t <= closes[i - 1]);
+      let to: usize = self.labels.partition_point(|t| *t <= closes[i]);
+      payments[i] = self.rates[from..to].iter().sum();
+    }
+    payments
+  }
+}
+
+/// Get funding url
+/// Retrieves the funding history url for a given exchange (None if not a perpetual venue)
+fn get_funding_url(exchange: &Exchange) -> Option<String> {
+  let url: &str = match exchange {
+    Exchange::Binance => "https://fapi.binance.com/fapi/v1/fundingRate?symbol={symbol}&limit=1000",
+    Exchange::BinanceCoinM => "https://dapi.binance.com/dapi/v1/fundingRate?symbol={symbol}&limit=1000",
+    Exchange::ByBit => "https://api.bybit.com/v5/market/funding/history?category=linear&symbol={symbol}&limit=200",
+    Exchange::ByBitInverse => "https://api.bybit.com/v5/market/funding/history?category=inverse&symbol={symbol}&limit=200",
+    Exchange::Dydx => "https://indexer.dydx.trade/v4/historicalFunding/{symbol}",
+    _ => return None
+  };
+  Some(url.to_string())
+}
+
+/// Extract Funding Binance
+/// Takes Binance funding history and returns funding events
+fn extract_funding_binance(data_obj: &serde_json::Value) -> Vec<(u64, f64)> {
+  data_obj.as_array().map(|items| items.iter().filter_map(|item| {
+    let time: u64 = item.get("fundingTime")?.as_u64()? / 1000;
+    let rate: f64 = item.get("fundingRate")?.as_str()?.parse().ok()?;
+    Some((time, rate))
+  }).collect()).unwrap_or_default()
+}
+
+/// Extract Funding ByBit
+/// Takes ByBit funding history and returns funding events
+fn extract_funding_bybit(data_obj: &serde_json::Value) -> Vec<(u64, f64)> {
+  data_obj.get("result").and_then(|r| r.get("list")).and_then(|l| l.as_array())
+    .map(|items| items.iter().filter_map(|item| {
+      let time: u64 = item.get("fundingRateTimestamp")?.as_str()?.parse::<u64>().ok()? / 1000;
+      let rate: f64 = item.get("fundingRate")?.as_str()?.parse().ok()?;
+      Some((time, rate))
+    }).collect())
+    .unwrap_or_default()
+}
+
+/// Extract Funding Dydx
+/// Takes Dydx funding history and returns funding events
+fn extract_funding_dydx(data_obj: &serde_json::Value) -> Vec<(u64, f64)> {
+  data_obj.get("historicalFunding").and_then(|l| l.as_array())
+    .map(|items| items.iter().filter_map(|item| {
+      let effective_at: String = item.get("effectiveAt")?.as_str()?.to_string();
+      let time: u64 = convert_iso_to_timestamp(effective_at, "%Y-%m-%dT%H:%M:%S%.3f%z");
+      let rate: f64 = item.get("rate")?.as_str()?.parse().ok()?;
+      Some((time, rate))
+    }).collect())
+    .unwrap_or_default()
+}
+
+/// Parse Funding Rates
+/// Structures a funding history response for the exchange
+pub fn parse_funding_rates(exchange: &Exchange, data_obj: &serde_json::Value) -> FundingRates {
+  let events: Vec<(u64, f64)> = match exchange {
+    Exchange::Binance | Exchange::BinanceCoinM => extract_funding_binance(data_obj),
+    Exchange::ByBit | Exchange::ByBitInverse => extract_funding_bybit(data_obj),
+    Exchange::Dydx => extract_funding_dydx(data_obj),
+    _ => vec![]
+  };
+  FundingRates::from_events(events)
+}
+
+/// Request Funding Rates
+/// Requests the most recent funding history for a perpetual
+pub async fn request_funding_rates(exchange: &Exchange, symbol: &str) -> Result<FundingRates, SmartError> {
+  let Some(request_url) = get_funding_url(exchange) else {
+    return Err(SmartError::RuntimeCheck(format!("Funding rates not supported for exchange: {}", exchange.as_string())))
+  };
+  let request_url: String = request_url.replace("{symbol}", symbol);
+
+  // Make request
+  let res_data: reqwest::Response = api_request(&request_url).await?;
+
+  // Guard: Ensure status code
+  if res_data.status() != 200 {
+    let e: String = format!("Failed to extract data: {:?}", res_data.text().await);
+    return Err(SmartError::APIResponseStatus(e));
+  }
+
+  let data_obj: serde_json::Value = res_data.json().await?;
+  Ok(parse_funding_rates(exchange, &data_obj))
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_parses_and_aligns_funding() {
+    let bybit: serde_json::Value = serde_json::json!({ "retCode": 0, "result": { "list": [
+      { "symbol": "BTCUSDT", "fundingRate": "0.0003", "fundingRateTimestamp": "57600000" },
+      { "symbol": "BTCUSDT", "fundingRate": "0.0001", "fundingRateTimestamp": "28800000" }
+    ]}});
+    let funding: FundingRates = parse_funding_rates(&Exchange::ByBit, &bybit);
+    assert_eq!(funding.labels, vec![28_800, 57_600]);
+
+    // Hourly bars labeled by open time, bar 7 closes at the first funding event
+    let labels: Vec<u64> = (0..20).map(|h| h * 3600).collect();
+    let interval: IntervalPeriod = IntervalPeriod::Hour(1, 20);
+    let rates: Vec<f64> = funding.rates_at_labels(&labels, LabelConvention::OpenTime, &interval);
+    assert_eq!(rates[6], 0.0);
+    assert_eq!(rates[7], 0.0001);
+    assert_eq!(rates[15], 0.0003);
+
+    let payments: Vec<f64> = funding.payments_per_bar(&labels, LabelConvention::OpenTime, &interval);
+    assert_eq!(payments[7], 0.0001);
+    assert_eq!(payments[15], 0.0003);
+    assert!((payments.iter().sum::<f64>() - 0.0004).abs() < 1e-12);
+
+    let binance: serde_json::Value = serde_json::json!([{ "symbol": "BTCUSDT", "fundingTime": 28800000u64, "fundingRate": "-0.0002" }]);
+    assert_eq!(parse_funding_rates(&Exchange::Binance, &binance).rates, vec![-0.0002]);
+
+    let dydx: serde_json::Value = serde_json::json!({ "historicalFunding": [
+      { "ticker": "BTC-USD", "rate": "0.00001", "price": "50000.5", "effectiveAt": "1970-01-01T08:00:00.000Z", "effectiveAtHeight": "100" }
+    ]});
+    assert_eq!(parse_funding_rates(&Exchange::Dydx, &dydx).labels, vec![28_800]);
+  }
+
+  #[tokio::test]
+  async fn tests_request_funding_rates_binance() {
+    let funding: FundingRates = request_funding_rates(&Exchange::Binance, "BTCUSDT").await.unwrap();
+    assert!(!funding.rates.is_empty());
+  }
+}
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use super::clock::now_ms;
+use super::models::Exchange;
+use super::utils::api_request;
+
+/*
+  Exchange Health
+  Probes each venue's ping / status endpoint for latency and operational status
+  Lets a monitor switch to an alternate data source before a pair fetch fails on an outage
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, TS)]
+#[ts(export)]
+pub enum HealthStatus {
+  Operational,
+  Maintenance, // venue reports planned maintenance
+  Degraded, // reachable but reporting an error
+  Down, // unreachable or failing requests
+  Unknown // no public status endpoint
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct ExchangeHealth {
+  pub exchange: Exchange,
+  pub status: HealthStatus,
+  pub latency_ms: Option<i64>,
+  pub message: Option<String>
+}
+
+impl ExchangeHealth {
+  pub fn is_operational(&self) -> bool {
+    self.status == HealthStatus::Operational
+  }
+}
+
+/// Get health url
+/// Ping or system status endpoint for a given exchange (None if the venue has no public endpoint)
+fn get_health_url(exchange: &Exchange) -> Option<String> {
+  let url: &str = match exchange {
+    Exchange::Alpaca => return None, // status requires the authenticated trading api
+    Exchange::AlphaVantage => return None, // no status endpoint and probes would spend the call quota
+    Exchange::Binance | Exchange::BinanceSpot => "https://api.binance.com/sapi/v1/system/status",
+    Exchange::BinanceCoinM => "https://dapi.binance.com/dapi/v1/ping",
+    Exchange::BinanceUs => "https://api.binance.us/api/v3/ping",
+    Exchange::Bitfinex => "https://api-pub.bitfinex.com/v2/platform/status",
+    Exchange::ByBit | Exchange::ByBitInverse => "https://api.bybit.com/v5/market/time",
+    Exchange::Coinbase => "https://api.exchange.coinbase.com/time",
+    Exchange::Dydx => "https://indexer.dydx.trade/v4/time",
+    Exchange::GateIo => "https://api.gateio.ws/api/v4/spot/time",
+    Exchange::Hyperliquid => return None, // info API is POST only and has no status request
+    Exchange::Kraken => "https://api.kraken.com/0/public/SystemStatus",
+    Exchange::KuCoin => "https://api.kucoin.com/api/v1/status",
+    Exchange::Okx => "https://www.okx.com/api/v5/public/time",
+    Exchange::Tiingo => return None, // test endpoint requires a token
+    Exchange::Twelve => return None,
+    Exchange::Yahoo => return None // unofficial api without a status endpoint
+  };
+  Some(url.to_string())
+}
+
+/// Parse Health Status
+/// Interprets a successful probe response body
+pub fn parse_health_status(exchange: &Exchange, data_obj: &serde_json::Value) -> HealthStatus {
+  match exchange {
+    Exchange::Binance | Exchange::BinanceSpot => match data_obj.get("status").and_then(|v| v.as_i64()) {
+      Some(0) => HealthStatus::Operational,
+      Some(1) => HealthStatus::Maintenance,
+      _ => HealthStatus::Degraded
+    },
+    Exchange::Bitfinex => match data_obj.get(0).and_then(|v| v.as_i64()) {
+      Some(1) => HealthStatus::Operational,
+      Some(0) => HealthStatus::Maintenance,
+      _ => HealthStatus::Degraded
+    },
+    Exchange::ByBit | Exchange::ByBitInverse => match data_obj.get("retCode").and_then(|v| v.as_i64()) {
+      Some(0) => HealthStatus::Operational,
+      _ => HealthStatus::Degraded
+    },
+    Exchange::Kraken => match data_obj.get("result").and_then(|r| r.get("status")).and_then(|v| v.as_str()) {
+      Some("online") => HealthStatus::Operational,
+      Some("maintenance") => HealthStatus::Maintenance,
+      _ => HealthStatus::Degraded // cancel_only and post_only restrict trading
+    },
+    Exchange::KuCoin => match data_obj.get("data").and_then(|d| d.get("status")).and_then(|v| v.as_str()) {
+      Some("open") => HealthStatus::Operational,
+      Some("close") => HealthStatus::Maintenance,
+      _ => HealthStatus::Degraded // cancelonly restricts trading
+    },
+    Exchange::Okx => match data_obj.get("code").and_then(|v| v.as_str()) {
+      Some("0") => HealthStatus::Operational,
+      _ => HealthStatus::Degraded
+    },
+    _ => HealthStatus::Operational
+  }
+}
+
+/// Exchange Health
+/// Probes the exchange and reports round trip latency and status
+/// Failed probes are reported as Down rather than returned as errors
+pub async fn exchange_health(exchange: &Exchange) -> ExchangeHealth {
+  let Some(request_url) = get_health_url(exchange) else {
+    return ExchangeHealth { exchange: exchange.clone(), status: HealthStatus::Unknown, latency_ms: None, message: None }
+  };
+
+  let started_ms: i64 = now_ms();
+  let probe: Result<serde_json::Value, SmartError> = match api_request(&request_url).await {
+    Ok(res) => res.json::<serde_json::Value>().await.map_err(SmartError::from),
+    Err(e) => Err(e)
+  };
+  let latency_ms: i64 = now_ms() - started_ms;
+
+  match probe {
+    Ok(data_obj) => ExchangeHealth {
+      exchange: exchange.clone(),
+      status: parse_health_status(exchange, &data_obj),
+      latency_ms: Some(latency_ms),
+      message: None
+    },
+    Err(e) => ExchangeHealth {
+      exchange: exchange.clone(),
+      status: HealthStatus::Down,
+      latency_ms: None,
+      message: Some(e.to_string())
+    }
+  }
+}
+
+/// First Healthy
+/// Probes exchanges in order of preference and returns the first operational one
+pub async fn first_healthy(exchanges: &[Exchange]) -> Option<ExchangeHealth> {
+  for exchange in exchanges {
+    let health: ExchangeHealth = exchange_health(exchange).await;
+    if health.is_operational() { return Some(health) }
+  }
+  None
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_parses_status_responses() {
+    let normal: serde_json::Value = serde_json::json!({ "status": 0, "msg": "normal" });
+    let maintenance: serde_json::Value = serde_json::json!({ "status": 1, "msg": "system maintenance" });
+    assert_eq!(parse_health_status(&Exchange::Binance, &normal), HealthStatus::Operational);
+    assert_eq!(parse_health_status(&Exchange::Binance, &maintenance), HealthStatus::Maintenance);
+
+    let bybit_error: serde_json::Value = serde_json::json!({ "retCode": 10006, "retMsg": "Too many visits" });
+    assert_eq!(parse_health_status(&Exchange::ByBit, &bybit_error), HealthStatus::Degraded);
+
+    let kraken: serde_json::Value = serde_json::json!({ "error": [], "result": { "status": "cancel_only", "timestamp": "2023-11-01T00:00:00Z" } });
+    assert_eq!(parse_health_status(&Exchange::Kraken, &kraken), HealthStatus::Degraded);
+
+    assert_eq!(parse_health_status(&Exchange::Bitfinex, &serde_json::json!([0])), HealthStatus::Maintenance);
+
+    let kucoin: serde_json::Value = serde_json::json!({ "code": "200000", "data": { "status": "close", "msg": "upgrade match engine" } });
+    assert_eq!(parse_health_status(&Exchange::KuCoin, &kucoin), HealthStatus::Maintenance);
+
+    let okx_error: serde_json::Value = serde_json::json!({ "code": "50001", "msg": "Service temporarily unavailable", "data": [] });
+    assert_eq!(parse_health_status(&Exchange::Okx, &okx_error), HealthStatus::Degraded);
+
+    let twelve: ExchangeHealth = exchange_health(&Exchange::Twelve).await;
+    assert_eq!(twelve.status, HealthStatus::Unknown);
+  }
+
+  #[tokio::test]
+  async fn tests_exchange_health_bybit() {
+    let health: ExchangeHealth = exchange_health(&Exchange::ByBit).await;
+    assert!(health.is_operational());
+    assert!(health.latency_ms.is_some());
+  }
+}
@@ -0,0 +1,91 @@
+use crate::SmartError;
+use super::models::Exchange;
+use super::translate::parse_symbol;
+
+/*
+  Inverse Contracts
+  Coin-margined futures (Binance COIN-M, ByBit inverse) trade in contracts of a fixed USD face value
+  Quotes and candles are still USD per coin so prices compare directly with linear perps (basis trades)
+  Sizing and PnL however are in contracts and settle in the base coin, handled here
+*/
+
+/// Contract Value
+/// USD face value of one contract (None for linear or spot venues)
+pub fn contract_value(exchange: &Exchange, symbol: &str) -> Option<f64> {
+  match exchange {
+    Exchange::BinanceCoinM => {
+      let base: String = parse_symbol(symbol).ok()?.base;
+      if base == "BTC" { Some(100.0) } else { Some(10.0) }
+    },
+    Exchange::ByBitInverse => Some(1.0),
+    _ => None
+  }
+}
+
+/// Required Contract Value
+/// Contract value or an error if the exchange is not inverse
+fn required_contract_value(exchange: &Exchange, symbol: &str) -> Result<f64, SmartError> {
+  contract_value(exchange, symbol)
+    .ok_or(SmartError::RuntimeCheck(format!("{} is not an inverse contract on {}", symbol, exchange.as_string())))
+}
+
+/// Contracts For Notional
+/// Whole number of contracts closest to (not exceeding) a USD notional
+pub fn contracts_for_notional(exchange: &Exchange, symbol: &str, notional_usd: f64) -> Result<u64, SmartError> {
+  let value: f64 = required_contract_value(exchange, symbol)?;
+  Ok((notional_usd.max(0.0) / value).floor() as u64)
+}
+
+/// Contracts To Base
+/// Coin exposure of a number of contracts at a given price
+pub fn contracts_to_base(exchange: &Exchange, symbol: &str, contracts: f64, price: f64) -> Result<f64, SmartError> {
+
+  // Guard: Ensure price is usable
+  if price <= 0.0 {
+    return Err(SmartError::RuntimeCheck("Price must be positive to convert inverse contracts".to_string()));
+  }
+
+  let value: f64 = required_contract_value(exchange, symbol)?;
+  Ok(contracts * value / price)
+}
+
+/// Inverse PnL
+/// Profit in the base coin for contracts opened at entry and closed at exit
+/// Inverse payoff is contracts * value * (1 / entry - 1 / exit), negated for shorts
+pub fn inverse_pnl(exchange: &Exchange, symbol: &str, contracts: f64, entry: f64, exit: f64, is_long: bool) -> Result<f64, SmartError> {
+
+  // Guard: Ensure prices are usable
+  if entry <= 0.0 || exit <= 0.0 {
+    return Err(SmartError::RuntimeCheck("Prices must be positive to calculate inverse pnl".to_string()));
+  }
+
+  let value: f64 = required_contract_value(exchange, symbol)?;
+  let pnl: f64 = contracts * value * (1.0 / entry - 1.0 / exit);
+  Ok(if is_long { pnl } else { -pnl })
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_sizes_and_values_inverse_contracts() {
+    assert_eq!(contract_value(&Exchange::BinanceCoinM, "BTCUSD_PERP"), Some(100.0));
+    assert_eq!(contract_value(&Exchange::BinanceCoinM, "ETHUSD_PERP"), Some(10.0));
+    assert_eq!(contract_value(&Exchange::ByBitInverse, "BTCUSD"), Some(1.0));
+    assert!(contract_value(&Exchange::Binance, "BTCUSDT").is_none());
+
+    assert_eq!(contracts_for_notional(&Exchange::BinanceCoinM, "BTCUSD_PERP", 1_050.0).unwrap(), 10);
+    assert!(contracts_for_notional(&Exchange::Binance, "BTCUSDT", 1_000.0).is_err());
+
+    let base: f64 = contracts_to_base(&Exchange::BinanceCoinM, "BTCUSD_PERP", 10.0, 50_000.0).unwrap();
+    assert!((base - 0.02).abs() < 1e-12);
+
+    // 1000 USD long from 50k to 100k earns 0.01 BTC
+    let pnl: f64 = inverse_pnl(&Exchange::BinanceCoinM, "BTCUSD_PERP", 10.0, 50_000.0, 100_000.0, true).unwrap();
+    assert!((pnl - 0.01).abs() < 1e-12);
+    let short: f64 = inverse_pnl(&Exchange::ByBitInverse, "BTCUSD", 1_000.0, 50_000.0, 100_000.0, false).unwrap();
+    assert!((short + 0.01).abs() < 1e-12);
+  }
+}
@@ -0,0 +1,236 @@
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+
+use crate::SmartError;
+use super::models::Exchange;
+
+/*
+  Streaming JSON
+  Decodes the elements of one large array in a response as the body arrives rather than buffering the full text
+  Only the element being read is held in memory; everything outside the array (status, meta, errors) is kept as a small envelope
+  Keeps WASM memory flat for multi megabyte payloads (exchangeInfo, 24h tickers, 5000 candle Twelve pages)
+*/
+
+enum Frame {
+  Object { is_key_next: bool, key: Option<String> },
+  Array
+}
+
+/// Array Decoder
+/// Incremental decoder for the array found at a path of object keys (empty path is a top level array)
+pub struct ArrayDecoder<T> {
+  exchange: Exchange,
+  path: Vec<String>,
+  stack: Vec<Frame>,
+  is_in_string: bool,
+  is_escaped: bool,
+  key: Option<Vec<u8>>,
+  target_depth: Option<usize>,
+  is_target_found: bool,
+  element: Vec<u8>,
+  envelope: Vec<u8>,
+  items: Vec<T>
+}
+
+impl<T: DeserializeOwned> ArrayDecoder<T> {
+  pub fn new(exchange: &Exchange, path: &[&str]) -> Self {
+    Self {
+      exchange: exchange.clone(),
+      path: path.iter().map(|k| k.to_string()).collect(),
+      stack: vec![],
+      is_in_string: false,
+      is_escaped: false,
+      key: None,
+      target_depth: None,
+      is_target_found: false,
+      element: vec![],
+      envelope: vec![],
+      items: vec![]
+    }
+  }
+
+  /// Is Target Position
+  /// Whether an array opening at the current position is the one being streamed
+  fn is_target_position(&self) -> bool {
+    if self.is_target_found || self.stack.len() != self.path.len() { return false }
+    self.stack.iter().zip(&self.path).all(|(frame, expected)| match frame {
+      Frame::Object { key: Some(key), .. } => key == expected,
+      _ => false
+    })
+  }
+
+  /// Is Inside Target
+  fn is_inside_target(&self) -> bool {
+    self.target_depth.is_some_and(|depth| self.stack.len() >= depth)
+  }
+
+  /// Route
+  /// Sends a byte to the current element or the envelope
+  fn route(&mut self, byte: u8) {
+    if self.is_inside_target() {
+      self.element.push(byte);
+    } else {
+      self.envelope.push(byte);
+    }
+  }
+
+  /// Finish Element
+  /// Decodes the completed element
+  fn finish_element(&mut self) -> Result<(), SmartError> {
+    if self.element.is_empty() { return Ok(()) }
+    let item: T = serde_json::from_slice(&self.element).map_err(|e| {
+      SmartError::SchemaMismatch(format!("{}: element {}: {}", self.exchange.as_string(), self.items.len(), e))
+    })?;
+    self.items.push(item);
+    self.element.clear();
+    Ok(())
+  }
+
+  /// Feed
+  /// Consumes the next chunk of the body
+  pub fn feed(&mut self, chunk: &[u8]) -> Result<(), SmartError> {
+    for &byte in chunk {
+      if self.is_in_string {
+        if self.is_escaped {
+          self.is_escaped = false;
+        } else if byte == b'\\' {
+          self.is_escaped = true;
+        } else if byte == b'"' {
+          self.is_in_string = false;
+          if let (Some(key), Some(Frame::Object { key: current, .. })) = (self.key.take(), self.stack.last_mut()) {
+            *current = Some(String::from_utf8_lossy(&key).into_owned());
+          }
+        }
+        if self.is_in_string {
+          if let Some(key) = self.key.as_mut() { key.push(byte) }
+        }
+        self.route(byte);
+        continue;
+      }
+
+      match byte {
+        b'"' => {
+          self.is_in_string = true;
+          if let Some(Frame::Object { is_key_next: true, .. }) = self.stack.last() {
+            self.key = Some(vec![]);
+          }
+          self.route(byte);
+        },
+        b'[' if self.is_target_position() => {
+          self.stack.push(Frame::Array);
+          self.target_depth = Some(self.stack.len());
+          self.envelope.push(byte);
+        },
+        b'{' | b'[' => {
+          self.route(byte);
+          self.stack.push(if byte == b'{' { Frame::Object { is_key_next: true, key: None } } else { Frame::Array });
+        },
+        b']' if self.target_depth == Some(self.stack.len()) => {
+          self.finish_element()?;
+          self.stack.pop();
+          self.target_depth = None;
+          self.is_target_found = true;
+          self.envelope.push(byte);
+        },
+        b'}' | b']' => {
+          self.stack.pop();
+          self.route(byte);
+        },
+        b',' if self.target_depth == Some(self.stack.len()) => self.finish_element()?,
+        b',' => {
+          if let Some(Frame::Object { is_key_next, key }) = self.stack.last_mut() {
+            *is_key_next = true;
+            *key = None;
+          }
+          self.route(byte);
+        },
+        b':' => {
+          if let Some(Frame::Object { is_key_next, .. }) = self.stack.last_mut() {
+            *is_key_next = false;
+          }
+          self.route(byte);
+        },
+        b' ' | b'\n' | b'\r' | b'\t' => {},
+        _ => self.route(byte)
+      }
+    }
+    Ok(())
+  }
+
+  /// Finish
+  /// Returns the decoded elements and the envelope (with the streamed array left empty)
+  pub fn finish(self) -> Result<(Vec<T>, serde_json::Value), SmartError> {
+
+    // Guard: Ensure the body was complete
+    if !self.stack.is_empty() || self.is_in_string {
+      return Err(SmartError::SchemaMismatch(format!("{}: response body ended mid document", self.exchange.as_string())));
+    }
+
+    let envelope: serde_json::Value = serde_json::from_slice(&self.envelope)
+      .map_err(|e| SmartError::SchemaMismatch(format!("{}: {}", self.exchange.as_string(), e)))?;
+    Ok((self.items, envelope))
+  }
+}
+
+/// Stream JSON Array
+/// Decodes the array at the given path as the response body arrives
+/// The envelope should still be decoded with the response schema to surface exchange errors
+pub async fn stream_json_array<T: DeserializeOwned>(
+  res_data: reqwest::Response,
+  exchange: &Exchange,
+  path: &[&str]
+) -> Result<(Vec<T>, serde_json::Value), SmartError> {
+  let mut decoder: ArrayDecoder<T> = ArrayDecoder::new(exchange, path);
+  let mut chunks = res_data.bytes_stream();
+  while let Some(chunk) = chunks.next().await {
+    decoder.feed(&chunk?)?;
+  }
+  decoder.finish()
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::pricing::schemas::{CandleRow, TwelveValue};
+
+  #[tokio::test]
+  async fn it_decodes_array_elements_across_chunks() {
+    let body: &str = r#"{"meta":{"symbol":"A\"[B","interval":"1h"},"values":[
+      {"datetime":"2023-11-01 01:00:00","close":"1.5"},
+      {"datetime":"2023-11-01 00:00:00","close":"1.25","note":"x]},"}
+    ],"status":"ok"}"#;
+
+    // Split into awkward chunk sizes to cross tokens and strings
+    let mut decoder: ArrayDecoder<TwelveValue> = ArrayDecoder::new(&Exchange::Twelve, &["values"]);
+    for chunk in body.as_bytes().chunks(7) {
+      decoder.feed(chunk).unwrap();
+    }
+    let (values, envelope) = decoder.finish().unwrap();
+    assert_eq!(values.len(), 2);
+    assert_eq!(values[1].close.0, 1.25);
+    assert_eq!(envelope["values"], serde_json::json!([]));
+    assert_eq!(envelope["meta"]["symbol"], "A\"[B");
+    assert_eq!(envelope["status"], "ok");
+
+    // Top level arrays stream each row
+    let mut rows: ArrayDecoder<CandleRow> = ArrayDecoder::new(&Exchange::Binance, &[]);
+    rows.feed(br#"[[1700000000000,"1","2","0.5","1.5","10"],[1700003600000,"1","2","0.5","#).unwrap();
+    rows.feed(br#""1.75","10"]]"#).unwrap();
+    let (candles, _) = rows.finish().unwrap();
+    assert_eq!(candles[1], CandleRow { time: 1700003600000, open: 1.0, high: 2.0, low: 0.5, close: 1.75, volume: 10.0 });
+
+    // Error payloads without the array are left in the envelope
+    let mut missing: ArrayDecoder<TwelveValue> = ArrayDecoder::new(&Exchange::Twelve, &["values"]);
+    missing.feed(br#"{"code":400,"message":"symbol not found","status":"error"}"#).unwrap();
+    let (values, envelope) = missing.finish().unwrap();
+    assert!(values.is_empty() && envelope["status"] == "error");
+
+    // Bad elements and truncated bodies are schema mismatches
+    let mut bad: ArrayDecoder<CandleRow> = ArrayDecoder::new(&Exchange::Binance, &[]);
+    assert!(matches!(bad.feed(br#"[[1700000000000,"1"]]"#), Err(SmartError::SchemaMismatch(_))));
+    let mut truncated: ArrayDecoder<CandleRow> = ArrayDecoder::new(&Exchange::Binance, &[]);
+    truncated.feed(br#"[[1700000000000,"1","2","0.5","1.5"]"#).unwrap();
+    assert!(matches!(truncated.finish(), Err(SmartError::SchemaMismatch(_))));
+  }
+}
@@ -1,10 +1,44 @@
+pub mod basis;
+pub mod basket;
+pub mod breaker;
+pub mod cache;
+pub mod capacity;
+pub mod browsercache;
+pub mod calendar;
 pub mod candles;
+pub mod clock;
+pub mod coalesce;
+pub mod composite;
+pub mod continuous;
 pub mod controller;
 pub mod entry;
+pub mod fees;
+pub mod files;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod fixtures;
+pub mod forex;
+pub mod funding;
+pub mod health;
+pub mod inverse;
+pub mod jsonstream;
 pub mod models;
+pub mod openinterest;
+#[cfg(all(feature = "parquet", not(target_arch = "wasm32")))]
+pub mod parquetfile;
+pub mod prefetch;
+pub mod quotecache;
 pub mod quotes;
 pub mod quotemulti;
+pub mod quotestream;
+pub mod ratelimit;
+pub mod schemas;
+pub mod sectors;
+pub mod stablecoin;
 pub mod symbols;
 pub mod times;
+pub mod trades;
+pub mod translate;
 pub mod utils;
 pub mod volume;
+#[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+pub mod wsclient;
@@ -16,7 +16,9 @@ pub struct DataCriteria {
   pub exchange: Exchange,
   pub asset_0: String,
   pub asset_1: String,
-  pub interval_period: IntervalPeriod
+  pub interval_period: IntervalPeriod,
+  #[serde(default)]
+  pub fallback_exchanges: Vec<Exchange> // tried in order (symbols translated) if the primary exchange fails
 }
 
 /*
@@ -26,12 +28,25 @@ pub struct DataCriteria {
 #[derive(Debug, Serialize, TS)]
 #[ts(export)]
 pub struct QuoteExch {
+  pub alpaca: f64,
+  pub alpha_vantage: f64,
   pub binance: f64,
+  pub binance_coinm: f64,
+  pub binance_spot: f64,
   pub binance_us: f64,
+  pub bitfinex: f64,
   pub bybit: f64,
+  pub bybit_inverse: f64,
   pub coinbase: f64,
   pub dydx: f64,
+  pub gateio: f64,
+  pub hyperliquid: f64,
+  pub kraken: f64,
+  pub kucoin: f64,
+  pub okx: f64,
+  pub tiingo: f64,
   pub twelve: f64,
+  pub yahoo: f64,
 }
 
 /*
@@ -69,6 +84,44 @@ impl IntervalPeriod {
       Self::Day(x, y) => format!("[Day][{},{}]", x, y),
     }
   }
+
+  /// Interval Seconds
+  /// Length of a single bar in seconds
+  pub fn interval_seconds(&self) -> u64 {
+    match &self {
+      Self::Min(x, _) => *x as u64 * 60,
+      Self::Hour(x, _) => *x as u64 * 60 * 60,
+      Self::Day(x, _) => *x as u64 * 60 * 60 * 24,
+    }
+  }
+
+  /// Period
+  /// Number of bars requested
+  pub fn period(&self) -> u32 {
+    match &self {
+      Self::Min(_, y) | Self::Hour(_, y) | Self::Day(_, y) => *y
+    }
+  }
+
+  /// With Period
+  /// Same interval with a different number of bars
+  pub fn with_period(&self, period: u32) -> Self {
+    match &self {
+      Self::Min(x, _) => Self::Min(*x, period),
+      Self::Hour(x, _) => Self::Hour(*x, period),
+      Self::Day(x, _) => Self::Day(*x, period),
+    }
+  }
+}
+
+/// Label Convention
+/// Whether a candle's time label marks the open or the close of the bar
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default, TS)]
+#[ts(export)]
+pub enum LabelConvention {
+  #[default]
+  OpenTime,
+  CloseTime
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, TS)]
@@ -96,62 +149,172 @@ pub enum AssetType {
   Stock
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Hash, TS)]
 #[ts(export)]
 
 pub enum Exchange {
+  Alpaca, // US stocks and crypto (api key id and secret required)
+  AlphaVantage, // stocks and forex (api key required)
   Binance,
+  BinanceCoinM, // coin-margined (inverse) futures
+  BinanceSpot, // spot (Binance is USDT-margined futures)
   BinanceUs,
+  Bitfinex, // spot
   ByBit,
+  ByBitInverse, // coin-margined (inverse) perpetuals
   Coinbase,
-  Dydx,
-  Twelve
+  Dydx, // v4 indexer perpetuals
+  GateIo, // USDT-margined perpetual futures
+  Hyperliquid, // USDC-margined perpetuals
+  Kraken,
+  KuCoin, // spot
+  Okx, // USDT-margined perpetual swaps
+  Tiingo, // US stocks (IEX intraday) and crypto (api key required)
+  Twelve,
+  Yahoo // daily and weekly stocks, ETFs and indices (no api key)
 }
 
 impl Exchange {
   pub fn create_from_string(exchange_str: &str) -> Self {
     match exchange_str {
+      "Alpaca" => Exchange::Alpaca,
+      "AlphaVantage" => Exchange::AlphaVantage,
       "Binance" => Exchange::Binance,
+      "BinanceCoinM" => Exchange::BinanceCoinM,
+      "BinanceSpot" => Exchange::BinanceSpot,
       "BinanceUs" => Exchange::BinanceUs,
+      "Bitfinex" => Exchange::Bitfinex,
       "ByBit" => Exchange::ByBit,
+      "ByBitInverse" => Exchange::ByBitInverse,
       "Coinbase" => Exchange::Coinbase,
       "Dydx" => Exchange::Dydx,
+      "GateIo" => Exchange::GateIo,
+      "Hyperliquid" => Exchange::Hyperliquid,
+      "Kraken" => Exchange::Kraken,
+      "KuCoin" => Exchange::KuCoin,
+      "Okx" => Exchange::Okx,
+      "Tiingo" => Exchange::Tiingo,
       "Twelve" => Exchange::Twelve,
+      "Yahoo" => Exchange::Yahoo,
       _ => panic!("Incorrect or unknown exchange")
     }
   }
 
   pub fn as_string(&self) -> String {
     match self {
+      Exchange::Alpaca => "Alpaca".to_string(),
+      Exchange::AlphaVantage => "AlphaVantage".to_string(),
       Exchange::Binance => "Binance".to_string(),
+      Exchange::BinanceCoinM => "BinanceCoinM".to_string(),
+      Exchange::BinanceSpot => "BinanceSpot".to_string(),
       Exchange::BinanceUs => "BinanceUs".to_string(),
+      Exchange::Bitfinex => "Bitfinex".to_string(),
       Exchange::ByBit => "ByBit".to_string(),
+      Exchange::ByBitInverse => "ByBitInverse".to_string(),
       Exchange::Coinbase => "Coinbase".to_string(),
       Exchange::Dydx => "Dydx".to_string(),
-      Exchange::Twelve => "Twelve".to_string()
+      Exchange::GateIo => "GateIo".to_string(),
+      Exchange::Hyperliquid => "Hyperliquid".to_string(),
+      Exchange::Kraken => "Kraken".to_string(),
+      Exchange::KuCoin => "KuCoin".to_string(),
+      Exchange::Okx => "Okx".to_string(),
+      Exchange::Tiingo => "Tiingo".to_string(),
+      Exchange::Twelve => "Twelve".to_string(),
+      Exchange::Yahoo => "Yahoo".to_string()
     }
   }
 
+  /// From URL
+  /// Identifies the exchange a request url belongs to (by host)
+  pub fn from_url(url: &str) -> Option<Self> {
+    let host: &str = url.split("://").nth(1).unwrap_or(url).split('/').next().unwrap_or("");
+    match host {
+      "data.alpaca.markets" => Some(Exchange::Alpaca),
+      "www.alphavantage.co" => Some(Exchange::AlphaVantage),
+      "fapi.binance.com" => Some(Exchange::Binance),
+      "dapi.binance.com" => Some(Exchange::BinanceCoinM),
+      "api.binance.com" => Some(Exchange::BinanceSpot),
+      "api.binance.us" => Some(Exchange::BinanceUs),
+      "api-pub.bitfinex.com" => Some(Exchange::Bitfinex),
+      "api.bybit.com" => Some(Exchange::ByBit),
+      "api.exchange.coinbase.com" => Some(Exchange::Coinbase),
+      "indexer.dydx.trade" => Some(Exchange::Dydx),
+      "api.gateio.ws" => Some(Exchange::GateIo),
+      "api.hyperliquid.xyz" => Some(Exchange::Hyperliquid),
+      "api.kraken.com" => Some(Exchange::Kraken),
+      "api.kucoin.com" => Some(Exchange::KuCoin),
+      "www.okx.com" => Some(Exchange::Okx),
+      "api.tiingo.com" => Some(Exchange::Tiingo),
+      "api.twelvedata.com" => Some(Exchange::Twelve),
+      "query1.finance.yahoo.com" => Some(Exchange::Yahoo),
+      _ => None
+    }
+  }
+
+  /// Label Convention
+  /// Convention the exchange uses to label candles in its raw api response
+  pub fn label_convention(&self) -> LabelConvention {
+    match self {
+      Exchange::Alpaca | Exchange::AlphaVantage | Exchange::Binance | Exchange::BinanceCoinM | Exchange::BinanceSpot | Exchange::BinanceUs
+        | Exchange::Bitfinex | Exchange::ByBit | Exchange::ByBitInverse | Exchange::Coinbase | Exchange::Dydx | Exchange::GateIo | Exchange::Hyperliquid | Exchange::Kraken | Exchange::KuCoin | Exchange::Okx
+        | Exchange::Tiingo | Exchange::Twelve | Exchange::Yahoo => LabelConvention::OpenTime
+    }
+  }
+
+  /// Is Inverse
+  /// Whether contracts are coin-margined with a fixed USD face value (see inverse module)
+  pub fn is_inverse(&self) -> bool {
+    matches!(self, Exchange::BinanceCoinM | Exchange::ByBitInverse)
+  }
+
   /// Default Ticker Assets
   pub fn default_assets(&self) -> (String, String) {
     let asset_1: String = match self {
-      Exchange::Binance | Exchange::BinanceUs | Exchange::ByBit => "BTCUSDT".to_string(),
+      Exchange::Alpaca => "SPY".to_string(),
+      Exchange::AlphaVantage => "EUR/USD".to_string(),
+      Exchange::Binance | Exchange::BinanceSpot | Exchange::BinanceUs | Exchange::ByBit => "BTCUSDT".to_string(),
+      Exchange::BinanceCoinM => "BTCUSD_PERP".to_string(),
+      Exchange::ByBitInverse => "BTCUSD".to_string(),
+      Exchange::Bitfinex => "tBTCUSD".to_string(),
       Exchange::Coinbase | Exchange::Dydx  => "BTC-USD".to_string(),
-      Exchange::Twelve  => "USD/GBP".to_string()
+      Exchange::GateIo => "BTC_USDT".to_string(),
+      Exchange::Hyperliquid => "BTC".to_string(),
+      Exchange::Kraken => "XBTUSD".to_string(),
+      Exchange::KuCoin => "BTC-USDT".to_string(),
+      Exchange::Okx => "BTC-USDT-SWAP".to_string(),
+      Exchange::Tiingo => "SPY".to_string(),
+      Exchange::Twelve  => "USD/GBP".to_string(),
+      Exchange::Yahoo => "SPY".to_string()
     };
 
     let asset_2: String = match self {
-      Exchange::Binance | Exchange::BinanceUs | Exchange::ByBit  => "ETHUSDT".to_string(),
+      Exchange::Alpaca => "QQQ".to_string(),
+      Exchange::AlphaVantage => "GBP/USD".to_string(),
+      Exchange::Binance | Exchange::BinanceSpot | Exchange::BinanceUs | Exchange::ByBit  => "ETHUSDT".to_string(),
+      Exchange::BinanceCoinM => "ETHUSD_PERP".to_string(),
+      Exchange::ByBitInverse => "ETHUSD".to_string(),
+      Exchange::Bitfinex => "tETHUSD".to_string(),
       Exchange::Coinbase | Exchange::Dydx  => "ETH-USD".to_string(),
-      Exchange::Twelve  => "USD/GBP".to_string()
+      Exchange::GateIo => "ETH_USDT".to_string(),
+      Exchange::Hyperliquid => "ETH".to_string(),
+      Exchange::Kraken => "ETHUSD".to_string(),
+      Exchange::KuCoin => "ETH-USDT".to_string(),
+      Exchange::Okx => "ETH-USDT-SWAP".to_string(),
+      Exchange::Tiingo => "QQQ".to_string(),
+      Exchange::Twelve  => "USD/GBP".to_string(),
+      Exchange::Yahoo => "QQQ".to_string()
     };
 
     (asset_1, asset_2)
   }
 
   /// Default Interval Period
+  /// Yahoo serves daily and weekly bars only
   pub fn default_interval_period(&self) -> IntervalPeriod {
-    IntervalPeriod::Hour(1, 700)
+    match self {
+      Exchange::Yahoo => IntervalPeriod::Day(1, 700),
+      _ => IntervalPeriod::Hour(1, 700)
+    }
   }
 
   /// Available Assets
@@ -165,7 +328,24 @@ impl Exchange {
     let mut intervals_hm: HashMap<&str, IntervalPeriod> = HashMap::new();
 
     match self {
-      Exchange::Binance | Exchange::BinanceUs => {
+      Exchange::Alpaca => {
+        intervals_hm.insert("5min", IntervalPeriod::Min(5, default_period));
+        intervals_hm.insert("15min", IntervalPeriod::Min(15, default_period));
+        intervals_hm.insert("30min", IntervalPeriod::Min(30, default_period));
+        intervals_hm.insert("1hour", IntervalPeriod::Hour(1, default_period));
+        intervals_hm.insert("2hour", IntervalPeriod::Hour(2, default_period));
+        intervals_hm.insert("4hour", IntervalPeriod::Hour(4, default_period));
+        intervals_hm.insert("12hour", IntervalPeriod::Hour(12, default_period));
+        intervals_hm.insert("daily", IntervalPeriod::Day(1, default_period));
+      },
+      Exchange::AlphaVantage => {
+        intervals_hm.insert("5min", IntervalPeriod::Min(5, default_period));
+        intervals_hm.insert("15min", IntervalPeriod::Min(15, default_period));
+        intervals_hm.insert("30min", IntervalPeriod::Min(30, default_period));
+        intervals_hm.insert("60min", IntervalPeriod::Hour(1, default_period));
+        intervals_hm.insert("daily", IntervalPeriod::Day(1, default_period));
+      },
+      Exchange::Binance | Exchange::BinanceCoinM | Exchange::BinanceSpot | Exchange::BinanceUs => {
         intervals_hm.insert("5min", IntervalPeriod::Min(5, default_period));
         intervals_hm.insert("15min", IntervalPeriod::Min(15, default_period));
         intervals_hm.insert("30min", IntervalPeriod::Min(30, default_period));
@@ -177,7 +357,16 @@ impl Exchange {
         intervals_hm.insert("12hour", IntervalPeriod::Hour(12, default_period));
         intervals_hm.insert("1day", IntervalPeriod::Day(1, default_period));
       },
-      Exchange::ByBit => {
+      Exchange::Bitfinex => {
+        intervals_hm.insert("5m", IntervalPeriod::Min(5, default_period));
+        intervals_hm.insert("15m", IntervalPeriod::Min(15, default_period));
+        intervals_hm.insert("30m", IntervalPeriod::Min(30, default_period));
+        intervals_hm.insert("1h", IntervalPeriod::Hour(1, default_period));
+        intervals_hm.insert("6h", IntervalPeriod::Hour(6, default_period));
+        intervals_hm.insert("12h", IntervalPeriod::Hour(12, default_period));
+        intervals_hm.insert("1D", IntervalPeriod::Day(1, default_period));
+      },
+      Exchange::ByBit | Exchange::ByBitInverse => {
         intervals_hm.insert("5", IntervalPeriod::Min(5, default_period));
         intervals_hm.insert("15", IntervalPeriod::Min(15, default_period));
         intervals_hm.insert("30", IntervalPeriod::Min(30, default_period));
@@ -203,6 +392,59 @@ impl Exchange {
         intervals_hm.insert("4HOURS", IntervalPeriod::Hour(4, default_period));
         intervals_hm.insert("1DAY", IntervalPeriod::Day(1, default_period));
       },
+      Exchange::GateIo => {
+        intervals_hm.insert("5m", IntervalPeriod::Min(5, default_period));
+        intervals_hm.insert("15m", IntervalPeriod::Min(15, default_period));
+        intervals_hm.insert("30m", IntervalPeriod::Min(30, default_period));
+        intervals_hm.insert("1h", IntervalPeriod::Hour(1, default_period));
+        intervals_hm.insert("4h", IntervalPeriod::Hour(4, default_period));
+        intervals_hm.insert("8h", IntervalPeriod::Hour(8, default_period));
+        intervals_hm.insert("1d", IntervalPeriod::Day(1, default_period));
+      },
+      Exchange::Hyperliquid => {
+        intervals_hm.insert("5m", IntervalPeriod::Min(5, default_period));
+        intervals_hm.insert("15m", IntervalPeriod::Min(15, default_period));
+        intervals_hm.insert("30m", IntervalPeriod::Min(30, default_period));
+        intervals_hm.insert("1h", IntervalPeriod::Hour(1, default_period));
+        intervals_hm.insert("2h", IntervalPeriod::Hour(2, default_period));
+        intervals_hm.insert("4h", IntervalPeriod::Hour(4, default_period));
+        intervals_hm.insert("8h", IntervalPeriod::Hour(8, default_period));
+        intervals_hm.insert("12h", IntervalPeriod::Hour(12, default_period));
+        intervals_hm.insert("1d", IntervalPeriod::Day(1, default_period));
+      },
+      Exchange::Kraken => {
+        intervals_hm.insert("5", IntervalPeriod::Min(5, default_period));
+        intervals_hm.insert("15", IntervalPeriod::Min(15, default_period));
+        intervals_hm.insert("30", IntervalPeriod::Min(30, default_period));
+        intervals_hm.insert("60", IntervalPeriod::Hour(1, default_period));
+        intervals_hm.insert("240", IntervalPeriod::Hour(4, default_period));
+        intervals_hm.insert("1440", IntervalPeriod::Day(1, default_period));
+      },
+      Exchange::KuCoin => {
+        intervals_hm.insert("5min", IntervalPeriod::Min(5, default_period));
+        intervals_hm.insert("15min", IntervalPeriod::Min(15, default_period));
+        intervals_hm.insert("30min", IntervalPeriod::Min(30, default_period));
+        intervals_hm.insert("1hour", IntervalPeriod::Hour(1, default_period));
+        intervals_hm.insert("4hour", IntervalPeriod::Hour(4, default_period));
+        intervals_hm.insert("1day", IntervalPeriod::Day(1, default_period));
+      },
+      Exchange::Okx => {
+        intervals_hm.insert("5m", IntervalPeriod::Min(5, default_period));
+        intervals_hm.insert("15m", IntervalPeriod::Min(15, default_period));
+        intervals_hm.insert("30m", IntervalPeriod::Min(30, default_period));
+        intervals_hm.insert("1H", IntervalPeriod::Hour(1, default_period));
+        intervals_hm.insert("4H", IntervalPeriod::Hour(4, default_period));
+        intervals_hm.insert("1Dutc", IntervalPeriod::Day(1, default_period));
+      },
+      Exchange::Tiingo => {
+        intervals_hm.insert("5min", IntervalPeriod::Min(5, default_period));
+        intervals_hm.insert("15min", IntervalPeriod::Min(15, default_period));
+        intervals_hm.insert("30min", IntervalPeriod::Min(30, default_period));
+        intervals_hm.insert("1hour", IntervalPeriod::Hour(1, default_period));
+        intervals_hm.insert("2hour", IntervalPeriod::Hour(2, default_period));
+        intervals_hm.insert("4hour", IntervalPeriod::Hour(4, default_period));
+        intervals_hm.insert("daily", IntervalPeriod::Day(1, default_period));
+      },
       Exchange::Twelve => {
         intervals_hm.insert("5m", IntervalPeriod::Min(5, default_period));
         intervals_hm.insert("15min", IntervalPeriod::Min(15, default_period));
@@ -211,6 +453,10 @@ impl Exchange {
         intervals_hm.insert("2h", IntervalPeriod::Hour(2, default_period));
         intervals_hm.insert("4h", IntervalPeriod::Hour(4, default_period));
         intervals_hm.insert("1day", IntervalPeriod::Day(1, default_period));
+      },
+      Exchange::Yahoo => {
+        intervals_hm.insert("1d", IntervalPeriod::Day(1, default_period));
+        intervals_hm.insert("1wk", IntervalPeriod::Day(7, default_period));
       }
     };
     intervals_hm
@@ -218,23 +464,24 @@ impl Exchange {
 
 }
 
+/// Dydx Candle Obj
+/// v4 indexer perpetual market candle (startedAt is the open time)
 #[derive(Debug, Deserialize)]
 #[allow(non_snake_case)]
 pub struct DydxCandleObj {
-  pub baseTokenVolume: String,
-  pub close: String,
-  pub high: String,
+  pub startedAt: String,
+  pub ticker: String,
+  pub resolution: String,
   pub low: String,
-  pub market: String,
+  pub high: String,
   pub open: String,
-  pub resolution: String,
-  pub startedAt: String,
-  pub startingOpenInterest: String,
-  pub trades: String,
-  pub updatedAt: String,
-  pub usdVolume: String,
+  pub close: String,
+  pub baseTokenVolume: String,
+  pub usdVolume: String
 }
 
+/// Dydx Candle
+/// v4 indexer candles response (newest first)
 #[derive(Debug, Deserialize)]
 pub struct DydxCandle {
   pub candles: Vec<DydxCandleObj>
@@ -244,11 +491,199 @@ pub struct DydxCandle {
   Price Controller Models
 */
 
+/// Data Tolerance
+/// Strict fails the whole fetch on any failed call (required for backtesting)
+/// Partial accepts missing pages provided the completeness ratio is met (suitable for screening)
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default, TS)]
+#[ts(export)]
+pub enum DataTolerance {
+  #[default]
+  Strict,
+  Partial(f64) // minimum received / requested bars ratio
+}
+
+/// Missing Data Policy
+/// Applied after joining both legs on timestamp when one exchange omits a candle the other has
+/// Strict errors, ForwardFill repeats the last known price and DropBar removes the bar from both legs
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default, TS)]
+#[ts(export)]
+pub enum MissingDataPolicy {
+  Strict,
+  ForwardFill,
+  #[default]
+  DropBar
+}
+
+/// Gap Kind
+/// Closure gaps span only hours the market was shut (weekends, holidays, overnight) and hold no missing bars
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default, TS)]
+#[ts(export)]
+pub enum GapKind {
+  #[default]
+  Missing,
+  Closure
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct LabelGap {
+  pub from_label: u64,
+  pub to_label: u64,
+  pub missing_bars: u32, // bars absent while the market was open
+  #[serde(default)]
+  pub kind: GapKind
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default, TS)]
+#[ts(export)]
+pub struct FetchReport {
+  pub requested_bars: u32,
+  pub received_bars: u32,
+  pub gaps: Vec<LabelGap>,
+  pub failed_calls: u32
+}
+
+impl FetchReport {
+
+  /// Completeness
+  /// Ratio of received to requested bars (capped at 1)
+  pub fn completeness(&self) -> f64 {
+    if self.requested_bars == 0 { return 1.0 }
+    (self.received_bars as f64 / self.requested_bars as f64).min(1.0)
+  }
+
+  /// Is Complete
+  /// True if every call in the fetch succeeded
+  pub fn is_complete(&self) -> bool {
+    self.failed_calls == 0
+  }
+
+  /// Missing Gaps
+  /// Gaps holding missing data (expected market closures excluded)
+  pub fn missing_gaps(&self) -> Vec<&LabelGap> {
+    self.gaps.iter().filter(|gap| gap.kind == GapKind::Missing).collect()
+  }
+
+  /// Combine
+  /// Merges the reports of two legs into a single pair report
+  pub fn combine(&self, other: &Self) -> Self {
+    let mut gaps: Vec<LabelGap> = self.gaps.clone();
+    gaps.extend(other.gaps.iter().cloned());
+    Self {
+      requested_bars: self.requested_bars + other.requested_bars,
+      received_bars: self.received_bars + other.received_bars,
+      gaps,
+      failed_calls: self.failed_calls + other.failed_calls
+    }
+  }
+}
+
+/// Find Label Gaps
+/// Identifies jumps in labels greater than one interval (assumes continuous trading, see MarketCalendar for closures)
+pub fn find_label_gaps(labels: &[u64], interval: &IntervalPeriod) -> Vec<LabelGap> {
+  let step: u64 = interval.interval_seconds();
+  if step == 0 { return vec![] }
+  labels.windows(2)
+    .filter(|w| w[1] > w[0] + step)
+    .map(|w| LabelGap { from_label: w[0], to_label: w[1], missing_bars: ((w[1] - w[0]) / step - 1) as u32, kind: GapKind::Missing })
+    .collect()
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, TS)]
 #[ts(export)]
 pub struct HistoricalPrices {
   pub prices: Vec<f64>,
-  pub labels: Vec<u64>
+  pub labels: Vec<u64>,
+  #[serde(default)]
+  pub label_convention: LabelConvention,
+  #[serde(default)]
+  pub fetch_report: Option<FetchReport>
+}
+
+impl HistoricalPrices {
+
+  /// To Label Convention
+  /// Shifts labels by one bar so they follow the target convention
+  pub fn to_label_convention(&self, target: LabelConvention, interval: &IntervalPeriod) -> Self {
+    let labels: Vec<u64> = convert_label_convention(&self.labels, self.label_convention, target, interval);
+    Self { prices: self.prices.clone(), labels, label_convention: target, fetch_report: self.fetch_report.clone() }
+  }
+}
+
+/// Convert Label Convention
+/// Shifts labels by one bar when moving between open and close time conventions
+fn convert_label_convention(labels: &[u64], from: LabelConvention, target: LabelConvention, interval: &IntervalPeriod) -> Vec<u64> {
+  let shift: u64 = interval.interval_seconds();
+  match (from, target) {
+    (LabelConvention::OpenTime, LabelConvention::CloseTime) => labels.iter().map(|l| l + shift).collect(),
+    (LabelConvention::CloseTime, LabelConvention::OpenTime) => labels.iter().map(|l| l.saturating_sub(shift)).collect(),
+    _ => labels.to_vec()
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct OhlcvSeries {
+  pub labels: Vec<u64>,
+  pub open: Vec<f64>,
+  pub high: Vec<f64>,
+  pub low: Vec<f64>,
+  pub close: Vec<f64>,
+  pub volume: Vec<f64>,
+  #[serde(default)]
+  pub label_convention: LabelConvention
+}
+
+impl OhlcvSeries {
+
+  /// Ensure Aligned
+  /// Errors if any field length differs from the labels length
+  pub fn ensure_aligned(&self) -> Result<(), SmartError> {
+    let len: usize = self.labels.len();
+    let lens: [usize; 5] = [self.open.len(), self.high.len(), self.low.len(), self.close.len(), self.volume.len()];
+    if lens.iter().any(|l| *l != len) {
+      let e: String = format!("OHLCV lengths do not match labels length ({}): {:?}", len, lens);
+      return Err(SmartError::RuntimeCheck(e));
+    }
+    Ok(())
+  }
+
+  /// To Label Convention
+  /// Shifts labels by one bar so they follow the target convention
+  pub fn to_label_convention(&self, target: LabelConvention, interval: &IntervalPeriod) -> Self {
+    let labels: Vec<u64> = convert_label_convention(&self.labels, self.label_convention, target, interval);
+    Self { labels, label_convention: target, ..self.clone() }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub enum EventKind {
+  Listing,
+  Delisting,
+  Fork,
+  Earnings,
+  TokenUnlock,
+  Other
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct SeriesEvent {
+  pub label: u64, // unix seconds
+  pub kind: EventKind,
+  pub name: String,
+  pub exclude_before_secs: u64, // bars within this window are excluded from backtests
+  pub exclude_after_secs: u64
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct EventMarker {
+  pub index: usize, // first bar at or after the event
+  pub label: u64,
+  pub kind: EventKind,
+  pub name: String
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, TS)]
@@ -256,5 +691,95 @@ pub struct HistoricalPrices {
 pub struct PairPrices {
   pub series_0: Vec<f64>,
   pub series_1: Vec<f64>,
-  pub labels: Vec<u64> 
+  pub labels: Vec<u64>,
+  #[serde(default)]
+  pub label_convention: LabelConvention,
+  #[serde(default)]
+  pub fetch_report: Option<FetchReport>,
+  #[serde(default)]
+  pub events: Vec<SeriesEvent>,
+  #[serde(default)]
+  pub source: Option<Exchange> // exchange which served the prices
+}
+
+impl PairPrices {
+
+  /// Ensure Complete
+  /// Errors if the prices were fetched with failed calls (partial data)
+  pub fn ensure_complete(&self) -> Result<(), SmartError> {
+    if let Some(report) = &self.fetch_report {
+      if !report.is_complete() {
+        let e: String = format!("Partial price data ({} failed calls) cannot be backtested", report.failed_calls);
+        return Err(SmartError::RuntimeCheck(e));
+      }
+    }
+    Ok(())
+  }
+
+  /// Resample
+  /// Downsamples to a coarser interval using the last close within each bucket
+  /// Buckets are aligned to unix time (so daily bars start at UTC midnight) and only the last period bars are kept
+  pub fn resample(&self, interval: &IntervalPeriod) -> Self {
+    let step: u64 = interval.interval_seconds();
+    let mut series_0: Vec<f64> = vec![];
+    let mut series_1: Vec<f64> = vec![];
+    let mut labels: Vec<u64> = vec![];
+
+    for i in 0..self.labels.len() {
+      let bucket: u64 = match self.label_convention {
+        LabelConvention::OpenTime => self.labels[i] / step * step,
+        LabelConvention::CloseTime => self.labels[i].div_ceil(step) * step
+      };
+      if labels.last() == Some(&bucket) {
+        let last: usize = labels.len() - 1;
+        series_0[last] = self.series_0[i];
+        series_1[last] = self.series_1[i];
+      } else {
+        labels.push(bucket);
+        series_0.push(self.series_0[i]);
+        series_1.push(self.series_1[i]);
+      }
+    }
+
+    let skip: usize = labels.len().saturating_sub(interval.period() as usize);
+    Self {
+      series_0: series_0.split_off(skip),
+      series_1: series_1.split_off(skip),
+      labels: labels.split_off(skip),
+      label_convention: self.label_convention,
+      fetch_report: self.fetch_report.clone(),
+      events: self.events.clone(),
+      source: self.source.clone()
+    }
+  }
+
+  /// With Events
+  /// Attaches labeled events (listings, forks, earnings etc) to the series
+  pub fn with_events(mut self, events: Vec<SeriesEvent>) -> Self {
+    self.events = events;
+    self.events.sort_by_key(|e| e.label);
+    self
+  }
+
+  /// Event Markers
+  /// Events falling within the series mapped to the index of the first bar at or after each event (for chart data)
+  pub fn event_markers(&self) -> Vec<EventMarker> {
+    self.events.iter()
+      .filter_map(|e| {
+        let index: usize = self.labels.partition_point(|l| *l < e.label);
+        if index >= self.labels.len() { return None }
+        Some(EventMarker { index, label: e.label, kind: e.kind.clone(), name: e.name.clone() })
+      })
+      .collect()
+  }
+
+  /// Excluded Bars
+  /// True for each bar falling within the exclusion window of any event
+  pub fn excluded_bars(&self) -> Vec<bool> {
+    self.labels.iter()
+      .map(|l| self.events.iter().any(|e| {
+        *l >= e.label.saturating_sub(e.exclude_before_secs) && *l <= e.label.saturating_add(e.exclude_after_secs)
+      }))
+      .collect()
+  }
 }
\ No newline at end of file
@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use super::models::{Exchange, HistoricalPrices, IntervalPeriod, LabelConvention};
+use super::utils::api_request;
+
+/*
+  Open Interest
+  Perpetual open interest history, aligned to candle labels for use as a regime filter or ML feature
+  Snapshots are taken at the finest exchange period not longer than the candle interval
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default, TS)]
+#[ts(export)]
+pub struct OpenInterest {
+  pub labels: Vec<u64>, // snapshot times (secs)
+  pub values: Vec<f64> // contracts outstanding (base asset for linear perpetuals)
+}
+
+impl OpenInterest {
+
+  /// From Snapshots
+  /// Structures (time, value) snapshots sorted by time with duplicates removed
+  pub fn from_snapshots(mut snapshots: Vec<(u64, f64)>) -> Self {
+    snapshots.sort_by_key(|s| s.0);
+    snapshots.dedup_by_key(|s| s.0);
+    let (labels, values) = snapshots.into_iter().unzip();
+    Self { labels, values }
+  }
+
+  /// Values At Labels
+  /// Latest open interest known at each bar close (NaN before the first snapshot), usable without lookahead
+  pub fn values_at_labels(&self, labels: &[u64], label_convention: LabelConvention, interval: &IntervalPeriod) -> Vec<f64> {
+    let offset: u64 = if label_convention == LabelConvention::OpenTime { interval.interval_seconds() } else { 0 };
+    labels.iter()
+      .map(|label| {
+        let idx: usize = self.labels.partition_point(|t| *t <= label + offset);
+        if idx == 0 { f64::NAN } else { self.values[idx - 1] }
+      })
+      .collect()
+  }
+
+  /// Change At Labels
+  /// Fractional change in open interest over each bar (0.0 where either side is unknown)
+  pub fn change_at_labels(&self, labels: &[u64], label_convention: LabelConvention, interval: &IntervalPeriod) -> Vec<f64> {
+    let values: Vec<f64> = self.values_at_labels(labels, label_convention, interval);
+    let mut changes: Vec<f64> = vec![0.0; values.len()];
+    for i in 1..values.len() {
+      let (prev, curr) = (values[i - 1], values[i]);
+      if prev.is_finite() && curr.is_finite() && prev > 0.0 {
+        changes[i] = curr / prev - 1.0;
+      }
+    }
+    changes
+  }
+}
+
+/// Open Interest Period
+/// Finest exchange period not longer than the interval (None if the exchange has no open interest history)
+fn open_interest_period(exchange: &Exchange, interval: &IntervalPeriod) -> Option<&'static str> {
+  let periods: &[(u64, &'static str)] = match exchange {
+    Exchange::Binance => &[(300, "5m"), (900, "15m"), (1800, "30m"), (3600, "1h"), (7200, "2h"), (14400, "4h"), (21600, "6h"), (43200, "12h"), (86400, "1d")],
+    Exchange::ByBit | Exchange::ByBitInverse => &[(300, "5min"), (900, "15min"), (1800, "30min"), (3600, "1h"), (14400, "4h"), (86400, "1d")],
+    _ => return None
+  };
+  let seconds: u64 = interval.interval_seconds();
+  periods.iter().rev().find(|(secs, _)| *secs <= seconds).or(periods.first()).map(|(_, p)| *p)
+}
+
+/// Get open interest url
+/// Retrieves the open interest history url for a given exchange (None if not supported)
+fn get_open_interest_url(exchange: &Exchange, symbol: &str, interval: &IntervalPeriod) -> Option<String> {
+  let period: &str = open_interest_period(exchange, interval)?;
+  let url: String = match exchange {
+    Exchange::Binance => {
+      let limit: u32 = interval.period().clamp(1, 500);
+      format!("https://fapi.binance.com/futures/data/openInterestHist?symbol={}&period={}&limit={}", symbol, period, limit)
+    },
+    Exchange::ByBit | Exchange::ByBitInverse => {
+      let category: &str = if exchange == &Exchange::ByBit { "linear" } else { "inverse" };
+      let limit: u32 = interval.period().clamp(1, 200);
+      format!("https://api.bybit.com/v5/market/open-interest?category={}&symbol={}&intervalTime={}&limit={}", category, symbol, period, limit)
+    },
+    _ => return None
+  };
+  Some(url)
+}
+
+/// Extract Open Interest Binance
+/// Takes Binance open interest history and returns snapshots
+fn extract_open_interest_binance(data_obj: &serde_json::Value) -> Vec<(u64, f64)> {
+  data_obj.as_array().map(|items| items.iter().filter_map(|item| {
+    let time: u64 = item.get("timestamp")?.as_u64()? / 1000;
+    let value: f64 = item.get("sumOpenInterest")?.as_str()?.parse().ok()?;
+    Some((time, value))
+  }).collect()).unwrap_or_default()
+}
+
+/// Extract Open Interest ByBit
+/// Takes ByBit open interest history and returns snapshots
+fn extract_open_interest_bybit(data_obj: &serde_json::Value) -> Vec<(u64, f64)> {
+  data_obj.get("result").and_then(|r| r.get("list")).and_then(|l| l.as_array())
+    .map(|items| items.iter().filter_map(|item| {
+      let time: u64 = item.get("timestamp")?.as_str()?.parse::<u64>().ok()? / 1000;
+      let value: f64 = item.get("openInterest")?.as_str()?.parse().ok()?;
+      Some((time, value))
+    }).collect())
+    .unwrap_or_default()
+}
+
+/// Parse Open Interest
+/// Structures an open interest history response for the exchange
+pub fn parse_open_interest(exchange: &Exchange, data_obj: &serde_json::Value) -> OpenInterest {
+  let snapshots: Vec<(u64, f64)> = match exchange {
+    Exchange::Binance => extract_open_interest_binance(data_obj),
+    Exchange::ByBit | Exchange::ByBitInverse => extract_open_interest_bybit(data_obj),
+    _ => vec![]
+  };
+  OpenInterest::from_snapshots(snapshots)
+}
+
+/// Request Open Interest
+/// Requests the most recent open interest history for a perpetual
+pub async fn request_open_interest(exchange: &Exchange, symbol: &str, interval: &IntervalPeriod) -> Result<OpenInterest, SmartError> {
+  let Some(request_url) = get_open_interest_url(exchange, symbol, interval) else {
+    return Err(SmartError::RuntimeCheck(format!("Open interest not supported for exchange: {}", exchange.as_string())))
+  };
+
+  // Make request
+  let res_data: reqwest::Response = api_request(&request_url).await?;
+
+  // Guard: Ensure status code
+  if res_data.status() != 200 {
+    let e: String = format!("Failed to extract data: {:?}", res_data.text().await);
+    return Err(SmartError::APIResponseStatus(e));
+  }
+
+  let data_obj: serde_json::Value = res_data.json().await?;
+  Ok(parse_open_interest(exchange, &data_obj))
+}
+
+/// Request Open Interest For Prices
+/// Open interest at each label of fetched prices
+pub async fn request_open_interest_for_prices(
+  exchange: &Exchange,
+  symbol: &str,
+  interval: &IntervalPeriod,
+  prices: &HistoricalPrices
+) -> Result<Vec<f64>, SmartError> {
+  let open_interest: OpenInterest = request_open_interest(exchange, symbol, interval).await?;
+  Ok(open_interest.values_at_labels(&prices.labels, prices.label_convention, interval))
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_parses_and_aligns_open_interest() {
+    let bybit: serde_json::Value = serde_json::json!({ "retCode": 0, "result": { "list": [
+      { "openInterest": "1200", "timestamp": "7200000" },
+      { "openInterest": "1000", "timestamp": "3600000" }
+    ]}});
+    let open_interest: OpenInterest = parse_open_interest(&Exchange::ByBit, &bybit);
+    assert_eq!(open_interest.labels, vec![3_600, 7_200]);
+
+    // Hourly bars labeled by open time, bar 0 closes at the first snapshot
+    let labels: Vec<u64> = (0..4).map(|h| h * 3600).collect();
+    let interval: IntervalPeriod = IntervalPeriod::Hour(1, 4);
+    let values: Vec<f64> = open_interest.values_at_labels(&labels, LabelConvention::CloseTime, &interval);
+    assert!(values[0].is_nan());
+    assert_eq!(&values[1..], &[1000.0, 1200.0, 1200.0]);
+    assert_eq!(open_interest.values_at_labels(&labels, LabelConvention::OpenTime, &interval)[0], 1000.0);
+
+    let changes: Vec<f64> = open_interest.change_at_labels(&labels, LabelConvention::CloseTime, &interval);
+    assert_eq!(changes[1], 0.0);
+    assert!((changes[2] - 0.2).abs() < 1e-12);
+    assert_eq!(changes[3], 0.0);
+
+    let binance: serde_json::Value = serde_json::json!([{ "symbol": "BTCUSDT", "sumOpenInterest": "8000.5", "sumOpenInterestValue": "1", "timestamp": 3600000u64 }]);
+    assert_eq!(parse_open_interest(&Exchange::Binance, &binance).values, vec![8000.5]);
+
+    // Periods fall back to the finest available not longer than the interval
+    assert_eq!(open_interest_period(&Exchange::Binance, &IntervalPeriod::Hour(3, 10)), Some("2h"));
+    assert_eq!(open_interest_period(&Exchange::ByBit, &IntervalPeriod::Min(1, 10)), Some("5min"));
+    assert!(request_open_interest(&Exchange::Coinbase, "BTC-USD", &interval).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn tests_request_open_interest_binance() {
+    let open_interest: OpenInterest = request_open_interest(&Exchange::Binance, "BTCUSDT", &IntervalPeriod::Hour(1, 100)).await.unwrap();
+    assert!(!open_interest.values.is_empty());
+  }
+}
@@ -0,0 +1,189 @@
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use ::parquet::data_type::{DoubleType, Int64Type};
+use ::parquet::file::properties::WriterProperties;
+use ::parquet::file::reader::{FileReader, SerializedFileReader};
+use ::parquet::file::writer::SerializedFileWriter;
+use ::parquet::format::KeyValue;
+use ::parquet::record::{Row, RowAccessor};
+use ::parquet::schema::parser::parse_message_type;
+use ::parquet::schema::types::Type;
+
+use crate::SmartError;
+use super::models::{Exchange, HistoricalPrices, LabelConvention, PairPrices};
+
+/*
+  Parquet Files
+  Columnar save and load of fetched prices (feature "parquet"), so multi year datasets fetched once reload in
+  milliseconds inside research loops. Labels and prices are stored as columns, the label convention and source
+  exchange as file metadata (fetch reports and events are not kept)
+*/
+
+const HISTORICAL_SCHEMA: &str = "message historical_prices { REQUIRED INT64 label; REQUIRED DOUBLE price; }";
+const PAIR_SCHEMA: &str = "message pair_prices { REQUIRED INT64 label; REQUIRED DOUBLE series_0; REQUIRED DOUBLE series_1; }";
+
+/// Labels, price columns and file metadata read from a parquet file
+type ParquetColumns = (Vec<u64>, Vec<Vec<f64>>, Vec<KeyValue>);
+
+/// Parquet Error
+fn parquet_error(e: ::parquet::errors::ParquetError) -> SmartError {
+  SmartError::RuntimeCheck(format!("Parquet error: {}", e))
+}
+
+/// Write Columns
+/// Writes a label column followed by price columns as one row group, with json encoded metadata
+fn write_columns(path: &Path, schema: &str, labels: &[u64], columns: &[&[f64]], metadata: Vec<KeyValue>) -> Result<(), SmartError> {
+  let schema: Arc<Type> = Arc::new(parse_message_type(schema).map_err(parquet_error)?);
+  let props: Arc<WriterProperties> = Arc::new(WriterProperties::builder().set_key_value_metadata(Some(metadata)).build());
+  let mut writer: SerializedFileWriter<File> = SerializedFileWriter::new(File::create(path)?, schema, props).map_err(parquet_error)?;
+
+  let labels: Vec<i64> = labels.iter().map(|l| *l as i64).collect();
+  let mut row_group = writer.next_row_group().map_err(parquet_error)?;
+  let mut idx: usize = 0;
+  while let Some(mut column) = row_group.next_column().map_err(parquet_error)? {
+    match idx {
+      0 => column.typed::<Int64Type>().write_batch(&labels, None, None).map_err(parquet_error)?,
+      _ => column.typed::<DoubleType>().write_batch(columns[idx - 1], None, None).map_err(parquet_error)?
+    };
+    column.close().map_err(parquet_error)?;
+    idx += 1;
+  }
+  row_group.close().map_err(parquet_error)?;
+  writer.close().map_err(parquet_error)?;
+  Ok(())
+}
+
+/// Read Columns
+/// Reads the label column and price_count price columns along with the file metadata
+fn read_columns(path: &Path, price_count: usize) -> Result<ParquetColumns, SmartError> {
+  let reader: SerializedFileReader<File> = SerializedFileReader::new(File::open(path)?).map_err(parquet_error)?;
+  let metadata: Vec<KeyValue> = reader.metadata().file_metadata().key_value_metadata().cloned().unwrap_or_default();
+
+  // Guard: Ensure the file has the expected columns
+  let column_count: usize = reader.metadata().file_metadata().schema_descr().num_columns();
+  if column_count != price_count + 1 {
+    return Err(SmartError::RuntimeCheck(format!("Parquet file has {} columns, expected {}", column_count, price_count + 1)));
+  }
+
+  let mut labels: Vec<u64> = vec![];
+  let mut columns: Vec<Vec<f64>> = vec![vec![]; price_count];
+  for row in reader.get_row_iter(None).map_err(parquet_error)? {
+    let row: Row = row.map_err(parquet_error)?;
+    labels.push(row.get_long(0).map_err(parquet_error)? as u64);
+    for (i, column) in columns.iter_mut().enumerate() {
+      column.push(row.get_double(i + 1).map_err(parquet_error)?);
+    }
+  }
+  Ok((labels, columns, metadata))
+}
+
+/// Metadata Value
+/// Decodes a json metadata value if present
+fn metadata_value<T: serde::de::DeserializeOwned>(metadata: &[KeyValue], key: &str) -> Option<T> {
+  let entry: &KeyValue = metadata.iter().find(|kv| kv.key == key)?;
+  serde_json::from_str(entry.value.as_ref()?).ok()
+}
+
+/// Metadata Entry
+fn metadata_entry<T: serde::Serialize>(key: &str, value: &T) -> Result<KeyValue, SmartError> {
+  Ok(KeyValue::new(key.to_string(), serde_json::to_string(value)?))
+}
+
+impl HistoricalPrices {
+
+  /// To Parquet
+  /// Saves labels and prices as a parquet file
+  pub fn to_parquet(&self, path: impl AsRef<Path>) -> Result<(), SmartError> {
+
+    // Guard: Ensure labels and prices line up
+    if self.labels.len() != self.prices.len() {
+      return Err(SmartError::RuntimeCheck(format!("Labels ({}) and prices ({}) differ in length", self.labels.len(), self.prices.len())));
+    }
+
+    let metadata: Vec<KeyValue> = vec![metadata_entry("label_convention", &self.label_convention)?];
+    write_columns(path.as_ref(), HISTORICAL_SCHEMA, &self.labels, &[&self.prices], metadata)
+  }
+
+  /// From Parquet
+  /// Loads prices saved with to_parquet
+  pub fn from_parquet(path: impl AsRef<Path>) -> Result<Self, SmartError> {
+    let (labels, mut columns, metadata) = read_columns(path.as_ref(), 1)?;
+    Ok(Self {
+      prices: columns.remove(0),
+      labels,
+      label_convention: metadata_value(&metadata, "label_convention").unwrap_or_default(),
+      fetch_report: None
+    })
+  }
+}
+
+impl PairPrices {
+
+  /// To Parquet
+  /// Saves labels and both series as a parquet file
+  pub fn to_parquet(&self, path: impl AsRef<Path>) -> Result<(), SmartError> {
+
+    // Guard: Ensure labels and both series line up
+    if self.series_0.len() != self.labels.len() || self.series_1.len() != self.labels.len() {
+      return Err(SmartError::RuntimeCheck(format!(
+        "Labels ({}) and series ({}, {}) differ in length", self.labels.len(), self.series_0.len(), self.series_1.len()
+      )));
+    }
+
+    let metadata: Vec<KeyValue> = vec![
+      metadata_entry("label_convention", &self.label_convention)?,
+      metadata_entry("source", &self.source)?
+    ];
+    write_columns(path.as_ref(), PAIR_SCHEMA, &self.labels, &[&self.series_0, &self.series_1], metadata)
+  }
+
+  /// From Parquet
+  /// Loads pair prices saved with to_parquet
+  pub fn from_parquet(path: impl AsRef<Path>) -> Result<Self, SmartError> {
+    let (labels, mut columns, metadata) = read_columns(path.as_ref(), 2)?;
+    let series_1: Vec<f64> = columns.remove(1);
+    let series_0: Vec<f64> = columns.remove(0);
+    let label_convention: LabelConvention = metadata_value(&metadata, "label_convention").unwrap_or_default();
+    let source: Option<Exchange> = metadata_value(&metadata, "source").unwrap_or_default();
+    Ok(Self { series_0, series_1, labels, label_convention, fetch_report: None, events: vec![], source })
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_round_trips_prices_through_parquet() {
+    let dir: std::path::PathBuf = std::env::temp_dir().join(format!("zscore_parquet_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let pair: PairPrices = PairPrices {
+      series_0: vec![100.0, 101.5, f64::NAN],
+      series_1: vec![50.0, 49.25, 48.0],
+      labels: vec![1_700_000_000, 1_700_003_600, 1_700_007_200],
+      label_convention: LabelConvention::CloseTime,
+      fetch_report: None,
+      events: vec![],
+      source: Some(Exchange::Binance)
+    };
+    pair.to_parquet(dir.join("pair.parquet")).unwrap();
+    let loaded: PairPrices = PairPrices::from_parquet(dir.join("pair.parquet")).unwrap();
+    assert_eq!(loaded.labels, pair.labels);
+    assert_eq!(&loaded.series_0[..2], &pair.series_0[..2]);
+    assert!(loaded.series_0[2].is_nan());
+    assert_eq!(loaded.series_1, pair.series_1);
+    assert_eq!((loaded.label_convention, loaded.source), (LabelConvention::CloseTime, Some(Exchange::Binance)));
+
+    let single: HistoricalPrices = HistoricalPrices { prices: vec![1.0, 2.0], labels: vec![10, 20], label_convention: LabelConvention::OpenTime, fetch_report: None };
+    single.to_parquet(dir.join("single.parquet")).unwrap();
+    assert_eq!(HistoricalPrices::from_parquet(dir.join("single.parquet")).unwrap().prices, vec![1.0, 2.0]);
+
+    // Column counts are checked
+    assert!(PairPrices::from_parquet(dir.join("single.parquet")).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+}
@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use ts_rs::TS;
+
+use crate::SmartError;
+use super::candles::CandleBuilder;
+use super::models::{Exchange, HistoricalPrices, IntervalPeriod};
+use super::times::get_world_time_utc;
+
+/*
+  Prefetch
+  Warms the candle cache for a watchlist ahead of interactive analysis
+  Symbols are fetched one at a time so the exchange rate limits (see ratelimit) are respected
+  The store is bounded: entries expire after a day and the oldest are dropped beyond the entry limit
+*/
+
+/// Prefetched entries older than this are dropped (no interval served from the store spans more than a day)
+const PREFETCH_TTL_SECS: i64 = 86_400;
+
+/// Maximum prefetched series held at once
+const PREFETCH_MAX_ENTRIES: usize = 256;
+
+type PrefetchCache = HashMap<String, (i64, HistoricalPrices)>;
+static PREFETCHED: RwLock<Option<PrefetchCache>> = RwLock::new(None);
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, TS)]
+#[ts(export)]
+pub struct PrefetchReport {
+  pub fetched: Vec<String>,
+  pub failed: Vec<(String, String)> // symbol, error
+}
+
+/// Store Prefetched
+/// Holds candles fetched at the given time under the candle request key
+/// Expired entries are evicted first, then the oldest entries while the store is full
+pub fn store_prefetched(key: String, prices: HistoricalPrices, fetched_at: i64) {
+  if let Ok(mut store) = PREFETCHED.write() {
+    let store: &mut PrefetchCache = store.get_or_insert_with(HashMap::new);
+    store.retain(|_, (stored_at, _)| fetched_at - *stored_at < PREFETCH_TTL_SECS);
+    while store.len() >= PREFETCH_MAX_ENTRIES && !store.contains_key(&key) {
+      let Some(oldest) = store.iter().min_by_key(|(_, (stored_at, _))| *stored_at).map(|(k, _)| k.clone()) else { break };
+      store.remove(&oldest);
+    }
+    store.insert(key, (fetched_at, prices));
+  }
+}
+
+/// Prefetched
+/// Cached candles for the request key if fetched during the current bar (no new bar since)
+pub fn prefetched(key: &str, now: i64, interval: &IntervalPeriod) -> Option<HistoricalPrices> {
+  let step: i64 = (interval.interval_seconds() as i64).max(1);
+  let store = PREFETCHED.read().ok()?;
+  let (fetched_at, prices) = store.as_ref()?.get(key)?;
+  if now.div_euclid(step) == fetched_at.div_euclid(step) { Some(prices.clone()) } else { None }
+}
+
+/// Clear Prefetched
+/// Drops all prefetched candles
+pub fn clear_prefetched() {
+  if let Ok(mut store) = PREFETCHED.write() { *store = None; }
+}
+
+/// Prefetch
+/// Fetches candles for each symbol in turn and caches them for subsequent candle requests
+/// Intended to be spawned in the background (e.g. tokio::spawn or wasm_bindgen_futures::spawn_local)
+/// Alpaca, AlphaVantage, Tiingo and Twelve require an api key
+pub async fn prefetch(exchange: &Exchange, symbols: &[&str], interval: &IntervalPeriod, api_key: Option<&str>) -> Result<PrefetchReport, SmartError> {
+
+  // Guard: Ensure a key is provided for keyed providers
+  let needs_key: bool = matches!(exchange, Exchange::Alpaca | Exchange::AlphaVantage | Exchange::Tiingo | Exchange::Twelve);
+  if needs_key && api_key.is_none() {
+    return Err(SmartError::RuntimeCheck(format!("Must provide an API key to prefetch from {:?}", exchange)));
+  }
+
+  let mut report: PrefetchReport = PrefetchReport::default();
+  for symbol in symbols {
+    let builder: CandleBuilder = CandleBuilder::new(symbol.to_string(), interval.clone(), exchange.clone(), api_key);
+    match builder.fetch_prices_candles().await {
+      Ok(prices) => {
+        store_prefetched(builder.cache_key(), prices, get_world_time_utc()?);
+        report.fetched.push(symbol.to_string());
+      },
+      Err(e) => report.failed.push((symbol.to_string(), e.to_string()))
+    }
+  }
+  Ok(report)
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_serves_prefetched_candles_until_next_bar() {
+    let interval: IntervalPeriod = IntervalPeriod::Hour(1, 10);
+    let builder: CandleBuilder = CandleBuilder::new("PREFETCHUSDT".to_string(), interval.clone(), Exchange::Binance, None);
+    let prices: HistoricalPrices = HistoricalPrices { prices: vec![1.0, 2.0], labels: vec![0, 3600], label_convention: Default::default(), fetch_report: None };
+    let now: i64 = get_world_time_utc().unwrap();
+    store_prefetched(builder.cache_key(), prices, now);
+
+    let bar_end: i64 = (now / 3600 + 1) * 3600;
+    assert!(prefetched(&builder.cache_key(), bar_end - 1, &interval).is_some());
+    assert!(prefetched(&builder.cache_key(), bar_end, &interval).is_none());
+
+    // Candle requests are served from the cache without network access
+    let served: HistoricalPrices = builder.fetch_prices_candles().await.unwrap();
+    assert_eq!(served.prices, vec![1.0, 2.0]);
+  }
+
+  #[tokio::test]
+  async fn it_evicts_expired_prefetched_candles() {
+    let interval: IntervalPeriod = IntervalPeriod::Day(1, 10);
+    let prices: HistoricalPrices = HistoricalPrices { prices: vec![1.0], labels: vec![0], label_convention: Default::default(), fetch_report: None };
+    let now: i64 = get_world_time_utc().unwrap();
+    store_prefetched("PREFETCH_EXPIRED".to_string(), prices.clone(), now - PREFETCH_TTL_SECS);
+
+    // A later store drops entries past their time to live
+    store_prefetched("PREFETCH_FRESH".to_string(), prices, now);
+    assert!(prefetched("PREFETCH_EXPIRED", now - PREFETCH_TTL_SECS, &interval).is_none());
+    assert!(prefetched("PREFETCH_FRESH", now, &interval).is_some());
+  }
+
+  #[tokio::test]
+  async fn it_rejects_prefetch_without_required_api_key() {
+    let result: Result<PrefetchReport, SmartError> = prefetch(&Exchange::Twelve, &["AAPL"], &IntervalPeriod::Day(1, 10), None).await;
+    assert!(matches!(result, Err(SmartError::RuntimeCheck(_))));
+  }
+}
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use super::models::{Exchange, QuotePrice};
+
+/*
+  Quote Cache
+  Short lived cache of quotes so rapid repeated UI calls and the monitor loop share one request
+  Entries are per exchange and symbol, so multi quote responses also serve single quote requests
+*/
+
+/// Default time quotes are served from the cache
+const DEFAULT_QUOTE_TTL_MS: u64 = 2_000;
+
+type QuoteStore = HashMap<String, (i64, f64)>; // key, (fetched at ms, price)
+
+static QUOTE_CACHE: RwLock<Option<QuoteStore>> = RwLock::new(None);
+static QUOTE_TTL_MS: RwLock<Option<u64>> = RwLock::new(None);
+
+/// Set Quote Cache TTL
+/// Overrides how long quotes are reused in milliseconds (0 disables caching)
+pub fn set_quote_cache_ttl(ttl_ms: u64) {
+  if let Ok(mut guard) = QUOTE_TTL_MS.write() {
+    *guard = Some(ttl_ms);
+  }
+}
+
+/// Get Quote Cache TTL
+pub fn get_quote_cache_ttl() -> u64 {
+  QUOTE_TTL_MS.read().ok().and_then(|guard| *guard).unwrap_or(DEFAULT_QUOTE_TTL_MS)
+}
+
+fn quote_key(exchange: &Exchange, symbol: &str) -> String {
+  format!("{:?}:{}", exchange, symbol)
+}
+
+/// Store Quotes
+/// Records prices fetched at the given time
+pub fn store_quotes(exchange: &Exchange, quotes: &[QuotePrice], fetched_at_ms: i64) {
+  if get_quote_cache_ttl() == 0 { return }
+  if let Ok(mut store) = QUOTE_CACHE.write() {
+    let cache: &mut QuoteStore = store.get_or_insert_with(HashMap::new);
+    cache.retain(|_, (fetched_at, _)| fetched_at_ms - *fetched_at < get_quote_cache_ttl() as i64);
+    for quote in quotes {
+      cache.insert(quote_key(exchange, &quote.symbol), (fetched_at_ms, quote.price));
+    }
+  }
+}
+
+/// Cached Quote
+/// Price for the symbol if fetched within the ttl
+pub fn cached_quote(exchange: &Exchange, symbol: &str, now_ms: i64) -> Option<f64> {
+  let ttl_ms: i64 = get_quote_cache_ttl() as i64;
+  let store = QUOTE_CACHE.read().ok()?;
+  let (fetched_at, price) = store.as_ref()?.get(&quote_key(exchange, symbol))?;
+  if now_ms - fetched_at < ttl_ms { Some(*price) } else { None }
+}
+
+/// Cached Quotes
+/// Prices for all symbols if every one is fresh (a partial hit still requires a request)
+pub fn cached_quotes(exchange: &Exchange, symbols: &[&str], now_ms: i64) -> Option<Vec<QuotePrice>> {
+  symbols.iter()
+    .map(|symbol| cached_quote(exchange, symbol, now_ms).map(|price| QuotePrice { symbol: symbol.to_string(), price }))
+    .collect()
+}
+
+/// Clear Quote Cache
+/// Drops all cached quotes
+pub fn clear_quote_cache() {
+  if let Ok(mut store) = QUOTE_CACHE.write() { *store = None; }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_serves_quotes_within_ttl() {
+    let quotes: Vec<QuotePrice> = vec![
+      QuotePrice { symbol: "QCTEST1".to_string(), price: 1.5 },
+      QuotePrice { symbol: "QCTEST2".to_string(), price: 2.5 }
+    ];
+    store_quotes(&Exchange::Coinbase, &quotes, 1_000_000);
+
+    // Multi quote responses serve single quotes and vice versa
+    assert_eq!(cached_quote(&Exchange::Coinbase, "QCTEST1", 1_000_500), Some(1.5));
+    assert_eq!(cached_quotes(&Exchange::Coinbase, &["QCTEST1", "QCTEST2"], 1_000_500).map(|q| q.len()), Some(2));
+
+    // Partial hits, other exchanges and expired entries miss
+    assert!(cached_quotes(&Exchange::Coinbase, &["QCTEST1", "QCTEST3"], 1_000_500).is_none());
+    assert!(cached_quote(&Exchange::Dydx, "QCTEST1", 1_000_500).is_none());
+    assert!(cached_quote(&Exchange::Coinbase, "QCTEST1", 1_000_000 + DEFAULT_QUOTE_TTL_MS as i64).is_none());
+  }
+}
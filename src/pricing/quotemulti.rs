@@ -1,31 +1,56 @@
 use crate::SmartError;
-use super::quotes::request_quote;
+use super::quotes::{alpaca_quote_path, alpha_vantage_quote_query, parse_quote, request_quote, tiingo_quote_path, tiingo_quote_prices, yahoo_symbol, HYPERLIQUID_MIDS_BODY};
+use super::translate::is_tiingo_crypto;
 use super::models::{Exchange, QuotePrice};
-use super::utils::{api_request, sleep};
+use super::schemas::{decode, kraken_pair_matches, AlpacaLatestQuotes, BinanceTicker, BitfinexTicker, ByBitResponse, ByBitTicker, DydxMarkets, GateTicker, HyperliquidMids, KrakenResponse, KrakenTicker, KuCoinAllTickers, KuCoinResponse, OkxResponse, OkxTicker, TwelvePrice};
+use super::quotecache::{cached_quotes, store_quotes};
+use super::clock::now_ms;
+use super::utils::{alpaca_auth_query, api_post, api_request, sleep};
 use serde::Deserialize;
 use std::collections::HashMap;
 
 #[derive(Debug, Deserialize)]
-struct BinanceRawQuote {
-  price: String,
-  symbol: String,
-  _time: Option<u64>,
-}
-
-#[derive(Debug, Deserialize)]
-struct PriceWrapper {
-  price: String,
+struct CoinbaseProduct {
+  base_currency: String,
+  quote_currency: String
 }
 
 /// Get multi quote url
 /// Retrieves quote url for a given exchange
 fn get_multi_quote_url(exchange: &Exchange, twelve_api_key: Option<&str>) -> String {
   match exchange {
+    Exchange::Alpaca => {
+      match twelve_api_key {
+        Some(api_key) => format!("https://data.alpaca.markets/{{quotepath}}?symbols={{symbolstring}}&{}", alpaca_auth_query(api_key)),
+        None => panic!("Must provide an API key for Alpaca provider")
+      }
+    },
+    Exchange::AlphaVantage => {
+      match twelve_api_key {
+        Some(api_key) => format!("https://www.alphavantage.co/query?{{query}}&apikey={}", api_key),
+        None => panic!("Must provide an API key for AlphaVantage provider")
+      }
+    },
     Exchange::Binance => "https://fapi.binance.com/fapi/v1/ticker/price".to_string(),
+    Exchange::BinanceCoinM => "https://dapi.binance.com/dapi/v1/ticker/price".to_string(),
+    Exchange::BinanceSpot => "https://api.binance.com/api/v3/ticker/price".to_string(),
     Exchange::BinanceUs => "https://api.binance.us/api/v3/ticker/price".to_string(),
+    Exchange::Bitfinex => "https://api-pub.bitfinex.com/v2/tickers?symbols={symbolstring}".to_string(),
     Exchange::ByBit => "https://api.bybit.com/v5/market/tickers?category=linear".to_string(),
+    Exchange::ByBitInverse => "https://api.bybit.com/v5/market/tickers?category=inverse".to_string(),
     Exchange::Coinbase => "https://api.exchange.coinbase.com/products/".to_string(),
-    Exchange::Dydx => "https://api.dydx.exchange/v3/markets".to_string(),
+    Exchange::Dydx => "https://indexer.dydx.trade/v4/perpetualMarkets".to_string(),
+    Exchange::GateIo => "https://api.gateio.ws/api/v4/futures/usdt/tickers".to_string(),
+    Exchange::Hyperliquid => "https://api.hyperliquid.xyz/info".to_string(),
+    Exchange::Kraken => "https://api.kraken.com/0/public/Ticker?pair={symbolstring}".to_string(),
+    Exchange::KuCoin => "https://api.kucoin.com/api/v1/market/allTickers".to_string(),
+    Exchange::Okx => "https://www.okx.com/api/v5/market/tickers?instType=SWAP".to_string(),
+    Exchange::Tiingo => {
+      match twelve_api_key {
+        Some(api_key) => format!("https://api.tiingo.com/{{quotepath}}?tickers={{symbolstring}}&token={}", api_key),
+        None => panic!("Must provide an API key for Tiingo provider")
+      }
+    },
     Exchange::Twelve => {
       match twelve_api_key {
         Some(api_key) => {
@@ -34,78 +59,83 @@ fn get_multi_quote_url(exchange: &Exchange, twelve_api_key: Option<&str>) -> Str
         },
         None => panic!("Must provide an API key for Twelve provider")
       }
-    }
+    },
+    Exchange::Yahoo => "https://query1.finance.yahoo.com/v8/finance/chart/{symbol}?interval=1d&range=1d".to_string()
   }
 }
 
+/// Decode Alpaca Quote Data
+/// Structures received data into the required price struct
+fn decode_alpaca_quote_data(exchange: &Exchange, data_obj: serde_json::Value, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  let quotes: AlpacaLatestQuotes = decode(exchange, data_obj)?;
+  let prices: Vec<QuotePrice> = quotes.quotes.into_iter()
+    .filter(|(symbol, _)| symbols.contains(&symbol.as_str()))
+    .filter_map(|(symbol, quote)| quote.price().map(|price| QuotePrice { symbol, price }))
+    .collect();
+  Ok(prices)
+}
+
+/// Decode AlphaVantage Quote Data
+/// Structures received data into the required price struct (one symbol per request)
+fn decode_alpha_vantage_quote_data(exchange: &Exchange, data_obj: serde_json::Value, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  let symbol: &str = symbols.first().copied().unwrap_or_default();
+  let price: f64 = parse_quote(exchange, symbol, data_obj)?;
+  Ok(vec![QuotePrice { symbol: symbol.to_string(), price }])
+}
+
 /// Decode Binance Quote Data
 /// Structures received data into the required price struct
-fn decode_binance_quote_data(data_str: String, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
-  let data: Vec<BinanceRawQuote> = serde_json::from_str::<Vec<BinanceRawQuote>>(data_str.as_str())?;
-  let mut prices: Vec<QuotePrice> = Vec::new();
-  for quote in data {
-    if symbols.contains(&quote.symbol.as_str()) {
-      if let Ok(price) = quote.price.parse::<f64>() {
-        prices.push(QuotePrice {
-          symbol: quote.symbol,
-          price,
-        });
-      }
-    }
-  }
+fn decode_binance_quote_data(exchange: &Exchange, data_obj: serde_json::Value, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  let tickers: Vec<BinanceTicker> = decode(exchange, data_obj)?;
+  let prices: Vec<QuotePrice> = tickers.into_iter()
+    .filter(|t| symbols.contains(&t.symbol.as_str()))
+    .map(|t| QuotePrice { symbol: t.symbol, price: t.price.0 })
+    .collect();
+  Ok(prices)
+}
+
+/// Decode Bitfinex Quote Data
+/// Structures received data into the required price struct
+fn decode_bitfinex_quote_data(exchange: &Exchange, data_obj: serde_json::Value, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  let tickers: Vec<BitfinexTicker> = decode(exchange, data_obj)?;
+  let prices: Vec<QuotePrice> = tickers.into_iter()
+    .filter(|t| symbols.contains(&t.symbol.as_str()))
+    .map(|t| QuotePrice { symbol: t.symbol, price: t.last_price })
+    .collect();
   Ok(prices)
 }
 
 /// Decode ByBit Quote Data
 /// Structures received data into the required price struct
-fn decode_bybit_quote_data(data_str: String, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
-  let v: serde_json::Value = serde_json::from_str(&data_str)?;
-  let list = v["result"]["list"].as_array().ok_or("Failed to get the list")
-    .map_err(|e| SmartError::RuntimeCheck(e.to_string()))?;
-  let mut prices: Vec<QuotePrice> = Vec::new();
-  for item in list {
-    if let Some(symbol) = item["symbol"].as_str() {
-      if symbols.contains(&symbol) {
-        if let Some(price_str) = item["lastPrice"].as_str() {
-          if let Ok(price) = price_str.parse::<f64>() {
-            prices.push(QuotePrice {
-              symbol: symbol.to_string(),
-              price,
-            });
-          }
-        }
-      }
-    }
-  }
+fn decode_bybit_quote_data(exchange: &Exchange, data_obj: serde_json::Value, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  let tickers: Vec<ByBitTicker> = decode::<ByBitResponse>(exchange, data_obj)?.into_list()?;
+  let prices: Vec<QuotePrice> = tickers.into_iter()
+    .filter(|t| symbols.contains(&t.symbol.as_str()))
+    .map(|t| QuotePrice { symbol: t.symbol, price: t.last_price.0 })
+    .collect();
   Ok(prices)
 }
 
 /// Decode Coinbase Quote Data
 /// Structures received data into the required price struct
-async fn decode_coinbase_quote_data(data_str: String, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
-  let data: serde_json::Value = serde_json::from_str(&data_str)?;
+async fn decode_coinbase_quote_data(exchange: &Exchange, data_obj: serde_json::Value, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  let products: Vec<CoinbaseProduct> = decode(exchange, data_obj)?;
   let mut prices: Vec<QuotePrice> = Vec::new();
   let mut counts = 0;
-  if let Some(array) = data.as_array() {
-    for obj in array {
-      if let Some(base_currency) = obj["base_currency"].as_str() {
-        if let Some(quote_currency) = obj["quote_currency"].as_str() {
-          let symbol = format!("{}-{}", base_currency, quote_currency);
-
-          if symbols.contains(&symbol.as_str()) {
-            counts += 1;
-            if counts > 1 { sleep(100).await; }
-
-            // Call price from api call
-            // This is because there is no mass price list found for coinbase
-            let price: f64 = request_quote(&Exchange::Coinbase, symbol.as_str(), None).await?;
-            prices.push(QuotePrice {
-                symbol,
-                price,
-            });
-          }
-        }
-      }
+  for product in products {
+    let symbol = format!("{}-{}", product.base_currency, product.quote_currency);
+
+    if symbols.contains(&symbol.as_str()) {
+      counts += 1;
+      if counts > 1 { sleep(100).await; }
+
+      // Call price from api call
+      // This is because there is no mass price list found for coinbase
+      let price: f64 = request_quote(&Exchange::Coinbase, symbol.as_str(), None).await?;
+      prices.push(QuotePrice {
+          symbol,
+          price,
+      });
     }
   }
 
@@ -114,57 +144,163 @@ async fn decode_coinbase_quote_data(data_str: String, symbols: Vec<&str>) -> Res
 
 /// Decode Dydx Quote Data
 /// Structures received data into the required price struct
-fn decode_dydx_quote_data(data_str: String, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
-  let data: serde_json::Value = serde_json::from_str(&data_str)?;
-  let markets = data["markets"].as_object().ok_or("Failed to parse markets")
-    .map_err(|e| SmartError::RuntimeCheck(e.to_string()))?;
-  let mut prices: Vec<QuotePrice> = Vec::new();
-  for symbol in &symbols {
-    if let Some(market) = markets.get(*symbol) {
-      if let Some(price_str) = market["indexPrice"].as_str() {
-        if let Ok(price) = price_str.parse::<f64>() {
-          prices.push(QuotePrice {
-            symbol: symbol.to_string(),
-            price,
-          });
-        }
-      }
-    }
-  }
+fn decode_dydx_quote_data(exchange: &Exchange, data_obj: serde_json::Value, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  let markets: DydxMarkets = decode(exchange, data_obj)?;
+  let prices: Vec<QuotePrice> = symbols.iter()
+    .filter_map(|symbol| markets.markets.get(*symbol).map(|m| QuotePrice { symbol: symbol.to_string(), price: m.oracle_price.0 }))
+    .collect();
+  Ok(prices)
+}
+
+/// Decode GateIo Quote Data
+/// Structures received data into the required price struct
+fn decode_gateio_quote_data(exchange: &Exchange, data_obj: serde_json::Value, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  let tickers: Vec<GateTicker> = decode(exchange, data_obj)?;
+  let prices: Vec<QuotePrice> = tickers.into_iter()
+    .filter(|t| symbols.contains(&t.contract.as_str()))
+    .map(|t| QuotePrice { symbol: t.contract, price: t.last.0 })
+    .collect();
+  Ok(prices)
+}
+
+/// Decode Hyperliquid Quote Data
+/// Structures received data into the required price struct
+fn decode_hyperliquid_quote_data(exchange: &Exchange, data_obj: serde_json::Value, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  let mids: HyperliquidMids = decode(exchange, data_obj)?;
+  let prices: Vec<QuotePrice> = symbols.into_iter()
+    .filter_map(|symbol| mids.get(symbol).map(|mid| QuotePrice { symbol: symbol.to_string(), price: mid.0 }))
+    .collect();
+  Ok(prices)
+}
+
+/// Decode Kraken Quote Data
+/// Structures received data into the required price struct
+/// Results are keyed by Kraken's internal pair names so are matched back to the requested symbols
+fn decode_kraken_quote_data(exchange: &Exchange, data_obj: serde_json::Value, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  let tickers: HashMap<String, KrakenTicker> = decode::<KrakenResponse>(exchange, data_obj)?.into_result()?;
+  let prices: Vec<QuotePrice> = symbols.iter()
+    .filter_map(|symbol| {
+      let (_, ticker) = tickers.iter().find(|(key, _)| kraken_pair_matches(key, symbol))?;
+      ticker.c.first().map(|price| QuotePrice { symbol: symbol.to_string(), price: price.0 })
+    })
+    .collect();
+  Ok(prices)
+}
+
+/// Decode KuCoin Quote Data
+/// Structures received data into the required price struct
+fn decode_kucoin_quote_data(exchange: &Exchange, data_obj: serde_json::Value, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  let tickers: KuCoinAllTickers = decode::<KuCoinResponse>(exchange, data_obj)?.into_data()?;
+  let prices: Vec<QuotePrice> = tickers.ticker.into_iter()
+    .filter(|t| symbols.contains(&t.symbol.as_str()))
+    .filter_map(|t| t.last.map(|last| QuotePrice { symbol: t.symbol, price: last.0 }))
+    .collect();
+  Ok(prices)
+}
+
+/// Decode Okx Quote Data
+/// Structures received data into the required price struct
+fn decode_okx_quote_data(exchange: &Exchange, data_obj: serde_json::Value, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  let tickers: Vec<OkxTicker> = decode::<OkxResponse>(exchange, data_obj)?.into_list()?;
+  let prices: Vec<QuotePrice> = tickers.into_iter()
+    .filter(|t| symbols.contains(&t.inst_id.as_str()))
+    .map(|t| QuotePrice { symbol: t.inst_id, price: t.last.0 })
+    .collect();
+  Ok(prices)
+}
+
+/// Decode Tiingo Quote Data
+/// Structures received data into the required price struct (tickers matched to the requested symbols ignoring case)
+fn decode_tiingo_quote_data(exchange: &Exchange, data_obj: serde_json::Value, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  let is_crypto: bool = symbols.first().is_some_and(|symbol| is_tiingo_crypto(symbol));
+  let quoted: Vec<QuotePrice> = tiingo_quote_prices(exchange, data_obj, is_crypto)?;
+  let prices: Vec<QuotePrice> = symbols.iter()
+    .filter_map(|symbol| {
+      let quote: &QuotePrice = quoted.iter().find(|q| q.symbol.eq_ignore_ascii_case(symbol))?;
+      Some(QuotePrice { symbol: symbol.to_string(), price: quote.price })
+    })
+    .collect();
   Ok(prices)
 }
 
 /// Decode Twelve Quote Data
 /// Structures received data into the required price struct
-fn decode_twelve_quote_data(data_str: String, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
-  let data: HashMap<String, PriceWrapper> = serde_json::from_str(&data_str)?;
-  let mut prices: Vec<QuotePrice> = Vec::new();
-  for (symbol, quote) in data.iter() {
-    if symbols.contains(&symbol.as_str()) {
-      if let Ok(price) = quote.price.parse::<f64>() {
-        prices.push(QuotePrice {
-          symbol: symbol.clone(),
-          price,
-        });
-      }
-    }
-  }
+fn decode_twelve_quote_data(exchange: &Exchange, data_obj: serde_json::Value, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  let data: HashMap<String, TwelvePrice> = decode(exchange, data_obj)?;
+  let prices: Vec<QuotePrice> = data.into_iter()
+    .filter(|(symbol, _)| symbols.contains(&symbol.as_str()))
+    .map(|(symbol, quote)| QuotePrice { symbol, price: quote.price.0 })
+    .collect();
   Ok(prices)
 }
 
+/// Decode Yahoo Quote Data
+/// Structures received data into the required price struct (one symbol per request)
+fn decode_yahoo_quote_data(exchange: &Exchange, data_obj: serde_json::Value, symbols: Vec<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  let symbol: &str = symbols.first().copied().unwrap_or_default();
+  let price: f64 = parse_quote(exchange, symbol, data_obj)?;
+  Ok(vec![QuotePrice { symbol: symbol.to_string(), price }])
+}
+
 /// Request Multi Quote
 /// Requests a Quotes from a given exchange
+/// Served from the quote cache when every symbol was quoted within the ttl
 pub async fn request_multi_quote(exchange: &Exchange, symbols: Vec<&str>, twelve_api_key: Option<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+  if let Some(prices) = cached_quotes(exchange, &symbols, now_ms()) {
+    return Ok(prices);
+  }
+  let prices: Vec<QuotePrice> = request_multi_quote_uncached(exchange, symbols, twelve_api_key).await?;
+  store_quotes(exchange, &prices, now_ms());
+  Ok(prices)
+}
+
+/// Request Multi Quote (uncached)
+/// Requests quotes from the exchange
+async fn request_multi_quote_uncached(exchange: &Exchange, symbols: Vec<&str>, twelve_api_key: Option<&str>) -> Result<Vec<QuotePrice>, SmartError> {
+
+  // AlphaVantage and Yahoo have no batch quote endpoint so symbols are quoted one at a time (AlphaVantage paced by the call quota)
+  if matches!(exchange, Exchange::AlphaVantage | Exchange::Yahoo) && symbols.len() > 1 {
+    let mut prices: Vec<QuotePrice> = vec![];
+    for symbol in symbols {
+      let price: f64 = request_quote(exchange, symbol, twelve_api_key).await?;
+      prices.push(QuotePrice { symbol: symbol.to_string(), price });
+    }
+    return Ok(prices);
+  }
 
   // Initialize url
   let mut request_url: String = get_multi_quote_url(&exchange, twelve_api_key);
+  if exchange == &Exchange::AlphaVantage {
+    let symbol: &str = symbols.first().copied().unwrap_or_default();
+    request_url = request_url.replace("{query}", &alpha_vantage_quote_query(symbol));
+  }
+  if exchange == &Exchange::Yahoo {
+    let symbol: &str = symbols.first().copied().unwrap_or_default();
+    request_url = request_url.replace("{symbol}", &yahoo_symbol(symbol));
+  }
   if exchange == &Exchange::Twelve {
     let symbolstring: String = symbols.iter().map(|&s| format!("{},",s)).collect();
     request_url = request_url.replace("{symbolstring}", symbolstring.as_str());
   }
+  if exchange == &Exchange::Alpaca {
+    // Stocks and crypto are served by separate endpoints, the first symbol decides which is called
+    let symbol: &str = symbols.first().copied().unwrap_or_default();
+    request_url = request_url.replace("{quotepath}", alpaca_quote_path(symbol)).replace("{symbolstring}", &symbols.join(","));
+  }
+  if exchange == &Exchange::Tiingo {
+    // Stocks and crypto are served by separate endpoints, the first symbol decides which is called
+    let symbol: &str = symbols.first().copied().unwrap_or_default();
+    request_url = request_url.replace("{quotepath}", tiingo_quote_path(symbol)).replace("{symbolstring}", &symbols.join(","));
+  }
+  if exchange == &Exchange::Kraken || exchange == &Exchange::Bitfinex {
+    request_url = request_url.replace("{symbolstring}", &symbols.join(","));
+  }
 
   // Make request
-  let res_data: reqwest::Response = api_request(&request_url).await?;
+  let res_data: reqwest::Response = match exchange {
+    Exchange::Hyperliquid => api_post(&request_url, HYPERLIQUID_MIDS_BODY).await?,
+    _ => api_request(&request_url).await?
+  };
 
   // Guard: Ensure status code
   if res_data.status() != 200 {
@@ -173,13 +309,23 @@ pub async fn request_multi_quote(exchange: &Exchange, symbols: Vec<&str>, twelve
   }
 
   // Extract result
-  let data_str: String = res_data.text().await?;
+  let data_obj: serde_json::Value = res_data.json().await?;
   match exchange {
-    Exchange::Binance | Exchange::BinanceUs => Ok(decode_binance_quote_data(data_str, symbols)?),
-    Exchange::ByBit => Ok(decode_bybit_quote_data(data_str, symbols)?),
-    Exchange::Coinbase => Ok(decode_coinbase_quote_data(data_str, symbols).await?),
-    Exchange::Dydx => Ok(decode_dydx_quote_data(data_str, symbols)?),
-    Exchange::Twelve => Ok(decode_twelve_quote_data(data_str, symbols)?)
+    Exchange::Alpaca => decode_alpaca_quote_data(exchange, data_obj, symbols),
+    Exchange::AlphaVantage => decode_alpha_vantage_quote_data(exchange, data_obj, symbols),
+    Exchange::Binance | Exchange::BinanceCoinM | Exchange::BinanceSpot | Exchange::BinanceUs => decode_binance_quote_data(exchange, data_obj, symbols),
+    Exchange::Bitfinex => decode_bitfinex_quote_data(exchange, data_obj, symbols),
+    Exchange::ByBit | Exchange::ByBitInverse => decode_bybit_quote_data(exchange, data_obj, symbols),
+    Exchange::Coinbase => decode_coinbase_quote_data(exchange, data_obj, symbols).await,
+    Exchange::Dydx => decode_dydx_quote_data(exchange, data_obj, symbols),
+    Exchange::GateIo => decode_gateio_quote_data(exchange, data_obj, symbols),
+    Exchange::Hyperliquid => decode_hyperliquid_quote_data(exchange, data_obj, symbols),
+    Exchange::Kraken => decode_kraken_quote_data(exchange, data_obj, symbols),
+    Exchange::KuCoin => decode_kucoin_quote_data(exchange, data_obj, symbols),
+    Exchange::Okx => decode_okx_quote_data(exchange, data_obj, symbols),
+    Exchange::Tiingo => decode_tiingo_quote_data(exchange, data_obj, symbols),
+    Exchange::Twelve => decode_twelve_quote_data(exchange, data_obj, symbols),
+    Exchange::Yahoo => decode_yahoo_quote_data(exchange, data_obj, symbols)
   }
 }
 
@@ -196,6 +342,13 @@ mod tests {
     assert!(prices.len() > 0);
   }
 
+  #[tokio::test]
+  async fn tests_retrieve_quotes_multi_binance_spot() {
+    let symbols = vec!["BTCUSDT", "ETHUSDT"];
+    let prices = request_multi_quote(&Exchange::BinanceSpot, symbols, None).await.unwrap();
+    assert!(!prices.is_empty());
+  }
+
   #[tokio::test]
   async fn tests_retrieve_quotes_multi_binance_us() {
     let symbols = vec!["BTCUSDT", "ETHUSDT"];
@@ -228,6 +381,48 @@ mod tests {
     assert!(prices.len() > 0);
   }
 
+  #[tokio::test]
+  async fn tests_retrieve_quote_multi_bitfinex() {
+    let symbols = vec!["tBTCUSD", "tETHUSD"];
+    let prices = request_multi_quote(&Exchange::Bitfinex, symbols, None).await.unwrap();
+    assert_eq!(prices.len(), 2);
+  }
+
+  #[tokio::test]
+  async fn tests_retrieve_quote_multi_gateio() {
+    let symbols = vec!["BTC_USDT", "ETH_USDT"];
+    let prices = request_multi_quote(&Exchange::GateIo, symbols, None).await.unwrap();
+    assert_eq!(prices.len(), 2);
+  }
+
+  #[tokio::test]
+  async fn tests_retrieve_quote_multi_hyperliquid() {
+    let symbols = vec!["BTC", "ETH"];
+    let prices = request_multi_quote(&Exchange::Hyperliquid, symbols, None).await.unwrap();
+    assert_eq!(prices.len(), 2);
+  }
+
+  #[tokio::test]
+  async fn tests_retrieve_quote_multi_kraken() {
+    let symbols = vec!["XBTUSD", "ETHUSD"];
+    let prices = request_multi_quote(&Exchange::Kraken, symbols, None).await.unwrap();
+    assert_eq!(prices.len(), 2);
+  }
+
+  #[tokio::test]
+  async fn tests_retrieve_quote_multi_kucoin() {
+    let symbols = vec!["BTC-USDT", "ETH-USDT"];
+    let prices = request_multi_quote(&Exchange::KuCoin, symbols, None).await.unwrap();
+    assert_eq!(prices.len(), 2);
+  }
+
+  #[tokio::test]
+  async fn tests_retrieve_quote_multi_okx() {
+    let symbols = vec!["BTC-USDT-SWAP", "ETH-USDT-SWAP"];
+    let prices = request_multi_quote(&Exchange::Okx, symbols, None).await.unwrap();
+    assert_eq!(prices.len(), 2);
+  }
+
   #[tokio::test]
   async fn tests_retrieve_quote_multi_twelve() {
     use dotenv::dotenv;
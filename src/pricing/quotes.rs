@@ -1,16 +1,54 @@
+use std::collections::HashMap;
+
 use crate::SmartError;
-use super::models::{Exchange, QuoteExch};
-use super::utils::api_request;
+use super::models::{Exchange, QuoteExch, QuotePrice};
+use super::quotecache::{cached_quote, store_quotes};
+use super::clock::now_ms;
+use super::schemas::{
+  decode, required, AlpacaLatestQuotes, AlphaVantageQuote, BinanceTicker, BitfinexTicker, ByBitResponse, ByBitTicker, CoinbaseBook, DydxMarkets, GateTicker, HyperliquidMids, KrakenResponse, KrakenTicker, KuCoinLevel1, KuCoinResponse, Num, OkxResponse, OkxTicker, TiingoCryptoTop, TiingoIexQuote, TwelvePrice, YahooChart, YahooResult
+};
+use super::translate::is_tiingo_crypto;
+use super::utils::{alpaca_auth_query, api_post, api_request};
+
+/// Hyperliquid request body for the mid price of every coin (the info API is POST only)
+pub const HYPERLIQUID_MIDS_BODY: &str = r#"{"type":"allMids"}"#;
 
 /// Get quote url
 /// Retrieves quote url for a given exchange
 fn get_quote_url(exchange: &Exchange, twelve_api_key: Option<&str>) -> String {
   match exchange {
+    Exchange::Alpaca => {
+      match twelve_api_key {
+        Some(api_key) => format!("https://data.alpaca.markets/{{quotepath}}?symbols={{symbol}}&{}", alpaca_auth_query(api_key)),
+        None => panic!("Must provide an API key for Alpaca provider")
+      }
+    },
+    Exchange::AlphaVantage => {
+      match twelve_api_key {
+        Some(api_key) => format!("https://www.alphavantage.co/query?{{query}}&apikey={}", api_key),
+        None => panic!("Must provide an API key for AlphaVantage provider")
+      }
+    },
     Exchange::Binance => "https://fapi.binance.com/fapi/v1/ticker/price?symbol={symbol}".to_string(),
+    Exchange::BinanceCoinM => "https://dapi.binance.com/dapi/v1/ticker/price?symbol={symbol}".to_string(),
+    Exchange::BinanceSpot => "https://api.binance.com/api/v3/ticker/price?symbol={symbol}".to_string(),
     Exchange::BinanceUs => "https://api.binance.us/api/v3/ticker/price?symbol={symbol}".to_string(),
+    Exchange::Bitfinex => "https://api-pub.bitfinex.com/v2/tickers?symbols={symbol}".to_string(),
     Exchange::ByBit => "https://api.bybit.com/v5/market/tickers?category=linear&symbol={symbol}".to_string(),
+    Exchange::ByBitInverse => "https://api.bybit.com/v5/market/tickers?category=inverse&symbol={symbol}".to_string(),
     Exchange::Coinbase => "https://api.exchange.coinbase.com/products/{symbol}/book?level=0".to_string(),
-    Exchange::Dydx => "https://api.dydx.exchange/v3/markets?market={symbol}".to_string(),
+    Exchange::Dydx => "https://indexer.dydx.trade/v4/perpetualMarkets?ticker={symbol}".to_string(),
+    Exchange::GateIo => "https://api.gateio.ws/api/v4/futures/usdt/tickers?contract={symbol}".to_string(),
+    Exchange::Hyperliquid => "https://api.hyperliquid.xyz/info".to_string(),
+    Exchange::Kraken => "https://api.kraken.com/0/public/Ticker?pair={symbol}".to_string(),
+    Exchange::KuCoin => "https://api.kucoin.com/api/v1/market/orderbook/level1?symbol={symbol}".to_string(),
+    Exchange::Okx => "https://www.okx.com/api/v5/market/ticker?instId={symbol}".to_string(),
+    Exchange::Tiingo => {
+      match twelve_api_key {
+        Some(api_key) => format!("https://api.tiingo.com/{{quotepath}}?tickers={{symbol}}&token={}", api_key),
+        None => panic!("Must provide an API key for Tiingo provider")
+      }
+    },
     Exchange::Twelve => {
       match twelve_api_key {
         Some(api_key) => {
@@ -19,20 +57,166 @@ fn get_quote_url(exchange: &Exchange, twelve_api_key: Option<&str>) -> String {
         },
         None => panic!("Must provide an API key for Twelve provider")
       }
-    }
+    },
+    Exchange::Yahoo => "https://query1.finance.yahoo.com/v8/finance/chart/{symbol}?interval=1d&range=1d".to_string()
+  }
+}
+
+/// Alpaca Quote Path
+/// Latest quotes endpoint for crypto symbols (BTC/USD) or stocks (AAPL)
+pub fn alpaca_quote_path(symbol: &str) -> &'static str {
+  match symbol.contains('/') {
+    true => "v1beta3/crypto/us/latest/quotes",
+    false => "v2/stocks/quotes/latest"
+  }
+}
+
+/// Tiingo Quote Path
+/// Top of book endpoint for crypto tickers (btcusd) or IEX stocks (AAPL)
+pub fn tiingo_quote_path(symbol: &str) -> &'static str {
+  match is_tiingo_crypto(symbol) {
+    true => "tiingo/crypto/top",
+    false => "iex/"
   }
 }
 
+/// Tiingo Quote Prices
+/// Prices by ticker from either top of book endpoint (tickers as returned, lowercase for crypto)
+pub fn tiingo_quote_prices(exchange: &Exchange, data_obj: serde_json::Value, is_crypto: bool) -> Result<Vec<QuotePrice>, SmartError> {
+  let prices: Vec<QuotePrice> = match is_crypto {
+    true => decode::<Vec<TiingoCryptoTop>>(exchange, data_obj)?.into_iter()
+      .filter_map(|top| {
+        let price: Num = top.top_of_book_data.first()?.last_price?;
+        Some(QuotePrice { symbol: top.ticker, price: price.0 })
+      })
+      .collect(),
+    false => decode::<Vec<TiingoIexQuote>>(exchange, data_obj)?.into_iter()
+      .filter_map(|quote| quote.tngo_last.map(|price| QuotePrice { symbol: quote.ticker, price: price.0 }))
+      .collect()
+  };
+  Ok(prices)
+}
+
+/// Alpha Vantage Quote Query
+/// Forex symbols (EUR/USD) are quoted as an exchange rate and stocks (AAPL) as a global quote
+pub fn alpha_vantage_quote_query(symbol: &str) -> String {
+  match symbol.split_once('/') {
+    Some((from, to)) => format!("function=CURRENCY_EXCHANGE_RATE&from_currency={}&to_currency={}", from, to),
+    None => format!("function=GLOBAL_QUOTE&symbol={}", symbol)
+  }
+}
+
+/// Yahoo Symbol
+/// Chart path segment for a symbol (index symbols such as ^GSPC are percent encoded)
+pub fn yahoo_symbol(symbol: &str) -> String {
+  symbol.replace('^', "%5E")
+}
+
+/// Parse Quote
+/// Extracts the symbol's price from a quote response, failing on schema mismatch rather than returning zero
+pub fn parse_quote(exchange: &Exchange, symbol: &str, data_obj: serde_json::Value) -> Result<f64, SmartError> {
+  let price: Num = match exchange {
+    Exchange::Alpaca => {
+      let quotes: AlpacaLatestQuotes = decode(exchange, data_obj)?;
+      Num(required(quotes.quotes.get(symbol).and_then(|quote| quote.price()), exchange, &format!("quotes.{}", symbol))?)
+    },
+    Exchange::AlphaVantage => {
+      let quote: AlphaVantageQuote = decode(exchange, data_obj)?;
+      let price: Option<Num> = quote.global_quote.map(|q| q.price).or(quote.exchange_rate.map(|r| r.rate));
+      required(price, exchange, "Global Quote.05. price")?
+    },
+    Exchange::Binance | Exchange::BinanceSpot | Exchange::BinanceUs => decode::<BinanceTicker>(exchange, data_obj)?.price,
+    Exchange::BinanceCoinM => {
+      // COIN-M returns a list (one entry per contract) even when a symbol is given
+      let tickers: Vec<BinanceTicker> = decode(exchange, data_obj)?;
+      required(tickers.into_iter().next(), exchange, "[0]")?.price
+    },
+    Exchange::Bitfinex => {
+      let tickers: Vec<BitfinexTicker> = decode(exchange, data_obj)?;
+      Num(required(tickers.into_iter().next(), exchange, "[0]")?.last_price)
+    },
+    Exchange::ByBit | Exchange::ByBitInverse => {
+      let tickers: Vec<ByBitTicker> = decode::<ByBitResponse>(exchange, data_obj)?.into_list()?;
+      required(tickers.into_iter().next(), exchange, "result.list[0]")?.last_price
+    },
+    Exchange::Coinbase => {
+      let book: CoinbaseBook = decode(exchange, data_obj)?;
+      *required(book.asks.first().and_then(|ask| ask.first()), exchange, "asks[0][0]")?
+    },
+    Exchange::Dydx => {
+      let markets: DydxMarkets = decode(exchange, data_obj)?;
+      required(markets.markets.into_values().next(), exchange, "markets.<ticker>")?.oracle_price
+    },
+    Exchange::GateIo => {
+      let tickers: Vec<GateTicker> = decode(exchange, data_obj)?;
+      required(tickers.into_iter().next(), exchange, "[0]")?.last
+    },
+    Exchange::Hyperliquid => {
+      // Mids are returned for every coin
+      let mids: HyperliquidMids = decode(exchange, data_obj)?;
+      *required(mids.get(symbol), exchange, symbol)?
+    },
+    Exchange::Kraken => {
+      let tickers: HashMap<String, KrakenTicker> = decode::<KrakenResponse>(exchange, data_obj)?.into_result()?;
+      let ticker: KrakenTicker = required(tickers.into_values().next(), exchange, "result.<pair>")?;
+      *required(ticker.c.first(), exchange, "result.<pair>.c[0]")?
+    },
+    Exchange::KuCoin => decode::<KuCoinResponse>(exchange, data_obj)?.into_data::<KuCoinLevel1>()?.price,
+    Exchange::Okx => {
+      let tickers: Vec<OkxTicker> = decode::<OkxResponse>(exchange, data_obj)?.into_list()?;
+      required(tickers.into_iter().next(), exchange, "data[0]")?.last
+    },
+    Exchange::Tiingo => {
+      let prices: Vec<QuotePrice> = tiingo_quote_prices(exchange, data_obj, is_tiingo_crypto(symbol))?;
+      let price: Option<f64> = prices.into_iter().find(|p| p.symbol.eq_ignore_ascii_case(symbol)).map(|p| p.price);
+      Num(required(price, exchange, &format!("[{}].last", symbol))?)
+    },
+    Exchange::Twelve => decode::<TwelvePrice>(exchange, data_obj)?.price,
+    Exchange::Yahoo => {
+      let result: YahooResult = decode::<YahooChart>(exchange, data_obj)?.into_result()?;
+      required(result.meta.regular_market_price, exchange, "chart.result[0].meta.regularMarketPrice")?
+    }
+  };
+  Ok(price.0)
+}
+
 /// Request quote
 /// Requests a quote from a given exchange
+/// Served from the quote cache when quoted within the ttl
 pub async fn request_quote(exchange: &Exchange, symbol: &str, twelve_api_key: Option<&str>) -> Result<f64, SmartError> {
+  if let Some(price) = cached_quote(exchange, symbol, now_ms()) {
+    return Ok(price);
+  }
+  let price: f64 = request_quote_uncached(exchange, symbol, twelve_api_key).await?;
+  store_quotes(exchange, &[QuotePrice { symbol: symbol.to_string(), price }], now_ms());
+  Ok(price)
+}
+
+/// Request quote (uncached)
+/// Requests a quote from the exchange
+async fn request_quote_uncached(exchange: &Exchange, symbol: &str, twelve_api_key: Option<&str>) -> Result<f64, SmartError> {
 
   // Initialize url
   let mut request_url: String = get_quote_url(&exchange, twelve_api_key);
-  request_url = request_url.replace("{symbol}", symbol);
+  request_url = match exchange {
+    Exchange::Yahoo => request_url.replace("{symbol}", &yahoo_symbol(symbol)),
+    _ => request_url.replace("{symbol}", symbol)
+  };
+  if exchange == &Exchange::Alpaca {
+    request_url = request_url.replace("{quotepath}", alpaca_quote_path(symbol));
+  }
+  if exchange == &Exchange::Tiingo {
+    request_url = request_url.replace("{quotepath}", tiingo_quote_path(symbol));
+  }
+  if exchange == &Exchange::AlphaVantage {
+    request_url = request_url.replace("{query}", &alpha_vantage_quote_query(symbol));
+  }
 
   // Make request
-  let res_data: reqwest::Response = api_request(&request_url).await?;
+  let res_data: reqwest::Response = match exchange {
+    Exchange::Hyperliquid => api_post(&request_url, HYPERLIQUID_MIDS_BODY).await?,
+    _ => api_request(&request_url).await?
+  };
 
   // Guard: Ensure status code
   if res_data.status() != 200 {
@@ -42,74 +226,36 @@ pub async fn request_quote(exchange: &Exchange, symbol: &str, twelve_api_key: Op
 
   // Extract result
   let data_obj: serde_json::Value = res_data.json().await?;
-  let price: f64 = match exchange {
-    Exchange::Binance | Exchange::BinanceUs => {
-      let price = data_obj.get("price")
-        .and_then(|v| v.as_str())
-        .and_then(|s| s.parse::<f64>().ok())
-        .unwrap_or(0.0);
-      price
-    },
-    Exchange::ByBit => {
-      let price = data_obj.get("result")
-        .and_then(|v| v.get("list"))
-        .and_then(|list| list.get(0))
-        .and_then(|obj| obj.get("lastPrice"))
-        .and_then(|v| v.as_str())
-        .and_then(|s| s.parse::<f64>().ok())
-        .unwrap_or(0.0);
-      price
-    },
-    Exchange::Coinbase => {
-      let price = data_obj.get("asks")
-        .and_then(serde_json::Value::as_array)
-        .and_then(|asks| asks.first())
-        .and_then(serde_json::Value::as_array)
-        .and_then(|ask| ask.get(0))
-        .and_then(serde_json::Value::as_str)
-        .and_then(|s| s.parse::<f64>().ok())
-        .unwrap_or(0.0);
-      price
-    },
-    Exchange::Dydx => {
-      let price: f64 = if let Some(markets) = data_obj.get("markets").and_then(serde_json::Value::as_object) {
-        let mut price_detail: f64 = 0.0;
-        for (_, details) in markets {
-          price_detail = details.get("indexPrice")
-            .and_then(serde_json::Value::as_str)
-            .and_then(|s| s.parse::<f64>().ok())
-            .unwrap_or(0.0);
-          break;
-        }
-        price_detail
-      } else {
-        0.0
-      };
-      price
-    },
-    Exchange::Twelve => {
-      let price: f64 = data_obj.get("price")
-        .and_then(|v| v.as_str())
-        .and_then(|s| s.parse::<f64>().ok())
-        .unwrap_or(0.0);
-      price
-    }
-  };
-  
-  Ok(price)
+  parse_quote(exchange, symbol, data_obj)
 }
 
 /// Get Quotes All Exchanges
 /// Retrieve quotes for all exchanges
 pub async fn get_quotes_all_exchanges(twelve_api_key: Option<&str>) -> Result<QuoteExch, SmartError> {
-  let exchanges: [Exchange; 6] = [Exchange::Binance, Exchange::BinanceUs, Exchange::ByBit, Exchange::Coinbase, Exchange::Dydx, Exchange::Twelve];
-  let mut quote_exch: QuoteExch = QuoteExch { binance: 0.0, binance_us: 0.0, bybit: 0.0, coinbase: 0.0, dydx: 0.0, twelve: 0.0 };
+  let exchanges: [Exchange; 19] = [
+    Exchange::Alpaca, Exchange::AlphaVantage, Exchange::Binance, Exchange::BinanceCoinM, Exchange::BinanceSpot, Exchange::BinanceUs, Exchange::Bitfinex,
+    Exchange::ByBit, Exchange::ByBitInverse, Exchange::Coinbase, Exchange::Dydx, Exchange::GateIo, Exchange::Hyperliquid, Exchange::Kraken,
+    Exchange::KuCoin, Exchange::Okx, Exchange::Tiingo, Exchange::Twelve, Exchange::Yahoo
+  ];
+  let mut quote_exch: QuoteExch = QuoteExch {
+    alpaca: 0.0, alpha_vantage: 0.0, binance: 0.0, binance_coinm: 0.0, binance_spot: 0.0, binance_us: 0.0, bitfinex: 0.0, bybit: 0.0, bybit_inverse: 0.0, coinbase: 0.0, dydx: 0.0, gateio: 0.0, hyperliquid: 0.0, kraken: 0.0, kucoin: 0.0, okx: 0.0, tiingo: 0.0, twelve: 0.0, yahoo: 0.0
+  };
 
   for exchange in exchanges {
 
     let symbol: &str = match exchange {
-      Exchange::Binance | Exchange::BinanceUs | Exchange::ByBit => "BTCUSDT",
+      Exchange::Alpaca | Exchange::AlphaVantage => "AAPL",
+      Exchange::Binance | Exchange::BinanceSpot | Exchange::BinanceUs | Exchange::ByBit => "BTCUSDT",
+      Exchange::BinanceCoinM => "BTCUSD_PERP",
+      Exchange::ByBitInverse => "BTCUSD",
       Exchange::Coinbase | Exchange::Dydx  => "BTC-USD",
+      Exchange::Bitfinex => "tBTCUSD",
+      Exchange::GateIo => "BTC_USDT",
+      Exchange::Hyperliquid => "BTC",
+      Exchange::Kraken => "XBTUSD",
+      Exchange::KuCoin => "BTC-USDT",
+      Exchange::Okx => "BTC-USDT-SWAP",
+      Exchange::Tiingo | Exchange::Yahoo => "SPY",
       Exchange::Twelve => "BTCUSD"
     };
 
@@ -117,12 +263,25 @@ pub async fn get_quotes_all_exchanges(twelve_api_key: Option<&str>) -> Result<Qu
 
     if let Ok(quote) = quote_res {
       match exchange {
+        Exchange::Alpaca => quote_exch.alpaca = quote,
+        Exchange::AlphaVantage => quote_exch.alpha_vantage = quote,
         Exchange::Binance => quote_exch.binance = quote,
+        Exchange::BinanceCoinM => quote_exch.binance_coinm = quote,
+        Exchange::BinanceSpot => quote_exch.binance_spot = quote,
         Exchange::BinanceUs => quote_exch.binance_us = quote,
         Exchange::ByBit => quote_exch.bybit = quote,
+        Exchange::ByBitInverse => quote_exch.bybit_inverse = quote,
         Exchange::Coinbase => quote_exch.coinbase = quote,
         Exchange::Dydx => quote_exch.dydx = quote,
-        Exchange::Twelve => quote_exch.twelve = quote
+        Exchange::Bitfinex => quote_exch.bitfinex = quote,
+        Exchange::GateIo => quote_exch.gateio = quote,
+        Exchange::Hyperliquid => quote_exch.hyperliquid = quote,
+        Exchange::Kraken => quote_exch.kraken = quote,
+        Exchange::KuCoin => quote_exch.kucoin = quote,
+        Exchange::Okx => quote_exch.okx = quote,
+        Exchange::Tiingo => quote_exch.tiingo = quote,
+        Exchange::Twelve => quote_exch.twelve = quote,
+        Exchange::Yahoo => quote_exch.yahoo = quote
       }
     }
   }
@@ -135,18 +294,82 @@ pub async fn get_quotes_all_exchanges(twelve_api_key: Option<&str>) -> Result<Qu
 mod tests {
   use super::*;
 
+  #[tokio::test]
+  async fn it_parses_typed_quotes() {
+    let coinm: serde_json::Value = serde_json::json!([{ "symbol": "BTCUSD_PERP", "ps": "BTCUSD", "price": "50000.1", "time": 1 }]);
+    assert_eq!(parse_quote(&Exchange::BinanceCoinM, "BTCUSD_PERP", coinm).unwrap(), 50000.1);
+
+    let coinbase: serde_json::Value = serde_json::json!({ "bids": [["99.0", "1.0", 3]], "asks": [["101.5", "2.0", 1]], "sequence": 1 });
+    assert_eq!(parse_quote(&Exchange::Coinbase, "BTC-USD", coinbase).unwrap(), 101.5);
+
+    // Empty books and missing markets used to silently quote zero
+    let empty_book: serde_json::Value = serde_json::json!({ "bids": [], "asks": [] });
+    let err: SmartError = parse_quote(&Exchange::Coinbase, "BTC-USD", empty_book).unwrap_err();
+    assert!(err.to_string().contains("asks[0][0]"));
+    assert!(parse_quote(&Exchange::Dydx, "BTC-USD", serde_json::json!({ "markets": {} })).is_err());
+    let dydx: serde_json::Value = serde_json::json!({ "markets": { "BTC-USD": { "ticker": "BTC-USD", "status": "ACTIVE", "oraclePrice": "50000.25" } } });
+    assert_eq!(parse_quote(&Exchange::Dydx, "BTC-USD", dydx).unwrap(), 50000.25);
+    assert!(parse_quote(&Exchange::Binance, "BTCUSDT", serde_json::json!({ "code": -1121, "msg": "Invalid symbol." })).is_err());
+
+    let mids: serde_json::Value = serde_json::json!({ "BTC": "50000.5", "ETH": "3000.25" });
+    assert_eq!(parse_quote(&Exchange::Hyperliquid, "ETH", mids.clone()).unwrap(), 3000.25);
+    assert!(parse_quote(&Exchange::Hyperliquid, "DOGE", mids).is_err());
+
+    let rate: serde_json::Value = serde_json::json!({ "Realtime Currency Exchange Rate": { "1. From_Currency Code": "EUR", "5. Exchange Rate": "1.09120000" } });
+    assert_eq!(parse_quote(&Exchange::AlphaVantage, "EUR/USD", rate).unwrap(), 1.0912);
+    let stock: serde_json::Value = serde_json::json!({ "Global Quote": { "01. symbol": "AAPL", "05. price": "189.2500" } });
+    assert_eq!(parse_quote(&Exchange::AlphaVantage, "AAPL", stock).unwrap(), 189.25);
+    assert!(parse_quote(&Exchange::AlphaVantage, "AAPL", serde_json::json!({})).is_err());
+    let alpaca: serde_json::Value = serde_json::json!({ "quotes": { "AAPL": { "ap": 190.2, "as": 1, "bp": 190.0, "bs": 2, "t": "2023-07-03T14:00:00Z" } } });
+    assert!((parse_quote(&Exchange::Alpaca, "AAPL", alpaca.clone()).unwrap() - 190.1).abs() < 1e-9);
+    assert!(parse_quote(&Exchange::Alpaca, "MSFT", alpaca).is_err());
+
+    let chart: serde_json::Value = serde_json::json!({ "chart": { "result": [{ "meta": { "symbol": "SPY", "regularMarketPrice": 443.28, "gmtoffset": -14400 }, "indicators": { "quote": [{}] } }], "error": null } });
+    assert_eq!(parse_quote(&Exchange::Yahoo, "SPY", chart).unwrap(), 443.28);
+    let not_found: serde_json::Value = serde_json::json!({ "chart": { "result": null, "error": { "code": "Not Found", "description": "No data found, symbol may be delisted" } } });
+    assert!(matches!(parse_quote(&Exchange::Yahoo, "XXXX", not_found), Err(SmartError::APIResponseStatus(_))));
+    assert_eq!(yahoo_symbol("^GSPC"), "%5EGSPC");
+
+    let iex: serde_json::Value = serde_json::json!([{ "ticker": "AAPL", "tngoLast": 190.1, "last": 190.1, "bidPrice": 190.0, "askPrice": 190.2 }]);
+    assert_eq!(parse_quote(&Exchange::Tiingo, "aapl", iex).unwrap(), 190.1);
+    let top: serde_json::Value = serde_json::json!([{ "ticker": "btcusd", "topOfBookData": [{ "lastPrice": 30650.5, "bidPrice": 30650.0, "askPrice": 30651.0 }] }]);
+    assert_eq!(parse_quote(&Exchange::Tiingo, "BTCUSD", top).unwrap(), 30650.5);
+    assert_eq!(tiingo_quote_path("btcusd"), "tiingo/crypto/top");
+    assert_eq!(tiingo_quote_path("SPY"), "iex/");
+    assert_eq!(alpaca_quote_path("BTC/USD"), "v1beta3/crypto/us/latest/quotes");
+    assert_eq!(alpha_vantage_quote_query("EUR/USD"), "function=CURRENCY_EXCHANGE_RATE&from_currency=EUR&to_currency=USD");
+  }
+
   #[tokio::test]
   async fn tests_retrieve_quote_binance() {
     let price = request_quote(&Exchange::Binance, "BTCUSDT", None).await;
     assert!(price.unwrap() > 0.0);
   }
 
+  #[tokio::test]
+  async fn tests_retrieve_quote_binance_spot() {
+    let price = request_quote(&Exchange::BinanceSpot, "BTCUSDT", None).await;
+    assert!(price.unwrap() > 0.0);
+  }
+
   #[tokio::test]
   async fn tests_retrieve_quote_binance_us() {
     let price = request_quote(&Exchange::BinanceUs, "BTCUSDT", None).await;
     assert!(price.unwrap() > 0.0);
   }
 
+  #[tokio::test]
+  async fn tests_retrieve_quote_binance_coinm() {
+    let price = request_quote(&Exchange::BinanceCoinM, "BTCUSD_PERP", None).await;
+    assert!(price.unwrap() > 0.0);
+  }
+
+  #[tokio::test]
+  async fn tests_retrieve_quote_bybit_inverse() {
+    let price = request_quote(&Exchange::ByBitInverse, "BTCUSD", None).await;
+    assert!(price.unwrap() > 0.0);
+  }
+
   #[tokio::test]
   async fn tests_retrieve_quote_bybit() {
     let price = request_quote(&Exchange::ByBit, "BTCUSDT", None).await;
@@ -165,6 +388,42 @@ mod tests {
     assert!(price.unwrap() > 0.0);
   }
 
+  #[tokio::test]
+  async fn tests_retrieve_quote_gateio() {
+    let price = request_quote(&Exchange::GateIo, "BTC_USDT", None).await;
+    assert!(price.unwrap() > 0.0);
+  }
+
+  #[tokio::test]
+  async fn tests_retrieve_quote_hyperliquid() {
+    let price = request_quote(&Exchange::Hyperliquid, "BTC", None).await;
+    assert!(price.unwrap() > 0.0);
+  }
+
+  #[tokio::test]
+  async fn tests_retrieve_quote_kraken() {
+    let price = request_quote(&Exchange::Kraken, "XBTUSD", None).await;
+    assert!(price.unwrap() > 0.0);
+  }
+
+  #[tokio::test]
+  async fn tests_retrieve_quote_bitfinex() {
+    let price = request_quote(&Exchange::Bitfinex, "tBTCUSD", None).await;
+    assert!(price.unwrap() > 0.0);
+  }
+
+  #[tokio::test]
+  async fn tests_retrieve_quote_kucoin() {
+    let price = request_quote(&Exchange::KuCoin, "BTC-USDT", None).await;
+    assert!(price.unwrap() > 0.0);
+  }
+
+  #[tokio::test]
+  async fn tests_retrieve_quote_okx() {
+    let price = request_quote(&Exchange::Okx, "BTC-USDT-SWAP", None).await;
+    assert!(price.unwrap() > 0.0);
+  }
+
   #[tokio::test]
   async fn tests_retrieve_quote_twelve() {
     use dotenv::dotenv;
@@ -180,6 +439,57 @@ mod tests {
     assert!(price.unwrap() > 0.0);
   }
 
+  #[tokio::test]
+  async fn tests_retrieve_quote_tiingo() {
+    use dotenv::dotenv;
+    use std::env;
+    dotenv().ok();
+
+    let api_key: String = match env::var("TIINGO_API_KEY") {
+      Ok(val) => val,
+      Err(_e) => panic!("Failed to read TIINGO_API_KEY"),
+    };
+
+    let price = request_quote(&Exchange::Tiingo, "SPY", Some(&api_key)).await;
+    assert!(price.unwrap() > 0.0);
+  }
+
+  #[tokio::test]
+  async fn tests_retrieve_quote_alpaca() {
+    use dotenv::dotenv;
+    use std::env;
+    dotenv().ok();
+
+    let api_key: String = match env::var("ALPACA_API_KEY") {
+      Ok(val) => val,
+      Err(_e) => panic!("Failed to read ALPACA_API_KEY"),
+    };
+
+    let price = request_quote(&Exchange::Alpaca, "BTC/USD", Some(&api_key)).await;
+    assert!(price.unwrap() > 0.0);
+  }
+
+  #[tokio::test]
+  async fn tests_retrieve_quote_yahoo() {
+    let price = request_quote(&Exchange::Yahoo, "^GSPC", None).await;
+    assert!(price.unwrap() > 0.0);
+  }
+
+  #[tokio::test]
+  async fn tests_retrieve_quote_alpha_vantage() {
+    use dotenv::dotenv;
+    use std::env;
+    dotenv().ok();
+
+    let api_key: String = match env::var("ALPHA_VANTAGE_API_KEY") {
+      Ok(val) => val,
+      Err(_e) => panic!("Failed to read ALPHA_VANTAGE_API_KEY"),
+    };
+
+    let price = request_quote(&Exchange::AlphaVantage, "EUR/USD", Some(&api_key)).await;
+    assert!(price.unwrap() > 0.0);
+  }
+
   #[tokio::test]
   async fn tests_get_quotes_all_exchanges() {
     use dotenv::dotenv;
@@ -0,0 +1,491 @@
+use futures::Stream;
+use futures::stream::{LocalBoxStream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use ts_rs::TS;
+
+use crate::SmartError;
+use super::models::{Exchange, QuotePrice};
+use super::quotemulti::request_multi_quote;
+use super::translate::parse_symbol;
+use super::utils::sleep;
+#[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+use super::clock::now_ms;
+#[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+use super::wsclient::{WsConnection, WsMessage};
+
+/*
+  Quote Stream
+  Live last price updates for a set of symbols as a stream, only emitting a quote when its price moves
+  With the websocket feature, exchanges with a public ticker socket (Binance family, ByBit, Coinbase, Kraken, OKX) are streamed
+  over that socket. Other exchanges, WASM builds and sockets that keep failing fall back to polling the REST multi quote endpoint,
+  which costs one request per poll against the exchange rate limits
+  A live spread keeps the spread and zscore of a pair current from these updates between bar closes
+*/
+
+/// Default milliseconds between quote polls
+pub const QUOTE_POLL_MS: u64 = 5000;
+
+/// Shortest allowed poll interval (one request per second per stream)
+pub const MIN_QUOTE_POLL_MS: u64 = 1000;
+
+/// Consecutive socket failures before a stream falls back to polling
+pub const SOCKET_MAX_FAILURES: u32 = 5;
+
+/// Base delay between socket reconnects (doubled per consecutive failure)
+pub const SOCKET_RECONNECT_MS: u64 = 1000;
+
+/// Interval between application level pings on feeds that require them
+pub const SOCKET_KEEPALIVE_MS: i64 = 20_000;
+
+struct StreamState {
+  exchange: Exchange,
+  symbols: Vec<String>,
+  twelve_api_key: Option<String>,
+  poll_ms: u64,
+  last: HashMap<String, f64>,
+  pending: VecDeque<QuotePrice>,
+  is_first_poll: bool
+}
+
+/// Changed Quotes
+/// Quotes whose price differs from the last seen price (first quotes always count), updating the last seen prices
+pub fn changed_quotes(last: &mut HashMap<String, f64>, quotes: Vec<QuotePrice>) -> Vec<QuotePrice> {
+  quotes.into_iter()
+    .filter(|quote| quote.price.is_finite())
+    .filter(|quote| {
+      let is_changed: bool = last.get(&quote.symbol) != Some(&quote.price);
+      last.insert(quote.symbol.clone(), quote.price);
+      is_changed
+    })
+    .collect()
+}
+
+/// Stream Quotes
+/// Endless stream of last price updates for the symbols, over the exchange ticker socket where available (websocket feature)
+/// and polled every QUOTE_POLL_MS otherwise, a socket that fails SOCKET_MAX_FAILURES times in a row hands over to polling
+pub fn stream_quotes(exchange: &Exchange, symbols: &[String], twelve_api_key: Option<&str>) -> LocalBoxStream<'static, QuotePrice> {
+  #[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+  if let Some(feed) = socket_feed(exchange, symbols) {
+    return socket_quotes(feed).chain(poll_quotes(exchange, symbols, twelve_api_key)).boxed_local();
+  }
+  poll_quotes(exchange, symbols, twelve_api_key).boxed_local()
+}
+
+/// Poll Quotes
+/// Endless stream of best price updates for the symbols, polled every QUOTE_POLL_MS
+pub fn poll_quotes(exchange: &Exchange, symbols: &[String], twelve_api_key: Option<&str>) -> impl Stream<Item = QuotePrice> {
+  poll_quotes_every(exchange, symbols, twelve_api_key, QUOTE_POLL_MS)
+}
+
+/// Poll Quotes Every
+/// As poll_quotes with a custom poll interval (at least MIN_QUOTE_POLL_MS), failed polls are retried on the next interval
+pub fn poll_quotes_every(exchange: &Exchange, symbols: &[String], twelve_api_key: Option<&str>, poll_ms: u64) -> impl Stream<Item = QuotePrice> {
+  let state: StreamState = StreamState {
+    exchange: exchange.clone(),
+    symbols: symbols.to_vec(),
+    twelve_api_key: twelve_api_key.map(|k| k.to_string()),
+    poll_ms: poll_ms.max(MIN_QUOTE_POLL_MS),
+    last: HashMap::new(),
+    pending: VecDeque::new(),
+    is_first_poll: true
+  };
+
+  futures::stream::unfold(state, |mut state| async move {
+    loop {
+      if let Some(quote) = state.pending.pop_front() {
+        return Some((quote, state));
+      }
+
+      // Guard: Nothing to poll
+      if state.symbols.is_empty() { return None }
+
+      if !state.is_first_poll { sleep(state.poll_ms).await; }
+      state.is_first_poll = false;
+
+      let symbols: Vec<&str> = state.symbols.iter().map(|s| s.as_str()).collect();
+      if let Ok(quotes) = request_multi_quote(&state.exchange, symbols, state.twelve_api_key.as_deref()).await {
+        let changed: Vec<QuotePrice> = changed_quotes(&mut state.last, quotes);
+        state.pending.extend(changed);
+      }
+    }
+  })
+}
+
+/*
+  Socket Feeds
+  Public ticker channel, subscription messages and message decoding per exchange
+  Quotes are reported under the symbols requested, whatever form the exchange uses on the wire
+*/
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SocketFeed {
+  pub exchange: Exchange,
+  pub url: String,
+  pub subscribe: Vec<String>, // sent after connecting
+  pub keepalive: Option<String>, // application ping sent every SOCKET_KEEPALIVE_MS
+  pub symbols: HashMap<String, String> // wire symbol to requested symbol
+}
+
+/// Socket Feed
+/// Ticker socket for the symbols on an exchange, None where the exchange has no supported socket
+pub fn socket_feed(exchange: &Exchange, symbols: &[String]) -> Option<SocketFeed> {
+
+  // Guard: Nothing to subscribe to
+  if symbols.is_empty() { return None }
+
+  let wire = |symbol: &String| -> Option<(String, String)> {
+    let wire_symbol: String = match exchange {
+      Exchange::Kraken => parse_symbol(symbol).ok().map(|p| format!("{}/{}", p.base, p.quote))?,
+      _ => symbol.to_string()
+    };
+    Some((wire_symbol, symbol.to_string()))
+  };
+  let wire_symbols: HashMap<String, String> = symbols.iter().filter_map(wire).collect();
+  let mut names: Vec<String> = wire_symbols.keys().cloned().collect();
+  names.sort();
+
+  let binance_url = |host: &str| format!(
+    "wss://{}/stream?streams={}", host, names.iter().map(|s| format!("{}@miniTicker", s.to_lowercase())).collect::<Vec<String>>().join("/")
+  );
+  let bybit_subscribe = || vec![serde_json::json!({ "op": "subscribe", "args": names.iter().map(|s| format!("tickers.{}", s)).collect::<Vec<String>>() }).to_string()];
+  let (url, subscribe, keepalive) = match exchange {
+    Exchange::Binance => (binance_url("fstream.binance.com"), vec![], None),
+    Exchange::BinanceCoinM => (binance_url("dstream.binance.com"), vec![], None),
+    Exchange::BinanceSpot => (binance_url("stream.binance.com:9443"), vec![], None),
+    Exchange::BinanceUs => (binance_url("stream.binance.us:9443"), vec![], None),
+    Exchange::ByBit => ("wss://stream.bybit.com/v5/public/linear".to_string(), bybit_subscribe(), Some(r#"{"op":"ping"}"#.to_string())),
+    Exchange::ByBitInverse => ("wss://stream.bybit.com/v5/public/inverse".to_string(), bybit_subscribe(), Some(r#"{"op":"ping"}"#.to_string())),
+    Exchange::Coinbase => (
+      "wss://ws-feed.exchange.coinbase.com".to_string(),
+      vec![serde_json::json!({ "type": "subscribe", "product_ids": names, "channels": ["ticker"] }).to_string()],
+      None
+    ),
+    Exchange::Kraken => (
+      "wss://ws.kraken.com/v2".to_string(),
+      vec![serde_json::json!({ "method": "subscribe", "params": { "channel": "ticker", "symbol": names } }).to_string()],
+      None
+    ),
+    Exchange::Okx => (
+      "wss://ws.okx.com:8443/ws/v5/public".to_string(),
+      vec![serde_json::json!({ "op": "subscribe", "args": names.iter().map(|s| serde_json::json!({ "channel": "tickers", "instId": s })).collect::<Vec<_>>() }).to_string()],
+      Some("ping".to_string())
+    ),
+    _ => return None
+  };
+  Some(SocketFeed { exchange: exchange.clone(), url, subscribe, keepalive, symbols: wire_symbols })
+}
+
+/// Json Price
+/// Price from a json string or number
+fn json_price(value: &serde_json::Value) -> Option<f64> {
+  value.as_f64().or_else(|| value.as_str().and_then(|s| s.parse::<f64>().ok())).filter(|p| p.is_finite())
+}
+
+impl SocketFeed {
+
+  /// Parse Quotes
+  /// Last prices in a feed message (subscription acks, heartbeats and pongs yield none)
+  pub fn parse_quotes(&self, text: &str) -> Vec<QuotePrice> {
+    let Ok(message) = serde_json::from_str::<serde_json::Value>(text) else { return vec![] };
+    let tickers: Vec<(&serde_json::Value, &serde_json::Value)> = match self.exchange {
+      Exchange::Binance | Exchange::BinanceCoinM | Exchange::BinanceSpot | Exchange::BinanceUs => {
+        let data: &serde_json::Value = &message["data"];
+        vec![(&data["s"], &data["c"])]
+      },
+      Exchange::ByBit | Exchange::ByBitInverse => {
+        let data: &serde_json::Value = &message["data"];
+        vec![(&data["symbol"], &data["lastPrice"])]
+      },
+      Exchange::Coinbase if message["type"] == "ticker" => vec![(&message["product_id"], &message["price"])],
+      Exchange::Kraken if message["channel"] == "ticker" => message["data"].as_array()
+        .map(|data| data.iter().map(|t| (&t["symbol"], &t["last"])).collect())
+        .unwrap_or_default(),
+      Exchange::Okx => message["data"].as_array()
+        .map(|data| data.iter().map(|t| (&t["instId"], &t["last"])).collect())
+        .unwrap_or_default(),
+      _ => vec![]
+    };
+    tickers.into_iter()
+      .filter_map(|(symbol, price)| {
+        let symbol: &String = self.symbols.get(symbol.as_str()?)?;
+        Some(QuotePrice { symbol: symbol.clone(), price: json_price(price)? })
+      })
+      .collect()
+  }
+}
+
+#[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+struct SocketState {
+  feed: SocketFeed,
+  connection: Option<WsConnection>,
+  last: HashMap<String, f64>,
+  pending: VecDeque<QuotePrice>,
+  failures: u32,
+  last_ping_ms: i64
+}
+
+#[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+impl SocketState {
+
+  /// Connect
+  /// Opens the socket and sends the subscriptions
+  async fn connect(&mut self) -> Result<(), SmartError> {
+    let mut connection: WsConnection = WsConnection::connect(&self.feed.url).await?;
+    for message in self.feed.subscribe.iter() {
+      connection.send_text(message).await?;
+    }
+    self.connection = Some(connection);
+    self.last_ping_ms = now_ms();
+    Ok(())
+  }
+
+  /// Receive
+  /// Reads the next message into the pending quotes, sending the keepalive ping when due
+  async fn receive(&mut self) -> Result<(), SmartError> {
+    let Some(connection) = self.connection.as_mut() else { return Ok(()) };
+    match connection.read_message().await? {
+      WsMessage::Text(text) => {
+        let quotes: Vec<QuotePrice> = self.feed.parse_quotes(&text);
+        if !quotes.is_empty() { self.failures = 0; }
+        self.pending.extend(changed_quotes(&mut self.last, quotes));
+      },
+      WsMessage::Close => return Err(SmartError::APIResponseStatus(format!("Quote socket closed by {:?}", self.feed.exchange)))
+    }
+    if let Some(ping) = self.feed.keepalive.as_deref() {
+      if now_ms() - self.last_ping_ms >= SOCKET_KEEPALIVE_MS {
+        connection.send_text(ping).await?;
+        self.last_ping_ms = now_ms();
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Socket Quotes
+/// Stream of changed last prices from an exchange socket, reconnecting with backoff and ending after SOCKET_MAX_FAILURES
+#[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+pub fn socket_quotes(feed: SocketFeed) -> impl Stream<Item = QuotePrice> {
+  let state: SocketState = SocketState { feed, connection: None, last: HashMap::new(), pending: VecDeque::new(), failures: 0, last_ping_ms: 0 };
+
+  futures::stream::unfold(state, |mut state| async move {
+    loop {
+      if let Some(quote) = state.pending.pop_front() {
+        return Some((quote, state));
+      }
+
+      // Guard: Hand over to polling once the socket keeps failing
+      if state.failures >= SOCKET_MAX_FAILURES { return None }
+
+      let result: Result<(), SmartError> = match state.connection {
+        Some(_) => state.receive().await,
+        None => state.connect().await
+      };
+      if result.is_err() {
+        if let Some(mut connection) = state.connection.take() { connection.close().await; }
+        state.failures += 1;
+        sleep(SOCKET_RECONNECT_MS << state.failures.min(6)).await;
+      }
+    }
+  })
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct LiveSpreadTick {
+  pub price_0: f64,
+  pub price_1: f64,
+  pub spread: f64,
+  pub zscore: f64 // against the last zscore_window closed spreads
+}
+
+/// Live Spread
+/// Spread (series_0 - hedge_ratio * series_1) and zscore of a pair updated from streamed quotes
+#[derive(Debug, Clone)]
+pub struct LiveSpread {
+  pub asset_0: String,
+  pub asset_1: String,
+  pub hedge_ratio: f64,
+  zscore_window: usize,
+  history: VecDeque<f64>,
+  price_0: Option<f64>,
+  price_1: Option<f64>
+}
+
+impl LiveSpread {
+
+  /// New
+  /// Seeds the spread history from the closed bars of both legs
+  pub fn new(asset_0: &str, asset_1: &str, series_0: &[f64], series_1: &[f64], hedge_ratio: f64, zscore_window: usize) -> Result<Self, SmartError> {
+
+    // Guard: Ensure enough aligned history for the window
+    if series_0.len() != series_1.len() {
+      return Err(SmartError::RuntimeCheck("Live spread series lengths differ".to_string()));
+    }
+    if zscore_window < 2 || series_0.len() < zscore_window {
+      return Err(SmartError::RuntimeCheck(format!("Live spread needs at least {} bars of history", zscore_window.max(2))));
+    }
+
+    let history: VecDeque<f64> = series_0.iter().zip(series_1.iter())
+      .skip(series_0.len() - zscore_window)
+      .map(|(p0, p1)| p0 - hedge_ratio * p1)
+      .collect();
+    Ok(Self {
+      asset_0: asset_0.to_string(),
+      asset_1: asset_1.to_string(),
+      hedge_ratio,
+      zscore_window,
+      history,
+      price_0: series_0.last().copied(),
+      price_1: series_1.last().copied()
+    })
+  }
+
+  /// Update
+  /// Applies a quote for either leg (other symbols are ignored) and returns the current spread and zscore
+  pub fn update(&mut self, quote: &QuotePrice) -> Option<LiveSpreadTick> {
+    if quote.symbol == self.asset_0 {
+      self.price_0 = Some(quote.price);
+    } else if quote.symbol == self.asset_1 {
+      self.price_1 = Some(quote.price);
+    } else {
+      return None;
+    }
+    self.tick()
+  }
+
+  /// Tick
+  /// Spread and zscore at the latest prices
+  pub fn tick(&self) -> Option<LiveSpreadTick> {
+    let (price_0, price_1) = (self.price_0?, self.price_1?);
+    let spread: f64 = price_0 - self.hedge_ratio * price_1;
+    let n: f64 = self.history.len() as f64;
+    let mean: f64 = self.history.iter().sum::<f64>() / n;
+    let std_dev: f64 = (self.history.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+
+    // Guard: Flat history has no zscore
+    if std_dev == 0.0 || !std_dev.is_finite() { return None }
+
+    Some(LiveSpreadTick { price_0, price_1, spread, zscore: (spread - mean) / std_dev })
+  }
+
+  /// Close Bar
+  /// Commits the spread at the latest prices to the history (call once per bar close)
+  pub fn close_bar(&mut self) {
+    if let (Some(price_0), Some(price_1)) = (self.price_0, self.price_1) {
+      self.history.push_back(price_0 - self.hedge_ratio * price_1);
+      while self.history.len() > self.zscore_window {
+        self.history.pop_front();
+      }
+    }
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use futures::StreamExt;
+
+  fn quote(symbol: &str, price: f64) -> QuotePrice {
+    QuotePrice { symbol: symbol.to_string(), price }
+  }
+
+  #[tokio::test]
+  async fn it_emits_only_changed_quotes() {
+    let mut last: HashMap<String, f64> = HashMap::new();
+    let first: Vec<QuotePrice> = changed_quotes(&mut last, vec![quote("ETHUSDT", 2000.0), quote("BTCUSDT", 40000.0)]);
+    assert_eq!(first.len(), 2);
+
+    let second: Vec<QuotePrice> = changed_quotes(&mut last, vec![quote("ETHUSDT", 2000.0), quote("BTCUSDT", 40010.0), quote("SOLUSDT", f64::NAN)]);
+    assert_eq!(second.len(), 1);
+    assert_eq!((second[0].symbol.as_str(), second[0].price), ("BTCUSDT", 40010.0));
+  }
+
+  #[tokio::test]
+  async fn it_tracks_live_spread_zscore() {
+    let series_0: Vec<f64> = vec![10.0, 11.0, 12.0, 11.0, 10.0];
+    let series_1: Vec<f64> = vec![5.0, 5.0, 5.0, 5.0, 5.0];
+    let mut live: LiveSpread = LiveSpread::new("ETHUSDT", "BTCUSDT", &series_0, &series_1, 1.0, 4).unwrap();
+
+    // History holds spreads 6, 7, 6, 5 (mean 6, std sqrt(2/3))
+    let tick: LiveSpreadTick = live.update(&quote("ETHUSDT", 12.0)).unwrap();
+    assert_eq!(tick.spread, 7.0);
+    assert!((tick.zscore - 1.0 / (2.0f64 / 3.0).sqrt()).abs() < 1e-9);
+    assert!(live.update(&quote("SOLUSDT", 100.0)).is_none());
+
+    // Closing the bar rolls the window forward
+    live.close_bar();
+    let rolled: LiveSpreadTick = live.tick().unwrap();
+    assert_eq!(rolled.spread, 7.0);
+    assert!(rolled.zscore > 0.0);
+
+    assert!(LiveSpread::new("ETHUSDT", "BTCUSDT", &series_0, &series_1[..4], 1.0, 4).is_err());
+    assert!(LiveSpread::new("ETHUSDT", "BTCUSDT", &series_0, &series_1, 1.0, 6).is_err());
+  }
+
+  #[tokio::test]
+  async fn it_ends_stream_without_symbols() {
+    let quotes: Vec<QuotePrice> = poll_quotes(&Exchange::Binance, &[], None).collect().await;
+    assert!(quotes.is_empty());
+    let quotes: Vec<QuotePrice> = stream_quotes(&Exchange::Binance, &[], None).collect().await;
+    assert!(quotes.is_empty());
+  }
+
+  #[tokio::test]
+  async fn it_builds_socket_feeds() {
+    let symbols: Vec<String> = vec!["ETHUSDT".to_string(), "BTCUSDT".to_string()];
+    let binance: SocketFeed = socket_feed(&Exchange::Binance, &symbols).unwrap();
+    assert_eq!(binance.url, "wss://fstream.binance.com/stream?streams=btcusdt@miniTicker/ethusdt@miniTicker");
+    assert!(binance.subscribe.is_empty());
+
+    let bybit: SocketFeed = socket_feed(&Exchange::ByBit, &symbols).unwrap();
+    assert_eq!(bybit.subscribe, vec![r#"{"args":["tickers.BTCUSDT","tickers.ETHUSDT"],"op":"subscribe"}"#.to_string()]);
+    assert!(bybit.keepalive.is_some());
+
+    let kraken: SocketFeed = socket_feed(&Exchange::Kraken, &["XBTUSD".to_string()]).unwrap();
+    assert_eq!(kraken.symbols.get("BTC/USD").map(|s| s.as_str()), Some("XBTUSD"));
+
+    assert!(socket_feed(&Exchange::Twelve, &symbols).is_none());
+    assert!(socket_feed(&Exchange::Binance, &[]).is_none());
+  }
+
+  #[tokio::test]
+  async fn it_parses_socket_ticker_messages() {
+    let parse = |exchange: Exchange, symbol: &str, text: &str| -> Vec<(String, f64)> {
+      socket_feed(&exchange, &[symbol.to_string()]).unwrap().parse_quotes(text).into_iter().map(|q| (q.symbol, q.price)).collect()
+    };
+    assert_eq!(
+      parse(Exchange::Binance, "BTCUSDT", r#"{"stream":"btcusdt@miniTicker","data":{"e":"24hrMiniTicker","s":"BTCUSDT","c":"40000.5"}}"#),
+      vec![("BTCUSDT".to_string(), 40000.5)]
+    );
+    assert_eq!(parse(Exchange::ByBit, "BTCUSDT", r#"{"topic":"tickers.BTCUSDT","type":"delta","data":{"symbol":"BTCUSDT","lastPrice":"40001"}}"#).len(), 1);
+    assert!(parse(Exchange::ByBit, "BTCUSDT", r#"{"topic":"tickers.BTCUSDT","type":"delta","data":{"symbol":"BTCUSDT","bid1Price":"40001"}}"#).is_empty());
+    assert_eq!(parse(Exchange::Coinbase, "BTC-USD", r#"{"type":"ticker","product_id":"BTC-USD","price":"39990.01"}"#)[0].1, 39990.01);
+    assert!(parse(Exchange::Coinbase, "BTC-USD", r#"{"type":"subscriptions","channels":[]}"#).is_empty());
+    assert_eq!(
+      parse(Exchange::Kraken, "XBTUSD", r#"{"channel":"ticker","type":"update","data":[{"symbol":"BTC/USD","last":39995.5}]}"#),
+      vec![("XBTUSD".to_string(), 39995.5)]
+    );
+    assert_eq!(parse(Exchange::Okx, "BTC-USDT-SWAP", r#"{"arg":{"channel":"tickers"},"data":[{"instId":"BTC-USDT-SWAP","last":"40002"}]}"#)[0].1, 40002.0);
+    assert!(parse(Exchange::Okx, "BTC-USDT-SWAP", "pong").is_empty());
+  }
+
+  #[tokio::test]
+  async fn tests_stream_quotes_binance() {
+    let symbols: Vec<String> = vec!["ETHUSDT".to_string(), "BTCUSDT".to_string()];
+    let stream = stream_quotes(&Exchange::Binance, &symbols, None).take(2).collect::<Vec<QuotePrice>>();
+    let quotes: Vec<QuotePrice> = tokio::time::timeout(std::time::Duration::from_secs(30), stream).await.unwrap();
+    assert_eq!(quotes.len(), 2);
+    assert!(quotes.iter().all(|q| q.price > 0.0));
+  }
+
+  #[tokio::test]
+  async fn tests_poll_quotes_binance() {
+    let symbols: Vec<String> = vec!["ETHUSDT".to_string(), "BTCUSDT".to_string()];
+    let stream = poll_quotes(&Exchange::Binance, &symbols, None).take(2).collect::<Vec<QuotePrice>>();
+    let quotes: Vec<QuotePrice> = tokio::time::timeout(std::time::Duration::from_secs(30), stream).await.unwrap();
+    assert_eq!(quotes.len(), 2);
+    assert!(quotes.iter().all(|q| q.price > 0.0));
+  }
+}
@@ -0,0 +1,233 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use super::clock::now_ms;
+use super::models::Exchange;
+
+/*
+  Request Weight Accounting
+  Tracks exchange rate limit usage from response headers
+  Allows requests to pause before a ban instead of relying on blind sleeps
+*/
+
+/// Fraction of the limit kept in reserve before requests are paused
+const SAFETY_RESERVE: f64 = 0.1;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct RequestBudget {
+  pub used: u32,
+  pub limit: u32,
+  pub remaining: u32,
+  pub reset_at_ms: i64 // unix time (ms) at which the budget window resets
+}
+
+static BUDGETS: RwLock<Option<HashMap<Exchange, RequestBudget>>> = RwLock::new(None);
+
+/// Tracks Request Weight
+/// Exchanges which report rate limit usage via response headers
+pub fn tracks_request_weight(exchange: &Exchange) -> bool {
+  matches!(exchange, Exchange::Binance | Exchange::BinanceCoinM | Exchange::BinanceSpot | Exchange::BinanceUs | Exchange::ByBit)
+}
+
+/// Default Weight Limit
+/// Per minute weight limit for Binance style accounting
+fn default_weight_limit(exchange: &Exchange) -> u32 {
+  match exchange {
+    Exchange::Binance | Exchange::BinanceCoinM => 2400,
+    Exchange::BinanceSpot => 6000,
+    Exchange::BinanceUs => 1200,
+    _ => 0
+  }
+}
+
+/// Header Value
+/// Extracts a numeric header value (case insensitive)
+fn header_value<T: std::str::FromStr>(headers: &reqwest::header::HeaderMap, name: &str) -> Option<T> {
+  headers.get(name).and_then(|v| v.to_str().ok()).and_then(|v| v.trim().parse::<T>().ok())
+}
+
+/// Next Minute
+/// Binance weights reset on the minute boundary
+fn next_minute_ms(now_ms: i64) -> i64 {
+  (now_ms / 60_000 + 1) * 60_000
+}
+
+/// Budget From Headers
+/// Structures the remaining budget for an exchange from its response headers
+pub fn budget_from_headers(exchange: &Exchange, headers: &reqwest::header::HeaderMap, now_ms: i64) -> Option<RequestBudget> {
+  match exchange {
+    Exchange::Binance | Exchange::BinanceCoinM | Exchange::BinanceSpot | Exchange::BinanceUs => {
+      let used: u32 = header_value(headers, "x-mbx-used-weight-1m")
+        .or_else(|| header_value(headers, "x-mbx-used-weight"))?;
+      let limit: u32 = default_weight_limit(exchange);
+      let mut reset_at_ms: i64 = next_minute_ms(now_ms);
+      if let Some(retry_after) = header_value::<i64>(headers, "retry-after") {
+        reset_at_ms = reset_at_ms.max(now_ms + retry_after * 1000);
+      }
+      Some(RequestBudget { used, limit, remaining: limit.saturating_sub(used), reset_at_ms })
+    },
+    Exchange::ByBit => {
+      let limit: u32 = header_value(headers, "x-bapi-limit")?;
+      let remaining: u32 = header_value(headers, "x-bapi-limit-status")?;
+      let reset_at_ms: i64 = header_value(headers, "x-bapi-limit-reset-timestamp").unwrap_or(now_ms + 1000);
+      Some(RequestBudget { used: limit.saturating_sub(remaining), limit, remaining, reset_at_ms })
+    },
+    _ => None
+  }
+}
+
+/// Record Response Headers
+/// Updates the tracked budget for the exchange the url belongs to
+pub fn record_response_headers(url: &str, headers: &reqwest::header::HeaderMap) {
+  let Some(exchange) = Exchange::from_url(url) else { return };
+  let now_ms: i64 = now_ms();
+  let Some(budget) = budget_from_headers(&exchange, headers, now_ms) else { return };
+  if let Ok(mut guard) = BUDGETS.write() {
+    guard.get_or_insert_with(HashMap::new).insert(exchange, budget);
+  }
+}
+
+/// Request Budget
+/// Returns the last known request budget for an exchange
+pub fn request_budget(exchange: &Exchange) -> Option<RequestBudget> {
+  BUDGETS.read().ok()
+    .and_then(|guard| guard.as_ref().and_then(|hm| hm.get(exchange).cloned()))
+}
+
+/// Required Pause
+/// Milliseconds to wait before the next request so the safety reserve is not breached
+pub fn required_pause_ms(budget: &RequestBudget, now_ms: i64) -> u64 {
+  if now_ms >= budget.reset_at_ms { return 0 }
+  let reserve: u32 = (budget.limit as f64 * SAFETY_RESERVE).ceil() as u32;
+  if budget.remaining > reserve { return 0 }
+  (budget.reset_at_ms - now_ms) as u64
+}
+
+/*
+  Call Quotas
+  Client side per minute call limits for providers which report no usage (e.g. Alpha Vantage free tier)
+  Each request reserves a send slot so concurrent callers queue rather than exhaust the quota
+*/
+
+/// Milliseconds in a call quota window
+const QUOTA_WINDOW_MS: i64 = 60_000;
+
+static CALL_QUOTAS: RwLock<Option<HashMap<Exchange, u32>>> = RwLock::new(None);
+static CALL_LOG: RwLock<Option<HashMap<Exchange, Vec<i64>>>> = RwLock::new(None);
+
+/// Default Calls Per Minute
+/// Per minute call quota for providers without rate limit headers
+fn default_calls_per_minute(exchange: &Exchange) -> Option<u32> {
+  match exchange {
+    Exchange::Alpaca => Some(200), // free data plan
+    Exchange::AlphaVantage => Some(5), // free tier
+    _ => None
+  }
+}
+
+/// Set Calls Per Minute
+/// Overrides the per minute call quota for an exchange (e.g. 75 for a premium Alpha Vantage key)
+pub fn set_calls_per_minute(exchange: Exchange, calls: u32) {
+  if let Ok(mut guard) = CALL_QUOTAS.write() {
+    guard.get_or_insert_with(HashMap::new).insert(exchange, calls);
+  }
+}
+
+/// Calls Per Minute
+/// Returns the configured per minute call quota for an exchange, falling back to its default
+pub fn calls_per_minute(exchange: &Exchange) -> Option<u32> {
+  CALL_QUOTAS.read().ok()
+    .and_then(|guard| guard.as_ref().and_then(|hm| hm.get(exchange).copied()))
+    .or_else(|| default_calls_per_minute(exchange))
+}
+
+/// Window Pause
+/// Milliseconds to wait so no more than the quota of calls fall in any one minute window
+/// Calls are send times (ms) in ascending order, including slots already reserved in the future
+pub fn window_pause_ms(calls: &[i64], quota: u32, now_ms: i64) -> u64 {
+  let recent: Vec<i64> = calls.iter().copied().filter(|t| *t > now_ms - QUOTA_WINDOW_MS).collect();
+  if quota == 0 || recent.len() < quota as usize { return 0 }
+  let oldest: i64 = recent[recent.len() - quota as usize];
+  (oldest + QUOTA_WINDOW_MS - now_ms).max(0) as u64
+}
+
+/// Reserve Slot
+/// Returns the pause before the next call and records its send time in the call log
+fn reserve_slot(calls: &mut Vec<i64>, quota: u32, now_ms: i64) -> u64 {
+  calls.retain(|t| *t > now_ms - QUOTA_WINDOW_MS);
+  let pause_ms: u64 = window_pause_ms(calls, quota, now_ms);
+  calls.push(now_ms + pause_ms as i64);
+  pause_ms
+}
+
+/// Reserve Call Slot
+/// Reserves the next send slot for an exchange
+fn reserve_call_slot(exchange: &Exchange, quota: u32, now_ms: i64) -> u64 {
+  let Ok(mut guard) = CALL_LOG.write() else { return 0 };
+  let calls: &mut Vec<i64> = guard.get_or_insert_with(HashMap::new).entry(exchange.clone()).or_default();
+  reserve_slot(calls, quota, now_ms)
+}
+
+/// Pause Duration For URL
+/// Milliseconds to wait before calling the given url (0 if budget is healthy or unknown)
+/// Exchanges with a call quota also reserve their send slot here
+pub fn pause_for_url_ms(url: &str) -> u64 {
+  let Some(exchange) = Exchange::from_url(url) else { return 0 };
+  let now_ms: i64 = now_ms();
+  let budget_pause: u64 = request_budget(&exchange).map(|budget| required_pause_ms(&budget, now_ms)).unwrap_or(0);
+  let quota_pause: u64 = match calls_per_minute(&exchange) {
+    Some(quota) => reserve_call_slot(&exchange, quota, now_ms + budget_pause as i64),
+    None => 0
+  };
+  budget_pause + quota_pause
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use reqwest::header::{HeaderMap, HeaderValue};
+
+  #[tokio::test]
+  async fn it_reads_binance_weight_headers() {
+    let mut headers: HeaderMap = HeaderMap::new();
+    headers.insert("X-MBX-USED-WEIGHT-1M", HeaderValue::from_static("2300"));
+    let budget: RequestBudget = budget_from_headers(&Exchange::Binance, &headers, 1_700_000_010_000).unwrap();
+    assert_eq!(budget.remaining, 100);
+    assert_eq!(budget.reset_at_ms, 1_700_000_040_000);
+    assert_eq!(required_pause_ms(&budget, 1_700_000_010_000), 30_000);
+  }
+
+  #[tokio::test]
+  async fn it_reads_bybit_limit_headers() {
+    let mut headers: HeaderMap = HeaderMap::new();
+    headers.insert("X-Bapi-Limit", HeaderValue::from_static("120"));
+    headers.insert("X-Bapi-Limit-Status", HeaderValue::from_static("100"));
+    headers.insert("X-Bapi-Limit-Reset-Timestamp", HeaderValue::from_static("1700000001000"));
+    let budget: RequestBudget = budget_from_headers(&Exchange::ByBit, &headers, 1_700_000_000_000).unwrap();
+    assert_eq!(budget.used, 20);
+    assert_eq!(required_pause_ms(&budget, 1_700_000_000_000), 0);
+  }
+
+  #[tokio::test]
+  async fn it_paces_calls_to_the_minute_quota() {
+    let calls: Vec<i64> = vec![0, 10_000, 20_000, 30_000, 40_000];
+    assert_eq!(window_pause_ms(&calls, 5, 50_000), 10_000);
+    assert_eq!(window_pause_ms(&calls[1..], 5, 50_000), 0);
+    assert_eq!(window_pause_ms(&calls, 5, 61_000), 0); // first call has left the window
+
+    // Reserved slots queue behind one another
+    let mut log: Vec<i64> = vec![];
+    assert_eq!(reserve_slot(&mut log, 2, 1_000), 0);
+    assert_eq!(reserve_slot(&mut log, 2, 2_000), 0);
+    assert_eq!(reserve_slot(&mut log, 2, 3_000), 58_000);
+    assert_eq!(reserve_slot(&mut log, 2, 3_000), 59_000);
+
+    let exchange: Exchange = Exchange::AlphaVantage;
+    assert_eq!(calls_per_minute(&exchange), Some(5));
+    set_calls_per_minute(exchange.clone(), 75);
+    assert_eq!(calls_per_minute(&exchange), Some(75));
+    set_calls_per_minute(exchange, 5);
+  }
+}
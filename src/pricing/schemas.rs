@@ -0,0 +1,973 @@
+use serde::de::{self, DeserializeOwned, Deserializer, IgnoredAny, SeqAccess, Visitor};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::SmartError;
+use super::models::Exchange;
+
+/*
+  Response Schemas
+  Typed exchange responses for quotes and candles
+  Missing or malformed fields fail with a schema mismatch naming the field rather than defaulting to zero
+*/
+
+/// Num
+/// Number sent either as a json number or a numeric string (most exchanges quote prices as strings)
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(try_from = "NumRepr")]
+pub struct Num(pub f64);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumRepr {
+  Str(String),
+  Num(f64)
+}
+
+impl TryFrom<NumRepr> for Num {
+  type Error = String;
+  fn try_from(repr: NumRepr) -> Result<Self, Self::Error> {
+    match repr {
+      NumRepr::Num(n) => Ok(Num(n)),
+      NumRepr::Str(s) => s.parse::<f64>().map(Num).map_err(|_| format!("invalid number \"{}\"", s))
+    }
+  }
+}
+
+/// Candle Row
+/// Positional candle array ordered [time, open, high, low, close, volume, ...] (Binance, ByBit and OKX layouts)
+/// Time is left in the exchange's native unit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CandleRow {
+  pub time: u64,
+  pub open: f64,
+  pub high: f64,
+  pub low: f64,
+  pub close: f64,
+  pub volume: f64
+}
+
+impl CandleRow {
+
+  /// From Fields
+  /// Bar from keyed fields, open, high and low fall back to the close and volume to zero where the provider omits them
+  pub fn from_fields(time: u64, open: Option<Num>, high: Option<Num>, low: Option<Num>, close: f64, volume: Option<Num>) -> Self {
+    let or_close = |field: Option<Num>| field.map(|n| n.0).unwrap_or(close);
+    Self { time, open: or_close(open), high: or_close(high), low: or_close(low), close, volume: volume.map(|n| n.0).unwrap_or(0.0) }
+  }
+}
+
+/// Row Layout
+/// Positions of open, high, low, close and volume within a positional candle array (time is always index 0)
+struct RowLayout {
+  open: usize,
+  high: usize,
+  low: usize,
+  close: usize,
+  volume: usize
+}
+
+const STANDARD_LAYOUT: RowLayout = RowLayout { open: 1, high: 2, low: 3, close: 4, volume: 5 };
+const OPEN_CLOSE_LAYOUT: RowLayout = RowLayout { open: 1, close: 2, high: 3, low: 4, volume: 5 };
+const COINBASE_LAYOUT: RowLayout = RowLayout { low: 1, high: 2, open: 3, close: 4, volume: 5 };
+const KRAKEN_LAYOUT: RowLayout = RowLayout { open: 1, high: 2, low: 3, close: 4, volume: 6 };
+
+/// Row Visitor
+/// Reads time at index 0 and the price and volume fields at the positions given by the layout
+struct RowVisitor {
+  layout: RowLayout
+}
+
+impl<'de> Visitor<'de> for RowVisitor {
+  type Value = CandleRow;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "a candle array with time at index 0 and close at index {}", self.layout.close)
+  }
+
+  fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<CandleRow, A::Error> {
+    let time: Num = seq.next_element()?.ok_or_else(|| de::Error::missing_field("time [0]"))?;
+    let layout: &RowLayout = &self.layout;
+    let last: usize = layout.open.max(layout.high).max(layout.low).max(layout.close).max(layout.volume);
+    let mut fields: Vec<f64> = vec![0.0; last + 1];
+    for (i, field) in fields.iter_mut().enumerate().skip(1) {
+      let used: bool = [layout.open, layout.high, layout.low, layout.close, layout.volume].contains(&i);
+      match used {
+        true => *field = seq.next_element::<Num>()?.ok_or_else(|| de::Error::invalid_length(i, &self))?.0,
+        false => { seq.next_element::<IgnoredAny>()?.ok_or_else(|| de::Error::invalid_length(i, &self))?; }
+      }
+    }
+    while seq.next_element::<IgnoredAny>()?.is_some() {}
+    Ok(CandleRow {
+      time: time.0 as u64,
+      open: fields[layout.open],
+      high: fields[layout.high],
+      low: fields[layout.low],
+      close: fields[layout.close],
+      volume: fields[layout.volume]
+    })
+  }
+}
+
+impl<'de> Deserialize<'de> for CandleRow {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_seq(RowVisitor { layout: STANDARD_LAYOUT })
+  }
+}
+
+/// Open Close Row
+/// Positional candle array ordered [time, open, close, high, low, volume, ...] (KuCoin and Bitfinex layouts)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpenCloseRow(pub CandleRow);
+
+impl<'de> Deserialize<'de> for OpenCloseRow {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_seq(RowVisitor { layout: OPEN_CLOSE_LAYOUT }).map(OpenCloseRow)
+  }
+}
+
+/// Coinbase Row
+/// Positional candle array ordered [time, low, high, open, close, volume]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoinbaseRow(pub CandleRow);
+
+impl<'de> Deserialize<'de> for CoinbaseRow {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_seq(RowVisitor { layout: COINBASE_LAYOUT }).map(CoinbaseRow)
+  }
+}
+
+/// Kraken Row
+/// Positional candle array ordered [time, open, high, low, close, vwap, volume, count]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KrakenRow(pub CandleRow);
+
+impl<'de> Deserialize<'de> for KrakenRow {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_seq(RowVisitor { layout: KRAKEN_LAYOUT }).map(KrakenRow)
+  }
+}
+
+/// Alpaca Bars
+/// Stock bars are listed directly, crypto bars are keyed by symbol (bars are null when none fall in the window)
+#[derive(Debug, Deserialize)]
+pub struct AlpacaBars {
+  pub bars: Option<AlpacaBarSet>
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum AlpacaBarSet {
+  Stock(Vec<AlpacaBar>),
+  Crypto(HashMap<String, Vec<AlpacaBar>>)
+}
+
+impl AlpacaBars {
+
+  /// Into Bars
+  /// Bars for the requested symbol ordered oldest first
+  pub fn into_bars(self, symbol: &str) -> Vec<AlpacaBar> {
+    match self.bars {
+      Some(AlpacaBarSet::Stock(bars)) => bars,
+      Some(AlpacaBarSet::Crypto(mut by_symbol)) => by_symbol.remove(symbol).unwrap_or_default(),
+      None => vec![]
+    }
+  }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AlpacaBar {
+  pub t: String, // RFC-3339 open time
+  #[serde(default)]
+  pub o: Option<Num>,
+  #[serde(default)]
+  pub h: Option<Num>,
+  #[serde(default)]
+  pub l: Option<Num>,
+  pub c: Num,
+  #[serde(default)]
+  pub v: Option<Num>
+}
+
+/// Alpaca Latest Quotes
+/// Latest bid and ask keyed by symbol (stocks and crypto)
+#[derive(Debug, Deserialize)]
+pub struct AlpacaLatestQuotes {
+  pub quotes: HashMap<String, AlpacaQuote>
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AlpacaQuote {
+  pub ap: Num,
+  pub bp: Num
+}
+
+impl AlpacaQuote {
+
+  /// Price
+  /// Mid price, or the one side quoted when the other is empty (e.g. stocks outside market hours)
+  pub fn price(&self) -> Option<f64> {
+    match (self.ap.0 > 0.0, self.bp.0 > 0.0) {
+      (true, true) => Some((self.ap.0 + self.bp.0) / 2.0),
+      (true, false) => Some(self.ap.0),
+      (false, true) => Some(self.bp.0),
+      (false, false) => None
+    }
+  }
+}
+
+/// Alpha Vantage Series
+/// Bars keyed by timestamp under a function specific key (e.g. "Time Series (5min)", "Time Series FX (Daily)")
+#[derive(Debug, Deserialize)]
+pub struct AlphaVantageSeries {
+  #[serde(rename = "Meta Data")]
+  pub meta: HashMap<String, String>,
+  #[serde(flatten)]
+  pub series: HashMap<String, BTreeMap<String, AlphaVantageBar>>
+}
+
+impl AlphaVantageSeries {
+
+  /// Time Zone
+  /// Time zone the bar timestamps are reported in (e.g. US/Eastern for stocks, UTC for forex)
+  pub fn time_zone(&self) -> Option<&str> {
+    self.meta.iter().find(|(key, _)| key.ends_with("Time Zone")).map(|(_, zone)| zone.as_str())
+  }
+
+  /// Into Bars
+  /// Bars ordered oldest first
+  pub fn into_bars(self) -> Result<BTreeMap<String, AlphaVantageBar>, SmartError> {
+    let bars = self.series.into_iter().find(|(key, _)| key.starts_with("Time Series")).map(|(_, bars)| bars);
+    required(bars, &Exchange::AlphaVantage, "Time Series")
+  }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AlphaVantageBar {
+  #[serde(default, rename = "1. open")]
+  pub open: Option<Num>,
+  #[serde(default, rename = "2. high")]
+  pub high: Option<Num>,
+  #[serde(default, rename = "3. low")]
+  pub low: Option<Num>,
+  #[serde(rename = "4. close")]
+  pub close: Num,
+  #[serde(default, rename = "5. volume")]
+  pub volume: Option<Num> // absent for forex
+}
+
+/// Alpha Vantage Quote
+/// Global quote for stocks or realtime exchange rate for forex
+#[derive(Debug, Deserialize)]
+pub struct AlphaVantageQuote {
+  #[serde(rename = "Global Quote")]
+  pub global_quote: Option<AlphaVantageGlobalQuote>,
+  #[serde(rename = "Realtime Currency Exchange Rate")]
+  pub exchange_rate: Option<AlphaVantageRate>
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AlphaVantageGlobalQuote {
+  #[serde(rename = "05. price")]
+  pub price: Num
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AlphaVantageRate {
+  #[serde(rename = "5. Exchange Rate")]
+  pub rate: Num
+}
+
+/// Binance Ticker
+/// Price ticker (Binance, Binance US and COIN-M)
+#[derive(Debug, Deserialize)]
+pub struct BinanceTicker {
+  pub symbol: String,
+  pub price: Num
+}
+
+/// Binance Ticker 24h
+/// 24 hour rolling window ticker (Binance and Binance US)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceTicker24h {
+  pub symbol: String,
+  pub quote_volume: Num
+}
+
+/// Binance Depth
+/// Order book snapshot, each level is [price, qty] (Binance, Binance Spot and Binance US)
+#[derive(Debug, Deserialize)]
+pub struct BinanceDepth {
+  pub bids: Vec<(Num, Num)>,
+  pub asks: Vec<(Num, Num)>
+}
+
+/// Binance Symbol
+/// Exchange info symbol (COIN-M reports contractStatus rather than status)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceSymbol {
+  pub symbol: String,
+  #[serde(default)]
+  pub status: Option<String>,
+  #[serde(default)]
+  pub contract_status: Option<String>
+}
+
+/// Binance Exchange Info
+/// Envelope for exchange info, the symbols array is streamed separately
+#[derive(Debug, Deserialize)]
+pub struct BinanceExchangeInfo {
+  pub symbols: Vec<BinanceSymbol>
+}
+
+/// Bitfinex Ticker
+/// Trading pair ticker row [SYMBOL, BID, BID_SIZE, ASK, ASK_SIZE, DAILY_CHANGE, DAILY_CHANGE_RELATIVE, LAST_PRICE, ...]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitfinexTicker {
+  pub symbol: String,
+  pub last_price: f64
+}
+
+impl<'de> Deserialize<'de> for BitfinexTicker {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    struct TickerVisitor;
+
+    impl<'de> Visitor<'de> for TickerVisitor {
+      type Value = BitfinexTicker;
+
+      fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a ticker array with symbol at index 0 and last price at index 7")
+      }
+
+      fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<BitfinexTicker, A::Error> {
+        let symbol: String = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        for i in 1..7 {
+          seq.next_element::<IgnoredAny>()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        let last_price: Num = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(7, &self))?;
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(BitfinexTicker { symbol, last_price: last_price.0 })
+      }
+    }
+
+    deserializer.deserialize_seq(TickerVisitor)
+  }
+}
+
+/// ByBit Response
+/// Envelope for all ByBit v5 responses (result is empty when retCode is non zero)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ByBitResponse {
+  pub ret_code: i64,
+  pub ret_msg: String,
+  #[serde(default)]
+  pub result: serde_json::Value
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ByBitList<T> {
+  pub list: Vec<T>
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ByBitTicker {
+  pub symbol: String,
+  pub last_price: Num
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ByBitTicker24h {
+  pub symbol: String,
+  pub turnover24h: Num
+}
+
+/// ByBit Book
+/// Order book result, each level is [price, size]
+#[derive(Debug, Deserialize)]
+pub struct ByBitBook {
+  pub b: Vec<(Num, Num)>,
+  pub a: Vec<(Num, Num)>
+}
+
+impl ByBitResponse {
+
+  /// Into List
+  /// Decodes the result list or returns the exchange's error message
+  pub fn into_list<T: DeserializeOwned>(self) -> Result<Vec<T>, SmartError> {
+    if self.ret_code != 0 {
+      return Err(SmartError::APIResponseStatus(format!("ByBit error {}: {}", self.ret_code, self.ret_msg)));
+    }
+    let list: ByBitList<T> = decode(&Exchange::ByBit, self.result)?;
+    Ok(list.list)
+  }
+
+  /// Into Result
+  /// Decodes a result object (order book) or returns the exchange's error message
+  pub fn into_result<T: DeserializeOwned>(self) -> Result<T, SmartError> {
+    if self.ret_code != 0 {
+      return Err(SmartError::APIResponseStatus(format!("ByBit error {}: {}", self.ret_code, self.ret_msg)));
+    }
+    decode(&Exchange::ByBit, self.result)
+  }
+}
+
+/// Coinbase Book
+/// Level 1 order book, each level is [price, size, num_orders]
+#[derive(Debug, Deserialize)]
+pub struct CoinbaseBook {
+  pub asks: Vec<Vec<Num>>
+}
+
+/// Dydx Markets
+/// v4 indexer perpetual markets keyed by ticker
+#[derive(Debug, Deserialize)]
+pub struct DydxMarkets {
+  pub markets: HashMap<String, DydxMarket>
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DydxMarket {
+  pub ticker: String,
+  pub status: String, // ACTIVE, PAUSED, CANCEL_ONLY, POST_ONLY, INITIALIZING, FINAL_SETTLEMENT
+  pub oracle_price: Num
+}
+
+/// Gate Candle
+/// USDT futures candlestick (time in seconds)
+#[derive(Debug, Deserialize)]
+pub struct GateCandle {
+  pub t: Num,
+  #[serde(default)]
+  pub o: Option<Num>,
+  #[serde(default)]
+  pub h: Option<Num>,
+  #[serde(default)]
+  pub l: Option<Num>,
+  pub c: Num,
+  #[serde(default)]
+  pub v: Option<Num> // contracts traded
+}
+
+/// Gate Ticker
+#[derive(Debug, Deserialize)]
+pub struct GateTicker {
+  pub contract: String,
+  pub last: Num
+}
+
+/// Gate Contract
+#[derive(Debug, Deserialize)]
+pub struct GateContract {
+  pub name: String,
+  #[serde(default)]
+  pub in_delisting: bool
+}
+
+/// Hyperliquid Candle
+/// Perpetual candle snapshot (open time in milliseconds)
+#[derive(Debug, Deserialize)]
+pub struct HyperliquidCandle {
+  pub t: u64,
+  #[serde(default)]
+  pub o: Option<Num>,
+  #[serde(default)]
+  pub h: Option<Num>,
+  #[serde(default)]
+  pub l: Option<Num>,
+  pub c: Num,
+  #[serde(default)]
+  pub v: Option<Num>
+}
+
+/// Hyperliquid Mids
+/// Mid price per coin from allMids
+pub type HyperliquidMids = HashMap<String, Num>;
+
+/// Hyperliquid Meta
+/// Perpetuals universe
+#[derive(Debug, Deserialize)]
+pub struct HyperliquidMeta {
+  pub universe: Vec<HyperliquidAsset>
+}
+
+/// Hyperliquid Asset
+#[derive(Debug, Deserialize)]
+pub struct HyperliquidAsset {
+  pub name: String,
+  #[serde(default, rename = "isDelisted")]
+  pub is_delisted: bool
+}
+
+/// Kraken Response
+/// Envelope for all Kraken public responses, errors are reported with a 200 status
+#[derive(Debug, Deserialize)]
+pub struct KrakenResponse {
+  pub error: Vec<String>,
+  #[serde(default)]
+  pub result: serde_json::Value
+}
+
+impl KrakenResponse {
+
+  /// Into Result
+  /// Decodes the result or returns the exchange's error messages
+  pub fn into_result<T: DeserializeOwned>(self) -> Result<T, SmartError> {
+    if !self.error.is_empty() {
+      return Err(SmartError::APIResponseStatus(format!("Kraken error: {}", self.error.join(", "))));
+    }
+    decode(&Exchange::Kraken, self.result)
+  }
+
+  /// Into Candles
+  /// OHLC results are keyed by Kraken's internal pair name alongside a `last` cursor
+  pub fn into_candles(self) -> Result<Vec<CandleRow>, SmartError> {
+    let mut result: HashMap<String, serde_json::Value> = self.into_result()?;
+    result.remove("last");
+    let rows: serde_json::Value = required(result.into_values().next(), &Exchange::Kraken, "result.<pair>")?;
+    let rows: Vec<KrakenRow> = decode(&Exchange::Kraken, rows)?;
+    Ok(rows.into_iter().map(|row| row.0).collect())
+  }
+}
+
+/// Kraken Ticker
+/// Last trade closed is `c` as [price, lot volume]
+#[derive(Debug, Deserialize)]
+pub struct KrakenTicker {
+  pub c: Vec<Num>
+}
+
+/// Kraken Asset Pair
+#[derive(Debug, Deserialize)]
+pub struct KrakenAssetPair {
+  pub altname: String,
+  #[serde(default)]
+  pub status: Option<String>
+}
+
+/// Kraken Pair Matches
+/// Whether a result key (e.g. XXBTZUSD for legacy pairs) refers to the requested altname (XBTUSD)
+pub fn kraken_pair_matches(key: &str, altname: &str) -> bool {
+  if key == altname { return true }
+  key.len() == 8 && key.starts_with('X') && key[5..].len() == 3 && format!("{}{}", &key[1..4], &key[5..]) == altname
+}
+
+/// KuCoin Response
+/// Envelope for all KuCoin responses, success is code 200000
+#[derive(Debug, Deserialize)]
+pub struct KuCoinResponse {
+  pub code: String,
+  #[serde(default)]
+  pub msg: Option<String>,
+  #[serde(default)]
+  pub data: serde_json::Value
+}
+
+impl KuCoinResponse {
+
+  /// Into Data
+  /// Decodes the data or returns the exchange's error message
+  pub fn into_data<T: DeserializeOwned>(self) -> Result<T, SmartError> {
+    if self.code != "200000" {
+      return Err(SmartError::APIResponseStatus(format!("KuCoin error {}: {}", self.code, self.msg.unwrap_or_default())));
+    }
+    decode(&Exchange::KuCoin, self.data)
+  }
+}
+
+/// KuCoin Level 1
+/// Best bid and ask with the last traded price
+#[derive(Debug, Deserialize)]
+pub struct KuCoinLevel1 {
+  pub price: Num
+}
+
+/// KuCoin All Tickers
+#[derive(Debug, Deserialize)]
+pub struct KuCoinAllTickers {
+  pub ticker: Vec<KuCoinTicker>
+}
+
+/// KuCoin Ticker
+/// Last is null for pairs that have not traded in the last 24h
+#[derive(Debug, Deserialize)]
+pub struct KuCoinTicker {
+  pub symbol: String,
+  pub last: Option<Num>
+}
+
+/// KuCoin Symbol
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KuCoinSymbol {
+  pub symbol: String,
+  pub enable_trading: bool
+}
+
+/// Okx Response
+/// Envelope for all OKX v5 responses, errors are reported with a non zero code and empty data
+#[derive(Debug, Deserialize)]
+pub struct OkxResponse {
+  pub code: String,
+  pub msg: String,
+  #[serde(default)]
+  pub data: serde_json::Value
+}
+
+impl OkxResponse {
+
+  /// Into List
+  /// Decodes the data list or returns the exchange's error message
+  pub fn into_list<T: DeserializeOwned>(self) -> Result<Vec<T>, SmartError> {
+    if self.code != "0" {
+      return Err(SmartError::APIResponseStatus(format!("OKX error {}: {}", self.code, self.msg)));
+    }
+    decode(&Exchange::Okx, self.data)
+  }
+}
+
+/// Okx Ticker
+/// 24h ticker, volume in the traded currency (base for SWAP contracts)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxTicker {
+  pub inst_id: String,
+  pub last: Num,
+  #[serde(default)]
+  pub vol_ccy24h: Option<Num>
+}
+
+/// Okx Instrument
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxInstrument {
+  pub inst_id: String,
+  pub state: String
+}
+
+/// Tiingo Bar
+/// End of day, IEX intraday and crypto price row (ordered oldest first)
+#[derive(Debug, Deserialize)]
+pub struct TiingoBar {
+  pub date: String, // ISO-8601 open time in UTC
+  #[serde(default)]
+  pub open: Option<Num>,
+  #[serde(default)]
+  pub high: Option<Num>,
+  #[serde(default)]
+  pub low: Option<Num>,
+  pub close: Num,
+  #[serde(default)]
+  pub volume: Option<Num>
+}
+
+/// Tiingo Crypto Prices
+/// Crypto bars are wrapped per ticker
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TiingoCryptoPrices {
+  pub ticker: String,
+  #[serde(default)]
+  pub price_data: Vec<TiingoBar>
+}
+
+/// Tiingo IEX Quote
+/// Stock top of book, tngoLast is the last trade (or mid when no trade has printed)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TiingoIexQuote {
+  pub ticker: String,
+  pub tngo_last: Option<Num>
+}
+
+/// Tiingo Crypto Top
+/// Crypto top of book per ticker
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TiingoCryptoTop {
+  pub ticker: String,
+  #[serde(default)]
+  pub top_of_book_data: Vec<TiingoTopOfBook>
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TiingoTopOfBook {
+  pub last_price: Option<Num>
+}
+
+/// Twelve Price
+#[derive(Debug, Deserialize)]
+pub struct TwelvePrice {
+  pub price: Num
+}
+
+/// Twelve Series
+/// Time series values, newest first
+#[derive(Debug, Deserialize)]
+pub struct TwelveSeries {
+  pub values: Vec<TwelveValue>
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TwelveValue {
+  pub datetime: String,
+  #[serde(default)]
+  pub open: Option<Num>,
+  #[serde(default)]
+  pub high: Option<Num>,
+  #[serde(default)]
+  pub low: Option<Num>,
+  pub close: Num,
+  #[serde(default)]
+  pub volume: Option<Num> // absent for forex
+}
+
+/// Yahoo Chart
+/// Chart api response, result is null and error set for unknown symbols
+#[derive(Debug, Deserialize)]
+pub struct YahooChart {
+  pub chart: YahooChartBody
+}
+
+#[derive(Debug, Deserialize)]
+pub struct YahooChartBody {
+  pub result: Option<Vec<YahooResult>>
+}
+
+#[derive(Debug, Deserialize)]
+pub struct YahooResult {
+  pub meta: YahooMeta,
+  #[serde(default)]
+  pub timestamp: Vec<u64>, // absent when no bars fall in the window
+  pub indicators: YahooIndicators
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YahooMeta {
+  pub regular_market_price: Option<Num>,
+  #[serde(default)]
+  pub gmtoffset: i64 // exchange utc offset in seconds
+}
+
+#[derive(Debug, Deserialize)]
+pub struct YahooIndicators {
+  pub quote: Vec<YahooQuoteSeries>
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct YahooQuoteSeries {
+  #[serde(default)]
+  pub open: Vec<Option<f64>>,
+  #[serde(default)]
+  pub high: Vec<Option<f64>>,
+  #[serde(default)]
+  pub low: Vec<Option<f64>>,
+  #[serde(default)]
+  pub close: Vec<Option<f64>>, // null for bars without trades
+  #[serde(default)]
+  pub volume: Vec<Option<f64>>
+}
+
+impl YahooChart {
+
+  /// Into Result
+  /// First (only) chart result
+  pub fn into_result(self) -> Result<YahooResult, SmartError> {
+    let result: Option<YahooResult> = self.chart.result.and_then(|results| results.into_iter().next());
+    required(result, &Exchange::Yahoo, "chart.result")
+  }
+}
+
+impl YahooResult {
+
+  /// Into Bars
+  /// Bars ordered oldest first (time in seconds), skipping bars without a close
+  pub fn into_bars(self) -> Vec<CandleRow> {
+    let quote: YahooQuoteSeries = self.indicators.quote.into_iter().next().unwrap_or_default();
+    let field = |values: &[Option<f64>], i: usize| values.get(i).copied().flatten().map(Num);
+    self.timestamp.into_iter().enumerate()
+      .filter_map(|(i, time)| {
+        let close: f64 = field(&quote.close, i)?.0;
+        Some(CandleRow::from_fields(time, field(&quote.open, i), field(&quote.high, i), field(&quote.low, i), close, field(&quote.volume, i)))
+      })
+      .collect()
+  }
+}
+
+/// Yahoo Error
+/// Chart errors (unknown or delisted symbol) are reported alongside a null result
+#[derive(Debug, Deserialize)]
+struct YahooError {
+  chart: YahooErrorBody
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooErrorBody {
+  error: Option<YahooErrorDetail>
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooErrorDetail {
+  description: String
+}
+
+/// Twelve Error
+/// Twelve reports errors (bad symbol, credits exhausted) with a 200 status
+#[derive(Debug, Deserialize)]
+struct TwelveError {
+  status: String,
+  message: Option<String>
+}
+
+/// Decode
+/// Deserializes a response into its schema, naming the offending field on mismatch
+pub fn decode<T: DeserializeOwned>(exchange: &Exchange, data_obj: serde_json::Value) -> Result<T, SmartError> {
+
+  // Guard: Surface Bitfinex error payloads (["error", code, message])
+  if exchange == &Exchange::Bitfinex && data_obj.get(0).and_then(|v| v.as_str()) == Some("error") {
+    let message: &str = data_obj.get(2).and_then(|v| v.as_str()).unwrap_or("Bitfinex error");
+    return Err(SmartError::APIResponseStatus(message.to_string()));
+  }
+
+  // Guard: Surface Alpha Vantage errors and call quota notices (also sent with a 200 status)
+  if exchange == &Exchange::AlphaVantage {
+    let notice: Option<&str> = ["Error Message", "Note", "Information"].iter()
+      .find_map(|key| data_obj.get(*key).and_then(|v| v.as_str()));
+    if let Some(message) = notice {
+      return Err(SmartError::APIResponseStatus(message.to_string()));
+    }
+  }
+
+  // Guard: Surface Twelve error payloads rather than reporting a schema mismatch
+  if exchange == &Exchange::Twelve {
+    if let Ok(err) = serde_json::from_value::<TwelveError>(data_obj.clone()) {
+      if err.status == "error" {
+        return Err(SmartError::APIResponseStatus(err.message.unwrap_or_else(|| "Twelve error".to_string())));
+      }
+    }
+  }
+
+  // Guard: Surface Tiingo error details (unknown ticker, invalid token)
+  if exchange == &Exchange::Tiingo {
+    if let Some(detail) = data_obj.get("detail").and_then(|v| v.as_str()) {
+      return Err(SmartError::APIResponseStatus(detail.to_string()));
+    }
+  }
+
+  // Guard: Surface Yahoo chart errors
+  if exchange == &Exchange::Yahoo {
+    if let Ok(YahooError { chart: YahooErrorBody { error: Some(err) } }) = serde_json::from_value::<YahooError>(data_obj.clone()) {
+      return Err(SmartError::APIResponseStatus(err.description));
+    }
+  }
+
+  serde_json::from_value(data_obj)
+    .map_err(|e| SmartError::SchemaMismatch(format!("{}: {}", exchange.as_string(), e)))
+}
+
+/// Required
+/// Fails with a schema mismatch when an expected element is absent
+pub fn required<T>(value: Option<T>, exchange: &Exchange, field: &str) -> Result<T, SmartError> {
+  value.ok_or_else(|| SmartError::SchemaMismatch(format!("{}: missing field `{}`", exchange.as_string(), field)))
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_decodes_typed_responses() {
+    let rows: Vec<CandleRow> = decode(&Exchange::Binance, serde_json::json!([
+      [1700000000000u64, "1.0", "2.0", "0.5", "1.5", "100", 1700003599999u64, "0", 10, "0", "0", "0"]
+    ])).unwrap();
+    assert_eq!(rows[0], CandleRow { time: 1700000000000, open: 1.0, high: 2.0, low: 0.5, close: 1.5, volume: 100.0 });
+
+    let coinbase: Vec<CoinbaseRow> = decode(&Exchange::Coinbase, serde_json::json!([[1700000000u64, 0.5, 2.0, 1.0, 1.25, 10.0]])).unwrap();
+    assert_eq!(coinbase[0].0, CandleRow { time: 1700000000, open: 1.0, high: 2.0, low: 0.5, close: 1.25, volume: 10.0 });
+
+    // Short rows and unparsable numbers name the problem
+    let short: SmartError = decode::<Vec<CandleRow>>(&Exchange::ByBit, serde_json::json!([["1700000000000", "1.0"]])).unwrap_err();
+    assert!(short.to_string().contains("invalid length"));
+    let missing: SmartError = decode::<BinanceTicker>(&Exchange::Binance, serde_json::json!({ "symbol": "BTCUSDT" })).unwrap_err();
+    assert!(missing.to_string().contains("missing field `price`"));
+    assert!(decode::<TwelvePrice>(&Exchange::Twelve, serde_json::json!({ "price": "abc" })).is_err());
+
+    let bybit: ByBitResponse = decode(&Exchange::ByBit, serde_json::json!({ "retCode": 10001, "retMsg": "params error", "result": {} })).unwrap();
+    assert!(matches!(bybit.into_list::<ByBitTicker>(), Err(SmartError::APIResponseStatus(_))));
+    let bybit_ok: ByBitResponse = decode(&Exchange::ByBit, serde_json::json!({ "retCode": 0, "retMsg": "OK", "result": { "list": [{ "symbol": "BTCUSDT", "lastPrice": "50000.5" }] } })).unwrap();
+    assert_eq!(bybit_ok.into_list::<ByBitTicker>().unwrap()[0].last_price, Num(50000.5));
+
+    let gate: Vec<GateCandle> = decode(&Exchange::GateIo, serde_json::json!([{ "t": 1700000000, "v": 10, "c": "1.5", "h": "2", "l": "0.5", "o": "1" }])).unwrap();
+    assert_eq!((gate[0].t.0, gate[0].c.0), (1700000000.0, 1.5));
+
+    let dydx: crate::pricing::models::DydxCandle = decode(&Exchange::Dydx, serde_json::json!({ "candles": [{
+      "startedAt": "2023-11-14T22:00:00.000Z", "ticker": "BTC-USD", "resolution": "1HOUR", "low": "0.5", "high": "2", "open": "1", "close": "1.5",
+      "baseTokenVolume": "10", "usdVolume": "15", "trades": 5, "startingOpenInterest": "100", "id": "x"
+    }]})).unwrap();
+    assert_eq!(dydx.candles[0].close, "1.5");
+
+    let hyperliquid: Vec<HyperliquidCandle> = decode(&Exchange::Hyperliquid, serde_json::json!([
+      { "t": 1700000000000u64, "T": 1700003599999u64, "s": "BTC", "i": "1h", "o": "1", "c": "1.5", "h": "2", "l": "0.5", "v": "10", "n": 5 }
+    ])).unwrap();
+    assert_eq!((hyperliquid[0].t, hyperliquid[0].c.0), (1700000000000, 1.5));
+    let meta: HyperliquidMeta = decode(&Exchange::Hyperliquid, serde_json::json!({ "universe": [
+      { "name": "BTC", "szDecimals": 5, "maxLeverage": 40 }, { "name": "FTT", "szDecimals": 1, "maxLeverage": 3, "isDelisted": true }
+    ]})).unwrap();
+    assert!(!meta.universe[0].is_delisted && meta.universe[1].is_delisted);
+
+    let kraken: KrakenResponse = decode(&Exchange::Kraken, serde_json::json!({ "error": [], "result": {
+      "XXBTZUSD": [[1700000000, "1", "2", "0.5", "1.5", "1.2", "10", 5]], "last": 1700000000
+    }})).unwrap();
+    assert_eq!(kraken.into_candles().unwrap(), vec![CandleRow { time: 1700000000, open: 1.0, high: 2.0, low: 0.5, close: 1.5, volume: 10.0 }]);
+    let kraken_err: KrakenResponse = decode(&Exchange::Kraken, serde_json::json!({ "error": ["EQuery:Unknown asset pair"] })).unwrap();
+    assert!(matches!(kraken_err.into_candles(), Err(SmartError::APIResponseStatus(_))));
+    assert!(kraken_pair_matches("XXBTZUSD", "XBTUSD") && kraken_pair_matches("SOLUSD", "SOLUSD"));
+    assert!(!kraken_pair_matches("XETHZUSD", "XBTUSD"));
+
+    let kucoin: KuCoinResponse = decode(&Exchange::KuCoin, serde_json::json!({ "code": "200000", "data": [
+      ["1700003600", "1", "1.5", "2", "0.5", "10", "15"]
+    ]})).unwrap();
+    assert_eq!(kucoin.into_data::<Vec<OpenCloseRow>>().unwrap()[0].0, CandleRow { time: 1700003600, open: 1.0, high: 2.0, low: 0.5, close: 1.5, volume: 10.0 });
+    let kucoin_err: KuCoinResponse = decode(&Exchange::KuCoin, serde_json::json!({ "code": "400100", "msg": "This pair is not provided at present" })).unwrap();
+    assert!(matches!(kucoin_err.into_data::<KuCoinLevel1>(), Err(SmartError::APIResponseStatus(_))));
+
+    let bitfinex: Vec<OpenCloseRow> = decode(&Exchange::Bitfinex, serde_json::json!([[1700000000000u64, 1.0, 1.5, 2.0, 0.5, 10.0]])).unwrap();
+    assert_eq!(bitfinex[0].0, CandleRow { time: 1700000000000, open: 1.0, high: 2.0, low: 0.5, close: 1.5, volume: 10.0 });
+    let tickers: Vec<BitfinexTicker> = decode(&Exchange::Bitfinex, serde_json::json!([["tBTCUSD", 1, 2, 3, 4, 5, 0.01, 50000.5, 10, 51000, 49000]])).unwrap();
+    assert_eq!(tickers[0], BitfinexTicker { symbol: "tBTCUSD".to_string(), last_price: 50000.5 });
+    let bitfinex_err: SmartError = decode::<Vec<OpenCloseRow>>(&Exchange::Bitfinex, serde_json::json!(["error", 10020, "limit: invalid"])).unwrap_err();
+    assert!(matches!(bitfinex_err, SmartError::APIResponseStatus(msg) if msg == "limit: invalid"));
+
+    let okx: OkxResponse = decode(&Exchange::Okx, serde_json::json!({ "code": "0", "msg": "", "data": [
+      ["1700003600000", "1", "2", "0.5", "1.5", "10", "10", "15", "1"], ["1700000000000", "1", "2", "0.5", "1.25", "10", "10", "15", "1"]
+    ]})).unwrap();
+    assert_eq!(okx.into_list::<CandleRow>().unwrap()[1], CandleRow { time: 1700000000000, open: 1.0, high: 2.0, low: 0.5, close: 1.25, volume: 10.0 });
+    let okx_err: OkxResponse = decode(&Exchange::Okx, serde_json::json!({ "code": "51001", "msg": "Instrument ID does not exist", "data": [] })).unwrap();
+    assert!(matches!(okx_err.into_list::<OkxTicker>(), Err(SmartError::APIResponseStatus(_))));
+
+    let twelve: SmartError = decode::<TwelveSeries>(&Exchange::Twelve, serde_json::json!({ "code": 400, "message": "symbol not found", "status": "error" })).unwrap_err();
+    assert!(matches!(twelve, SmartError::APIResponseStatus(msg) if msg == "symbol not found"));
+
+    let alpha: AlphaVantageSeries = decode(&Exchange::AlphaVantage, serde_json::json!({
+      "Meta Data": { "1. Information": "Intraday (5min) open, high, low, close prices and volume", "2. Symbol": "IBM", "6. Time Zone": "US/Eastern" },
+      "Time Series (5min)": {
+        "2024-01-05 19:55:00": { "1. open": "159.1", "2. high": "159.2", "3. low": "159.0", "4. close": "159.15", "5. volume": "120" },
+        "2024-01-05 19:50:00": { "1. open": "159.0", "2. high": "159.1", "3. low": "158.9", "4. close": "159.05", "5. volume": "80" }
+      }
+    })).unwrap();
+    assert_eq!(alpha.time_zone(), Some("US/Eastern"));
+    let bars: Vec<f64> = alpha.into_bars().unwrap().into_values().map(|bar| bar.close.0).collect();
+    assert_eq!(bars, vec![159.05, 159.15]);
+    let quota: SmartError = decode::<AlphaVantageSeries>(&Exchange::AlphaVantage, serde_json::json!({ "Note": "Our standard API call frequency is 5 calls per minute" })).unwrap_err();
+    assert!(matches!(quota, SmartError::APIResponseStatus(msg) if msg.contains("5 calls per minute")));
+  }
+}
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use ts_rs::TS;
+
+use super::translate::parse_symbol;
+
+/*
+  Sector Tagging
+  Static category tags for common crypto bases so pair selection can be restricted to one sector
+  Cointegration between assets driven by the same narrative is more likely to persist
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, TS)]
+#[ts(export)]
+pub enum Sector {
+  Layer1,
+  Layer2,
+  DeFi,
+  Meme,
+  ExchangeToken,
+  Oracle,
+  Gaming,
+  AI,
+  Privacy,
+  Payments,
+  Storage,
+  Stablecoin,
+  Unknown
+}
+
+static SECTOR_OVERRIDES: RwLock<Option<HashMap<String, Sector>>> = RwLock::new(None);
+
+const SECTOR_TABLE: [(&str, Sector); 62] = [
+  ("BTC", Sector::Layer1), ("ETH", Sector::Layer1), ("SOL", Sector::Layer1), ("ADA", Sector::Layer1),
+  ("AVAX", Sector::Layer1), ("DOT", Sector::Layer1), ("ATOM", Sector::Layer1), ("NEAR", Sector::Layer1),
+  ("APT", Sector::Layer1), ("SUI", Sector::Layer1), ("TRX", Sector::Layer1), ("ALGO", Sector::Layer1),
+  ("TON", Sector::Layer1), ("SEI", Sector::Layer1), ("ICP", Sector::Layer1), ("FTM", Sector::Layer1),
+  ("MATIC", Sector::Layer2), ("POL", Sector::Layer2), ("ARB", Sector::Layer2), ("OP", Sector::Layer2),
+  ("IMX", Sector::Layer2), ("STRK", Sector::Layer2), ("MNT", Sector::Layer2), ("METIS", Sector::Layer2),
+  ("UNI", Sector::DeFi), ("AAVE", Sector::DeFi), ("MKR", Sector::DeFi), ("COMP", Sector::DeFi),
+  ("CRV", Sector::DeFi), ("SNX", Sector::DeFi), ("LDO", Sector::DeFi), ("SUSHI", Sector::DeFi),
+  ("DYDX", Sector::DeFi), ("1INCH", Sector::DeFi), ("PENDLE", Sector::DeFi),
+  ("DOGE", Sector::Meme), ("SHIB", Sector::Meme), ("PEPE", Sector::Meme), ("WIF", Sector::Meme),
+  ("BONK", Sector::Meme), ("FLOKI", Sector::Meme),
+  ("BNB", Sector::ExchangeToken), ("OKB", Sector::ExchangeToken), ("CRO", Sector::ExchangeToken),
+  ("LINK", Sector::Oracle), ("BAND", Sector::Oracle), ("PYTH", Sector::Oracle),
+  ("AXS", Sector::Gaming), ("SAND", Sector::Gaming), ("MANA", Sector::Gaming), ("GALA", Sector::Gaming),
+  ("FET", Sector::AI), ("RNDR", Sector::AI), ("TAO", Sector::AI), ("WLD", Sector::AI),
+  ("XMR", Sector::Privacy), ("ZEC", Sector::Privacy),
+  ("XRP", Sector::Payments), ("XLM", Sector::Payments), ("LTC", Sector::Payments),
+  ("FIL", Sector::Storage), ("AR", Sector::Storage)
+];
+
+/// Set Sector Overrides
+/// Adds or replaces tags for bases (e.g. from CoinGecko categories), taking precedence over the static table
+pub fn set_sector_overrides(overrides: HashMap<String, Sector>) {
+  if let Ok(mut store) = SECTOR_OVERRIDES.write() {
+    let upper: HashMap<String, Sector> = overrides.into_iter().map(|(k, v)| (k.to_uppercase(), v)).collect();
+    *store = Some(upper);
+  }
+}
+
+/// Sector For Base
+/// Sector of a base asset (e.g. "ETH")
+pub fn sector_for_base(base: &str) -> Sector {
+  let base: String = base.to_uppercase();
+  let overridden: Option<Sector> = SECTOR_OVERRIDES.read().ok()
+    .and_then(|store| store.as_ref().and_then(|m| m.get(&base).copied()));
+  if let Some(sector) = overridden { return sector }
+
+  if matches!(base.as_str(), "USDT" | "USDC" | "DAI" | "BUSD" | "TUSD" | "FDUSD") { return Sector::Stablecoin }
+  SECTOR_TABLE.iter().find(|(b, _)| *b == base).map(|(_, s)| *s).unwrap_or(Sector::Unknown)
+}
+
+/// Sector For Symbol
+/// Sector of an exchange ticker (e.g. "ETHUSDT", "ETH-USD")
+pub fn sector_for_symbol(symbol: &str) -> Sector {
+  match parse_symbol(symbol) {
+    Ok(parts) => sector_for_base(&parts.base),
+    Err(_) => Sector::Unknown
+  }
+}
+
+/// Same Sector
+/// True if both symbols share a known sector
+pub fn same_sector(symbol_0: &str, symbol_1: &str) -> bool {
+  let sector: Sector = sector_for_symbol(symbol_0);
+  sector != Sector::Unknown && sector == sector_for_symbol(symbol_1)
+}
+
+/// Same Sector Pairs
+/// All symbol pairs (in input order) that share a known sector
+pub fn same_sector_pairs(symbols: &[&str]) -> Vec<(String, String)> {
+  let sectors: Vec<Sector> = symbols.iter().map(|s| sector_for_symbol(s)).collect();
+  let mut pairs: Vec<(String, String)> = vec![];
+  for i in 0..symbols.len() {
+    for j in (i + 1)..symbols.len() {
+      if sectors[i] != Sector::Unknown && sectors[i] == sectors[j] {
+        pairs.push((symbols[i].to_string(), symbols[j].to_string()));
+      }
+    }
+  }
+  pairs
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_tags_symbols_by_sector() {
+    assert_eq!(sector_for_symbol("ETHUSDT"), Sector::Layer1);
+    assert_eq!(sector_for_symbol("UNI-USD"), Sector::DeFi);
+    assert!(same_sector("DOGEUSDT", "SHIBUSDT"));
+    assert!(!same_sector("DOGEUSDT", "BTCUSDT"));
+    assert!(!same_sector("FOOUSDT", "BARUSDT"));
+
+    let pairs = same_sector_pairs(&["BTCUSDT", "AAVEUSDT", "SOLUSDT", "UNIUSDT"]);
+    assert_eq!(pairs.len(), 2);
+    assert_eq!(pairs[0], ("BTCUSDT".to_string(), "SOLUSDT".to_string()));
+  }
+}
@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use super::candles::CandleBuilder;
+use super::models::{Exchange, HistoricalPrices, IntervalPeriod};
+use super::translate::{is_usd_quote, parse_symbol};
+
+/*
+  Stablecoin Quote Normalization
+  Treats USD, USDT and USDC quotes as equivalent when comparing and merging cross exchange data
+  Where series quoted in USD and a stablecoin are merged, the stablecoin series are converted to USD so stablecoin drift
+  does not show up as spread. Only stablecoins with a USD rate market are converted, the others are taken at par
+*/
+
+/// Stablecoins converted to USD and the Coinbase market quoting their USD rate
+const STABLECOIN_USD_MARKETS: [(&str, &str); 1] = [("USDT", "USDT-USD")];
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, TS)]
+#[ts(export)]
+pub enum QuoteNormalization {
+  Strict, // Quotes must match exactly
+  StablecoinEquivalent // USD, USDT, USDC etc are treated as the same quote
+}
+
+/// Normalized Symbol Key
+/// Canonical "BASE/QUOTE" key used to compare symbols across exchanges
+pub fn normalized_symbol_key(symbol: &str, normalization: QuoteNormalization) -> String {
+  match parse_symbol(symbol) {
+    Ok(parts) => {
+      let quote: &str = match normalization {
+        QuoteNormalization::StablecoinEquivalent if is_usd_quote(&parts.quote) => "USD",
+        _ => parts.quote.as_str()
+      };
+      format!("{}/{}", parts.base, quote)
+    },
+    Err(_) => symbol.to_uppercase()
+  }
+}
+
+/// Symbols Match
+/// Whether two tickers (possibly from different exchanges) refer to the same market
+pub fn symbols_match(symbol_0: &str, symbol_1: &str, normalization: QuoteNormalization) -> bool {
+  normalized_symbol_key(symbol_0, normalization) == normalized_symbol_key(symbol_1, normalization)
+}
+
+/// Is Stablecoin Quoted
+/// True if the symbol is quoted in a dollar stablecoin rather than USD itself
+pub fn is_stablecoin_quoted(symbol: &str) -> bool {
+  parse_symbol(symbol).map(|p| p.quote != "USD" && is_usd_quote(&p.quote)).unwrap_or(false)
+}
+
+/// Stablecoin Rate
+/// Stablecoin/USD series converting prices quoted in that stablecoin
+#[derive(Debug, Clone)]
+pub struct StablecoinRate {
+  pub stablecoin: String,
+  pub rates: HistoricalPrices
+}
+
+/// USD Rate Market
+/// Coinbase market quoting a stablecoin in USD, None for stablecoins taken at par
+pub fn usd_rate_market(stablecoin: &str) -> Option<&'static str> {
+  STABLECOIN_USD_MARKETS.iter().find(|(coin, _)| coin.eq_ignore_ascii_case(stablecoin)).map(|(_, market)| *market)
+}
+
+/// Quotes Needing Conversion
+/// Stablecoin quotes to convert when the symbols mix dollar quotes (e.g. BTC-USD with ETHUSDT), empty when all share one quote
+pub fn quotes_needing_conversion(symbols: &[&str]) -> Vec<String> {
+  let mut quotes: Vec<String> = symbols.iter()
+    .filter_map(|symbol| parse_symbol(symbol).ok())
+    .map(|parts| parts.quote)
+    .filter(|quote| is_usd_quote(quote))
+    .collect();
+  quotes.sort();
+  quotes.dedup();
+
+  // Guard: A single quote needs no conversion
+  if quotes.len() < 2 { return vec![] }
+
+  quotes.into_iter().filter(|quote| usd_rate_market(quote).is_some()).collect()
+}
+
+/// Convert To USD
+/// Multiplies stablecoin quoted prices by the stablecoin/USD rate at each label
+/// Uses the most recent conversion rate at or before each label (first rate if none precede it)
+pub fn convert_to_usd(prices: &HistoricalPrices, conversion: &HistoricalPrices) -> Result<HistoricalPrices, SmartError> {
+
+  // Guard: Ensure conversion series present
+  if conversion.prices.is_empty() || conversion.prices.len() != conversion.labels.len() {
+    return Err(SmartError::RuntimeCheck("Conversion series is empty or malformed".to_string()));
+  }
+
+  let mut idx: usize = 0;
+  let mut converted: Vec<f64> = Vec::with_capacity(prices.prices.len());
+  for (label, price) in prices.labels.iter().zip(prices.prices.iter()) {
+    while idx + 1 < conversion.labels.len() && conversion.labels[idx + 1] <= *label {
+      idx += 1;
+    }
+    converted.push(price * conversion.prices[idx]);
+  }
+
+  Ok(HistoricalPrices {
+    prices: converted,
+    labels: prices.labels.clone(),
+    label_convention: prices.label_convention,
+    fetch_report: prices.fetch_report.clone()
+  })
+}
+
+/// Request USDT USD Series
+/// Fetches the USDT/USD conversion series from Coinbase for the given interval
+pub async fn request_usdt_usd_series(interval_period: &IntervalPeriod) -> Result<HistoricalPrices, SmartError> {
+  Ok(request_stablecoin_rate("USDT", interval_period).await?.rates)
+}
+
+/// Request Stablecoin Rate
+/// Fetches the USD rate series of a stablecoin from its Coinbase market
+pub async fn request_stablecoin_rate(stablecoin: &str, interval_period: &IntervalPeriod) -> Result<StablecoinRate, SmartError> {
+  let Some(market) = usd_rate_market(stablecoin) else {
+    return Err(SmartError::RuntimeCheck(format!("No USD rate market for stablecoin: {}", stablecoin)))
+  };
+  let builder: CandleBuilder = CandleBuilder::new(market.to_string(), interval_period.clone(), Exchange::Coinbase, None);
+  Ok(StablecoinRate { stablecoin: stablecoin.to_uppercase(), rates: builder.fetch_prices_candles().await? })
+}
+
+/// Request Conversion Rates
+/// USD rates for every stablecoin quote needing conversion among the symbols (none when they share one quote)
+pub async fn request_conversion_rates(symbols: &[&str], interval_period: &IntervalPeriod) -> Result<Vec<StablecoinRate>, SmartError> {
+  let quotes: Vec<String> = quotes_needing_conversion(symbols);
+  let results: Vec<Result<StablecoinRate, SmartError>> = futures::future::join_all(
+    quotes.iter().map(|quote| request_stablecoin_rate(quote, interval_period))
+  ).await;
+  results.into_iter().collect()
+}
+
+/// Normalize Historical Prices
+/// Converts prices to USD with the rate of the symbol's own quote (other quotes are returned unchanged)
+pub fn normalize_historical_prices(
+  prices: HistoricalPrices,
+  symbol: &str,
+  rates: &[StablecoinRate]
+) -> Result<HistoricalPrices, SmartError> {
+  let quote: Option<String> = parse_symbol(symbol).ok().map(|p| p.quote);
+  match rates.iter().find(|rate| quote.as_deref() == Some(rate.stablecoin.as_str())) {
+    Some(rate) if is_stablecoin_quoted(symbol) => convert_to_usd(&prices, &rate.rates),
+    _ => Ok(prices)
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::pricing::models::LabelConvention;
+
+  #[tokio::test]
+  async fn it_matches_stablecoin_quotes() {
+    assert!(symbols_match("BTCUSDT", "BTC-USD", QuoteNormalization::StablecoinEquivalent));
+    assert!(symbols_match("BTC-USDC", "BTC/USD", QuoteNormalization::StablecoinEquivalent));
+    assert!(!symbols_match("BTCUSDT", "BTC-USD", QuoteNormalization::Strict));
+    assert!(!symbols_match("BTCUSDT", "ETH-USD", QuoteNormalization::StablecoinEquivalent));
+  }
+
+  #[tokio::test]
+  async fn it_converts_stablecoin_prices_to_usd() {
+    let prices: HistoricalPrices = HistoricalPrices { prices: vec![100.0, 200.0, 300.0], labels: vec![10, 20, 30], label_convention: LabelConvention::OpenTime, fetch_report: None };
+    let conversion: HistoricalPrices = HistoricalPrices { prices: vec![0.99, 1.01], labels: vec![15, 25], label_convention: LabelConvention::OpenTime, fetch_report: None };
+    let rates: Vec<StablecoinRate> = vec![StablecoinRate { stablecoin: "USDT".to_string(), rates: conversion }];
+    let usd: HistoricalPrices = normalize_historical_prices(prices.clone(), "BTCUSDT", &rates).unwrap();
+    assert_eq!(usd.prices, vec![99.0, 198.0, 303.0]);
+
+    // Only series quoted in the rate's own stablecoin are converted
+    assert_eq!(normalize_historical_prices(prices.clone(), "BTCUSDC", &rates).unwrap().prices, prices.prices);
+    assert_eq!(normalize_historical_prices(prices.clone(), "BTCFDUSD", &rates).unwrap().prices, prices.prices);
+    assert_eq!(normalize_historical_prices(prices.clone(), "BTC-USD", &rates).unwrap().prices, prices.prices);
+  }
+
+  #[tokio::test]
+  async fn it_converts_only_mixed_quotes_with_a_rate_market() {
+    assert_eq!(quotes_needing_conversion(&["BTC-USD", "ETHUSDT"]), vec!["USDT".to_string()]);
+    assert!(quotes_needing_conversion(&["BTCUSDT", "ETHUSDT"]).is_empty());
+    assert!(quotes_needing_conversion(&["BTC-USD", "ETH-USDC"]).is_empty());
+    assert_eq!(quotes_needing_conversion(&["BTCUSDC", "ETHUSDT", "SOLFDUSD"]), vec!["USDT".to_string()]);
+  }
+}
@@ -1,19 +1,67 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::RwLock;
 
 use crate::SmartError;
 use super::models::{Exchange, AssetType};
-use super::utils::api_request;
+use super::jsonstream::stream_json_array;
+use super::schemas::{decode, BinanceExchangeInfo, BinanceSymbol, DydxMarkets, GateContract, HyperliquidMeta, KrakenAssetPair, KrakenResponse, KuCoinResponse, KuCoinSymbol, OkxInstrument, OkxResponse};
+use super::times::get_world_time_utc;
+use super::translate::base_if_preferred_quote;
+use super::utils::{alpaca_auth_query, api_post, api_request};
+
+/// Hyperliquid request body for the perpetuals universe (the info API is POST only)
+const HYPERLIQUID_META_BODY: &str = r#"{"type":"meta"}"#;
+
+/// Default seconds symbol listings are cached for before being requested again
+const DEFAULT_SYMBOL_CACHE_TTL_SECS: i64 = 3600;
+
+type SymbolCache = HashMap<String, (i64, Vec<SymbolListing>)>; // key, (fetched at secs, listings)
+static SYMBOL_CACHE: RwLock<Option<SymbolCache>> = RwLock::new(None);
+static SYMBOL_CACHE_TTL_SECS: RwLock<Option<i64>> = RwLock::new(None);
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SymbolListing {
+  pub symbol: String,
+  pub is_trading: bool
+}
 
 
 /// Get symbols url
 /// Retrieves symbols url for a given exchange
 fn get_symbols_url(exchange: &Exchange, asset_type: Option<AssetType>) -> String {
 
+  let alpaca_symbols: &str = match asset_type {
+    Some(AssetType::Crypto) => "https://paper-api.alpaca.markets/v2/assets?status=active&asset_class=crypto",
+    _ => "https://paper-api.alpaca.markets/v2/assets?status=active&asset_class=us_equity"
+  }; // trading api, requires credentials
+  let alpha_vantage_symbols: &str = "https://www.alphavantage.co/query?function=LISTING_STATUS&apikey=demo"; // csv, stocks and ETFs only
+
   let binance_symbols: &str = "https://fapi.binance.com/fapi/v1/exchangeInfo";
+  let binance_coinm_symbols: &str = "https://dapi.binance.com/dapi/v1/exchangeInfo";
+  let binance_spot_symbols: &str = "https://api.binance.com/api/v3/exchangeInfo?permissions=SPOT";
   let binance_us_symbols: &str = "https://api.binance.us/api/v3/exchangeInfo";
+  let bitfinex_symbols: &str = "https://api-pub.bitfinex.com/v2/conf/pub:list:pair:exchange";
   let bybit_symbols: &str = "https://api.bybit.com/v5/market/instruments-info?category=linear";
+  let bybit_inverse_symbols: &str = "https://api.bybit.com/v5/market/instruments-info?category=inverse";
   let coinbase_symbols: &str = "https://api.exchange.coinbase.com/products";
-  let dydx_symbols: &str = "https://api.dydx.exchange/v3/markets";
+  let dydx_symbols: &str = "https://indexer.dydx.trade/v4/perpetualMarkets";
+  let gateio_symbols: &str = "https://api.gateio.ws/api/v4/futures/usdt/contracts";
+  let hyperliquid_symbols: &str = "https://api.hyperliquid.xyz/info";
+  let kraken_symbols: &str = "https://api.kraken.com/0/public/AssetPairs";
+  let kucoin_symbols: &str = "https://api.kucoin.com/api/v2/symbols";
+  let okx_symbols: &str = "https://www.okx.com/api/v5/public/instruments?instType=SWAP";
+
+  let tiingo_symbols: &str = match asset_type {
+    Some(AssetType::Crypto) => "https://api.tiingo.com/tiingo/crypto",
+    _ => "https://api.tiingo.com/iex"
+  }; // requires a token, IEX top of book doubles as the stock listing
+
+  let yahoo_symbols: &str = match asset_type {
+    Some(AssetType::Etf) => "https://query1.finance.yahoo.com/v1/finance/screener/predefined/saved?scrIds=top_etfs_us&count=250",
+    _ => "https://query1.finance.yahoo.com/v1/finance/screener/predefined/saved?scrIds=most_actives&count=250"
+  }; // no full listing endpoint, most active stocks or top ETFs only
 
   let twelve_symbols: &str = match asset_type {
     Some(t) => match t {
@@ -27,40 +75,103 @@ fn get_symbols_url(exchange: &Exchange, asset_type: Option<AssetType>) -> String
   };
 
   match exchange {
+    Exchange::Alpaca => alpaca_symbols.to_string(),
+    Exchange::AlphaVantage => alpha_vantage_symbols.to_string(),
     Exchange::Binance => binance_symbols.to_string(),
+    Exchange::BinanceCoinM => binance_coinm_symbols.to_string(),
+    Exchange::BinanceSpot => binance_spot_symbols.to_string(),
     Exchange::BinanceUs => binance_us_symbols.to_string(),
+    Exchange::Bitfinex => bitfinex_symbols.to_string(),
     Exchange::ByBit => bybit_symbols.to_string(),
+    Exchange::ByBitInverse => bybit_inverse_symbols.to_string(),
     Exchange::Coinbase => coinbase_symbols.to_string(),
     Exchange::Dydx => dydx_symbols.to_string(),
+    Exchange::GateIo => gateio_symbols.to_string(),
+    Exchange::Hyperliquid => hyperliquid_symbols.to_string(),
+    Exchange::Kraken => kraken_symbols.to_string(),
+    Exchange::KuCoin => kucoin_symbols.to_string(),
+    Exchange::Okx => okx_symbols.to_string(),
+    Exchange::Tiingo => tiingo_symbols.to_string(),
     Exchange::Twelve => twelve_symbols.to_string(),
+    Exchange::Yahoo => yahoo_symbols.to_string()
   }
 }
 
+/// Extract Symbols Alpaca
+/// Takes Alpaca asset data and returns vector of api endpoints
+fn extract_symbols_alpaca(json_text: String) -> Result<Vec<SymbolListing>, SmartError> {
+  let assets: Vec<serde_json::Value> = serde_json::from_str(&json_text)?;
+  let tickers: Vec<SymbolListing> = assets.iter()
+    .filter_map(|asset| {
+      let symbol: &str = asset["symbol"].as_str()?;
+      let is_trading: bool = asset["status"].as_str() == Some("active") && asset["tradable"].as_bool().unwrap_or(false);
+      Some(SymbolListing { symbol: symbol.to_string(), is_trading })
+    })
+    .collect();
+  Ok(tickers)
+}
+
+/// Extract Symbols AlphaVantage
+/// Takes AlphaVantage listing csv (symbol,name,exchange,assetType,ipoDate,delistingDate,status) and returns listings of the asset type
+/// Columns are read from the end as names may contain commas
+fn extract_symbols_alpha_vantage(csv_text: String, asset_type: Option<AssetType>) -> Result<Vec<SymbolListing>, SmartError> {
+  let wanted: Option<&str> = match asset_type {
+    Some(AssetType::Stock) => Some("Stock"),
+    Some(AssetType::Etf) => Some("ETF"),
+    Some(_) => return Ok(vec![]),
+    None => None
+  };
+
+  let tickers: Vec<SymbolListing> = csv_text.lines()
+    .skip(1)
+    .filter_map(|line| {
+      let cols: Vec<&str> = line.trim().split(',').collect();
+      if cols.len() < 7 { return None }
+      let kind: &str = cols[cols.len() - 4];
+      if wanted.map(|w| w != kind).unwrap_or(false) { return None }
+      Some(SymbolListing { symbol: cols[0].to_string(), is_trading: cols[cols.len() - 1] == "Active" })
+    })
+    .collect();
+
+  Ok(tickers)
+}
+
 /// Extract Symbols Binance
 /// Takes Binance data and returns vector of api endpoints
-fn extract_symbols_binance(json_text: String) -> Result<Vec<String>, SmartError> {
-  let ticker_obj: serde_json::Value = serde_json::Value::from_str(&json_text)?;
-
-  let mut tickers: Vec<String> = ticker_obj["symbols"]
-    .as_array()
-    .ok_or(SmartError::RuntimeCheck("Expected 'symbols' to be an array".to_string()))?
-    .iter()
-    .filter_map(|symbol_obj| {
-      if symbol_obj["status"].as_str() == Some("TRADING") {
-        symbol_obj["symbol"].as_str().map(|s| s.to_string())
-      } else {
-        None
-      }
+/// Exchange info runs to several megabytes so symbols are decoded as the body streams in
+async fn extract_symbols_binance(res_data: reqwest::Response, exchange: &Exchange) -> Result<Vec<SymbolListing>, SmartError> {
+  let (symbols, envelope) = stream_json_array::<BinanceSymbol>(res_data, exchange, &["symbols"]).await?;
+  decode::<BinanceExchangeInfo>(exchange, envelope)?;
+
+  let mut tickers: Vec<SymbolListing> = symbols.into_iter()
+    .map(|s| SymbolListing {
+      is_trading: s.status.or(s.contract_status).as_deref() == Some("TRADING"), // COIN-M reports contractStatus
+      symbol: s.symbol
     })
     .collect();
 
-  tickers.sort();
+  tickers.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+  Ok(tickers)
+}
+
+/// Extract Symbols Bitfinex
+/// Takes Bitfinex exchange pairs (BTCUSD, DOGE:USD) and returns them as trading symbols (tBTCUSD)
+/// The pair list only includes pairs currently listed for trading
+fn extract_symbols_bitfinex(json_text: String) -> Result<Vec<SymbolListing>, SmartError> {
+  let data_obj: serde_json::Value = serde_json::Value::from_str(&json_text)?;
+  let lists: Vec<Vec<String>> = decode(&Exchange::Bitfinex, data_obj)?;
+
+  let mut tickers: Vec<SymbolListing> = lists.into_iter().flatten()
+    .map(|pair| SymbolListing { symbol: format!("t{}", pair), is_trading: true })
+    .collect();
+
+  tickers.sort_by(|a, b| a.symbol.cmp(&b.symbol));
   Ok(tickers)
 }
 
 /// Extract Symbols ByBit
 /// Retrieves tickers for ByBit
-fn extract_symbols_bybit(json_text: String) -> Result<Vec<String>, SmartError> {
+fn extract_symbols_bybit(json_text: String) -> Result<Vec<SymbolListing>, SmartError> {
   let ticker_obj: serde_json::Value = serde_json::from_str(&json_text)?;
 
   // Navigating to the list within result.
@@ -70,18 +181,13 @@ fn extract_symbols_bybit(json_text: String) -> Result<Vec<String>, SmartError> {
     .ok_or("Expected 'result.list' to be an array")
     .map_err(|e| SmartError::RuntimeCheck(e.to_string()))?;
 
-  // Extracting symbols where status is "Trading".
-  let tickers: Vec<String> = list.iter()
+  // Extracting symbols and whether status is "Trading".
+  let tickers: Vec<SymbolListing> = list.iter()
       .filter_map(|item| {
-        item.get("status")
-          .and_then(serde_json::Value::as_str)
-          .and_then(|status| {
-            if status == "Trading" {
-              item.get("symbol").and_then(serde_json::Value::as_str).map(ToString::to_string)
-            } else {
-              None
-            }
-          })
+        item.get("symbol").and_then(serde_json::Value::as_str).map(|symbol| SymbolListing {
+          symbol: symbol.to_string(),
+          is_trading: item.get("status").and_then(serde_json::Value::as_str) == Some("Trading")
+        })
       })
       .collect();
 
@@ -90,43 +196,125 @@ fn extract_symbols_bybit(json_text: String) -> Result<Vec<String>, SmartError> {
 
 /// Extract Symbols Coinbase
 /// Takes Coinbase data and returns vector of api endpoints
-fn extract_symbols_coinbase(json_text: String) -> Result<Vec<String>, SmartError> {
+fn extract_symbols_coinbase(json_text: String) -> Result<Vec<SymbolListing>, SmartError> {
   let ticker_obj: serde_json::Value = serde_json::Value::from_str(&json_text)?;
   let ticker_array: &Vec<serde_json::Value> = ticker_obj
     .as_array()
     .ok_or(SmartError::RuntimeCheck("Expected an array".to_string()))?;
 
   // Extract 'id' from each object in the array
-  let tickers: Vec<String> = ticker_array
+  let tickers: Vec<SymbolListing> = ticker_array
     .iter()
-    .filter_map(|item| item["id"].as_str())
-    .map(|s| s.to_string())
+    .filter_map(|item| item["id"].as_str().map(|s| SymbolListing {
+      symbol: s.to_string(),
+      is_trading: item["status"].as_str() != Some("delisted")
+    }))
     .collect();
 
   Ok(tickers)
 }
 
 /// Extract Symbols Dydx
-/// Takes Dydx data and returns vector of api endpoints
-fn extract_symbols_dydx(json_text: String) -> Result<Vec<String>, SmartError> {
-  let ticker_obj: serde_json::Value = serde_json::Value::from_str(&json_text)?;
-  let markets_obj = ticker_obj["markets"]
-    .as_object()
-    .ok_or(SmartError::RuntimeCheck("Expected 'markets' to be an object".to_string()))?;
-
-  // Extract 'market' from each object in the 'markets' object
-  let tickers: Vec<String> = markets_obj
-    .values()
-    .filter_map(|item| item["market"].as_str())
-    .map(|s| s.to_string())
+/// Takes Dydx v4 perpetual markets and returns their tickers (e.g. BTC-USD)
+fn extract_symbols_dydx(json_text: String) -> Result<Vec<SymbolListing>, SmartError> {
+  let data_obj: serde_json::Value = serde_json::Value::from_str(&json_text)?;
+  let markets: DydxMarkets = decode(&Exchange::Dydx, data_obj)?;
+
+  let mut tickers: Vec<SymbolListing> = markets.markets.into_values()
+    .map(|m| SymbolListing { is_trading: m.status == "ACTIVE", symbol: m.ticker })
+    .collect();
+
+  tickers.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+  Ok(tickers)
+}
+
+/// Extract Symbols GateIo
+/// Takes Gate.io USDT futures contracts and returns their names (e.g. BTC_USDT)
+fn extract_symbols_gateio(json_text: String) -> Result<Vec<SymbolListing>, SmartError> {
+  let data_obj: serde_json::Value = serde_json::Value::from_str(&json_text)?;
+  let contracts: Vec<GateContract> = decode(&Exchange::GateIo, data_obj)?;
+
+  let mut tickers: Vec<SymbolListing> = contracts.into_iter()
+    .map(|c| SymbolListing { symbol: c.name, is_trading: !c.in_delisting })
+    .collect();
+
+  tickers.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+  Ok(tickers)
+}
+
+/// Extract Symbols Hyperliquid
+/// Takes the Hyperliquid perpetuals universe and returns its coin names (e.g. BTC, kPEPE)
+fn extract_symbols_hyperliquid(json_text: String) -> Result<Vec<SymbolListing>, SmartError> {
+  let data_obj: serde_json::Value = serde_json::Value::from_str(&json_text)?;
+  let meta: HyperliquidMeta = decode(&Exchange::Hyperliquid, data_obj)?;
+
+  let mut tickers: Vec<SymbolListing> = meta.universe.into_iter()
+    .map(|a| SymbolListing { symbol: a.name, is_trading: !a.is_delisted })
+    .collect();
+
+  tickers.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+  Ok(tickers)
+}
+
+/// Extract Symbols Kraken
+/// Takes Kraken asset pairs and returns their altnames (e.g. XBTUSD) as used by the public endpoints
+fn extract_symbols_kraken(json_text: String) -> Result<Vec<SymbolListing>, SmartError> {
+  let data_obj: serde_json::Value = serde_json::Value::from_str(&json_text)?;
+  let pairs: HashMap<String, KrakenAssetPair> = decode::<KrakenResponse>(&Exchange::Kraken, data_obj)?.into_result()?;
+
+  let mut tickers: Vec<SymbolListing> = pairs.into_values()
+    .map(|pair| SymbolListing {
+      is_trading: pair.status.as_deref().is_none_or(|s| s == "online"),
+      symbol: pair.altname
+    })
+    .collect();
+
+  tickers.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+  Ok(tickers)
+}
+
+/// Extract Symbols KuCoin
+/// Takes KuCoin spot symbols and returns their tickers (e.g. BTC-USDT)
+fn extract_symbols_kucoin(json_text: String) -> Result<Vec<SymbolListing>, SmartError> {
+  let data_obj: serde_json::Value = serde_json::Value::from_str(&json_text)?;
+  let symbols: Vec<KuCoinSymbol> = decode::<KuCoinResponse>(&Exchange::KuCoin, data_obj)?.into_data()?;
+
+  let mut tickers: Vec<SymbolListing> = symbols.into_iter()
+    .map(|s| SymbolListing { symbol: s.symbol, is_trading: s.enable_trading })
     .collect();
 
+  tickers.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+  Ok(tickers)
+}
+
+/// Extract Symbols Okx
+/// Takes OKX perpetual swap instruments and returns their instrument ids (e.g. BTC-USDT-SWAP)
+fn extract_symbols_okx(json_text: String) -> Result<Vec<SymbolListing>, SmartError> {
+  let data_obj: serde_json::Value = serde_json::Value::from_str(&json_text)?;
+  let instruments: Vec<OkxInstrument> = decode::<OkxResponse>(&Exchange::Okx, data_obj)?.into_list()?;
+
+  let mut tickers: Vec<SymbolListing> = instruments.into_iter()
+    .map(|inst| SymbolListing { is_trading: inst.state == "live", symbol: inst.inst_id })
+    .collect();
+
+  tickers.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+  Ok(tickers)
+}
+
+/// Extract Symbols Tiingo
+/// Takes Tiingo crypto metadata or IEX top of book and returns vector of api endpoints
+fn extract_symbols_tiingo(json_text: String) -> Result<Vec<SymbolListing>, SmartError> {
+  let assets: Vec<serde_json::Value> = serde_json::from_str(&json_text)?;
+  let tickers: Vec<SymbolListing> = assets.iter()
+    .filter_map(|asset| asset["ticker"].as_str())
+    .map(|s| SymbolListing { symbol: s.to_string(), is_trading: true })
+    .collect();
   Ok(tickers)
 }
 
 /// Extract Symbols Twelve
 /// Takes Twelve data and returns vector of api endpoints
-fn extract_symbols_twelve(json_text: String) -> Result<Vec<String>, SmartError> {
+fn extract_symbols_twelve(json_text: String) -> Result<Vec<SymbolListing>, SmartError> {
   let ticker_obj: serde_json::Value = serde_json::Value::from_str(&json_text)?;
   
   // Access the 'data' array from the parsed JSON
@@ -135,24 +323,112 @@ fn extract_symbols_twelve(json_text: String) -> Result<Vec<String>, SmartError>
     .ok_or(SmartError::RuntimeCheck("Expected 'data' to be an array".to_string()))?;
   
   // Extract 'symbol' from each object in the 'data' array
-  let tickers: Vec<String> = data_arr
+  let tickers: Vec<SymbolListing> = data_arr
     .iter()
     .filter_map(|item| item["symbol"].as_str())
-    .map(|s| s.to_string())
+    .map(|s| SymbolListing { symbol: s.to_string(), is_trading: true })
     .collect();
 
   Ok(tickers)
 }
 
-/// Request tickers
-/// Requests list of available tickers for a given exchange
-pub async fn request_symbols(exchange: &Exchange, asset_type: Option<AssetType>) -> Result<Vec<String>, SmartError> {
+/// Extract Symbols Yahoo
+/// Takes Yahoo predefined screener data and returns vector of api endpoints
+fn extract_symbols_yahoo(json_text: String) -> Result<Vec<SymbolListing>, SmartError> {
+  let screener_obj: serde_json::Value = serde_json::Value::from_str(&json_text)?;
 
-  // Initialize url
-  let request_url: String = get_symbols_url(&exchange, asset_type);
+  // Access the 'quotes' array of the first screener result
+  let quotes_arr = screener_obj["finance"]["result"][0]["quotes"]
+    .as_array()
+    .ok_or(SmartError::RuntimeCheck("Expected 'finance.result[0].quotes' to be an array".to_string()))?;
+
+  let tickers: Vec<SymbolListing> = quotes_arr
+    .iter()
+    .filter_map(|item| item["symbol"].as_str())
+    .map(|s| SymbolListing { symbol: s.to_string(), is_trading: true })
+    .collect();
+
+  Ok(tickers)
+}
+
+/// Set Symbol Cache TTL
+/// Overrides how long symbol listings are reused in seconds (0 disables caching)
+pub fn set_symbol_cache_ttl(ttl_secs: i64) {
+  if let Ok(mut guard) = SYMBOL_CACHE_TTL_SECS.write() {
+    *guard = Some(ttl_secs);
+  }
+}
+
+/// Get Symbol Cache TTL
+pub fn get_symbol_cache_ttl() -> i64 {
+  SYMBOL_CACHE_TTL_SECS.read().ok().and_then(|guard| *guard).unwrap_or(DEFAULT_SYMBOL_CACHE_TTL_SECS)
+}
+
+fn listings_key(exchange: &Exchange, asset_type: &Option<AssetType>) -> String {
+  format!("{:?}:{:?}", exchange, asset_type)
+}
+
+/// Cached Listings
+/// Listings for the exchange and asset type if fetched within the ttl
+fn cached_listings(key: &str, now: i64) -> Option<Vec<SymbolListing>> {
+  let guard = SYMBOL_CACHE.read().ok()?;
+  let (fetched_at, listings) = guard.as_ref()?.get(key)?;
+  if now - fetched_at < get_symbol_cache_ttl() { Some(listings.clone()) } else { None }
+}
+
+/// Store Listings
+fn store_listings(key: String, listings: &[SymbolListing], now: i64) {
+  if get_symbol_cache_ttl() <= 0 { return }
+  if let Ok(mut guard) = SYMBOL_CACHE.write() {
+    guard.get_or_insert_with(HashMap::new).insert(key, (now, listings.to_vec()));
+  }
+}
+
+/// Clear Symbol Cache
+/// Drops all cached symbol listings
+pub fn clear_symbol_cache() {
+  if let Ok(mut guard) = SYMBOL_CACHE.write() { *guard = None; }
+}
+
+/// Request Symbol Listings
+/// Requests list of all listed tickers (including non trading) for a given exchange
+/// Served from the process cache within the ttl, so screening many pairs requests the instruments list once
+pub async fn request_symbol_listings(exchange: &Exchange, asset_type: Option<AssetType>) -> Result<Vec<SymbolListing>, SmartError> {
+  let now: i64 = get_world_time_utc()?;
+  let key: String = listings_key(exchange, &asset_type);
+  if let Some(listings) = cached_listings(&key, now) {
+    return Ok(listings);
+  }
+
+  let request_url: String = get_symbols_url(exchange, asset_type.clone());
+  let listings: Vec<SymbolListing> = fetch_symbol_listings(exchange, &request_url, asset_type).await?;
+  store_listings(key, &listings, now);
+  Ok(listings)
+}
+
+/// Request Alpaca Symbol Listings
+/// Alpaca lists assets on its trading api which requires credentials (api key given as "KEY_ID:SECRET_KEY")
+pub async fn request_alpaca_symbol_listings(api_key: &str, asset_type: Option<AssetType>) -> Result<Vec<SymbolListing>, SmartError> {
+  let request_url: String = format!("{}&{}", get_symbols_url(&Exchange::Alpaca, asset_type.clone()), alpaca_auth_query(api_key));
+  fetch_symbol_listings(&Exchange::Alpaca, &request_url, asset_type).await
+}
+
+/// Request Tiingo Symbol Listings
+/// Tiingo requires a token for every request including listings
+pub async fn request_tiingo_symbol_listings(api_key: &str, asset_type: Option<AssetType>) -> Result<Vec<SymbolListing>, SmartError> {
+  let request_url: String = format!("{}?token={}", get_symbols_url(&Exchange::Tiingo, asset_type.clone()), api_key);
+  fetch_symbol_listings(&Exchange::Tiingo, &request_url, asset_type).await
+}
+
+/// Fetch Symbol Listings
+/// Requests and extracts listings from the given url
+async fn fetch_symbol_listings(exchange: &Exchange, request_url: &str, asset_type: Option<AssetType>) -> Result<Vec<SymbolListing>, SmartError> {
 
   // Make request
-  let res_data: reqwest::Response = api_request(&request_url).await?;
+  let res_data: reqwest::Response = match exchange {
+    Exchange::Hyperliquid => api_post(request_url, HYPERLIQUID_META_BODY).await?,
+    _ => api_request(request_url).await?
+  };
 
   // Guard: Ensure status code
   if res_data.status() != 200 {
@@ -160,25 +436,119 @@ pub async fn request_symbols(exchange: &Exchange, asset_type: Option<AssetType>)
     return Err(SmartError::APIResponseStatus(e));
   }
 
-  // Send JSON
-  let json_text: String = res_data.text().await?;
-  let tickers: Vec<String> = match exchange {
-    Exchange::Binance => extract_symbols_binance(json_text)?,
-    Exchange::BinanceUs => extract_symbols_binance(json_text)?,
-    Exchange::ByBit => extract_symbols_bybit(json_text)?,
-    Exchange::Coinbase => extract_symbols_coinbase(json_text)?,
-    Exchange::Dydx => extract_symbols_dydx(json_text)?,
-    Exchange::Twelve => extract_symbols_twelve(json_text)?,
+  // Extract symbols
+  let tickers: Vec<SymbolListing> = match exchange {
+    Exchange::Alpaca => extract_symbols_alpaca(res_data.text().await?)?,
+    Exchange::AlphaVantage => extract_symbols_alpha_vantage(res_data.text().await?, asset_type)?,
+    Exchange::Binance | Exchange::BinanceCoinM | Exchange::BinanceSpot | Exchange::BinanceUs => extract_symbols_binance(res_data, exchange).await?,
+    Exchange::Bitfinex => extract_symbols_bitfinex(res_data.text().await?)?,
+    Exchange::ByBit | Exchange::ByBitInverse => extract_symbols_bybit(res_data.text().await?)?,
+    Exchange::Coinbase => extract_symbols_coinbase(res_data.text().await?)?,
+    Exchange::Dydx => extract_symbols_dydx(res_data.text().await?)?,
+    Exchange::GateIo => extract_symbols_gateio(res_data.text().await?)?,
+    Exchange::Hyperliquid => extract_symbols_hyperliquid(res_data.text().await?)?,
+    Exchange::Kraken => extract_symbols_kraken(res_data.text().await?)?,
+    Exchange::KuCoin => extract_symbols_kucoin(res_data.text().await?)?,
+    Exchange::Okx => extract_symbols_okx(res_data.text().await?)?,
+    Exchange::Tiingo => extract_symbols_tiingo(res_data.text().await?)?,
+    Exchange::Twelve => extract_symbols_twelve(res_data.text().await?)?,
+    Exchange::Yahoo => extract_symbols_yahoo(res_data.text().await?)?
   };
 
   Ok(tickers)
 }
 
+/// Request tickers
+/// Requests list of available tickers for a given exchange
+pub async fn request_symbols(exchange: &Exchange, asset_type: Option<AssetType>) -> Result<Vec<String>, SmartError> {
+  let listings: Vec<SymbolListing> = request_symbol_listings(exchange, asset_type).await?;
+  let tickers: Vec<String> = listings.into_iter()
+    .filter(|l| l.is_trading)
+    .map(|l| l.symbol)
+    .collect();
+  Ok(tickers)
+}
+
+/// Request Screener Symbols
+/// Trading symbols quoted in the exchange's preferred quote currency (avoids screening BTCUSDT against BTCUSDC)
+/// Twelve, Alpaca, AlphaVantage, Tiingo and Yahoo symbols are returned unfiltered as their quotes are not dollar pairs, as are Hyperliquid coins which carry no quote
+pub async fn request_screener_symbols(exchange: &Exchange, asset_type: Option<AssetType>) -> Result<Vec<String>, SmartError> {
+  let tickers: Vec<String> = request_symbols(exchange, asset_type).await?;
+  if matches!(exchange, Exchange::Alpaca | Exchange::AlphaVantage | Exchange::Tiingo | Exchange::Twelve | Exchange::Yahoo | Exchange::Hyperliquid) { return Ok(tickers) }
+  Ok(filter_preferred_quote(tickers, exchange))
+}
+
+/// Filter Preferred Quote
+/// Keeps symbols quoted in the exchange's preferred quote currency
+pub fn filter_preferred_quote(symbols: Vec<String>, exchange: &Exchange) -> Vec<String> {
+  symbols.into_iter().filter(|s| base_if_preferred_quote(s, exchange).is_some()).collect()
+}
+
+/// Cached Symbol Listings
+/// Returns symbol listings for an exchange, only calling the api once the cache has expired
+pub async fn cached_symbol_listings(exchange: &Exchange) -> Result<Vec<SymbolListing>, SmartError> {
+  request_symbol_listings(exchange, None).await
+}
+
+/// Validate Symbol
+/// Checks a symbol against the exchange listings
+pub fn validate_symbol(listings: &[SymbolListing], symbol: &str) -> Result<(), SmartError> {
+  match listings.iter().find(|l| l.symbol == symbol) {
+    Some(listing) if listing.is_trading => Ok(()),
+    Some(_) => Err(SmartError::Delisted(symbol.to_string())),
+    None => Err(SmartError::UnknownSymbol(symbol.to_string()))
+  }
+}
+
+/// Validate Pair
+/// Confirms both symbols exist and are trading before any candles are fetched
+/// Twelve and AlphaVantage listings are split by asset type and so are not validated, nor are Alpaca's and Tiingo's which need credentials or Yahoo's which are partial
+pub async fn validate_pair(exchange: &Exchange, asset_0: &str, asset_1: &str) -> Result<(), SmartError> {
+  if matches!(exchange, Exchange::Alpaca | Exchange::AlphaVantage | Exchange::Tiingo | Exchange::Twelve | Exchange::Yahoo) { return Ok(()) }
+  let listings: Vec<SymbolListing> = cached_symbol_listings(exchange).await?;
+  validate_symbol(&listings, asset_0)?;
+  validate_symbol(&listings, asset_1)?;
+  Ok(())
+}
+
 #[cfg(test)]
 mod tests {
   use crate::pricing::models::{Exchange, AssetType};
   use super::request_symbols;
 
+  #[tokio::test]
+  async fn it_serves_symbol_listings_within_ttl() {
+    use super::{cached_listings, listings_key, store_listings, SymbolListing, DEFAULT_SYMBOL_CACHE_TTL_SECS};
+    let listings: Vec<SymbolListing> = vec![SymbolListing { symbol: "SCTEST".to_string(), is_trading: true }];
+    let key: String = listings_key(&Exchange::GateIo, &Some(AssetType::Crypto));
+    store_listings(key.clone(), &listings, 1_000);
+
+    // Asset types are cached separately and entries expire after the ttl
+    assert_eq!(cached_listings(&key, 1_000 + DEFAULT_SYMBOL_CACHE_TTL_SECS - 1), Some(listings));
+    assert!(cached_listings(&listings_key(&Exchange::GateIo, &None), 1_000).is_none());
+    assert!(cached_listings(&key, 1_000 + DEFAULT_SYMBOL_CACHE_TTL_SECS).is_none());
+  }
+
+  #[tokio::test]
+  async fn tests_filter_preferred_quote() {
+    use super::filter_preferred_quote;
+    let symbols: Vec<String> = vec!["BTCUSDT".to_string(), "BTCUSDC".to_string(), "ETHBTC".to_string()];
+    assert_eq!(filter_preferred_quote(symbols, &Exchange::ByBit), vec!["BTCUSDT".to_string()]);
+  }
+
+  #[tokio::test]
+  async fn tests_validate_symbol_errors() {
+    use crate::SmartError;
+    use super::{validate_symbol, SymbolListing};
+    let listings: Vec<SymbolListing> = vec![
+      SymbolListing { symbol: "BTCUSDT".to_string(), is_trading: true },
+      SymbolListing { symbol: "LUNAUSDT".to_string(), is_trading: false }
+    ];
+    assert!(validate_symbol(&listings, "BTCUSDT").is_ok());
+    assert!(matches!(validate_symbol(&listings, "LUNAUSDT"), Err(SmartError::Delisted(_))));
+    assert!(matches!(validate_symbol(&listings, "NOPEUSDT"), Err(SmartError::UnknownSymbol(_))));
+  }
+
   #[tokio::test]
   async fn tests_get_available_symbols_binance_main() {
     let exchange: Exchange = Exchange::Binance;
@@ -186,6 +556,13 @@ mod tests {
     assert!(tickers.len() > 0);
   }
 
+  #[tokio::test]
+  async fn tests_get_available_symbols_binance_spot() {
+    let exchange: Exchange = Exchange::BinanceSpot;
+    let tickers: Vec<String> = request_symbols(&exchange, None).await.unwrap();
+    assert!(!tickers.is_empty());
+  }
+
   #[tokio::test]
   async fn tests_get_available_symbols_binance_us() {
     let exchange: Exchange = Exchange::BinanceUs;
@@ -214,6 +591,156 @@ mod tests {
     assert!(tickers.len() > 0);
   }
 
+  #[tokio::test]
+  async fn tests_extract_symbols_bitfinex() {
+    use super::extract_symbols_bitfinex;
+    let json_text: String = serde_json::json!([["BTCUSD", "DOGE:USD"]]).to_string();
+    let listings = extract_symbols_bitfinex(json_text).unwrap();
+    assert_eq!(listings.iter().map(|l| l.symbol.as_str()).collect::<Vec<&str>>(), vec!["tBTCUSD", "tDOGE:USD"]);
+  }
+
+  #[tokio::test]
+  async fn tests_get_available_symbols_bitfinex() {
+    let exchange: Exchange = Exchange::Bitfinex;
+    let tickers: Vec<String> = request_symbols(&exchange, None).await.unwrap();
+    assert!(tickers.contains(&"tBTCUSD".to_string()));
+  }
+
+  #[tokio::test]
+  async fn tests_extract_symbols_dydx() {
+    use super::extract_symbols_dydx;
+    let json_text: String = serde_json::json!({ "markets": {
+      "ETH-USD": { "ticker": "ETH-USD", "status": "ACTIVE", "oraclePrice": "3000.5" },
+      "LUNA-USD": { "ticker": "LUNA-USD", "status": "FINAL_SETTLEMENT", "oraclePrice": "0.5" }
+    }}).to_string();
+    let listings = extract_symbols_dydx(json_text).unwrap();
+    assert_eq!(listings[0].symbol, "ETH-USD");
+    assert!(listings[0].is_trading && !listings[1].is_trading);
+  }
+
+  #[tokio::test]
+  async fn tests_extract_symbols_alpaca() {
+    use super::extract_symbols_alpaca;
+    let json_text: String = serde_json::json!([
+      { "symbol": "AAPL", "class": "us_equity", "status": "active", "tradable": true },
+      { "symbol": "XYZ", "class": "us_equity", "status": "active", "tradable": false }
+    ]).to_string();
+    let listings = extract_symbols_alpaca(json_text).unwrap();
+    assert_eq!(listings[0].symbol, "AAPL");
+    assert!(listings[0].is_trading && !listings[1].is_trading);
+  }
+
+  #[tokio::test]
+  async fn tests_extract_symbols_alpha_vantage() {
+    use super::extract_symbols_alpha_vantage;
+    let csv_text: String = [
+      "symbol,name,exchange,assetType,ipoDate,delistingDate,status",
+      "AAPL,Apple Inc,NASDAQ,Stock,1980-12-12,null,Active",
+      "BRK-B,Berkshire Hathaway Inc, Class B,NYSE,Stock,1996-05-09,null,Active",
+      "SPY,SPDR S&P 500 ETF Trust,NYSE ARCA,ETF,1993-01-29,null,Active"
+    ].join("\r\n");
+    let stocks = extract_symbols_alpha_vantage(csv_text.clone(), Some(AssetType::Stock)).unwrap();
+    assert_eq!(stocks.iter().map(|l| l.symbol.as_str()).collect::<Vec<_>>(), vec!["AAPL", "BRK-B"]);
+    assert!(stocks.iter().all(|l| l.is_trading));
+    assert_eq!(extract_symbols_alpha_vantage(csv_text.clone(), None).unwrap().len(), 3);
+    assert!(extract_symbols_alpha_vantage(csv_text, Some(AssetType::Forex)).unwrap().is_empty());
+  }
+
+  #[tokio::test]
+  async fn tests_extract_symbols_gateio() {
+    use super::extract_symbols_gateio;
+    let json_text: String = serde_json::json!([
+      { "name": "PEPE_USDT", "type": "direct", "in_delisting": false },
+      { "name": "LUNC_USDT", "type": "direct", "in_delisting": true }
+    ]).to_string();
+    let listings = extract_symbols_gateio(json_text).unwrap();
+    assert_eq!(listings[1].symbol, "PEPE_USDT");
+    assert!(listings[1].is_trading && !listings[0].is_trading);
+  }
+
+  #[tokio::test]
+  async fn tests_get_available_symbols_gateio() {
+    let exchange: Exchange = Exchange::GateIo;
+    let tickers: Vec<String> = request_symbols(&exchange, None).await.unwrap();
+    assert!(tickers.contains(&"BTC_USDT".to_string()));
+  }
+
+  #[tokio::test]
+  async fn tests_extract_symbols_hyperliquid() {
+    use super::extract_symbols_hyperliquid;
+    let json_text: String = serde_json::json!({ "universe": [
+      { "name": "kPEPE", "szDecimals": 0, "maxLeverage": 10 },
+      { "name": "FTT", "szDecimals": 1, "maxLeverage": 3, "isDelisted": true }
+    ]}).to_string();
+    let listings = extract_symbols_hyperliquid(json_text).unwrap();
+    assert_eq!(listings[1].symbol, "kPEPE");
+    assert!(listings[1].is_trading && !listings[0].is_trading);
+  }
+
+  #[tokio::test]
+  async fn tests_get_available_symbols_hyperliquid() {
+    let exchange: Exchange = Exchange::Hyperliquid;
+    let tickers: Vec<String> = request_symbols(&exchange, None).await.unwrap();
+    assert!(tickers.contains(&"BTC".to_string()));
+  }
+
+  #[tokio::test]
+  async fn tests_extract_symbols_kraken() {
+    use super::extract_symbols_kraken;
+    let json_text: String = serde_json::json!({ "error": [], "result": {
+      "XXBTZUSD": { "altname": "XBTUSD", "wsname": "XBT/USD", "status": "online" },
+      "LUNAUSD": { "altname": "LUNAUSD", "wsname": "LUNA/USD", "status": "cancel_only" }
+    }}).to_string();
+    let listings = extract_symbols_kraken(json_text).unwrap();
+    assert_eq!(listings[1].symbol, "XBTUSD");
+    assert!(listings[1].is_trading && !listings[0].is_trading);
+  }
+
+  #[tokio::test]
+  async fn tests_get_available_symbols_kraken() {
+    let exchange: Exchange = Exchange::Kraken;
+    let tickers: Vec<String> = request_symbols(&exchange, None).await.unwrap();
+    assert!(tickers.contains(&"XBTUSD".to_string()));
+  }
+
+  #[tokio::test]
+  async fn tests_extract_symbols_kucoin() {
+    use super::extract_symbols_kucoin;
+    let json_text: String = serde_json::json!({ "code": "200000", "data": [
+      { "symbol": "PEPE-USDT", "baseCurrency": "PEPE", "quoteCurrency": "USDT", "enableTrading": true },
+      { "symbol": "AKRO-USDT", "baseCurrency": "AKRO", "quoteCurrency": "USDT", "enableTrading": false }
+    ]}).to_string();
+    let listings = extract_symbols_kucoin(json_text).unwrap();
+    assert_eq!(listings[1].symbol, "PEPE-USDT");
+    assert!(listings[1].is_trading && !listings[0].is_trading);
+  }
+
+  #[tokio::test]
+  async fn tests_get_available_symbols_kucoin() {
+    let exchange: Exchange = Exchange::KuCoin;
+    let tickers: Vec<String> = request_symbols(&exchange, None).await.unwrap();
+    assert!(tickers.contains(&"BTC-USDT".to_string()));
+  }
+
+  #[tokio::test]
+  async fn tests_extract_symbols_okx() {
+    use super::extract_symbols_okx;
+    let json_text: String = serde_json::json!({ "code": "0", "msg": "", "data": [
+      { "instId": "ETH-USDT-SWAP", "instType": "SWAP", "state": "live" },
+      { "instId": "BTC-USDT-SWAP", "instType": "SWAP", "state": "suspend" }
+    ]}).to_string();
+    let listings = extract_symbols_okx(json_text).unwrap();
+    assert_eq!(listings[0].symbol, "BTC-USDT-SWAP");
+    assert!(!listings[0].is_trading && listings[1].is_trading);
+  }
+
+  #[tokio::test]
+  async fn tests_get_available_symbols_okx() {
+    let exchange: Exchange = Exchange::Okx;
+    let tickers: Vec<String> = request_symbols(&exchange, None).await.unwrap();
+    assert!(tickers.contains(&"BTC-USDT-SWAP".to_string()));
+  }
+
   #[tokio::test]
   async fn tests_get_available_symbols_twelve() {
     let exchange: Exchange = Exchange::Twelve;
@@ -1,12 +1,12 @@
-use chrono::{DateTime, Utc, NaiveDateTime, Timelike, Duration};
+use chrono::{DateTime, Datelike, Utc, NaiveDate, NaiveDateTime, Timelike, Duration, Weekday};
+use super::clock::now_secs;
 use super::models::IntervalPeriod;
 use crate::SmartError;
 
 /// Gets Current World Time in UTC
-/// Retrieves world time from external API and tries another if fails
+/// Retrieves current time in seconds from the configured clock
 pub fn get_world_time_utc() -> Result<i64, SmartError> {
-  let now: DateTime<Utc> = Utc::now();
-  Ok(now.timestamp())
+  Ok(now_secs())
 }
 
 /// Convert unix timestamp to ISO format
@@ -18,6 +18,12 @@ pub fn convert_timestamp_to_iso(timestamp: i64) -> String {
   datetime.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
 }
 
+/// Convert unix timestamp to date
+/// Required for providers like Tiingo which bound requests by whole (UTC) days
+pub fn convert_timestamp_to_date(timestamp: i64) -> String {
+  convert_timestamp_to_dt(timestamp).format("%Y-%m-%d").to_string()
+}
+
 /// Convert ISO format to unix timestamp
 /// Required for exchanges like DYDX
 pub fn convert_iso_to_timestamp(mut iso_string: String, from_format: &str) -> u64 {
@@ -46,6 +52,36 @@ pub fn convert_iso_to_timestamp(mut iso_string: String, from_format: &str) -> u6
   dt.timestamp() as u64
 }
 
+/// Is US Eastern DST
+/// DST runs from 02:00 on the second Sunday of March to 02:00 on the first Sunday of November (local time)
+fn is_us_eastern_dst(local_dt: NaiveDateTime) -> bool {
+  let year: i32 = local_dt.year();
+  let dst_start: NaiveDateTime = NaiveDate::from_weekday_of_month_opt(year, 3, Weekday::Sun, 2)
+    .and_then(|d| d.and_hms_opt(2, 0, 0))
+    .expect("Failed to build DST start");
+  let dst_end: NaiveDateTime = NaiveDate::from_weekday_of_month_opt(year, 11, Weekday::Sun, 1)
+    .and_then(|d| d.and_hms_opt(2, 0, 0))
+    .expect("Failed to build DST end");
+  local_dt >= dst_start && local_dt < dst_end
+}
+
+/// Convert US/Eastern local time to unix timestamp
+/// Required for providers like Alpha Vantage which label intraday bars in exchange local time
+pub fn convert_us_eastern_to_timestamp(local_string: &str) -> u64 {
+  let local_dt: NaiveDateTime = NaiveDateTime::parse_from_str(local_string.trim(), "%Y-%m-%d %H:%M:%S")
+    .expect("Failed to parse datetime from local_string");
+  let offset_hours: i64 = if is_us_eastern_dst(local_dt) { 4 } else { 5 };
+  let utc_dt: NaiveDateTime = local_dt + Duration::hours(offset_hours);
+  DateTime::<Utc>::from_naive_utc_and_offset(utc_dt, Utc).timestamp() as u64
+}
+
+/// Convert unix timestamp to US/Eastern local time
+/// Used to place bars within exchange sessions (e.g. the NYSE 09:30 open)
+pub fn convert_timestamp_to_us_eastern(timestamp: i64) -> NaiveDateTime {
+  let standard: NaiveDateTime = convert_timestamp_to_dt(timestamp).naive_utc() - Duration::hours(5);
+  if is_us_eastern_dst(standard) { standard + Duration::hours(1) } else { standard }
+}
+
 /// Convert unix timestamp to DateTime
 /// Takes in timestamp and converts into datetime
 fn convert_timestamp_to_dt(timestamp: i64) -> DateTime<Utc> {
@@ -110,4 +146,14 @@ mod tests {
     let unix_start: i64 = subtract_time(unix_ts, &interval, &0);
     assert_eq!(unix_start, 1688083200);
   }
+
+  #[tokio::test]
+  async fn it_converts_us_eastern_to_timestamp() {
+    let summer: u64 = convert_us_eastern_to_timestamp("2023-07-03 09:30:00");
+    assert_eq!(summer, 1688391000);
+    let winter: u64 = convert_us_eastern_to_timestamp("2023-01-03 09:30:00");
+    assert_eq!(winter, 1672756200);
+    assert_eq!(convert_timestamp_to_us_eastern(summer as i64).to_string(), "2023-07-03 09:30:00");
+    assert_eq!(convert_timestamp_to_us_eastern(winter as i64).to_string(), "2023-01-03 09:30:00");
+  }
 }
@@ -0,0 +1,249 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use super::models::{Exchange, HistoricalPrices, LabelConvention, OhlcvSeries};
+use super::utils::api_request;
+
+/*
+  Trades
+  Raw trade (tick) fetching with local aggregation into candles of any resolution
+  Kline endpoints start at 1 minute (and some venues skip intervals), trades allow bars of a few seconds
+*/
+
+/// Largest number of trade pages requested per fetch
+const MAX_TRADE_PAGES: usize = 50;
+
+/// Binance aggTrades window when both start and end times are given
+const BINANCE_WINDOW_MS: u64 = 60 * 60 * 1000;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, TS)]
+#[ts(export)]
+pub struct Trade {
+  pub id: u64,
+  pub time_ms: u64,
+  pub price: f64,
+  pub qty: f64,
+  pub is_buyer_maker: bool // true when the taker sold
+}
+
+/// Get trades url
+/// Retrieves the trades url for a given exchange (None if not supported)
+fn get_trades_url(exchange: &Exchange) -> Option<&'static str> {
+  match exchange {
+    Exchange::Binance => Some("https://fapi.binance.com/fapi/v1/aggTrades?symbol={symbol}&limit=1000"),
+    Exchange::BinanceSpot => Some("https://api.binance.com/api/v3/aggTrades?symbol={symbol}&limit=1000"),
+    Exchange::BinanceUs => Some("https://api.binance.us/api/v3/aggTrades?symbol={symbol}&limit=1000"),
+    Exchange::Coinbase => Some("https://api.exchange.coinbase.com/products/{symbol}/trades?limit=1000"),
+    _ => None
+  }
+}
+
+/// Extract Trades Binance
+/// Takes Binance aggregate trades and returns trades
+fn extract_trades_binance(data_obj: &serde_json::Value) -> Vec<Trade> {
+  data_obj.as_array().map(|items| items.iter().filter_map(|item| {
+    Some(Trade {
+      id: item.get("a")?.as_u64()?,
+      time_ms: item.get("T")?.as_u64()?,
+      price: item.get("p")?.as_str()?.parse().ok()?,
+      qty: item.get("q")?.as_str()?.parse().ok()?,
+      is_buyer_maker: item.get("m")?.as_bool()?
+    })
+  }).collect()).unwrap_or_default()
+}
+
+/// Extract Trades Coinbase
+/// Takes Coinbase trades (side is the maker's side) and returns trades
+fn extract_trades_coinbase(data_obj: &serde_json::Value) -> Vec<Trade> {
+  data_obj.as_array().map(|items| items.iter().filter_map(|item| {
+    let time: chrono::DateTime<chrono::FixedOffset> = chrono::DateTime::parse_from_rfc3339(item.get("time")?.as_str()?).ok()?;
+    Some(Trade {
+      id: item.get("trade_id")?.as_u64()?,
+      time_ms: u64::try_from(time.timestamp_millis()).ok()?,
+      price: item.get("price")?.as_str()?.parse().ok()?,
+      qty: item.get("size")?.as_str()?.parse().ok()?,
+      is_buyer_maker: item.get("side")?.as_str()? == "buy"
+    })
+  }).collect()).unwrap_or_default()
+}
+
+/// Parse Trades
+/// Structures a trades response for the exchange, oldest first
+pub fn parse_trades(exchange: &Exchange, data_obj: &serde_json::Value) -> Vec<Trade> {
+  let mut trades: Vec<Trade> = match exchange {
+    Exchange::Coinbase => extract_trades_coinbase(data_obj),
+    _ => extract_trades_binance(data_obj)
+  };
+  trades.sort_by_key(|t| (t.time_ms, t.id));
+  trades
+}
+
+/// Request Trades Page
+async fn request_trades_page(exchange: &Exchange, request_url: &str) -> Result<Vec<Trade>, SmartError> {
+  let res_data: reqwest::Response = api_request(request_url).await?;
+
+  // Guard: Ensure status code
+  if res_data.status() != 200 {
+    let e: String = format!("Failed to extract data: {:?}", res_data.text().await);
+    return Err(SmartError::APIResponseStatus(e));
+  }
+
+  let data_obj: serde_json::Value = res_data.json().await?;
+  Ok(parse_trades(exchange, &data_obj))
+}
+
+/// Fetch Trades
+/// Trades between two times (unix ms, inclusive), paging forward on Binance and backward on Coinbase
+/// Stops after MAX_TRADE_PAGES pages, so very active symbols may need shorter ranges
+pub async fn fetch_trades(exchange: &Exchange, symbol: &str, start_ms: u64, end_ms: u64) -> Result<Vec<Trade>, SmartError> {
+  let Some(base_url) = get_trades_url(exchange) else {
+    return Err(SmartError::RuntimeCheck(format!("Trades not supported for exchange: {}", exchange.as_string())))
+  };
+
+  // Guard: Ensure a valid range
+  if start_ms > end_ms {
+    return Err(SmartError::RuntimeCheck(format!("Trades start ({}) is after end ({})", start_ms, end_ms)));
+  }
+
+  let base_url: String = base_url.replace("{symbol}", symbol);
+  let mut trades: Vec<Trade> = vec![];
+  match exchange {
+
+    // Coinbase pages from the latest trade backwards by trade id
+    Exchange::Coinbase => {
+      let mut after: Option<u64> = None;
+      for _ in 0..MAX_TRADE_PAGES {
+        let request_url: String = match after {
+          Some(id) => format!("{}&after={}", base_url, id),
+          None => base_url.clone()
+        };
+        let page: Vec<Trade> = request_trades_page(exchange, &request_url).await?;
+        let Some(oldest) = page.first().copied() else { break };
+        trades.extend(page.into_iter().filter(|t| t.time_ms >= start_ms && t.time_ms <= end_ms));
+        if oldest.time_ms < start_ms { break }
+        after = Some(oldest.id);
+      }
+    },
+
+    // Binance pages forward by aggregate trade id, stepping windows of time until the first trade is found
+    _ => {
+      let window_url = |from: u64| format!("{}&startTime={}&endTime={}", base_url, from, end_ms.min(from + BINANCE_WINDOW_MS));
+      let mut window_start: u64 = start_ms;
+      let mut request_url: String = window_url(window_start);
+      for _ in 0..MAX_TRADE_PAGES {
+        let page: Vec<Trade> = request_trades_page(exchange, &request_url).await?;
+        let Some(latest) = page.last().copied() else {
+
+          // Guard: Only an empty window before any trades moves on to the next window
+          if !trades.is_empty() || window_start + BINANCE_WINDOW_MS >= end_ms { break }
+          window_start += BINANCE_WINDOW_MS + 1;
+          request_url = window_url(window_start);
+          continue
+        };
+        trades.extend(page.into_iter().filter(|t| t.time_ms >= start_ms && t.time_ms <= end_ms));
+        if latest.time_ms >= end_ms { break }
+        request_url = format!("{}&fromId={}", base_url, latest.id + 1);
+      }
+    }
+  }
+
+  trades.sort_by_key(|t| (t.time_ms, t.id));
+  trades.dedup_by_key(|t| t.id);
+  Ok(trades)
+}
+
+/// Aggregate Trades
+/// Builds candles of any whole number of seconds from trades (labels are bar open times, bars without trades are skipped)
+pub fn aggregate_trades(trades: &[Trade], bar_seconds: u64) -> Result<OhlcvSeries, SmartError> {
+
+  // Guard: Ensure a usable bar size
+  if bar_seconds == 0 {
+    return Err(SmartError::RuntimeCheck("Bar size must be at least 1 second".to_string()));
+  }
+
+  let mut sorted: Vec<&Trade> = trades.iter().filter(|t| t.price.is_finite() && t.qty.is_finite()).collect();
+  sorted.sort_by_key(|t| (t.time_ms, t.id));
+
+  let mut series: OhlcvSeries = OhlcvSeries {
+    labels: vec![], open: vec![], high: vec![], low: vec![], close: vec![], volume: vec![],
+    label_convention: LabelConvention::OpenTime
+  };
+  for trade in sorted {
+    let label: u64 = trade.time_ms / 1000 / bar_seconds * bar_seconds;
+    if series.labels.last() == Some(&label) {
+      let i: usize = series.labels.len() - 1;
+      series.high[i] = series.high[i].max(trade.price);
+      series.low[i] = series.low[i].min(trade.price);
+      series.close[i] = trade.price;
+      series.volume[i] += trade.qty;
+    } else {
+      series.labels.push(label);
+      series.open.push(trade.price);
+      series.high.push(trade.price);
+      series.low.push(trade.price);
+      series.close.push(trade.price);
+      series.volume.push(trade.qty);
+    }
+  }
+  Ok(series)
+}
+
+/// Fetch Trade Candles
+/// Fetches trades and aggregates them into close prices of the given bar size
+pub async fn fetch_trade_candles(exchange: &Exchange, symbol: &str, start_ms: u64, end_ms: u64, bar_seconds: u64) -> Result<HistoricalPrices, SmartError> {
+  let trades: Vec<Trade> = fetch_trades(exchange, symbol, start_ms, end_ms).await?;
+  let series: OhlcvSeries = aggregate_trades(&trades, bar_seconds)?;
+  Ok(HistoricalPrices { prices: series.close, labels: series.labels, label_convention: LabelConvention::OpenTime, fetch_report: None })
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_parses_trades() {
+    let binance: serde_json::Value = serde_json::json!([
+      { "a": 2, "p": "101.5", "q": "0.5", "f": 10, "l": 11, "T": 1700000001500u64, "m": false },
+      { "a": 1, "p": "101.0", "q": "1.0", "f": 9, "l": 9, "T": 1700000000500u64, "m": true }
+    ]);
+    let trades: Vec<Trade> = parse_trades(&Exchange::Binance, &binance);
+    assert_eq!(trades.iter().map(|t| t.id).collect::<Vec<u64>>(), vec![1, 2]);
+    assert!(trades[0].is_buyer_maker);
+
+    let coinbase: serde_json::Value = serde_json::json!([
+      { "time": "2023-11-14T22:13:21.123Z", "trade_id": 7, "price": "35000.10", "size": "0.01", "side": "sell" }
+    ]);
+    let trades: Vec<Trade> = parse_trades(&Exchange::Coinbase, &coinbase);
+    assert_eq!((trades[0].time_ms, trades[0].price, trades[0].is_buyer_maker), (1700000001123, 35000.10, false));
+  }
+
+  #[tokio::test]
+  async fn it_aggregates_trades_into_candles() {
+    let trade = |id: u64, time_ms: u64, price: f64, qty: f64| Trade { id, time_ms, price, qty, is_buyer_maker: false };
+    let trades: Vec<Trade> = vec![
+      trade(3, 1_700_000_012_000, 99.0, 2.0),
+      trade(1, 1_700_000_000_100, 100.0, 1.0),
+      trade(2, 1_700_000_004_900, 102.0, 0.5),
+      trade(4, 1_700_000_031_000, 101.0, 1.0)
+    ];
+
+    // 15 second bars, the bar from 1700000015 has no trades and is skipped
+    let series: OhlcvSeries = aggregate_trades(&trades, 15).unwrap();
+    assert_eq!(series.labels, vec![1_699_999_995, 1_700_000_010, 1_700_000_025]);
+    assert_eq!(series.open[0], 100.0);
+    assert_eq!((series.high[0], series.low[0], series.close[0], series.volume[0]), (102.0, 100.0, 102.0, 1.5));
+    assert_eq!(series.close[1], 99.0);
+    assert!(series.ensure_aligned().is_ok());
+    assert!(aggregate_trades(&trades, 0).is_err());
+    assert!(fetch_trades(&Exchange::Kraken, "XBTUSD", 0, 1).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn tests_fetch_trade_candles_binance() {
+    let end_ms: u64 = crate::pricing::clock::now_ms() as u64;
+    let prices: HistoricalPrices = fetch_trade_candles(&Exchange::Binance, "BTCUSDT", end_ms - 5 * 60 * 1000, end_ms, 10).await.unwrap();
+    assert!(!prices.prices.is_empty());
+  }
+}
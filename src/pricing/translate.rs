@@ -0,0 +1,282 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::SmartError;
+use super::models::{DataCriteria, Exchange};
+
+/*
+  Symbol Translation
+  Maps ticker formats between exchanges (BTCUSDT <-> BTC-USD <-> BTC/USD)
+  Allows the same logical pair to be re-run on a different exchange
+  The preferred quote per exchange (USDT vs USDC vs USD) is configurable and shared by translation and volume ranking
+*/
+
+static QUOTE_PREFERENCES: RwLock<Option<HashMap<Exchange, String>>> = RwLock::new(None);
+
+/// Quote currencies recognised on concatenated tickers (longest first)
+const KNOWN_QUOTES: [&str; 12] = ["FDUSD", "USDT", "USDC", "BUSD", "TUSD", "USD", "EUR", "GBP", "TRY", "BTC", "ETH", "BNB"];
+
+/// Suffix Binance COIN-M uses for perpetual (non dated) contracts
+const PERPETUAL_SUFFIX: &str = "_PERP";
+
+/// Suffix OKX uses for perpetual swap instruments
+const SWAP_SUFFIX: &str = "-SWAP";
+
+/// Prefix Bitfinex uses for trading (as opposed to funding) pairs
+const BITFINEX_PAIR_PREFIX: char = 't';
+
+/// Kraken specific asset codes (Kraken code, common code)
+const KRAKEN_ASSET_ALIASES: [(&str, &str); 2] = [("XBT", "BTC"), ("XDG", "DOGE")];
+
+/// Quotes treated as US dollar equivalent when translating
+const USD_QUOTES: [&str; 6] = ["USD", "USDT", "USDC", "BUSD", "TUSD", "FDUSD"];
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SymbolParts {
+  pub base: String,
+  pub quote: String
+}
+
+/// Symbol Separator
+/// Separator used by each exchange between base and quote (None if concatenated)
+fn symbol_separator(exchange: &Exchange) -> Option<char> {
+  match exchange {
+    Exchange::Binance | Exchange::BinanceCoinM | Exchange::BinanceSpot | Exchange::BinanceUs | Exchange::Bitfinex | Exchange::ByBit
+      | Exchange::ByBitInverse | Exchange::Hyperliquid | Exchange::Kraken | Exchange::Tiingo => None,
+    Exchange::Coinbase | Exchange::Dydx | Exchange::KuCoin | Exchange::Okx | Exchange::Yahoo => Some('-'),
+    Exchange::GateIo => Some('_'),
+    Exchange::Alpaca | Exchange::AlphaVantage | Exchange::Twelve => Some('/')
+  }
+}
+
+/// Default USD Quote
+/// The dollar quote an exchange primarily lists pairs against
+pub fn default_usd_quote(exchange: &Exchange) -> &'static str {
+  match exchange {
+    Exchange::Binance | Exchange::BinanceSpot | Exchange::BinanceUs | Exchange::ByBit | Exchange::GateIo | Exchange::KuCoin | Exchange::Okx => "USDT",
+    Exchange::BinanceCoinM | Exchange::ByBitInverse => "USD", // inverse contracts are quoted in USD
+    Exchange::Hyperliquid => "USDC", // coins are listed without a quote and settled in USDC
+    Exchange::Alpaca | Exchange::AlphaVantage | Exchange::Bitfinex | Exchange::Coinbase | Exchange::Dydx | Exchange::Kraken | Exchange::Tiingo
+      | Exchange::Twelve | Exchange::Yahoo => "USD"
+  }
+}
+
+/// Set Quote Preferences
+/// Overrides the quote currency used per exchange (e.g. USDC on ByBit), replacing any previous overrides
+pub fn set_quote_preferences(preferences: HashMap<Exchange, String>) {
+  if let Ok(mut store) = QUOTE_PREFERENCES.write() {
+    let upper: HashMap<Exchange, String> = preferences.into_iter().map(|(k, v)| (k, v.to_uppercase())).collect();
+    *store = Some(upper);
+  }
+}
+
+/// Preferred Quote
+/// Configured quote currency for an exchange, falling back to its default dollar quote
+pub fn preferred_quote(exchange: &Exchange) -> String {
+  QUOTE_PREFERENCES.read().ok()
+    .and_then(|store| store.as_ref().and_then(|m| m.get(exchange).cloned()))
+    .unwrap_or_else(|| default_usd_quote(exchange).to_string())
+}
+
+/// Base If Preferred Quote
+/// Base asset of a ticker when it is quoted in the exchange's preferred quote currency
+pub fn base_if_preferred_quote(symbol: &str, exchange: &Exchange) -> Option<String> {
+  let parts: SymbolParts = parse_symbol(symbol).ok()?;
+  if parts.quote == preferred_quote(exchange) { Some(parts.base) } else { None }
+}
+
+/// Is USD Quote
+/// Whether a quote currency is dollar denominated (fiat or stablecoin)
+pub fn is_usd_quote(quote: &str) -> bool {
+  USD_QUOTES.contains(&quote.to_uppercase().as_str())
+}
+
+/// Is Tiingo Crypto
+/// Tiingo crypto tickers concatenate base and quote (btcusd) where stock tickers run to five letters at most (BRK-B uses a dash)
+pub fn is_tiingo_crypto(symbol: &str) -> bool {
+  let symbol: &str = symbol.trim();
+  symbol.len() > 5 && symbol.chars().all(|c| c.is_ascii_alphanumeric()) && parse_symbol(symbol).is_ok()
+}
+
+/// Common Asset Code
+/// Maps exchange specific asset codes (Kraken XBT) to their common code (BTC)
+fn common_asset_code(asset: &str) -> String {
+  KRAKEN_ASSET_ALIASES.iter()
+    .find(|(kraken, _)| *kraken == asset)
+    .map(|(_, common)| common.to_string())
+    .unwrap_or_else(|| asset.to_string())
+}
+
+/// Strip Bitfinex Prefix
+/// Bitfinex trading pairs carry a lowercase t prefix (tBTCUSD), lowercase tickers from elsewhere are left untouched
+fn strip_bitfinex_prefix(symbol: &str) -> &str {
+  match symbol.strip_prefix(BITFINEX_PAIR_PREFIX) {
+    Some(pair) if !pair.is_empty() && !pair.chars().any(|c| c.is_lowercase()) => pair,
+    _ => symbol
+  }
+}
+
+/// Parse Symbol
+/// Splits an exchange ticker into base and quote
+pub fn parse_symbol(symbol: &str) -> Result<SymbolParts, SmartError> {
+  let symbol: &str = strip_bitfinex_prefix(symbol.trim());
+  let symbol: String = symbol.to_uppercase();
+  let symbol: &str = symbol.strip_suffix(PERPETUAL_SUFFIX)
+    .or_else(|| symbol.strip_suffix(SWAP_SUFFIX))
+    .unwrap_or(&symbol);
+
+  for sep in ['-', '/', '_', ':'] {
+    if let Some((base, quote)) = symbol.split_once(sep) {
+      return Ok(SymbolParts { base: common_asset_code(base), quote: quote.to_string() });
+    }
+  }
+
+  // Kraken codes take precedence (XBTUSD would otherwise match the TUSD quote)
+  for (kraken, common) in KRAKEN_ASSET_ALIASES {
+    if let Some(quote) = symbol.strip_prefix(kraken) {
+      if KNOWN_QUOTES.contains(&quote) {
+        return Ok(SymbolParts { base: common.to_string(), quote: quote.to_string() });
+      }
+    }
+  }
+
+  for quote in KNOWN_QUOTES {
+    if let Some(base) = symbol.strip_suffix(quote) {
+      if !base.is_empty() {
+        return Ok(SymbolParts { base: common_asset_code(base), quote: quote.to_string() });
+      }
+    }
+  }
+
+  Err(SmartError::RuntimeCheck(format!("Unable to identify quote currency for symbol: {}", symbol)))
+}
+
+/// Format Symbol
+/// Structures base and quote into an exchange ticker
+pub fn format_symbol(exchange: &Exchange, parts: &SymbolParts) -> String {
+  let base: &str = match exchange {
+    Exchange::Kraken => KRAKEN_ASSET_ALIASES.iter()
+      .find(|(_, common)| *common == parts.base)
+      .map(|(kraken, _)| *kraken)
+      .unwrap_or(&parts.base),
+    _ => &parts.base
+  };
+  let symbol: String = match symbol_separator(exchange) {
+    Some(sep) => format!("{}{}{}", base, sep, parts.quote),
+    None => format!("{}{}", base, parts.quote)
+  };
+  match exchange {
+    Exchange::BinanceCoinM => format!("{}{}", symbol, PERPETUAL_SUFFIX),
+    Exchange::Bitfinex if base.len() > 3 || parts.quote.len() > 3 => format!("{}{}:{}", BITFINEX_PAIR_PREFIX, base, parts.quote), // long codes are colon separated
+    Exchange::Bitfinex => format!("{}{}", BITFINEX_PAIR_PREFIX, symbol),
+    Exchange::Okx => format!("{}{}", symbol, SWAP_SUFFIX),
+    Exchange::Hyperliquid => base.to_string(), // perpetuals are named by coin only
+    Exchange::Tiingo => symbol.to_lowercase(), // crypto tickers are lowercase (btcusd)
+    _ => symbol
+  }
+}
+
+/// Translate Symbol
+/// Converts a ticker from one exchange format to another
+/// Dollar quotes are mapped to the target exchange's preferred quote
+pub fn translate_symbol(symbol: &str, to_exchange: &Exchange) -> Result<String, SmartError> {
+  let mut parts: SymbolParts = parse_symbol(symbol)?;
+  if is_usd_quote(&parts.quote) {
+    parts.quote = preferred_quote(to_exchange);
+  }
+  Ok(format_symbol(to_exchange, &parts))
+}
+
+impl DataCriteria {
+
+  /// For Exchange
+  /// Returns the same logical pair criteria translated for another exchange
+  pub fn for_exchange(&self, exchange: &Exchange) -> Result<Self, SmartError> {
+    Ok(Self {
+      exchange: exchange.clone(),
+      asset_0: translate_symbol(&self.asset_0, exchange)?,
+      asset_1: translate_symbol(&self.asset_1, exchange)?,
+      interval_period: self.interval_period.clone(),
+      fallback_exchanges: vec![]
+    })
+  }
+
+  /// Source Chain
+  /// Primary criteria followed by the criteria translated for each fallback exchange
+  /// Fallbacks whose symbols cannot be translated are skipped
+  pub fn source_chain(&self) -> Vec<Self> {
+    let primary: Self = Self { fallback_exchanges: vec![], ..self.clone() };
+    let fallbacks = self.fallback_exchanges.iter()
+      .filter(|exchange| *exchange != &self.exchange)
+      .filter_map(|exchange| self.for_exchange(exchange).ok());
+    std::iter::once(primary).chain(fallbacks).collect()
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_translates_between_exchange_formats() {
+    assert_eq!(translate_symbol("BTCUSDT", &Exchange::Coinbase).unwrap(), "BTC-USD");
+    assert_eq!(translate_symbol("BTC-USD", &Exchange::Twelve).unwrap(), "BTC/USD");
+    assert_eq!(translate_symbol("BTCUSDT", &Exchange::Alpaca).unwrap(), "BTC/USD");
+    assert_eq!(translate_symbol("BTC-USD", &Exchange::Tiingo).unwrap(), "btcusd");
+    assert!(is_tiingo_crypto("btcusd") && is_tiingo_crypto("ETHBTC"));
+    assert!(!is_tiingo_crypto("AAPL") && !is_tiingo_crypto("BRK-B"));
+    assert_eq!(translate_symbol("BTCUSDT", &Exchange::Yahoo).unwrap(), "BTC-USD");
+    assert_eq!(translate_symbol("ETH/USD", &Exchange::ByBit).unwrap(), "ETHUSDT");
+    assert_eq!(translate_symbol("ETHBTC", &Exchange::Dydx).unwrap(), "ETH-BTC");
+    assert_eq!(translate_symbol("BTCUSDT", &Exchange::BinanceCoinM).unwrap(), "BTCUSD_PERP");
+    assert_eq!(translate_symbol("ETHUSD_PERP", &Exchange::ByBitInverse).unwrap(), "ETHUSD");
+    assert_eq!(translate_symbol("BTCUSDT", &Exchange::Kraken).unwrap(), "XBTUSD");
+    assert_eq!(translate_symbol("XBTUSD", &Exchange::Coinbase).unwrap(), "BTC-USD");
+    assert_eq!(translate_symbol("SOL-USD", &Exchange::Kraken).unwrap(), "SOLUSD");
+    assert_eq!(translate_symbol("BTCUSDT", &Exchange::Bitfinex).unwrap(), "tBTCUSD");
+    assert_eq!(translate_symbol("DOGE-USD", &Exchange::Bitfinex).unwrap(), "tDOGE:USD");
+    assert_eq!(translate_symbol("tDOGE:USD", &Exchange::Binance).unwrap(), "DOGEUSDT");
+    assert_eq!(translate_symbol("trxusdt", &Exchange::Coinbase).unwrap(), "TRX-USD");
+    assert_eq!(translate_symbol("PEPE-USD", &Exchange::GateIo).unwrap(), "PEPE_USDT");
+    assert_eq!(translate_symbol("PEPE_USDT", &Exchange::Coinbase).unwrap(), "PEPE-USD");
+    assert_eq!(translate_symbol("SOLUSDT", &Exchange::Hyperliquid).unwrap(), "SOL");
+    assert_eq!(translate_symbol("BTCUSD_PERP", &Exchange::BinanceSpot).unwrap(), "BTCUSDT");
+    assert_eq!(translate_symbol("BTC-USD", &Exchange::KuCoin).unwrap(), "BTC-USDT");
+    assert_eq!(translate_symbol("BTCUSDT", &Exchange::Okx).unwrap(), "BTC-USDT-SWAP");
+    assert_eq!(translate_symbol("ETH-USDT-SWAP", &Exchange::Binance).unwrap(), "ETHUSDT");
+  }
+
+  #[tokio::test]
+  async fn it_applies_configured_quote_preferences() {
+    assert_eq!(preferred_quote(&Exchange::BinanceUs), "USDT");
+    set_quote_preferences(HashMap::from([(Exchange::BinanceUs, "usdc".to_string())]));
+    assert_eq!(translate_symbol("SOL-USD", &Exchange::BinanceUs).unwrap(), "SOLUSDC");
+    assert_eq!(base_if_preferred_quote("SOLUSDC", &Exchange::BinanceUs), Some("SOL".to_string()));
+    assert_eq!(base_if_preferred_quote("SOLUSDT", &Exchange::BinanceUs), None);
+    set_quote_preferences(HashMap::new());
+    assert_eq!(preferred_quote(&Exchange::BinanceUs), "USDT");
+  }
+
+  #[tokio::test]
+  async fn it_builds_failover_source_chain() {
+    let criteria: DataCriteria = DataCriteria {
+      exchange: Exchange::Binance,
+      asset_0: "BTCUSDT".to_string(),
+      asset_1: "ETHUSDT".to_string(),
+      interval_period: crate::pricing::models::IntervalPeriod::Hour(1, 100),
+      fallback_exchanges: vec![Exchange::Coinbase, Exchange::Binance, Exchange::Dydx]
+    };
+    let chain: Vec<DataCriteria> = criteria.source_chain();
+    let exchanges: Vec<Exchange> = chain.iter().map(|c| c.exchange.clone()).collect();
+    assert_eq!(exchanges, vec![Exchange::Binance, Exchange::Coinbase, Exchange::Dydx]);
+    assert_eq!(chain[1].asset_0, "BTC-USD");
+    assert!(chain.iter().all(|c| c.fallback_exchanges.is_empty()));
+  }
+
+  #[tokio::test]
+  async fn it_rejects_unknown_quotes() {
+    assert!(parse_symbol("ABCXYZ").is_err());
+  }
+}
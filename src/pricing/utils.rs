@@ -1,16 +1,185 @@
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::Duration;
 use crate::SmartError;
-use super::models::HistoricalPrices;
+use super::models::{HistoricalPrices, MissingDataPolicy};
+use super::clock::get_clock;
+use super::breaker::{check_breaker, record_outcome, is_breaker_failure};
+use super::ratelimit::{pause_for_url_ms, record_response_headers};
+
+/// Query parameters holding credentials, matched case insensitively
+const SECRET_PARAMS: [&str; 10] = [
+  "apikey", "api_key", "key", "token", "secret", "signature", "passphrase", "access_key", "apca-api-key-id", "apca-api-secret-key"
+];
+
+/// Query parameters moved into request headers before sending (Alpaca only accepts credentials as headers)
+/// Carrying them in the url keeps redaction, fixtures and coalescing keyed on the url alone
+const HEADER_PARAMS: [&str; 2] = ["apca-api-key-id", "apca-api-secret-key"];
+
+/// Url path prefixes followed by a credential (Telegram bot tokens and Discord webhook ids and tokens)
+const SECRET_PATH_PREFIXES: [(&str, bool); 2] = [
+  ("api.telegram.org/bot", false), // token is one path segment
+  ("/api/webhooks/", true) // id and token make up the rest of the path
+];
+
+static LOG_REQUESTS: AtomicBool = AtomicBool::new(false);
+
+/// Timeout Config
+/// Connect and total (connect through to full response) timeouts for api requests
+/// Connect timeout is not supported by the browser fetch api so only the total applies in wasm
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub struct TimeoutConfig {
+  pub connect_ms: u64,
+  pub total_ms: u64
+}
+
+impl Default for TimeoutConfig {
+  fn default() -> Self {
+    Self { connect_ms: 5_000, total_ms: 10_000 }
+  }
+}
+
+static TIMEOUT_CONFIG: RwLock<Option<TimeoutConfig>> = RwLock::new(None);
+
+/// Set Timeout Config
+/// Overrides the default timeouts for all api requests
+pub fn set_timeout_config(config: TimeoutConfig) {
+  if let Ok(mut guard) = TIMEOUT_CONFIG.write() {
+    *guard = Some(config);
+  }
+}
+
+/// Get Timeout Config
+pub fn get_timeout_config() -> TimeoutConfig {
+  TIMEOUT_CONFIG.read().ok().and_then(|guard| *guard).unwrap_or_default()
+}
+
+/// Retry Policy
+/// Retries of transient failures (listed statuses, timeouts and connection errors) with exponential backoff and jitter
+/// POST requests are only retried when enabled as they may not be idempotent (e.g. webhook orders)
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct RetryPolicy {
+  pub max_attempts: u32, // including the first, 1 disables retries
+  pub base_delay_ms: u64, // delay before the first retry, doubled for each retry after
+  pub max_delay_ms: u64,
+  pub jitter: f64, // fraction of the delay randomly removed (0.0 to 1.0)
+  pub retry_statuses: Vec<u16>,
+  pub retry_post: bool
+}
+
+impl Default for RetryPolicy {
+  fn default() -> Self {
+    Self { max_attempts: 3, base_delay_ms: 250, max_delay_ms: 5_000, jitter: 0.5, retry_statuses: vec![429, 500, 502, 503, 504], retry_post: false }
+  }
+}
+
+impl RetryPolicy {
+
+  /// Delay Ms
+  /// Backoff before the given retry (1 for the first) where sample is uniform in [0, 1)
+  pub fn delay_ms(&self, retry: u32, sample: f64) -> u64 {
+    let backoff: u64 = self.base_delay_ms.saturating_mul(1u64 << retry.saturating_sub(1).min(32)).min(self.max_delay_ms);
+    (backoff as f64 * (1.0 - self.jitter.clamp(0.0, 1.0) * sample)).round() as u64
+  }
+
+  pub fn is_retry_status(&self, status: reqwest::StatusCode) -> bool {
+    self.retry_statuses.contains(&status.as_u16())
+  }
+}
+
+static RETRY_POLICY: RwLock<Option<RetryPolicy>> = RwLock::new(None);
+static JITTER_STATE: AtomicU64 = AtomicU64::new(0);
+
+/// Set Retry Policy
+/// Overrides the default retry policy for all api requests
+pub fn set_retry_policy(policy: RetryPolicy) {
+  if let Ok(mut guard) = RETRY_POLICY.write() {
+    *guard = Some(policy);
+  }
+}
+
+/// Get Retry Policy
+pub fn get_retry_policy() -> RetryPolicy {
+  RETRY_POLICY.read().ok().and_then(|guard| guard.clone()).unwrap_or_default()
+}
+
+/// Jitter Sample
+/// Uniform in [0, 1), seeded from the clock so concurrent clients spread their retries
+fn jitter_sample() -> f64 {
+  let seed: u64 = JITTER_STATE.load(Ordering::Relaxed) ^ get_clock().now_ms() as u64;
+  let next: u64 = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+  JITTER_STATE.store(next, Ordering::Relaxed);
+  (next >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Set Request Logging
+/// Enables a debug log line (secrets redacted) for every outgoing request
+pub fn set_request_logging(is_enabled: bool) {
+  LOG_REQUESTS.store(is_enabled, Ordering::Relaxed);
+}
+
+/// Is Secret Param
+/// Whether a query parameter (name=value) carries a credential
+pub fn is_secret_param(param: &str) -> bool {
+  let name: String = param.split('=').next().unwrap_or_default().to_lowercase();
+  SECRET_PARAMS.contains(&name.as_str())
+}
+
+/// Redact Path
+/// Replaces credentials carried in a url path (the query string is left untouched)
+pub fn redact_path(url: &str) -> String {
+  let (base, query) = match url.split_once('?') {
+    Some((base, query)) => (base, Some(query)),
+    None => (url, None)
+  };
+  let mut redacted: String = base.to_string();
+  for (prefix, to_end) in SECRET_PATH_PREFIXES {
+    if let Some(idx) = base.find(prefix) {
+      let start: usize = idx + prefix.len();
+      let end: usize = if to_end { base.len() } else { base[start..].find('/').map(|i| start + i).unwrap_or(base.len()) };
+      redacted = format!("{}REDACTED{}", &base[..start], &base[end..]);
+      break;
+    }
+  }
+  match query {
+    Some(query) => format!("{}?{}", redacted, query),
+    None => redacted
+  }
+}
+
+/// Redact URL
+/// Replaces credential values in a url path or query string so it is safe to log or return in errors
+pub fn redact_url(url: &str) -> String {
+  let url: String = redact_path(url);
+  let Some((base, query)) = url.split_once('?') else { return url };
+  let params: Vec<String> = query.split('&')
+    .map(|p| if is_secret_param(p) { format!("{}=REDACTED", p.split('=').next().unwrap_or_default()) } else { p.to_string() })
+    .collect();
+  format!("{}?{}", base, params.join("&"))
+}
+
+/// Redact Reqwest Error
+/// Redacts the url carried by a reqwest error (its display includes the full url)
+pub fn redact_reqwest_error(mut e: reqwest::Error) -> reqwest::Error {
+  if let Some(url) = e.url_mut() {
+    if let Ok(redacted) = reqwest::Url::parse(&redact_url(url.as_str())) { *url = redacted; }
+  }
+  e
+}
+
+/// Log Request
+/// Writes the sanitized url to stderr when request logging is enabled
+fn log_request(url: &str, is_post: bool) {
+  if LOG_REQUESTS.load(Ordering::Relaxed) {
+    eprintln!("Request: {} {}", if is_post { "POST" } else { "GET" }, redact_url(url));
+  }
+}
 
 /// Sleep
-/// Basic sleep function
+/// Sleeps on the configured clock (simulated clocks return immediately)
 pub async fn sleep(millis: u64) {
-  // tokio::time::sleep(Duration::from_millis(millis)).await;
-  let sleep_count: u64 = millis * 1_000_000;
-  for _ in 0..sleep_count {
-    // Do nothing, just loop
-    // Wasm hack
-  }
+  get_clock().sleep(millis).await;
 }
 
 /// Match Pair Series
@@ -24,6 +193,11 @@ pub fn extract_match_series(asset_1: HistoricalPrices, asset_2: HistoricalPrices
   let mut series_2: Vec<f64> = vec![];
   let mut labels: Vec<u64> = vec![];
 
+  // Ensure label conventions are the same
+  if asset_1.label_convention != asset_2.label_convention {
+    return Err("Error: Failed to match series (label conventions differ)".to_string())
+  }
+
   // Ensure last label is the same
   let a1_last_label: &u64 = asset_1.labels.last().unwrap_or(&0);
   let a2_last_label: &u64 = asset_1.labels.last().unwrap_or(&0);
@@ -49,56 +223,424 @@ pub fn extract_match_series(asset_1: HistoricalPrices, asset_2: HistoricalPrices
   Ok((series_1, series_2, labels))
 }
 
+type JoinedSeries = (Vec<f64>, Vec<f64>, Vec<u64>);
+
+/// Join Pair Series
+/// Joins both legs on timestamp from the first label both legs share, applying the missing data policy
+/// to labels present in only one leg
+pub fn join_pair_series(asset_0: HistoricalPrices, asset_1: HistoricalPrices, policy: MissingDataPolicy)
+-> Result<JoinedSeries, SmartError>
+{
+
+  // Guard: Ensure label conventions are the same
+  if asset_0.label_convention != asset_1.label_convention {
+    return Err(SmartError::RuntimeCheck("Failed to join series (label conventions differ)".to_string()))
+  }
+
+  let start: u64 = match (asset_0.labels.first(), asset_1.labels.first()) {
+    (Some(a), Some(b)) => *a.max(b),
+    _ => return Err(SmartError::RuntimeCheck("Failed to join series (no prices)".to_string()))
+  };
+
+  let mut labels: Vec<u64> = asset_0.labels.iter().chain(asset_1.labels.iter()).copied().filter(|l| *l >= start).collect();
+  labels.sort_unstable();
+  labels.dedup();
+
+  let mut series_0: Vec<f64> = vec![];
+  let mut series_1: Vec<f64> = vec![];
+  let mut joined: Vec<u64> = vec![];
+  let (mut idx_0, mut idx_1) = (0, 0);
+  for label in labels {
+    while idx_0 < asset_0.labels.len() && asset_0.labels[idx_0] < label { idx_0 += 1; }
+    while idx_1 < asset_1.labels.len() && asset_1.labels[idx_1] < label { idx_1 += 1; }
+    let price_0: Option<f64> = asset_0.labels.get(idx_0).filter(|l| **l == label).map(|_| asset_0.prices[idx_0]);
+    let price_1: Option<f64> = asset_1.labels.get(idx_1).filter(|l| **l == label).map(|_| asset_1.prices[idx_1]);
+
+    let (price_0, price_1) = match (price_0, price_1, policy) {
+      (Some(p0), Some(p1), _) => (p0, p1),
+      (_, _, MissingDataPolicy::Strict) => {
+        let e: String = format!("Missing candle at label {} (asset_0 present: {}, asset_1 present: {})", label, price_0.is_some(), price_1.is_some());
+        return Err(SmartError::RuntimeCheck(e))
+      },
+      (_, _, MissingDataPolicy::DropBar) => continue,
+      (_, _, MissingDataPolicy::ForwardFill) => {
+        let prev_0: Option<f64> = idx_0.checked_sub(1).map(|i| asset_0.prices[i]);
+        let prev_1: Option<f64> = idx_1.checked_sub(1).map(|i| asset_1.prices[i]);
+        match (price_0.or(prev_0), price_1.or(prev_1)) {
+          (Some(p0), Some(p1)) => (p0, p1),
+          _ => continue
+        }
+      }
+    };
+    series_0.push(price_0);
+    series_1.push(price_1);
+    joined.push(label);
+  }
+
+  Ok((series_0, series_1, joined))
+}
+
 /// Send API Request
-/// Sends GET request to given url and returns response
+/// Sends GET request to given url and returns response using the configured timeouts
+pub async fn api_request(url: &str) -> Result<reqwest::Response, SmartError> {
+  api_request_with_timeout(url, None).await
+}
+
+/// Send API Request With Timeout
+/// Sends GET request with a per call timeout override (None uses the configured timeouts)
+pub async fn api_request_with_timeout(url: &str, timeout_opt: Option<TimeoutConfig>) -> Result<reqwest::Response, SmartError> {
+  send_request(url, None, timeout_opt).await
+}
+
+/// Send API Post
+/// Sends POST request with a JSON body using the configured timeouts (for POST only APIs such as Hyperliquid)
+pub async fn api_post(url: &str, body: &str) -> Result<reqwest::Response, SmartError> {
+  api_post_with_timeout(url, body, None).await
+}
+
+/// Send API Post With Timeout
+/// Sends POST request with a JSON body and a per call timeout override
+pub async fn api_post_with_timeout(url: &str, body: &str, timeout_opt: Option<TimeoutConfig>) -> Result<reqwest::Response, SmartError> {
+  send_request(url, Some(body), timeout_opt).await
+}
+
+/// Attempt
+/// Outcome of a single request, transient failures are retried under the retry policy
+enum Attempt {
+  Done(Result<reqwest::Response, SmartError>),
+  Transient(SmartError)
+}
+
+/// Send Request
+/// Sends the request, retrying transient failures with backoff (each attempt goes through breaker and pacing)
+async fn send_request(url: &str, body: Option<&str>, timeout_opt: Option<TimeoutConfig>) -> Result<reqwest::Response, SmartError> {
+  let policy: RetryPolicy = get_retry_policy();
+  let max_attempts: u32 = if body.is_some() && !policy.retry_post { 1 } else { policy.max_attempts.max(1) };
+  let mut attempt: u32 = 1;
+  loop {
+    match send_attempt(url, body, timeout_opt, &policy).await {
+      Attempt::Done(result) => return result,
+      Attempt::Transient(e) if attempt >= max_attempts => return Err(e),
+      Attempt::Transient(_) => {
+        sleep(policy.delay_ms(attempt, jitter_sample())).await;
+        attempt += 1;
+      }
+    }
+  }
+}
+
+/// Fixture Key
+/// Identifies a request for fixtures (POST bodies are appended as the url alone is shared by every request)
+#[cfg(not(target_arch = "wasm32"))]
+fn fixture_key(url: &str, body: Option<&str>) -> String {
+  match body {
+    Some(body) => format!("{}#{}", url, body),
+    None => url.to_string()
+  }
+}
+
+/// Split Header Params
+/// Removes header carried credentials from a url query string, returning them as header name and value pairs
+pub fn split_header_params(url: &str) -> (String, Vec<(String, String)>) {
+  let Some((base, query)) = url.split_once('?') else { return (url.to_string(), vec![]) };
+  let (headers, params): (Vec<&str>, Vec<&str>) = query.split('&')
+    .partition(|p| HEADER_PARAMS.contains(&p.split('=').next().unwrap_or_default().to_lowercase().as_str()));
+  let headers: Vec<(String, String)> = headers.into_iter()
+    .filter_map(|p| p.split_once('='))
+    .map(|(name, value)| (name.to_string(), value.to_string()))
+    .collect();
+  match params.is_empty() {
+    true => (base.to_string(), headers),
+    false => (format!("{}?{}", base, params.join("&")), headers)
+  }
+}
+
+/// Alpaca Auth Query
+/// Header carried credential params from an Alpaca key given as "KEY_ID:SECRET_KEY"
+pub fn alpaca_auth_query(api_key: &str) -> String {
+  let (key_id, secret_key) = api_key.split_once(':').unwrap_or((api_key, ""));
+  format!("APCA-API-KEY-ID={}&APCA-API-SECRET-KEY={}", key_id.trim(), secret_key.trim())
+}
+
+/// Build Request
+/// GET request, or POST with a JSON body when a body is given
+fn build_request(client: &reqwest::Client, url: &str, body: Option<&str>) -> reqwest::RequestBuilder {
+  let (url, headers) = split_header_params(url);
+  let request: reqwest::RequestBuilder = match body {
+    Some(body) => client.post(url)
+      .header(reqwest::header::CONTENT_TYPE, "application/json")
+      .body(body.to_string()),
+    None => client.get(url)
+  };
+  headers.into_iter().fold(request, |request, (name, value)| request.header(name, value))
+}
+
+/// Send Attempt
+/// Sends the request once with breaker, pacing, fixture and metrics handling
 /// NON WASM VERSION
 #[cfg(not(target_arch = "wasm32"))]
-pub async fn api_request(url: &str) -> Result<reqwest::Response, SmartError> {
-  let client: reqwest::Client = reqwest::Client::builder()
-    .timeout(Duration::from_secs(10))
-    .build()?;
+async fn send_attempt(url: &str, body: Option<&str>, timeout_opt: Option<TimeoutConfig>, policy: &RetryPolicy) -> Attempt {
+  use super::fixtures::{get_fixture_config, load_fixture, fixture_into_response, record_response, FixtureMode};
+  use crate::metrics::record_request;
+
+  // Replay recorded fixture if enabled
+  let fixture_config = get_fixture_config();
+  let key: String = fixture_key(url, body);
+  if let Some(config) = &fixture_config {
+    if config.mode == FixtureMode::Replay || config.mode == FixtureMode::Auto {
+      match load_fixture(config, &key) {
+        Err(e) => return Attempt::Done(Err(e)),
+        Ok(Some(fixture)) => return Attempt::Done(fixture_into_response(fixture)),
+        Ok(None) if config.mode == FixtureMode::Replay => {
+          let err: String = format!("No recorded fixture for: {}", redact_url(url));
+          return Attempt::Done(Err(SmartError::APIResponseStatus(err)))
+        },
+        Ok(None) => {}
+      }
+    }
+  }
+
+  // Guard: Fail fast if exchange circuit breaker is open
+  if let Err(e) = check_breaker(url) { return Attempt::Done(Err(e)) }
+
+  // Pause if exchange request budget is close to exhausted
+  let pause_ms: u64 = pause_for_url_ms(url);
+  if pause_ms > 0 { sleep(pause_ms).await; }
+  log_request(url, body.is_some());
+
+  let timeout: TimeoutConfig = timeout_opt.unwrap_or_else(get_timeout_config);
+  // Compressed responses are negotiated and decoded transparently (browsers do the same for wasm)
+  let client: Result<reqwest::Client, reqwest::Error> = reqwest::Client::builder()
+    .connect_timeout(Duration::from_millis(timeout.connect_ms))
+    .timeout(Duration::from_millis(timeout.total_ms))
+    .gzip(true)
+    .deflate(true)
+    .build();
+  let client: reqwest::Client = match client {
+    Ok(client) => client,
+    Err(e) => return Attempt::Done(Err(SmartError::Reqwest(e)))
+  };
 
   // Extract response
-  let res: reqwest::Response = client
-    .get(url)
+  let started_ms: i64 = get_clock().now_ms();
+  let res: reqwest::Response = match build_request(&client, url, body)
     .header(reqwest::header::USER_AGENT, "CryptoWizardsApp/1.0.0")
     .send()
-    .await?;
+    .await {
+      Ok(res) => res,
+      Err(e) => {
+        record_outcome(url, false);
+        record_request(url, get_clock().now_ms() - started_ms, false);
+        let is_transient: bool = e.is_timeout() || e.is_connect();
+        let e: SmartError = SmartError::Reqwest(redact_reqwest_error(e));
+        return if is_transient { Attempt::Transient(e) } else { Attempt::Done(Err(e)) }
+      }
+    };
+
+  // Track exchange request weight and health
+  record_response_headers(url, res.headers());
+  record_outcome(url, !is_breaker_failure(res.status()));
+  record_request(url, get_clock().now_ms() - started_ms, res.status() == 200);
   
   // Guard: Ensure 200 status
   if res.status() != 200 {
-    let err: String = format!("Failed to retrieve data for: {}", url);
+    let is_transient: bool = policy.is_retry_status(res.status());
+    let err: String = format!("Failed to retrieve data for: {}", redact_url(url));
     eprintln!("Error: {:?}", res.text().await);
-    return Err(SmartError::APIResponseStatus(err))
+    let e: SmartError = SmartError::APIResponseStatus(err);
+    return if is_transient { Attempt::Transient(e) } else { Attempt::Done(Err(e)) }
+  }
+
+  // Record fixture if enabled
+  if let Some(config) = &fixture_config {
+    return Attempt::Done(record_response(config, &key, res).await)
   }
   
-  Ok(res)
+  Attempt::Done(Ok(res))
 }
 
 
-/// Send API Request
-/// Sends GET request to given url and returns response
+/// Compression Stats
+/// Bytes transferred versus decoded for a compressed response
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub struct CompressionStats {
+  pub encoded_bytes: usize,
+  pub decoded_bytes: usize
+}
+
+impl CompressionStats {
+
+  /// Saving
+  /// Fraction of bandwidth saved by compression
+  pub fn saving(&self) -> f64 {
+    if self.decoded_bytes == 0 { return 0.0 }
+    1.0 - self.encoded_bytes as f64 / self.decoded_bytes as f64
+  }
+}
+
+/// Measure Compression
+/// Requests a url with gzip negotiated but decoding disabled to benchmark the bandwidth win
+/// NON WASM ONLY (browsers do not expose the encoded body)
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn measure_compression(url: &str) -> Result<CompressionStats, SmartError> {
+  use flate2::read::GzDecoder;
+  use std::io::Read;
+
+  let client: reqwest::Client = reqwest::Client::builder()
+    .timeout(Duration::from_millis(get_timeout_config().total_ms))
+    .gzip(false)
+    .build()?;
+  let res: reqwest::Response = client.get(url)
+    .header(reqwest::header::ACCEPT_ENCODING, "gzip")
+    .header(reqwest::header::USER_AGENT, "CryptoWizardsApp/1.0.0")
+    .send()
+    .await?;
+  let is_gzip: bool = res.headers().get(reqwest::header::CONTENT_ENCODING).is_some_and(|v| v == "gzip");
+  let body: Vec<u8> = res.bytes().await?.to_vec();
+
+  // Guard: Server may ignore the requested encoding
+  if !is_gzip {
+    return Ok(CompressionStats { encoded_bytes: body.len(), decoded_bytes: body.len() })
+  }
+
+  let mut decoded: Vec<u8> = vec![];
+  GzDecoder::new(body.as_slice()).read_to_end(&mut decoded)?;
+  Ok(CompressionStats { encoded_bytes: body.len(), decoded_bytes: decoded.len() })
+}
+
+/// Send Attempt
+/// Sends the request once with breaker and pacing handling
 /// WASM VERSION
 #[cfg(target_arch = "wasm32")]
-pub async fn api_request(url: &str) -> Result<reqwest::Response, SmartError> {
+async fn send_attempt(url: &str, body: Option<&str>, timeout_opt: Option<TimeoutConfig>, policy: &RetryPolicy) -> Attempt {
   use async_std::future::timeout;
 
+  // Guard: Fail fast if exchange circuit breaker is open
+  if let Err(e) = check_breaker(url) { return Attempt::Done(Err(e)) }
+
+  // Pause if exchange request budget is close to exhausted
+  let pause_ms: u64 = pause_for_url_ms(url);
+  if pause_ms > 0 { sleep(pause_ms).await; }
+  log_request(url, body.is_some());
+
   // WASM VERSION
-  let req_future = reqwest::Client::new()
-    .get(url)
-    .send();
+  let req_future = build_request(&reqwest::Client::new(), url, body).send();
 
-  let duration = Duration::from_secs(10);
+  let duration = Duration::from_millis(timeout_opt.unwrap_or_else(get_timeout_config).total_ms);
   let resonse_result = timeout(duration, req_future).await;
-  let Ok(res_async) = resonse_result else { return Err(SmartError::RuntimeCheck("Failed to get async response".to_string())) };
-  let Ok(res) = res_async else { return Err(SmartError::RuntimeCheck("Failed to get response".to_string())) };
+  let Ok(res_async) = resonse_result else {
+    record_outcome(url, false);
+    return Attempt::Transient(SmartError::RuntimeCheck("Failed to get async response".to_string()))
+  };
+  let Ok(res) = res_async else {
+    record_outcome(url, false);
+    return Attempt::Transient(SmartError::RuntimeCheck("Failed to get response".to_string()))
+  };
+
+  // Track exchange request weight and health
+  record_response_headers(url, res.headers());
+  record_outcome(url, !is_breaker_failure(res.status()));
   
   // Guard: Ensure 200 status
   if res.status() != 200 {
-    let err: String = format!("Failed to retrieve data for: {}", url);
+    let is_transient: bool = policy.is_retry_status(res.status());
+    let err: String = format!("Failed to retrieve data for: {}", redact_url(url));
     eprintln!("Error: {:?}", res.text().await);
-    return Err(SmartError::APIResponseStatus(err))
+    let e: SmartError = SmartError::APIResponseStatus(err);
+    return if is_transient { Attempt::Transient(e) } else { Attempt::Done(Err(e)) }
   }
   
-  Ok(res)
-}
\ No newline at end of file
+  Attempt::Done(Ok(res))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_applies_per_call_timeout() {
+    assert_eq!(get_timeout_config(), TimeoutConfig::default());
+
+    // Listener accepts the connection but never responds
+    let listener: std::net::TcpListener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url: String = format!("http://{}/slow", listener.local_addr().unwrap());
+    let timeout: TimeoutConfig = TimeoutConfig { connect_ms: 100, total_ms: 200 };
+
+    let started: std::time::Instant = std::time::Instant::now();
+    let res: Result<reqwest::Response, SmartError> = api_request_with_timeout(&url, Some(timeout)).await;
+    assert!(matches!(res, Err(SmartError::Reqwest(e)) if e.is_timeout()));
+    assert!(started.elapsed() < Duration::from_secs(5));
+  }
+
+  #[tokio::test]
+  async fn it_backs_off_exponentially_with_jitter() {
+    let policy: RetryPolicy = RetryPolicy { max_delay_ms: 1_500, ..Default::default() };
+    assert_eq!((policy.delay_ms(1, 0.0), policy.delay_ms(2, 0.0), policy.delay_ms(3, 0.0), policy.delay_ms(40, 0.0)), (250, 500, 1_000, 1_500));
+    assert_eq!(policy.delay_ms(2, 0.5), 375);
+    assert!(policy.is_retry_status(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+    assert!(!policy.is_retry_status(reqwest::StatusCode::NOT_FOUND));
+    assert!((0..100).map(|_| jitter_sample()).all(|x| (0.0..1.0).contains(&x)));
+  }
+
+  #[tokio::test]
+  async fn it_retries_transient_statuses() {
+    use std::io::{Read, Write};
+
+    // Server answers 503, 200, then 503 again
+    let listener: std::net::TcpListener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url: String = format!("http://{}/candles", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+      for (i, stream) in listener.incoming().take(3).enumerate() {
+        let mut stream: std::net::TcpStream = stream.unwrap();
+        let _ = stream.read(&mut [0u8; 4096]);
+        let status: &str = if i == 1 { "200 OK" } else { "503 Service Unavailable" };
+        let _ = stream.write_all(format!("HTTP/1.1 {}\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok", status).as_bytes());
+      }
+    });
+
+    let res: reqwest::Response = api_request(&url).await.unwrap();
+    assert_eq!(res.text().await.unwrap(), "ok");
+
+    // POST is not retried by default
+    assert!(matches!(api_post(&url, "{}").await, Err(SmartError::APIResponseStatus(_))));
+  }
+
+  #[tokio::test]
+  async fn it_reports_compression_saving() {
+    let stats: CompressionStats = CompressionStats { encoded_bytes: 250, decoded_bytes: 1_000 };
+    assert_eq!(stats.saving(), 0.75);
+    assert_eq!(CompressionStats { encoded_bytes: 0, decoded_bytes: 0 }.saving(), 0.0);
+  }
+
+  #[tokio::test]
+  async fn tests_measure_compression_binance_exchange_info() {
+    let stats: CompressionStats = measure_compression("https://fapi.binance.com/fapi/v1/exchangeInfo").await.unwrap();
+    assert!(stats.saving() > 0.5);
+  }
+
+  #[tokio::test]
+  async fn it_redacts_secrets_from_urls() {
+    let url: &str = "https://api.twelvedata.com/price?symbol=AAPL&apikey=abc123&Token=xyz";
+    let redacted: String = redact_url(url);
+    assert_eq!(redacted, "https://api.twelvedata.com/price?symbol=AAPL&apikey=REDACTED&Token=REDACTED");
+    assert_eq!(redact_url("https://api.binance.com/api/v3/ping"), "https://api.binance.com/api/v3/ping");
+
+    // Alpaca credentials travel as headers
+    let alpaca: &str = "https://data.alpaca.markets/v2/stocks/quotes/latest?symbols=AAPL&APCA-API-KEY-ID=id1&APCA-API-SECRET-KEY=sec2";
+    assert_eq!(redact_url(alpaca), "https://data.alpaca.markets/v2/stocks/quotes/latest?symbols=AAPL&APCA-API-KEY-ID=REDACTED&APCA-API-SECRET-KEY=REDACTED");
+    let (stripped, headers) = split_header_params(alpaca);
+    assert_eq!(stripped, "https://data.alpaca.markets/v2/stocks/quotes/latest?symbols=AAPL");
+    assert_eq!(headers, vec![("APCA-API-KEY-ID".to_string(), "id1".to_string()), ("APCA-API-SECRET-KEY".to_string(), "sec2".to_string())]);
+
+    // Notifier credentials travel in the path
+    assert_eq!(redact_url("https://api.telegram.org/bot123:ABC/sendMessage"), "https://api.telegram.org/botREDACTED/sendMessage");
+    assert_eq!(redact_url("https://discord.com/api/webhooks/42/tok-en?wait=true"), "https://discord.com/api/webhooks/REDACTED?wait=true");
+
+    // Reqwest errors display their url
+    let e: reqwest::Error = reqwest::get("http://127.0.0.1:9/price?apikey=abc123").await.unwrap_err();
+    assert!(e.to_string().contains("abc123"));
+    let redacted_e: SmartError = SmartError::from(e);
+    assert!(!redacted_e.to_string().contains("abc123"));
+    assert!(redacted_e.to_string().contains("apikey=REDACTED"));
+  }
+}
@@ -1,5 +1,8 @@
 use crate::SmartError;
 use super::models::Exchange;
+use super::jsonstream::stream_json_array;
+use super::schemas::{decode, BinanceTicker24h, OkxResponse, OkxTicker};
+use super::translate::base_if_preferred_quote;
 use super::utils::api_request;
 
 use std::collections::{HashMap, HashSet};
@@ -9,13 +12,17 @@ use std::collections::{HashMap, HashSet};
 fn get_tickers_url(exchange: &Exchange) -> Option<String> {
 
   let binance_tickers: &str = "https://fapi.binance.com/fapi/v1/ticker/24hr";
+  let binance_spot_tickers: &str = "https://api.binance.com/api/v3/ticker/24hr";
   let binance_us_tickers: &str = "https://api.binance.us/api/v3/ticker/24hr";
   let bybit_tickers: &str = "https://api.bybit.com/v5/market/tickers?category=linear";
+  let okx_tickers: &str = "https://www.okx.com/api/v5/market/tickers?instType=SWAP";
 
   let url: &str = match exchange {
     Exchange::Binance => binance_tickers,
+    Exchange::BinanceSpot => binance_spot_tickers,
     Exchange::BinanceUs => binance_us_tickers,
     Exchange::ByBit => bybit_tickers,
+    Exchange::Okx => okx_tickers,
     _ => return None
   };
 
@@ -24,17 +31,13 @@ fn get_tickers_url(exchange: &Exchange) -> Option<String> {
 
 /// Extract High Volume Tickers Binance
 /// Ranks tickers in order of volume traded
-fn extract_high_volume_tickers_binance(json_text: String) -> Result<HashMap<i32, String>, SmartError> {
-  let ticker_array: Vec<serde_json::Value> = serde_json::from_str(&json_text)?;
+/// The full 24h ticker list is large so tickers are decoded as the body streams in
+async fn extract_high_volume_tickers_binance(res_data: reqwest::Response, exchange: &Exchange) -> Result<HashMap<i32, String>, SmartError> {
+  let (tickers, _) = stream_json_array::<BinanceTicker24h>(res_data, exchange, &[]).await?;
   let mut volume_map: HashMap<i32, String> = HashMap::new();
-  for item in ticker_array {
-    if let (Some(symbol), Some(quote_volume)) = (
-      item.get("symbol").and_then(|s| s.as_str()),
-      item.get("quoteVolume").and_then(|v| v.as_str()),
-    ) {
-      let total_vol: i32 = quote_volume.parse::<f32>().unwrap_or(0.0) as i32;
-      volume_map.insert(total_vol, symbol.to_string());
-    }
+  for ticker in tickers {
+    let total_vol: i32 = ticker.quote_volume.0 as i32;
+    volume_map.insert(total_vol, ticker.symbol);
   }
   Ok(volume_map)
 }
@@ -62,6 +65,21 @@ fn extract_high_volume_tickers_bybit(json_text: String) -> Result<HashMap<i32, S
   Ok(volume_map)
 }
 
+/// Extract High Volume Tickers Okx
+/// Ranks tickers in order of volume traded (swap volume is in base currency so is valued at the last price)
+fn extract_high_volume_tickers_okx(json_text: String) -> Result<HashMap<i32, String>, SmartError> {
+  let data_obj: serde_json::Value = serde_json::from_str(&json_text)?;
+  let tickers: Vec<OkxTicker> = decode::<OkxResponse>(&Exchange::Okx, data_obj)?.into_list()?;
+  let mut volume_map: HashMap<i32, String> = HashMap::new();
+  for ticker in tickers {
+    if let Some(volume_24h) = ticker.vol_ccy24h {
+      let total_vol: f64 = (volume_24h.0 * ticker.last.0) / 1000.0;
+      volume_map.insert(total_vol as i32, ticker.inst_id);
+    }
+  }
+  Ok(volume_map)
+}
+
 /// Request High Volume Tickers
 /// Requests list of available tickers for a given exchange
 pub async fn request_high_volume_tickers(exchange: &Exchange) -> Result<Vec<String>, SmartError> {
@@ -88,29 +106,24 @@ pub async fn request_high_volume_tickers(exchange: &Exchange) -> Result<Vec<Stri
     return Err(SmartError::APIResponseStatus(e));
   }
 
-  // Send JSON
-  let json_text: String = res_data.text().await?;
-
+  // Extract volumes
   let tickers_hm: HashMap<i32, String> = match exchange {
-    Exchange::Binance => extract_high_volume_tickers_binance(json_text)?,
-    Exchange::BinanceUs => extract_high_volume_tickers_binance(json_text)?,
-    Exchange::ByBit => extract_high_volume_tickers_bybit(json_text)?,
-    _ => panic!("should only include Binance, BinanceUs and ByBit")
+    Exchange::Binance | Exchange::BinanceSpot | Exchange::BinanceUs => extract_high_volume_tickers_binance(res_data, exchange).await?,
+    Exchange::ByBit => extract_high_volume_tickers_bybit(res_data.text().await?)?,
+    Exchange::Okx => extract_high_volume_tickers_okx(res_data.text().await?)?,
+    _ => panic!("should only include Binance, BinanceSpot, BinanceUs, ByBit and Okx")
   };
 
-  let mut sorted: Vec<_> = tickers_hm.iter().collect();
+  // Keep only tickers quoted in the exchange's preferred quote currency (see translate)
+  let mut sorted: Vec<(i32, String)> = tickers_hm.into_iter()
+    .filter_map(|(volume, symbol)| base_if_preferred_quote(&symbol, exchange).map(|base| (volume, base)))
+    .collect();
   sorted.sort_by_key(|a| a.0);
   sorted.reverse();
   
   // Condense into standalone symbol only for top 25% volume traded
-  let mut symbols: Vec<String> = vec![];
   let breakpoint: usize = (sorted.len() as f32 * 0.25) as usize;
-  for (i, symbol) in sorted.iter().enumerate() {
-    if i >= breakpoint { break; }
-    let standalone = symbol.1.replace("USDT", "");
-    let standalone = standalone.replace("USDC", "");
-    symbols.push(standalone);
-  }
+  let symbols: Vec<String> = sorted.into_iter().take(breakpoint).map(|(_, base)| base).collect();
 
   Ok(symbols)
 }
@@ -122,11 +135,13 @@ pub async fn request_high_volume_tickers_all() -> Result<Vec<String>, SmartError
   let binance_res = request_high_volume_tickers(&Exchange::Binance).await;
   let binance_us_res = request_high_volume_tickers(&Exchange::BinanceUs).await;
   let bybit_res = request_high_volume_tickers(&Exchange::ByBit).await;
+  let okx_res = request_high_volume_tickers(&Exchange::Okx).await;
   let twelve_res = request_high_volume_tickers(&Exchange::Twelve).await;
   
   if let Ok(binance) = binance_res { all_tickers.extend(binance); }
   if let Ok(binance_us) = binance_us_res { all_tickers.extend(binance_us); }
   if let Ok(bybit) = bybit_res { all_tickers.extend(bybit); }
+  if let Ok(okx) = okx_res { all_tickers.extend(okx); }
   if let Ok(twelve) = twelve_res { all_tickers.extend(twelve); }
 
   let unique_values: HashSet<String> = all_tickers.into_iter().collect();
@@ -139,6 +154,18 @@ pub async fn request_high_volume_tickers_all() -> Result<Vec<String>, SmartError
 mod tests {
   use super::*;
 
+  #[tokio::test]
+  async fn it_ranks_okx_swaps_by_quote_volume() {
+    let json_text: String = serde_json::json!({ "code": "0", "msg": "", "data": [
+      { "instId": "BTC-USDT-SWAP", "last": "50000", "volCcy24h": "100" },
+      { "instId": "ETH-USDT-SWAP", "last": "2000", "volCcy24h": "1000" }
+    ]}).to_string();
+    let volume_map: HashMap<i32, String> = extract_high_volume_tickers_okx(json_text).unwrap();
+    assert_eq!(volume_map.get(&5000).map(|s| s.as_str()), Some("BTC-USDT-SWAP"));
+    assert_eq!(volume_map.get(&2000).map(|s| s.as_str()), Some("ETH-USDT-SWAP"));
+    assert_eq!(base_if_preferred_quote("BTC-USDT-SWAP", &Exchange::Okx).as_deref(), Some("BTC"));
+  }
+
   #[tokio::test]
   async fn it_combines_all_known_high_volume_symbols() {
     let tickers: Vec<String> = request_high_volume_tickers_all().await.unwrap();
@@ -0,0 +1,300 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_native_tls::{native_tls, TlsConnector, TlsStream};
+
+use crate::SmartError;
+
+/*
+  WebSocket Client
+  Minimal RFC 6455 client over TLS for exchange market data feeds: text messages, ping / pong and close
+  The feeds only exchange small JSON text messages, so the handshake and framing are handled here rather than through a websocket crate
+*/
+
+/// Appended to the client key to derive the accept key (RFC 6455 section 1.3)
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Largest message accepted from a feed
+const MAX_MESSAGE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Largest handshake response accepted
+const MAX_HANDSHAKE_BYTES: usize = 16 * 1024;
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WsMessage {
+  Text(String),
+  Close
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WsFrame {
+  pub fin: bool,
+  pub opcode: u8,
+  pub payload: Vec<u8>
+}
+
+fn ws_error(e: impl std::fmt::Display) -> SmartError {
+  SmartError::APIResponseStatus(format!("WebSocket error: {}", e))
+}
+
+/// Parse Wss Url
+/// Host, port (443 unless given) and request path of a wss:// url
+pub fn parse_wss_url(url: &str) -> Result<(String, u16, String), SmartError> {
+  let Some(rest) = url.strip_prefix("wss://") else {
+    return Err(SmartError::RuntimeCheck(format!("Only wss:// urls are supported: {}", url)))
+  };
+  let (authority, path) = match rest.find(['/', '?']) {
+    Some(idx) if rest[idx..].starts_with('/') => (&rest[..idx], rest[idx..].to_string()),
+    Some(idx) => (&rest[..idx], format!("/{}", &rest[idx..])),
+    None => (rest, "/".to_string())
+  };
+  let (host, port) = match authority.split_once(':') {
+    Some((host, port)) => (host, port.parse::<u16>().map_err(|_| SmartError::RuntimeCheck(format!("Invalid port in url: {}", url)))?),
+    None => (authority, 443)
+  };
+
+  // Guard: Ensure a host
+  if host.is_empty() {
+    return Err(SmartError::RuntimeCheck(format!("Missing host in url: {}", url)));
+  }
+  Ok((host.to_string(), port, path))
+}
+
+/// Sha1
+/// SHA-1 digest, only used to check the handshake accept key
+fn sha1(data: &[u8]) -> [u8; 20] {
+  let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+  let mut message: Vec<u8> = data.to_vec();
+  message.push(0x80);
+  while message.len() % 64 != 56 { message.push(0); }
+  message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+  for chunk in message.chunks(64) {
+    let mut w: [u32; 80] = [0; 80];
+    for (i, word) in chunk.chunks(4).enumerate() {
+      w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+    }
+    for i in 16..80 {
+      w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e] = h;
+    for (i, word) in w.iter().enumerate() {
+      let (f, k) = match i {
+        0..=19 => ((b & c) | (!b & d), 0x5A827999),
+        20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+        40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+        _ => (b ^ c ^ d, 0xCA62C1D6)
+      };
+      let temp: u32 = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(*word);
+      (e, d, c, b, a) = (d, c, b.rotate_left(30), a, temp);
+    }
+    for (state, value) in h.iter_mut().zip([a, b, c, d, e]) {
+      *state = state.wrapping_add(value);
+    }
+  }
+
+  let mut digest: [u8; 20] = [0; 20];
+  for (i, state) in h.iter().enumerate() {
+    digest[i * 4..i * 4 + 4].copy_from_slice(&state.to_be_bytes());
+  }
+  digest
+}
+
+/// Accept Key
+/// Sec-WebSocket-Accept value the server must return for a client key
+pub fn accept_key(client_key: &str) -> String {
+  BASE64.encode(sha1(format!("{}{}", client_key, WEBSOCKET_GUID).as_bytes()))
+}
+
+/// Encode Frame
+/// Client frame (always masked) with the fin bit set
+pub fn encode_frame(opcode: u8, payload: &[u8], mask: [u8; 4]) -> Vec<u8> {
+  let mut frame: Vec<u8> = vec![0x80 | opcode];
+  match payload.len() {
+    len if len < 126 => frame.push(0x80 | len as u8),
+    len if len <= u16::MAX as usize => {
+      frame.push(0x80 | 126);
+      frame.extend_from_slice(&(len as u16).to_be_bytes());
+    },
+    len => {
+      frame.push(0x80 | 127);
+      frame.extend_from_slice(&(len as u64).to_be_bytes());
+    }
+  }
+  frame.extend_from_slice(&mask);
+  frame.extend(payload.iter().enumerate().map(|(i, byte)| byte ^ mask[i % 4]));
+  frame
+}
+
+/// Read Frame
+/// Reads one frame, unmasking the payload if the sender masked it
+pub async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> Result<WsFrame, SmartError> {
+  let mut head: [u8; 2] = [0; 2];
+  reader.read_exact(&mut head).await?;
+  let fin: bool = head[0] & 0x80 != 0;
+  let opcode: u8 = head[0] & 0x0F;
+  let is_masked: bool = head[1] & 0x80 != 0;
+  let len: u64 = match head[1] & 0x7F {
+    126 => reader.read_u16().await? as u64,
+    127 => reader.read_u64().await?,
+    len => len as u64
+  };
+
+  // Guard: Ensure the frame is a sane size
+  if len > MAX_MESSAGE_BYTES as u64 {
+    return Err(ws_error(format!("frame of {} bytes exceeds the limit", len)));
+  }
+
+  let mut mask: [u8; 4] = [0; 4];
+  if is_masked { reader.read_exact(&mut mask).await?; }
+  let mut payload: Vec<u8> = vec![0; len as usize];
+  reader.read_exact(&mut payload).await?;
+  if is_masked {
+    payload.iter_mut().enumerate().for_each(|(i, byte)| *byte ^= mask[i % 4]);
+  }
+  Ok(WsFrame { fin, opcode, payload })
+}
+
+/// WebSocket Connection
+/// Open TLS websocket to a feed
+pub struct WsConnection {
+  stream: TlsStream<TcpStream>
+}
+
+impl WsConnection {
+
+  /// Connect
+  /// Opens the TLS connection and performs the upgrade handshake
+  pub async fn connect(url: &str) -> Result<Self, SmartError> {
+    let (host, port, path) = parse_wss_url(url)?;
+    let tcp: TcpStream = TcpStream::connect((host.as_str(), port)).await?;
+    let connector: TlsConnector = TlsConnector::from(native_tls::TlsConnector::new().map_err(ws_error)?);
+    let mut stream: TlsStream<TcpStream> = connector.connect(&host, tcp).await.map_err(ws_error)?;
+
+    let nonce: [u8; 16] = std::array::from_fn(|_| fastrand::u8(..));
+    let client_key: String = BASE64.encode(nonce);
+    let request: String = format!(
+      "GET {} HTTP/1.1\r\nHost: {}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: {}\r\nSec-WebSocket-Version: 13\r\n\r\n",
+      path, host, client_key
+    );
+    stream.write_all(request.as_bytes()).await?;
+
+    // Read the response head byte by byte so no frame bytes are consumed
+    let mut response: Vec<u8> = vec![];
+    while !response.ends_with(b"\r\n\r\n") {
+      if response.len() >= MAX_HANDSHAKE_BYTES {
+        return Err(ws_error("handshake response too large"));
+      }
+      response.push(stream.read_u8().await?);
+    }
+    let response: String = String::from_utf8_lossy(&response).to_string();
+
+    // Guard: Ensure the server switched protocols with the expected key
+    let status_line: &str = response.lines().next().unwrap_or_default();
+    if status_line.split_whitespace().nth(1) != Some("101") {
+      return Err(ws_error(format!("upgrade rejected by {}: {}", host, status_line)));
+    }
+    let expected: String = accept_key(&client_key);
+    let is_accepted: bool = response.lines()
+      .filter_map(|line| line.split_once(':'))
+      .any(|(name, value)| name.trim().eq_ignore_ascii_case("sec-websocket-accept") && value.trim() == expected);
+    if !is_accepted {
+      return Err(ws_error(format!("invalid accept key from {}", host)));
+    }
+
+    Ok(Self { stream })
+  }
+
+  async fn send_frame(&mut self, opcode: u8, payload: &[u8]) -> Result<(), SmartError> {
+    let mask: [u8; 4] = fastrand::u32(..).to_be_bytes();
+    self.stream.write_all(&encode_frame(opcode, payload, mask)).await?;
+    Ok(())
+  }
+
+  /// Send Text
+  pub async fn send_text(&mut self, text: &str) -> Result<(), SmartError> {
+    self.send_frame(OPCODE_TEXT, text.as_bytes()).await
+  }
+
+  /// Read Message
+  /// Next text message, answering pings and joining fragmented messages (binary messages are skipped)
+  pub async fn read_message(&mut self) -> Result<WsMessage, SmartError> {
+    let mut message: Vec<u8> = vec![];
+    let mut message_opcode: Option<u8> = None;
+    loop {
+      let frame: WsFrame = read_frame(&mut self.stream).await?;
+      match frame.opcode {
+        OPCODE_PING => self.send_frame(OPCODE_PONG, &frame.payload).await?,
+        OPCODE_PONG => {},
+        OPCODE_CLOSE => {
+          let _ = self.send_frame(OPCODE_CLOSE, &frame.payload).await;
+          return Ok(WsMessage::Close);
+        },
+        OPCODE_TEXT | OPCODE_BINARY | OPCODE_CONTINUATION => {
+          if frame.opcode != OPCODE_CONTINUATION { message_opcode = Some(frame.opcode); }
+          message.extend_from_slice(&frame.payload);
+
+          // Guard: Ensure joined fragments stay within the limit
+          if message.len() > MAX_MESSAGE_BYTES {
+            return Err(ws_error("message exceeds the limit"));
+          }
+          if frame.fin {
+            if message_opcode == Some(OPCODE_TEXT) {
+              return String::from_utf8(message).map(WsMessage::Text).map_err(ws_error);
+            }
+            message = vec![];
+            message_opcode = None;
+          }
+        },
+        opcode => return Err(ws_error(format!("unknown opcode {}", opcode)))
+      }
+    }
+  }
+
+  /// Close
+  /// Sends a close frame (errors ignored as the connection is being dropped)
+  pub async fn close(&mut self) {
+    let _ = self.send_frame(OPCODE_CLOSE, &[]).await;
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_derives_handshake_accept_key() {
+    // Example from RFC 6455 section 1.3
+    assert_eq!(accept_key("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+    assert_eq!(BASE64.encode(sha1(b"")), "2jmj7l5rSw0yVb/vlWAYkK/YBwk=");
+  }
+
+  #[tokio::test]
+  async fn it_parses_wss_urls() {
+    assert_eq!(parse_wss_url("wss://stream.binance.com:9443/stream?streams=a").unwrap(), ("stream.binance.com".to_string(), 9443, "/stream?streams=a".to_string()));
+    assert_eq!(parse_wss_url("wss://ws-feed.exchange.coinbase.com").unwrap(), ("ws-feed.exchange.coinbase.com".to_string(), 443, "/".to_string()));
+    assert!(parse_wss_url("https://api.binance.com").is_err());
+  }
+
+  #[tokio::test]
+  async fn it_round_trips_masked_frames() {
+    let text: String = "x".repeat(300);
+    let bytes: Vec<u8> = encode_frame(OPCODE_TEXT, text.as_bytes(), [1, 2, 3, 4]);
+    assert_eq!((bytes[1], &bytes[2..4]), (0x80 | 126, &300u16.to_be_bytes()[..]));
+    let frame: WsFrame = read_frame(&mut bytes.as_slice()).await.unwrap();
+    assert_eq!(frame, WsFrame { fin: true, opcode: OPCODE_TEXT, payload: text.into_bytes() });
+
+    // Server frames are unmasked
+    let frame: WsFrame = read_frame(&mut [0x89u8, 0x02, b'h', b'i'].as_slice()).await.unwrap();
+    assert_eq!((frame.opcode, frame.payload), (OPCODE_PING, b"hi".to_vec()));
+  }
+}
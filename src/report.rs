@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use crate::alerts::Notifier;
+use crate::backtest::models::LongSeries;
+use crate::state::SignalAction;
+use crate::watchlist::Degradation;
+
+/*
+  Daily Report
+  End of day summary of monitor and paper activity (signals fired, trades, realized pnl and degraded pairs)
+  Activity is recorded as it happens, each UTC day is closed into a report once activity or a poll passes midnight
+*/
+
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct ReportTrade {
+  pub pair_id: String,
+  pub action: SignalAction,
+  pub pnl: Option<f64>, // realized on exits
+  pub time: i64 // unix seconds
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default, TS)]
+#[ts(export)]
+pub struct DailyReport {
+  pub day_start: i64, // unix seconds of UTC midnight
+  pub signals: usize,
+  pub trades: Vec<ReportTrade>,
+  pub realized_pnl: f64,
+  pub wins: usize,
+  pub losses: usize,
+  pub degraded: Vec<String>, // pair ids that fell below their min score
+  pub open_pairs: Vec<String> // still held at the end of the day
+}
+
+impl DailyReport {
+
+  /// Entries / Exits
+  pub fn entries(&self) -> usize {
+    self.trades.iter().filter(|t| matches!(t.action, SignalAction::Open(_))).count()
+  }
+
+  pub fn exits(&self) -> usize {
+    self.trades.iter().filter(|t| t.action == SignalAction::Close).count()
+  }
+
+  /// Render
+  /// Plain text summary for notifiers
+  pub fn render(&self) -> String {
+    let date: String = chrono::DateTime::from_timestamp(self.day_start, 0)
+      .map(|d| d.format("%Y-%m-%d").to_string())
+      .unwrap_or_else(|| self.day_start.to_string());
+    let list = |pairs: &[String]| if pairs.is_empty() { "none".to_string() } else { pairs.join(", ") };
+    format!(
+      "Daily report {}\nSignals: {}\nTrades: {} entries / {} exits ({} won, {} lost)\nRealized pnl: {:.2}\nDegraded: {}\nOpen: {}",
+      date, self.signals, self.entries(), self.exits(), self.wins, self.losses, self.realized_pnl, list(&self.degraded), list(&self.open_pairs)
+    )
+  }
+}
+
+/// Daily Reporter
+/// Accumulates the current day and queues finished reports (serializable so it can be saved with the engine state)
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct DailyReporter {
+  current: Option<DailyReport>,
+  open_pairs: Vec<String>,
+  completed: Vec<DailyReport>
+}
+
+impl DailyReporter {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Day
+  /// The report for the day of a time, closing the previous day first if it has ended
+  fn day(&mut self, time: i64) -> &mut DailyReport {
+    self.roll(time);
+    let open_pairs: Vec<String> = self.open_pairs.clone();
+    self.current.get_or_insert_with(|| DailyReport { day_start: time.div_euclid(DAY_SECS) * DAY_SECS, open_pairs, ..Default::default() })
+  }
+
+  /// Roll
+  /// Moves the current report to the completed queue once its day has ended
+  fn roll(&mut self, time: i64) {
+    if self.current.as_ref().is_some_and(|r| time >= r.day_start + DAY_SECS) {
+      if let Some(report) = self.current.take() { self.completed.push(report); }
+    }
+  }
+
+  pub fn record_signal(&mut self, time: i64) {
+    self.day(time).signals += 1;
+  }
+
+  pub fn record_entry(&mut self, pair_id: &str, long_series: &LongSeries, time: i64) {
+    if !self.open_pairs.iter().any(|p| p == pair_id) { self.open_pairs.push(pair_id.to_string()); }
+    let open_pairs: Vec<String> = self.open_pairs.clone();
+    let report: &mut DailyReport = self.day(time);
+    report.trades.push(ReportTrade { pair_id: pair_id.to_string(), action: SignalAction::Open(long_series.clone()), pnl: None, time });
+    report.open_pairs = open_pairs;
+  }
+
+  pub fn record_exit(&mut self, pair_id: &str, realized_pnl: f64, time: i64) {
+    self.open_pairs.retain(|p| p != pair_id);
+    let open_pairs: Vec<String> = self.open_pairs.clone();
+    let report: &mut DailyReport = self.day(time);
+    report.trades.push(ReportTrade { pair_id: pair_id.to_string(), action: SignalAction::Close, pnl: Some(realized_pnl), time });
+    report.realized_pnl += realized_pnl;
+    if realized_pnl > 0.0 { report.wins += 1 } else { report.losses += 1 }
+    report.open_pairs = open_pairs;
+  }
+
+  pub fn record_degradation(&mut self, degradation: &Degradation, time: i64) {
+    let pair_id: String = format!("{}/{}", degradation.data_criteria.asset_0, degradation.data_criteria.asset_1);
+    let report: &mut DailyReport = self.day(time);
+    if !report.degraded.contains(&pair_id) { report.degraded.push(pair_id); }
+  }
+
+  /// Take Due
+  /// Finished reports up to now (call on a schedule, e.g. each loop iteration), a quiet day produces no report
+  pub fn take_due(&mut self, now: i64) -> Vec<DailyReport> {
+    self.roll(now);
+    std::mem::take(&mut self.completed)
+  }
+}
+
+/// Send Report
+/// Sends a report through every notifier, returning the errors of any that failed
+pub async fn send_report(notifiers: &[Notifier], report: &DailyReport) -> Vec<SmartError> {
+  let text: String = report.render();
+  let results: Vec<Result<(), SmartError>> = futures::future::join_all(notifiers.iter().map(|n| n.send_text(&text))).await;
+  results.into_iter().filter_map(|r| r.err()).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_aggregates_daily_reports() {
+    let day: i64 = 1_700_006_400; // UTC midnight
+    let mut reporter: DailyReporter = DailyReporter::new();
+    reporter.record_signal(day + 60);
+    reporter.record_entry("BTC/ETH", &LongSeries::Series0, day + 120);
+    reporter.record_entry("SOL/AVAX", &LongSeries::Series0, day + 180);
+    reporter.record_signal(day + 3_600);
+    reporter.record_exit("BTC/ETH", 42.5, day + 3_660);
+    reporter.record_exit("SOL/AVAX", -10.0, day + 3_720);
+    reporter.record_entry("SOL/AVAX", &LongSeries::Series0, day + 7_200);
+    assert!(reporter.take_due(day + DAY_SECS - 1).is_empty());
+
+    let reports: Vec<DailyReport> = reporter.take_due(day + DAY_SECS);
+    assert_eq!(reports.len(), 1);
+    let report: &DailyReport = &reports[0];
+    assert_eq!((report.day_start, report.signals, report.entries(), report.exits()), (day, 2, 3, 2));
+    assert_eq!((report.realized_pnl, report.wins, report.losses), (32.5, 1, 1));
+    assert_eq!(report.open_pairs, vec!["SOL/AVAX".to_string()]);
+    assert_eq!(
+      report.render(),
+      "Daily report 2023-11-15\nSignals: 2\nTrades: 3 entries / 2 exits (1 won, 1 lost)\nRealized pnl: 32.50\nDegraded: none\nOpen: SOL/AVAX"
+    );
+
+    // The next day starts with the pairs still held
+    reporter.record_signal(day + DAY_SECS + 60);
+    let next: Vec<DailyReport> = reporter.take_due(day + 2 * DAY_SECS);
+    assert_eq!(next[0].open_pairs, vec!["SOL/AVAX".to_string()]);
+    assert!(reporter.take_due(day + 3 * DAY_SECS).is_empty());
+  }
+}
@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+
+/*
+  Risk Guard
+  Account wide limits enforced by the execution layer across every pair traded
+  Entries beyond the open pair or notional limits are rejected, a daily loss breach (or a manual kill) halts new entries
+  until reset and can ask for every open pair to be flattened, exits are never blocked
+*/
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default, TS)]
+#[ts(export)]
+pub struct RiskLimits {
+  pub max_daily_loss: Option<f64>, // quote amount, realized plus unrealized since the UTC day began
+  pub max_open_pairs: Option<usize>,
+  pub max_notional: Option<f64>, // gross notional across all open pairs
+  pub flatten_on_breach: bool
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, TS)]
+#[ts(export)]
+pub enum RiskHalt {
+  DailyLoss,
+  Manual // kill switch
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub enum RiskAction {
+  Continue,
+  HaltEntries,
+  Flatten(Vec<String>) // pair ids to close
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct OpenPair {
+  pub pair_id: String,
+  pub notional: f64
+}
+
+/// Risk Guard
+/// Tracks open pairs and daily pnl against the limits (serializable so it survives restarts)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RiskGuard {
+  pub limits: RiskLimits,
+  day: u64,
+  realized_today: f64,
+  unrealized: f64,
+  open_pairs: Vec<OpenPair>,
+  halted: Option<RiskHalt>
+}
+
+impl RiskGuard {
+  pub fn new(limits: RiskLimits) -> Self {
+    Self { limits, day: 0, realized_today: 0.0, unrealized: 0.0, open_pairs: vec![], halted: None }
+  }
+
+  pub fn halted(&self) -> Option<RiskHalt> {
+    self.halted
+  }
+
+  pub fn open_pairs(&self) -> &[OpenPair] {
+    &self.open_pairs
+  }
+
+  /// Open Notional
+  /// Gross notional across open pairs
+  pub fn open_notional(&self) -> f64 {
+    self.open_pairs.iter().map(|p| p.notional).sum()
+  }
+
+  /// Daily Pnl
+  /// Realized pnl since the UTC day began plus the latest unrealized pnl
+  pub fn daily_pnl(&self) -> f64 {
+    self.realized_today + self.unrealized
+  }
+
+  /// Check Entry
+  /// Errors when a new pair of the given notional would breach a limit or entries are halted
+  pub fn check_entry(&self, pair_id: &str, notional: f64) -> Result<(), SmartError> {
+
+    // Guard: Ensure entries are not halted
+    if let Some(halt) = self.halted {
+      return Err(SmartError::RuntimeCheck(format!("Entries halted ({:?}), rejected entry for {}", halt, pair_id)));
+    }
+
+    // Guard: Ensure the pair is not already open
+    if self.open_pairs.iter().any(|p| p.pair_id == pair_id) {
+      return Err(SmartError::RuntimeCheck(format!("Pair {} is already open", pair_id)));
+    }
+
+    if let Some(max_open_pairs) = self.limits.max_open_pairs {
+      if self.open_pairs.len() + 1 > max_open_pairs {
+        return Err(SmartError::RuntimeCheck(format!("Max open pairs ({}) reached, rejected entry for {}", max_open_pairs, pair_id)));
+      }
+    }
+    if let Some(max_notional) = self.limits.max_notional {
+      let total: f64 = self.open_notional() + notional.abs();
+      if total > max_notional {
+        return Err(SmartError::RuntimeCheck(format!("Notional {:.2} would exceed max notional {:.2}, rejected entry for {}", total, max_notional, pair_id)));
+      }
+    }
+    Ok(())
+  }
+
+  /// Open Pair
+  /// Checks limits then records the pair as open
+  pub fn open_pair(&mut self, pair_id: &str, notional: f64) -> Result<(), SmartError> {
+    self.check_entry(pair_id, notional)?;
+    self.open_pairs.push(OpenPair { pair_id: pair_id.to_string(), notional: notional.abs() });
+    Ok(())
+  }
+
+  /// Close Pair
+  /// Removes a pair and books its realized pnl
+  pub fn close_pair(&mut self, pair_id: &str, realized_pnl: f64, time_ms: u64) -> RiskAction {
+    self.open_pairs.retain(|p| p.pair_id != pair_id);
+    self.roll_day(time_ms);
+    self.realized_today += realized_pnl;
+    self.evaluate()
+  }
+
+  /// Mark To Market
+  /// Updates unrealized pnl across open pairs and checks the daily loss limit
+  pub fn mark_to_market(&mut self, unrealized_pnl: f64, time_ms: u64) -> RiskAction {
+    self.roll_day(time_ms);
+    self.unrealized = unrealized_pnl;
+    self.evaluate()
+  }
+
+  /// Halt
+  /// Kill switch, stops new entries until reset
+  pub fn halt(&mut self) -> RiskAction {
+    self.halted = Some(RiskHalt::Manual);
+    self.breach_action()
+  }
+
+  /// Reset
+  /// Clears a halt so entries can resume (open pairs and pnl are kept)
+  pub fn reset(&mut self) {
+    self.halted = None;
+  }
+
+  /// Roll Day
+  /// Realized pnl starts again from zero each UTC day
+  fn roll_day(&mut self, time_ms: u64) {
+    let day: u64 = time_ms / DAY_MS;
+    if day > self.day {
+      self.day = day;
+      self.realized_today = 0.0;
+    }
+  }
+
+  /// Evaluate
+  /// Halts on a daily loss breach, otherwise keeps the current state
+  fn evaluate(&mut self) -> RiskAction {
+    if let Some(max_daily_loss) = self.limits.max_daily_loss {
+      if self.halted.is_none() && self.daily_pnl() <= -max_daily_loss.abs() {
+        self.halted = Some(RiskHalt::DailyLoss);
+        return self.breach_action();
+      }
+    }
+    match self.halted {
+      Some(_) => RiskAction::HaltEntries,
+      None => RiskAction::Continue
+    }
+  }
+
+  /// Breach Action
+  fn breach_action(&self) -> RiskAction {
+    match self.limits.flatten_on_breach {
+      true => RiskAction::Flatten(self.open_pairs.iter().map(|p| p.pair_id.clone()).collect()),
+      false => RiskAction::HaltEntries
+    }
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_rejects_entries_beyond_limits() {
+    let mut guard: RiskGuard = RiskGuard::new(RiskLimits { max_open_pairs: Some(2), max_notional: Some(25_000.0), ..Default::default() });
+    guard.open_pair("BTC/ETH", 10_000.0).unwrap();
+    assert!(guard.open_pair("BTC/ETH", 1_000.0).is_err());
+    assert!(guard.open_pair("SOL/AVAX", 20_000.0).is_err());
+    guard.open_pair("SOL/AVAX", 15_000.0).unwrap();
+    assert!(guard.check_entry("SPY/QQQ", 1.0).is_err());
+
+    assert_eq!(guard.close_pair("BTC/ETH", 50.0, 0), RiskAction::Continue);
+    assert_eq!(guard.open_notional(), 15_000.0);
+    assert!(guard.check_entry("SPY/QQQ", 10_000.0).is_ok());
+  }
+
+  #[tokio::test]
+  async fn it_halts_and_flattens_on_daily_loss() {
+    let limits: RiskLimits = RiskLimits { max_daily_loss: Some(500.0), flatten_on_breach: true, ..Default::default() };
+    let mut guard: RiskGuard = RiskGuard::new(limits);
+    guard.open_pair("BTC/ETH", 10_000.0).unwrap();
+    guard.open_pair("SOL/AVAX", 10_000.0).unwrap();
+
+    assert_eq!(guard.close_pair("SOL/AVAX", -300.0, 1_000), RiskAction::Continue);
+    assert_eq!(guard.mark_to_market(-250.0, 2_000), RiskAction::Flatten(vec!["BTC/ETH".to_string()]));
+    assert_eq!(guard.halted(), Some(RiskHalt::DailyLoss));
+    assert!(guard.check_entry("SPY/QQQ", 1.0).is_err());
+    assert_eq!(guard.close_pair("BTC/ETH", -250.0, 3_000), RiskAction::HaltEntries);
+
+    // Realized pnl restarts on a new day but the halt holds until reset
+    assert_eq!(guard.mark_to_market(0.0, DAY_MS + 1), RiskAction::HaltEntries);
+    assert_eq!(guard.daily_pnl(), 0.0);
+    guard.reset();
+    assert!(guard.check_entry("SPY/QQQ", 1.0).is_ok());
+
+    // Kill switch
+    assert_eq!(guard.halt(), RiskAction::Flatten(vec![]));
+    assert_eq!(guard.halted(), Some(RiskHalt::Manual));
+  }
+}
@@ -0,0 +1,983 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::sync::RwLock;
+use ts_rs::TS;
+
+use crate::SmartError;
+use crate::backtest::evaluation::BacktestMetrics;
+use crate::backtest::models::{Backtest, BacktestCriteria};
+use crate::prelude::{default_backtest_criteria_for, StatsCriteria};
+use crate::pricing::capacity::{pair_capacity, request_leg_capacity, CapacityConfig, LegCapacity};
+use crate::pricing::controller::PriceController;
+use crate::pricing::sectors::{same_sector, sector_for_symbol, Sector};
+use crate::pricing::stablecoin::{normalize_historical_prices, request_conversion_rates, usd_rate_market, StablecoinRate};
+use crate::pricing::translate::{is_usd_quote, parse_symbol, preferred_quote, SymbolParts};
+use crate::pricing::models::{DataCriteria, Exchange, HistoricalPrices, IntervalPeriod, MissingDataPolicy, PairPrices};
+use crate::pricing::utils::join_pair_series;
+use crate::stats::metrics::pearson_correlation_coefficient;
+use crate::stats::models::Statistics;
+use crate::stats::score::{PairScore, ScoreConfig};
+
+/*
+  Screener
+  Scores every pair from a list of symbols, each symbol fetched once and joined per pair on timestamp
+  Results are held by screen id so a UI can page, sort and filter them without receiving every row at once
+  The candidate universe keeps one dollar quote variant per base (the exchange's preferred quote) and can be restricted by sector
+*/
+
+/// Largest page a query may request
+pub const MAX_PAGE_SIZE: usize = 500;
+
+static SCREENER_RESULTS: RwLock<Option<HashMap<String, Vec<ScreenerRow>>>> = RwLock::new(None);
+
+#[derive(Debug, Deserialize, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct ScreenerCriteria {
+  pub exchange: Exchange,
+  pub interval_period: IntervalPeriod,
+  pub symbols: Vec<String>,
+  pub stats_criteria: StatsCriteria,
+  #[serde(default)]
+  pub score_config: ScoreConfig,
+  #[serde(default)]
+  pub capacity: Option<CapacityConfig>, // when set, rows report the notional each leg can trade within the impact limit
+  #[serde(default)]
+  pub sectors: Vec<Sector>, // when set, only symbols tagged with one of these sectors are screened
+  #[serde(default)]
+  pub same_sector: bool // only pair symbols sharing a known sector
+}
+
+impl ScreenerCriteria {
+
+  /// Screen Id
+  /// Identifies a screen by exchange, interval and candidate universe (results for the same screen replace each other)
+  pub fn screen_id(&self) -> String {
+    let scope: &str = if self.same_sector { ":same_sector" } else { "" };
+    format!("{:?}:{:?}:{}{}", self.exchange, self.interval_period, self.universe().join(","), scope)
+  }
+
+  /// Universe
+  /// Symbols screened: one dollar quote variant per base (preferring the exchange's preferred quote) within the sectors set
+  /// Symbols whose quote cannot be identified or is not dollar denominated are kept as listed
+  pub fn universe(&self) -> Vec<String> {
+    let preferred: String = preferred_quote(&self.exchange);
+    let dollar_base = |symbol: &str| parse_symbol(symbol).ok().filter(|p: &SymbolParts| is_usd_quote(&p.quote));
+
+    let mut universe: Vec<String> = vec![];
+    let mut bases: HashMap<String, usize> = HashMap::new();
+    for symbol in self.symbols.iter() {
+      if !self.sectors.is_empty() && !self.sectors.contains(&sector_for_symbol(symbol)) { continue }
+      let Some(parts) = dollar_base(symbol) else {
+        universe.push(symbol.clone());
+        continue
+      };
+      match bases.get(&parts.base) {
+        Some(idx) => if parts.quote == preferred { universe[*idx] = symbol.clone() },
+        None => {
+          bases.insert(parts.base, universe.len());
+          universe.push(symbol.clone());
+        }
+      }
+    }
+    universe
+  }
+
+  /// Pairs
+  /// Every unordered pair of the universe (asset_0 listed first), only pairs within a sector when same_sector is set
+  pub fn pairs(&self) -> Vec<(String, String)> {
+    let universe: Vec<String> = self.universe();
+    let mut pairs: Vec<(String, String)> = vec![];
+    for i in 0..universe.len() {
+      for j in (i + 1)..universe.len() {
+        if self.same_sector && !same_sector(&universe[i], &universe[j]) { continue }
+        pairs.push((universe[i].clone(), universe[j].clone()));
+      }
+    }
+    pairs
+  }
+
+  /// Data Criteria
+  /// Data criteria for a screened pair
+  pub fn data_criteria(&self, asset_0: &str, asset_1: &str) -> DataCriteria {
+    DataCriteria {
+      interval_period: self.interval_period.clone(),
+      asset_0: asset_0.to_string(),
+      asset_1: asset_1.to_string(),
+      exchange: self.exchange.clone(),
+      fallback_exchanges: vec![]
+    }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct ScreenerRow {
+  pub asset_0: String,
+  pub asset_1: String,
+  pub score: f64,
+  pub corr: f64,
+  pub coint_p_value: f64,
+  pub half_life: f64,
+  pub hedge_ratio: f64,
+  pub hurst: f64,
+  pub zscore_latest: f64,
+  pub sharpe_ratio: f64,
+  pub bars: usize,
+  #[serde(default)]
+  pub capacity: Option<f64> // notional per leg, limited by the thinner leg
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default, TS)]
+#[ts(export)]
+pub enum ScreenerSortKey {
+  #[default]
+  Score,
+  Corr,
+  CointPValue,
+  HalfLife,
+  Hurst,
+  ZscoreAbs, // absolute latest zscore (largest dislocation first by default)
+  SharpeRatio,
+  Capacity
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default, TS)]
+#[ts(export)]
+pub struct ScreenerFilter {
+  pub min_score: Option<f64>,
+  pub min_corr: Option<f64>,
+  pub max_coint_p_value: Option<f64>,
+  pub max_half_life: Option<f64>,
+  pub min_zscore_abs: Option<f64>,
+  pub min_capacity: Option<f64>, // rows without a capacity estimate are dropped
+  pub symbol: Option<String> // keeps rows with either leg containing this text (case insensitive)
+}
+
+impl ScreenerFilter {
+
+  /// Matches
+  /// True if the row passes every filter set
+  pub fn matches(&self, row: &ScreenerRow) -> bool {
+    let symbol_ok: bool = match &self.symbol {
+      Some(text) => {
+        let text: String = text.to_uppercase();
+        row.asset_0.to_uppercase().contains(&text) || row.asset_1.to_uppercase().contains(&text)
+      },
+      None => true
+    };
+    symbol_ok
+      && self.min_score.map(|m| row.score >= m).unwrap_or(true)
+      && self.min_corr.map(|m| row.corr >= m).unwrap_or(true)
+      && self.max_coint_p_value.map(|m| row.coint_p_value <= m).unwrap_or(true)
+      && self.max_half_life.map(|m| row.half_life <= m).unwrap_or(true)
+      && self.min_zscore_abs.map(|m| row.zscore_latest.abs() >= m).unwrap_or(true)
+      && self.min_capacity.map(|m| row.capacity.is_some_and(|c| c >= m)).unwrap_or(true)
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct ScreenerQuery {
+  pub page: usize, // zero based
+  pub page_size: usize,
+  #[serde(default)]
+  pub sort_by: ScreenerSortKey,
+  #[serde(default)]
+  pub ascending: bool, // largest first unless set
+  #[serde(default)]
+  pub filter: ScreenerFilter
+}
+
+impl Default for ScreenerQuery {
+  fn default() -> Self {
+    Self { page: 0, page_size: 50, sort_by: ScreenerSortKey::Score, ascending: false, filter: ScreenerFilter::default() }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct ScreenerPage {
+  pub screen_id: String,
+  pub rows: Vec<ScreenerRow>,
+  pub page: usize,
+  pub page_size: usize,
+  pub total_rows: usize, // rows passing the filter
+  pub total_pages: usize
+}
+
+/// Sort Value
+/// Value of the row for the sort key (non finite values sort last)
+fn sort_value(row: &ScreenerRow, key: ScreenerSortKey) -> f64 {
+  match key {
+    ScreenerSortKey::Score => row.score,
+    ScreenerSortKey::Corr => row.corr,
+    ScreenerSortKey::CointPValue => row.coint_p_value,
+    ScreenerSortKey::HalfLife => row.half_life,
+    ScreenerSortKey::Hurst => row.hurst,
+    ScreenerSortKey::ZscoreAbs => row.zscore_latest.abs(),
+    ScreenerSortKey::SharpeRatio => row.sharpe_ratio,
+    ScreenerSortKey::Capacity => row.capacity.unwrap_or(f64::NAN)
+  }
+}
+
+impl ScreenerQuery {
+
+  /// Apply
+  /// Filters, sorts and pages screener rows
+  pub fn apply(&self, screen_id: &str, rows: &[ScreenerRow]) -> Result<ScreenerPage, SmartError> {
+
+    // Guard: Ensure page size usable
+    if self.page_size == 0 || self.page_size > MAX_PAGE_SIZE {
+      return Err(SmartError::RuntimeCheck(format!("Page size must be between 1 and {}", MAX_PAGE_SIZE)));
+    }
+
+    let mut filtered: Vec<&ScreenerRow> = rows.iter().filter(|row| self.filter.matches(row)).collect();
+    filtered.sort_by(|a, b| {
+      let (va, vb) = (sort_value(a, self.sort_by), sort_value(b, self.sort_by));
+      match (va.is_finite(), vb.is_finite()) {
+        (true, true) if self.ascending => va.total_cmp(&vb),
+        (true, true) => vb.total_cmp(&va),
+        (a_finite, b_finite) => b_finite.cmp(&a_finite)
+      }
+    });
+
+    let total_rows: usize = filtered.len();
+    let rows: Vec<ScreenerRow> = filtered.into_iter()
+      .skip(self.page.saturating_mul(self.page_size))
+      .take(self.page_size)
+      .cloned()
+      .collect();
+    Ok(ScreenerPage {
+      screen_id: screen_id.to_string(),
+      rows,
+      page: self.page,
+      page_size: self.page_size,
+      total_rows,
+      total_pages: total_rows.div_ceil(self.page_size)
+    })
+  }
+}
+
+/// Score Pair
+/// Statistics, default backtest and score for a pair (as per a watchlist re-score)
+pub fn score_pair(criteria: &ScreenerCriteria, prices: &PairPrices, asset_0: &str, asset_1: &str) -> Result<ScreenerRow, SmartError> {
+  prices.ensure_complete()?;
+  let cleaned: PairPrices = match &criteria.stats_criteria.preprocess {
+    Some(config) => prices.preprocessed(config)?,
+    None => prices.clone()
+  };
+  let stats: Statistics = Statistics::calculate_statistics(
+    &cleaned.series_0,
+    &cleaned.series_1,
+    criteria.stats_criteria.spread_type.clone(),
+    criteria.stats_criteria.zscore_window,
+    criteria.stats_criteria.roll_window
+  )?;
+  let data_criteria: DataCriteria = criteria.data_criteria(asset_0, asset_1);
+  let bt_criteria: BacktestCriteria = default_backtest_criteria_for(&data_criteria, prices, stats.zscore.clone());
+  let backtest: Backtest = Backtest::new(&cleaned.series_0, &cleaned.series_1, bt_criteria)?
+    .with_excluded_bars(prices.excluded_bars())?;
+  let bt_metrics: BacktestMetrics = backtest.run_backtest()?;
+  let score: PairScore = PairScore::calculate(&stats, Some(&bt_metrics), &criteria.score_config)?;
+
+  Ok(ScreenerRow {
+    asset_0: asset_0.to_string(),
+    asset_1: asset_1.to_string(),
+    score: score.score,
+    corr: stats.corr,
+    coint_p_value: stats.coint.p_value,
+    half_life: stats.half_life,
+    hedge_ratio: stats.hedge_ratio,
+    hurst: score.hurst,
+    zscore_latest: stats.zscore.last().copied().unwrap_or(0.0),
+    sharpe_ratio: bt_metrics.sharpe_ratio,
+    bars: prices.labels.len(),
+    capacity: None
+  })
+}
+
+/// Screen Pair
+/// Joins two fetched legs on timestamp (bars missing from either leg dropped) and scores the pair
+pub fn screen_pair(criteria: &ScreenerCriteria, legs: &HashMap<String, HistoricalPrices>, asset_0: &str, asset_1: &str) -> Result<ScreenerRow, SmartError> {
+  let (Some(leg_0), Some(leg_1)) = (legs.get(asset_0), legs.get(asset_1)) else {
+    return Err(SmartError::RuntimeCheck(format!("Prices not fetched for {} / {}", asset_0, asset_1)))
+  };
+  let (series_0, series_1, labels) = join_pair_series(leg_0.clone(), leg_1.clone(), MissingDataPolicy::DropBar)?;
+  let prices: PairPrices = PairPrices {
+    series_0,
+    series_1,
+    labels,
+    label_convention: leg_0.label_convention,
+    fetch_report: None,
+    events: vec![],
+    source: Some(criteria.exchange.clone())
+  };
+  score_pair(criteria, &prices, asset_0, asset_1)
+}
+
+/// Screen Legs
+/// Scores every pair of the fetched legs, pairs that cannot be joined or scored are skipped
+pub fn screen_legs(criteria: &ScreenerCriteria, legs: &HashMap<String, HistoricalPrices>) -> Vec<ScreenerRow> {
+  criteria.pairs().into_iter()
+    .filter_map(|(asset_0, asset_1)| screen_pair(criteria, legs, &asset_0, &asset_1).ok())
+    .collect()
+}
+
+/// Fetch Legs
+/// Fetches each symbol once (concurrently), symbols that fail to fetch are left out
+/// Symbols mixing USD and stablecoin quotes are normalized to USD, a leg that cannot be converted is left out too
+pub async fn fetch_legs(criteria: &ScreenerCriteria, symbols: &[String], twelve_api_key: Option<&str>) -> HashMap<String, HistoricalPrices> {
+  let controllers: Vec<PriceController> = symbols.iter()
+    .map(|symbol| PriceController::new(symbol.clone(), criteria.interval_period.clone(), criteria.exchange.clone(), twelve_api_key))
+    .collect();
+  let symbol_refs: Vec<&str> = symbols.iter().map(|s| s.as_str()).collect();
+  let (results, rates) = futures::join!(
+    futures::future::join_all(controllers.iter().map(|c| c.get_latest_prices())),
+    request_conversion_rates(&symbol_refs, &criteria.interval_period)
+  );
+  normalize_legs(symbols.iter().cloned().zip(results).collect(), rates)
+}
+
+/// Normalize Legs
+/// Converts the fetched legs to USD with the conversion rates, leaving out legs that failed to fetch or convert
+fn normalize_legs(
+  results: Vec<(String, Result<HistoricalPrices, SmartError>)>,
+  rates: Result<Vec<StablecoinRate>, SmartError>
+) -> HashMap<String, HistoricalPrices> {
+  let needs_conversion = |symbol: &str| parse_symbol(symbol).is_ok_and(|parts| usd_rate_market(&parts.quote).is_some());
+  results.into_iter()
+    .filter_map(|(symbol, res)| {
+      let prices: HistoricalPrices = res.ok()?;
+      match &rates {
+        Ok(rates) => normalize_historical_prices(prices, &symbol, rates).ok(),
+        Err(_) if needs_conversion(&symbol) => None,
+        Err(_) => Some(prices)
+      }.map(|prices| (symbol, prices))
+    })
+    .collect()
+}
+
+/// Attach Capacity
+/// Estimates the capacity of each symbol in the rows once and sets each row's pair capacity (no-op without a capacity config)
+/// Symbols whose book cannot be fetched leave their rows without an estimate
+pub async fn attach_capacity(criteria: &ScreenerCriteria, rows: &mut [ScreenerRow]) {
+  let Some(config) = criteria.capacity else { return };
+  let symbols: Vec<String> = criteria.symbols.iter()
+    .filter(|s| rows.iter().any(|r| &r.asset_0 == *s || &r.asset_1 == *s))
+    .cloned()
+    .collect();
+  let results: Vec<Result<LegCapacity, SmartError>> = futures::future::join_all(
+    symbols.iter().map(|symbol| request_leg_capacity(&criteria.exchange, symbol, &config))
+  ).await;
+  let legs: HashMap<String, LegCapacity> = symbols.into_iter().zip(results)
+    .filter_map(|(symbol, res)| res.ok().map(|leg| (symbol, leg)))
+    .collect();
+  for row in rows.iter_mut() {
+    if let (Some(leg_0), Some(leg_1)) = (legs.get(&row.asset_0), legs.get(&row.asset_1)) {
+      row.capacity = Some(pair_capacity(leg_0, leg_1));
+    }
+  }
+}
+
+/// Screen Pairs
+/// Fetches and scores every pair, storing the rows under the screen id for paging
+pub async fn screen_pairs(criteria: &ScreenerCriteria, twelve_api_key: Option<&str>) -> Result<Vec<ScreenerRow>, SmartError> {
+
+  // Guard: Ensure at least one pair
+  if criteria.universe().len() < 2 {
+    return Err(SmartError::RuntimeCheck("Screening requires at least 2 symbols".to_string()));
+  }
+
+  let legs: HashMap<String, HistoricalPrices> = fetch_legs(criteria, &criteria.universe(), twelve_api_key).await;
+  let mut rows: Vec<ScreenerRow> = screen_legs(criteria, &legs);
+  attach_capacity(criteria, &mut rows).await;
+  store_screener_results(&criteria.screen_id(), rows.clone());
+  Ok(rows)
+}
+
+/// Store Screener Results
+/// Holds rows under a screen id (replacing any earlier results for it)
+pub fn store_screener_results(screen_id: &str, rows: Vec<ScreenerRow>) {
+  if let Ok(mut store) = SCREENER_RESULTS.write() {
+    store.get_or_insert_with(HashMap::new).insert(screen_id.to_string(), rows);
+  }
+}
+
+/// Clear Screener Results
+/// Drops all stored screens
+pub fn clear_screener_results() {
+  if let Ok(mut store) = SCREENER_RESULTS.write() {
+    *store = None;
+  }
+}
+
+/// Query Screener
+/// Page of the stored rows for a screen id
+pub fn query_screener(screen_id: &str, query: &ScreenerQuery) -> Result<ScreenerPage, SmartError> {
+  let store = SCREENER_RESULTS.read().map_err(|_| SmartError::RuntimeCheck("Screener results lock poisoned".to_string()))?;
+  let Some(rows) = store.as_ref().and_then(|s| s.get(screen_id)) else {
+    return Err(SmartError::RuntimeCheck(format!("No screener results for screen id: {}", screen_id)))
+  };
+  query.apply(screen_id, rows)
+}
+
+
+/*
+  Screening Plan
+  Cheap filters (correlation and the sum of squared differences of rebased prices) run on every pair first
+  Only the top K survivors, popped from a priority queue, go on to cointegration and a backtest
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default, TS)]
+#[ts(export)]
+pub enum CheapMetric {
+  #[default]
+  Correlation, // highest first
+  Ssd // lowest first
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct ScreeningPlan {
+  #[serde(default)]
+  pub rank_by: CheapMetric,
+  pub top_k: usize, // pairs passed on to the full score
+  pub min_corr: Option<f64>,
+  pub max_ssd: Option<f64>,
+  pub prefilter_period: Option<u32> // bars fetched for the cheap pass, the full history is then only fetched for survivors
+}
+
+impl Default for ScreeningPlan {
+  fn default() -> Self {
+    Self { rank_by: CheapMetric::Correlation, top_k: 20, min_corr: None, max_ssd: None, prefilter_period: None }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct CheapScore {
+  pub asset_0: String,
+  pub asset_1: String,
+  pub corr: f64,
+  pub ssd: f64, // per bar, on prices rebased to 1.0 at the first joined bar
+  pub bars: usize
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct PlannedScreening {
+  pub screen_id: String,
+  pub rows: Vec<ScreenerRow>,
+  pub survivors: Vec<CheapScore>, // pairs sent to the full score, in priority order
+  pub pairs_total: usize,
+  pub pairs_passed: usize // pairs passing the cheap filters (before the top K cut)
+}
+
+/// Ranked Pair
+/// Priority queue entry ordered by its cheap metric
+struct RankedPair {
+  priority: f64,
+  idx: usize
+}
+
+impl PartialEq for RankedPair {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl Eq for RankedPair {}
+
+impl PartialOrd for RankedPair {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for RankedPair {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.priority.total_cmp(&other.priority).then_with(|| other.idx.cmp(&self.idx))
+  }
+}
+
+impl ScreeningPlan {
+
+  /// Passes
+  /// True if the cheap score passes every filter set
+  pub fn passes(&self, cheap: &CheapScore) -> bool {
+    cheap.corr.is_finite() && cheap.ssd.is_finite()
+      && self.min_corr.map(|m| cheap.corr >= m).unwrap_or(true)
+      && self.max_ssd.map(|m| cheap.ssd <= m).unwrap_or(true)
+  }
+
+  /// Select
+  /// Top K cheap scores passing the filters, best first
+  pub fn select(&self, scores: &[CheapScore]) -> Vec<CheapScore> {
+    let mut queue: BinaryHeap<RankedPair> = scores.iter().enumerate()
+      .filter(|(_, cheap)| self.passes(cheap))
+      .map(|(idx, cheap)| {
+        let priority: f64 = match self.rank_by {
+          CheapMetric::Correlation => cheap.corr,
+          CheapMetric::Ssd => -cheap.ssd
+        };
+        RankedPair { priority, idx }
+      })
+      .collect();
+
+    let mut survivors: Vec<CheapScore> = vec![];
+    while survivors.len() < self.top_k {
+      let Some(ranked) = queue.pop() else { break };
+      survivors.push(scores[ranked.idx].clone());
+    }
+    survivors
+  }
+}
+
+/// Cheap Score
+/// Correlation and sum of squared differences of a joined pair (no cointegration or backtest)
+pub fn cheap_score(legs: &HashMap<String, HistoricalPrices>, asset_0: &str, asset_1: &str) -> Result<CheapScore, SmartError> {
+  let (Some(leg_0), Some(leg_1)) = (legs.get(asset_0), legs.get(asset_1)) else {
+    return Err(SmartError::RuntimeCheck(format!("Prices not fetched for {} / {}", asset_0, asset_1)))
+  };
+  let (series_0, series_1, _) = join_pair_series(leg_0.clone(), leg_1.clone(), MissingDataPolicy::DropBar)?;
+
+  // Guard: Ensure enough bars and a usable base price
+  if series_0.len() < 2 {
+    return Err(SmartError::RuntimeCheck(format!("Not enough joined bars for {} / {}", asset_0, asset_1)));
+  }
+  let (base_0, base_1) = (series_0[0], series_1[0]);
+  if !(base_0.is_finite() && base_1.is_finite() && base_0 > 0.0 && base_1 > 0.0) {
+    return Err(SmartError::RuntimeCheck(format!("No positive base price for {} / {}", asset_0, asset_1)));
+  }
+
+  let corr: f64 = pearson_correlation_coefficient(&series_0, &series_1)?;
+  let ssd: f64 = series_0.iter().zip(series_1.iter())
+    .map(|(p0, p1)| (p0 / base_0 - p1 / base_1).powi(2))
+    .sum::<f64>() / series_0.len() as f64;
+  Ok(CheapScore { asset_0: asset_0.to_string(), asset_1: asset_1.to_string(), corr, ssd, bars: series_0.len() })
+}
+
+/// Cheap Scores
+/// Cheap score for every pair of the fetched legs, pairs that cannot be joined are skipped
+pub fn cheap_scores(criteria: &ScreenerCriteria, legs: &HashMap<String, HistoricalPrices>) -> Vec<CheapScore> {
+  criteria.pairs().into_iter()
+    .filter_map(|(asset_0, asset_1)| cheap_score(legs, &asset_0, &asset_1).ok())
+    .collect()
+}
+
+/// Screen Survivors
+/// Full score for the pairs kept by a plan, pairs that cannot be scored are skipped
+pub fn screen_survivors(criteria: &ScreenerCriteria, survivors: &[CheapScore], legs: &HashMap<String, HistoricalPrices>) -> Vec<ScreenerRow> {
+  survivors.iter()
+    .filter_map(|cheap| screen_pair(criteria, legs, &cheap.asset_0, &cheap.asset_1).ok())
+    .collect()
+}
+
+/// Screen Pairs Planned
+/// As screen_pairs, but only the top K pairs of the cheap pass are cointegration tested and backtested
+pub async fn screen_pairs_planned(criteria: &ScreenerCriteria, plan: &ScreeningPlan, twelve_api_key: Option<&str>) -> Result<PlannedScreening, SmartError> {
+
+  // Guard: Ensure at least one pair and a survivor to score
+  if criteria.universe().len() < 2 {
+    return Err(SmartError::RuntimeCheck("Screening requires at least 2 symbols".to_string()));
+  }
+  if plan.top_k == 0 {
+    return Err(SmartError::RuntimeCheck("Screening plan top_k must be at least 1".to_string()));
+  }
+
+  // Cheap pass over every pair (on a shorter history if set)
+  let cheap_criteria: ScreenerCriteria = match plan.prefilter_period {
+    Some(period) => ScreenerCriteria { interval_period: criteria.interval_period.with_period(period), ..criteria.clone() },
+    None => criteria.clone()
+  };
+  let cheap_legs: HashMap<String, HistoricalPrices> = fetch_legs(&cheap_criteria, &criteria.universe(), twelve_api_key).await;
+  let scores: Vec<CheapScore> = cheap_scores(criteria, &cheap_legs);
+  let pairs_passed: usize = scores.iter().filter(|cheap| plan.passes(cheap)).count();
+  let survivors: Vec<CheapScore> = plan.select(&scores);
+
+  // Full history only for symbols of the survivors
+  let legs: HashMap<String, HistoricalPrices> = match plan.prefilter_period {
+    Some(_) => {
+      let needed: Vec<String> = criteria.universe().into_iter()
+        .filter(|s| survivors.iter().any(|c| c.asset_0 == *s || c.asset_1 == *s))
+        .collect();
+      fetch_legs(criteria, &needed, twelve_api_key).await
+    },
+    None => cheap_legs
+  };
+
+  let mut rows: Vec<ScreenerRow> = screen_survivors(criteria, &survivors, &legs);
+  attach_capacity(criteria, &mut rows).await;
+  store_screener_results(&criteria.screen_id(), rows.clone());
+  Ok(PlannedScreening { screen_id: criteria.screen_id(), rows, survivors, pairs_total: criteria.pairs().len(), pairs_passed })
+}
+
+
+/*
+  Resumable Screening
+  Scored pairs are checkpointed to a progress file keyed by job id as each batch of symbols is fetched
+  A rerun of the same job skips pairs already scored (or found unscorable) and only fetches symbols it still needs
+*/
+
+/// Symbols fetched between checkpoints
+#[cfg(not(target_arch = "wasm32"))]
+const CHECKPOINT_BATCH: usize = 10;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct ScreeningCheckpoint {
+  pub job_id: String,
+  pub screen_id: String,
+  pub rows: Vec<ScreenerRow>,
+  pub skipped: Vec<(String, String)>, // pairs whose prices could not be joined or scored (not retried)
+  pub updated_at: i64 // unix seconds
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct ScreeningReport {
+  pub job_id: String,
+  pub screen_id: String,
+  pub rows: Vec<ScreenerRow>,
+  pub skipped: Vec<(String, String)>,
+  pub pending: Vec<(String, String)>, // pairs with a leg that failed to fetch (retried on the next run)
+  pub resumed_rows: usize, // rows carried over from an earlier run
+  pub is_complete: bool
+}
+
+/// Pair Key
+fn pair_key(asset_0: &str, asset_1: &str) -> String {
+  format!("{}|{}", asset_0, asset_1)
+}
+
+impl ScreeningCheckpoint {
+  pub fn new(job_id: &str, criteria: &ScreenerCriteria) -> Self {
+    Self { job_id: job_id.to_string(), screen_id: criteria.screen_id(), rows: vec![], skipped: vec![], updated_at: 0 }
+  }
+
+  /// Pending Pairs
+  /// Pairs of the screen neither scored nor skipped so far
+  pub fn pending_pairs(&self, criteria: &ScreenerCriteria) -> Vec<(String, String)> {
+    let done: HashSet<String> = self.rows.iter().map(|r| pair_key(&r.asset_0, &r.asset_1))
+      .chain(self.skipped.iter().map(|(a, b)| pair_key(a, b)))
+      .collect();
+    criteria.pairs().into_iter().filter(|(a, b)| !done.contains(&pair_key(a, b))).collect()
+  }
+
+  /// Score Available
+  /// Scores the pending pairs whose legs are both held, returning the number of pairs processed
+  pub fn score_available(&mut self, criteria: &ScreenerCriteria, legs: &HashMap<String, HistoricalPrices>, now: i64) -> usize {
+    let ready: Vec<(String, String)> = self.pending_pairs(criteria).into_iter()
+      .filter(|(a, b)| legs.contains_key(a) && legs.contains_key(b))
+      .collect();
+    for (asset_0, asset_1) in ready.iter() {
+      match screen_pair(criteria, legs, asset_0, asset_1) {
+        Ok(row) => self.rows.push(row),
+        Err(_) => self.skipped.push((asset_0.clone(), asset_1.clone()))
+      }
+    }
+    self.updated_at = now;
+    ready.len()
+  }
+
+  /// Report
+  /// Merged results of every run of the job so far
+  pub fn report(&self, criteria: &ScreenerCriteria, resumed_rows: usize) -> ScreeningReport {
+    let pending: Vec<(String, String)> = self.pending_pairs(criteria);
+    ScreeningReport {
+      job_id: self.job_id.clone(),
+      screen_id: self.screen_id.clone(),
+      rows: self.rows.clone(),
+      skipped: self.skipped.clone(),
+      is_complete: pending.is_empty(),
+      pending,
+      resumed_rows
+    }
+  }
+
+  /// To JSON / From JSON
+  pub fn to_json(&self) -> Result<String, SmartError> {
+    Ok(serde_json::to_string(self)?)
+  }
+
+  pub fn from_json(json: &str) -> Result<Self, SmartError> {
+    Ok(serde_json::from_str::<Self>(json)?)
+  }
+
+  /// Save / Load
+  /// Persists the checkpoint to its progress file
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), SmartError> {
+    std::fs::write(path, self.to_json()?)?;
+    Ok(())
+  }
+
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, SmartError> {
+    Self::from_json(&std::fs::read_to_string(path)?)
+  }
+}
+
+/// Checkpoint Path
+/// Progress file for a job id within a directory (characters unsafe in file names are replaced)
+#[cfg(not(target_arch = "wasm32"))]
+pub fn checkpoint_path(dir: impl AsRef<std::path::Path>, job_id: &str) -> std::path::PathBuf {
+  let safe: String = job_id.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect();
+  dir.as_ref().join(format!("screen_{}.json", safe))
+}
+
+/// Screen Pairs Resumable
+/// As screen_pairs, checkpointing after each batch of symbols so an interrupted job continues where it stopped
+/// A checkpoint written for a different screen (other symbols, interval or exchange) is discarded
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn screen_pairs_resumable(
+  criteria: &ScreenerCriteria,
+  job_id: &str,
+  dir: impl AsRef<std::path::Path>,
+  twelve_api_key: Option<&str>
+) -> Result<ScreeningReport, SmartError> {
+
+  // Guard: Ensure at least one pair
+  if criteria.universe().len() < 2 {
+    return Err(SmartError::RuntimeCheck("Screening requires at least 2 symbols".to_string()));
+  }
+
+  let path: std::path::PathBuf = checkpoint_path(dir, job_id);
+  let mut checkpoint: ScreeningCheckpoint = match ScreeningCheckpoint::load(&path) {
+    Ok(saved) if saved.screen_id == criteria.screen_id() => saved,
+    _ => ScreeningCheckpoint::new(job_id, criteria)
+  };
+  let resumed_rows: usize = checkpoint.rows.len();
+
+  // Symbols still needed, fetched in batches with the checkpoint saved after each
+  let pending: Vec<(String, String)> = checkpoint.pending_pairs(criteria);
+  let needed: Vec<String> = criteria.universe().into_iter()
+    .filter(|s| pending.iter().any(|(a, b)| a == s || b == s))
+    .collect();
+  let mut legs: HashMap<String, HistoricalPrices> = HashMap::new();
+  for batch in needed.chunks(CHECKPOINT_BATCH) {
+    legs.extend(fetch_legs(criteria, batch, twelve_api_key).await);
+    if checkpoint.score_available(criteria, &legs, crate::pricing::clock::now_secs()) > 0 {
+      checkpoint.save(&path)?;
+    }
+  }
+
+  let mut report: ScreeningReport = checkpoint.report(criteria, resumed_rows);
+  attach_capacity(criteria, &mut report.rows).await;
+  store_screener_results(&report.screen_id, report.rows.clone());
+  Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::pricing::models::LabelConvention;
+  use crate::stats::models::SpreadType;
+
+  fn row(asset_0: &str, asset_1: &str, score: f64, zscore_latest: f64) -> ScreenerRow {
+    ScreenerRow {
+      asset_0: asset_0.to_string(), asset_1: asset_1.to_string(), score, corr: 0.9, coint_p_value: 0.05,
+      half_life: 10.0, hedge_ratio: 1.0, hurst: 0.4, zscore_latest, sharpe_ratio: 1.0, bars: 200, capacity: None
+    }
+  }
+
+  fn criteria(symbols: &[&str]) -> ScreenerCriteria {
+    ScreenerCriteria {
+      exchange: Exchange::Binance,
+      interval_period: IntervalPeriod::Hour(1, 300),
+      symbols: symbols.iter().map(|s| s.to_string()).collect(),
+      stats_criteria: StatsCriteria { spread_type: SpreadType::Static, zscore_window: 21, roll_window: 90, preprocess: None },
+      score_config: ScoreConfig::default(),
+      capacity: None,
+      sectors: vec![],
+      same_sector: false
+    }
+  }
+
+  #[tokio::test]
+  async fn it_builds_universe_from_preferred_quotes_and_sectors() {
+
+    // One dollar quote variant per base, the preferred quote (USDT on Binance) replacing others
+    let criteria: ScreenerCriteria = criteria(&["BTCUSDC", "BTCUSDT", "ETHUSDT", "ETHUSDC", "UNIUSDT", "ETHBTC"]);
+    assert_eq!(criteria.universe(), vec!["BTCUSDT", "ETHUSDT", "UNIUSDT", "ETHBTC"]);
+    assert_eq!(criteria.pairs().len(), 6);
+
+    // Sector filter and same sector pairs
+    let layer_1: ScreenerCriteria = ScreenerCriteria { sectors: vec![Sector::Layer1], ..criteria.clone() };
+    assert_eq!(layer_1.universe(), vec!["BTCUSDT", "ETHUSDT", "ETHBTC"]);
+    let within: ScreenerCriteria = ScreenerCriteria { same_sector: true, ..criteria.clone() };
+    assert!(within.pairs().iter().all(|(a, b)| a != "UNIUSDT" && b != "UNIUSDT"));
+    assert_ne!(within.screen_id(), criteria.screen_id());
+  }
+
+  #[tokio::test]
+  async fn it_normalizes_mixed_quote_legs() {
+    let leg = |prices: Vec<f64>| HistoricalPrices { prices, labels: vec![10, 20], label_convention: LabelConvention::OpenTime, fetch_report: None };
+    let rates: Vec<StablecoinRate> = vec![StablecoinRate { stablecoin: "USDT".to_string(), rates: leg(vec![0.5, 2.0]) }];
+    let results = || vec![
+      ("BTC-USD".to_string(), Ok(leg(vec![100.0, 100.0]))),
+      ("ETHUSDT".to_string(), Ok(leg(vec![10.0, 10.0]))),
+      ("SOLUSDC".to_string(), Ok(leg(vec![1.0, 1.0]))),
+      ("ADAUSDT".to_string(), Err(SmartError::RuntimeCheck("failed".to_string())))
+    ];
+
+    let legs: HashMap<String, HistoricalPrices> = normalize_legs(results(), Ok(rates));
+    assert_eq!(legs.len(), 3);
+    assert_eq!(legs["ETHUSDT"].prices, vec![5.0, 20.0]);
+    assert_eq!((legs["BTC-USD"].prices[1], legs["SOLUSDC"].prices[1]), (100.0, 1.0));
+
+    // Without the rate a USDT leg cannot be compared in USD and is left out
+    let legs: HashMap<String, HistoricalPrices> = normalize_legs(results(), Err(SmartError::RuntimeCheck("no rate".to_string())));
+    assert!(legs.contains_key("BTC-USD") && legs.contains_key("SOLUSDC") && !legs.contains_key("ETHUSDT"));
+  }
+
+  #[tokio::test]
+  async fn it_pages_sorts_and_filters_rows() {
+    let rows: Vec<ScreenerRow> = (0..120).map(|i| row(&format!("S{}USDT", i), "BTCUSDT", i as f64, (i as f64 - 60.0) / 20.0)).collect();
+
+    let page: ScreenerPage = ScreenerQuery { page: 1, page_size: 50, ..Default::default() }.apply("id", &rows).unwrap();
+    assert_eq!((page.total_rows, page.total_pages, page.rows.len()), (120, 3, 50));
+    assert_eq!(page.rows[0].score, 69.0);
+
+    let last: ScreenerPage = ScreenerQuery { page: 2, page_size: 50, ..Default::default() }.apply("id", &rows).unwrap();
+    assert_eq!(last.rows.len(), 20);
+
+    let filter: ScreenerFilter = ScreenerFilter { min_score: Some(100.0), min_zscore_abs: Some(2.5), ..Default::default() };
+    let filtered: ScreenerPage = ScreenerQuery { sort_by: ScreenerSortKey::ZscoreAbs, filter, ..Default::default() }.apply("id", &rows).unwrap();
+    assert_eq!(filtered.total_rows, 10);
+    assert_eq!(filtered.rows[0].asset_0, "S119USDT");
+
+    let symbol: ScreenerFilter = ScreenerFilter { symbol: Some("s11".to_string()), ..Default::default() };
+    let ascending: ScreenerPage = ScreenerQuery { ascending: true, filter: symbol, ..Default::default() }.apply("id", &rows).unwrap();
+    assert_eq!(ascending.rows.first().map(|r| r.score), Some(11.0));
+
+    assert!(ScreenerQuery { page_size: 0, ..Default::default() }.apply("id", &rows).is_err());
+  }
+
+  #[tokio::test]
+  async fn it_filters_and_sorts_by_capacity() {
+    let rows: Vec<ScreenerRow> = vec![
+      ScreenerRow { capacity: Some(5_000.0), ..row("ETHUSDT", "BTCUSDT", 1.0, 0.0) },
+      ScreenerRow { capacity: Some(250_000.0), ..row("SOLUSDT", "BTCUSDT", 2.0, 0.0) },
+      row("PEPEUSDT", "BTCUSDT", 3.0, 0.0)
+    ];
+    let by_capacity: ScreenerPage = ScreenerQuery { sort_by: ScreenerSortKey::Capacity, ..Default::default() }.apply("id", &rows).unwrap();
+    assert_eq!(by_capacity.rows[0].asset_0, "SOLUSDT");
+    assert_eq!(by_capacity.rows[2].asset_0, "PEPEUSDT");
+
+    let filter: ScreenerFilter = ScreenerFilter { min_capacity: Some(10_000.0), ..Default::default() };
+    let tradable: ScreenerPage = ScreenerQuery { filter, ..Default::default() }.apply("id", &rows).unwrap();
+    assert_eq!(tradable.total_rows, 1);
+
+    // Without a capacity config rows are left unestimated
+    let mut unestimated: Vec<ScreenerRow> = rows.clone();
+    attach_capacity(&criteria(&["ETHUSDT", "BTCUSDT"]), &mut unestimated).await;
+    assert_eq!(unestimated, rows);
+  }
+
+  #[tokio::test]
+  async fn it_screens_and_stores_pairs() {
+    let labels: Vec<u64> = (0..300).map(|i| 1700000000 + i * 3600).collect();
+    let base: Vec<f64> = (0..300).map(|i| 100.0 + (i as f64 / 10.0).sin() * 5.0 + i as f64 * 0.1).collect();
+    let leg = |prices: Vec<f64>| HistoricalPrices { prices, labels: labels.clone(), label_convention: LabelConvention::OpenTime, fetch_report: None };
+    let mut legs: HashMap<String, HistoricalPrices> = HashMap::new();
+    legs.insert("ETHUSDT".to_string(), leg(base.iter().enumerate().map(|(i, p)| p * 2.0 + (i as f64 / 3.0).cos() * 2.0).collect()));
+    legs.insert("SOLUSDT".to_string(), leg(base.clone()));
+    legs.insert("AVAXUSDT".to_string(), leg(base.iter().enumerate().map(|(i, p)| p + (i as f64 / 5.0).sin()).collect()));
+
+    let criteria: ScreenerCriteria = criteria(&["ETHUSDT", "SOLUSDT", "AVAXUSDT", "MISSINGUSDT"]);
+    let rows: Vec<ScreenerRow> = screen_legs(&criteria, &legs);
+    assert_eq!(rows.len(), 3);
+    assert!(rows.iter().all(|r| r.bars == 300 && r.score.is_finite()));
+
+    store_screener_results(&criteria.screen_id(), rows);
+    let page: ScreenerPage = query_screener(&criteria.screen_id(), &ScreenerQuery::default()).unwrap();
+    assert_eq!(page.total_rows, 3);
+    assert!(page.rows[0].score >= page.rows[2].score);
+    assert!(query_screener("unknown", &ScreenerQuery::default()).is_err());
+  }
+
+  #[tokio::test]
+  async fn it_resumes_screening_from_checkpoint() {
+    let labels: Vec<u64> = (0..300).map(|i| 1700000000 + i * 3600).collect();
+    let leg = |shift: f64| HistoricalPrices {
+      prices: (0..300).map(|i| 100.0 + (i as f64 / 10.0 + shift).sin() * 5.0 + i as f64 * 0.1).collect(),
+      labels: labels.clone(),
+      label_convention: LabelConvention::OpenTime,
+      fetch_report: None
+    };
+    let criteria: ScreenerCriteria = criteria(&["ETHUSDT", "SOLUSDT", "AVAXUSDT", "NEARUSDT"]);
+    let mut legs: HashMap<String, HistoricalPrices> = HashMap::new();
+    legs.insert("ETHUSDT".to_string(), leg(0.0));
+    legs.insert("SOLUSDT".to_string(), leg(0.3));
+    legs.insert("AVAXUSDT".to_string(), leg(0.6));
+
+    // First run stops with NEARUSDT unfetched
+    let mut checkpoint: ScreeningCheckpoint = ScreeningCheckpoint::new("job/1", &criteria);
+    assert_eq!(checkpoint.score_available(&criteria, &legs, 100), 3);
+    let first: ScreeningReport = checkpoint.report(&criteria, 0);
+    assert_eq!((first.rows.len(), first.pending.len(), first.is_complete), (3, 3, false));
+
+    let path: std::path::PathBuf = checkpoint_path(std::env::temp_dir(), "job/1");
+    assert!(path.ends_with("screen_job_1.json"));
+    checkpoint.save(&path).unwrap();
+
+    // Rerun only scores the remaining pairs and merges them with the saved rows
+    let mut resumed: ScreeningCheckpoint = ScreeningCheckpoint::load(&path).unwrap();
+    legs.insert("NEARUSDT".to_string(), leg(0.9));
+    assert_eq!(resumed.score_available(&criteria, &legs, 200), 3);
+    let report: ScreeningReport = resumed.report(&criteria, 3);
+    assert_eq!((report.rows.len(), report.pending.len(), report.is_complete), (6, 0, true));
+    assert_eq!(resumed.score_available(&criteria, &legs, 300), 0);
+    let _ = std::fs::remove_file(&path);
+  }
+
+  #[tokio::test]
+  async fn it_screens_top_k_by_plan() {
+    let labels: Vec<u64> = (0..300).map(|i| 1700000000 + i * 3600).collect();
+    let base: Vec<f64> = (0..300).map(|i| 100.0 + (i as f64 / 10.0).sin() * 5.0 + i as f64 * 0.1).collect();
+    let leg = |prices: Vec<f64>| HistoricalPrices { prices, labels: labels.clone(), label_convention: LabelConvention::OpenTime, fetch_report: None };
+    let mut legs: HashMap<String, HistoricalPrices> = HashMap::new();
+    legs.insert("ETHUSDT".to_string(), leg(base.iter().enumerate().map(|(i, p)| p * 2.0 + (i as f64 / 3.0).cos() * 2.0).collect()));
+    legs.insert("SOLUSDT".to_string(), leg(base.clone()));
+    legs.insert("AVAXUSDT".to_string(), leg(base.iter().enumerate().map(|(i, p)| p + (i as f64 / 5.0).sin()).collect()));
+    legs.insert("DOGEUSDT".to_string(), leg((0..300).map(|i| 100.0 + (i as f64 / 7.0).cos() * 20.0 - i as f64 * 0.1).collect()));
+
+    let criteria: ScreenerCriteria = criteria(&["ETHUSDT", "SOLUSDT", "AVAXUSDT", "DOGEUSDT"]);
+    let scores: Vec<CheapScore> = cheap_scores(&criteria, &legs);
+    assert_eq!(scores.len(), 6);
+
+    // Uncorrelated DOGEUSDT pairs are dropped before the full score
+    let plan: ScreeningPlan = ScreeningPlan { top_k: 2, min_corr: Some(0.5), ..Default::default() };
+    assert_eq!(scores.iter().filter(|c| plan.passes(c)).count(), 3);
+    let survivors: Vec<CheapScore> = plan.select(&scores);
+    assert_eq!(survivors.len(), 2);
+    assert!(survivors[0].corr >= survivors[1].corr);
+    assert!(survivors.iter().all(|c| c.asset_0 != "DOGEUSDT" && c.asset_1 != "DOGEUSDT"));
+
+    // Ranking by distance keeps the closest rebased pair first
+    let by_ssd: Vec<CheapScore> = ScreeningPlan { rank_by: CheapMetric::Ssd, top_k: 1, ..Default::default() }.select(&scores);
+    assert_eq!((by_ssd[0].asset_0.as_str(), by_ssd[0].asset_1.as_str()), ("SOLUSDT", "AVAXUSDT"));
+
+    let rows: Vec<ScreenerRow> = screen_survivors(&criteria, &survivors, &legs);
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|r| r.score.is_finite()));
+  }
+
+  #[tokio::test]
+  async fn tests_screen_pairs_binance() {
+    let criteria: ScreenerCriteria = criteria(&["ETHUSDT", "SOLUSDT", "AVAXUSDT"]);
+    let rows: Vec<ScreenerRow> = screen_pairs(&criteria, None).await.unwrap();
+    assert!(!rows.is_empty());
+  }
+
+  #[tokio::test]
+  async fn tests_screen_pairs_planned_binance() {
+    let criteria: ScreenerCriteria = criteria(&["ETHUSDT", "SOLUSDT", "AVAXUSDT", "NEARUSDT"]);
+    let plan: ScreeningPlan = ScreeningPlan { top_k: 2, prefilter_period: Some(100), ..Default::default() };
+    let planned: PlannedScreening = screen_pairs_planned(&criteria, &plan, None).await.unwrap();
+    assert_eq!(planned.pairs_total, 6);
+    assert!(planned.rows.len() <= 2);
+  }
+}
@@ -0,0 +1,132 @@
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+use crate::SmartError;
+use crate::prelude::{full_analysis_from_pair_prices, AnalysisCriteria, PairAnalysis};
+
+/*
+  Snapshot Bundles
+  Bundles prices, criteria, stats and backtest metrics into a single gzipped json file
+  Allows analyses to be shared and re-run later on exactly the same data
+*/
+
+const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Snapshot {
+  pub version: u32,
+  pub created_at: i64, // unix seconds
+  pub lib_version: String,
+  pub criteria: AnalysisCriteria,
+  pub analysis: PairAnalysis
+}
+
+impl Snapshot {
+  pub fn new(criteria: AnalysisCriteria, analysis: PairAnalysis) -> Self {
+    Self {
+      version: SNAPSHOT_VERSION,
+      created_at: crate::pricing::clock::now_secs(),
+      lib_version: env!("CARGO_PKG_VERSION").to_string(),
+      criteria,
+      analysis
+    }
+  }
+
+  /// To Bytes
+  /// Gzipped json representation
+  pub fn to_bytes(&self) -> Result<Vec<u8>, SmartError> {
+    let json: Vec<u8> = serde_json::to_vec(self)?;
+    let mut encoder: GzEncoder<Vec<u8>> = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&json)?;
+    Ok(encoder.finish()?)
+  }
+
+  /// From Bytes
+  /// Reads a gzipped json snapshot
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, SmartError> {
+    let mut json: String = String::new();
+    GzDecoder::new(bytes).read_to_string(&mut json)?;
+    let snapshot: Self = serde_json::from_str::<Self>(&json)?;
+
+    // Guard: Ensure supported version
+    if snapshot.version > SNAPSHOT_VERSION {
+      let e: String = format!("Snapshot version {} is newer than supported version {}", snapshot.version, SNAPSHOT_VERSION);
+      return Err(SmartError::RuntimeCheck(e));
+    }
+
+    Ok(snapshot)
+  }
+
+  /// Rerun
+  /// Re-runs the analysis on the bundled prices and criteria (no network access)
+  pub async fn rerun(&self) -> Result<PairAnalysis, SmartError> {
+    full_analysis_from_pair_prices(
+      self.analysis.prices.clone(),
+      self.criteria.stats_criteria.clone(),
+      self.criteria.backtest_criteria.clone()
+    ).await
+  }
+
+  /// Reproduces
+  /// True if re-running gives identical stats and backtest metrics
+  pub async fn reproduces(&self) -> Result<bool, SmartError> {
+    let rerun: PairAnalysis = self.rerun().await?;
+    Ok(serde_json::to_value(&rerun.stats)? == serde_json::to_value(&self.analysis.stats)?
+      && serde_json::to_value(&rerun.bt_metrics)? == serde_json::to_value(&self.analysis.bt_metrics)?)
+  }
+}
+
+/// Save
+/// Writes the snapshot to a .json.gz file
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save(snapshot: &Snapshot, path: impl AsRef<std::path::Path>) -> Result<(), SmartError> {
+  std::fs::write(path, snapshot.to_bytes()?)?;
+  Ok(())
+}
+
+/// Load
+/// Reads a snapshot from a .json.gz file
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load(path: impl AsRef<std::path::Path>) -> Result<Snapshot, SmartError> {
+  Snapshot::from_bytes(&std::fs::read(path)?)
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::pricing::models::{DataCriteria, Exchange, IntervalPeriod, MissingDataPolicy, PairPrices};
+
+  #[tokio::test]
+  async fn it_saves_loads_and_reruns_snapshot() {
+    let series_1: Vec<f64> = (0..300).map(|i| 100.0 + (i as f64 / 10.0).sin() * 5.0 + i as f64 * 0.1).collect();
+    let series_0: Vec<f64> = series_1.iter().enumerate().map(|(i, p)| p * 2.0 + (i as f64 / 3.0).cos() * 2.0).collect();
+    let labels: Vec<u64> = (0..300).map(|i| i * 3600).collect();
+    let prices: PairPrices = PairPrices { series_0, series_1, labels, label_convention: Default::default(), fetch_report: None, events: vec![], source: None };
+
+    let criteria: AnalysisCriteria = AnalysisCriteria {
+      data_criteria: DataCriteria {
+        exchange: Exchange::Binance,
+        asset_0: "BTCUSDT".to_string(),
+        asset_1: "ETHUSDT".to_string(),
+        interval_period: IntervalPeriod::Hour(1, 300),
+        fallback_exchanges: vec![]
+      },
+      stats_criteria: None,
+      backtest_criteria: None,
+      missing_data_policy: MissingDataPolicy::DropBar,
+      asset_type: None
+    };
+    let analysis: PairAnalysis = full_analysis_from_pair_prices(prices, None, None).await.unwrap();
+    let snapshot: Snapshot = Snapshot::new(criteria, analysis);
+
+    let path = std::env::temp_dir().join("zscore_lib_snapshot.json.gz");
+    save(&snapshot, &path).unwrap();
+    let loaded: Snapshot = load(&path).unwrap();
+    assert_eq!(loaded.analysis.prices.labels.len(), 300);
+    assert!(loaded.reproduces().await.unwrap());
+  }
+}
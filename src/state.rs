@@ -0,0 +1,250 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use ts_rs::TS;
+
+use crate::SmartError;
+use crate::account::ExpectedPosition;
+use crate::backtest::models::LongSeries;
+use crate::paper::PaperFill;
+use crate::risk::RiskGuard;
+
+/*
+  Engine State
+  Open positions, the last bar processed per pair, pending signals and the risk guard, saved to disk periodically
+  and restored on restart so a crash neither orphans open positions nor re-enters a pair already held
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub enum SignalAction {
+  Open(LongSeries),
+  Close
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct PendingSignal {
+  pub pair_id: String,
+  pub action: SignalAction,
+  pub zscore: f64,
+  pub bar_label: u64
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct EnginePosition {
+  pub pair_id: String,
+  pub long_series: LongSeries,
+  pub entry_label: u64,
+  pub legs: Vec<ExpectedPosition>, // signed quantity held per symbol
+  pub entry_fills: Vec<PaperFill> // one per leg, used to book pnl on exit
+}
+
+/// Engine State
+/// Everything a monitor or execution loop needs to resume after a restart
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct EngineState {
+  pub positions: Vec<EnginePosition>,
+  pub last_bars: HashMap<String, u64>, // last bar label processed per pair
+  pub pending_signals: Vec<PendingSignal>,
+  pub risk: Option<RiskGuard>,
+  pub saved_at: Option<i64> // unix seconds
+}
+
+impl EngineState {
+
+  /// Record Bar
+  /// Marks a bar as processed, false if it (or a later bar) was already processed so it is skipped after a restart
+  pub fn record_bar(&mut self, pair_id: &str, label: u64) -> bool {
+    match self.last_bars.get(pair_id) {
+      Some(last) if *last >= label => false,
+      _ => {
+        self.last_bars.insert(pair_id.to_string(), label);
+        true
+      }
+    }
+  }
+
+  /// Queue Signal
+  /// Holds a signal until it has been acted on (replacing any earlier signal for the pair)
+  pub fn queue_signal(&mut self, signal: PendingSignal) {
+    self.pending_signals.retain(|s| s.pair_id != signal.pair_id);
+    self.pending_signals.push(signal);
+  }
+
+  /// Take Signal
+  /// Removes and returns the pending signal for a pair
+  pub fn take_signal(&mut self, pair_id: &str) -> Option<PendingSignal> {
+    let idx: usize = self.pending_signals.iter().position(|s| s.pair_id == pair_id)?;
+    Some(self.pending_signals.remove(idx))
+  }
+
+  pub fn position(&self, pair_id: &str) -> Option<&EnginePosition> {
+    self.positions.iter().find(|p| p.pair_id == pair_id)
+  }
+
+  /// Open Position
+  /// Records a filled entry, erroring if the pair is already held so a restored engine never double enters
+  pub fn open_position(&mut self, position: EnginePosition) -> Result<(), SmartError> {
+    if self.position(&position.pair_id).is_some() {
+      return Err(SmartError::RuntimeCheck(format!("Pair {} already has an open position", position.pair_id)));
+    }
+    self.positions.push(position);
+    Ok(())
+  }
+
+  /// Close Position
+  /// Removes and returns a pair's position
+  pub fn close_position(&mut self, pair_id: &str) -> Option<EnginePosition> {
+    let idx: usize = self.positions.iter().position(|p| p.pair_id == pair_id)?;
+    Some(self.positions.remove(idx))
+  }
+
+  /// Expected Positions
+  /// Net quantity per symbol across open positions, for reconciling against the exchange account
+  pub fn expected_positions(&self) -> Vec<ExpectedPosition> {
+    let mut expected: Vec<ExpectedPosition> = vec![];
+    for leg in self.positions.iter().flat_map(|p| p.legs.iter()) {
+      match expected.iter_mut().find(|e| e.symbol == leg.symbol) {
+        Some(existing) => existing.qty += leg.qty,
+        None => expected.push(leg.clone())
+      }
+    }
+    expected
+  }
+
+  /// To JSON / From JSON
+  pub fn to_json(&self) -> Result<String, SmartError> {
+    Ok(serde_json::to_string(self)?)
+  }
+
+  pub fn from_json(json: &str) -> Result<Self, SmartError> {
+    Ok(serde_json::from_str::<Self>(json)?)
+  }
+
+  /// Save
+  /// Writes through a temporary file then renames, so a crash mid write leaves the previous state intact
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn save(&mut self, path: impl AsRef<std::path::Path>, now: i64) -> Result<(), SmartError> {
+    self.saved_at = Some(now);
+    let path: &std::path::Path = path.as_ref();
+    let tmp_path: std::path::PathBuf = path.with_extension("tmp");
+    std::fs::write(&tmp_path, self.to_json()?)?;
+    std::fs::rename(tmp_path, path)?;
+    Ok(())
+  }
+
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, SmartError> {
+    Self::from_json(&std::fs::read_to_string(path)?)
+  }
+
+  /// Restore
+  /// Loads saved state, or starts empty when no state has been saved yet (a corrupt file is an error, not a reset)
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn restore(path: impl AsRef<std::path::Path>) -> Result<Self, SmartError> {
+    match path.as_ref().exists() {
+      true => Self::load(path),
+      false => Ok(Self::default())
+    }
+  }
+}
+
+/// State Saver
+/// Saves engine state at most every interval (call on each loop iteration and after every fill)
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub struct StateSaver {
+  pub path: std::path::PathBuf,
+  pub every_secs: i64,
+  last_saved_at: Option<i64>
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl StateSaver {
+  pub fn new(path: impl Into<std::path::PathBuf>, every_secs: i64) -> Self {
+    Self { path: path.into(), every_secs, last_saved_at: None }
+  }
+
+  /// Maybe Save
+  /// Saves if the interval has passed since the last save, returning whether it saved
+  pub fn maybe_save(&mut self, state: &mut EngineState, now: i64) -> Result<bool, SmartError> {
+    if self.last_saved_at.is_some_and(|last| now - last < self.every_secs) { return Ok(false) }
+    self.save_now(state, now)?;
+    Ok(true)
+  }
+
+  /// Save Now
+  /// Saves regardless of the interval (after fills, where losing the change would orphan a position)
+  pub fn save_now(&mut self, state: &mut EngineState, now: i64) -> Result<(), SmartError> {
+    state.save(&self.path, now)?;
+    self.last_saved_at = Some(now);
+    Ok(())
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::risk::RiskLimits;
+
+  fn position(pair_id: &str, legs: &[(&str, f64)]) -> EnginePosition {
+    EnginePosition {
+      pair_id: pair_id.to_string(),
+      long_series: LongSeries::Series0,
+      entry_label: 3_600,
+      legs: legs.iter().map(|(symbol, qty)| ExpectedPosition { symbol: symbol.to_string(), qty: *qty }).collect(),
+      entry_fills: vec![]
+    }
+  }
+
+  #[tokio::test]
+  async fn it_tracks_bars_signals_and_positions() {
+    let mut state: EngineState = EngineState::default();
+    assert!(state.record_bar("BTC/ETH", 3_600));
+    assert!(!state.record_bar("BTC/ETH", 3_600));
+    assert!(state.record_bar("BTC/ETH", 7_200));
+
+    state.queue_signal(PendingSignal { pair_id: "BTC/ETH".to_string(), action: SignalAction::Open(LongSeries::Series0), zscore: -2.1, bar_label: 7_200 });
+    state.queue_signal(PendingSignal { pair_id: "BTC/ETH".to_string(), action: SignalAction::Close, zscore: 0.1, bar_label: 10_800 });
+    assert_eq!(state.pending_signals.len(), 1);
+    assert_eq!(state.take_signal("BTC/ETH").map(|s| s.action), Some(SignalAction::Close));
+    assert!(state.take_signal("BTC/ETH").is_none());
+
+    state.open_position(position("BTC/ETH", &[("BTCUSDT", 0.1), ("ETHUSDT", -2.0)])).unwrap();
+    state.open_position(position("ETH/SOL", &[("ETHUSDT", 1.0), ("SOLUSDT", -20.0)])).unwrap();
+    assert!(state.open_position(position("BTC/ETH", &[])).is_err());
+    let expected: Vec<ExpectedPosition> = state.expected_positions();
+    assert_eq!(expected.iter().find(|e| e.symbol == "ETHUSDT").map(|e| e.qty), Some(-1.0));
+    assert!(state.close_position("BTC/ETH").is_some());
+    assert_eq!(state.positions.len(), 1);
+  }
+
+  #[tokio::test]
+  async fn it_saves_and_restores_state() {
+    let path: std::path::PathBuf = std::env::temp_dir().join(format!("zscore_engine_state_{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    assert!(EngineState::restore(&path).unwrap().positions.is_empty());
+
+    let mut state: EngineState = EngineState::default();
+    let mut guard: RiskGuard = RiskGuard::new(RiskLimits { max_open_pairs: Some(1), ..Default::default() });
+    guard.open_pair("BTC/ETH", 1_000.0).unwrap();
+    state.risk = Some(guard);
+    state.record_bar("BTC/ETH", 7_200);
+    state.open_position(position("BTC/ETH", &[("BTCUSDT", 0.1)])).unwrap();
+
+    let mut saver: StateSaver = StateSaver::new(&path, 60);
+    assert!(saver.maybe_save(&mut state, 1_000).unwrap());
+    assert!(!saver.maybe_save(&mut state, 1_030).unwrap());
+    assert!(saver.maybe_save(&mut state, 1_060).unwrap());
+
+    // A restarted engine skips the processed bar and cannot enter the held pair again
+    let mut restored: EngineState = EngineState::restore(&path).unwrap();
+    assert_eq!(restored.saved_at, Some(1_060));
+    assert!(!restored.record_bar("BTC/ETH", 7_200));
+    assert!(restored.open_position(position("BTC/ETH", &[])).is_err());
+    assert!(restored.risk.as_ref().unwrap().check_entry("ETH/SOL", 1.0).is_err());
+    std::fs::remove_file(&path).unwrap();
+  }
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use crate::backtest::models::{BacktestCriteria, LongSeries, Relation, SignalTiming, TriggerIndicator};
+use crate::pricing::fees::FeeSide;
+use super::metrics::rolling_zscore;
+
+/*
+  Cross Venue Arbitrage
+  Statistics for the same asset quoted on two venues (series_0 venue 0, series_1 venue 1)
+  The hedge ratio is fixed at one so the spread is the premium / discount of venue 0 in bps
+*/
+
+/// Default cost per leg for arbitrage backtests (taker fees at higher volume tiers)
+pub const ARB_COST_PER_LEG: f64 = 0.0002;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct ArbStats {
+  pub premium_bps: Vec<f64>, // venue 0 over venue 1
+  pub zscore: Vec<f64>,
+  pub mean_premium_bps: f64,
+  pub std_premium_bps: f64,
+  pub latest_premium_bps: f64,
+  pub max_abs_premium_bps: f64
+}
+
+/// Premium Bps
+/// Premium (positive) or discount (negative) of series_0 over series_1 in basis points
+pub fn premium_bps(series_0: &[f64], series_1: &[f64]) -> Result<Vec<f64>, SmartError> {
+
+  // Guard: Ensure length matches
+  if series_0.len() != series_1.len() {
+    return Err(SmartError::RuntimeCheck("Input vectors have different sizes".to_string()));
+  }
+
+  // Guard: Ensure prices are positive
+  if series_0.iter().chain(series_1.iter()).any(|p| *p <= 0.0) {
+    return Err(SmartError::RuntimeCheck("Prices must be positive to calculate premium".to_string()));
+  }
+
+  Ok(series_0.iter().zip(series_1.iter()).map(|(a, b)| (a / b - 1.0) * 10_000.0).collect())
+}
+
+impl ArbStats {
+
+  /// Calculate Arb Stats
+  /// Premium series, its summary and rolling zscore
+  pub fn calculate(series_0: &[f64], series_1: &[f64], zscore_window: usize) -> Result<Self, SmartError> {
+    let premium_bps: Vec<f64> = premium_bps(series_0, series_1)?;
+
+    // Guard: Ensure series is not empty
+    if premium_bps.len() < 2 {
+      return Err(SmartError::RuntimeCheck("At least 2 bars are required for arbitrage stats".to_string()));
+    }
+
+    let n: f64 = premium_bps.len() as f64;
+    let mean_premium_bps: f64 = premium_bps.iter().sum::<f64>() / n;
+    let std_premium_bps: f64 = (premium_bps.iter().map(|p| (p - mean_premium_bps).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+    let latest_premium_bps: f64 = *premium_bps.last().unwrap_or(&0.0);
+    let max_abs_premium_bps: f64 = premium_bps.iter().fold(0.0, |acc: f64, p| acc.max(p.abs()));
+    let zscore: Vec<f64> = rolling_zscore(&premium_bps, zscore_window)?;
+
+    Ok(Self { premium_bps, zscore, mean_premium_bps, std_premium_bps, latest_premium_bps, max_abs_premium_bps })
+  }
+}
+
+/// Arb Backtest Criteria
+/// Zscore criteria with equal leg weighting and arbitrage level costs
+/// Short the premium (sell venue 0, buy venue 1) when rich and the reverse when cheap
+pub fn arb_backtest_criteria(indicator_values: Vec<f64>) -> BacktestCriteria {
+  BacktestCriteria {
+    indicator_values,
+    trigger_indicator: TriggerIndicator::Zscore,
+    relation: Relation::Ignore,
+    cost_per_leg: Some(ARB_COST_PER_LEG),
+    rets_weighting_s0_perc: 0.5,
+    long_series: LongSeries::Series0,
+    stop_loss: 0.0,
+    long_thresh: -2.0,
+    long_close_thresh: 0.0,
+    short_thresh: 2.0,
+    short_close_thresh: 0.0,
+    long_entry: None,
+    short_entry: None,
+    index_beta: None,
+    exchange: None,
+    fee_side: FeeSide::Taker,
+    warmup_bars: None,
+    signal_timing: SignalTiming::SameClose
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_calculates_cross_venue_premium() {
+    let series_1: Vec<f64> = (0..60).map(|i| 100.0 + i as f64 * 0.1).collect();
+    let series_0: Vec<f64> = series_1.iter().enumerate()
+      .map(|(i, p)| p * (1.0 + if i % 2 == 0 { 0.0005 } else { -0.0005 }))
+      .collect();
+
+    let stats: ArbStats = ArbStats::calculate(&series_0, &series_1, 20).unwrap();
+    assert!((stats.premium_bps[0] - 5.0).abs() < 1e-9);
+    assert!((stats.premium_bps[1] + 5.0).abs() < 1e-9);
+    assert!(stats.mean_premium_bps.abs() < 1.0);
+    assert!((stats.max_abs_premium_bps - 5.0).abs() < 1e-9);
+    assert_eq!(stats.zscore.len(), series_0.len());
+
+    assert!(premium_bps(&[1.0], &[0.0]).is_err());
+    assert_eq!(arb_backtest_criteria(vec![]).cost_per_leg, Some(ARB_COST_PER_LEG));
+  }
+}
@@ -0,0 +1,349 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use crate::backtest::models::{SignalSource, SignalThresholds};
+use crate::pricing::models::OhlcvSeries;
+
+/*
+  Indicators
+  Per symbol indicators (realized volatility, ATR, returns skew) calculated on OHLCV series
+  Reusable as ML features and as screener filters (e.g. exclude legs that are too volatile)
+  Spread oscillators are provided as SignalSources so the backtest engine can trade them instead of the zscore
+  Funding rates are likewise provided as a zscore SignalSource for funding harvest strategies
+*/
+
+type MacdLines = (Vec<f64>, Vec<f64>, Vec<f64>);
+
+const OSCILLATOR_NEUTRAL: f64 = 50.0; // warm-up padding for bounded oscillators so no trade is triggered
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct LegIndicators {
+  pub realized_vol: f64, // annualized
+  pub atr_perc: f64, // latest ATR as a fraction of the latest close
+  pub returns_skew: f64
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, TS)]
+#[ts(export)]
+pub struct IndicatorFilter {
+  pub max_realized_vol: Option<f64>,
+  pub max_atr_perc: Option<f64>,
+  pub max_abs_skew: Option<f64>
+}
+
+impl IndicatorFilter {
+
+  /// Passes
+  /// True if the leg is within every configured limit
+  pub fn passes(&self, leg: &LegIndicators) -> bool {
+    self.max_realized_vol.map(|max| leg.realized_vol <= max).unwrap_or(true)
+      && self.max_atr_perc.map(|max| leg.atr_perc <= max).unwrap_or(true)
+      && self.max_abs_skew.map(|max| leg.returns_skew.abs() <= max).unwrap_or(true)
+  }
+}
+
+/// Log Returns
+/// Log return per bar (one shorter than the series)
+fn log_returns(series: &[f64]) -> Vec<f64> {
+  series.windows(2).map(|w| (w[1] / w[0]).ln()).collect()
+}
+
+/// Ensure Window
+/// Window must be at least 2 and leave at least one full window of returns
+fn ensure_window(len: usize, window: usize) -> Result<(), SmartError> {
+  if window < 2 || window >= len {
+    let e: String = format!("Window ({}) must be at least 2 and less than series length ({})", window, len);
+    return Err(SmartError::RuntimeCheck(e));
+  }
+  Ok(())
+}
+
+/// Rolling Realized Vol
+/// Annualized standard deviation of the previous window log returns (padded with 0.0 for the first window bars)
+pub fn rolling_realized_vol(closes: &[f64], window: usize, periods_per_year: f64) -> Result<Vec<f64>, SmartError> {
+  ensure_window(closes.len(), window)?;
+  let rets: Vec<f64> = log_returns(closes);
+  let mut vol: Vec<f64> = vec![0.0; window];
+  for i in window..closes.len() {
+    let slice: &[f64] = &rets[i - window..i];
+    let mean: f64 = slice.iter().sum::<f64>() / window as f64;
+    let var: f64 = slice.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (window - 1) as f64;
+    vol.push(var.sqrt() * periods_per_year.sqrt());
+  }
+  Ok(vol)
+}
+
+/// True Range
+/// Largest of high - low and the gaps from the previous close
+pub fn true_range(ohlcv: &OhlcvSeries) -> Result<Vec<f64>, SmartError> {
+  ohlcv.ensure_aligned()?;
+  Ok((0..ohlcv.labels.len())
+    .map(|i| {
+      let range: f64 = ohlcv.high[i] - ohlcv.low[i];
+      if i == 0 { return range }
+      let prev_close: f64 = ohlcv.close[i - 1];
+      range.max((ohlcv.high[i] - prev_close).abs()).max((ohlcv.low[i] - prev_close).abs())
+    })
+    .collect())
+}
+
+/// Average True Range
+/// Wilder smoothed true range (padded with 0.0 until the first full window)
+pub fn average_true_range(ohlcv: &OhlcvSeries, window: usize) -> Result<Vec<f64>, SmartError> {
+  let tr: Vec<f64> = true_range(ohlcv)?;
+  ensure_window(tr.len(), window)?;
+  let mut atr: Vec<f64> = vec![0.0; window - 1];
+  let mut last: f64 = tr[..window].iter().sum::<f64>() / window as f64;
+  atr.push(last);
+  for value in tr.iter().skip(window) {
+    last = (last * (window - 1) as f64 + value) / window as f64;
+    atr.push(last);
+  }
+  Ok(atr)
+}
+
+/// Rolling Returns Skew
+/// Sample skewness of the previous window log returns (padded with 0.0 for the first window bars)
+pub fn rolling_returns_skew(closes: &[f64], window: usize) -> Result<Vec<f64>, SmartError> {
+  ensure_window(closes.len(), window)?;
+  let rets: Vec<f64> = log_returns(closes);
+  let mut skew: Vec<f64> = vec![0.0; window];
+  for i in window..closes.len() {
+    let slice: &[f64] = &rets[i - window..i];
+    let n: f64 = window as f64;
+    let mean: f64 = slice.iter().sum::<f64>() / n;
+    let m2: f64 = slice.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n;
+    let m3: f64 = slice.iter().map(|r| (r - mean).powi(3)).sum::<f64>() / n;
+    skew.push(if m2 == 0.0 { 0.0 } else { m3 / m2.powf(1.5) });
+  }
+  Ok(skew)
+}
+
+/// Latest Leg Indicators
+/// Most recent realized vol, ATR (as a fraction of close) and returns skew for a symbol
+pub fn latest_leg_indicators(ohlcv: &OhlcvSeries, window: usize, periods_per_year: f64) -> Result<LegIndicators, SmartError> {
+  let vol: Vec<f64> = rolling_realized_vol(&ohlcv.close, window, periods_per_year)?;
+  let atr: Vec<f64> = average_true_range(ohlcv, window)?;
+  let skew: Vec<f64> = rolling_returns_skew(&ohlcv.close, window)?;
+  let close: f64 = ohlcv.close[ohlcv.close.len() - 1];
+  Ok(LegIndicators {
+    realized_vol: vol[vol.len() - 1],
+    atr_perc: if close == 0.0 { 0.0 } else { atr[atr.len() - 1] / close },
+    returns_skew: skew[skew.len() - 1]
+  })
+}
+
+/// EMA
+/// Exponential moving average seeded with the first value
+pub fn ema(series: &[f64], window: usize) -> Vec<f64> {
+  let alpha: f64 = 2.0 / (window as f64 + 1.0);
+  series.iter()
+    .scan(None, |state: &mut Option<f64>, &x| {
+      let next: f64 = state.map(|prev| prev + alpha * (x - prev)).unwrap_or(x);
+      *state = Some(next);
+      Some(next)
+    })
+    .collect()
+}
+
+/// MACD
+/// MACD line (fast EMA - slow EMA), its signal line and histogram (MACD - signal)
+pub fn macd(series: &[f64], fast: usize, slow: usize, signal: usize) -> Result<MacdLines, SmartError> {
+
+  // Guard: Ensure valid windows
+  if fast == 0 || fast >= slow || signal == 0 {
+    let e: String = format!("MACD windows must satisfy 0 < fast ({}) < slow ({}) and signal ({}) > 0", fast, slow, signal);
+    return Err(SmartError::RuntimeCheck(e));
+  }
+
+  let macd_line: Vec<f64> = ema(series, fast).iter().zip(ema(series, slow)).map(|(f, s)| f - s).collect();
+  let signal_line: Vec<f64> = ema(&macd_line, signal);
+  let histogram: Vec<f64> = macd_line.iter().zip(signal_line.iter()).map(|(m, s)| m - s).collect();
+  Ok((macd_line, signal_line, histogram))
+}
+
+/// MACD Signal Source
+/// MACD histogram of the spread as a backtest signal source (thresholds in spread units)
+pub fn macd_signal_source(spread: &[f64], fast: usize, slow: usize, signal: usize, thresholds: SignalThresholds) -> Result<SignalSource, SmartError> {
+  let (_, _, histogram) = macd(spread, fast, slow, signal)?;
+  let name: String = format!("macd_spread_{}_{}_{}", fast, slow, signal);
+  Ok(SignalSource::new(&name, histogram, thresholds))
+}
+
+
+/// RSI
+/// Wilder relative strength index (0 - 100, padded with 50 for the first window bars)
+pub fn rsi(series: &[f64], window: usize) -> Result<Vec<f64>, SmartError> {
+  ensure_window(series.len(), window)?;
+  let changes: Vec<f64> = series.windows(2).map(|w| w[1] - w[0]).collect();
+  let to_rsi = |gain: f64, loss: f64| if loss == 0.0 { if gain == 0.0 { OSCILLATOR_NEUTRAL } else { 100.0 } } else { 100.0 - 100.0 / (1.0 + gain / loss) };
+
+  let mut avg_gain: f64 = changes[..window].iter().map(|c| c.max(0.0)).sum::<f64>() / window as f64;
+  let mut avg_loss: f64 = changes[..window].iter().map(|c| (-c).max(0.0)).sum::<f64>() / window as f64;
+  let mut values: Vec<f64> = vec![OSCILLATOR_NEUTRAL; window];
+  values.push(to_rsi(avg_gain, avg_loss));
+  for change in changes.iter().skip(window) {
+    avg_gain = (avg_gain * (window - 1) as f64 + change.max(0.0)) / window as f64;
+    avg_loss = (avg_loss * (window - 1) as f64 + (-change).max(0.0)) / window as f64;
+    values.push(to_rsi(avg_gain, avg_loss));
+  }
+  Ok(values)
+}
+
+/// Stochastic
+/// %K (position of the value within the window high / low range, 0 - 100) and %D (simple average of %K)
+/// Padded with 50 until each window is full
+pub fn stochastic(series: &[f64], k_window: usize, d_window: usize) -> Result<(Vec<f64>, Vec<f64>), SmartError> {
+  ensure_window(series.len(), k_window)?;
+  if d_window == 0 {
+    return Err(SmartError::RuntimeCheck("Stochastic %D window must be greater than 0".to_string()));
+  }
+
+  let mut k: Vec<f64> = vec![OSCILLATOR_NEUTRAL; k_window - 1];
+  for i in (k_window - 1)..series.len() {
+    let slice: &[f64] = &series[i + 1 - k_window..=i];
+    let low: f64 = slice.iter().cloned().fold(f64::INFINITY, f64::min);
+    let high: f64 = slice.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    k.push(if high == low { OSCILLATOR_NEUTRAL } else { (series[i] - low) / (high - low) * 100.0 });
+  }
+
+  let warm_up: usize = k_window + d_window - 2;
+  let d: Vec<f64> = (0..k.len())
+    .map(|i| if i < warm_up { OSCILLATOR_NEUTRAL } else { k[i + 1 - d_window..=i].iter().sum::<f64>() / d_window as f64 })
+    .collect();
+  Ok((k, d))
+}
+
+/// RSI Signal Source
+/// RSI of the spread, long below 30 and short above 70, closing at 50
+pub fn rsi_signal_source(spread: &[f64], window: usize) -> Result<SignalSource, SmartError> {
+  let thresholds: SignalThresholds = SignalThresholds { long: 30.0, long_close: 50.0, short: 70.0, short_close: 50.0 };
+  Ok(SignalSource::new(&format!("rsi_spread_{}", window), rsi(spread, window)?, thresholds))
+}
+
+/// Stochastic Signal Source
+/// Stochastic %D of the spread, long below 20 and short above 80, closing at 50
+pub fn stochastic_signal_source(spread: &[f64], k_window: usize, d_window: usize) -> Result<SignalSource, SmartError> {
+  let thresholds: SignalThresholds = SignalThresholds { long: 20.0, long_close: 50.0, short: 80.0, short_close: 50.0 };
+  let (_, d) = stochastic(spread, k_window, d_window)?;
+  Ok(SignalSource::new(&format!("stochastic_spread_{}_{}", k_window, d_window), d, thresholds))
+}
+
+/// Funding Zscore
+/// Rolling zscore of funding rates aligned to bars (0.0 for warm-up and for windows with constant funding)
+pub fn funding_zscore(funding_rates: &[f64], window: usize) -> Result<Vec<f64>, SmartError> {
+  ensure_window(funding_rates.len(), window)?;
+  let mut zscore: Vec<f64> = vec![0.0; window];
+  for i in window..funding_rates.len() {
+    let window_data: &[f64] = &funding_rates[i - window..i];
+    let mean: f64 = window_data.iter().sum::<f64>() / window as f64;
+    let std: f64 = (window_data.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (window - 1) as f64).sqrt();
+    zscore.push(if std > 0.0 { (funding_rates[i] - mean) / std } else { 0.0 });
+  }
+  Ok(zscore)
+}
+
+/// Funding Signal Source
+/// Funding rate zscore as a backtest signal source with series_0 as the perpetual
+/// Rich funding shorts the perp (receiving funding) against series_1, cheap funding the reverse, closing at 0
+pub fn funding_signal_source(funding_rates: &[f64], window: usize, entry_zscore: f64) -> Result<SignalSource, SmartError> {
+  let thresholds: SignalThresholds = SignalThresholds { long: -entry_zscore, long_close: 0.0, short: entry_zscore, short_close: 0.0 };
+  Ok(SignalSource::new(&format!("funding_zscore_{}", window), funding_zscore(funding_rates, window)?, thresholds))
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn ohlcv(close: Vec<f64>) -> OhlcvSeries {
+    let len: usize = close.len();
+    OhlcvSeries {
+      labels: (0..len as u64).map(|i| i * 3600).collect(),
+      open: close.clone(),
+      high: close.iter().map(|c| c + 1.0).collect(),
+      low: close.iter().map(|c| c - 1.0).collect(),
+      close,
+      volume: vec![100.0; len],
+      label_convention: Default::default()
+    }
+  }
+
+  #[tokio::test]
+  async fn it_calculates_atr_vol_and_skew() {
+    let close: Vec<f64> = (0..50).map(|i| 100.0 + (i as f64 / 3.0).sin() * 3.0).collect();
+    let series: OhlcvSeries = ohlcv(close.clone());
+
+    let atr: Vec<f64> = average_true_range(&series, 14).unwrap();
+    assert_eq!(atr.len(), 50);
+    assert_eq!(atr[12], 0.0);
+    assert!(atr[13] >= 2.0 && atr[49] >= 2.0);
+
+    let vol: Vec<f64> = rolling_realized_vol(&close, 20, 365.0).unwrap();
+    assert_eq!(vol.len(), 50);
+    assert!(vol[19] == 0.0 && vol[20] > 0.0);
+
+    // Single crash return produces negative skew
+    let mut crash: Vec<f64> = vec![100.0; 30];
+    for (i, c) in crash.iter_mut().enumerate() { *c += (i % 2) as f64 * 0.5; }
+    crash[29] = 80.0;
+    let skew: Vec<f64> = rolling_returns_skew(&[crash.clone(), vec![80.0]].concat(), 20).unwrap();
+    assert!(skew[30] < -1.0);
+  }
+
+  #[tokio::test]
+  async fn it_filters_legs_by_indicators() {
+    let close: Vec<f64> = (0..50).map(|i| 100.0 + (i as f64 / 3.0).sin() * 3.0).collect();
+    let leg: LegIndicators = latest_leg_indicators(&ohlcv(close), 14, 365.0).unwrap();
+    assert!(IndicatorFilter::default().passes(&leg));
+    assert!(!IndicatorFilter { max_realized_vol: Some(leg.realized_vol / 2.0), ..Default::default() }.passes(&leg));
+    assert!(average_true_range(&ohlcv(vec![1.0, 2.0]), 14).is_err());
+  }
+
+  #[tokio::test]
+  async fn it_builds_macd_signal_source() {
+    let spread: Vec<f64> = (0..100).map(|i| (i as f64 / 8.0).sin()).collect();
+    let thresholds: SignalThresholds = SignalThresholds { long: -0.05, long_close: 0.0, short: 0.05, short_close: 0.0 };
+    let source: SignalSource = macd_signal_source(&spread, 12, 26, 9, thresholds).unwrap();
+    assert_eq!(source.name, "macd_spread_12_26_9");
+    assert_eq!(source.values.len(), 100);
+    assert_eq!(source.values[0], 0.0);
+    assert!(source.values.iter().any(|v| *v > 0.05) && source.values.iter().any(|v| *v < -0.05));
+    assert!(macd(&spread, 26, 12, 9).is_err());
+  }
+
+  #[tokio::test]
+  async fn it_builds_bounded_spread_oscillators() {
+    let spread: Vec<f64> = (0..120).map(|i| (i as f64 / 6.0).sin() * 2.0).collect();
+
+    let rsi_source: SignalSource = rsi_signal_source(&spread, 14).unwrap();
+    assert_eq!(rsi_source.values.len(), 120);
+    assert_eq!(rsi_source.values[13], 50.0);
+    assert!(rsi_source.values.iter().all(|v| (0.0..=100.0).contains(v)));
+    assert!(rsi_source.values.iter().any(|v| *v < 30.0) && rsi_source.values.iter().any(|v| *v > 70.0));
+
+    let (k, d) = stochastic(&spread, 14, 3).unwrap();
+    assert_eq!((k.len(), d.len()), (120, 120));
+    assert!(k.contains(&100.0) && k.contains(&0.0));
+    assert!((d[20] - (k[18] + k[19] + k[20]) / 3.0).abs() < 1e-9);
+
+    let stoch_source: SignalSource = stochastic_signal_source(&spread, 14, 3).unwrap();
+    assert_eq!(stoch_source.thresholds.short, 80.0);
+  }
+
+  #[tokio::test]
+  async fn it_builds_funding_signal_source() {
+    let mut rates: Vec<f64> = (0..40).map(|i| 0.0001 * (i % 2) as f64).collect();
+    rates[30] = 0.002;
+
+    let source: SignalSource = funding_signal_source(&rates, 10, 2.0).unwrap();
+    assert_eq!(source.name, "funding_zscore_10");
+    assert_eq!(source.values.len(), 40);
+    assert!(source.values[..10].iter().all(|z| *z == 0.0));
+    assert!(source.values[30] > source.thresholds.short);
+
+    assert_eq!(funding_zscore(&[0.0001; 20], 5).unwrap(), vec![0.0; 20]);
+  }
+}
@@ -0,0 +1,272 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use ts_rs::TS;
+
+use crate::SmartError;
+use crate::backtest::utils::round_float;
+use super::metrics::cointegration_test_eg;
+use super::models::Coint;
+
+type CointCache = HashMap<(u64, u64), Coint>;
+static COINT_CACHE: RwLock<Option<CointCache>> = RwLock::new(None);
+const COINT_CACHE_MAX_ENTRIES: usize = 10_000;
+
+/*
+  Correlation Matrix
+  Pairwise Pearson correlation for N symbols computed once and shared by the screener, clustering and PCA
+  Series are standardized up front so each pair is a single dot product (pairs computed in parallel)
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct CorrelationMatrix {
+  pub symbols: Vec<String>,
+  pub values: Vec<Vec<f64>>
+}
+
+impl CorrelationMatrix {
+
+  /// With Symbols
+  /// Names the rows and columns (defaults to the series index)
+  pub fn with_symbols(mut self, symbols: &[&str]) -> Result<Self, SmartError> {
+    if symbols.len() != self.values.len() {
+      return Err(SmartError::RuntimeCheck("Symbols length does not match number of series".to_string()));
+    }
+    self.symbols = symbols.iter().map(|s| s.to_string()).collect();
+    Ok(self)
+  }
+
+  /// Index Of
+  /// Row / column index of a symbol
+  pub fn index_of(&self, symbol: &str) -> Option<usize> {
+    self.symbols.iter().position(|s| s == symbol)
+  }
+
+  /// Get
+  /// Correlation between two symbols
+  pub fn get(&self, symbol_0: &str, symbol_1: &str) -> Option<f64> {
+    Some(self.values[self.index_of(symbol_0)?][self.index_of(symbol_1)?])
+  }
+}
+
+/// Standardize
+/// Centers and scales a series so the dot product of two standardized series is their correlation
+/// Constant series standardize to zeros (zero correlation with everything else)
+fn standardize(series: &[f64]) -> Vec<f64> {
+  let n: f64 = series.len() as f64;
+  let mean: f64 = series.iter().sum::<f64>() / n;
+  let norm: f64 = series.iter().map(|x| (x - mean).powi(2)).sum::<f64>().sqrt();
+  if norm == 0.0 { return vec![0.0; series.len()] }
+  series.iter().map(|x| (x - mean) / norm).collect()
+}
+
+/// Matrix From Standardized
+/// Fills the upper triangle in parallel and mirrors it
+fn matrix_from_standardized(standardized: &[Vec<f64>]) -> Vec<Vec<f64>> {
+  let n: usize = standardized.len();
+  let pairs: Vec<(usize, usize)> = (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j))).collect();
+  let corrs: Vec<f64> = pairs.par_iter()
+    .map(|(i, j)| standardized[*i].iter().zip(standardized[*j].iter()).map(|(a, b)| a * b).sum::<f64>())
+    .collect();
+
+  let mut values: Vec<Vec<f64>> = vec![vec![0.0; n]; n];
+  for (i, row) in values.iter_mut().enumerate() { row[i] = 1.0; }
+  for ((i, j), corr) in pairs.into_iter().zip(corrs) {
+    values[i][j] = corr;
+    values[j][i] = corr;
+  }
+  values
+}
+
+/// Validate Series
+/// Ensures at least one series is supplied and all lengths match
+fn validate_series(series: &[Vec<f64>]) -> Result<usize, SmartError> {
+  let Some(first) = series.first() else {
+    return Err(SmartError::RuntimeCheck("No series supplied".to_string()))
+  };
+  if series.iter().any(|s| s.len() != first.len()) {
+    return Err(SmartError::RuntimeCheck("Input vectors have different sizes".to_string()));
+  }
+  if first.len() < 2 {
+    return Err(SmartError::RuntimeCheck("Series length must be greater than 1".to_string()));
+  }
+  Ok(first.len())
+}
+
+/// Correlation Matrix
+/// Symmetric matrix of pairwise correlations (rows and columns follow the series order)
+pub fn correlation_matrix(series: &[Vec<f64>]) -> Result<CorrelationMatrix, SmartError> {
+  validate_series(series)?;
+  let standardized: Vec<Vec<f64>> = series.par_iter().map(|s| standardize(s)).collect();
+  Ok(CorrelationMatrix {
+    symbols: (0..series.len()).map(|i| i.to_string()).collect(),
+    values: matrix_from_standardized(&standardized)
+  })
+}
+
+/// Rolling Correlation Matrix
+/// Correlation matrix for each window ending at every bar from window - 1 onwards
+pub fn rolling_correlation_matrix(series: &[Vec<f64>], window: usize) -> Result<Vec<CorrelationMatrix>, SmartError> {
+  let len: usize = validate_series(series)?;
+
+  // Guard: Ensure correct window size
+  if window < 2 || window > len {
+    return Err(SmartError::RuntimeCheck("Window size must be between 2 and the series length".to_string()));
+  }
+
+  let matrices: Vec<CorrelationMatrix> = (window..=len).into_par_iter()
+    .map(|end| {
+      let standardized: Vec<Vec<f64>> = series.iter().map(|s| standardize(&s[end - window..end])).collect();
+      CorrelationMatrix {
+        symbols: (0..series.len()).map(|i| i.to_string()).collect(),
+        values: matrix_from_standardized(&standardized)
+      }
+    })
+    .collect();
+
+  Ok(matrices)
+}
+
+/*
+  Cointegration Matrix
+  Engle Granger results for every ordered pair of a symbol universe (row regressed on column)
+  Results are cached by series fingerprint so repeated screens only test new or changed series
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, TS)]
+#[ts(export)]
+pub enum CointMetric {
+  TestStatistic,
+  PValue
+}
+
+/// Cointegration Heatmap
+/// Compact row major matrix (n * n values rounded to 4dp, diagonal is zero) for UI heatmaps
+#[derive(Debug, Deserialize, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct CointHeatmap {
+  pub symbols: Vec<String>,
+  pub metric: CointMetric,
+  pub values: Vec<f64>
+}
+
+impl CointHeatmap {
+
+  /// Get
+  /// Metric for the row symbol regressed on the column symbol
+  pub fn get(&self, symbol_0: &str, symbol_1: &str) -> Option<f64> {
+    let i: usize = self.symbols.iter().position(|s| s == symbol_0)?;
+    let j: usize = self.symbols.iter().position(|s| s == symbol_1)?;
+    Some(self.values[i * self.symbols.len() + j])
+  }
+}
+
+/// Series Fingerprint
+/// FNV-1a hash over the bits of every value
+fn series_fingerprint(series: &[f64]) -> u64 {
+  series.iter().fold(0xcbf29ce484222325, |hash, x| {
+    x.to_bits().to_le_bytes().iter().fold(hash, |h, b| (h ^ *b as u64).wrapping_mul(0x100000001b3))
+  })
+}
+
+/// Clear Cointegration Cache
+/// Drops all cached pair results
+pub fn clear_coint_cache() {
+  if let Ok(mut cache) = COINT_CACHE.write() {
+    *cache = None;
+  }
+}
+
+/// Cointegration Matrix
+/// Runs the Engle Granger test for each ordered pair in parallel, reusing cached results
+pub fn cointegration_matrix(symbols: &[&str], series: &[Vec<f64>], metric: CointMetric) -> Result<CointHeatmap, SmartError> {
+  validate_series(series)?;
+
+  // Guard: Ensure symbols match series
+  if symbols.len() != series.len() {
+    return Err(SmartError::RuntimeCheck("Symbols length does not match number of series".to_string()));
+  }
+
+  let n: usize = series.len();
+  let fingerprints: Vec<u64> = series.par_iter().map(|s| series_fingerprint(s)).collect();
+  let pairs: Vec<(usize, usize)> = (0..n).flat_map(|i| (0..n).filter(move |j| *j != i).map(move |j| (i, j))).collect();
+
+  let cached: CointCache = COINT_CACHE.read().ok().and_then(|c| c.clone()).unwrap_or_default();
+  let results: Vec<((u64, u64), Coint)> = pairs.par_iter()
+    .map(|(i, j)| {
+      let key: (u64, u64) = (fingerprints[*i], fingerprints[*j]);
+      match cached.get(&key) {
+        Some(coint) => Ok((key, coint.clone())),
+        None => Ok((key, cointegration_test_eg(&series[*i], &series[*j])?))
+      }
+    })
+    .collect::<Result<Vec<_>, SmartError>>()?;
+
+  let mut values: Vec<f64> = vec![0.0; n * n];
+  for ((i, j), (_, coint)) in pairs.iter().zip(results.iter()) {
+    let value: f64 = match metric {
+      CointMetric::TestStatistic => coint.test_statistic,
+      CointMetric::PValue => coint.p_value
+    };
+    values[i * n + j] = round_float(value, 4);
+  }
+
+  if let Ok(mut cache) = COINT_CACHE.write() {
+    let store: &mut CointCache = cache.get_or_insert_with(HashMap::new);
+    if store.len() + results.len() > COINT_CACHE_MAX_ENTRIES { store.clear(); }
+    store.extend(results);
+  }
+
+  Ok(CointHeatmap { symbols: symbols.iter().map(|s| s.to_string()).collect(), metric, values })
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_computes_symmetric_correlation_matrix() {
+    let series: Vec<Vec<f64>> = vec![
+      vec![1.0, 2.0, 3.0, 4.0, 5.0],
+      vec![2.0, 4.0, 6.0, 8.0, 10.0],
+      vec![5.0, 4.0, 3.0, 2.0, 1.0],
+      vec![1.0, 3.0, 2.0, 5.0, 4.0]
+    ];
+    let matrix: CorrelationMatrix = correlation_matrix(&series).unwrap()
+      .with_symbols(&["BTC", "ETH", "SOL", "XRP"]).unwrap();
+
+    assert!((matrix.get("BTC", "ETH").unwrap() - 1.0).abs() < 1e-12);
+    assert!((matrix.get("BTC", "SOL").unwrap() + 1.0).abs() < 1e-12);
+    assert!((matrix.get("BTC", "XRP").unwrap() - 0.8).abs() < 1e-12);
+    assert_eq!(matrix.get("XRP", "BTC"), matrix.get("BTC", "XRP"));
+    assert_eq!(matrix.index_of("SOL"), Some(2));
+  }
+
+  #[tokio::test]
+  async fn it_computes_rolling_correlation_matrices() {
+    let series: Vec<Vec<f64>> = vec![vec![1.0, 2.0, 3.0, 2.0, 1.0], vec![1.0, 2.0, 3.0, 4.0, 5.0]];
+    let matrices: Vec<CorrelationMatrix> = rolling_correlation_matrix(&series, 3).unwrap();
+    assert_eq!(matrices.len(), 3);
+    assert!((matrices[0].values[0][1] - 1.0).abs() < 1e-12);
+    assert!(matrices[2].values[0][1] < 0.0);
+  }
+
+  #[tokio::test]
+  async fn it_builds_cointegration_heatmap() {
+    let base: Vec<f64> = (0..200).map(|i| 100.0 + (i as f64 / 7.0).sin() * 10.0 + i as f64 * 0.2).collect();
+    let paired: Vec<f64> = base.iter().enumerate().map(|(i, p)| p * 1.5 + (i as f64 / 2.0).cos()).collect();
+    let trend: Vec<f64> = (0..200).map(|i| 50.0 + (i as f64).powf(1.3)).collect();
+    let series: Vec<Vec<f64>> = vec![base, paired, trend];
+
+    let heatmap: CointHeatmap = cointegration_matrix(&["A", "B", "C"], &series, CointMetric::PValue).unwrap();
+    assert_eq!(heatmap.values.len(), 9);
+    assert_eq!(heatmap.get("A", "A"), Some(0.0));
+    assert!(heatmap.get("A", "B").unwrap() < 0.05);
+
+    let again: CointHeatmap = cointegration_matrix(&["A", "B", "C"], &series, CointMetric::PValue).unwrap();
+    assert_eq!(again.values, heatmap.values);
+  }
+}
@@ -1,3 +1,4 @@
+use rayon::prelude::*;
 use crate::SmartError;
 use super::mackinnon::{critical_values_mackinnon_cointegration, p_value_mackinnon_cointegration};
 use super::models::Coint;
@@ -26,6 +27,36 @@ pub fn half_life_mean_reversion(series: &Vec<f64>) -> Result<f64, SmartError> {
   Ok(half_life)
 }
 
+/// Hurst Exponent
+/// Estimated from the scaling of lagged differences (H < 0.5 mean reverting, 0.5 random walk, > 0.5 trending)
+pub fn hurst_exponent(series: &[f64], max_lag: usize) -> Result<f64, SmartError> {
+
+  // Guard: Ensure enough data for the lags
+  if max_lag < 3 || series.len() <= max_lag * 2 {
+    return Err(SmartError::RuntimeCheck("Series too short for Hurst exponent lags".to_string()));
+  }
+
+  let mut log_lags: Vec<f64> = vec![];
+  let mut log_taus: Vec<f64> = vec![];
+  for lag in 2..=max_lag {
+    let diffs: Vec<f64> = series[lag..].iter().zip(series.iter()).map(|(a, b)| a - b).collect();
+    let mean: f64 = diffs.iter().sum::<f64>() / diffs.len() as f64;
+    let std: f64 = (diffs.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / diffs.len() as f64).sqrt();
+    if std > 0.0 {
+      log_lags.push((lag as f64).ln());
+      log_taus.push(std.ln());
+    }
+  }
+
+  // Guard: Ensure series is not constant
+  if log_lags.len() < 2 {
+    return Err(SmartError::RuntimeCheck("Cannot calculate Hurst exponent for a constant series".to_string()));
+  }
+
+  let ((_, hurst), _) = simple_linear_regression(&log_lags, &log_taus)?;
+  Ok(hurst)
+}
+
 /// Calculate Static Hedge Ratio
 pub fn intercept_hedge_ratio_static(series_0: &Vec<f64>, series_1: &Vec<f64>) -> Result<(f64, f64), SmartError> {
   let ((intercept, hedge_ratio), _) = simple_linear_regression(&series_1, &series_0)?;
@@ -107,6 +138,27 @@ pub fn rolling_zscore(series: &Vec<f64>, window: usize) -> Result<Vec<f64>, Smar
   Ok(z_scores)
 }
 
+/// Spread Leg Contributions
+/// Attributes each bar's spread move (spread = series_0 - hedge_ratio * series_1) to leg 0 and leg 1
+/// Leg 0 contribution is the change in series_0, leg 1 contribution is minus the hedged change in series_1
+pub fn spread_leg_contributions(series_0: &[f64], series_1: &[f64], hedge_ratio: f64) -> Result<(Vec<f64>, Vec<f64>), SmartError> {
+
+  // Guard: Ensure length matches
+  if series_0.len() != series_1.len() {
+    return Err(SmartError::RuntimeCheck("Input vectors have different sizes".to_string()));
+  }
+
+  if series_0.is_empty() { return Ok((vec![], vec![])) }
+
+  let mut leg_0: Vec<f64> = vec![0.0];
+  let mut leg_1: Vec<f64> = vec![0.0];
+  for i in 1..series_0.len() {
+    leg_0.push(series_0[i] - series_0[i - 1]);
+    leg_1.push(-hedge_ratio * (series_1[i] - series_1[i - 1]));
+  }
+  Ok((leg_0, leg_1))
+}
+
 /// Correlation
 /// Using Pearsons Correlation Coefficient
 pub fn pearson_correlation_coefficient(x: &Vec<f64>, y: &Vec<f64>) -> Result<f64, SmartError> {
@@ -196,15 +248,17 @@ pub fn rolling_cointegration(series_1: &Vec<f64>, series_2: &Vec<f64>, window: u
     return Err(Box::new(std::io::Error::new(std::io::ErrorKind::Other, "Window size is greater than vector length")));
   }
 
-  // Calculate rolling cointegration for each window
-  for i in window..series_1.len() {
-    let series_1_i: &Vec<f64> = &series_1[i-window..i].to_vec();
-    let series_2_i: &Vec<f64> = &series_2[i-window..i].to_vec();
-    let coint: Coint = cointegration_test_eg(series_1_i, series_2_i)?;
-    let t_stat: f64 = coint.test_statistic;
-    let c_value: f64 = coint.critical_values.1 as f64;
-    let t_distance: f64 = -(t_stat - c_value);
-    t_distances.push(t_distance);
-  }
+  // Calculate rolling cointegration for each window (in parallel, web workers on threaded wasm builds)
+  let rolled: Vec<f64> = (window..series_1.len()).into_par_iter()
+    .map(|i| {
+      let series_1_i: &Vec<f64> = &series_1[i-window..i].to_vec();
+      let series_2_i: &Vec<f64> = &series_2[i-window..i].to_vec();
+      let coint: Coint = cointegration_test_eg(series_1_i, series_2_i)?;
+      let t_stat: f64 = coint.test_statistic;
+      let c_value: f64 = coint.critical_values.1;
+      Ok(-(t_stat - c_value))
+    })
+    .collect::<Result<Vec<f64>, SmartError>>()?;
+  t_distances.extend(rolled);
   Ok(t_distances)
 }
@@ -1,5 +1,10 @@
+pub mod arbitrage;
+pub mod indicators;
 pub mod mackinnon;
+pub mod matrix;
 pub mod metrics;
+pub mod preprocess;
 pub mod models;
 pub mod regression;
+pub mod score;
 pub mod statistics;
\ No newline at end of file
@@ -1,4 +1,5 @@
 use crate::SmartError;
+use crate::presets::DEFAULT_TRADING_DAYS;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
@@ -14,7 +15,7 @@ use super::metrics::{
 
 use super::statistics::calculate_relaitonship;
 
-#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, TS)]
 #[ts(export)]
 pub enum SpreadType {
   Static,
@@ -65,6 +66,19 @@ impl Statistics {
     z_score_w: usize, 
     roll_w: usize,
   ) -> Result<Self, SmartError> {
+    Self::calculate_statistics_annualized(series_0, series_1, calc_type, z_score_w, roll_w, DEFAULT_TRADING_DAYS)
+  }
+
+  /// Calculate Statistics Annualized
+  /// As calculate_statistics with volatilities annualized over the given bars per year
+  pub fn calculate_statistics_annualized(
+    series_0: &Vec<f64>, 
+    series_1: &Vec<f64>, 
+    calc_type: SpreadType, 
+    z_score_w: usize, 
+    roll_w: usize,
+    periods_per_year: usize
+  ) -> Result<Self, SmartError> {
 
     // Guard: Ensure lengh > 0
     if series_0.len() == 0 { return Err(SmartError::RuntimeCheck("Series_0 length zero".to_string())) }
@@ -123,8 +137,7 @@ impl Statistics {
     };
 
     // Relationship
-    let trading_days: usize = 252;
-    let relationship: Relationship = calculate_relaitonship(&series_0, &series_1, trading_days).map_err(|e| SmartError::RuntimeCheck(e.to_string()))?;
+    let relationship: Relationship = calculate_relaitonship(series_0, series_1, periods_per_year).map_err(|e| SmartError::RuntimeCheck(e.to_string()))?;
 
     // Consolidate Result
     let stats: Self = Self {
@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use crate::pricing::models::PairPrices;
+
+/*
+  Outlier Preprocessing
+  Winsorizes returns and clamps single bar wicks before stats and backtests are calculated
+  Single flash crash candles otherwise dominate Kalman and zscore estimates
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, TS)]
+#[ts(export)]
+pub struct PreprocessConfig {
+  pub winsorize_percentile: Option<f64>, // e.g. 0.01 clips log returns below the 1st and above the 99th percentile
+  pub wick_threshold: Option<f64> // log move away from both neighbours above which a single bar is treated as a wick
+}
+
+/// Percentile
+/// Linearly interpolated percentile of sorted values
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+  let pos: f64 = p * (sorted.len() - 1) as f64;
+  let lower: usize = pos.floor() as usize;
+  let upper: usize = pos.ceil() as usize;
+  sorted[lower] + (sorted[upper] - sorted[lower]) * (pos - lower as f64)
+}
+
+/// Ensure Positive
+/// Log returns require strictly positive prices
+fn ensure_positive(series: &[f64]) -> Result<(), SmartError> {
+  if let Some(idx) = series.iter().position(|p| !p.is_finite() || *p <= 0.0) {
+    return Err(SmartError::RuntimeCheck(format!("Price at index {} must be a positive number to preprocess", idx)));
+  }
+  Ok(())
+}
+
+/// Winsorize Returns
+/// Clips log returns to the given lower / upper percentile and rebuilds prices from the first price
+pub fn winsorize_returns(series: &[f64], percentile_clip: f64) -> Result<Vec<f64>, SmartError> {
+
+  // Guard: Ensure percentile in range
+  if !(0.0..0.5).contains(&percentile_clip) {
+    return Err(SmartError::RuntimeCheck(format!("Winsorize percentile ({}) must be between 0 and 0.5", percentile_clip)));
+  }
+  ensure_positive(series)?;
+  if series.len() < 3 { return Ok(series.to_vec()) }
+
+  let log_rets: Vec<f64> = series.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+  let mut sorted: Vec<f64> = log_rets.clone();
+  sorted.sort_by(|a, b| a.total_cmp(b));
+  let lower: f64 = percentile(&sorted, percentile_clip);
+  let upper: f64 = percentile(&sorted, 1.0 - percentile_clip);
+
+  let mut winsorized: Vec<f64> = vec![series[0]];
+  for r in log_rets {
+    let last: f64 = winsorized[winsorized.len() - 1];
+    winsorized.push(last * r.clamp(lower, upper).exp());
+  }
+  Ok(winsorized)
+}
+
+/// Clamp Wicks
+/// Replaces bars that jump away from both neighbours in the same direction by more than the threshold
+/// with the geometric mid of the neighbours
+pub fn clamp_wicks(series: &[f64], wick_threshold: f64) -> Result<Vec<f64>, SmartError> {
+  ensure_positive(series)?;
+  let mut clamped: Vec<f64> = series.to_vec();
+  for i in 1..series.len().saturating_sub(1) {
+    let from_prev: f64 = (series[i] / series[i - 1]).ln();
+    let from_next: f64 = (series[i] / series[i + 1]).ln();
+    let is_wick: bool = (from_prev > wick_threshold && from_next > wick_threshold)
+      || (from_prev < -wick_threshold && from_next < -wick_threshold);
+    if is_wick { clamped[i] = (series[i - 1] * series[i + 1]).sqrt(); }
+  }
+  Ok(clamped)
+}
+
+/// Preprocess Series
+/// Clamps wicks then winsorizes returns as configured
+pub fn preprocess_series(series: &[f64], config: &PreprocessConfig) -> Result<Vec<f64>, SmartError> {
+  let mut cleaned: Vec<f64> = series.to_vec();
+  if let Some(threshold) = config.wick_threshold { cleaned = clamp_wicks(&cleaned, threshold)?; }
+  if let Some(p) = config.winsorize_percentile { cleaned = winsorize_returns(&cleaned, p)?; }
+  Ok(cleaned)
+}
+
+impl PairPrices {
+
+  /// Preprocessed
+  /// Copy of the prices with both series cleaned (the original prices are left untouched)
+  pub fn preprocessed(&self, config: &PreprocessConfig) -> Result<Self, SmartError> {
+    Ok(Self {
+      series_0: preprocess_series(&self.series_0, config)?,
+      series_1: preprocess_series(&self.series_1, config)?,
+      ..self.clone()
+    })
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_clamps_flash_crash_wick() {
+    let series: Vec<f64> = vec![100.0, 101.0, 60.0, 102.0, 103.0];
+    let clamped: Vec<f64> = clamp_wicks(&series, 0.1).unwrap();
+    assert!((clamped[2] - (101.0_f64 * 102.0).sqrt()).abs() < 1e-9);
+    assert_eq!(clamped[3], 102.0);
+
+    // Genuine level shift is not a wick
+    let shift: Vec<f64> = vec![100.0, 101.0, 60.0, 61.0, 62.0];
+    assert_eq!(clamp_wicks(&shift, 0.1).unwrap(), shift);
+  }
+
+  #[tokio::test]
+  async fn it_winsorizes_returns() {
+    let mut series: Vec<f64> = (0..100).map(|i| 100.0 + (i as f64 / 5.0).sin()).collect();
+    series[50] = 50.0;
+    let config: PreprocessConfig = PreprocessConfig { winsorize_percentile: Some(0.02), wick_threshold: None };
+    let cleaned: Vec<f64> = preprocess_series(&series, &config).unwrap();
+    assert_eq!(cleaned.len(), 100);
+    assert_eq!(cleaned[0], series[0]);
+    assert!((cleaned[50] / cleaned[49]).ln().abs() < 0.05);
+    assert!(winsorize_returns(&series, 0.6).is_err());
+    assert!(winsorize_returns(&[1.0, -1.0, 2.0], 0.01).is_err());
+  }
+}
@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use crate::backtest::evaluation::BacktestMetrics;
+use crate::backtest::utils::round_float;
+use super::metrics::hurst_exponent;
+use super::models::Statistics;
+
+/*
+  Pair Score
+  Single comparable health score (0 - 100) built from weighted sub scores (each 0 - 1)
+  Allows screener results to be ranked across different pairs and exchanges
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct ScoreWeights {
+  pub coint: f64,
+  pub half_life: f64,
+  pub hurst: f64,
+  pub corr_stability: f64,
+  pub sharpe: f64
+}
+
+impl Default for ScoreWeights {
+  fn default() -> Self {
+    Self { coint: 0.3, half_life: 0.15, hurst: 0.15, corr_stability: 0.15, sharpe: 0.25 }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct ScoreConfig {
+  pub weights: ScoreWeights,
+  pub half_life_min: f64, // bars, faster reversion than this is likely noise
+  pub half_life_max: f64, // bars, slower reversion than this ties up capital
+  pub hurst_max_lag: usize,
+  pub sharpe_cap: f64 // sharpe at or above this scores 1
+}
+
+impl Default for ScoreConfig {
+  fn default() -> Self {
+    Self { weights: ScoreWeights::default(), half_life_min: 2.0, half_life_max: 50.0, hurst_max_lag: 20, sharpe_cap: 3.0 }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct SubScores {
+  pub coint: f64,
+  pub half_life: f64,
+  pub hurst: f64,
+  pub corr_stability: f64,
+  pub sharpe: Option<f64> // None when no backtest supplied (weight excluded)
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct PairScore {
+  pub score: f64,
+  pub hurst: f64,
+  pub sub_scores: SubScores
+}
+
+/// Half Life Score
+/// 1 within the target range, decaying proportionally outside it
+fn half_life_score(half_life: f64, min: f64, max: f64) -> f64 {
+  if !half_life.is_finite() || half_life <= 0.0 { return 0.0 }
+  if half_life < min { return half_life / min }
+  if half_life > max { return max / half_life }
+  1.0
+}
+
+/// Corr Stability Score
+/// 1 for a constant rolling correlation, 0 once its standard deviation reaches 0.5
+fn corr_stability_score(corr_roll: &[f64]) -> f64 {
+  let values: Vec<f64> = corr_roll.iter().copied().skip_while(|c| *c == 0.0).filter(|c| c.is_finite()).collect();
+  if values.len() < 2 { return 0.0 }
+  let mean: f64 = values.iter().sum::<f64>() / values.len() as f64;
+  let std: f64 = (values.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / values.len() as f64).sqrt();
+  (1.0 - std * 2.0).clamp(0.0, 1.0)
+}
+
+impl PairScore {
+
+  /// Calculate
+  /// Scores a pair from its statistics and (optionally) its backtest metrics
+  pub fn calculate(stats: &Statistics, bt_metrics: Option<&BacktestMetrics>, config: &ScoreConfig) -> Result<Self, SmartError> {
+    let hurst: f64 = hurst_exponent(&stats.spread, config.hurst_max_lag)?;
+
+    let sub_scores: SubScores = SubScores {
+      coint: (1.0 - stats.coint.p_value).clamp(0.0, 1.0),
+      half_life: half_life_score(stats.half_life, config.half_life_min, config.half_life_max),
+      hurst: ((0.5 - hurst) / 0.5).clamp(0.0, 1.0),
+      corr_stability: corr_stability_score(&stats.corr_roll),
+      sharpe: bt_metrics.map(|m| (m.sharpe_ratio / config.sharpe_cap).clamp(0.0, 1.0))
+    };
+
+    let w: &ScoreWeights = &config.weights;
+    let mut weighted: Vec<(f64, f64)> = vec![
+      (w.coint, sub_scores.coint),
+      (w.half_life, sub_scores.half_life),
+      (w.hurst, sub_scores.hurst),
+      (w.corr_stability, sub_scores.corr_stability)
+    ];
+    if let Some(sharpe) = sub_scores.sharpe { weighted.push((w.sharpe, sharpe)); }
+
+    // Guard: Ensure weights are usable
+    let total_weight: f64 = weighted.iter().map(|(w, _)| w).sum();
+    if total_weight <= 0.0 || weighted.iter().any(|(w, _)| *w < 0.0) {
+      return Err(SmartError::RuntimeCheck("Score weights must be non negative with a positive sum".to_string()));
+    }
+
+    let score: f64 = weighted.iter().map(|(w, s)| w * s).sum::<f64>() / total_weight * 100.0;
+    Ok(Self { score: round_float(score, 2), hurst: round_float(hurst, 4), sub_scores })
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::stats::models::SpreadType;
+
+  #[tokio::test]
+  async fn it_scores_mean_reverting_pair_above_trending_pair() {
+    let series_1: Vec<f64> = (0..300).map(|i| 100.0 + (i as f64 / 10.0).sin() * 5.0 + i as f64 * 0.1).collect();
+    let reverting: Vec<f64> = series_1.iter().enumerate().map(|(i, p)| p * 2.0 + (i as f64 / 3.0).cos() * 2.0).collect();
+    let trending: Vec<f64> = series_1.iter().enumerate().map(|(i, p)| p * 2.0 + (i as f64).powf(1.4)).collect();
+
+    let config: ScoreConfig = ScoreConfig::default();
+    let stats_0: Statistics = Statistics::calculate_statistics(&reverting, &series_1, SpreadType::Static, 35, 90).unwrap();
+    let stats_1: Statistics = Statistics::calculate_statistics(&trending, &series_1, SpreadType::Static, 35, 90).unwrap();
+    let score_0: PairScore = PairScore::calculate(&stats_0, None, &config).unwrap();
+    let score_1: PairScore = PairScore::calculate(&stats_1, None, &config).unwrap();
+
+    assert!(score_0.score > score_1.score);
+    assert!(score_0.hurst < 0.5);
+    assert!(score_0.sub_scores.sharpe.is_none());
+  }
+}
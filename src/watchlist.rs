@@ -0,0 +1,286 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use crate::metrics::record_signal_event;
+use crate::backtest::evaluation::BacktestMetrics;
+use crate::backtest::models::{Backtest, BacktestCriteria};
+use crate::prelude::{default_backtest_criteria_for, StatsCriteria};
+use crate::pricing::entry::get_prices_pair;
+use crate::pricing::models::{DataCriteria, PairPrices};
+use crate::stats::models::Statistics;
+use crate::stats::score::{PairScore, ScoreConfig};
+
+/*
+  Watchlist
+  Stores selected pairs with their criteria and re-scores them on a schedule
+  Prices are cached per entry so each re-score only fetches the bars added since the last one
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct WatchlistEntry {
+  pub data_criteria: DataCriteria,
+  pub stats_criteria: StatsCriteria,
+  pub min_score: f64,
+  pub prices: Option<PairPrices>,
+  pub last_score: Option<PairScore>,
+  pub last_scored_at: Option<i64> // unix seconds
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct Degradation {
+  pub data_criteria: DataCriteria,
+  pub previous_score: Option<f64>,
+  pub score: f64,
+  pub min_score: f64
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, TS)]
+#[ts(export)]
+pub struct RescoreReport {
+  pub degradations: Vec<Degradation>,
+  pub failed: Vec<(DataCriteria, String)> // entry, error
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct Watchlist {
+  pub entries: Vec<WatchlistEntry>,
+  pub rescore_interval_secs: i64,
+  pub score_config: ScoreConfig
+}
+
+/// Merge Prices
+/// Appends newer bars to the cached prices (newer values win on overlap) and keeps the last period bars
+fn merge_prices(cached: &PairPrices, update: PairPrices, period: usize) -> PairPrices {
+  let last_new: usize = update.labels.len();
+  let first_update: u64 = update.labels.first().copied().unwrap_or(u64::MAX);
+  let keep: usize = cached.labels.iter().take_while(|l| **l < first_update).count();
+
+  let mut series_0: Vec<f64> = cached.series_0[..keep].to_vec();
+  let mut series_1: Vec<f64> = cached.series_1[..keep].to_vec();
+  let mut labels: Vec<u64> = cached.labels[..keep].to_vec();
+  series_0.extend(update.series_0);
+  series_1.extend(update.series_1);
+  labels.extend(update.labels);
+
+  let skip: usize = labels.len().saturating_sub(period.max(last_new));
+  PairPrices {
+    series_0: series_0.split_off(skip),
+    series_1: series_1.split_off(skip),
+    labels: labels.split_off(skip),
+    label_convention: cached.label_convention,
+    fetch_report: update.fetch_report,
+    events: cached.events.clone(),
+    source: update.source
+  }
+}
+
+impl Watchlist {
+  pub fn new(rescore_interval_secs: i64, score_config: ScoreConfig) -> Self {
+    Self { entries: vec![], rescore_interval_secs, score_config }
+  }
+
+  /// Add
+  /// Adds a pair to the watchlist (replacing any existing entry for the same pair)
+  pub fn add(&mut self, data_criteria: DataCriteria, stats_criteria: StatsCriteria, min_score: f64) {
+    self.remove(&data_criteria);
+    self.entries.push(WatchlistEntry { data_criteria, stats_criteria, min_score, prices: None, last_score: None, last_scored_at: None });
+  }
+
+  /// Remove
+  /// Removes a pair from the watchlist
+  pub fn remove(&mut self, data_criteria: &DataCriteria) {
+    self.entries.retain(|e| {
+      e.data_criteria.exchange != data_criteria.exchange
+        || e.data_criteria.asset_0 != data_criteria.asset_0
+        || e.data_criteria.asset_1 != data_criteria.asset_1
+    });
+  }
+
+  /// Due
+  /// Indexes of entries never scored or last scored at least one rescore interval ago
+  pub fn due(&self, now: i64) -> Vec<usize> {
+    self.entries.iter().enumerate()
+      .filter(|(_, e)| e.last_scored_at.map(|t| now - t >= self.rescore_interval_secs).unwrap_or(true))
+      .map(|(i, _)| i)
+      .collect()
+  }
+
+  /// Rescore With Prices
+  /// Merges new prices into the entry cache, re-scores it and reports a degradation if now below its minimum score
+  pub fn rescore_with_prices(&mut self, idx: usize, update: PairPrices, now: i64) -> Result<Option<Degradation>, SmartError> {
+    let config: ScoreConfig = self.score_config.clone();
+    let Some(entry) = self.entries.get_mut(idx) else {
+      return Err(SmartError::RuntimeCheck(format!("No watchlist entry at index {}", idx)))
+    };
+
+    let period: usize = entry.data_criteria.interval_period.period() as usize;
+    let prices: PairPrices = match &entry.prices {
+      Some(cached) => merge_prices(cached, update, period),
+      None => update
+    };
+    prices.ensure_complete()?;
+
+    let cleaned: PairPrices = match &entry.stats_criteria.preprocess {
+      Some(config) => prices.preprocessed(config)?,
+      None => prices.clone()
+    };
+    let stats: Statistics = Statistics::calculate_statistics(
+      &cleaned.series_0,
+      &cleaned.series_1,
+      entry.stats_criteria.spread_type.clone(),
+      entry.stats_criteria.zscore_window,
+      entry.stats_criteria.roll_window
+    )?;
+    let criteria: BacktestCriteria = default_backtest_criteria_for(&entry.data_criteria, &prices, stats.zscore.clone());
+    let backtest: Backtest = Backtest::new(&cleaned.series_0, &cleaned.series_1, criteria)?
+      .with_excluded_bars(prices.excluded_bars())?;
+    let bt_metrics: BacktestMetrics = backtest.run_backtest()?;
+    let score: PairScore = PairScore::calculate(&stats, Some(&bt_metrics), &config)?;
+
+    let previous_score: Option<f64> = entry.last_score.as_ref().map(|s| s.score);
+    let degradation: Option<Degradation> = if score.score < entry.min_score {
+      record_signal_event("degradation");
+      Some(Degradation { data_criteria: entry.data_criteria.clone(), previous_score, score: score.score, min_score: entry.min_score })
+    } else {
+      None
+    };
+
+    entry.prices = Some(prices);
+    entry.last_score = Some(score);
+    entry.last_scored_at = Some(now);
+    Ok(degradation)
+  }
+
+  /// Rescore Due
+  /// Fetches only the bars added since each due entry was last scored and re-scores it
+  /// Reports the pairs that have degraded below their minimum score and the entries that failed (left due for the next run)
+  pub async fn rescore_due(&mut self, now: i64, twelve_api_key: Option<&str>) -> RescoreReport {
+    let mut report: RescoreReport = RescoreReport::default();
+    for idx in self.due(now) {
+      let entry: &WatchlistEntry = &self.entries[idx];
+      let mut data_criteria: DataCriteria = entry.data_criteria.clone();
+
+      // Incremental fetch (with a small overlap) when cached prices exist
+      let cached: Option<(&PairPrices, u64)> = entry.prices.as_ref().and_then(|p| p.labels.last().map(|l| (p, *l)));
+      if let Some((prices, last)) = cached {
+        let step: u64 = data_criteria.interval_period.interval_seconds().max(1);
+        let new_bars: u64 = (now.max(0) as u64).saturating_sub(last) / step + 2;
+        let period: u32 = data_criteria.interval_period.period();
+        if prices.labels.len() >= period as usize && new_bars < period as u64 {
+          data_criteria.interval_period = data_criteria.interval_period.with_period(new_bars as u32);
+        }
+      }
+
+      let entry_criteria: DataCriteria = entry.data_criteria.clone();
+      let rescored: Result<Option<Degradation>, SmartError> = match get_prices_pair(data_criteria, twelve_api_key).await {
+        Ok(update) => self.rescore_with_prices(idx, update, now),
+        Err(e) => Err(e)
+      };
+      match rescored {
+        Ok(Some(degradation)) => report.degradations.push(degradation),
+        Ok(None) => {},
+        Err(e) => report.failed.push((entry_criteria, e.to_string()))
+      }
+    }
+    report
+  }
+
+  /// To JSON / From JSON
+  /// Serialized watchlist including cached prices and scores
+  pub fn to_json(&self) -> Result<String, SmartError> {
+    Ok(serde_json::to_string(self)?)
+  }
+
+  pub fn from_json(json: &str) -> Result<Self, SmartError> {
+    Ok(serde_json::from_str::<Self>(json)?)
+  }
+
+  /// Save / Load
+  /// Persists the watchlist to a local json file
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), SmartError> {
+    std::fs::write(path, self.to_json()?)?;
+    Ok(())
+  }
+
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, SmartError> {
+    Self::from_json(&std::fs::read_to_string(path)?)
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::pricing::models::{Exchange, IntervalPeriod};
+  use crate::stats::models::SpreadType;
+
+  fn synthetic_prices(start: u64, len: u64, noise: f64) -> PairPrices {
+    let series_1: Vec<f64> = (start..start + len).map(|i| 100.0 + (i as f64 / 10.0).sin() * 5.0 + i as f64 * 0.1).collect();
+    let series_0: Vec<f64> = series_1.iter().zip(start..).map(|(p, i)| p * 2.0 + (i as f64 / 3.0).cos() * 2.0 + (i as f64).powf(noise)).collect();
+    let labels: Vec<u64> = (start..start + len).map(|i| i * 3600).collect();
+    PairPrices { series_0, series_1, labels, label_convention: Default::default(), fetch_report: None, events: vec![], source: None }
+  }
+
+  fn watchlist() -> Watchlist {
+    let mut watchlist: Watchlist = Watchlist::new(3600, ScoreConfig::default());
+    let data_criteria: DataCriteria = DataCriteria {
+      exchange: Exchange::Binance,
+      asset_0: "BTCUSDT".to_string(),
+      asset_1: "ETHUSDT".to_string(),
+      interval_period: IntervalPeriod::Hour(1, 300),
+      fallback_exchanges: vec![]
+    };
+    let stats_criteria: StatsCriteria = StatsCriteria { spread_type: SpreadType::Static, zscore_window: 35, roll_window: 90, preprocess: None };
+    watchlist.add(data_criteria, stats_criteria, 40.0);
+    watchlist
+  }
+
+  #[tokio::test]
+  async fn it_rescores_incrementally_and_reports_degradation() {
+    let mut watchlist: Watchlist = watchlist();
+    assert_eq!(watchlist.due(0), vec![0]);
+
+    let degraded = watchlist.rescore_with_prices(0, synthetic_prices(0, 300, 0.0), 1000).unwrap();
+    assert!(degraded.is_none());
+    assert!(watchlist.due(2000).is_empty());
+    assert_eq!(watchlist.due(4600), vec![0]);
+
+    // Trending update degrades the spread
+    let degraded = watchlist.rescore_with_prices(0, synthetic_prices(290, 200, 1.5), 5000).unwrap();
+    let prices: &PairPrices = watchlist.entries[0].prices.as_ref().unwrap();
+    assert_eq!(prices.labels.len(), 300);
+    assert_eq!(*prices.labels.last().unwrap(), 489 * 3600);
+    assert!(degraded.is_some());
+  }
+
+  #[tokio::test]
+  async fn tests_rescore_due_continues_past_failed_entries() {
+    let mut watchlist: Watchlist = watchlist();
+    let mut unknown: DataCriteria = watchlist.entries[0].data_criteria.clone();
+    unknown.asset_0 = "NOTAPAIRUSDT".to_string();
+    unknown.asset_1 = "NOTAPAIRUSDC".to_string();
+    watchlist.entries[0].data_criteria = unknown.clone();
+    unknown.asset_0 = "NOTAPAIRBTC".to_string();
+    watchlist.add(unknown, watchlist.entries[0].stats_criteria.clone(), 40.0);
+
+    // Every failed entry is reported and stays due
+    let report: RescoreReport = watchlist.rescore_due(0, None).await;
+    assert_eq!(report.failed.len(), 2);
+    assert!(report.degradations.is_empty());
+    assert_eq!(watchlist.due(0), vec![0, 1]);
+  }
+
+  #[tokio::test]
+  async fn it_round_trips_watchlist_json() {
+    let watchlist: Watchlist = watchlist();
+    let restored: Watchlist = Watchlist::from_json(&watchlist.to_json().unwrap()).unwrap();
+    assert_eq!(restored.entries.len(), 1);
+    assert_eq!(restored.entries[0].min_score, 40.0);
+  }
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::SmartError;
+use crate::backtest::models::LongSeries;
+use crate::pricing::utils::api_post;
+use crate::state::SignalAction;
+
+/*
+  Webhook Signals
+  Pair signals as the flat JSON orders webhook execution bots accept from TradingView alerts ({symbol, side, qty, comment})
+  Each leg is its own order, posted as a separate request as most bots take one order per message
+*/
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, TS)]
+#[ts(export)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookSide {
+  Buy,
+  Sell
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, TS)]
+#[ts(export)]
+pub struct WebhookSignal {
+  pub symbol: String,
+  pub side: WebhookSide,
+  pub qty: f64,
+  pub comment: String
+}
+
+/// Leg Sides
+/// Sides for asset 0 and asset 1 when opening (closing reverses them)
+fn leg_sides(long_series: &LongSeries, is_open: bool) -> (WebhookSide, WebhookSide) {
+  let (side_0, side_1) = match long_series {
+    LongSeries::Series0 => (WebhookSide::Buy, WebhookSide::Sell),
+    LongSeries::Series1 => (WebhookSide::Sell, WebhookSide::Buy)
+  };
+  let flip = |side: WebhookSide| if side == WebhookSide::Buy { WebhookSide::Sell } else { WebhookSide::Buy };
+  if is_open { (side_0, side_1) } else { (flip(side_0), flip(side_1)) }
+}
+
+/// Signal Payloads
+/// One order per leg for a signal, closing needs the long series of the open position
+pub fn signal_payloads(
+  action: &SignalAction,
+  open_long_series: Option<&LongSeries>,
+  legs: [(&str, f64); 2], // symbol and quantity per asset
+  comment: &str
+) -> Result<Vec<WebhookSignal>, SmartError> {
+  let (side_0, side_1) = match (action, open_long_series) {
+    (SignalAction::Open(long_series), _) => leg_sides(long_series, true),
+    (SignalAction::Close, Some(long_series)) => leg_sides(long_series, false),
+    (SignalAction::Close, None) => return Err(SmartError::RuntimeCheck("Closing signal has no open position".to_string()))
+  };
+  Ok(legs.iter().zip([side_0, side_1])
+    .map(|((symbol, qty), side)| WebhookSignal { symbol: symbol.to_string(), side, qty: qty.abs(), comment: comment.to_string() })
+    .collect())
+}
+
+/// Post Webhook Signals
+/// Posts each order to the webhook url in turn, stopping at the first failure so a leg is never sent after its pair leg failed
+pub async fn post_webhook_signals(url: &str, signals: &[WebhookSignal]) -> Result<(), SmartError> {
+  for signal in signals {
+    let res_data: reqwest::Response = api_post(url, &serde_json::to_string(signal)?).await?;
+
+    // Guard: Ensure success
+    if !res_data.status().is_success() {
+      let e: String = format!("Webhook rejected {} {:?}: {:?}", signal.symbol, signal.side, res_data.text().await);
+      return Err(SmartError::APIResponseStatus(e));
+    }
+  }
+  Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn it_builds_webhook_payloads_per_leg() {
+    let legs: [(&str, f64); 2] = [("BTCUSDT", 0.1), ("ETHUSDT", -2.0)];
+    let open: Vec<WebhookSignal> = signal_payloads(&SignalAction::Open(LongSeries::Series1), None, legs, "zscore 2.10").unwrap();
+    assert_eq!((open[0].side, open[1].side), (WebhookSide::Sell, WebhookSide::Buy));
+    assert_eq!(open[1].qty, 2.0);
+    assert_eq!(
+      serde_json::to_value(&open[0]).unwrap(),
+      serde_json::json!({ "symbol": "BTCUSDT", "side": "sell", "qty": 0.1, "comment": "zscore 2.10" })
+    );
+
+    let close: Vec<WebhookSignal> = signal_payloads(&SignalAction::Close, Some(&LongSeries::Series1), legs, "exit").unwrap();
+    assert_eq!((close[0].side, close[1].side), (WebhookSide::Buy, WebhookSide::Sell));
+    assert!(signal_payloads(&SignalAction::Close, None, legs, "exit").is_err());
+  }
+}